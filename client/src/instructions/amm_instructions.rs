@@ -9,8 +9,9 @@ use solana_sdk::{
 use raydium_amm_v3::accounts as raydium_accounts;
 use raydium_amm_v3::instruction as raydium_instruction;
 use raydium_amm_v3::states::{
-    AMM_CONFIG_SEED, OBSERVATION_SEED, OPERATION_SEED, POOL_SEED, POOL_VAULT_SEED, POSITION_SEED,
-    TICK_ARRAY_SEED,
+    ADMIN_HEARTBEAT_SEED, ADMIN_TIMELOCK_SEED, AMM_CONFIG_PENDING_OWNER_SEED, AMM_CONFIG_SEED,
+    OBSERVATION_SEED, OPERATION_SEED, POOL_SEED, POOL_VAULT_SEED, POSITION_REWARD_EXTENSION_SEED,
+    POSITION_SEED, TICK_ARRAY_SEED, WHITELIST_CREATOR_SEED,
 };
 use std::rc::Rc;
 
@@ -38,6 +39,11 @@ pub fn create_amm_config_instr(
         .accounts(raydium_accounts::CreateAmmConfig {
             owner: program.payer(),
             amm_config: amm_config_key,
+            admin_heartbeat: Pubkey::find_program_address(
+                &[ADMIN_HEARTBEAT_SEED.as_bytes()],
+                &program.id(),
+            )
+            .0,
             system_program: system_program::id(),
         })
         .args(raydium_instruction::CreateAmmConfig {
@@ -76,6 +82,67 @@ pub fn update_amm_config_instr(
     Ok(instructions)
 }
 
+pub fn propose_ownership_instr(
+    config: &ClientConfig,
+    amm_config: Pubkey,
+    new_owner: Pubkey,
+    param: u8,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let admin = read_keypair_file(&config.admin_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.raydium_v3_program)?;
+    let (pending_owner, __bump) = Pubkey::find_program_address(
+        &[
+            AMM_CONFIG_PENDING_OWNER_SEED.as_bytes(),
+            amm_config.to_bytes().as_ref(),
+        ],
+        &program.id(),
+    );
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::ProposeOwnership {
+            owner: admin.pubkey(),
+            amm_config,
+            new_owner,
+            pending_owner,
+            system_program: system_program::id(),
+        })
+        .args(raydium_instruction::ProposeOwnership { param })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn accept_ownership_instr(
+    config: &ClientConfig,
+    amm_config: Pubkey,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.raydium_v3_program)?;
+    let (pending_owner, __bump) = Pubkey::find_program_address(
+        &[
+            AMM_CONFIG_PENDING_OWNER_SEED.as_bytes(),
+            amm_config.to_bytes().as_ref(),
+        ],
+        &program.id(),
+    );
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::AcceptOwnership {
+            new_owner: program.payer(),
+            amm_config,
+            pending_owner,
+        })
+        .args(raydium_instruction::AcceptOwnership {})
+        .instructions()?;
+    Ok(instructions)
+}
+
 pub fn create_operation_account_instr(config: &ClientConfig) -> Result<Vec<Instruction>> {
     let payer = read_keypair_file(&config.admin_path)?;
     let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
@@ -120,14 +187,71 @@ pub fn update_operation_account_instr(
     Ok(instructions)
 }
 
+pub fn create_whitelist_creator_instr(
+    config: &ClientConfig,
+    creator: Pubkey,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.admin_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.raydium_v3_program)?;
+    let (whitelist_creator_key, __bump) = Pubkey::find_program_address(
+        &[
+            WHITELIST_CREATOR_SEED.as_bytes(),
+            creator.to_bytes().as_ref(),
+        ],
+        &program.id(),
+    );
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::CreateWhitelistCreator {
+            owner: program.payer(),
+            whitelist_creator: whitelist_creator_key,
+            system_program: system_program::id(),
+        })
+        .args(raydium_instruction::CreateWhitelistCreator { creator })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn delete_whitelist_creator_instr(
+    config: &ClientConfig,
+    creator: Pubkey,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.admin_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.raydium_v3_program)?;
+    let (whitelist_creator_key, __bump) = Pubkey::find_program_address(
+        &[
+            WHITELIST_CREATOR_SEED.as_bytes(),
+            creator.to_bytes().as_ref(),
+        ],
+        &program.id(),
+    );
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::DeleteWhitelistCreator {
+            owner: program.payer(),
+            whitelist_creator: whitelist_creator_key,
+        })
+        .args(raydium_instruction::DeleteWhitelistCreator)
+        .instructions()?;
+    Ok(instructions)
+}
+
 pub fn create_pool_instr(
     config: &ClientConfig,
     amm_config: Pubkey,
+    fund_owner: Pubkey,
     token_mint_0: Pubkey,
     token_mint_1: Pubkey,
     token_program_0: Pubkey,
     token_program_1: Pubkey,
     tick_array_bitmap: Pubkey,
+    remaining_accounts: Vec<AccountMeta>,
     sqrt_price_x64: u128,
     open_time: u64,
 ) -> Result<Vec<Instruction>> {
@@ -173,6 +297,7 @@ pub fn create_pool_instr(
         .accounts(raydium_accounts::CreatePool {
             pool_creator: program.payer(),
             amm_config,
+            fund_owner,
             pool_state: pool_account_key,
             token_mint_0,
             token_mint_1,
@@ -185,6 +310,7 @@ pub fn create_pool_instr(
             system_program: system_program::id(),
             rent: sysvar::rent::id(),
         })
+        .accounts(remaining_accounts)
         .args(raydium_instruction::CreatePool {
             sqrt_price_x64,
             open_time,
@@ -426,28 +552,28 @@ pub fn increase_liquidity_instr(
     // Client.
     let client = Client::new(url, Rc::new(payer));
     let program = client.program(config.raydium_v3_program)?;
-    let (tick_array_lower, __bump) = Pubkey::find_program_address(
+    let (protocol_position_key, __bump) = Pubkey::find_program_address(
         &[
-            TICK_ARRAY_SEED.as_bytes(),
+            POSITION_SEED.as_bytes(),
             pool_account_key.to_bytes().as_ref(),
-            &tick_array_lower_start_index.to_be_bytes(),
+            &tick_lower_index.to_be_bytes(),
+            &tick_upper_index.to_be_bytes(),
         ],
         &program.id(),
     );
-    let (tick_array_upper, __bump) = Pubkey::find_program_address(
+    let (tick_array_lower, __bump) = Pubkey::find_program_address(
         &[
             TICK_ARRAY_SEED.as_bytes(),
             pool_account_key.to_bytes().as_ref(),
-            &tick_array_upper_start_index.to_be_bytes(),
+            &tick_array_lower_start_index.to_be_bytes(),
         ],
         &program.id(),
     );
-    let (protocol_position_key, __bump) = Pubkey::find_program_address(
+    let (tick_array_upper, __bump) = Pubkey::find_program_address(
         &[
-            POSITION_SEED.as_bytes(),
+            TICK_ARRAY_SEED.as_bytes(),
             pool_account_key.to_bytes().as_ref(),
-            &tick_lower_index.to_be_bytes(),
-            &tick_upper_index.to_be_bytes(),
+            &tick_array_upper_start_index.to_be_bytes(),
         ],
         &program.id(),
     );
@@ -575,6 +701,7 @@ pub fn close_personal_position_instr(
     nft_mint_key: Pubkey,
     nft_token_key: Pubkey,
     nft_token_program: Pubkey,
+    has_reward_extension: bool,
 ) -> Result<Vec<Instruction>> {
     let payer = read_keypair_file(&config.payer_path)?;
     let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
@@ -585,6 +712,18 @@ pub fn close_personal_position_instr(
         &[POSITION_SEED.as_bytes(), nft_mint_key.to_bytes().as_ref()],
         &program.id(),
     );
+    // the position reward extension is closed with the position
+    let mut remaining_accounts = Vec::new();
+    if has_reward_extension {
+        let (position_reward_extension_key, __bump) = Pubkey::find_program_address(
+            &[
+                POSITION_REWARD_EXTENSION_SEED.as_bytes(),
+                nft_mint_key.to_bytes().as_ref(),
+            ],
+            &program.id(),
+        );
+        remaining_accounts.push(AccountMeta::new(position_reward_extension_key, false));
+    }
     let instructions = program
         .request()
         .accounts(raydium_accounts::ClosePosition {
@@ -595,11 +734,37 @@ pub fn close_personal_position_instr(
             system_program: system_program::id(),
             token_program: nft_token_program,
         })
+        .accounts(remaining_accounts)
         .args(raydium_instruction::ClosePosition)
         .instructions()?;
     Ok(instructions)
 }
 
+pub fn close_tick_array_instr(
+    config: &ClientConfig,
+    pool_account_key: Pubkey,
+    tick_array_key: Pubkey,
+    rent_recipient: Pubkey,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.raydium_v3_program)?;
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::CloseTickArray {
+            pool_state: pool_account_key,
+            tick_array: tick_array_key,
+            rent_recipient,
+        })
+        .accounts(remaining_accounts)
+        .args(raydium_instruction::CloseTickArray)
+        .instructions()?;
+    Ok(instructions)
+}
+
 pub fn swap_instr(
     config: &ClientConfig,
     amm_config: Pubkey,
@@ -662,13 +827,15 @@ pub fn swap_v2_instr(
     other_amount_threshold: u64,
     sqrt_price_limit_x64: Option<u128>,
     is_base_input: bool,
+    max_price_impact_bps: Option<u16>,
+    slippage: Option<raydium_amm_v3::instructions::SwapSlippage>,
 ) -> Result<Vec<Instruction>> {
     let payer = read_keypair_file(&config.payer_path)?;
     let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
     // Client.
     let client = Client::new(url, Rc::new(payer));
     let program = client.program(config.raydium_v3_program)?;
-    let instructions = program
+    let request = program
         .request()
         .accounts(raydium_accounts::SwapSingleV2 {
             payer: program.payer(),
@@ -685,13 +852,57 @@ pub fn swap_v2_instr(
             input_vault_mint,
             output_vault_mint,
         })
-        .accounts(remaining_accounts)
-        .args(raydium_instruction::SwapV2 {
+        .accounts(remaining_accounts);
+    // the guards of the price impact and the slippage are only taken by swap_v3
+    let request = if max_price_impact_bps.is_none() && slippage.is_none() {
+        request.args(raydium_instruction::SwapV2 {
             amount,
             other_amount_threshold,
             sqrt_price_limit_x64: sqrt_price_limit_x64.unwrap_or(0u128),
             is_base_input,
         })
+    } else {
+        request.args(raydium_instruction::SwapV3 {
+            amount,
+            other_amount_threshold,
+            sqrt_price_limit_x64: sqrt_price_limit_x64.unwrap_or(0u128),
+            is_base_input,
+            max_price_impact_bps,
+            slippage,
+        })
+    };
+    let instructions = request.instructions()?;
+    Ok(instructions)
+}
+
+pub fn swap_router_base_in_instr(
+    config: &ClientConfig,
+    input_token_account: Pubkey,
+    input_token_mint: Pubkey,
+    remaining_accounts: Vec<AccountMeta>,
+    amount_in: u64,
+    amount_out_minimum: u64,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.raydium_v3_program)?;
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::SwapRouterBaseIn {
+            payer: program.payer(),
+            input_token_account,
+            input_token_mint,
+            token_program: spl_token::id(),
+            token_program_2022: spl_token_2022::id(),
+            memo_program: spl_memo::id(),
+        })
+        .accounts(remaining_accounts)
+        .args(raydium_instruction::SwapRouterBaseInV2 {
+            amount_in,
+            amount_out_minimum,
+        })
         .instructions()?;
     Ok(instructions)
 }
@@ -758,11 +969,26 @@ pub fn set_reward_params_instr(
     let client = Client::new(url, Rc::new(admin));
     let program = client.program(config.raydium_v3_program)?;
 
-    let remaining_accounts = vec![
+    let mut remaining_accounts = Vec::new();
+    // the rewards beyond the pool slots are in the reward info extension
+    if usize::from(reward_index) >= raydium_amm_v3::states::REWARD_NUM {
+        remaining_accounts.push(AccountMeta::new(
+            Pubkey::find_program_address(
+                &[
+                    raydium_amm_v3::states::REWARD_INFO_EXTENSION_SEED.as_bytes(),
+                    pool_account_key.to_bytes().as_ref(),
+                ],
+                &program.id(),
+            )
+            .0,
+            false,
+        ));
+    }
+    remaining_accounts.extend([
         AccountMeta::new(reward_token_vault, false),
         AccountMeta::new(user_reward_token, false),
         AccountMeta::new_readonly(spl_token::id(), false),
-    ];
+    ]);
 
     let instructions = program
         .request()
@@ -797,6 +1023,8 @@ pub fn transfer_reward_owner(
     // Client.
     let client = Client::new(url, Rc::new(admin));
     let program = client.program(config.raydium_v3_program)?;
+    let (admin_timelock, __bump) =
+        Pubkey::find_program_address(&[ADMIN_TIMELOCK_SEED.as_bytes()], &program.id());
 
     let instructions = program
         .request()
@@ -808,7 +1036,44 @@ pub fn transfer_reward_owner(
             },
             pool_state: pool_account_key,
         })
+        .accounts(AccountMeta::new_readonly(admin_timelock, false))
         .args(raydium_instruction::TransferRewardOwner { new_owner })
         .instructions()?;
     Ok(instructions)
 }
+
+pub fn inspect_account_instr(config: &ClientConfig, account: Pubkey) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.raydium_v3_program)?;
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::InspectAccount { account })
+        .args(raydium_instruction::InspectAccount {})
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn get_twap_instr(
+    config: &ClientConfig,
+    pool_state: Pubkey,
+    observation_state: Pubkey,
+    window_seconds: u32,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.raydium_v3_program)?;
+    let instructions = program
+        .request()
+        .accounts(raydium_accounts::GetTwap {
+            pool_state,
+            observation_state,
+        })
+        .args(raydium_instruction::GetTwap { window_seconds })
+        .instructions()?;
+    Ok(instructions)
+}
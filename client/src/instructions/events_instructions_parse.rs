@@ -35,7 +35,15 @@ pub fn parse_program_event(
     } else {
         Vec::new()
     };
-    let mut logs = &logs[..];
+    parse_program_event_logs(self_program_str, &logs)
+}
+
+/// Decodes the events of the program in the log messages of a transaction
+pub fn parse_program_event_logs(
+    self_program_str: &str,
+    logs: &[String],
+) -> Result<(), ClientError> {
+    let mut logs = logs;
     if !logs.is_empty() {
         if let Ok(mut execution) = Execution::new(&mut logs) {
             for l in logs {
@@ -131,63 +139,7 @@ pub fn handle_program_log(
             }
         };
 
-        let mut slice: &[u8] = &borsh_bytes[..];
-        let disc: [u8; 8] = {
-            let mut disc = [0; 8];
-            disc.copy_from_slice(&borsh_bytes[..8]);
-            slice = &slice[8..];
-            disc
-        };
-        match disc {
-            ConfigChangeEvent::DISCRIMINATOR => {
-                println!("{:#?}", decode_event::<ConfigChangeEvent>(&mut slice)?);
-            }
-            CollectPersonalFeeEvent::DISCRIMINATOR => {
-                println!(
-                    "{:#?}",
-                    decode_event::<CollectPersonalFeeEvent>(&mut slice)?
-                );
-            }
-            CollectProtocolFeeEvent::DISCRIMINATOR => {
-                println!(
-                    "{:#?}",
-                    decode_event::<CollectProtocolFeeEvent>(&mut slice)?
-                );
-            }
-            CreatePersonalPositionEvent::DISCRIMINATOR => {
-                println!(
-                    "{:#?}",
-                    decode_event::<CreatePersonalPositionEvent>(&mut slice)?
-                );
-            }
-            DecreaseLiquidityEvent::DISCRIMINATOR => {
-                println!("{:#?}", decode_event::<DecreaseLiquidityEvent>(&mut slice)?);
-            }
-            IncreaseLiquidityEvent::DISCRIMINATOR => {
-                println!("{:#?}", decode_event::<IncreaseLiquidityEvent>(&mut slice)?);
-            }
-            LiquidityCalculateEvent::DISCRIMINATOR => {
-                println!(
-                    "{:#?}",
-                    decode_event::<LiquidityCalculateEvent>(&mut slice)?
-                );
-            }
-            LiquidityChangeEvent::DISCRIMINATOR => {
-                println!("{:#?}", decode_event::<LiquidityChangeEvent>(&mut slice)?);
-            }
-            // PriceChangeEvent::DISCRIMINATOR => {
-            //     println!("{:#?}", decode_event::<PriceChangeEvent>(&mut slice)?);
-            // }
-            SwapEvent::DISCRIMINATOR => {
-                println!("{:#?}", decode_event::<SwapEvent>(&mut slice)?);
-            }
-            PoolCreatedEvent::DISCRIMINATOR => {
-                println!("{:#?}", decode_event::<PoolCreatedEvent>(&mut slice)?);
-            }
-            _ => {
-                println!("unknow event: {}", l);
-            }
-        }
+        handle_program_event_data(&borsh_bytes)?;
         return Ok((None, false));
     } else {
         let (program, did_pop) = handle_system_log(self_program_str, l);
@@ -195,6 +147,230 @@ pub fn handle_program_log(
     }
 }
 
+/// Decodes an event emitted to the program logs or by the self CPI of the program
+fn handle_program_event_data(borsh_bytes: &[u8]) -> Result<(), ClientError> {
+    let mut slice: &[u8] = &borsh_bytes[..];
+    let disc: [u8; 8] = {
+        let mut disc = [0; 8];
+        disc.copy_from_slice(&borsh_bytes[..8]);
+        slice = &slice[8..];
+        disc
+    };
+    match disc {
+        ConfigChangeEvent::DISCRIMINATOR => {
+            println!("{:#?}", decode_event::<ConfigChangeEvent>(&mut slice)?);
+        }
+        AmmConfigExtensionChangeEvent::DISCRIMINATOR => {
+            println!(
+                "{:#?}",
+                decode_event::<AmmConfigExtensionChangeEvent>(&mut slice)?
+            );
+        }
+        CollectPersonalFeeEvent::DISCRIMINATOR => {
+            println!(
+                "{:#?}",
+                decode_event::<CollectPersonalFeeEvent>(&mut slice)?
+            );
+        }
+        CollectProtocolFeeEvent::DISCRIMINATOR => {
+            println!(
+                "{:#?}",
+                decode_event::<CollectProtocolFeeEvent>(&mut slice)?
+            );
+        }
+        CreatePersonalPositionEvent::DISCRIMINATOR => {
+            println!(
+                "{:#?}",
+                decode_event::<CreatePersonalPositionEvent>(&mut slice)?
+            );
+        }
+        DecreaseLiquidityEvent::DISCRIMINATOR => {
+            println!("{:#?}", decode_event::<DecreaseLiquidityEvent>(&mut slice)?);
+        }
+        IncreaseLiquidityEvent::DISCRIMINATOR => {
+            println!("{:#?}", decode_event::<IncreaseLiquidityEvent>(&mut slice)?);
+        }
+        LiquidityCalculateEvent::DISCRIMINATOR => {
+            println!(
+                "{:#?}",
+                decode_event::<LiquidityCalculateEvent>(&mut slice)?
+            );
+        }
+        LiquidityChangeEvent::DISCRIMINATOR => {
+            println!("{:#?}", decode_event::<LiquidityChangeEvent>(&mut slice)?);
+        }
+        // PriceChangeEvent::DISCRIMINATOR => {
+        //     println!("{:#?}", decode_event::<PriceChangeEvent>(&mut slice)?);
+        // }
+        SwapEvent::DISCRIMINATOR => {
+            println!("{:#?}", decode_event::<SwapEvent>(&mut slice)?);
+        }
+        PoolCreatedEvent::DISCRIMINATOR => {
+            println!("{:#?}", decode_event::<PoolCreatedEvent>(&mut slice)?);
+        }
+        TickArrayDeltaEvent::DISCRIMINATOR => {
+            println!("{:#?}", decode_event::<TickArrayDeltaEvent>(&mut slice)?);
+        }
+        UpdateLiquiditySecondsEvent::DISCRIMINATOR => {
+            println!(
+                "{:#?}",
+                decode_event::<UpdateLiquiditySecondsEvent>(&mut slice)?
+            );
+        }
+        PoolPriceBandUpdatedEvent::DISCRIMINATOR => {
+            println!(
+                "{:#?}",
+                decode_event::<PoolPriceBandUpdatedEvent>(&mut slice)?
+            );
+        }
+        IncreaseObservationCardinalityEvent::DISCRIMINATOR => {
+            println!(
+                "{:#?}",
+                decode_event::<IncreaseObservationCardinalityEvent>(&mut slice)?
+            );
+        }
+        CloseObservationEvent::DISCRIMINATOR => {
+            println!("{:#?}", decode_event::<CloseObservationEvent>(&mut slice)?);
+        }
+        CloseTickArrayEvent::DISCRIMINATOR => {
+            println!("{:#?}", decode_event::<CloseTickArrayEvent>(&mut slice)?);
+        }
+        ClosePoolEvent::DISCRIMINATOR => {
+            println!("{:#?}", decode_event::<ClosePoolEvent>(&mut slice)?);
+        }
+        LockPositionEvent::DISCRIMINATOR => {
+            println!("{:#?}", decode_event::<LockPositionEvent>(&mut slice)?);
+        }
+        LockPositionUntilEvent::DISCRIMINATOR => {
+            println!("{:#?}", decode_event::<LockPositionUntilEvent>(&mut slice)?);
+        }
+        ApproveOperatorEvent::DISCRIMINATOR => {
+            println!("{:#?}", decode_event::<ApproveOperatorEvent>(&mut slice)?);
+        }
+        InitializeExtensionRewardEvent::DISCRIMINATOR => {
+            println!(
+                "{:#?}",
+                decode_event::<InitializeExtensionRewardEvent>(&mut slice)?
+            );
+        }
+        PoolCheckpointEvent::DISCRIMINATOR => {
+            println!("{:#?}", decode_event::<PoolCheckpointEvent>(&mut slice)?);
+        }
+        FundRewardEvent::DISCRIMINATOR => {
+            println!("{:#?}", decode_event::<FundRewardEvent>(&mut slice)?);
+        }
+        SetRewardVestingEvent::DISCRIMINATOR => {
+            println!("{:#?}", decode_event::<SetRewardVestingEvent>(&mut slice)?);
+        }
+        RebalancePositionEvent::DISCRIMINATOR => {
+            println!("{:#?}", decode_event::<RebalancePositionEvent>(&mut slice)?);
+        }
+        MigratePositionEvent::DISCRIMINATOR => {
+            println!("{:#?}", decode_event::<MigratePositionEvent>(&mut slice)?);
+        }
+        MigrateAccountEvent::DISCRIMINATOR => {
+            println!("{:#?}", decode_event::<MigrateAccountEvent>(&mut slice)?);
+        }
+        CloseProtocolPositionEvent::DISCRIMINATOR => {
+            println!(
+                "{:#?}",
+                decode_event::<CloseProtocolPositionEvent>(&mut slice)?
+            );
+        }
+        SweepDustEvent::DISCRIMINATOR => {
+            println!("{:#?}", decode_event::<SweepDustEvent>(&mut slice)?);
+        }
+        PoolSolvencyEvent::DISCRIMINATOR => {
+            println!("{:#?}", decode_event::<PoolSolvencyEvent>(&mut slice)?);
+        }
+        SetPoolMetadataEvent::DISCRIMINATOR => {
+            println!("{:#?}", decode_event::<SetPoolMetadataEvent>(&mut slice)?);
+        }
+        CollectCreatorFeeEvent::DISCRIMINATOR => {
+            println!("{:#?}", decode_event::<CollectCreatorFeeEvent>(&mut slice)?);
+        }
+        FundFeeRecipientUpdatedEvent::DISCRIMINATOR => {
+            println!(
+                "{:#?}",
+                decode_event::<FundFeeRecipientUpdatedEvent>(&mut slice)?
+            );
+        }
+        AntiBotConfigUpdatedEvent::DISCRIMINATOR => {
+            println!(
+                "{:#?}",
+                decode_event::<AntiBotConfigUpdatedEvent>(&mut slice)?
+            );
+        }
+        UpdateOpenTimeEvent::DISCRIMINATOR => {
+            println!("{:#?}", decode_event::<UpdateOpenTimeEvent>(&mut slice)?);
+        }
+        PoolSqrtPriceResetEvent::DISCRIMINATOR => {
+            println!(
+                "{:#?}",
+                decode_event::<PoolSqrtPriceResetEvent>(&mut slice)?
+            );
+        }
+        DonateEvent::DISCRIMINATOR => {
+            println!("{:#?}", decode_event::<DonateEvent>(&mut slice)?);
+        }
+        SwapHookUpdatedEvent::DISCRIMINATOR => {
+            println!("{:#?}", decode_event::<SwapHookUpdatedEvent>(&mut slice)?);
+        }
+        ExternalOracleUpdatedEvent::DISCRIMINATOR => {
+            println!(
+                "{:#?}",
+                decode_event::<ExternalOracleUpdatedEvent>(&mut slice)?
+            );
+        }
+        TokenBadgeConfigChangeEvent::DISCRIMINATOR => {
+            println!(
+                "{:#?}",
+                decode_event::<TokenBadgeConfigChangeEvent>(&mut slice)?
+            );
+        }
+        CreateTokenBadgeWithFeeEvent::DISCRIMINATOR => {
+            println!(
+                "{:#?}",
+                decode_event::<CreateTokenBadgeWithFeeEvent>(&mut slice)?
+            );
+        }
+        CreateDistributionEvent::DISCRIMINATOR => {
+            println!(
+                "{:#?}",
+                decode_event::<CreateDistributionEvent>(&mut slice)?
+            );
+        }
+        ClaimWithProofEvent::DISCRIMINATOR => {
+            println!("{:#?}", decode_event::<ClaimWithProofEvent>(&mut slice)?);
+        }
+        DeployProtocolOwnedLiquidityEvent::DISCRIMINATOR => {
+            println!(
+                "{:#?}",
+                decode_event::<DeployProtocolOwnedLiquidityEvent>(&mut slice)?
+            );
+        }
+        WithdrawProtocolOwnedLiquidityEvent::DISCRIMINATOR => {
+            println!(
+                "{:#?}",
+                decode_event::<WithdrawProtocolOwnedLiquidityEvent>(&mut slice)?
+            );
+        }
+        ExitPositionEvent::DISCRIMINATOR => {
+            println!("{:#?}", decode_event::<ExitPositionEvent>(&mut slice)?);
+        }
+        FinalizeRewardCycleEvent::DISCRIMINATOR => {
+            println!(
+                "{:#?}",
+                decode_event::<FinalizeRewardCycleEvent>(&mut slice)?
+            );
+        }
+        _ => {
+            println!("unknow event: {:?}", disc);
+        }
+    }
+    Ok(())
+}
+
 fn handle_system_log(this_program_str: &str, log: &str) -> (Option<String>, bool) {
     if log.starts_with(&format!("Program {this_program_str} invoke")) {
         (Some(this_program_str.to_string()), false)
@@ -347,6 +523,10 @@ pub fn handle_program_instruction(
     };
     // println!("{:?}", disc);
 
+    if disc == anchor_lang::event::EVENT_IX_TAG_LE {
+        // the event emitted by the self CPI of the program
+        return handle_program_event_data(ix_data);
+    }
     match disc {
         instruction::CreateAmmConfig::DISCRIMINATOR => {
             let ix = decode_instruction::<instruction::CreateAmmConfig>(&mut ix_data).unwrap();
@@ -405,6 +585,25 @@ pub fn handle_program_instruction(
             }
             println!("{:#?}", CreatePool::from(ix));
         }
+        instruction::CreatePoolV2::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::CreatePoolV2>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct CreatePoolV2 {
+                pub sqrt_price_x64: u128,
+                pub open_time: u64,
+                pub options: Vec<CreatePoolOption>,
+            }
+            impl From<instruction::CreatePoolV2> for CreatePoolV2 {
+                fn from(instr: instruction::CreatePoolV2) -> CreatePoolV2 {
+                    CreatePoolV2 {
+                        sqrt_price_x64: instr.sqrt_price_x64,
+                        open_time: instr.open_time,
+                        options: instr.options,
+                    }
+                }
+            }
+            println!("{:#?}", CreatePoolV2::from(ix));
+        }
         instruction::UpdatePoolStatus::DISCRIMINATOR => {
             let ix = decode_instruction::<instruction::UpdatePoolStatus>(&mut ix_data).unwrap();
             #[derive(Debug)]
@@ -749,6 +948,31 @@ pub fn handle_program_instruction(
             }
             println!("{:#?}", SwapV2::from(ix));
         }
+        instruction::SwapV3::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::SwapV3>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct SwapV3 {
+                pub amount: u64,
+                pub other_amount_threshold: u64,
+                pub sqrt_price_limit_x64: u128,
+                pub is_base_input: bool,
+                pub max_price_impact_bps: Option<u16>,
+                pub slippage: Option<SwapSlippage>,
+            }
+            impl From<instruction::SwapV3> for SwapV3 {
+                fn from(instr: instruction::SwapV3) -> SwapV3 {
+                    SwapV3 {
+                        amount: instr.amount,
+                        other_amount_threshold: instr.other_amount_threshold,
+                        sqrt_price_limit_x64: instr.sqrt_price_limit_x64,
+                        is_base_input: instr.is_base_input,
+                        max_price_impact_bps: instr.max_price_impact_bps,
+                        slippage: instr.slippage,
+                    }
+                }
+            }
+            println!("{:#?}", SwapV3::from(ix));
+        }
         instruction::SwapRouterBaseIn::DISCRIMINATOR => {
             let ix = decode_instruction::<instruction::SwapRouterBaseIn>(&mut ix_data).unwrap();
             #[derive(Debug)]
@@ -766,6 +990,23 @@ pub fn handle_program_instruction(
             }
             println!("{:#?}", SwapRouterBaseIn::from(ix));
         }
+        instruction::SwapRouterBaseInV2::DISCRIMINATOR => {
+            let ix = decode_instruction::<instruction::SwapRouterBaseInV2>(&mut ix_data).unwrap();
+            #[derive(Debug)]
+            pub struct SwapRouterBaseInV2 {
+                pub amount_in: u64,
+                pub amount_out_minimum: u64,
+            }
+            impl From<instruction::SwapRouterBaseInV2> for SwapRouterBaseInV2 {
+                fn from(instr: instruction::SwapRouterBaseInV2) -> SwapRouterBaseInV2 {
+                    SwapRouterBaseInV2 {
+                        amount_in: instr.amount_in,
+                        amount_out_minimum: instr.amount_out_minimum,
+                    }
+                }
+            }
+            println!("{:#?}", SwapRouterBaseInV2::from(ix));
+        }
         _ => {
             println!("unknow instruction: {}", instr_data);
         }
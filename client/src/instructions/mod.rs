@@ -1,5 +1,6 @@
 pub mod amm_instructions;
 pub mod events_instructions_parse;
+pub mod pool_snapshot;
 pub mod rpc;
 pub mod token_instructions;
 pub mod utils;
@@ -0,0 +1,325 @@
+use anyhow::Result;
+use raydium_amm_v3::states::{
+    AmmConfig, PersonalPositionState, PoolState, TickArrayBitmapExtension, TickArrayState,
+};
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+use std::path::Path;
+
+/// A point-in-time dump of a pool, the tables are sorted so the same state gives the same file.
+/// The u128/i128 values are strings to keep their precision in any reader
+#[derive(Clone, Debug, Serialize)]
+pub struct PoolSnapshot {
+    pub pool: PoolRow,
+    pub amm_config: AmmConfigRow,
+    pub rewards: Vec<RewardRow>,
+    pub bitmap_extension: Option<BitmapExtensionRow>,
+    pub ticks: Vec<TickRow>,
+    pub positions: Vec<PositionRow>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct PoolRow {
+    /// The slot the snapshot was taken at
+    pub slot: u64,
+    pub key: String,
+    pub amm_config: String,
+    pub owner: String,
+    pub token_mint_0: String,
+    pub token_mint_1: String,
+    pub token_vault_0: String,
+    pub token_vault_1: String,
+    pub observation_key: String,
+    pub mint_decimals_0: u8,
+    pub mint_decimals_1: u8,
+    pub tick_spacing: u16,
+    pub liquidity: String,
+    pub sqrt_price_x64: String,
+    pub tick_current: i32,
+    pub fee_growth_global_0_x64: String,
+    pub fee_growth_global_1_x64: String,
+    pub protocol_fees_token_0: u64,
+    pub protocol_fees_token_1: u64,
+    pub fund_fees_token_0: u64,
+    pub fund_fees_token_1: u64,
+    pub swap_in_amount_token_0: String,
+    pub swap_out_amount_token_1: String,
+    pub swap_in_amount_token_1: String,
+    pub swap_out_amount_token_0: String,
+    pub status: u8,
+    pub open_time: u64,
+    pub tick_array_bitmap: Vec<u64>,
+}
+
+impl PoolRow {
+    pub fn new(slot: u64, key: &Pubkey, pool: &PoolState) -> Self {
+        Self {
+            slot,
+            key: key.to_string(),
+            amm_config: pool.amm_config.to_string(),
+            owner: pool.owner.to_string(),
+            token_mint_0: pool.token_mint_0.to_string(),
+            token_mint_1: pool.token_mint_1.to_string(),
+            token_vault_0: pool.token_vault_0.to_string(),
+            token_vault_1: pool.token_vault_1.to_string(),
+            observation_key: pool.observation_key.to_string(),
+            mint_decimals_0: pool.mint_decimals_0,
+            mint_decimals_1: pool.mint_decimals_1,
+            tick_spacing: pool.tick_spacing,
+            liquidity: { pool.liquidity }.to_string(),
+            sqrt_price_x64: { pool.sqrt_price_x64 }.to_string(),
+            tick_current: pool.tick_current,
+            fee_growth_global_0_x64: { pool.fee_growth_global_0_x64 }.to_string(),
+            fee_growth_global_1_x64: { pool.fee_growth_global_1_x64 }.to_string(),
+            protocol_fees_token_0: pool.protocol_fees_token_0,
+            protocol_fees_token_1: pool.protocol_fees_token_1,
+            fund_fees_token_0: pool.fund_fees_token_0,
+            fund_fees_token_1: pool.fund_fees_token_1,
+            swap_in_amount_token_0: { pool.swap_in_amount_token_0 }.to_string(),
+            swap_out_amount_token_1: { pool.swap_out_amount_token_1 }.to_string(),
+            swap_in_amount_token_1: { pool.swap_in_amount_token_1 }.to_string(),
+            swap_out_amount_token_0: { pool.swap_out_amount_token_0 }.to_string(),
+            status: pool.status,
+            open_time: pool.open_time,
+            tick_array_bitmap: { pool.tick_array_bitmap }.to_vec(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct AmmConfigRow {
+    pub key: String,
+    pub index: u16,
+    pub owner: String,
+    pub fund_owner: String,
+    pub trade_fee_rate: u32,
+    pub protocol_fee_rate: u32,
+    pub fund_fee_rate: u32,
+    pub tick_spacing: u16,
+}
+
+impl AmmConfigRow {
+    pub fn new(key: &Pubkey, amm_config: &AmmConfig) -> Self {
+        Self {
+            key: key.to_string(),
+            index: amm_config.index,
+            owner: amm_config.owner.to_string(),
+            fund_owner: amm_config.fund_owner.to_string(),
+            trade_fee_rate: amm_config.trade_fee_rate,
+            protocol_fee_rate: amm_config.protocol_fee_rate,
+            fund_fee_rate: amm_config.fund_fee_rate,
+            tick_spacing: amm_config.tick_spacing,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct RewardRow {
+    pub index: usize,
+    pub reward_state: u8,
+    pub token_mint: String,
+    pub token_vault: String,
+    pub authority: String,
+    pub open_time: u64,
+    pub end_time: u64,
+    pub last_update_time: u64,
+    pub emissions_per_second_x64: String,
+    pub reward_total_emissioned: u64,
+    pub reward_claimed: u64,
+    pub reward_growth_global_x64: String,
+}
+
+impl RewardRow {
+    /// The initialized rewards of the pool
+    pub fn from_pool(pool: &PoolState) -> Vec<Self> {
+        let reward_infos = pool.reward_infos;
+        reward_infos
+            .iter()
+            .enumerate()
+            .filter(|(_, reward_info)| reward_info.token_mint != Pubkey::default())
+            .map(|(index, reward_info)| Self {
+                index,
+                reward_state: reward_info.reward_state,
+                token_mint: reward_info.token_mint.to_string(),
+                token_vault: reward_info.token_vault.to_string(),
+                authority: reward_info.authority.to_string(),
+                open_time: reward_info.open_time,
+                end_time: reward_info.end_time,
+                last_update_time: reward_info.last_update_time,
+                emissions_per_second_x64: { reward_info.emissions_per_second_x64 }.to_string(),
+                reward_total_emissioned: reward_info.reward_total_emissioned,
+                reward_claimed: reward_info.reward_claimed,
+                reward_growth_global_x64: { reward_info.reward_growth_global_x64 }.to_string(),
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct BitmapExtensionRow {
+    pub key: String,
+    pub positive_tick_array_bitmap: Vec<Vec<u64>>,
+    pub negative_tick_array_bitmap: Vec<Vec<u64>>,
+}
+
+impl BitmapExtensionRow {
+    pub fn new(key: &Pubkey, extension: &TickArrayBitmapExtension) -> Self {
+        Self {
+            key: key.to_string(),
+            positive_tick_array_bitmap: { extension.positive_tick_array_bitmap }
+                .iter()
+                .map(|bitmap| bitmap.to_vec())
+                .collect(),
+            negative_tick_array_bitmap: { extension.negative_tick_array_bitmap }
+                .iter()
+                .map(|bitmap| bitmap.to_vec())
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TickRow {
+    pub tick_array: String,
+    pub start_tick_index: i32,
+    pub tick: i32,
+    pub liquidity_net: String,
+    pub liquidity_gross: String,
+    pub fee_growth_outside_0_x64: String,
+    pub fee_growth_outside_1_x64: String,
+    pub reward_growths_outside_x64: Vec<String>,
+}
+
+impl TickRow {
+    /// The initialized ticks of the tick array
+    pub fn from_tick_array(key: &Pubkey, tick_array: &TickArrayState) -> Vec<Self> {
+        let ticks = tick_array.ticks;
+        ticks
+            .iter()
+            .filter(|tick| tick.is_initialized())
+            .map(|tick| Self {
+                tick_array: key.to_string(),
+                start_tick_index: tick_array.start_tick_index,
+                tick: tick.tick,
+                liquidity_net: { tick.liquidity_net }.to_string(),
+                liquidity_gross: { tick.liquidity_gross }.to_string(),
+                fee_growth_outside_0_x64: { tick.fee_growth_outside_0_x64 }.to_string(),
+                fee_growth_outside_1_x64: { tick.fee_growth_outside_1_x64 }.to_string(),
+                reward_growths_outside_x64: { tick.reward_growths_outside_x64 }
+                    .iter()
+                    .map(|growth| growth.to_string())
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct PositionRow {
+    pub key: String,
+    pub nft_mint: String,
+    pub tick_lower_index: i32,
+    pub tick_upper_index: i32,
+    pub liquidity: String,
+    pub fee_growth_inside_0_last_x64: String,
+    pub fee_growth_inside_1_last_x64: String,
+    pub token_fees_owed_0: u64,
+    pub token_fees_owed_1: u64,
+    pub reward_growths_inside_last_x64: Vec<String>,
+    pub reward_amounts_owed: Vec<u64>,
+    pub locked: bool,
+}
+
+impl PositionRow {
+    pub fn new(key: &Pubkey, position: &PersonalPositionState) -> Self {
+        Self {
+            key: key.to_string(),
+            nft_mint: position.nft_mint.to_string(),
+            tick_lower_index: position.tick_lower_index,
+            tick_upper_index: position.tick_upper_index,
+            liquidity: position.liquidity.to_string(),
+            fee_growth_inside_0_last_x64: position.fee_growth_inside_0_last_x64.to_string(),
+            fee_growth_inside_1_last_x64: position.fee_growth_inside_1_last_x64.to_string(),
+            token_fees_owed_0: position.token_fees_owed_0,
+            token_fees_owed_1: position.token_fees_owed_1,
+            reward_growths_inside_last_x64: position
+                .reward_infos
+                .iter()
+                .map(|reward_info| reward_info.growth_inside_last_x64.to_string())
+                .collect(),
+            reward_amounts_owed: position
+                .reward_infos
+                .iter()
+                .map(|reward_info| reward_info.reward_amount_owed)
+                .collect(),
+            locked: position.locked,
+        }
+    }
+}
+
+/// Writes the rows as a csv table, the columns are the fields of the rows in alphabetical
+/// order and the nested values are written as json
+pub fn write_csv_table<T: Serialize>(path: &Path, rows: &[T]) -> Result<()> {
+    let escape = |field: String| {
+        if field.contains(|c| c == ',' || c == '"' || c == '\n') {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field
+        }
+    };
+    let mut lines = Vec::new();
+    for row in rows {
+        let fields = match serde_json::to_value(row)? {
+            serde_json::Value::Object(fields) => fields,
+            _ => unreachable!("the rows are structs"),
+        };
+        if lines.is_empty() {
+            lines.push(
+                fields
+                    .keys()
+                    .cloned()
+                    .map(escape)
+                    .collect::<Vec<_>>()
+                    .join(","),
+            );
+        }
+        lines.push(
+            fields
+                .into_iter()
+                .map(|(_, value)| match value {
+                    serde_json::Value::String(value) => escape(value),
+                    value => escape(value.to_string()),
+                })
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+    }
+    lines.push(String::new());
+    std::fs::write(path, lines.join("\n"))?;
+    Ok(())
+}
+
+impl PoolSnapshot {
+    /// Writes the snapshot as one json file
+    pub fn write_json(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Writes a csv file per table of the snapshot into the directory
+    pub fn write_csv(&self, dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(dir)?;
+        write_csv_table(&dir.join("pool.csv"), &[self.pool.clone()])?;
+        write_csv_table(&dir.join("amm_config.csv"), &[self.amm_config.clone()])?;
+        write_csv_table(&dir.join("rewards.csv"), &self.rewards)?;
+        if let Some(bitmap_extension) = &self.bitmap_extension {
+            write_csv_table(
+                &dir.join("bitmap_extension.csv"),
+                &[bitmap_extension.clone()],
+            )?;
+        }
+        write_csv_table(&dir.join("ticks.csv"), &self.ticks)?;
+        write_csv_table(&dir.join("positions.csv"), &self.positions)?;
+        Ok(())
+    }
+}
@@ -1,19 +1,19 @@
 use anyhow::{anyhow, Result};
 use solana_client::{
-    rpc_client::RpcClient,
+    rpc_client::{RpcClient, SerializableTransaction},
     rpc_config::RpcSendTransactionConfig,
     rpc_request::RpcRequest,
     rpc_response::{RpcResult, RpcSimulateTransactionResult},
 };
 use solana_sdk::{
     account::Account, commitment_config::CommitmentConfig, program_pack::Pack as TokenPack,
-    pubkey::Pubkey, signature::Signature, transaction::Transaction,
+    pubkey::Pubkey, signature::Signature,
 };
 use std::convert::Into;
 
 pub fn simulate_transaction(
     client: &RpcClient,
-    transaction: &Transaction,
+    transaction: &impl SerializableTransaction,
     sig_verify: bool,
     cfg: CommitmentConfig,
 ) -> RpcResult<RpcSimulateTransactionResult> {
@@ -26,7 +26,11 @@ pub fn simulate_transaction(
     )
 }
 
-pub fn send_txn(client: &RpcClient, txn: &Transaction, wait_confirm: bool) -> Result<Signature> {
+pub fn send_txn(
+    client: &RpcClient,
+    txn: &impl SerializableTransaction,
+    wait_confirm: bool,
+) -> Result<Signature> {
     Ok(client.send_and_confirm_transaction_with_spinner_and_config(
         txn,
         if wait_confirm {
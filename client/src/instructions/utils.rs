@@ -1,4 +1,4 @@
-use anchor_lang::AccountDeserialize;
+use anchor_lang::{AccountDeserialize, ZeroCopy};
 use anyhow::Result;
 use raydium_amm_v3::libraries::fixed_point_64;
 use raydium_amm_v3::libraries::*;
@@ -29,6 +29,16 @@ pub fn deserialize_anchor_account<T: AccountDeserialize>(account: &Account) -> R
     T::try_deserialize(&mut data).map_err(Into::into)
 }
 
+/// Deserializes a zero copy account, ignoring the tail of an account reallocated
+/// beyond its layout by a migration
+pub fn deserialize_zero_copy_account<T: AccountDeserialize + ZeroCopy>(
+    account: &Account,
+) -> Result<T> {
+    let len = account.data.len().min(8 + std::mem::size_of::<T>());
+    let mut data: &[u8] = &account.data[..len];
+    T::try_deserialize(&mut data).map_err(Into::into)
+}
+
 #[derive(Debug)]
 pub enum ExtensionStruct {
     ConfidentialTransferAccount(ConfidentialTransferAccount),
@@ -288,6 +298,8 @@ pub struct SwapState {
     pub tick: i32,
     // the current liquidity in range
     pub liquidity: u128,
+    // the fee paid in the input asset
+    pub fee_amount: u64,
 }
 #[derive(Default)]
 struct StepComputations {
@@ -317,11 +329,38 @@ pub fn get_out_put_amount_and_remaining_accounts(
     tickarray_bitmap_extension: &TickArrayBitmapExtension,
     tick_arrays: &mut VecDeque<TickArrayState>,
 ) -> Result<(u64, VecDeque<i32>), &'static str> {
+    let (state, tick_array_start_index_vec) = quote_swap(
+        input_amount,
+        sqrt_price_limit_x64,
+        zero_for_one,
+        is_base_input,
+        pool_config,
+        pool_state,
+        tickarray_bitmap_extension,
+        tick_arrays,
+    )?;
+    println!("tick_array_start_index:{:?}", tick_array_start_index_vec);
+
+    Ok((state.amount_calculated, tick_array_start_index_vec))
+}
+
+/// Computes the swap of `get_out_put_amount_and_remaining_accounts`, returning the final state of
+/// the swap with the fee paid and the price it ends at
+pub fn quote_swap(
+    input_amount: u64,
+    sqrt_price_limit_x64: Option<u128>,
+    zero_for_one: bool,
+    is_base_input: bool,
+    pool_config: &AmmConfig,
+    pool_state: &PoolState,
+    tickarray_bitmap_extension: &TickArrayBitmapExtension,
+    tick_arrays: &mut VecDeque<TickArrayState>,
+) -> Result<(SwapState, VecDeque<i32>), &'static str> {
     let (is_pool_current_tick_array, current_vaild_tick_array_start_index) = pool_state
         .get_first_initialized_tick_array(&Some(*tickarray_bitmap_extension), zero_for_one)
         .unwrap();
 
-    let (amount_calculated, tick_array_start_index_vec) = swap_compute(
+    swap_compute(
         zero_for_one,
         is_base_input,
         is_pool_current_tick_array,
@@ -332,10 +371,78 @@ pub fn get_out_put_amount_and_remaining_accounts(
         pool_state,
         tickarray_bitmap_extension,
         tick_arrays,
-    )?;
-    println!("tick_array_start_index:{:?}", tick_array_start_index_vec);
+    )
+}
 
-    Ok((amount_calculated, tick_array_start_index_vec))
+/// Finds the routes of at most `max_hops` pools from the input mint to the output mint without
+/// passing a mint twice, each hop is the index of the pool in `pools` and its swap direction
+pub fn find_swap_routes(
+    pools: &[(Pubkey, PoolState)],
+    input_mint: Pubkey,
+    output_mint: Pubkey,
+    max_hops: usize,
+) -> Vec<Vec<(usize, bool)>> {
+    let mut routes = Vec::new();
+    extend_swap_routes(
+        pools,
+        input_mint,
+        output_mint,
+        max_hops,
+        &mut Vec::new(),
+        &mut vec![input_mint],
+        &mut routes,
+    );
+    // the routes with less hops first
+    routes.sort_by_key(|route| route.len());
+    routes
+}
+
+fn extend_swap_routes(
+    pools: &[(Pubkey, PoolState)],
+    mint: Pubkey,
+    output_mint: Pubkey,
+    max_hops: usize,
+    route: &mut Vec<(usize, bool)>,
+    visited_mints: &mut Vec<Pubkey>,
+    routes: &mut Vec<Vec<(usize, bool)>>,
+) {
+    if route.len() == max_hops {
+        return;
+    }
+    for (index, (_, pool_state)) in pools.iter().enumerate() {
+        let zero_for_one = pool_state.token_mint_0 == mint;
+        if !zero_for_one && pool_state.token_mint_1 != mint {
+            continue;
+        }
+        let next_mint = if zero_for_one {
+            pool_state.token_mint_1
+        } else {
+            pool_state.token_mint_0
+        };
+        if visited_mints.contains(&next_mint)
+            || pool_state.liquidity == 0
+            || !pool_state.is_swap_enabled(zero_for_one)
+        {
+            continue;
+        }
+        route.push((index, zero_for_one));
+        if next_mint == output_mint {
+            routes.push(route.clone());
+        } else {
+            visited_mints.push(next_mint);
+            extend_swap_routes(
+                pools,
+                next_mint,
+                output_mint,
+                max_hops,
+                route,
+                visited_mints,
+                routes,
+            );
+            visited_mints.pop();
+        }
+        route.pop();
+    }
 }
 
 fn swap_compute(
@@ -349,7 +456,7 @@ fn swap_compute(
     pool_state: &PoolState,
     tickarray_bitmap_extension: &TickArrayBitmapExtension,
     tick_arrays: &mut VecDeque<TickArrayState>,
-) -> Result<(u64, VecDeque<i32>), &'static str> {
+) -> Result<(SwapState, VecDeque<i32>), &'static str> {
     if amount_specified == 0 {
         return Result::Err("amountSpecified must not be 0");
     }
@@ -385,6 +492,7 @@ fn swap_compute(
         sqrt_price_x64: pool_state.sqrt_price_x64,
         tick: pool_state.tick_current,
         liquidity: pool_state.liquidity,
+        fee_amount: 0,
     };
 
     let mut tick_array_current = tick_arrays.pop_front().unwrap();
@@ -478,6 +586,7 @@ fn swap_compute(
         step.amount_in = swap_step.amount_in;
         step.amount_out = swap_step.amount_out;
         step.fee_amount = swap_step.fee_amount;
+        state.fee_amount = state.fee_amount.checked_add(step.fee_amount).unwrap();
 
         if is_base_input {
             state.amount_specified_remaining = state
@@ -522,5 +631,5 @@ fn swap_compute(
         loop_count += 1;
     }
 
-    Ok((state.amount_calculated, tick_array_start_index_vec))
+    Ok((state, tick_array_start_index_vec))
 }
@@ -1,40 +1,60 @@
 #![allow(dead_code)]
 use anchor_client::{Client, Cluster};
 use anchor_lang::prelude::AccountMeta;
+use anchor_lang::Discriminator;
 use anyhow::{format_err, Result};
 use arrayref::array_ref;
 use clap::Parser;
 use configparser::ini::Ini;
 use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
 use solana_account_decoder::{
     parse_token::{TokenAccountType, UiAccountState},
     UiAccountData, UiAccountEncoding,
 };
+use solana_address_lookup_table_program::{
+    instruction::{create_lookup_table, extend_lookup_table},
+    state::AddressLookupTable,
+};
 use solana_client::{
+    pubsub_client::PubsubClient,
     rpc_client::RpcClient,
-    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcTransactionConfig},
+    rpc_config::{
+        RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcTransactionConfig,
+        RpcTransactionLogsConfig, RpcTransactionLogsFilter,
+    },
     rpc_filter::{Memcmp, RpcFilterType},
     rpc_request::TokenAccountsFilter,
 };
 use solana_sdk::{
+    address_lookup_table_account::AddressLookupTableAccount,
     commitment_config::CommitmentConfig,
     compute_budget::ComputeBudgetInstruction,
-    message::Message,
+    hash::Hash,
+    instruction::Instruction,
+    message::{v0, Message, VersionedMessage},
+    packet::PACKET_DATA_SIZE,
     program_pack::Pack,
     pubkey::Pubkey,
     signature::{Keypair, Signature, Signer},
-    transaction::Transaction,
+    transaction::{Transaction, VersionedTransaction},
 };
 use solana_transaction_status::UiTransactionEncoding;
 use std::path::Path;
 use std::rc::Rc;
 use std::str::FromStr;
-use std::{collections::VecDeque, convert::identity, mem::size_of};
+use std::{
+    collections::{HashMap, VecDeque},
+    convert::identity,
+    mem::size_of,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 mod instructions;
 use bincode::serialize;
 use instructions::amm_instructions::*;
 use instructions::events_instructions_parse::*;
+use instructions::pool_snapshot::*;
 use instructions::rpc::*;
 use instructions::token_instructions::*;
 use instructions::utils::*;
@@ -42,7 +62,9 @@ use raydium_amm_v3::{
     libraries::{fixed_point_64, liquidity_math, tick_math},
     states::{PoolState, TickArrayBitmapExtension, TickArrayState, POOL_TICK_ARRAY_BITMAP_SEED},
 };
-use spl_associated_token_account::get_associated_token_address;
+use spl_associated_token_account::{
+    get_associated_token_address, get_associated_token_address_with_program_id,
+};
 use spl_token_2022::{
     extension::StateWithExtensions,
     state::Mint,
@@ -194,6 +216,24 @@ fn load_cur_and_next_five_tick_array(
     pool_state: &PoolState,
     tickarray_bitmap_extension: &TickArrayBitmapExtension,
     zero_for_one: bool,
+) -> VecDeque<TickArrayState> {
+    load_pool_cur_and_next_five_tick_array(
+        rpc_client,
+        &pool_config.raydium_v3_program,
+        &pool_config.pool_id_account.unwrap(),
+        pool_state,
+        tickarray_bitmap_extension,
+        zero_for_one,
+    )
+}
+
+fn load_pool_cur_and_next_five_tick_array(
+    rpc_client: &RpcClient,
+    raydium_v3_program: &Pubkey,
+    pool_id: &Pubkey,
+    pool_state: &PoolState,
+    tickarray_bitmap_extension: &TickArrayBitmapExtension,
+    zero_for_one: bool,
 ) -> VecDeque<TickArrayState> {
     let (_, mut current_vaild_tick_array_start_index) = pool_state
         .get_first_initialized_tick_array(&Some(*tickarray_bitmap_extension), zero_for_one)
@@ -203,10 +243,10 @@ fn load_cur_and_next_five_tick_array(
         Pubkey::find_program_address(
             &[
                 raydium_amm_v3::states::TICK_ARRAY_SEED.as_bytes(),
-                pool_config.pool_id_account.unwrap().to_bytes().as_ref(),
+                pool_id.to_bytes().as_ref(),
                 &current_vaild_tick_array_start_index.to_be_bytes(),
             ],
-            &pool_config.raydium_v3_program,
+            raydium_v3_program,
         )
         .0,
     );
@@ -227,10 +267,10 @@ fn load_cur_and_next_five_tick_array(
             Pubkey::find_program_address(
                 &[
                     raydium_amm_v3::states::TICK_ARRAY_SEED.as_bytes(),
-                    pool_config.pool_id_account.unwrap().to_bytes().as_ref(),
+                    pool_id.to_bytes().as_ref(),
                     &current_vaild_tick_array_start_index.to_be_bytes(),
                 ],
-                &pool_config.raydium_v3_program,
+                raydium_v3_program,
             )
             .0,
         );
@@ -239,16 +279,479 @@ fn load_cur_and_next_five_tick_array(
     let tick_array_rsps = rpc_client.get_multiple_accounts(&tick_array_keys).unwrap();
     let mut tick_arrays = VecDeque::new();
     for tick_array in tick_array_rsps {
-        let tick_array_state =
-            deserialize_anchor_account::<raydium_amm_v3::states::TickArrayState>(
-                &tick_array.unwrap(),
-            )
-            .unwrap();
+        let tick_array_state = deserialize_zero_copy_account::<
+            raydium_amm_v3::states::TickArrayState,
+        >(&tick_array.unwrap())
+        .unwrap();
         tick_arrays.push_back(tick_array_state);
     }
     tick_arrays
 }
 
+/// The maximum number of the routes quoted by `SwapRouterBaseIn`
+const MAX_QUOTED_SWAP_ROUTES: usize = 50;
+
+/// Quotes the output of a base input swap along the route, each hop swaps the output of the
+/// previous hop net of the transfer fees. Returns the output and the tick arrays of each hop.
+fn quote_swap_route(
+    rpc_client: &RpcClient,
+    pool_config: &ClientConfig,
+    pools: &[(Pubkey, PoolState)],
+    route: &[(usize, bool)],
+    amm_configs: &HashMap<Pubkey, raydium_amm_v3::states::AmmConfig>,
+    tickarray_bitmap_extensions: &HashMap<Pubkey, TickArrayBitmapExtension>,
+    mints: &HashMap<Pubkey, solana_sdk::account::Account>,
+    tick_arrays_cache: &mut HashMap<(Pubkey, bool), VecDeque<TickArrayState>>,
+    epoch: u64,
+    amount_in: u64,
+) -> Option<(u64, Vec<VecDeque<i32>>)> {
+    let mut amount = amount_in;
+    let mut hops_tick_array_indexs = Vec::new();
+    for &(index, zero_for_one) in route {
+        let (pool_id, pool_state) = &pools[index];
+        let (input_mint, output_mint) = if zero_for_one {
+            (pool_state.token_mint_0, pool_state.token_mint_1)
+        } else {
+            (pool_state.token_mint_1, pool_state.token_mint_0)
+        };
+        let input_mint_state =
+            StateWithExtensions::<Mint>::unpack(&mints.get(&input_mint)?.data).ok()?;
+        let output_mint_state =
+            StateWithExtensions::<Mint>::unpack(&mints.get(&output_mint)?.data).ok()?;
+        let amm_config = amm_configs.get(&pool_state.amm_config)?;
+        let tickarray_bitmap_extension = tickarray_bitmap_extensions.get(pool_id)?;
+        let mut tick_arrays = tick_arrays_cache
+            .entry((*pool_id, zero_for_one))
+            .or_insert_with(|| {
+                load_pool_cur_and_next_five_tick_array(
+                    rpc_client,
+                    &pool_config.raydium_v3_program,
+                    pool_id,
+                    pool_state,
+                    tickarray_bitmap_extension,
+                    zero_for_one,
+                )
+            })
+            .clone();
+
+        let amount_specified =
+            amount.checked_sub(get_transfer_fee(&input_mint_state, epoch, amount))?;
+        let (amount_out, tick_array_indexs) = utils::get_out_put_amount_and_remaining_accounts(
+            amount_specified,
+            None,
+            zero_for_one,
+            true,
+            amm_config,
+            pool_state,
+            tickarray_bitmap_extension,
+            &mut tick_arrays,
+        )
+        .ok()?;
+        amount = amount_out.checked_sub(get_transfer_fee(&output_mint_state, epoch, amount_out))?;
+        hops_tick_array_indexs.push(tick_array_indexs);
+    }
+    Some((amount, hops_tick_array_indexs))
+}
+
+/// The compute budget of every transaction sent by the client
+#[derive(Clone, Debug, PartialEq, Eq, clap::Args)]
+pub struct ComputeBudgetOptions {
+    /// The compute unit price in micro lamports, the floor of the auto priority fee
+    #[arg(long, global = true)]
+    pub priority_fee_microlamports: Option<u64>,
+    /// The compute unit limit
+    #[arg(long, global = true, default_value_t = 1400_000)]
+    pub compute_unit_limit: u32,
+    /// Derives the compute unit price from the recent prioritization fees of the written accounts
+    #[arg(long, global = true)]
+    pub auto_priority_fee: bool,
+}
+
+/// The maximum number of accounts of the recent prioritization fees request
+const MAX_PRIORITIZATION_FEE_ACCOUNTS: usize = 128;
+
+/// Replaces the compute budget instructions of the transaction with the ones of the options, the
+/// auto priority fee is the median of the recent prioritization fees of the written accounts
+fn with_compute_budget(
+    rpc_client: &RpcClient,
+    options: &ComputeBudgetOptions,
+    instructions: &[Instruction],
+) -> Result<Vec<Instruction>> {
+    let instructions: Vec<Instruction> = instructions
+        .iter()
+        .filter(|instruction| instruction.program_id != solana_sdk::compute_budget::id())
+        .cloned()
+        .collect();
+    let mut priority_fee = options.priority_fee_microlamports.unwrap_or(0);
+    if options.auto_priority_fee {
+        let mut writable_accounts: Vec<Pubkey> = instructions
+            .iter()
+            .flat_map(|instruction| instruction.accounts.iter())
+            .filter(|meta| meta.is_writable)
+            .map(|meta| meta.pubkey)
+            .collect();
+        writable_accounts.sort();
+        writable_accounts.dedup();
+        writable_accounts.truncate(MAX_PRIORITIZATION_FEE_ACCOUNTS);
+        let mut fees: Vec<u64> = rpc_client
+            .get_recent_prioritization_fees(&writable_accounts)?
+            .into_iter()
+            .map(|fee| fee.prioritization_fee)
+            .collect();
+        fees.sort();
+        if let Some(median) = fees.get(fees.len() / 2) {
+            priority_fee = priority_fee.max(*median);
+        }
+    }
+    let mut budget_instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(
+        options.compute_unit_limit,
+    )];
+    if priority_fee > 0 {
+        budget_instructions.push(ComputeBudgetInstruction::set_compute_unit_price(
+            priority_fee,
+        ));
+    }
+    budget_instructions.extend(instructions);
+    Ok(budget_instructions)
+}
+
+/// The compute budget instructions reserved at the start of a packed transaction, replaced by
+/// `with_compute_budget` when the transaction is signed
+fn compute_budget_placeholder() -> Vec<Instruction> {
+    vec![
+        ComputeBudgetInstruction::set_compute_unit_limit(0),
+        ComputeBudgetInstruction::set_compute_unit_price(0),
+    ]
+}
+
+/// The address lookup tables of the swap and liquidity transactions
+#[derive(Clone, Debug, PartialEq, Eq, clap::Args)]
+pub struct LookupTableOptions {
+    /// An address lookup table resolving the accounts of the swap and liquidity transactions,
+    /// they are sent as v0 transactions when any is given
+    #[arg(long = "lookup-table", global = true)]
+    pub lookup_tables: Vec<Pubkey>,
+}
+
+/// The number of addresses appended to a lookup table by one transaction
+const LOOKUP_TABLE_EXTEND_CHUNK_SIZE: usize = 20;
+
+/// Loads the address lookup tables by their keys
+fn load_lookup_tables(
+    rpc_client: &RpcClient,
+    keys: &[Pubkey],
+) -> Result<Vec<AddressLookupTableAccount>> {
+    if keys.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut lookup_tables = Vec::new();
+    for (key, rsp) in keys.iter().zip(rpc_client.get_multiple_accounts(keys)?) {
+        let account = match rsp {
+            Some(account) => account,
+            None => return Err(format_err!("lookup table {} not found", key)),
+        };
+        let lookup_table = AddressLookupTable::deserialize(&account.data)
+            .map_err(|e| format_err!("invalid lookup table {}: {}", key, e))?;
+        lookup_tables.push(AddressLookupTableAccount {
+            key: *key,
+            addresses: lookup_table.addresses.to_vec(),
+        });
+    }
+    Ok(lookup_tables)
+}
+
+/// Compiles the instructions into a v0 message resolving the accounts through the lookup
+/// tables, or into a legacy message when there is no lookup table
+fn compile_message(
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    recent_hash: Hash,
+) -> Result<VersionedMessage> {
+    if lookup_tables.is_empty() {
+        Ok(VersionedMessage::Legacy(Message::new_with_blockhash(
+            instructions,
+            Some(payer),
+            &recent_hash,
+        )))
+    } else {
+        Ok(VersionedMessage::V0(v0::Message::try_compile(
+            payer,
+            instructions,
+            lookup_tables,
+            recent_hash,
+        )?))
+    }
+}
+
+/// Signs the instructions with the compute budget of the options as a v0 transaction using the
+/// lookup tables, or as a legacy transaction when there is no lookup table
+fn sign_versioned_transaction(
+    rpc_client: &RpcClient,
+    compute_budget: &ComputeBudgetOptions,
+    lookup_tables: &[AddressLookupTableAccount],
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    signers: &[&Keypair],
+) -> Result<VersionedTransaction> {
+    let instructions = with_compute_budget(rpc_client, compute_budget, instructions)?;
+    let recent_hash = rpc_client.get_latest_blockhash()?;
+    let message = compile_message(payer, &instructions, lookup_tables, recent_hash)?;
+    Ok(VersionedTransaction::try_new(message, signers)?)
+}
+
+/// Packs the groups of instructions into as few transactions as the packet size allows, each
+/// transaction starts with the compute budget placeholder and a group is never split. The
+/// accounts in the lookup tables are counted as their index.
+fn pack_instructions_into_transactions(
+    payer: &Pubkey,
+    lookup_tables: &[AddressLookupTableAccount],
+    groups: Vec<Vec<Instruction>>,
+) -> Vec<Vec<Instruction>> {
+    let fits = |instructions: &[Instruction]| {
+        let message = match compile_message(payer, instructions, lookup_tables, Hash::default()) {
+            Ok(message) => message,
+            Err(_) => return false,
+        };
+        let txn = VersionedTransaction {
+            signatures: vec![
+                Signature::default();
+                message.header().num_required_signatures as usize
+            ],
+            message,
+        };
+        serialize(&txn).unwrap().len() <= PACKET_DATA_SIZE
+    };
+    let header = compute_budget_placeholder();
+    let mut transactions = Vec::new();
+    let mut instructions = header.clone();
+    for group in groups {
+        let mut candidate = instructions.clone();
+        candidate.extend(group.clone());
+        if fits(&candidate) || instructions.len() == header.len() {
+            instructions = candidate;
+        } else {
+            transactions.push(instructions);
+            instructions = header.clone();
+            instructions.extend(group);
+        }
+    }
+    if instructions.len() > header.len() {
+        transactions.push(instructions);
+    }
+    transactions
+}
+
+/// Loads the pools of the positions by their keys
+fn load_pools_of_positions(
+    rpc_client: &RpcClient,
+    positions: &[raydium_amm_v3::states::PersonalPositionState],
+) -> Result<HashMap<Pubkey, PoolState>> {
+    let mut pool_keys: Vec<Pubkey> = positions.iter().map(|p| p.pool_id).collect();
+    pool_keys.sort();
+    pool_keys.dedup();
+    let mut pools = HashMap::new();
+    for (key, rsp) in pool_keys
+        .iter()
+        .zip(rpc_client.get_multiple_accounts(&pool_keys)?)
+    {
+        pools.insert(
+            *key,
+            deserialize_anchor_account::<PoolState>(&rsp.unwrap())?,
+        );
+    }
+    Ok(pools)
+}
+
+/// The fees and rewards a position can collect, estimated with the growths of the last update
+/// of its pool
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct PositionPendingFees {
+    fee_growth_inside_0_x64: u128,
+    fee_growth_inside_1_x64: u128,
+    fees_0: u64,
+    fees_1: u64,
+    rewards: [u64; raydium_amm_v3::states::REWARD_NUM],
+}
+
+/// Estimates the pending fees and rewards of the positions with the ticks of their tick arrays
+fn estimate_positions_pending_fees(
+    rpc_client: &RpcClient,
+    raydium_v3_program: &Pubkey,
+    positions: &[raydium_amm_v3::states::PersonalPositionState],
+    pools: &HashMap<Pubkey, PoolState>,
+) -> Result<Vec<PositionPendingFees>> {
+    let tick_array_key = |pool_id: &Pubkey, tick_index: i32, tick_spacing: u16| {
+        Pubkey::find_program_address(
+            &[
+                raydium_amm_v3::states::TICK_ARRAY_SEED.as_bytes(),
+                pool_id.to_bytes().as_ref(),
+                &TickArrayState::get_array_start_index(tick_index, tick_spacing).to_be_bytes(),
+            ],
+            raydium_v3_program,
+        )
+        .0
+    };
+    let mut tick_array_keys = Vec::new();
+    for position in positions {
+        let tick_spacing = pools[&position.pool_id].tick_spacing;
+        tick_array_keys.push(tick_array_key(
+            &position.pool_id,
+            position.tick_lower_index,
+            tick_spacing,
+        ));
+        tick_array_keys.push(tick_array_key(
+            &position.pool_id,
+            position.tick_upper_index,
+            tick_spacing,
+        ));
+    }
+    tick_array_keys.sort();
+    tick_array_keys.dedup();
+    let mut tick_arrays = HashMap::new();
+    for (key, rsp) in tick_array_keys
+        .iter()
+        .zip(get_multiple_accounts(rpc_client, &tick_array_keys)?)
+    {
+        tick_arrays.insert(
+            *key,
+            deserialize_zero_copy_account::<TickArrayState>(&rsp.unwrap())?,
+        );
+    }
+
+    let mut pending_fees = Vec::new();
+    for position in positions {
+        let pool = &pools[&position.pool_id];
+        let tick_lower = *tick_arrays[&tick_array_key(
+            &position.pool_id,
+            position.tick_lower_index,
+            pool.tick_spacing,
+        )]
+            .get_tick_state(position.tick_lower_index, pool.tick_spacing)?;
+        let tick_upper = *tick_arrays[&tick_array_key(
+            &position.pool_id,
+            position.tick_upper_index,
+            pool.tick_spacing,
+        )]
+            .get_tick_state(position.tick_upper_index, pool.tick_spacing)?;
+        let (fee_growth_inside_0_x64, fee_growth_inside_1_x64) =
+            raydium_amm_v3::states::get_fee_growth_inside(
+                &tick_lower,
+                &tick_upper,
+                pool.tick_current,
+                pool.fee_growth_global_0_x64,
+                pool.fee_growth_global_1_x64,
+            );
+        let reward_infos = pool.reward_infos;
+        let reward_growths_inside = raydium_amm_v3::states::get_reward_growths_inside(
+            &tick_lower,
+            &tick_upper,
+            pool.tick_current,
+            &reward_infos,
+        );
+        let mut rewards = [0; raydium_amm_v3::states::REWARD_NUM];
+        for (i, reward_info) in reward_infos.iter().enumerate() {
+            if reward_info.token_mint == Pubkey::default() {
+                continue;
+            }
+            rewards[i] = raydium_amm_v3::instructions::calculate_latest_token_fees(
+                position.reward_infos[i].reward_amount_owed,
+                position.reward_infos[i].growth_inside_last_x64,
+                reward_growths_inside[i],
+                position.liquidity,
+            );
+        }
+        pending_fees.push(PositionPendingFees {
+            fee_growth_inside_0_x64,
+            fee_growth_inside_1_x64,
+            fees_0: raydium_amm_v3::instructions::calculate_latest_token_fees(
+                position.token_fees_owed_0,
+                position.fee_growth_inside_0_last_x64,
+                fee_growth_inside_0_x64,
+                position.liquidity,
+            ),
+            fees_1: raydium_amm_v3::instructions::calculate_latest_token_fees(
+                position.token_fees_owed_1,
+                position.fee_growth_inside_1_last_x64,
+                fee_growth_inside_1_x64,
+                position.liquidity,
+            ),
+            rewards,
+        });
+    }
+    Ok(pending_fees)
+}
+
+/// The fee growths of a position recorded by a report, the next report derives the trailing
+/// fee APR and P&L from them
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct PositionSnapshot {
+    timestamp: u64,
+    fee_growth_inside_0_x64: u128,
+    fee_growth_inside_1_x64: u128,
+    value: f64,
+}
+
+/// The report of a position, the values are quoted in token_1 at the current pool price
+#[derive(Clone, Debug, PartialEq, Serialize)]
+struct PositionReportItem {
+    position: String,
+    pool: String,
+    tick_lower: i32,
+    tick_upper: i32,
+    liquidity: u128,
+    price: f64,
+    amount_0: f64,
+    amount_1: f64,
+    fees_0: f64,
+    fees_1: f64,
+    /// The raw pending amounts of the reward mints
+    rewards: Vec<(String, u64)>,
+    value: f64,
+    fees_value: f64,
+    /// The seconds since the snapshot of the last report
+    elapsed: Option<u64>,
+    /// The change of the value plus the fees earned since the last report
+    pnl: Option<f64>,
+    /// The annualized fees earned since the last report in percent of the value
+    fee_apr: Option<f64>,
+}
+
+/// A price range of the ladder spec of `OpenPositionsBatch` with the maximum amounts it is funded
+/// with, the liquidity is the most the amounts provide at the current price
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+struct LadderBand {
+    lower_price: f64,
+    upper_price: f64,
+    #[serde(default)]
+    amount_0: u64,
+    #[serde(default)]
+    amount_1: u64,
+}
+
+/// The ladder spec of `OpenPositionsBatch`, a toml file of the bands opened in the pool of the config
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+struct LadderSpec {
+    #[serde(default)]
+    with_metadata: bool,
+    bands: Vec<LadderBand>,
+}
+
+/// The updates streamed by the subscriptions of the `Watch` command
+#[derive(Debug)]
+enum WatchUpdate {
+    Account {
+        key: Pubkey,
+        slot: u64,
+        account: solana_sdk::account::Account,
+    },
+    Logs {
+        slot: u64,
+        signature: String,
+        logs: Vec<String>,
+    },
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 struct PositionNftTokenInfo {
     key: Pubkey,
@@ -341,6 +844,10 @@ fn get_nft_account_and_position_by_owner(
 
 #[derive(Debug, Parser)]
 pub struct Opts {
+    #[clap(flatten)]
+    pub compute_budget: ComputeBudgetOptions,
+    #[clap(flatten)]
+    pub lookup_table: LookupTableOptions,
     #[clap(subcommand)]
     pub command: CommandsName,
 }
@@ -395,11 +902,20 @@ pub enum CommandsName {
         value: u32,
         remaining: Option<Pubkey>,
     },
+    AcceptOwnership {
+        config_index: u16,
+    },
     CreateOperation,
     UpdateOperation {
         param: u8,
         keys: Vec<Pubkey>,
     },
+    CreateWhitelistCreator {
+        creator: Pubkey,
+    },
+    DeleteWhitelistCreator {
+        creator: Pubkey,
+    },
     CreatePool {
         config_index: u16,
         price: f64,
@@ -437,6 +953,21 @@ pub enum CommandsName {
         #[arg(short, long)]
         with_metadata: bool,
     },
+    OpenPositionsBatch {
+        spec_path: String,
+        #[arg(long)]
+        dry_run: bool,
+    },
+    Rebalance {
+        nft_mint: Pubkey,
+        tick_lower_price: f64,
+        tick_upper_price: f64,
+        /// Swaps the imbalance of the withdrawn amounts to the ratio of the new range
+        #[arg(long)]
+        swap: bool,
+        #[arg(short, long)]
+        simulate: bool,
+    },
     IncreaseLiquidity {
         tick_lower_price: f64,
         tick_upper_price: f64,
@@ -450,6 +981,9 @@ pub enum CommandsName {
         liquidity: Option<u128>,
         #[arg(short, long)]
         simulate: bool,
+        /// The token accounts receiving the rewards of their mints instead of the payer's ATA
+        #[arg(long)]
+        reward_recipient: Vec<Pubkey>,
     },
     Swap {
         input_token: Pubkey,
@@ -468,9 +1002,49 @@ pub enum CommandsName {
         base_in: bool,
         #[arg(short, long)]
         simulate: bool,
+        #[arg(long)]
+        max_price_impact_bps: Option<u16>,
+        #[arg(long)]
+        slippage_bps: Option<u16>,
+        amount: u64,
+        limit_price: Option<f64>,
+    },
+    Quote {
+        #[arg(long)]
+        pool_id: Option<Pubkey>,
+        input_mint: Pubkey,
+        #[arg(short, long)]
+        base_in: bool,
         amount: u64,
         limit_price: Option<f64>,
     },
+    SwapRouterBaseIn {
+        input_mint: Pubkey,
+        output_mint: Pubkey,
+        #[arg(long, default_value_t = 3)]
+        max_hops: usize,
+        #[arg(short, long)]
+        simulate: bool,
+        amount: u64,
+    },
+    HarvestAll {
+        #[arg(short, long)]
+        simulate: bool,
+    },
+    Watch {
+        #[arg(long)]
+        pool_id: Option<Pubkey>,
+        #[arg(long, default_value_t = 2)]
+        tick_array_range: i32,
+    },
+    PositionReport {
+        #[arg(long)]
+        user_wallet: Option<Pubkey>,
+        #[arg(long, default_value = "position_snapshots.json")]
+        snapshot_path: String,
+        #[arg(long)]
+        json: bool,
+    },
     PPositionByOwner {
         user_wallet: Pubkey,
     },
@@ -515,12 +1089,35 @@ pub enum CommandsName {
     PPersonalPositionByPool {
         pool_id: Option<Pubkey>,
     },
+    ExportPool {
+        #[arg(long)]
+        pool_id: Option<Pubkey>,
+        /// Writes a csv file per table into the output directory instead of one json file
+        #[arg(long)]
+        csv: bool,
+        output: String,
+    },
     PProtocolPositionByPool {
         pool_id: Option<Pubkey>,
     },
     PTickArrayByPool {
         pool_id: Option<Pubkey>,
     },
+    CrankCloseTickArrays {
+        #[arg(long)]
+        pool_id: Option<Pubkey>,
+        /// Scans the tick arrays of all the pools instead of one pool
+        #[arg(long)]
+        all: bool,
+        #[arg(short, long)]
+        simulate: bool,
+    },
+    /// Creates an address lookup table of the accounts of the swaps and the liquidity changes of
+    /// the pool, pass it with `--lookup-table` to send them as v0 transactions
+    CreateLookupTable {
+        #[arg(long)]
+        pool_id: Option<Pubkey>,
+    },
     PPool {
         pool_id: Option<Pubkey>,
     },
@@ -533,6 +1130,13 @@ pub enum CommandsName {
     PPersonal {
         personal_id: Pubkey,
     },
+    PInspect {
+        account: Pubkey,
+    },
+    PTwap {
+        pool_id: Pubkey,
+        window_seconds: u32,
+    },
     DecodeInstruction {
         instr_hex_data: String,
     },
@@ -562,6 +1166,8 @@ fn main() -> Result<()> {
     let program = anchor_client.program(pool_config.raydium_v3_program)?;
 
     let opts = Opts::parse();
+    let compute_budget = opts.compute_budget;
+    let lookup_tables = load_lookup_tables(&rpc_client, &opts.lookup_table.lookup_tables)?;
     match opts.command {
         CommandsName::NewMint {
             authority,
@@ -655,7 +1261,7 @@ fn main() -> Result<()> {
             let signers = vec![&payer, &mint];
             let recent_hash = rpc_client.get_latest_blockhash()?;
             let txn = Transaction::new_signed_with_payer(
-                &create_and_init_instr,
+                &with_compute_budget(&rpc_client, &compute_budget, &create_and_init_instr)?,
                 Some(&payer.pubkey()),
                 &signers,
                 recent_hash,
@@ -690,7 +1296,7 @@ fn main() -> Result<()> {
             // send
             let recent_hash = rpc_client.get_latest_blockhash()?;
             let txn = Transaction::new_signed_with_payer(
-                &create_ata_instr,
+                &with_compute_budget(&rpc_client, &compute_budget, &create_ata_instr)?,
                 Some(&payer.pubkey()),
                 &signers,
                 recent_hash,
@@ -716,7 +1322,7 @@ fn main() -> Result<()> {
             let signers = vec![&payer];
             let recent_hash = rpc_client.get_latest_blockhash()?;
             let txn = Transaction::new_signed_with_payer(
-                &mint_to_instr,
+                &with_compute_budget(&rpc_client, &compute_budget, &mint_to_instr)?,
                 Some(&payer.pubkey()),
                 &signers,
                 recent_hash,
@@ -730,7 +1336,7 @@ fn main() -> Result<()> {
             let signers = vec![&payer];
             let recent_hash = rpc_client.get_latest_blockhash()?;
             let txn = Transaction::new_signed_with_payer(
-                &wrap_sol_instr,
+                &with_compute_budget(&rpc_client, &compute_budget, &wrap_sol_instr)?,
                 Some(&payer.pubkey()),
                 &signers,
                 recent_hash,
@@ -745,7 +1351,7 @@ fn main() -> Result<()> {
             let signers = vec![&payer];
             let recent_hash = rpc_client.get_latest_blockhash()?;
             let txn = Transaction::new_signed_with_payer(
-                &unwrap_sol_instr,
+                &with_compute_budget(&rpc_client, &compute_budget, &unwrap_sol_instr)?,
                 Some(&payer.pubkey()),
                 &signers,
                 recent_hash,
@@ -772,7 +1378,7 @@ fn main() -> Result<()> {
             let signers = vec![&payer, &admin];
             let recent_hash = rpc_client.get_latest_blockhash()?;
             let txn = Transaction::new_signed_with_payer(
-                &create_instr,
+                &with_compute_budget(&rpc_client, &compute_budget, &create_instr)?,
                 Some(&payer.pubkey()),
                 &signers,
                 recent_hash,
@@ -786,21 +1392,20 @@ fn main() -> Result<()> {
             value,
             remaining,
         } => {
-            let mut remaing_accounts = Vec::new();
             let mut update_value = 0;
             let match_param = Some(param);
             match match_param {
                 Some(0) => update_value = value,
                 Some(1) => update_value = value,
                 Some(2) => update_value = value,
-                Some(3) => {
-                    let remaining_key = remaining.unwrap();
-                    remaing_accounts.push(AccountMeta::new_readonly(remaining_key, false));
-                }
-                Some(4) => {
-                    let remaining_key = remaining.unwrap();
-                    remaing_accounts.push(AccountMeta::new_readonly(remaining_key, false));
-                }
+                Some(3) => {}
+                Some(4) => {}
+                Some(5) => update_value = value,
+                Some(6) => update_value = value,
+                Some(7) => update_value = value,
+                Some(8) => update_value = value,
+                Some(9) => update_value = value,
+                Some(10) => update_value = value,
                 _ => panic!("error input"),
             }
             let (amm_config_key, __bump) = Pubkey::find_program_address(
@@ -810,18 +1415,55 @@ fn main() -> Result<()> {
                 ],
                 &pool_config.raydium_v3_program,
             );
-            let update_amm_config_instr = update_amm_config_instr(
-                &pool_config.clone(),
-                amm_config_key,
-                remaing_accounts,
-                param,
-                update_value,
-            )?;
+            let update_amm_config_instr = if param == 3 || param == 4 {
+                // the new owner must accept the ownership to take effect
+                propose_ownership_instr(
+                    &pool_config.clone(),
+                    amm_config_key,
+                    remaining.unwrap(),
+                    param,
+                )?
+            } else {
+                // the direct update of fee rates is rejected if the admin timelock is enabled
+                let (admin_timelock_key, __bump) = Pubkey::find_program_address(
+                    &[raydium_amm_v3::states::ADMIN_TIMELOCK_SEED.as_bytes()],
+                    &pool_config.raydium_v3_program,
+                );
+                update_amm_config_instr(
+                    &pool_config.clone(),
+                    amm_config_key,
+                    vec![AccountMeta::new_readonly(admin_timelock_key, false)],
+                    param,
+                    update_value,
+                )?
+            };
             // send
             let signers = vec![&payer, &admin];
             let recent_hash = rpc_client.get_latest_blockhash()?;
             let txn = Transaction::new_signed_with_payer(
-                &update_amm_config_instr,
+                &with_compute_budget(&rpc_client, &compute_budget, &update_amm_config_instr)?,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        CommandsName::AcceptOwnership { config_index } => {
+            let (amm_config_key, __bump) = Pubkey::find_program_address(
+                &[
+                    raydium_amm_v3::states::AMM_CONFIG_SEED.as_bytes(),
+                    &config_index.to_be_bytes(),
+                ],
+                &pool_config.raydium_v3_program,
+            );
+            let accept_ownership_instr =
+                accept_ownership_instr(&pool_config.clone(), amm_config_key)?;
+            // send
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &with_compute_budget(&rpc_client, &compute_budget, &accept_ownership_instr)?,
                 Some(&payer.pubkey()),
                 &signers,
                 recent_hash,
@@ -835,7 +1477,7 @@ fn main() -> Result<()> {
             let signers = vec![&payer, &admin];
             let recent_hash = rpc_client.get_latest_blockhash()?;
             let txn = Transaction::new_signed_with_payer(
-                &create_instr,
+                &with_compute_budget(&rpc_client, &compute_budget, &create_instr)?,
                 Some(&payer.pubkey()),
                 &signers,
                 recent_hash,
@@ -849,7 +1491,35 @@ fn main() -> Result<()> {
             let signers = vec![&payer, &admin];
             let recent_hash = rpc_client.get_latest_blockhash()?;
             let txn = Transaction::new_signed_with_payer(
-                &create_instr,
+                &with_compute_budget(&rpc_client, &compute_budget, &create_instr)?,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        CommandsName::CreateWhitelistCreator { creator } => {
+            let create_instr = create_whitelist_creator_instr(&pool_config.clone(), creator)?;
+            // send
+            let signers = vec![&payer, &admin];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &with_compute_budget(&rpc_client, &compute_budget, &create_instr)?,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        CommandsName::DeleteWhitelistCreator { creator } => {
+            let delete_instr = delete_whitelist_creator_instr(&pool_config.clone(), creator)?;
+            // send
+            let signers = vec![&payer, &admin];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &with_compute_budget(&rpc_client, &compute_budget, &delete_instr)?,
                 Some(&payer.pubkey()),
                 &signers,
                 recent_hash,
@@ -895,14 +1565,41 @@ fn main() -> Result<()> {
                 tick, price, sqrt_price_x64, amm_config_key
             );
 
+            let amm_config_state = deserialize_anchor_account::<raydium_amm_v3::states::AmmConfig>(
+                &rpc_client.get_account(&amm_config_key)?,
+            )?;
+            let mut remaining_accounts = Vec::new();
+            if amm_config_state.whitelist_pool_creation {
+                let (whitelist_creator_key, __bump) = Pubkey::find_program_address(
+                    &[
+                        raydium_amm_v3::states::WHITELIST_CREATOR_SEED.as_bytes(),
+                        payer.pubkey().to_bytes().as_ref(),
+                    ],
+                    &pool_config.raydium_v3_program,
+                );
+                let (operation_account_key, __bump) = Pubkey::find_program_address(
+                    &[raydium_amm_v3::states::OPERATION_SEED.as_bytes()],
+                    &pool_config.raydium_v3_program,
+                );
+                // the operation account is used if the payer is an operation owner but not whitelisted
+                if rpc_client.get_account(&whitelist_creator_key).is_ok() {
+                    remaining_accounts
+                        .push(AccountMeta::new_readonly(whitelist_creator_key, false));
+                } else {
+                    remaining_accounts
+                        .push(AccountMeta::new_readonly(operation_account_key, false));
+                }
+            }
             let create_pool_instr = create_pool_instr(
                 &pool_config.clone(),
                 amm_config_key,
+                amm_config_state.fund_owner,
                 mint0,
                 mint1,
                 mint0_owner,
                 mint1_owner,
                 pool_config.tickarray_bitmap_extension.unwrap(),
+                remaining_accounts,
                 sqrt_price_x64,
                 open_time,
             )?;
@@ -911,7 +1608,7 @@ fn main() -> Result<()> {
             let signers = vec![&payer];
             let recent_hash = rpc_client.get_latest_blockhash()?;
             let txn = Transaction::new_signed_with_payer(
-                &create_pool_instr,
+                &with_compute_budget(&rpc_client, &compute_budget, &create_pool_instr)?,
                 Some(&payer.pubkey()),
                 &signers,
                 recent_hash,
@@ -964,7 +1661,7 @@ fn main() -> Result<()> {
             let signers = vec![&payer, &admin];
             let recent_hash = rpc_client.get_latest_blockhash()?;
             let txn = Transaction::new_signed_with_payer(
-                &create_instr,
+                &with_compute_budget(&rpc_client, &compute_budget, &create_instr)?,
                 Some(&payer.pubkey()),
                 &signers,
                 recent_hash,
@@ -1017,7 +1714,7 @@ fn main() -> Result<()> {
             let signers = vec![&payer, &admin];
             let recent_hash = rpc_client.get_latest_blockhash()?;
             let txn = Transaction::new_signed_with_payer(
-                &create_instr,
+                &with_compute_budget(&rpc_client, &compute_budget, &create_instr)?,
                 Some(&payer.pubkey()),
                 &signers,
                 recent_hash,
@@ -1048,7 +1745,11 @@ fn main() -> Result<()> {
                 let signers = vec![&payer, &admin];
                 let recent_hash = rpc_client.get_latest_blockhash()?;
                 let txn = Transaction::new_signed_with_payer(
-                    &transfer_reward_owner_instrs,
+                    &with_compute_budget(
+                        &rpc_client,
+                        &compute_budget,
+                        &transfer_reward_owner_instrs,
+                    )?,
                     Some(&payer.pubkey()),
                     &signers,
                     recent_hash,
@@ -1195,9 +1896,6 @@ fn main() -> Result<()> {
                 ));
 
                 let mut instructions = Vec::new();
-                let request_inits_instr =
-                    ComputeBudgetInstruction::set_compute_unit_limit(1400_000u32);
-                instructions.push(request_inits_instr);
                 let open_position_instr = open_position_with_token22_nft_instr(
                     &pool_config.clone(),
                     pool_config.pool_id_account.unwrap(),
@@ -1230,13 +1928,14 @@ fn main() -> Result<()> {
                 instructions.extend(open_position_instr);
                 // send
                 let signers = vec![&payer, &nft_mint];
-                let recent_hash = rpc_client.get_latest_blockhash()?;
-                let txn = Transaction::new_signed_with_payer(
+                let txn = sign_versioned_transaction(
+                    &rpc_client,
+                    &compute_budget,
+                    &lookup_tables,
                     &instructions,
-                    Some(&payer.pubkey()),
+                    &payer.pubkey(),
                     &signers,
-                    recent_hash,
-                );
+                )?;
                 let signature = send_txn(&rpc_client, &txn, true)?;
                 println!("{}", signature);
             } else {
@@ -1244,52 +1943,625 @@ fn main() -> Result<()> {
                 println!("personal position exist:{:?}", find_position);
             }
         }
-        CommandsName::IncreaseLiquidity {
-            tick_lower_price,
-            tick_upper_price,
-            is_base_0,
-            imput_amount,
-        } => {
-            // load pool to get observation
-            let pool: raydium_amm_v3::states::PoolState =
-                program.account(pool_config.pool_id_account.unwrap())?;
-
-            // load position
-            let position_nft_infos = get_all_nft_and_position_by_owner(
-                &rpc_client,
-                &payer.pubkey(),
-                &pool_config.raydium_v3_program,
-            );
-            let positions: Vec<Pubkey> = position_nft_infos
-                .iter()
-                .map(|item| item.position)
+        CommandsName::OpenPositionsBatch { spec_path, dry_run } => {
+            let spec: LadderSpec = toml::from_str(&std::fs::read_to_string(&spec_path)?)?;
+            let pool_id = pool_config.pool_id_account.unwrap();
+            let pool: raydium_amm_v3::states::PoolState = program.account(pool_id)?;
+            let mint_programs: Vec<Pubkey> = rpc_client
+                .get_multiple_accounts(&[pool.token_mint_0, pool.token_mint_1])?
+                .into_iter()
+                .map(|rsp| rsp.unwrap().owner)
                 .collect();
-            let rsps = rpc_client.get_multiple_accounts(&positions)?;
-            let mut user_positions = Vec::new();
-            for rsp in rsps {
-                match rsp {
-                    None => continue,
-                    Some(rsp) => {
-                        let position = deserialize_anchor_account::<
-                            raydium_amm_v3::states::PersonalPositionState,
-                        >(&rsp)?;
-                        user_positions.push(position);
-                    }
-                }
-            }
-
-            let tick_lower_price_x64 = price_to_sqrt_price_x64(
-                tick_lower_price,
-                pool.mint_decimals_0,
-                pool.mint_decimals_1,
+            let user_token_account_0 = get_associated_token_address_with_program_id(
+                &payer.pubkey(),
+                &pool.token_mint_0,
+                &mint_programs[0],
             );
-            let tick_upper_price_x64 = price_to_sqrt_price_x64(
-                tick_upper_price,
-                pool.mint_decimals_0,
-                pool.mint_decimals_1,
+            let user_token_account_1 = get_associated_token_address_with_program_id(
+                &payer.pubkey(),
+                &pool.token_mint_1,
+                &mint_programs[1],
             );
-            let tick_lower_index = tick_with_spacing(
-                tick_math::get_tick_at_sqrt_price(tick_lower_price_x64)?,
+
+            let mut nft_mints = Vec::new();
+            let mut groups = Vec::new();
+            let (mut total_amount_0, mut total_amount_1) = (0u64, 0u64);
+            for band in spec.bands {
+                let tick_lower_index = tick_with_spacing(
+                    tick_math::get_tick_at_sqrt_price(price_to_sqrt_price_x64(
+                        band.lower_price,
+                        pool.mint_decimals_0,
+                        pool.mint_decimals_1,
+                    ))?,
+                    pool.tick_spacing.into(),
+                );
+                let tick_upper_index = tick_with_spacing(
+                    tick_math::get_tick_at_sqrt_price(price_to_sqrt_price_x64(
+                        band.upper_price,
+                        pool.mint_decimals_0,
+                        pool.mint_decimals_1,
+                    ))?,
+                    pool.tick_spacing.into(),
+                );
+                if tick_lower_index >= tick_upper_index {
+                    return Err(format_err!(
+                        "the band {}-{} is narrower than the tick spacing",
+                        band.lower_price,
+                        band.upper_price
+                    ));
+                }
+                let liquidity = liquidity_math::get_liquidity_from_amounts(
+                    pool.sqrt_price_x64,
+                    tick_math::get_sqrt_price_at_tick(tick_lower_index)?,
+                    tick_math::get_sqrt_price_at_tick(tick_upper_index)?,
+                    band.amount_0,
+                    band.amount_1,
+                );
+                let (amount_0, amount_1) = liquidity_math::get_delta_amounts_signed(
+                    pool.tick_current,
+                    pool.sqrt_price_x64,
+                    tick_lower_index,
+                    tick_upper_index,
+                    liquidity as i128,
+                )?;
+                println!(
+                    "band:{}-{}, tick_lower_index:{}, tick_upper_index:{}, liquidity:{}, amount_0:{}, amount_1:{}",
+                    band.lower_price,
+                    band.upper_price,
+                    tick_lower_index,
+                    tick_upper_index,
+                    liquidity,
+                    amount_0,
+                    amount_1
+                );
+                total_amount_0 = total_amount_0.checked_add(amount_0).unwrap();
+                total_amount_1 = total_amount_1.checked_add(amount_1).unwrap();
+                // calc with slippage and transfer_fee
+                let amount_0_with_slippage =
+                    amount_with_slippage(amount_0, pool_config.slippage, true);
+                let amount_1_with_slippage =
+                    amount_with_slippage(amount_1, pool_config.slippage, true);
+                let transfer_fee = get_pool_mints_inverse_fee(
+                    &rpc_client,
+                    pool.token_mint_0,
+                    pool.token_mint_1,
+                    amount_0_with_slippage,
+                    amount_1_with_slippage,
+                );
+                let amount_0_max = amount_0_with_slippage
+                    .checked_add(transfer_fee.0.transfer_fee)
+                    .unwrap();
+                let amount_1_max = amount_1_with_slippage
+                    .checked_add(transfer_fee.1.transfer_fee)
+                    .unwrap();
+
+                let nft_mint = Keypair::generate(&mut OsRng);
+                groups.push(open_position_with_token22_nft_instr(
+                    &pool_config.clone(),
+                    pool_id,
+                    pool.token_vault_0,
+                    pool.token_vault_1,
+                    pool.token_mint_0,
+                    pool.token_mint_1,
+                    nft_mint.pubkey(),
+                    payer.pubkey(),
+                    user_token_account_0,
+                    user_token_account_1,
+                    vec![AccountMeta::new(
+                        pool_config.tickarray_bitmap_extension.unwrap(),
+                        false,
+                    )],
+                    liquidity,
+                    amount_0_max,
+                    amount_1_max,
+                    tick_lower_index,
+                    tick_upper_index,
+                    TickArrayState::get_array_start_index(tick_lower_index, pool.tick_spacing),
+                    TickArrayState::get_array_start_index(tick_upper_index, pool.tick_spacing),
+                    spec.with_metadata,
+                )?);
+                nft_mints.push(nft_mint);
+            }
+
+            let transactions =
+                pack_instructions_into_transactions(&payer.pubkey(), &lookup_tables, groups);
+            println!(
+                "open {} positions in {} transactions, total amount_0:{}, total amount_1:{}",
+                nft_mints.len(),
+                transactions.len(),
+                total_amount_0,
+                total_amount_1
+            );
+            if dry_run {
+                return Ok(());
+            }
+            for instructions in transactions {
+                // the payer and the nft mints of the positions opened by the transaction
+                let mut signers = vec![&payer];
+                signers.extend(nft_mints.iter().filter(|nft_mint| {
+                    instructions.iter().any(|instruction| {
+                        instruction
+                            .accounts
+                            .iter()
+                            .any(|meta| meta.is_signer && meta.pubkey == nft_mint.pubkey())
+                    })
+                }));
+                let txn = sign_versioned_transaction(
+                    &rpc_client,
+                    &compute_budget,
+                    &lookup_tables,
+                    &instructions,
+                    &payer.pubkey(),
+                    &signers,
+                )?;
+                let signature = send_txn(&rpc_client, &txn, true)?;
+                println!("{}", signature);
+            }
+        }
+        CommandsName::Rebalance {
+            nft_mint,
+            tick_lower_price,
+            tick_upper_price,
+            swap,
+            simulate,
+        } => {
+            let payer_key = payer.pubkey();
+            let nft_info = get_all_nft_and_position_by_owner(
+                &rpc_client,
+                &payer_key,
+                &pool_config.raydium_v3_program,
+            )
+            .into_iter()
+            .find(|item| item.mint == nft_mint)
+            .ok_or(format_err!("position nft {} not found", nft_mint))?;
+            let position = deserialize_anchor_account::<
+                raydium_amm_v3::states::PersonalPositionState,
+            >(&rpc_client.get_account(&nft_info.position)?)?;
+            if position.locked {
+                return Err(format_err!("position {} is locked", nft_info.position));
+            }
+            let pools = load_pools_of_positions(&rpc_client, &[position.clone()])?;
+            let pool_id = position.pool_id;
+            let pool = pools[&pool_id];
+            let pending_fees = estimate_positions_pending_fees(
+                &rpc_client,
+                &pool_config.raydium_v3_program,
+                &[position.clone()],
+                &pools,
+            )?
+            .remove(0);
+            let bitmap_extension_key = Pubkey::find_program_address(
+                &[
+                    POOL_TICK_ARRAY_BITMAP_SEED.as_bytes(),
+                    pool_id.to_bytes().as_ref(),
+                ],
+                &pool_config.raydium_v3_program,
+            )
+            .0;
+            let load_accounts = vec![
+                pool.amm_config,
+                bitmap_extension_key,
+                pool.token_mint_0,
+                pool.token_mint_1,
+            ];
+            let rsps = rpc_client.get_multiple_accounts(&load_accounts)?;
+            let epoch = rpc_client.get_epoch_info()?.epoch;
+            let [amm_config_account, tickarray_bitmap_extension_account, mint0_account, mint1_account] =
+                array_ref![rsps, 0, 4];
+            let amm_config_state = deserialize_anchor_account::<raydium_amm_v3::states::AmmConfig>(
+                amm_config_account.as_ref().unwrap(),
+            )?;
+            let tickarray_bitmap_extension = deserialize_anchor_account::<TickArrayBitmapExtension>(
+                tickarray_bitmap_extension_account.as_ref().unwrap(),
+            )?;
+            let mint0_account = mint0_account.clone().unwrap();
+            let mint1_account = mint1_account.clone().unwrap();
+            let mint0_state = StateWithExtensions::<Mint>::unpack(&mint0_account.data)?;
+            let mint1_state = StateWithExtensions::<Mint>::unpack(&mint1_account.data)?;
+            let user_token_account_0 = get_associated_token_address_with_program_id(
+                &payer_key,
+                &pool.token_mint_0,
+                &mint0_account.owner,
+            );
+            let user_token_account_1 = get_associated_token_address_with_program_id(
+                &payer_key,
+                &pool.token_mint_1,
+                &mint1_account.owner,
+            );
+
+            // withdraw the liquidity, the fees and the rewards, then close the position
+            let (amount_0, amount_1) = liquidity_math::get_delta_amounts_signed(
+                pool.tick_current,
+                pool.sqrt_price_x64,
+                position.tick_lower_index,
+                position.tick_upper_index,
+                -(position.liquidity as i128),
+            )?;
+            let amount_0_with_slippage =
+                amount_with_slippage(amount_0, pool_config.slippage, false);
+            let amount_1_with_slippage =
+                amount_with_slippage(amount_1, pool_config.slippage, false);
+            let transfer_fee = get_pool_mints_transfer_fee(
+                &rpc_client,
+                pool.token_mint_0,
+                pool.token_mint_1,
+                amount_0_with_slippage,
+                amount_1_with_slippage,
+            );
+            let amount_0_min = amount_0_with_slippage
+                .checked_sub(transfer_fee.0.transfer_fee)
+                .unwrap();
+            let amount_1_min = amount_1_with_slippage
+                .checked_sub(transfer_fee.1.transfer_fee)
+                .unwrap();
+            let withdraw_amount_0 = amount_0 + pending_fees.fees_0;
+            let withdraw_amount_1 = amount_1 + pending_fees.fees_1;
+            let mut available_0 =
+                withdraw_amount_0 - get_transfer_fee(&mint0_state, epoch, withdraw_amount_0);
+            let mut available_1 =
+                withdraw_amount_1 - get_transfer_fee(&mint1_state, epoch, withdraw_amount_1);
+
+            let mut remaining_accounts = vec![AccountMeta::new(bitmap_extension_key, false)];
+            let mut reward_mints = Vec::new();
+            for reward_info in pool.reward_infos {
+                if reward_info.token_mint == Pubkey::default() {
+                    continue;
+                }
+                let reward_mint_program = rpc_client.get_account(&reward_info.token_mint)?.owner;
+                let user_reward_account = get_associated_token_address_with_program_id(
+                    &payer_key,
+                    &reward_info.token_mint,
+                    &reward_mint_program,
+                );
+                remaining_accounts.push(AccountMeta::new(reward_info.token_vault, false));
+                remaining_accounts.push(AccountMeta::new(user_reward_account, false));
+                remaining_accounts.push(AccountMeta::new(reward_info.token_mint, false));
+                reward_mints.push((reward_info.token_mint, reward_mint_program));
+            }
+            let mut withdraw_instructions = Vec::new();
+            for (mint, mint_program) in [
+                (pool.token_mint_0, mint0_account.owner),
+                (pool.token_mint_1, mint1_account.owner),
+            ]
+            .into_iter()
+            .chain(reward_mints)
+            {
+                withdraw_instructions.push(
+                    spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                        &payer_key,
+                        &payer_key,
+                        &mint,
+                        &mint_program,
+                    ),
+                );
+            }
+            withdraw_instructions.extend(decrease_liquidity_instr(
+                &pool_config.clone(),
+                pool_id,
+                pool.token_vault_0,
+                pool.token_vault_1,
+                pool.token_mint_0,
+                pool.token_mint_1,
+                nft_mint,
+                nft_info.key,
+                user_token_account_0,
+                user_token_account_1,
+                remaining_accounts,
+                position.liquidity,
+                amount_0_min,
+                amount_1_min,
+                position.tick_lower_index,
+                position.tick_upper_index,
+                TickArrayState::get_array_start_index(position.tick_lower_index, pool.tick_spacing),
+                TickArrayState::get_array_start_index(position.tick_upper_index, pool.tick_spacing),
+            )?);
+            withdraw_instructions.extend(close_personal_position_instr(
+                &pool_config.clone(),
+                nft_mint,
+                nft_info.key,
+                nft_info.program,
+                position.has_reward_extension,
+            )?);
+            let mut groups = vec![withdraw_instructions];
+
+            // the new range
+            let tick_lower_index = tick_with_spacing(
+                tick_math::get_tick_at_sqrt_price(price_to_sqrt_price_x64(
+                    tick_lower_price,
+                    pool.mint_decimals_0,
+                    pool.mint_decimals_1,
+                ))?,
+                pool.tick_spacing.into(),
+            );
+            let tick_upper_index = tick_with_spacing(
+                tick_math::get_tick_at_sqrt_price(price_to_sqrt_price_x64(
+                    tick_upper_price,
+                    pool.mint_decimals_0,
+                    pool.mint_decimals_1,
+                ))?,
+                pool.tick_spacing.into(),
+            );
+            if tick_lower_index >= tick_upper_index {
+                return Err(format_err!(
+                    "the new range is narrower than the tick spacing"
+                ));
+            }
+            let sqrt_price_lower_x64 = tick_math::get_sqrt_price_at_tick(tick_lower_index)?;
+            let sqrt_price_upper_x64 = tick_math::get_sqrt_price_at_tick(tick_upper_index)?;
+
+            // swap the imbalance so the amounts match the ratio of the new range at the current
+            // price, the ratio is token_1 per token_0 of a unit of liquidity
+            let (mut sqrt_price_x64, mut tick_current) = (pool.sqrt_price_x64, pool.tick_current);
+            let mut swap_summary = None;
+            if swap {
+                let price = sqrt_price_x64_to_price(pool.sqrt_price_x64, 0, 0);
+                let to_f64 =
+                    |sqrt_price_x64: u128| sqrt_price_x64 as f64 / fixed_point_64::Q64 as f64;
+                let value_1 = available_0 as f64 * price + available_1 as f64;
+                let target_0 = if pool.sqrt_price_x64 <= sqrt_price_lower_x64 {
+                    value_1 / price
+                } else if pool.sqrt_price_x64 >= sqrt_price_upper_x64 {
+                    0.0
+                } else {
+                    let ratio = (to_f64(pool.sqrt_price_x64) - to_f64(sqrt_price_lower_x64))
+                        / (1.0 / to_f64(pool.sqrt_price_x64) - 1.0 / to_f64(sqrt_price_upper_x64));
+                    value_1 / (price + ratio)
+                };
+                let (zero_for_one, swap_amount) = if (available_0 as f64) > target_0 {
+                    (true, (available_0 as f64 - target_0) as u64)
+                } else {
+                    (
+                        false,
+                        ((target_0 - available_0 as f64) * price).min(available_1 as f64) as u64,
+                    )
+                };
+                if swap_amount > 0 {
+                    let (input_mint_state, output_mint_state) = if zero_for_one {
+                        (&mint0_state, &mint1_state)
+                    } else {
+                        (&mint1_state, &mint0_state)
+                    };
+                    let amount_specified =
+                        swap_amount - get_transfer_fee(input_mint_state, epoch, swap_amount);
+                    let mut tick_arrays = load_pool_cur_and_next_five_tick_array(
+                        &rpc_client,
+                        &pool_config.raydium_v3_program,
+                        &pool_id,
+                        &pool,
+                        &tickarray_bitmap_extension,
+                        zero_for_one,
+                    );
+                    let (state, tick_array_indexs) = utils::quote_swap(
+                        amount_specified,
+                        None,
+                        zero_for_one,
+                        true,
+                        &amm_config_state,
+                        &pool,
+                        &tickarray_bitmap_extension,
+                        &mut tick_arrays,
+                    )
+                    .map_err(|e| format_err!(e))?;
+                    let amount_out = state.amount_calculated
+                        - get_transfer_fee(output_mint_state, epoch, state.amount_calculated);
+                    if zero_for_one {
+                        available_0 -= swap_amount;
+                        available_1 += amount_out;
+                    } else {
+                        available_1 -= swap_amount;
+                        available_0 += amount_out;
+                    }
+                    (sqrt_price_x64, tick_current) = (state.sqrt_price_x64, state.tick);
+
+                    let mut remaining_accounts =
+                        vec![AccountMeta::new_readonly(bitmap_extension_key, false)];
+                    for index in tick_array_indexs {
+                        remaining_accounts.push(AccountMeta::new(
+                            Pubkey::find_program_address(
+                                &[
+                                    raydium_amm_v3::states::TICK_ARRAY_SEED.as_bytes(),
+                                    pool_id.to_bytes().as_ref(),
+                                    &index.to_be_bytes(),
+                                ],
+                                &pool_config.raydium_v3_program,
+                            )
+                            .0,
+                            false,
+                        ));
+                    }
+                    let (user_input_token, user_output_token) = if zero_for_one {
+                        (user_token_account_0, user_token_account_1)
+                    } else {
+                        (user_token_account_1, user_token_account_0)
+                    };
+                    let (input_vault, output_vault) = if zero_for_one {
+                        (pool.token_vault_0, pool.token_vault_1)
+                    } else {
+                        (pool.token_vault_1, pool.token_vault_0)
+                    };
+                    let (input_mint, output_mint) = if zero_for_one {
+                        (pool.token_mint_0, pool.token_mint_1)
+                    } else {
+                        (pool.token_mint_1, pool.token_mint_0)
+                    };
+                    groups.push(swap_v2_instr(
+                        &pool_config.clone(),
+                        pool.amm_config,
+                        pool_id,
+                        input_vault,
+                        output_vault,
+                        pool.observation_key,
+                        user_input_token,
+                        user_output_token,
+                        input_mint,
+                        output_mint,
+                        remaining_accounts,
+                        swap_amount,
+                        amount_with_slippage(state.amount_calculated, pool_config.slippage, false),
+                        None,
+                        true,
+                        None,
+                        None,
+                    )?);
+                    swap_summary = Some((zero_for_one, swap_amount, amount_out));
+                }
+            }
+
+            // reopen with the most liquidity the available amounts provide after the slippage
+            let budget_0 = amount_with_slippage(available_0, pool_config.slippage, false);
+            let budget_1 = amount_with_slippage(available_1, pool_config.slippage, false);
+            let transfer_fee = get_pool_mints_transfer_fee(
+                &rpc_client,
+                pool.token_mint_0,
+                pool.token_mint_1,
+                budget_0,
+                budget_1,
+            );
+            let liquidity = liquidity_math::get_liquidity_from_amounts(
+                sqrt_price_x64,
+                sqrt_price_lower_x64,
+                sqrt_price_upper_x64,
+                budget_0 - transfer_fee.0.transfer_fee,
+                budget_1 - transfer_fee.1.transfer_fee,
+            );
+            let (deposit_amount_0, deposit_amount_1) = liquidity_math::get_delta_amounts_signed(
+                tick_current,
+                sqrt_price_x64,
+                tick_lower_index,
+                tick_upper_index,
+                liquidity as i128,
+            )?;
+            let new_nft_mint = Keypair::generate(&mut OsRng);
+            groups.push(open_position_with_token22_nft_instr(
+                &pool_config.clone(),
+                pool_id,
+                pool.token_vault_0,
+                pool.token_vault_1,
+                pool.token_mint_0,
+                pool.token_mint_1,
+                new_nft_mint.pubkey(),
+                payer_key,
+                user_token_account_0,
+                user_token_account_1,
+                vec![AccountMeta::new(bitmap_extension_key, false)],
+                liquidity,
+                budget_0,
+                budget_1,
+                tick_lower_index,
+                tick_upper_index,
+                TickArrayState::get_array_start_index(tick_lower_index, pool.tick_spacing),
+                TickArrayState::get_array_start_index(tick_upper_index, pool.tick_spacing),
+                false,
+            )?);
+
+            println!(
+                "before: position:{}, tick_lower_index:{}, tick_upper_index:{}, liquidity:{}, amount_0:{}, amount_1:{}, fees_0:{}, fees_1:{}",
+                nft_info.position,
+                position.tick_lower_index,
+                position.tick_upper_index,
+                position.liquidity,
+                amount_0,
+                amount_1,
+                pending_fees.fees_0,
+                pending_fees.fees_1
+            );
+            if let Some((zero_for_one, amount_in, amount_out)) = swap_summary {
+                println!(
+                    "swap: zero_for_one:{}, amount_in:{}, amount_out:{}",
+                    zero_for_one, amount_in, amount_out
+                );
+            }
+            println!(
+                "after: nft_mint:{}, tick_lower_index:{}, tick_upper_index:{}, liquidity:{}, amount_0:{}, amount_1:{}",
+                new_nft_mint.pubkey(),
+                tick_lower_index,
+                tick_upper_index,
+                liquidity,
+                deposit_amount_0,
+                deposit_amount_1
+            );
+
+            // the transactions run in order, each one simulated against the current state
+            for instructions in
+                pack_instructions_into_transactions(&payer_key, &lookup_tables, groups)
+            {
+                let mut signers = vec![&payer];
+                if instructions.iter().any(|instruction| {
+                    instruction
+                        .accounts
+                        .iter()
+                        .any(|meta| meta.is_signer && meta.pubkey == new_nft_mint.pubkey())
+                }) {
+                    signers.push(&new_nft_mint);
+                }
+                let txn = sign_versioned_transaction(
+                    &rpc_client,
+                    &compute_budget,
+                    &lookup_tables,
+                    &instructions,
+                    &payer_key,
+                    &signers,
+                )?;
+                if simulate {
+                    let ret = simulate_transaction(
+                        &rpc_client,
+                        &txn,
+                        true,
+                        CommitmentConfig::confirmed(),
+                    )?;
+                    println!("{:#?}", ret);
+                } else {
+                    let signature = send_txn(&rpc_client, &txn, true)?;
+                    println!("{}", signature);
+                }
+            }
+        }
+        CommandsName::IncreaseLiquidity {
+            tick_lower_price,
+            tick_upper_price,
+            is_base_0,
+            imput_amount,
+        } => {
+            // load pool to get observation
+            let pool: raydium_amm_v3::states::PoolState =
+                program.account(pool_config.pool_id_account.unwrap())?;
+
+            // load position
+            let position_nft_infos = get_all_nft_and_position_by_owner(
+                &rpc_client,
+                &payer.pubkey(),
+                &pool_config.raydium_v3_program,
+            );
+            let positions: Vec<Pubkey> = position_nft_infos
+                .iter()
+                .map(|item| item.position)
+                .collect();
+            let rsps = rpc_client.get_multiple_accounts(&positions)?;
+            let mut user_positions = Vec::new();
+            for rsp in rsps {
+                match rsp {
+                    None => continue,
+                    Some(rsp) => {
+                        let position = deserialize_anchor_account::<
+                            raydium_amm_v3::states::PersonalPositionState,
+                        >(&rsp)?;
+                        user_positions.push(position);
+                    }
+                }
+            }
+
+            let tick_lower_price_x64 = price_to_sqrt_price_x64(
+                tick_lower_price,
+                pool.mint_decimals_0,
+                pool.mint_decimals_1,
+            );
+            let tick_upper_price_x64 = price_to_sqrt_price_x64(
+                tick_upper_price,
+                pool.mint_decimals_0,
+                pool.mint_decimals_1,
+            );
+            let tick_lower_index = tick_with_spacing(
+                tick_math::get_tick_at_sqrt_price(tick_lower_price_x64)?,
                 pool.tick_spacing.into(),
             );
             let tick_upper_index = tick_with_spacing(
@@ -1415,13 +2687,14 @@ fn main() -> Result<()> {
                 )?;
                 // send
                 let signers = vec![&payer];
-                let recent_hash = rpc_client.get_latest_blockhash()?;
-                let txn = Transaction::new_signed_with_payer(
+                let txn = sign_versioned_transaction(
+                    &rpc_client,
+                    &compute_budget,
+                    &lookup_tables,
                     &increase_instr,
-                    Some(&payer.pubkey()),
+                    &payer.pubkey(),
                     &signers,
-                    recent_hash,
-                );
+                )?;
                 let signature = send_txn(&rpc_client, &txn, true)?;
                 println!("{}", signature);
             } else {
@@ -1434,6 +2707,7 @@ fn main() -> Result<()> {
             tick_upper_index,
             liquidity,
             simulate,
+            reward_recipient,
         } => {
             // load pool to get observation
             let pool: raydium_amm_v3::states::PoolState =
@@ -1489,14 +2763,30 @@ fn main() -> Result<()> {
                     .iter()
                     .find(|&nft_info| nft_info.mint == find_position.nft_mint)
                     .unwrap();
+                // the rewards can be sent to any token account of the reward mint
+                let mut reward_recipient_mints = Vec::new();
+                for (key, rsp) in reward_recipient
+                    .iter()
+                    .zip(rpc_client.get_multiple_accounts(&reward_recipient)?)
+                {
+                    let rsp = rsp.ok_or(format_err!("reward recipient {} not found", key))?;
+                    let token_account = StateWithExtensions::<Account>::unpack(&rsp.data)?;
+                    reward_recipient_mints.push((token_account.base.mint, *key));
+                }
                 let mut reward_vault_with_user_vault: Vec<Pubkey> = Vec::new();
                 for item in pool.reward_infos.into_iter() {
                     if item.token_mint != Pubkey::default() {
                         reward_vault_with_user_vault.push(item.token_vault);
-                        reward_vault_with_user_vault.push(get_associated_token_address(
-                            &payer.pubkey(),
-                            &item.token_mint,
-                        ));
+                        reward_vault_with_user_vault.push(
+                            reward_recipient_mints
+                                .iter()
+                                .find(|(mint, _)| *mint == item.token_mint)
+                                .map(|(_, key)| *key)
+                                .unwrap_or(get_associated_token_address(
+                                    &payer.pubkey(),
+                                    &item.token_mint,
+                                )),
+                        );
                         reward_vault_with_user_vault.push(item.token_mint);
                     }
                 }
@@ -1576,18 +2866,20 @@ fn main() -> Result<()> {
                         find_position.nft_mint,
                         user_nft_token_info.key,
                         user_nft_token_info.program,
+                        find_position.has_reward_extension,
                     )?;
                     decrease_instr.extend(close_position_instr);
                 }
                 // send
                 let signers = vec![&payer];
-                let recent_hash = rpc_client.get_latest_blockhash()?;
-                let txn = Transaction::new_signed_with_payer(
+                let txn = sign_versioned_transaction(
+                    &rpc_client,
+                    &compute_budget,
+                    &lookup_tables,
                     &decrease_instr,
-                    Some(&payer.pubkey()),
+                    &payer.pubkey(),
                     &signers,
-                    recent_hash,
-                );
+                )?;
                 if simulate {
                     let ret = simulate_transaction(
                         &rpc_client,
@@ -1720,8 +3012,6 @@ fn main() -> Result<()> {
                 .collect();
             remaining_accounts.append(&mut accounts);
             let mut instructions = Vec::new();
-            let request_inits_instr = ComputeBudgetInstruction::set_compute_unit_limit(1400_000u32);
-            instructions.push(request_inits_instr);
             let swap_instr = swap_instr(
                 &pool_config.clone(),
                 pool_state.amm_config,
@@ -1750,13 +3040,14 @@ fn main() -> Result<()> {
             instructions.extend(swap_instr);
             // send
             let signers = vec![&payer];
-            let recent_hash = rpc_client.get_latest_blockhash()?;
-            let txn = Transaction::new_signed_with_payer(
+            let txn = sign_versioned_transaction(
+                &rpc_client,
+                &compute_budget,
+                &lookup_tables,
                 &instructions,
-                Some(&payer.pubkey()),
+                &payer.pubkey(),
                 &signers,
-                recent_hash,
-            );
+            )?;
             if simulate {
                 let ret =
                     simulate_transaction(&rpc_client, &txn, true, CommitmentConfig::confirmed())?;
@@ -1766,11 +3057,148 @@ fn main() -> Result<()> {
                 println!("{}", signature);
             }
         }
+        CommandsName::Quote {
+            pool_id,
+            input_mint,
+            base_in,
+            amount,
+            limit_price,
+        } => {
+            let pool_id = pool_id.unwrap_or_else(|| pool_config.pool_id_account.unwrap());
+            let pool_state =
+                deserialize_anchor_account::<PoolState>(&rpc_client.get_account(&pool_id)?)?;
+            let zero_for_one = if input_mint == pool_state.token_mint_0 {
+                true
+            } else if input_mint == pool_state.token_mint_1 {
+                false
+            } else {
+                return Err(format_err!("input mint is not a mint of the pool"));
+            };
+            let bitmap_extension_key = Pubkey::find_program_address(
+                &[
+                    POOL_TICK_ARRAY_BITMAP_SEED.as_bytes(),
+                    pool_id.to_bytes().as_ref(),
+                ],
+                &pool_config.raydium_v3_program,
+            )
+            .0;
+            let load_accounts = vec![
+                pool_state.amm_config,
+                bitmap_extension_key,
+                pool_state.token_mint_0,
+                pool_state.token_mint_1,
+            ];
+            let rsps = rpc_client.get_multiple_accounts(&load_accounts)?;
+            let epoch = rpc_client.get_epoch_info()?.epoch;
+            let [amm_config_account, tickarray_bitmap_extension_account, mint0_account, mint1_account] =
+                array_ref![rsps, 0, 4];
+            let amm_config_state = deserialize_anchor_account::<raydium_amm_v3::states::AmmConfig>(
+                amm_config_account.as_ref().unwrap(),
+            )?;
+            let tickarray_bitmap_extension = deserialize_anchor_account::<TickArrayBitmapExtension>(
+                tickarray_bitmap_extension_account.as_ref().unwrap(),
+            )?;
+            let input_mint_data = if zero_for_one {
+                mint0_account.clone().unwrap().data
+            } else {
+                mint1_account.clone().unwrap().data
+            };
+            let input_mint_state = StateWithExtensions::<Mint>::unpack(&input_mint_data)?;
+
+            let transfer_fee = if base_in {
+                get_transfer_fee(&input_mint_state, epoch, amount)
+            } else {
+                0
+            };
+            let amount_specified = amount.checked_sub(transfer_fee).unwrap();
+            let mut tick_arrays = load_pool_cur_and_next_five_tick_array(
+                &rpc_client,
+                &pool_config.raydium_v3_program,
+                &pool_id,
+                &pool_state,
+                &tickarray_bitmap_extension,
+                zero_for_one,
+            );
+            let sqrt_price_limit_x64 = limit_price.map(|limit_price| {
+                price_to_sqrt_price_x64(
+                    limit_price,
+                    pool_state.mint_decimals_0,
+                    pool_state.mint_decimals_1,
+                )
+            });
+            let (state, tick_array_indexs) = utils::quote_swap(
+                amount_specified,
+                sqrt_price_limit_x64,
+                zero_for_one,
+                base_in,
+                &amm_config_state,
+                &pool_state,
+                &tickarray_bitmap_extension,
+                &mut tick_arrays,
+            )
+            .map_err(|e| format_err!(e))?;
+            let (amount_in, amount_out) = if base_in {
+                (
+                    amount_specified - state.amount_specified_remaining,
+                    state.amount_calculated,
+                )
+            } else {
+                (
+                    state.amount_calculated,
+                    amount_specified - state.amount_specified_remaining,
+                )
+            };
+
+            // the execution price without the fee against the spot price, in output per input
+            let spot_price = sqrt_price_x64_to_price(pool_state.sqrt_price_x64, 0, 0);
+            let spot_price = if zero_for_one {
+                spot_price
+            } else {
+                1.0 / spot_price
+            };
+            let execution_price = amount_out as f64 / (amount_in - state.fee_amount) as f64;
+            let price_impact = (1.0 - execution_price / spot_price) * 100.0;
+            println!(
+                "amount_in:{}, amount_out:{}, fee:{}, transfer_fee:{}",
+                amount_in, amount_out, state.fee_amount, transfer_fee
+            );
+            println!(
+                "price:{}, price_after:{}, price_impact:{:.4}%",
+                sqrt_price_x64_to_price(
+                    pool_state.sqrt_price_x64,
+                    pool_state.mint_decimals_0,
+                    pool_state.mint_decimals_1
+                ),
+                sqrt_price_x64_to_price(
+                    state.sqrt_price_x64,
+                    pool_state.mint_decimals_0,
+                    pool_state.mint_decimals_1
+                ),
+                price_impact
+            );
+            for tick_array_index in tick_array_indexs {
+                let tick_array_key = Pubkey::find_program_address(
+                    &[
+                        raydium_amm_v3::states::TICK_ARRAY_SEED.as_bytes(),
+                        pool_id.to_bytes().as_ref(),
+                        &tick_array_index.to_be_bytes(),
+                    ],
+                    &pool_config.raydium_v3_program,
+                )
+                .0;
+                println!(
+                    "tick_array:{}, start_index:{}",
+                    tick_array_key, tick_array_index
+                );
+            }
+        }
         CommandsName::SwapV2 {
             input_token,
             output_token,
             base_in,
             simulate,
+            max_price_impact_bps,
+            slippage_bps,
             amount,
             limit_price,
         } => {
@@ -1870,86 +3298,869 @@ fn main() -> Result<()> {
                 } else {
                     get_transfer_inverse_fee(&mint1_state, epoch, other_amount_threshold)
                 };
-                other_amount_threshold += transfer_fee;
+                other_amount_threshold += transfer_fee;
+            }
+
+            let mut remaining_accounts = Vec::new();
+            remaining_accounts.push(AccountMeta::new_readonly(
+                pool_config.tickarray_bitmap_extension.unwrap(),
+                false,
+            ));
+            let mut accounts = tick_array_indexs
+                .into_iter()
+                .map(|index| {
+                    AccountMeta::new(
+                        Pubkey::find_program_address(
+                            &[
+                                raydium_amm_v3::states::TICK_ARRAY_SEED.as_bytes(),
+                                pool_config.pool_id_account.unwrap().to_bytes().as_ref(),
+                                &index.to_be_bytes(),
+                            ],
+                            &pool_config.raydium_v3_program,
+                        )
+                        .0,
+                        false,
+                    )
+                })
+                .collect();
+            remaining_accounts.append(&mut accounts);
+            let mut instructions = Vec::new();
+            let swap_instr = swap_v2_instr(
+                &pool_config.clone(),
+                pool_state.amm_config,
+                pool_config.pool_id_account.unwrap(),
+                if zero_for_one {
+                    pool_state.token_vault_0
+                } else {
+                    pool_state.token_vault_1
+                },
+                if zero_for_one {
+                    pool_state.token_vault_1
+                } else {
+                    pool_state.token_vault_0
+                },
+                pool_state.observation_key,
+                input_token,
+                output_token,
+                if zero_for_one {
+                    pool_state.token_mint_0
+                } else {
+                    pool_state.token_mint_1
+                },
+                if zero_for_one {
+                    pool_state.token_mint_1
+                } else {
+                    pool_state.token_mint_0
+                },
+                remaining_accounts,
+                amount,
+                other_amount_threshold,
+                sqrt_price_limit_x64,
+                base_in,
+                max_price_impact_bps,
+                // the slippage is quoted at the pool price the swap was quoted at
+                slippage_bps.map(|slippage_bps| raydium_amm_v3::instructions::SwapSlippage {
+                    reference_sqrt_price_x64: pool_state.sqrt_price_x64,
+                    slippage_bps,
+                }),
+            )
+            .unwrap();
+            instructions.extend(swap_instr);
+            // send
+            let signers = vec![&payer];
+            let txn = sign_versioned_transaction(
+                &rpc_client,
+                &compute_budget,
+                &lookup_tables,
+                &instructions,
+                &payer.pubkey(),
+                &signers,
+            )?;
+            if simulate {
+                let ret =
+                    simulate_transaction(&rpc_client, &txn, true, CommitmentConfig::confirmed())?;
+                println!("{:#?}", ret);
+            } else {
+                let signature = send_txn(&rpc_client, &txn, true)?;
+                println!("{}", signature);
+            }
+        }
+        CommandsName::SwapRouterBaseIn {
+            input_mint,
+            output_mint,
+            max_hops,
+            simulate,
+            amount,
+        } => {
+            // load all the pools of the program
+            let pool_accounts = rpc_client.get_program_accounts_with_config(
+                &pool_config.raydium_v3_program,
+                RpcProgramAccountsConfig {
+                    filters: Some(vec![
+                        RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                            0,
+                            &PoolState::discriminator(),
+                        )),
+                        RpcFilterType::DataSize(PoolState::LEN as u64),
+                    ]),
+                    account_config: RpcAccountInfoConfig {
+                        encoding: Some(UiAccountEncoding::Base64Zstd),
+                        ..RpcAccountInfoConfig::default()
+                    },
+                    with_context: Some(false),
+                },
+            )?;
+            let mut pools = Vec::new();
+            for (pool_id, pool_account) in pool_accounts {
+                pools.push((
+                    pool_id,
+                    deserialize_anchor_account::<PoolState>(&pool_account)?,
+                ));
+            }
+            let mut routes = find_swap_routes(&pools, input_mint, output_mint, max_hops);
+            routes.truncate(MAX_QUOTED_SWAP_ROUTES);
+            if routes.is_empty() {
+                panic!("no route from {} to {}", input_mint, output_mint);
+            }
+
+            // load the configs, bitmap extensions and mints of the pools of the routes
+            let mut route_pools: Vec<usize> = routes.iter().flatten().map(|hop| hop.0).collect();
+            route_pools.sort();
+            route_pools.dedup();
+            let mut amm_config_keys: Vec<Pubkey> = route_pools
+                .iter()
+                .map(|index| pools[*index].1.amm_config)
+                .collect();
+            amm_config_keys.sort();
+            amm_config_keys.dedup();
+            let mut amm_configs = HashMap::new();
+            for (key, rsp) in amm_config_keys
+                .iter()
+                .zip(rpc_client.get_multiple_accounts(&amm_config_keys)?)
+            {
+                amm_configs.insert(
+                    *key,
+                    deserialize_anchor_account::<raydium_amm_v3::states::AmmConfig>(&rsp.unwrap())?,
+                );
+            }
+            let tickarray_bitmap_extension_keys: Vec<Pubkey> = route_pools
+                .iter()
+                .map(|index| {
+                    Pubkey::find_program_address(
+                        &[
+                            POOL_TICK_ARRAY_BITMAP_SEED.as_bytes(),
+                            pools[*index].0.to_bytes().as_ref(),
+                        ],
+                        &pool_config.raydium_v3_program,
+                    )
+                    .0
+                })
+                .collect();
+            let mut tickarray_bitmap_extensions = HashMap::new();
+            for (index, rsp) in route_pools
+                .iter()
+                .zip(rpc_client.get_multiple_accounts(&tickarray_bitmap_extension_keys)?)
+            {
+                if let Some(rsp) = rsp {
+                    tickarray_bitmap_extensions.insert(
+                        pools[*index].0,
+                        deserialize_anchor_account::<TickArrayBitmapExtension>(&rsp)?,
+                    );
+                }
+            }
+            let mut mint_keys: Vec<Pubkey> = route_pools
+                .iter()
+                .flat_map(|index| [pools[*index].1.token_mint_0, pools[*index].1.token_mint_1])
+                .collect();
+            mint_keys.sort();
+            mint_keys.dedup();
+            let mut mints = HashMap::new();
+            for (key, rsp) in mint_keys
+                .iter()
+                .zip(rpc_client.get_multiple_accounts(&mint_keys)?)
+            {
+                mints.insert(*key, rsp.unwrap());
+            }
+            let epoch = rpc_client.get_epoch_info().unwrap().epoch;
+
+            // quote the routes and take the one with the most output
+            let mut tick_arrays_cache = HashMap::new();
+            let (route, amount_out, hops_tick_array_indexs) = routes
+                .iter()
+                .filter_map(|route| {
+                    quote_swap_route(
+                        &rpc_client,
+                        &pool_config,
+                        &pools,
+                        route,
+                        &amm_configs,
+                        &tickarray_bitmap_extensions,
+                        &mints,
+                        &mut tick_arrays_cache,
+                        epoch,
+                        amount,
+                    )
+                    .map(|(amount_out, hops_tick_array_indexs)| {
+                        (route, amount_out, hops_tick_array_indexs)
+                    })
+                })
+                .max_by_key(|quote| quote.1)
+                .expect("no route can be quoted");
+            println!(
+                "route:{:?}, amount:{}, amount_out:{}",
+                route
+                    .iter()
+                    .map(|(index, _)| pools[*index].0)
+                    .collect::<Vec<Pubkey>>(),
+                amount,
+                amount_out
+            );
+            let amount_out_minimum = amount_with_slippage(amount_out, pool_config.slippage, false);
+
+            // the accounts of each hop, the output token account of a hop is the input of the next
+            let payer_key = payer.pubkey();
+            let mut instructions = Vec::new();
+            let mut remaining_accounts = Vec::new();
+            for (&(index, zero_for_one), tick_array_indexs) in
+                route.iter().zip(hops_tick_array_indexs)
+            {
+                let (pool_id, pool_state) = &pools[index];
+                let (input_vault, output_vault, hop_output_mint) = if zero_for_one {
+                    (
+                        pool_state.token_vault_0,
+                        pool_state.token_vault_1,
+                        pool_state.token_mint_1,
+                    )
+                } else {
+                    (
+                        pool_state.token_vault_1,
+                        pool_state.token_vault_0,
+                        pool_state.token_mint_0,
+                    )
+                };
+                let output_token_program = mints[&hop_output_mint].owner;
+                instructions.push(
+                    spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                        &payer_key,
+                        &payer_key,
+                        &hop_output_mint,
+                        &output_token_program,
+                    ),
+                );
+                remaining_accounts.extend([
+                    AccountMeta::new_readonly(pool_state.amm_config, false),
+                    AccountMeta::new(*pool_id, false),
+                    AccountMeta::new(
+                        get_associated_token_address_with_program_id(
+                            &payer_key,
+                            &hop_output_mint,
+                            &output_token_program,
+                        ),
+                        false,
+                    ),
+                    AccountMeta::new(input_vault, false),
+                    AccountMeta::new(output_vault, false),
+                    AccountMeta::new_readonly(hop_output_mint, false),
+                    AccountMeta::new(pool_state.observation_key, false),
+                    AccountMeta::new_readonly(
+                        Pubkey::find_program_address(
+                            &[
+                                POOL_TICK_ARRAY_BITMAP_SEED.as_bytes(),
+                                pool_id.to_bytes().as_ref(),
+                            ],
+                            &pool_config.raydium_v3_program,
+                        )
+                        .0,
+                        false,
+                    ),
+                ]);
+                if pool_state.extension_reward_count > 0 {
+                    remaining_accounts.push(AccountMeta::new(
+                        Pubkey::find_program_address(
+                            &[
+                                raydium_amm_v3::states::REWARD_INFO_EXTENSION_SEED.as_bytes(),
+                                pool_id.to_bytes().as_ref(),
+                            ],
+                            &pool_config.raydium_v3_program,
+                        )
+                        .0,
+                        false,
+                    ));
+                }
+                for index in tick_array_indexs {
+                    remaining_accounts.push(AccountMeta::new(
+                        Pubkey::find_program_address(
+                            &[
+                                raydium_amm_v3::states::TICK_ARRAY_SEED.as_bytes(),
+                                pool_id.to_bytes().as_ref(),
+                                &index.to_be_bytes(),
+                            ],
+                            &pool_config.raydium_v3_program,
+                        )
+                        .0,
+                        false,
+                    ));
+                }
+            }
+            let swap_instr = swap_router_base_in_instr(
+                &pool_config.clone(),
+                get_associated_token_address_with_program_id(
+                    &payer_key,
+                    &input_mint,
+                    &mints[&input_mint].owner,
+                ),
+                input_mint,
+                remaining_accounts,
+                amount,
+                amount_out_minimum,
+            )?;
+            instructions.extend(swap_instr);
+            // send
+            let signers = vec![&payer];
+            let txn = sign_versioned_transaction(
+                &rpc_client,
+                &compute_budget,
+                &lookup_tables,
+                &instructions,
+                &payer.pubkey(),
+                &signers,
+            )?;
+            if simulate {
+                let ret =
+                    simulate_transaction(&rpc_client, &txn, true, CommitmentConfig::confirmed())?;
+                println!("{:#?}", ret);
+            } else {
+                let signature = send_txn(&rpc_client, &txn, true)?;
+                println!("{}", signature);
+            }
+        }
+        CommandsName::HarvestAll { simulate } => {
+            // load the positions of the payer, the locked positions can not be decreased
+            // and the empty positions have nothing to harvest
+            let position_nft_infos = get_all_nft_and_position_by_owner(
+                &rpc_client,
+                &payer.pubkey(),
+                &pool_config.raydium_v3_program,
+            );
+            let position_keys: Vec<Pubkey> = position_nft_infos
+                .iter()
+                .map(|item| item.position)
+                .collect();
+            let mut positions = Vec::new();
+            for (nft_info, rsp) in position_nft_infos
+                .iter()
+                .zip(rpc_client.get_multiple_accounts(&position_keys)?)
+            {
+                if let Some(rsp) = rsp {
+                    let position = deserialize_anchor_account::<
+                        raydium_amm_v3::states::PersonalPositionState,
+                    >(&rsp)?;
+                    if !position.locked && position.liquidity > 0 {
+                        positions.push((nft_info, position));
+                    }
+                }
+            }
+            if positions.is_empty() {
+                println!("no position to harvest");
+                return Ok(());
+            }
+
+            // load the pools, their mints and the pending fees of the positions
+            let position_states: Vec<raydium_amm_v3::states::PersonalPositionState> =
+                positions.iter().map(|(_, p)| p.clone()).collect();
+            let pools = load_pools_of_positions(&rpc_client, &position_states)?;
+            let pending_fees = estimate_positions_pending_fees(
+                &rpc_client,
+                &pool_config.raydium_v3_program,
+                &position_states,
+                &pools,
+            )?;
+            let mut mint_keys = Vec::new();
+            for pool in pools.values() {
+                mint_keys.push(pool.token_mint_0);
+                mint_keys.push(pool.token_mint_1);
+                for reward_info in pool.reward_infos {
+                    if reward_info.token_mint != Pubkey::default() {
+                        mint_keys.push(reward_info.token_mint);
+                    }
+                }
+            }
+            mint_keys.sort();
+            mint_keys.dedup();
+            let mut mint_programs = HashMap::new();
+            for (key, rsp) in mint_keys
+                .iter()
+                .zip(rpc_client.get_multiple_accounts(&mint_keys)?)
+            {
+                mint_programs.insert(*key, rsp.unwrap().owner);
+            }
+
+            // the token accounts of the payer receiving the fees and rewards
+            let payer_key = payer.pubkey();
+            let user_token_account = |mint: &Pubkey| {
+                get_associated_token_address_with_program_id(&payer_key, mint, &mint_programs[mint])
+            };
+            let mut groups = vec![mint_keys
+                .iter()
+                .map(|mint| {
+                    spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                        &payer_key,
+                        &payer_key,
+                        mint,
+                        &mint_programs[mint],
+                    )
+                })
+                .collect::<Vec<Instruction>>()];
+            // the fees and rewards are estimated with the growths of the last update of the pools
+            let mut totals: HashMap<Pubkey, u64> = HashMap::new();
+            for ((nft_info, position), pending_fees) in positions.iter().zip(pending_fees) {
+                let pool = &pools[&position.pool_id];
+                *totals.entry(pool.token_mint_0).or_default() += pending_fees.fees_0;
+                *totals.entry(pool.token_mint_1).or_default() += pending_fees.fees_1;
+                let mut remaining_accounts = vec![AccountMeta::new(
+                    Pubkey::find_program_address(
+                        &[
+                            POOL_TICK_ARRAY_BITMAP_SEED.as_bytes(),
+                            position.pool_id.to_bytes().as_ref(),
+                        ],
+                        &pool_config.raydium_v3_program,
+                    )
+                    .0,
+                    false,
+                )];
+                for (i, reward_info) in pool.reward_infos.iter().enumerate() {
+                    if reward_info.token_mint == Pubkey::default() {
+                        continue;
+                    }
+                    *totals.entry(reward_info.token_mint).or_default() += pending_fees.rewards[i];
+                    remaining_accounts.push(AccountMeta::new(reward_info.token_vault, false));
+                    remaining_accounts.push(AccountMeta::new(
+                        user_token_account(&reward_info.token_mint),
+                        false,
+                    ));
+                    remaining_accounts.push(AccountMeta::new(reward_info.token_mint, false));
+                }
+                // decreasing zero liquidity collects the fees and rewards of the position
+                groups.push(decrease_liquidity_instr(
+                    &pool_config.clone(),
+                    position.pool_id,
+                    pool.token_vault_0,
+                    pool.token_vault_1,
+                    pool.token_mint_0,
+                    pool.token_mint_1,
+                    position.nft_mint,
+                    nft_info.key,
+                    user_token_account(&pool.token_mint_0),
+                    user_token_account(&pool.token_mint_1),
+                    remaining_accounts,
+                    0,
+                    0,
+                    0,
+                    position.tick_lower_index,
+                    position.tick_upper_index,
+                    TickArrayState::get_array_start_index(
+                        position.tick_lower_index,
+                        pool.tick_spacing,
+                    ),
+                    TickArrayState::get_array_start_index(
+                        position.tick_upper_index,
+                        pool.tick_spacing,
+                    ),
+                )?);
+            }
+
+            let transactions =
+                pack_instructions_into_transactions(&payer_key, &lookup_tables, groups);
+            println!(
+                "harvest {} positions in {} transactions",
+                positions.len(),
+                transactions.len()
+            );
+            for instructions in transactions {
+                let signers = vec![&payer];
+                let txn = sign_versioned_transaction(
+                    &rpc_client,
+                    &compute_budget,
+                    &lookup_tables,
+                    &instructions,
+                    &payer_key,
+                    &signers,
+                )?;
+                if simulate {
+                    let ret = simulate_transaction(
+                        &rpc_client,
+                        &txn,
+                        true,
+                        CommitmentConfig::confirmed(),
+                    )?;
+                    println!("{:#?}", ret);
+                } else {
+                    let signature = send_txn(&rpc_client, &txn, true)?;
+                    println!("{}", signature);
+                }
+            }
+            for (mint, amount) in totals {
+                println!("mint:{}, harvested:{}", mint, amount);
+            }
+        }
+        CommandsName::Watch {
+            pool_id,
+            tick_array_range,
+        } => {
+            let pool_id = pool_id.unwrap_or_else(|| pool_config.pool_id_account.unwrap());
+            let mut last_pool_state =
+                deserialize_anchor_account::<PoolState>(&rpc_client.get_account(&pool_id)?)?;
+            let observation_key = last_pool_state.observation_key;
+            // the tick arrays around the current tick
+            let tick_spacing = last_pool_state.tick_spacing;
+            let current_start_index =
+                TickArrayState::get_array_start_index(last_pool_state.tick_current, tick_spacing);
+            let tick_array_keys: Vec<Pubkey> = (-tick_array_range..=tick_array_range)
+                .map(|i| {
+                    Pubkey::find_program_address(
+                        &[
+                            raydium_amm_v3::states::TICK_ARRAY_SEED.as_bytes(),
+                            pool_id.to_bytes().as_ref(),
+                            &(current_start_index + i * TickArrayState::tick_count(tick_spacing))
+                                .to_be_bytes(),
+                        ],
+                        &pool_config.raydium_v3_program,
+                    )
+                    .0
+                })
+                .collect();
+            let mut tick_arrays = HashMap::new();
+            for (key, rsp) in tick_array_keys
+                .iter()
+                .zip(get_multiple_accounts(&rpc_client, &tick_array_keys)?)
+            {
+                if let Some(rsp) = rsp {
+                    tick_arrays
+                        .insert(*key, deserialize_zero_copy_account::<TickArrayState>(&rsp)?);
+                }
+            }
+
+            // forward the notifications of all the subscriptions to one channel
+            let (sender, receiver) = std::sync::mpsc::channel();
+            let mut subscriptions = Vec::new();
+            let account_config = RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                commitment: Some(CommitmentConfig::confirmed()),
+                ..RpcAccountInfoConfig::default()
+            };
+            let mut watched_keys = vec![pool_id, observation_key];
+            watched_keys.extend(tick_array_keys.iter());
+            for key in watched_keys {
+                let (subscription, account_receiver) = PubsubClient::account_subscribe(
+                    &pool_config.ws_url,
+                    &key,
+                    Some(account_config.clone()),
+                )?;
+                subscriptions.push(subscription);
+                let sender = sender.clone();
+                std::thread::spawn(move || {
+                    for response in account_receiver {
+                        if let Some(account) = response.value.decode() {
+                            let update = WatchUpdate::Account {
+                                key,
+                                slot: response.context.slot,
+                                account,
+                            };
+                            if sender.send(update).is_err() {
+                                break;
+                            }
+                        }
+                    }
+                });
+            }
+            let (logs_subscription, logs_receiver) = PubsubClient::logs_subscribe(
+                &pool_config.ws_url,
+                RpcTransactionLogsFilter::Mentions(vec![pool_id.to_string()]),
+                RpcTransactionLogsConfig {
+                    commitment: Some(CommitmentConfig::confirmed()),
+                },
+            )?;
+            std::thread::spawn(move || {
+                for response in logs_receiver {
+                    // the failed transactions emit no event
+                    if response.value.err.is_some() {
+                        continue;
+                    }
+                    let update = WatchUpdate::Logs {
+                        slot: response.context.slot,
+                        signature: response.value.signature,
+                        logs: response.value.logs,
+                    };
+                    if sender.send(update).is_err() {
+                        break;
+                    }
+                }
+            });
+
+            println!(
+                "watch pool:{}, price:{}, tick:{}, liquidity:{}",
+                pool_id,
+                sqrt_price_x64_to_price(
+                    last_pool_state.sqrt_price_x64,
+                    last_pool_state.mint_decimals_0,
+                    last_pool_state.mint_decimals_1
+                ),
+                { last_pool_state.tick_current },
+                { last_pool_state.liquidity }
+            );
+            for update in receiver {
+                match update {
+                    WatchUpdate::Account { key, slot, account } if key == pool_id => {
+                        let pool_state = deserialize_anchor_account::<PoolState>(&account)?;
+                        println!(
+                            "slot:{}, pool price:{}, tick:{}, liquidity:{}, sqrt_price_x64:{}",
+                            slot,
+                            sqrt_price_x64_to_price(
+                                pool_state.sqrt_price_x64,
+                                pool_state.mint_decimals_0,
+                                pool_state.mint_decimals_1
+                            ),
+                            { pool_state.tick_current },
+                            { pool_state.liquidity },
+                            { pool_state.sqrt_price_x64 }
+                        );
+                        // the initialized ticks crossed from the last to the current tick
+                        let lower = pool_state.tick_current.min(last_pool_state.tick_current);
+                        let upper = pool_state.tick_current.max(last_pool_state.tick_current);
+                        let mut crossed_ticks: Vec<_> = tick_arrays
+                            .values()
+                            .flat_map(|tick_array| tick_array.ticks.iter())
+                            .filter(|tick| {
+                                tick.is_initialized() && tick.tick > lower && tick.tick <= upper
+                            })
+                            .map(|tick| (tick.tick, tick.liquidity_net))
+                            .collect();
+                        crossed_ticks.sort();
+                        if pool_state.tick_current < last_pool_state.tick_current {
+                            crossed_ticks.reverse();
+                        }
+                        for (tick, liquidity_net) in crossed_ticks {
+                            println!("    cross tick:{}, liquidity_net:{}", tick, liquidity_net);
+                        }
+                        last_pool_state = pool_state;
+                    }
+                    WatchUpdate::Account { key, slot, account } if key == observation_key => {
+                        let observation_state = deserialize_zero_copy_account::<
+                            raydium_amm_v3::states::ObservationState,
+                        >(&account)?;
+                        let observation = observation_state.observations
+                            [observation_state.observation_index as usize];
+                        println!(
+                            "slot:{}, observation index:{}, block_timestamp:{}, tick_cumulative:{}",
+                            slot,
+                            { observation_state.observation_index },
+                            { observation.block_timestamp },
+                            { observation.tick_cumulative }
+                        );
+                    }
+                    WatchUpdate::Account { key, slot, account } => {
+                        let tick_array = deserialize_zero_copy_account::<TickArrayState>(&account)?;
+                        println!(
+                            "slot:{}, tick_array:{}, start_index:{}, initialized_tick_count:{}",
+                            slot,
+                            key,
+                            { tick_array.start_tick_index },
+                            tick_array.initialized_tick_count
+                        );
+                        tick_arrays.insert(key, tick_array);
+                    }
+                    WatchUpdate::Logs {
+                        slot,
+                        signature,
+                        logs,
+                    } => {
+                        println!("slot:{}, signature:{}", slot, signature);
+                        parse_program_event_logs(
+                            &pool_config.raydium_v3_program.to_string(),
+                            &logs,
+                        )?;
+                    }
+                }
+            }
+            drop(logs_subscription);
+            drop(subscriptions);
+        }
+        CommandsName::PositionReport {
+            user_wallet,
+            snapshot_path,
+            json,
+        } => {
+            let user_wallet = user_wallet.unwrap_or(payer.pubkey());
+            let position_nft_infos = get_all_nft_and_position_by_owner(
+                &rpc_client,
+                &user_wallet,
+                &pool_config.raydium_v3_program,
+            );
+            let position_keys: Vec<Pubkey> = position_nft_infos
+                .iter()
+                .map(|item| item.position)
+                .collect();
+            let mut positions = Vec::new();
+            for (key, rsp) in position_keys
+                .iter()
+                .zip(rpc_client.get_multiple_accounts(&position_keys)?)
+            {
+                if let Some(rsp) = rsp {
+                    positions.push((
+                        *key,
+                        deserialize_anchor_account::<raydium_amm_v3::states::PersonalPositionState>(
+                            &rsp,
+                        )?,
+                    ));
+                }
+            }
+            let position_states: Vec<raydium_amm_v3::states::PersonalPositionState> =
+                positions.iter().map(|(_, p)| p.clone()).collect();
+            let pools = load_pools_of_positions(&rpc_client, &position_states)?;
+            let pending_fees = estimate_positions_pending_fees(
+                &rpc_client,
+                &pool_config.raydium_v3_program,
+                &position_states,
+                &pools,
+            )?;
+
+            // the snapshots of the last report
+            let snapshots: HashMap<String, PositionSnapshot> = if path_is_exist(&snapshot_path) {
+                serde_json::from_str(&std::fs::read_to_string(&snapshot_path)?)?
+            } else {
+                HashMap::new()
+            };
+            let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+            let mut new_snapshots = HashMap::new();
+            let mut report = Vec::new();
+            for ((key, position), pending_fees) in positions.iter().zip(pending_fees) {
+                let pool = &pools[&position.pool_id];
+                let ui_amount =
+                    |amount: u64, decimals: u8| amount as f64 / 10f64.powi(decimals.into());
+                let (amount_0, amount_1) = liquidity_math::get_delta_amounts_signed(
+                    pool.tick_current,
+                    pool.sqrt_price_x64,
+                    position.tick_lower_index,
+                    position.tick_upper_index,
+                    -(position.liquidity as i128),
+                )?;
+                let price = sqrt_price_x64_to_price(
+                    pool.sqrt_price_x64,
+                    pool.mint_decimals_0,
+                    pool.mint_decimals_1,
+                );
+                let amount_0 = ui_amount(amount_0, pool.mint_decimals_0);
+                let amount_1 = ui_amount(amount_1, pool.mint_decimals_1);
+                let fees_0 = ui_amount(pending_fees.fees_0, pool.mint_decimals_0);
+                let fees_1 = ui_amount(pending_fees.fees_1, pool.mint_decimals_1);
+                let value = amount_0 * price + amount_1;
+                let fees_value = fees_0 * price + fees_1;
+
+                let snapshot = PositionSnapshot {
+                    timestamp,
+                    fee_growth_inside_0_x64: pending_fees.fee_growth_inside_0_x64,
+                    fee_growth_inside_1_x64: pending_fees.fee_growth_inside_1_x64,
+                    value,
+                };
+                let (mut elapsed, mut pnl, mut fee_apr) = (None, None, None);
+                if let Some(last_snapshot) = snapshots.get(&key.to_string()) {
+                    // the fees earned by the current liquidity since the last report
+                    let earned_0 = raydium_amm_v3::instructions::calculate_latest_token_fees(
+                        0,
+                        last_snapshot.fee_growth_inside_0_x64,
+                        snapshot.fee_growth_inside_0_x64,
+                        position.liquidity,
+                    );
+                    let earned_1 = raydium_amm_v3::instructions::calculate_latest_token_fees(
+                        0,
+                        last_snapshot.fee_growth_inside_1_x64,
+                        snapshot.fee_growth_inside_1_x64,
+                        position.liquidity,
+                    );
+                    let earned_value = ui_amount(earned_0, pool.mint_decimals_0) * price
+                        + ui_amount(earned_1, pool.mint_decimals_1);
+                    let seconds = timestamp.saturating_sub(last_snapshot.timestamp);
+                    elapsed = Some(seconds);
+                    pnl = Some(value - last_snapshot.value + earned_value);
+                    if seconds > 0 && value > 0.0 {
+                        fee_apr = Some(
+                            earned_value / value * (365 * 24 * 3600) as f64 / seconds as f64
+                                * 100.0,
+                        );
+                    }
+                }
+                new_snapshots.insert(key.to_string(), snapshot);
+
+                let rewards = pool
+                    .reward_infos
+                    .iter()
+                    .zip(pending_fees.rewards)
+                    .filter(|(reward_info, _)| reward_info.token_mint != Pubkey::default())
+                    .map(|(reward_info, amount)| (reward_info.token_mint.to_string(), amount))
+                    .collect();
+                report.push(PositionReportItem {
+                    position: key.to_string(),
+                    pool: position.pool_id.to_string(),
+                    tick_lower: position.tick_lower_index,
+                    tick_upper: position.tick_upper_index,
+                    liquidity: position.liquidity,
+                    price,
+                    amount_0,
+                    amount_1,
+                    fees_0,
+                    fees_1,
+                    rewards,
+                    value,
+                    fees_value,
+                    elapsed,
+                    pnl,
+                    fee_apr,
+                });
             }
+            std::fs::write(
+                &snapshot_path,
+                serde_json::to_string_pretty(&new_snapshots)?,
+            )?;
 
-            let mut remaining_accounts = Vec::new();
-            remaining_accounts.push(AccountMeta::new_readonly(
-                pool_config.tickarray_bitmap_extension.unwrap(),
-                false,
-            ));
-            let mut accounts = tick_array_indexs
-                .into_iter()
-                .map(|index| {
-                    AccountMeta::new(
-                        Pubkey::find_program_address(
-                            &[
-                                raydium_amm_v3::states::TICK_ARRAY_SEED.as_bytes(),
-                                pool_config.pool_id_account.unwrap().to_bytes().as_ref(),
-                                &index.to_be_bytes(),
-                            ],
-                            &pool_config.raydium_v3_program,
-                        )
-                        .0,
-                        false,
-                    )
-                })
-                .collect();
-            remaining_accounts.append(&mut accounts);
-            let mut instructions = Vec::new();
-            let request_inits_instr = ComputeBudgetInstruction::set_compute_unit_limit(1400_000u32);
-            instructions.push(request_inits_instr);
-            let swap_instr = swap_v2_instr(
-                &pool_config.clone(),
-                pool_state.amm_config,
-                pool_config.pool_id_account.unwrap(),
-                if zero_for_one {
-                    pool_state.token_vault_0
-                } else {
-                    pool_state.token_vault_1
-                },
-                if zero_for_one {
-                    pool_state.token_vault_1
-                } else {
-                    pool_state.token_vault_0
-                },
-                pool_state.observation_key,
-                input_token,
-                output_token,
-                if zero_for_one {
-                    pool_state.token_mint_0
-                } else {
-                    pool_state.token_mint_1
-                },
-                if zero_for_one {
-                    pool_state.token_mint_1
-                } else {
-                    pool_state.token_mint_0
-                },
-                remaining_accounts,
-                amount,
-                other_amount_threshold,
-                sqrt_price_limit_x64,
-                base_in,
-            )
-            .unwrap();
-            instructions.extend(swap_instr);
-            // send
-            let signers = vec![&payer];
-            let recent_hash = rpc_client.get_latest_blockhash()?;
-            let txn = Transaction::new_signed_with_payer(
-                &instructions,
-                Some(&payer.pubkey()),
-                &signers,
-                recent_hash,
-            );
-            if simulate {
-                let ret =
-                    simulate_transaction(&rpc_client, &txn, true, CommitmentConfig::confirmed())?;
-                println!("{:#?}", ret);
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
             } else {
-                let signature = send_txn(&rpc_client, &txn, true)?;
-                println!("{}", signature);
+                let optional = |value: Option<f64>| {
+                    value.map_or("-".to_string(), |value| format!("{:.4}", value))
+                };
+                println!(
+                    "{:<44} {:>8} {:>8} {:>16} {:>16} {:>16} {:>16} {:>16} {:>16} {:>12} {:>10}",
+                    "position",
+                    "lower",
+                    "upper",
+                    "price",
+                    "amount_0",
+                    "amount_1",
+                    "fees_0",
+                    "fees_1",
+                    "value",
+                    "pnl",
+                    "fee_apr%"
+                );
+                for item in report.iter() {
+                    println!(
+                        "{:<44} {:>8} {:>8} {:>16.6} {:>16.6} {:>16.6} {:>16.6} {:>16.6} {:>16.6} {:>12} {:>10}",
+                        item.position,
+                        item.tick_lower,
+                        item.tick_upper,
+                        item.price,
+                        item.amount_0,
+                        item.amount_1,
+                        item.fees_0,
+                        item.fees_1,
+                        item.value,
+                        optional(item.pnl),
+                        optional(item.fee_apr)
+                    );
+                    for (mint, amount) in item.rewards.iter() {
+                        println!("    reward mint:{}, pending:{}", mint, amount);
+                    }
+                }
             }
         }
         CommandsName::PPositionByOwner { user_wallet } => {
@@ -2008,8 +4219,9 @@ fn main() -> Result<()> {
                 ],
                 &program.id(),
             );
-            let mut tick_array_account: raydium_amm_v3::states::TickArrayState =
-                program.account(tick_array_key)?;
+            let mut tick_array_account = deserialize_zero_copy_account::<
+                raydium_amm_v3::states::TickArrayState,
+            >(&rpc_client.get_account(&tick_array_key)?)?;
             let tick_state = tick_array_account
                 .get_tick_state_mut(tick, pool.tick_spacing.into())
                 .unwrap();
@@ -2106,6 +4318,100 @@ fn main() -> Result<()> {
             )?;
             println!("amount_0:{}, amount_1:{}", amounts.0, amounts.1);
         }
+        CommandsName::ExportPool {
+            pool_id,
+            csv,
+            output,
+        } => {
+            let pool_id = pool_id.unwrap_or_else(|| pool_config.pool_id_account.unwrap());
+            let slot = rpc_client.get_slot()?;
+            let pool_state =
+                deserialize_anchor_account::<PoolState>(&rpc_client.get_account(&pool_id)?)?;
+            let amm_config_key = pool_state.amm_config;
+            let amm_config = deserialize_anchor_account::<raydium_amm_v3::states::AmmConfig>(
+                &rpc_client.get_account(&amm_config_key)?,
+            )?;
+            let bitmap_extension_key = Pubkey::find_program_address(
+                &[
+                    POOL_TICK_ARRAY_BITMAP_SEED.as_bytes(),
+                    pool_id.to_bytes().as_ref(),
+                ],
+                &pool_config.raydium_v3_program,
+            )
+            .0;
+            let bitmap_extension = match rpc_client
+                .get_account_with_commitment(&bitmap_extension_key, CommitmentConfig::confirmed())?
+                .value
+            {
+                Some(account) => Some(BitmapExtensionRow::new(
+                    &bitmap_extension_key,
+                    &deserialize_zero_copy_account::<TickArrayBitmapExtension>(&account)?,
+                )),
+                None => None,
+            };
+            let program_accounts_by_pool = |discriminator: [u8; 8], pool_id_offset: usize| {
+                rpc_client.get_program_accounts_with_config(
+                    &pool_config.raydium_v3_program,
+                    RpcProgramAccountsConfig {
+                        filters: Some(vec![
+                            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, &discriminator)),
+                            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                                pool_id_offset,
+                                &pool_id.to_bytes(),
+                            )),
+                        ]),
+                        account_config: RpcAccountInfoConfig {
+                            encoding: Some(UiAccountEncoding::Base64Zstd),
+                            ..RpcAccountInfoConfig::default()
+                        },
+                        with_context: Some(false),
+                    },
+                )
+            };
+            let mut tick_arrays = program_accounts_by_pool(TickArrayState::discriminator(), 8)?;
+            tick_arrays.sort_by_key(|(key, _)| *key);
+            let mut ticks = Vec::new();
+            for (key, account) in tick_arrays {
+                ticks.extend(TickRow::from_tick_array(
+                    &key,
+                    &deserialize_zero_copy_account::<TickArrayState>(&account)?,
+                ));
+            }
+            ticks.sort_by_key(|tick| tick.tick);
+            let mut positions = Vec::new();
+            for (key, account) in program_accounts_by_pool(
+                raydium_amm_v3::states::PersonalPositionState::discriminator(),
+                8 + 1 + size_of::<Pubkey>(),
+            )? {
+                let position = deserialize_anchor_account::<
+                    raydium_amm_v3::states::PersonalPositionState,
+                >(&account)?;
+                positions.push(PositionRow::new(&key, &position));
+            }
+            positions.sort_by(|a, b| a.key.cmp(&b.key));
+
+            let snapshot = PoolSnapshot {
+                pool: PoolRow::new(slot, &pool_id, &pool_state),
+                amm_config: AmmConfigRow::new(&amm_config_key, &amm_config),
+                rewards: RewardRow::from_pool(&pool_state),
+                bitmap_extension,
+                ticks,
+                positions,
+            };
+            if csv {
+                snapshot.write_csv(Path::new(&output))?;
+            } else {
+                snapshot.write_json(Path::new(&output))?;
+            }
+            println!(
+                "export pool:{} at slot:{}, ticks:{}, positions:{} to {}",
+                pool_id,
+                slot,
+                snapshot.ticks.len(),
+                snapshot.positions.len(),
+                output
+            );
+        }
         CommandsName::PPersonalPositionByPool { pool_id } => {
             let pool_id = if let Some(pool_id) = pool_id {
                 pool_id
@@ -2218,7 +4524,11 @@ fn main() -> Result<()> {
                 RpcProgramAccountsConfig {
                     filters: Some(vec![
                         RpcFilterType::Memcmp(Memcmp::new_base58_encoded(8, &pool_id.to_bytes())),
-                        RpcFilterType::DataSize(raydium_amm_v3::states::TickArrayState::LEN as u64),
+                        // the tick arrays may be reallocated beyond the layout by a migration
+                        RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                            0,
+                            &raydium_amm_v3::states::TickArrayState::discriminator(),
+                        )),
                     ]),
                     account_config: RpcAccountInfoConfig {
                         encoding: Some(UiAccountEncoding::Base64Zstd),
@@ -2229,7 +4539,7 @@ fn main() -> Result<()> {
             )?;
 
             for tick_array in tick_arrays_by_pool {
-                let tick_array_state = deserialize_anchor_account::<
+                let tick_array_state = deserialize_zero_copy_account::<
                     raydium_amm_v3::states::TickArrayState,
                 >(&tick_array.1)?;
                 if tick_array_state.pool_id == pool_id {
@@ -2247,6 +4557,230 @@ fn main() -> Result<()> {
                 }
             }
         }
+        CommandsName::CrankCloseTickArrays {
+            pool_id,
+            all,
+            simulate,
+        } => {
+            // the empty tick arrays, of one pool unless all the pools are scanned
+            let mut filters = vec![
+                RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                    0,
+                    &TickArrayState::discriminator(),
+                )),
+                RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                    8 + size_of::<Pubkey>()
+                        + size_of::<i32>()
+                        + raydium_amm_v3::states::TickState::LEN
+                            * raydium_amm_v3::states::TICK_ARRAY_SIZE_USIZE,
+                    &[0],
+                )),
+            ];
+            if !all {
+                let pool_id = pool_id.unwrap_or_else(|| pool_config.pool_id_account.unwrap());
+                filters.push(RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                    8,
+                    &pool_id.to_bytes(),
+                )));
+            }
+            let tick_arrays = rpc_client.get_program_accounts_with_config(
+                &pool_config.raydium_v3_program,
+                RpcProgramAccountsConfig {
+                    filters: Some(filters),
+                    account_config: RpcAccountInfoConfig {
+                        encoding: Some(UiAccountEncoding::Base64Zstd),
+                        ..RpcAccountInfoConfig::default()
+                    },
+                    with_context: Some(false),
+                },
+            )?;
+            let mut empty_tick_arrays = Vec::new();
+            for (key, account) in tick_arrays {
+                let tick_array = deserialize_zero_copy_account::<TickArrayState>(&account)?;
+                if tick_array.initialized_tick_count == 0 {
+                    empty_tick_arrays.push((key, tick_array, account.lamports));
+                }
+            }
+            if empty_tick_arrays.is_empty() {
+                println!("no empty tick array to close");
+                return Ok(());
+            }
+
+            let mut pool_keys: Vec<Pubkey> = empty_tick_arrays
+                .iter()
+                .map(|(_, tick_array, _)| tick_array.pool_id)
+                .collect();
+            pool_keys.sort();
+            pool_keys.dedup();
+            let mut pools = HashMap::new();
+            for (key, rsp) in pool_keys
+                .iter()
+                .zip(rpc_client.get_multiple_accounts(&pool_keys)?)
+            {
+                if let Some(rsp) = rsp {
+                    pools.insert(*key, deserialize_anchor_account::<PoolState>(&rsp)?);
+                }
+            }
+            let mut groups = Vec::new();
+            let mut recovered_rent = 0;
+            for (key, tick_array, lamports) in empty_tick_arrays.iter() {
+                let pool = match pools.get(&tick_array.pool_id) {
+                    Some(pool) => pool,
+                    None => continue,
+                };
+                // the tick array bitmap extension is required out of the default bitmap range
+                let mut remaining_accounts = Vec::new();
+                if pool.is_overflow_default_tickarray_bitmap(vec![tick_array.start_tick_index]) {
+                    remaining_accounts.push(AccountMeta::new(
+                        Pubkey::find_program_address(
+                            &[
+                                POOL_TICK_ARRAY_BITMAP_SEED.as_bytes(),
+                                tick_array.pool_id.to_bytes().as_ref(),
+                            ],
+                            &pool_config.raydium_v3_program,
+                        )
+                        .0,
+                        false,
+                    ));
+                }
+                groups.push(close_tick_array_instr(
+                    &pool_config.clone(),
+                    tick_array.pool_id,
+                    *key,
+                    tick_array.rent_recipient(pool.owner),
+                    remaining_accounts,
+                )?);
+                recovered_rent += lamports;
+            }
+
+            let close_count = groups.len();
+            let transactions = pack_instructions_into_transactions(&payer.pubkey(), &[], groups);
+            println!(
+                "close {} empty tick arrays in {} transactions, recovered rent:{} lamports",
+                close_count,
+                transactions.len(),
+                recovered_rent
+            );
+            for instructions in transactions {
+                let signers = vec![&payer];
+                let recent_hash = rpc_client.get_latest_blockhash()?;
+                let txn = Transaction::new_signed_with_payer(
+                    &with_compute_budget(&rpc_client, &compute_budget, &instructions)?,
+                    Some(&payer.pubkey()),
+                    &signers,
+                    recent_hash,
+                );
+                if simulate {
+                    let ret = simulate_transaction(
+                        &rpc_client,
+                        &txn,
+                        true,
+                        CommitmentConfig::confirmed(),
+                    )?;
+                    println!("{:#?}", ret);
+                } else {
+                    let signature = send_txn(&rpc_client, &txn, true)?;
+                    println!("{}", signature);
+                }
+            }
+        }
+        CommandsName::CreateLookupTable { pool_id } => {
+            let pool_id = pool_id.unwrap_or_else(|| pool_config.pool_id_account.unwrap());
+            let pool_state =
+                deserialize_anchor_account::<PoolState>(&rpc_client.get_account(&pool_id)?)?;
+            let bitmap_extension_key = Pubkey::find_program_address(
+                &[
+                    POOL_TICK_ARRAY_BITMAP_SEED.as_bytes(),
+                    pool_id.to_bytes().as_ref(),
+                ],
+                &pool_config.raydium_v3_program,
+            )
+            .0;
+            let tickarray_bitmap_extension = deserialize_anchor_account::<TickArrayBitmapExtension>(
+                &rpc_client.get_account(&bitmap_extension_key)?,
+            )?;
+            // the hot accounts of the pool, the tick arrays are the ones a swap in either
+            // direction starts with
+            let mut addresses = vec![
+                pool_id,
+                pool_state.amm_config,
+                pool_state.observation_key,
+                pool_state.token_vault_0,
+                pool_state.token_vault_1,
+                pool_state.token_mint_0,
+                pool_state.token_mint_1,
+                bitmap_extension_key,
+                spl_token::id(),
+                spl_token_2022::id(),
+                spl_memo::id(),
+            ];
+            let reward_infos = pool_state.reward_infos;
+            for reward_info in reward_infos
+                .iter()
+                .filter(|reward_info| reward_info.token_mint != Pubkey::default())
+            {
+                for key in [reward_info.token_vault, reward_info.token_mint] {
+                    if !addresses.contains(&key) {
+                        addresses.push(key);
+                    }
+                }
+            }
+            for zero_for_one in [true, false] {
+                let tick_arrays = load_pool_cur_and_next_five_tick_array(
+                    &rpc_client,
+                    &pool_config.raydium_v3_program,
+                    &pool_id,
+                    &pool_state,
+                    &tickarray_bitmap_extension,
+                    zero_for_one,
+                );
+                for tick_array in tick_arrays {
+                    let tick_array_key = Pubkey::find_program_address(
+                        &[
+                            raydium_amm_v3::states::TICK_ARRAY_SEED.as_bytes(),
+                            pool_id.to_bytes().as_ref(),
+                            &tick_array.start_tick_index.to_be_bytes(),
+                        ],
+                        &pool_config.raydium_v3_program,
+                    )
+                    .0;
+                    if !addresses.contains(&tick_array_key) {
+                        addresses.push(tick_array_key);
+                    }
+                }
+            }
+
+            // the table is derived from a recent slot, it must still be in the slot hashes
+            let recent_slot = rpc_client.get_slot_with_commitment(CommitmentConfig::finalized())?;
+            let (create_instr, lookup_table) =
+                create_lookup_table(payer.pubkey(), payer.pubkey(), recent_slot);
+            let mut instructions = vec![create_instr];
+            for chunk in addresses.chunks(LOOKUP_TABLE_EXTEND_CHUNK_SIZE) {
+                instructions.push(extend_lookup_table(
+                    lookup_table,
+                    payer.pubkey(),
+                    Some(payer.pubkey()),
+                    chunk.to_vec(),
+                ));
+                let signers = vec![&payer];
+                let recent_hash = rpc_client.get_latest_blockhash()?;
+                let txn = Transaction::new_signed_with_payer(
+                    &with_compute_budget(&rpc_client, &compute_budget, &instructions)?,
+                    Some(&payer.pubkey()),
+                    &signers,
+                    recent_hash,
+                );
+                let signature = send_txn(&rpc_client, &txn, true)?;
+                println!("{}", signature);
+                instructions.clear();
+            }
+            // the appended addresses can be looked up from the next slot
+            println!(
+                "lookup table:{} addresses:{}",
+                lookup_table,
+                addresses.len()
+            );
+        }
         CommandsName::PPool { pool_id } => {
             let pool_id = if let Some(pool_id) = pool_id {
                 pool_id
@@ -2278,6 +4812,57 @@ fn main() -> Result<()> {
                 program.account(personal_id)?;
             println!("{:#?}", personal_account);
         }
+        CommandsName::PInspect { account } => {
+            let inspect_instr = inspect_account_instr(&pool_config.clone(), account)?;
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &with_compute_budget(&rpc_client, &compute_budget, &inspect_instr)?,
+                Some(&payer.pubkey()),
+                &vec![&payer],
+                recent_hash,
+            );
+            let ret = simulate_transaction(&rpc_client, &txn, true, CommitmentConfig::confirmed())?;
+            let return_data = ret
+                .value
+                .return_data
+                .ok_or(format_err!("no return data, logs: {:?}", ret.value.logs))?;
+            let data = anchor_lang::__private::base64::decode(return_data.data.0)?;
+            let inspection =
+                <raydium_amm_v3::states::AccountInspection as anchor_lang::AnchorDeserialize>::deserialize(
+                    &mut data.as_slice(),
+                )?;
+            println!("{:#?}", inspection);
+        }
+        CommandsName::PTwap {
+            pool_id,
+            window_seconds,
+        } => {
+            let pool_account: raydium_amm_v3::states::PoolState = program.account(pool_id)?;
+            let twap_instr = get_twap_instr(
+                &pool_config.clone(),
+                pool_id,
+                pool_account.observation_key,
+                window_seconds,
+            )?;
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &with_compute_budget(&rpc_client, &compute_budget, &twap_instr)?,
+                Some(&payer.pubkey()),
+                &vec![&payer],
+                recent_hash,
+            );
+            let ret = simulate_transaction(&rpc_client, &txn, true, CommitmentConfig::confirmed())?;
+            let return_data = ret
+                .value
+                .return_data
+                .ok_or(format_err!("no return data, logs: {:?}", ret.value.logs))?;
+            let data = anchor_lang::__private::base64::decode(return_data.data.0)?;
+            let twap =
+                <raydium_amm_v3::states::TwapResult as anchor_lang::AnchorDeserialize>::deserialize(
+                    &mut data.as_slice(),
+                )?;
+            println!("{:#?}", twap);
+        }
         CommandsName::DecodeInstruction { instr_hex_data } => {
             handle_program_instruction(&instr_hex_data, InstructionDecodeType::BaseHex)?;
         }
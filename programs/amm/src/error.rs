@@ -105,4 +105,118 @@ pub enum ErrorCode {
     MaxTokenOverflow,
     #[msg("calculate overflow")]
     CalculateOverflow,
+    #[msg("Invalid partner fee accounts")]
+    InvalidPartnerFeeAccounts,
+    #[msg("Too many remaining accounts")]
+    TooManyRemainingAccounts,
+    #[msg("Not enough remaining accounts")]
+    NotEnoughRemainingAccounts,
+    #[msg("Invalid admin timelock account")]
+    InvalidAdminTimelockAccount,
+    #[msg("Admin timelock is enabled, the action must be queued")]
+    AdminTimelockEnabled,
+    #[msg("Admin action is not executable yet")]
+    AdminActionNotReady,
+    #[msg("Invalid price band")]
+    InvalidPriceBand,
+    #[msg("Swap price is out of the pool price band")]
+    PriceBandExceeded,
+    #[msg("Price impact exceeds the maximum")]
+    PriceImpactTooHigh,
+    #[msg("Invalid observation cardinality")]
+    InvalidObservationCardinality,
+    #[msg("The observation is older than the oldest stored observation")]
+    ObservationTooOld,
+    #[msg("Pool is not idle")]
+    PoolNotIdle,
+    #[msg("Tick array is not empty")]
+    TickArrayNotEmpty,
+    #[msg("Pool is not drained")]
+    PoolNotDrained,
+    #[msg("Position is locked")]
+    PositionLocked,
+    #[msg("Position is locked until the unlock timestamp")]
+    PositionTimeLocked,
+    #[msg("Invalid lock timestamp")]
+    InvalidLockTimestamp,
+    #[msg("The signer is not an operator of the position allowed to take the action")]
+    OperatorNotAllowed,
+    #[msg("Invalid operator permission")]
+    InvalidOperatorPermission,
+    #[msg("Missing the reward info extension account of the pool")]
+    MissingRewardInfoExtension,
+    #[msg("Missing the reward extension account of the position")]
+    MissingPositionRewardExtension,
+    #[msg("The cliff of the reward vesting exceeds the vesting duration")]
+    InvalidRewardVesting,
+    #[msg("The latest checkpoint of the pool is too recent")]
+    CheckpointTooFrequent,
+    #[msg("Invalid number of positions to open in a batch")]
+    InvalidOpenPositionsBatch,
+    #[msg("The position can not be migrated to the target pool")]
+    InvalidPositionMigration,
+    #[msg("The account is already at the current layout version")]
+    AccountAlreadyMigrated,
+    #[msg("The account type has no versioned layout")]
+    AccountNotMigratable,
+    #[msg("The swap can only be split across two pools of the same mint pair")]
+    InvalidSwapSplit,
+    #[msg("The tick arrays do not include all initialized tick arrays of the pool")]
+    IncompleteTickArrays,
+    #[msg("The vaults of the pool do not cover the amounts owed out of them")]
+    PoolInsolvent,
+    #[msg("The name, symbol or uri of the position NFT is too long")]
+    InvalidPositionNftMetadata,
+    #[msg("The account is not the MPL Core asset of the position")]
+    InvalidCoreAsset,
+    #[msg("The operation account is full, resize it to add more keys")]
+    OperationAccountFull,
+    #[msg("The operation account can only grow up to the maximum extension size")]
+    InvalidOperationAccountSize,
+    #[msg("The label or uri of the pool metadata is too long")]
+    InvalidPoolMetadata,
+    #[msg("The protocol, fund and creator fee rates exceed the trade fee")]
+    InvalidCreatorFeeRate,
+    #[msg("The recipient token accounts are not owned by the fund fee recipient of the pool")]
+    InvalidFundFeeRecipient,
+    #[msg("The launch fee rate must exceed the trade fee rate with a duration within the limit")]
+    InvalidLaunchFee,
+    #[msg("The pool has swapped")]
+    PoolAlreadySwapped,
+    #[msg("The anti-bot duration must be within the limit and end after the current time")]
+    InvalidAntiBotConfig,
+    #[msg("The output amount exceeds the anti-bot limit of the pool opening window")]
+    AntiBotAmountOutExceeded,
+    #[msg("The open time must be after the current time")]
+    InvalidOpenTime,
+    #[msg("The pool has no liquidity in range to receive the donation")]
+    NoLiquidityToDonate,
+    #[msg("The swap hook program of the pool is missing in remaining accounts")]
+    MissingSwapHook,
+    #[msg("Invalid swap hook program")]
+    InvalidSwapHook,
+    #[msg("Invalid merkle proof of the claim")]
+    InvalidMerkleProof,
+    #[msg("The claims exceed the total amount of the distribution")]
+    DistributionOverClaimed,
+    #[msg("Not enough fund fees to deploy")]
+    InsufficientFundFees,
+    #[msg("Invalid dynamic fee bounds")]
+    InvalidDynamicFeeBounds,
+    #[msg("The grace period after the end of the reward is not over")]
+    RewardGracePeriodNotEnded,
+    #[msg("The external oracle of the pool is missing in remaining accounts")]
+    MissingExternalOracle,
+    #[msg("Invalid external oracle")]
+    InvalidExternalOracle,
+    #[msg("The external oracle price is stale")]
+    StaleExternalOraclePrice,
+    #[msg("The execution price deviates from the external oracle price")]
+    ExternalOraclePriceDeviation,
+    #[msg("The self-service token badge is disabled by the token badge config")]
+    SelfServiceTokenBadgeDisabled,
+    #[msg("The expire duration of the admin heartbeat is too short")]
+    InvalidAdminHeartbeatExpireDuration,
+    #[msg("The owner and fund owner are transferred by propose_ownership and accept_ownership")]
+    OwnershipTransferRequiresTwoSteps,
 }
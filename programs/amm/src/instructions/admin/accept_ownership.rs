@@ -0,0 +1,51 @@
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct AcceptOwnership<'info> {
+    /// The proposed owner or fund owner
+    pub new_owner: Signer<'info>,
+
+    /// Amm config account to be transferred
+    #[account(mut)]
+    pub amm_config: Box<Account<'info, AmmConfig>>,
+
+    /// Store the proposed owners
+    #[account(
+        mut,
+        seeds = [
+            AMM_CONFIG_PENDING_OWNER_SEED.as_bytes(),
+            amm_config.key().as_ref()
+        ],
+        bump = pending_owner.bump,
+    )]
+    pub pending_owner: Box<Account<'info, AmmConfigPendingOwner>>,
+}
+
+pub fn accept_ownership(ctx: Context<AcceptOwnership>) -> Result<()> {
+    let amm_config = &mut ctx.accounts.amm_config;
+    require!(
+        ctx.accounts
+            .pending_owner
+            .accept(amm_config, ctx.accounts.new_owner.key()),
+        ErrorCode::NotApproved
+    );
+
+    emit_event!(
+        ctx,
+        ConfigChangeEvent {
+            index: amm_config.index,
+            owner: amm_config.owner,
+            trade_fee_rate: amm_config.trade_fee_rate,
+            protocol_fee_rate: amm_config.protocol_fee_rate,
+            tick_spacing: amm_config.tick_spacing,
+            fund_fee_rate: amm_config.fund_fee_rate,
+            fund_owner: amm_config.fund_owner,
+        }
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,243 @@
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::util::{close_account, close_spl_account, AccountLoad};
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Token2022, TokenAccount};
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct ClosePool<'info> {
+    /// Only admin can close a pool
+    #[account(address = crate::admin::id() @ ErrorCode::NotApproved)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: The designated account receives the rent of the closed accounts
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// The drained pool to be closed
+    #[account(mut, close = recipient)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// The address that holds pool tokens for token_0
+    #[account(
+        mut,
+        address = pool_state.load()?.token_vault_0
+    )]
+    pub token_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The address that holds pool tokens for token_1
+    #[account(
+        mut,
+        address = pool_state.load()?.token_vault_1
+    )]
+    pub token_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: The observation account of the pool, may be closed already
+    #[account(
+        mut,
+        address = pool_state.load()?.observation_key
+    )]
+    pub observation_state: UncheckedAccount<'info>,
+
+    /// The tick array bitmap extension of the pool
+    #[account(
+        mut,
+        address = TickArrayBitmapExtension::key(pool_state.key()),
+        close = recipient
+    )]
+    pub tickarray_bitmap_extension: AccountLoader<'info, TickArrayBitmapExtension>,
+
+    /// The SPL program to close token accounts
+    pub token_program: Program<'info, Token>,
+
+    /// The SPL program 2022 to close token accounts
+    pub token_program_2022: Program<'info, Token2022>,
+    // remaining accounts:
+    // the reward vaults of the initialized rewards of the pool and the reward info extension,
+    // then the accounts of the pool to be closed with it: the reward info extension, pool stats,
+    // pool metadata, pool checkpoint and price feed of the pool, which are required even if not created,
+    // followed by the tick arrays, protocol positions, twap snapshots and trader stats of the pool
+}
+
+pub fn close_pool<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ClosePool<'info>>,
+) -> Result<()> {
+    let pool_state = ctx.accounts.pool_state.load()?;
+    require!(
+        pool_state.liquidity == 0
+            && { pool_state.tick_array_bitmap } == [0; 16]
+            && ctx.accounts.tickarray_bitmap_extension.load()?.is_empty()
+            && pool_state.protocol_fees_token_0 == 0
+            && pool_state.protocol_fees_token_1 == 0
+            && pool_state.fund_fees_token_0 == 0
+            && pool_state.fund_fees_token_1 == 0
+            && pool_state.creator_fees_token_0 == 0
+            && pool_state.creator_fees_token_1 == 0
+            && ctx.accounts.token_vault_0.amount == 0
+            && ctx.accounts.token_vault_1.amount == 0,
+        ErrorCode::PoolNotDrained
+    );
+
+    let pool_id = ctx.accounts.pool_state.key();
+    let reward_info_extension_key = RewardInfoExtension::key(pool_id);
+    let mut reward_infos = { pool_state.reward_infos }.to_vec();
+    // the vaults of the extension rewards are checked and closed like the pool reward vaults
+    if pool_state.extension_reward_count > 0 {
+        let reward_info_extension_info = ctx
+            .remaining_accounts
+            .iter()
+            .find(|account_info| account_info.key == &reward_info_extension_key)
+            .ok_or(ErrorCode::MissingRewardInfoExtension)?;
+        let reward_info_extension =
+            AccountLoad::<RewardInfoExtension>::try_from(reward_info_extension_info)?;
+        reward_infos.extend({ reward_info_extension.load()?.reward_infos });
+    }
+    let reward_vaults: Vec<Pubkey> = reward_infos
+        .iter()
+        .filter(|reward_info| reward_info.initialized())
+        .map(|reward_info| reward_info.token_vault)
+        .collect();
+    require_gte!(
+        ctx.remaining_accounts.len(),
+        reward_vaults.len(),
+        ErrorCode::NotEnoughRemainingAccounts
+    );
+
+    let mut token_accounts = vec![
+        ctx.accounts.token_vault_0.to_account_info(),
+        ctx.accounts.token_vault_1.to_account_info(),
+    ];
+    let (reward_vault_accounts, pool_accounts) =
+        ctx.remaining_accounts.split_at(reward_vaults.len());
+    for (reward_vault, account_info) in reward_vaults.iter().zip(reward_vault_accounts) {
+        require_keys_eq!(*reward_vault, account_info.key());
+        let reward_vault = InterfaceAccount::<TokenAccount>::try_from(account_info)?;
+        require_eq!(reward_vault.amount, 0, ErrorCode::PoolNotDrained);
+        token_accounts.push(account_info.clone());
+    }
+    for token_account in token_accounts.iter() {
+        let token_program = if token_account.owner == ctx.accounts.token_program_2022.key {
+            ctx.accounts.token_program_2022.to_account_info()
+        } else {
+            ctx.accounts.token_program.to_account_info()
+        };
+        close_spl_account(
+            &ctx.accounts.pool_state.to_account_info(),
+            &ctx.accounts.recipient.to_account_info(),
+            token_account,
+            &token_program,
+            &[&pool_state.seeds()],
+        )?;
+    }
+
+    let observation_state_info = ctx.accounts.observation_state.to_account_info();
+    if observation_state_info.owner == &crate::id() {
+        close_account(
+            observation_state_info,
+            ctx.accounts.recipient.to_account_info(),
+        )?;
+    }
+
+    // the accounts derived from the pool would be taken over by a pool created at the same address
+    let singleton_keys = [
+        reward_info_extension_key,
+        PoolStatsState::key(pool_id),
+        PoolMetadata::key(pool_id),
+        PoolCheckpointState::key(pool_id),
+        PriceFeed::key(pool_id),
+    ];
+    for singleton_key in singleton_keys.iter() {
+        require!(
+            pool_accounts
+                .iter()
+                .any(|account_info| account_info.key == singleton_key),
+            ErrorCode::NotEnoughRemainingAccounts
+        );
+    }
+    for account_info in pool_accounts {
+        // the singletons not created are skipped
+        if account_info.owner != &crate::id() && singleton_keys.contains(account_info.key) {
+            continue;
+        }
+        require_keys_eq!(
+            pool_account_pool_id(account_info)?,
+            pool_id,
+            ErrorCode::NotApproved
+        );
+        close_account(
+            account_info.clone(),
+            ctx.accounts.recipient.to_account_info(),
+        )?;
+    }
+
+    emit_event!(
+        ctx,
+        ClosePoolEvent {
+            pool_state: ctx.accounts.pool_state.key(),
+            recipient: ctx.accounts.recipient.key(),
+        }
+    )?;
+    Ok(())
+}
+
+/// Returns the pool of an account derived from the pool, fails for the other accounts
+fn pool_account_pool_id<'info>(account_info: &'info AccountInfo<'info>) -> Result<Pubkey> {
+    let account_type = AccountType::from_data(&account_info.try_borrow_data()?)
+        .ok_or(anchor_lang::error::ErrorCode::AccountDiscriminatorNotFound)?;
+    let pool_id = match account_type {
+        AccountType::RewardInfoExtension => {
+            AccountLoader::<RewardInfoExtension>::try_from(account_info)?
+                .load()?
+                .pool_id
+        }
+        AccountType::PoolStats => {
+            let pool_stats_loader = AccountLoader::<PoolStatsState>::try_from(account_info)?;
+            let pool_stats = pool_stats_loader.load()?;
+            {
+                pool_stats.pool_id
+            }
+        }
+        AccountType::PoolMetadata => Account::<PoolMetadata>::try_from(account_info)?.pool_id,
+        AccountType::PoolCheckpoint => {
+            let pool_checkpoint_loader =
+                AccountLoader::<PoolCheckpointState>::try_from(account_info)?;
+            let pool_checkpoint = pool_checkpoint_loader.load()?;
+            {
+                pool_checkpoint.pool_id
+            }
+        }
+        AccountType::PriceFeed => {
+            let price_feed_loader = AccountLoader::<PriceFeed>::try_from(account_info)?;
+            let price_feed = price_feed_loader.load()?;
+            {
+                price_feed.pool_id
+            }
+        }
+        AccountType::TickArray => {
+            let tick_array_loader = AccountLoader::<TickArrayState>::try_from(account_info)?;
+            let tick_array = tick_array_loader.load()?;
+            require_eq!(
+                tick_array.initialized_tick_count,
+                0,
+                ErrorCode::TickArrayNotEmpty
+            );
+            tick_array.pool_id
+        }
+        AccountType::ProtocolPosition => {
+            Account::<ProtocolPositionState>::try_from(account_info)?.pool_id
+        }
+        AccountType::TwapSnapshot => Account::<TwapSnapshot>::try_from(account_info)?.pool_id,
+        AccountType::TraderStats => {
+            let trader_stats_loader = AccountLoader::<TraderStatsState>::try_from(account_info)?;
+            let trader_stats = trader_stats_loader.load()?;
+            {
+                trader_stats.pool_id
+            }
+        }
+        _ => return err!(ErrorCode::NotApproved),
+    };
+    Ok(pool_id)
+}
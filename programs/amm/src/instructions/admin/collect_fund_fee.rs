@@ -1,14 +1,22 @@
 use crate::decrease_liquidity::check_unclaimed_fees_and_vault;
+use crate::emit_event;
 use crate::error::ErrorCode;
 use crate::states::*;
 use crate::util::*;
 use anchor_lang::prelude::*;
 use anchor_spl::token::Token;
 use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 pub struct CollectFundFee<'info> {
-    /// Only admin or fund_owner can collect fee now
-    #[account(constraint = (owner.key() == amm_config.fund_owner || owner.key() == crate::admin::id()) @ ErrorCode::NotApproved)]
+    /// Only admin, fund_owner or the fund fee recipient of the pool can collect fee now
+    #[account(
+        constraint = (
+            owner.key() == amm_config.fund_owner
+                || owner.key() == crate::admin::id()
+                || owner.key() == pool_state.load()?.fund_fee_recipient
+        ) @ ErrorCode::NotApproved
+    )]
     pub owner: Signer<'info>,
 
     /// Pool state stores accumulated protocol fee amount
@@ -17,7 +25,7 @@ pub struct CollectFundFee<'info> {
 
     /// Amm config account stores fund_owner
     #[account(address = pool_state.load()?.amm_config)]
-    pub amm_config: Account<'info, AmmConfig>,
+    pub amm_config: Box<Account<'info, AmmConfig>>,
 
     /// The address that holds pool tokens for token_0
     #[account(
@@ -45,11 +53,13 @@ pub struct CollectFundFee<'info> {
     )]
     pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
 
-    /// The address that receives the collected token_0 protocol fees
+    /// The address that receives the collected token_0 protocol fees,
+    /// owned by the fund fee recipient of the pool if it is set
     #[account(mut)]
     pub recipient_token_account_0: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// The address that receives the collected token_1 protocol fees
+    /// The address that receives the collected token_1 protocol fees,
+    /// owned by the fund fee recipient of the pool if it is set
     #[account(mut)]
     pub recipient_token_account_1: Box<InterfaceAccount<'info, TokenAccount>>,
 
@@ -60,15 +70,29 @@ pub struct CollectFundFee<'info> {
     pub token_program_2022: Program<'info, Token2022>,
 }
 
-pub fn collect_fund_fee(
-    ctx: Context<CollectFundFee>,
+pub fn collect_fund_fee<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CollectFundFee<'info>>,
     amount_0_requested: u64,
     amount_1_requested: u64,
 ) -> Result<()> {
+    let vault_mints = [
+        ctx.accounts.vault_0_mint.to_account_info(),
+        ctx.accounts.vault_1_mint.to_account_info(),
+    ];
+    let (_, transfer_hook_accounts) =
+        split_transfer_hook_accounts(ctx.remaining_accounts, &vault_mints)?;
     let amount_0: u64;
     let amount_1: u64;
     {
         let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+        let fund_fee_recipient = pool_state.fund_fee_recipient;
+        if fund_fee_recipient != Pubkey::default() {
+            require!(
+                ctx.accounts.recipient_token_account_0.owner == fund_fee_recipient
+                    && ctx.accounts.recipient_token_account_1.owner == fund_fee_recipient,
+                ErrorCode::InvalidFundFeeRecipient
+            );
+        }
         amount_0 = amount_0_requested.min(pool_state.fund_fees_token_0);
         amount_1 = amount_1_requested.min(pool_state.fund_fees_token_1);
 
@@ -82,6 +106,7 @@ pub fn collect_fund_fee(
         Some(ctx.accounts.vault_0_mint.clone()),
         &ctx.accounts.token_program,
         Some(ctx.accounts.token_program_2022.to_account_info()),
+        transfer_hook_accounts,
         amount_0,
     )?;
 
@@ -92,6 +117,7 @@ pub fn collect_fund_fee(
         Some(ctx.accounts.vault_1_mint.clone()),
         &ctx.accounts.token_program,
         Some(ctx.accounts.token_program_2022.to_account_info()),
+        transfer_hook_accounts,
         amount_1,
     )?;
 
@@ -101,13 +127,16 @@ pub fn collect_fund_fee(
         &ctx.accounts.token_vault_1.to_account_info(),
     )?;
 
-    emit!(CollectProtocolFeeEvent {
-        pool_state: ctx.accounts.pool_state.key(),
-        recipient_token_account_0: ctx.accounts.recipient_token_account_0.key(),
-        recipient_token_account_1: ctx.accounts.recipient_token_account_1.key(),
-        amount_0,
-        amount_1,
-    });
+    emit_event!(
+        ctx,
+        CollectProtocolFeeEvent {
+            pool_state: ctx.accounts.pool_state.key(),
+            recipient_token_account_0: ctx.accounts.recipient_token_account_0.key(),
+            recipient_token_account_1: ctx.accounts.recipient_token_account_1.key(),
+            amount_0,
+            amount_1,
+        }
+    )?;
 
     Ok(())
 }
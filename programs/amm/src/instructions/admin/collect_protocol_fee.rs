@@ -1,15 +1,18 @@
 use crate::decrease_liquidity::check_unclaimed_fees_and_vault;
+use crate::emit_event;
 use crate::error::ErrorCode;
 use crate::states::*;
 use crate::util::*;
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
 use anchor_spl::token::Token;
 use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
 
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 pub struct CollectProtocolFee<'info> {
-    /// Only admin or config owner can collect fee now
-    #[account(constraint = (owner.key() == amm_config.owner || owner.key() == crate::admin::id()) @ ErrorCode::NotApproved)]
+    /// Admin or config owner can collect fee to any accounts, anyone else can only collect fee
+    /// to the associated token accounts of the config owner
     pub owner: Signer<'info>,
 
     /// Pool state stores accumulated protocol fee amount
@@ -20,7 +23,7 @@ pub struct CollectProtocolFee<'info> {
     #[account(
         address = pool_state.load()?.amm_config
     )]
-    pub amm_config: Account<'info, AmmConfig>,
+    pub amm_config: Box<Account<'info, AmmConfig>>,
 
     /// The address that holds pool tokens for token_0
     #[account(
@@ -61,13 +64,48 @@ pub struct CollectProtocolFee<'info> {
 
     /// The SPL program 2022 to perform token transfers
     pub token_program_2022: Program<'info, Token2022>,
+    // remaining accounts, required when amm config is a partner config
+    // #[account(
+    //     seeds = [
+    //         PARTNER_CONFIG_SEED.as_bytes(),
+    //         amm_config.key().as_ref(),
+    //     ],
+    //     bump
+    // )]
+    // pub partner_config: Account<'info, PartnerConfig>,
+    // #[account(mut, token::mint = token_vault_0.mint, token::authority = partner_config.partner_owner)]
+    // pub partner_token_account_0: Box<InterfaceAccount<'info, TokenAccount>>,
+    // #[account(mut, token::mint = token_vault_1.mint, token::authority = partner_config.partner_owner)]
+    // pub partner_token_account_1: Box<InterfaceAccount<'info, TokenAccount>>,
 }
 
-pub fn collect_protocol_fee(
-    ctx: Context<CollectProtocolFee>,
+pub fn collect_protocol_fee<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CollectProtocolFee<'info>>,
     amount_0_requested: u64,
     amount_1_requested: u64,
 ) -> Result<()> {
+    let vault_mints = [
+        ctx.accounts.vault_0_mint.to_account_info(),
+        ctx.accounts.vault_1_mint.to_account_info(),
+    ];
+    let (remaining_accounts, transfer_hook_accounts) =
+        split_transfer_hook_accounts(ctx.remaining_accounts, &vault_mints)?;
+    check_remaining_accounts_max(remaining_accounts, COLLECT_PROTOCOL_FEE_REMAINING_ACCOUNTS)?;
+    let config_owner = ctx.accounts.amm_config.owner;
+    if ctx.accounts.owner.key() != config_owner && ctx.accounts.owner.key() != crate::admin::id() {
+        require!(
+            is_associated_token_account(
+                &ctx.accounts.recipient_token_account_0,
+                &config_owner,
+                &ctx.accounts.vault_0_mint,
+            ) && is_associated_token_account(
+                &ctx.accounts.recipient_token_account_1,
+                &config_owner,
+                &ctx.accounts.vault_1_mint,
+            ),
+            ErrorCode::NotApproved
+        );
+    }
     let amount_0: u64;
     let amount_1: u64;
     {
@@ -85,6 +123,22 @@ pub fn collect_protocol_fee(
             .checked_sub(amount_1)
             .unwrap();
     }
+    let mut partner_amount_0 = 0;
+    let mut partner_amount_1 = 0;
+    if ctx.accounts.amm_config.is_partner_config() {
+        partner_amount_0 = ctx.accounts.amm_config.partner_fee_amount(amount_0);
+        partner_amount_1 = ctx.accounts.amm_config.partner_fee_amount(amount_1);
+        collect_partner_fee(
+            &ctx,
+            remaining_accounts,
+            transfer_hook_accounts,
+            partner_amount_0,
+            partner_amount_1,
+        )?;
+    }
+    let amount_0 = amount_0.checked_sub(partner_amount_0).unwrap();
+    let amount_1 = amount_1.checked_sub(partner_amount_1).unwrap();
+
     transfer_from_pool_vault_to_user(
         &ctx.accounts.pool_state,
         &ctx.accounts.token_vault_0.to_account_info(),
@@ -92,6 +146,7 @@ pub fn collect_protocol_fee(
         Some(ctx.accounts.vault_0_mint.clone()),
         &ctx.accounts.token_program,
         Some(ctx.accounts.token_program_2022.to_account_info()),
+        transfer_hook_accounts,
         amount_0,
     )?;
 
@@ -102,6 +157,7 @@ pub fn collect_protocol_fee(
         Some(ctx.accounts.vault_1_mint.clone()),
         &ctx.accounts.token_program,
         Some(ctx.accounts.token_program_2022.to_account_info()),
+        transfer_hook_accounts,
         amount_1,
     )?;
 
@@ -111,13 +167,94 @@ pub fn collect_protocol_fee(
         &ctx.accounts.token_vault_1.to_account_info(),
     )?;
 
-    emit!(CollectProtocolFeeEvent {
-        pool_state: ctx.accounts.pool_state.key(),
-        recipient_token_account_0: ctx.accounts.recipient_token_account_0.key(),
-        recipient_token_account_1: ctx.accounts.recipient_token_account_1.key(),
+    emit_event!(
+        ctx,
+        CollectProtocolFeeEvent {
+            pool_state: ctx.accounts.pool_state.key(),
+            recipient_token_account_0: ctx.accounts.recipient_token_account_0.key(),
+            recipient_token_account_1: ctx.accounts.recipient_token_account_1.key(),
+            amount_0,
+            amount_1,
+        }
+    )?;
+
+    Ok(())
+}
+
+/// Returns whether the token account is the associated token account of the wallet for the mint
+fn is_associated_token_account(
+    token_account: &InterfaceAccount<TokenAccount>,
+    wallet: &Pubkey,
+    mint: &InterfaceAccount<Mint>,
+) -> bool {
+    let token_program = mint.to_account_info().owner;
+    token_account.key()
+        == get_associated_token_address_with_program_id(wallet, &mint.key(), token_program)
+}
+
+fn collect_partner_fee<'a, 'b, 'c: 'info, 'info>(
+    ctx: &Context<'a, 'b, 'c, 'info, CollectProtocolFee<'info>>,
+    remaining_accounts: &'c [AccountInfo<'info>],
+    transfer_hook_accounts: &'c [AccountInfo<'info>],
+    amount_0: u64,
+    amount_1: u64,
+) -> Result<()> {
+    require_eq!(
+        remaining_accounts.len(),
+        COLLECT_PROTOCOL_FEE_REMAINING_ACCOUNTS,
+        ErrorCode::InvalidPartnerFeeAccounts
+    );
+    let partner_config = Account::<PartnerConfig>::try_from(&remaining_accounts[0])?;
+    require_keys_eq!(
+        partner_config.key(),
+        PartnerConfig::key(ctx.accounts.amm_config.key()),
+        ErrorCode::InvalidPartnerFeeAccounts
+    );
+    let partner_token_account_0 =
+        InterfaceAccount::<TokenAccount>::try_from(&remaining_accounts[1])?;
+    let partner_token_account_1 =
+        InterfaceAccount::<TokenAccount>::try_from(&remaining_accounts[2])?;
+    require!(
+        partner_token_account_0.mint == ctx.accounts.token_vault_0.mint
+            && partner_token_account_0.owner == partner_config.partner_owner
+            && partner_token_account_1.mint == ctx.accounts.token_vault_1.mint
+            && partner_token_account_1.owner == partner_config.partner_owner,
+        ErrorCode::InvalidPartnerFeeAccounts
+    );
+
+    transfer_from_pool_vault_to_user(
+        &ctx.accounts.pool_state,
+        &ctx.accounts.token_vault_0.to_account_info(),
+        &partner_token_account_0.to_account_info(),
+        Some(ctx.accounts.vault_0_mint.clone()),
+        &ctx.accounts.token_program,
+        Some(ctx.accounts.token_program_2022.to_account_info()),
+        transfer_hook_accounts,
         amount_0,
+    )?;
+
+    transfer_from_pool_vault_to_user(
+        &ctx.accounts.pool_state,
+        &ctx.accounts.token_vault_1.to_account_info(),
+        &partner_token_account_1.to_account_info(),
+        Some(ctx.accounts.vault_1_mint.clone()),
+        &ctx.accounts.token_program,
+        Some(ctx.accounts.token_program_2022.to_account_info()),
+        transfer_hook_accounts,
         amount_1,
-    });
+    )?;
+
+    emit_event!(
+        ctx,
+        CollectPartnerFeeEvent {
+            pool_state: ctx.accounts.pool_state.key(),
+            partner_owner: partner_config.partner_owner,
+            recipient_token_account_0: partner_token_account_0.key(),
+            recipient_token_account_1: partner_token_account_1.key(),
+            amount_0,
+            amount_1,
+        }
+    )?;
 
     Ok(())
 }
@@ -1,8 +1,10 @@
+use crate::emit_event;
 use crate::error::ErrorCode;
 use crate::states::*;
 use anchor_lang::prelude::*;
 use std::ops::DerefMut;
 
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 #[instruction(index: u16)]
 pub struct CreateAmmConfig<'info> {
@@ -24,7 +26,19 @@ pub struct CreateAmmConfig<'info> {
         payer = owner,
         space = AmmConfig::LEN
     )]
-    pub amm_config: Account<'info, AmmConfig>,
+    pub amm_config: Box<Account<'info, AmmConfig>>,
+
+    /// The admin heartbeat, initialized with the first amm config so that it always exists
+    #[account(
+        init_if_needed,
+        seeds = [
+            ADMIN_HEARTBEAT_SEED.as_bytes(),
+        ],
+        bump,
+        payer = owner,
+        space = AdminHeartbeat::LEN
+    )]
+    pub admin_heartbeat: Box<Account<'info, AdminHeartbeat>>,
 
     pub system_program: Program<'info, System>,
 }
@@ -46,16 +60,25 @@ pub fn create_amm_config(
     amm_config.tick_spacing = tick_spacing;
     amm_config.fund_fee_rate = fund_fee_rate;
     amm_config.fund_owner = ctx.accounts.owner.key();
+    // creating an amm config proves the admin is active
+    ctx.accounts.admin_heartbeat.refresh(
+        ctx.bumps.admin_heartbeat,
+        Clock::get()?.unix_timestamp as u64,
+        0,
+    )?;
 
-    emit!(ConfigChangeEvent {
-        index: amm_config.index,
-        owner: ctx.accounts.owner.key(),
-        protocol_fee_rate: amm_config.protocol_fee_rate,
-        trade_fee_rate: amm_config.trade_fee_rate,
-        tick_spacing: amm_config.tick_spacing,
-        fund_fee_rate: amm_config.fund_fee_rate,
-        fund_owner: amm_config.fund_owner,
-    });
+    emit_event!(
+        ctx,
+        ConfigChangeEvent {
+            index: amm_config.index,
+            owner: ctx.accounts.owner.key(),
+            protocol_fee_rate: amm_config.protocol_fee_rate,
+            trade_fee_rate: amm_config.trade_fee_rate,
+            tick_spacing: amm_config.tick_spacing,
+            fund_fee_rate: amm_config.fund_fee_rate,
+            fund_owner: amm_config.fund_owner,
+        }
+    )?;
 
     Ok(())
 }
@@ -0,0 +1,53 @@
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct CreateAmmConfigExtension<'info> {
+    /// Only admin can create the extension of amm config
+    #[account(
+        mut,
+        address = crate::admin::id() @ ErrorCode::NotApproved
+    )]
+    pub owner: Signer<'info>,
+
+    /// The amm config to extend
+    pub amm_config: Box<Account<'info, AmmConfig>>,
+
+    /// Stores the parameters of amm config which don't fit in its layout
+    #[account(
+        init,
+        seeds = [
+            AMM_CONFIG_EXTENSION_SEED.as_bytes(),
+            amm_config.key().as_ref()
+        ],
+        bump,
+        payer = owner,
+        space = AmmConfigExtension::LEN
+    )]
+    pub amm_config_extension: Box<Account<'info, AmmConfigExtension>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_amm_config_extension(ctx: Context<CreateAmmConfigExtension>) -> Result<()> {
+    let amm_config_extension = &mut ctx.accounts.amm_config_extension;
+    amm_config_extension.bump = ctx.bumps.amm_config_extension;
+    amm_config_extension.version = AmmConfigExtension::VERSION;
+    amm_config_extension.amm_config = ctx.accounts.amm_config.key();
+
+    emit_event!(
+        ctx,
+        AmmConfigExtensionChangeEvent {
+            amm_config: amm_config_extension.amm_config,
+            version: amm_config_extension.version,
+            min_dynamic_fee_rate: amm_config_extension.min_dynamic_fee_rate,
+            max_dynamic_fee_rate: amm_config_extension.max_dynamic_fee_rate,
+            treasury: amm_config_extension.treasury,
+        }
+    )?;
+
+    Ok(())
+}
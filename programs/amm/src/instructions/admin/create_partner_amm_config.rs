@@ -0,0 +1,98 @@
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use std::ops::DerefMut;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+#[instruction(index: u16)]
+pub struct CreatePartnerAmmConfig<'info> {
+    /// Only admin can approve a partner.
+    #[account(
+        mut,
+        address = crate::admin::id() @ ErrorCode::NotApproved
+    )]
+    pub owner: Signer<'info>,
+
+    /// CHECK: The approved partner, receives the partner part of protocol fee
+    pub partner_owner: UncheckedAccount<'info>,
+
+    /// Initialize config state account to store protocol owner address and fee rates.
+    #[account(
+        init,
+        seeds = [
+            AMM_CONFIG_SEED.as_bytes(),
+            &index.to_be_bytes()
+        ],
+        bump,
+        payer = owner,
+        space = AmmConfig::LEN
+    )]
+    pub amm_config: Box<Account<'info, AmmConfig>>,
+
+    /// Initialize partner config account to store the partner owner.
+    #[account(
+        init,
+        seeds = [
+            PARTNER_CONFIG_SEED.as_bytes(),
+            amm_config.key().as_ref()
+        ],
+        bump,
+        payer = owner,
+        space = PartnerConfig::LEN
+    )]
+    pub partner_config: Box<Account<'info, PartnerConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_partner_amm_config(
+    ctx: Context<CreatePartnerAmmConfig>,
+    index: u16,
+    tick_spacing: u16,
+    trade_fee_rate: u32,
+    protocol_fee_rate: u32,
+    fund_fee_rate: u32,
+    partner_fee_rate: u32,
+) -> Result<()> {
+    let amm_config = ctx.accounts.amm_config.deref_mut();
+    amm_config.owner = ctx.accounts.owner.key();
+    amm_config.bump = ctx.bumps.amm_config;
+    amm_config.index = index;
+    amm_config.trade_fee_rate = trade_fee_rate;
+    amm_config.protocol_fee_rate = protocol_fee_rate;
+    amm_config.tick_spacing = tick_spacing;
+    amm_config.fund_fee_rate = fund_fee_rate;
+    amm_config.fund_owner = ctx.accounts.owner.key();
+    amm_config.partner_fee_rate = partner_fee_rate;
+
+    let partner_config = ctx.accounts.partner_config.deref_mut();
+    partner_config.bump = ctx.bumps.partner_config;
+    partner_config.amm_config = ctx.accounts.amm_config.key();
+    partner_config.partner_owner = ctx.accounts.partner_owner.key();
+
+    let amm_config = &ctx.accounts.amm_config;
+    emit_event!(
+        ctx,
+        ConfigChangeEvent {
+            index: amm_config.index,
+            owner: amm_config.owner,
+            protocol_fee_rate: amm_config.protocol_fee_rate,
+            trade_fee_rate: amm_config.trade_fee_rate,
+            tick_spacing: amm_config.tick_spacing,
+            fund_fee_rate: amm_config.fund_fee_rate,
+            fund_owner: amm_config.fund_owner,
+        }
+    )?;
+    emit_event!(
+        ctx,
+        PartnerConfigCreatedEvent {
+            amm_config: amm_config.key(),
+            partner_owner: ctx.accounts.partner_owner.key(),
+            partner_fee_rate,
+        }
+    )?;
+
+    Ok(())
+}
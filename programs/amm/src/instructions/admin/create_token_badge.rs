@@ -0,0 +1,54 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+#[derive(Accounts)]
+pub struct CreateTokenBadge<'info> {
+    /// Only admin or operation owner can issue token badge
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// load info from the account to judge the authority
+    #[account(
+        seeds = [
+            OPERATION_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub operation_state: AccountLoader<'info, OperationState>,
+
+    /// The mint to be vetted
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Initialize token badge account of the mint
+    #[account(
+        init,
+        seeds = [
+            TOKEN_BADGE_SEED.as_bytes(),
+            token_mint.key().as_ref(),
+        ],
+        bump,
+        payer = authority,
+        space = TokenBadge::LEN
+    )]
+    pub token_badge: Box<Account<'info, TokenBadge>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_token_badge(ctx: Context<CreateTokenBadge>) -> Result<()> {
+    require!(
+        ctx.accounts.authority.key() == crate::admin::id()
+            || ctx
+                .accounts
+                .operation_state
+                .load()?
+                .validate_operation_owner(ctx.accounts.authority.key()),
+        ErrorCode::NotApproved
+    );
+    let token_badge = &mut ctx.accounts.token_badge;
+    token_badge.bump = ctx.bumps.token_badge;
+    token_badge.token_mint = ctx.accounts.token_mint.key();
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::util::{
+    check_remaining_accounts_min, create_or_allocate_account, CREATE_TOKEN_BADGES_GROUP_ACCOUNTS,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::Mint;
+
+#[derive(Accounts)]
+pub struct CreateTokenBadges<'info> {
+    /// Only admin or operation owner can issue token badges
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// load info from the account to judge the authority
+    #[account(
+        seeds = [
+            OPERATION_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub operation_state: AccountLoader<'info, OperationState>,
+
+    pub system_program: Program<'info, System>,
+    // remaining accounts, for each mint in order:
+    // token_mint, token_badge of the mint to be initialized
+}
+
+pub fn create_token_badges<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CreateTokenBadges<'info>>,
+) -> Result<()> {
+    require!(
+        ctx.accounts.authority.key() == crate::admin::id()
+            || ctx
+                .accounts
+                .operation_state
+                .load()?
+                .validate_operation_owner(ctx.accounts.authority.key()),
+        ErrorCode::NotApproved
+    );
+    let mut remaining_accounts: &'c [AccountInfo<'info>] = ctx.remaining_accounts;
+    check_remaining_accounts_min(remaining_accounts, CREATE_TOKEN_BADGES_GROUP_ACCOUNTS)?;
+    while !remaining_accounts.is_empty() {
+        check_remaining_accounts_min(remaining_accounts, CREATE_TOKEN_BADGES_GROUP_ACCOUNTS)?;
+        let (group, rest) = remaining_accounts.split_at(CREATE_TOKEN_BADGES_GROUP_ACCOUNTS);
+        remaining_accounts = rest;
+        let token_mint = InterfaceAccount::<Mint>::try_from(&group[0])?;
+        let token_badge_info = &group[1];
+
+        let (token_badge_key, bump) = Pubkey::find_program_address(
+            &[TOKEN_BADGE_SEED.as_bytes(), token_mint.key().as_ref()],
+            &crate::id(),
+        );
+        require_keys_eq!(token_badge_info.key(), token_badge_key);
+        // the token badge must be new, like the account of create_token_badge
+        require_keys_eq!(*token_badge_info.owner, system_program::ID);
+        create_or_allocate_account(
+            &crate::id(),
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            token_badge_info.clone(),
+            &[
+                TOKEN_BADGE_SEED.as_bytes(),
+                token_mint.key().as_ref(),
+                &[bump],
+            ],
+            TokenBadge::LEN,
+        )?;
+        let token_badge = TokenBadge {
+            bump,
+            token_mint: token_mint.key(),
+            ..Default::default()
+        };
+        token_badge.try_serialize(&mut &mut token_badge_info.try_borrow_mut_data()?[..])?;
+    }
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(creator: Pubkey)]
+pub struct CreateWhitelistCreator<'info> {
+    /// Only admin can whitelist pool creator
+    #[account(
+        mut,
+        address = crate::admin::id() @ ErrorCode::NotApproved
+    )]
+    pub owner: Signer<'info>,
+
+    /// Initialize whitelist creator account of the creator
+    #[account(
+        init,
+        seeds = [
+            WHITELIST_CREATOR_SEED.as_bytes(),
+            creator.as_ref(),
+        ],
+        bump,
+        payer = owner,
+        space = WhitelistCreator::LEN
+    )]
+    pub whitelist_creator: Box<Account<'info, WhitelistCreator>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_whitelist_creator(
+    ctx: Context<CreateWhitelistCreator>,
+    creator: Pubkey,
+) -> Result<()> {
+    let whitelist_creator = &mut ctx.accounts.whitelist_creator;
+    whitelist_creator.bump = ctx.bumps.whitelist_creator;
+    whitelist_creator.creator = creator;
+    Ok(())
+}
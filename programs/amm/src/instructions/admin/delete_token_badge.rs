@@ -0,0 +1,44 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct DeleteTokenBadge<'info> {
+    /// Only admin or operation owner can revoke token badge
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// load info from the account to judge the authority
+    #[account(
+        seeds = [
+            OPERATION_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub operation_state: AccountLoader<'info, OperationState>,
+
+    /// The token badge to be revoked, existing pools are not affected
+    #[account(
+        mut,
+        seeds = [
+            TOKEN_BADGE_SEED.as_bytes(),
+            token_badge.token_mint.as_ref(),
+        ],
+        bump = token_badge.bump,
+        close = authority
+    )]
+    pub token_badge: Box<Account<'info, TokenBadge>>,
+}
+
+pub fn delete_token_badge(ctx: Context<DeleteTokenBadge>) -> Result<()> {
+    require!(
+        ctx.accounts.authority.key() == crate::admin::id()
+            || ctx
+                .accounts
+                .operation_state
+                .load()?
+                .validate_operation_owner(ctx.accounts.authority.key()),
+        ErrorCode::NotApproved
+    );
+    Ok(())
+}
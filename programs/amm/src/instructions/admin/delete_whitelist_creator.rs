@@ -0,0 +1,29 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct DeleteWhitelistCreator<'info> {
+    /// Only admin can remove pool creator from whitelist
+    #[account(
+        mut,
+        address = crate::admin::id() @ ErrorCode::NotApproved
+    )]
+    pub owner: Signer<'info>,
+
+    /// The whitelist creator account to be closed, the pools already created are not affected
+    #[account(
+        mut,
+        seeds = [
+            WHITELIST_CREATOR_SEED.as_bytes(),
+            whitelist_creator.creator.as_ref(),
+        ],
+        bump = whitelist_creator.bump,
+        close = owner
+    )]
+    pub whitelist_creator: Box<Account<'info, WhitelistCreator>>,
+}
+
+pub fn delete_whitelist_creator(_ctx: Context<DeleteWhitelistCreator>) -> Result<()> {
+    Ok(())
+}
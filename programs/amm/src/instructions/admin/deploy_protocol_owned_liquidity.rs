@@ -0,0 +1,177 @@
+use crate::error::ErrorCode;
+use crate::increase_liquidity::increase_personal_position;
+use crate::open_position::add_liquidity_to_ticks;
+use crate::states::*;
+use crate::util::*;
+use anchor_lang::prelude::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct DeployProtocolOwnedLiquidity<'info> {
+    /// Only admin or fund_owner can deploy the fund fees
+    #[account(
+        constraint = (
+            owner.key() == amm_config.fund_owner || owner.key() == crate::admin::id()
+        ) @ ErrorCode::NotApproved
+    )]
+    pub owner: Signer<'info>,
+
+    /// Pool state stores accumulated fund fee amount
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// Amm config account stores fund_owner
+    #[account(address = pool_state.load()?.amm_config)]
+    pub amm_config: Box<Account<'info, AmmConfig>>,
+
+    /// CHECK: The PDA owning the protocol owned positions of the pool
+    #[account(
+        seeds = [PROTOCOL_OWNED_LIQUIDITY_SEED.as_bytes(), pool_state.key().as_ref()],
+        bump,
+    )]
+    pub protocol_owner: UncheckedAccount<'info>,
+
+    /// CHECK: The token account for the position nft or the MPL Core asset of the position,
+    /// owned by the protocol owner
+    #[account(
+        constraint = get_position_nft_owner(
+            &nft_account,
+            &personal_position.nft_mint
+        )? == protocol_owner.key() @ ErrorCode::NotApproved
+    )]
+    pub nft_account: UncheckedAccount<'info>,
+
+    /// The protocol owned position to increase
+    #[account(mut, constraint = personal_position.pool_id == pool_state.key())]
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
+
+    /// Stores init state for the lower tick
+    #[account(mut, constraint = tick_array_lower.load()?.pool_id == pool_state.key())]
+    pub tick_array_lower: AccountLoader<'info, TickArrayState>,
+
+    /// Stores init state for the upper tick
+    #[account(mut, constraint = tick_array_upper.load()?.pool_id == pool_state.key())]
+    pub tick_array_upper: AccountLoader<'info, TickArrayState>,
+    // remaining accounts are the same as increase_liquidity
+}
+
+pub fn deploy_protocol_owned_liquidity<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, DeployProtocolOwnedLiquidity<'info>>,
+    liquidity: u128,
+    amount_0_max: u64,
+    amount_1_max: u64,
+) -> Result<()> {
+    require_gt!(liquidity, 0, ErrorCode::InvaildLiquidity);
+    let remaining_accounts = ctx.remaining_accounts;
+    check_remaining_accounts_max(remaining_accounts, MAX_LIQUIDITY_REMAINING_ACCOUNTS)?;
+    let event_authority = crate::event_authority!(ctx.accounts, ctx.bumps);
+    let pool_state_loader = &ctx.accounts.pool_state;
+    let personal_position = &mut ctx.accounts.personal_position;
+    let pool_state = &mut pool_state_loader.load_mut()?;
+    if !pool_state.get_status_by_bit(PoolStatusBitIndex::OpenPositionOrIncreaseLiquidity) {
+        return err!(ErrorCode::NotApproved);
+    }
+    let tick_lower = personal_position.tick_lower_index;
+    let tick_upper = personal_position.tick_upper_index;
+
+    let use_tickarray_bitmap_extension =
+        pool_state.is_overflow_default_tickarray_bitmap(vec![tick_lower, tick_upper]);
+    let tickarray_bitmap_extension = if use_tickarray_bitmap_extension {
+        let tickarray_bitmap_extension = remaining_accounts
+            .first()
+            .ok_or(ErrorCode::NotEnoughRemainingAccounts)?;
+        require_keys_eq!(
+            tickarray_bitmap_extension.key(),
+            TickArrayBitmapExtension::key(pool_state_loader.key())
+        );
+        Some(tickarray_bitmap_extension)
+    } else {
+        None
+    };
+    let (reward_info_extension_info, position_reward_extension_info) =
+        find_reward_extension_accounts(
+            remaining_accounts,
+            pool_state_loader.key(),
+            pool_state,
+            Some(personal_position.nft_mint),
+        )?;
+    let mut reward_info_extension = reward_info_extension_info
+        .map(|account_info| AccountLoad::<RewardInfoExtension>::load_data_mut(account_info))
+        .transpose()?;
+    let mut position_reward_extension = position_reward_extension_info
+        .map(|account_info| AccountLoad::<PositionRewardExtension>::load_data_mut(account_info))
+        .transpose()?;
+
+    let liquidity_before = pool_state.liquidity;
+    let mut growths_inside = PositionGrowthsInside::default();
+    // the tokens backing the liquidity are already in the vaults as the fund fees,
+    // so the fund fees are deducted instead of transferring from the owner
+    let (amount_0, amount_1) = add_liquidity_to_ticks(
+        &AccountLoad::<TickArrayState>::try_from(&ctx.accounts.tick_array_lower.to_account_info())?,
+        &AccountLoad::<TickArrayState>::try_from(&ctx.accounts.tick_array_upper.to_account_info())?,
+        &mut growths_inside,
+        tickarray_bitmap_extension,
+        reward_info_extension.as_deref_mut(),
+        pool_state,
+        liquidity,
+        tick_lower,
+        tick_upper,
+        event_authority,
+    )?;
+    require_gte!(amount_0_max, amount_0, ErrorCode::PriceSlippageCheck);
+    require_gte!(amount_1_max, amount_1, ErrorCode::PriceSlippageCheck);
+    require_gte!(
+        pool_state.fund_fees_token_0,
+        amount_0,
+        ErrorCode::InsufficientFundFees
+    );
+    require_gte!(
+        pool_state.fund_fees_token_1,
+        amount_1,
+        ErrorCode::InsufficientFundFees
+    );
+    pool_state.fund_fees_token_0 = pool_state.fund_fees_token_0.checked_sub(amount_0).unwrap();
+    pool_state.fund_fees_token_1 = pool_state.fund_fees_token_1.checked_sub(amount_1).unwrap();
+
+    increase_personal_position(
+        personal_position,
+        &growths_inside,
+        position_reward_extension.as_deref_mut(),
+        liquidity,
+    )?;
+
+    event_authority.emit(LiquidityChangeEvent {
+        pool_state: pool_state_loader.key(),
+        tick: pool_state.tick_current,
+        tick_lower,
+        tick_upper,
+        liquidity_before,
+        liquidity_after: pool_state.liquidity,
+    })?;
+    event_authority.emit(DeployProtocolOwnedLiquidityEvent {
+        pool_state: pool_state_loader.key(),
+        position_nft_mint: personal_position.nft_mint,
+        liquidity,
+        amount_0,
+        amount_1,
+    })?;
+
+    Ok(())
+}
+
+/// Emitted when fund fees are deployed into a protocol owned position
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct DeployProtocolOwnedLiquidityEvent {
+    /// The pool of the fund fees
+    #[index]
+    pub pool_state: Pubkey,
+    /// The protocol owned position increased
+    pub position_nft_mint: Pubkey,
+    /// The liquidity added to the position
+    pub liquidity: u128,
+    /// The amount of token_0 fund fees deployed
+    pub amount_0: u64,
+    /// The amount of token_1 fund fees deployed
+    pub amount_1: u64,
+}
@@ -0,0 +1,79 @@
+use super::{set_amm_config_param, set_reward_owner};
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::util::AccountLoad;
+use anchor_lang::prelude::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct ExecuteAdminAction<'info> {
+    /// Only admin can execute an action, receives the rent of the action account
+    #[account(
+        mut,
+        address = crate::admin::id() @ ErrorCode::NotApproved
+    )]
+    pub owner: Signer<'info>,
+
+    /// The timelock holds the delay
+    #[account(
+        mut,
+        seeds = [
+            ADMIN_TIMELOCK_SEED.as_bytes(),
+        ],
+        bump = admin_timelock.bump,
+    )]
+    pub admin_timelock: Box<Account<'info, AdminTimelock>>,
+
+    /// CHECK: The account to be changed, checked by the queued action
+    #[account(
+        mut,
+        address = admin_action.target
+    )]
+    pub target: UncheckedAccount<'info>,
+
+    /// The queued action, closed after execution
+    #[account(
+        mut,
+        close = owner
+    )]
+    pub admin_action: Box<Account<'info, AdminActionState>>,
+}
+
+pub fn execute_admin_action(ctx: Context<ExecuteAdminAction>) -> Result<()> {
+    let admin_action = &ctx.accounts.admin_action;
+    require!(
+        admin_action.is_executable(Clock::get()?.unix_timestamp as u64),
+        ErrorCode::AdminActionNotReady
+    );
+    let target = ctx.accounts.target.to_account_info();
+    let match_action = Some(admin_action.action);
+    match match_action {
+        Some(ADMIN_ACTION_UPDATE_AMM_CONFIG) => {
+            require_keys_eq!(*target.owner, crate::id());
+            let mut amm_config = AmmConfig::try_deserialize(&mut &target.try_borrow_data()?[..])?;
+            set_amm_config_param(
+                &mut amm_config,
+                admin_action.param,
+                admin_action.value,
+                crate::event_authority!(ctx.accounts, ctx.bumps),
+            )?;
+            amm_config.try_serialize(&mut &mut target.try_borrow_mut_data()?[..])?;
+        }
+        Some(ADMIN_ACTION_UPDATE_POOL_STATUS) => {
+            AccountLoad::<PoolState>::load_data_mut(&target)?
+                .set_status(u8::try_from(admin_action.value).unwrap());
+        }
+        Some(ADMIN_ACTION_TRANSFER_REWARD_OWNER) => {
+            set_reward_owner(
+                &mut *AccountLoad::<PoolState>::load_data_mut(&target)?,
+                admin_action.new_key,
+            );
+        }
+        Some(ADMIN_ACTION_UPDATE_TIMELOCK_DELAY) => {
+            // the target is the timelock itself, update it through the typed account
+            ctx.accounts.admin_timelock.delay = admin_action.value;
+        }
+        _ => return err!(ErrorCode::InvalidUpdateConfigFlag),
+    }
+    Ok(())
+}
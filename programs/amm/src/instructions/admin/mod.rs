@@ -1,23 +1,98 @@
 pub mod create_amm_config;
 pub use create_amm_config::*;
 
+pub mod create_partner_amm_config;
+pub use create_partner_amm_config::*;
+
 pub mod update_amm_config;
 pub use update_amm_config::*;
 
+pub mod propose_ownership;
+pub use propose_ownership::*;
+
+pub mod accept_ownership;
+pub use accept_ownership::*;
+
 pub mod collect_protocol_fee;
 pub use collect_protocol_fee::*;
 
 pub mod collect_fund_fee;
 pub use collect_fund_fee::*;
 
+pub mod set_fund_fee_recipient;
+pub use set_fund_fee_recipient::*;
+
+pub mod sweep_dust;
+pub use sweep_dust::*;
+
 pub mod create_operation_account;
 pub use create_operation_account::*;
 
 pub mod update_operation_account;
 pub use update_operation_account::*;
 
+pub mod resize_operation_account;
+pub use resize_operation_account::*;
+
 pub mod transfer_reward_owner;
 pub use transfer_reward_owner::*;
 
 pub mod update_pool_status;
 pub use update_pool_status::*;
+
+pub mod update_pool_price_band;
+pub use update_pool_price_band::*;
+
+pub mod refresh_admin_heartbeat;
+pub use refresh_admin_heartbeat::*;
+
+pub mod update_admin_timelock;
+pub use update_admin_timelock::*;
+
+pub mod queue_admin_action;
+pub use queue_admin_action::*;
+
+pub mod execute_admin_action;
+pub use execute_admin_action::*;
+
+pub mod create_token_badge;
+pub use create_token_badge::*;
+
+pub mod create_token_badges;
+pub use create_token_badges::*;
+
+pub mod delete_token_badge;
+pub use delete_token_badge::*;
+
+pub mod set_token_badge_config;
+pub use set_token_badge_config::*;
+
+pub mod create_whitelist_creator;
+pub use create_whitelist_creator::*;
+
+pub mod delete_whitelist_creator;
+pub use delete_whitelist_creator::*;
+
+pub mod close_pool;
+pub use close_pool::*;
+
+pub mod reset_sqrt_price;
+pub use reset_sqrt_price::*;
+
+pub mod set_swap_hook;
+pub use set_swap_hook::*;
+
+pub mod deploy_protocol_owned_liquidity;
+pub use deploy_protocol_owned_liquidity::*;
+
+pub mod withdraw_protocol_owned_liquidity;
+pub use withdraw_protocol_owned_liquidity::*;
+
+pub mod create_amm_config_extension;
+pub use create_amm_config_extension::*;
+
+pub mod update_amm_config_extension;
+pub use update_amm_config_extension::*;
+
+pub mod set_external_oracle;
+pub use set_external_oracle::*;
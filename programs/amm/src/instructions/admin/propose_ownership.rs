@@ -0,0 +1,67 @@
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct ProposeOwnership<'info> {
+    /// Only admin can propose a new owner
+    #[account(
+        mut,
+        address = crate::admin::id() @ ErrorCode::NotApproved
+    )]
+    pub owner: Signer<'info>,
+
+    /// Amm config account to be transferred
+    pub amm_config: Box<Account<'info, AmmConfig>>,
+
+    /// CHECK: The proposed owner, must accept the ownership to take effect
+    pub new_owner: UncheckedAccount<'info>,
+
+    /// Store the proposed owners until they accept
+    #[account(
+        init_if_needed,
+        seeds = [
+            AMM_CONFIG_PENDING_OWNER_SEED.as_bytes(),
+            amm_config.key().as_ref()
+        ],
+        bump,
+        payer = owner,
+        space = AmmConfigPendingOwner::LEN
+    )]
+    pub pending_owner: Box<Account<'info, AmmConfigPendingOwner>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn propose_ownership(ctx: Context<ProposeOwnership>, param: u8) -> Result<()> {
+    let new_owner = ctx.accounts.new_owner.key();
+    let pending_owner = &mut ctx.accounts.pending_owner;
+    pending_owner.bump = ctx.bumps.pending_owner;
+    pending_owner.amm_config = ctx.accounts.amm_config.key();
+    let match_param = Some(param);
+    match match_param {
+        Some(3) => pending_owner.pending_owner = new_owner,
+        Some(4) => pending_owner.pending_fund_owner = new_owner,
+        _ => return err!(ErrorCode::InvalidUpdateConfigFlag),
+    }
+    #[cfg(feature = "enable-log")]
+    msg!(
+        "amm_config:{}, pending_owner:{}, pending_fund_owner:{}",
+        pending_owner.amm_config.to_string(),
+        pending_owner.pending_owner.to_string(),
+        pending_owner.pending_fund_owner.to_string()
+    );
+
+    emit_event!(
+        ctx,
+        OwnershipProposedEvent {
+            amm_config: pending_owner.amm_config,
+            pending_owner: pending_owner.pending_owner,
+            pending_fund_owner: pending_owner.pending_fund_owner,
+        }
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::util::AccountLoad;
+use anchor_lang::prelude::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+#[instruction(action: u8, param: u8)]
+pub struct QueueAdminAction<'info> {
+    /// Only admin can queue an action
+    #[account(
+        mut,
+        address = crate::admin::id() @ ErrorCode::NotApproved
+    )]
+    pub owner: Signer<'info>,
+
+    /// The timelock holds the delay
+    #[account(
+        seeds = [
+            ADMIN_TIMELOCK_SEED.as_bytes(),
+        ],
+        bump = admin_timelock.bump,
+    )]
+    pub admin_timelock: Box<Account<'info, AdminTimelock>>,
+
+    /// CHECK: The account to be changed, amm config, pool state or the timelock itself,
+    /// checked by the action
+    pub target: UncheckedAccount<'info>,
+
+    /// Queue the action, a queued action with the same target and kind is replaced
+    #[account(
+        init_if_needed,
+        seeds = [
+            ADMIN_ACTION_SEED.as_bytes(),
+            target.key().as_ref(),
+            &[action, param],
+        ],
+        bump,
+        payer = owner,
+        space = AdminActionState::LEN
+    )]
+    pub admin_action: Box<Account<'info, AdminActionState>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn queue_admin_action(
+    ctx: Context<QueueAdminAction>,
+    action: u8,
+    param: u8,
+    value: u64,
+    new_key: Pubkey,
+) -> Result<()> {
+    let target = ctx.accounts.target.to_account_info();
+    let match_action = Some(action);
+    match match_action {
+        Some(ADMIN_ACTION_UPDATE_AMM_CONFIG) => {
+            require_keys_eq!(*target.owner, crate::id());
+            AmmConfig::try_deserialize(&mut &target.try_borrow_data()?[..])?;
+            require!(
+                TIMELOCKED_AMM_CONFIG_PARAMS.contains(&param),
+                ErrorCode::InvalidUpdateConfigFlag
+            );
+        }
+        Some(ADMIN_ACTION_UPDATE_POOL_STATUS) => {
+            AccountLoad::<PoolState>::try_from(&target)?;
+            require_gte!(255, value);
+        }
+        Some(ADMIN_ACTION_TRANSFER_REWARD_OWNER) => {
+            AccountLoad::<PoolState>::try_from(&target)?;
+        }
+        Some(ADMIN_ACTION_UPDATE_TIMELOCK_DELAY) => {
+            require_keys_eq!(target.key(), ctx.accounts.admin_timelock.key());
+            require_gte!(MAX_ADMIN_TIMELOCK_DELAY, value);
+        }
+        _ => return err!(ErrorCode::InvalidUpdateConfigFlag),
+    }
+
+    let executable_after = (Clock::get()?.unix_timestamp as u64)
+        .checked_add(ctx.accounts.admin_timelock.delay)
+        .unwrap();
+    let admin_action = &mut ctx.accounts.admin_action;
+    admin_action.bump = ctx.bumps.admin_action;
+    admin_action.action = action;
+    admin_action.param = param;
+    admin_action.target = target.key();
+    admin_action.value = value;
+    admin_action.new_key = new_key;
+    admin_action.executable_after = executable_after;
+
+    emit_event!(
+        ctx,
+        AdminActionQueuedEvent {
+            action,
+            param,
+            target: target.key(),
+            value,
+            new_key,
+            executable_after,
+        }
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RefreshAdminHeartbeat<'info> {
+    /// Only admin can refresh the heartbeat
+    #[account(
+        mut,
+        address = crate::admin::id() @ ErrorCode::NotApproved
+    )]
+    pub owner: Signer<'info>,
+
+    /// Initialize the heartbeat account at the first refresh
+    #[account(
+        init_if_needed,
+        seeds = [
+            ADMIN_HEARTBEAT_SEED.as_bytes(),
+        ],
+        bump,
+        payer = owner,
+        space = AdminHeartbeat::LEN
+    )]
+    pub admin_heartbeat: Box<Account<'info, AdminHeartbeat>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn refresh_admin_heartbeat(
+    ctx: Context<RefreshAdminHeartbeat>,
+    expire_duration: u64,
+) -> Result<()> {
+    ctx.accounts.admin_heartbeat.refresh(
+        ctx.bumps.admin_heartbeat,
+        Clock::get()?.unix_timestamp as u64,
+        expire_duration,
+    )
+}
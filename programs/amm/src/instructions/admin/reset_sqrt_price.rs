@@ -0,0 +1,77 @@
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct ResetSqrtPrice<'info> {
+    /// Only admin or operation owner can reset the price
+    pub authority: Signer<'info>,
+
+    /// load info from the account to judge the authority
+    #[account(
+        seeds = [
+            OPERATION_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub operation_state: AccountLoader<'info, OperationState>,
+
+    /// The mispriced pool
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// The address that holds pool tokens for token_0, must be empty
+    #[account(address = pool_state.load()?.token_vault_0)]
+    pub token_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The address that holds pool tokens for token_1, must be empty
+    #[account(address = pool_state.load()?.token_vault_1)]
+    pub token_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: The observation account of the pool, cleared with the price reset
+    #[account(
+        mut,
+        address = pool_state.load()?.observation_key
+    )]
+    pub observation_state: UncheckedAccount<'info>,
+}
+
+pub fn reset_sqrt_price(ctx: Context<ResetSqrtPrice>, sqrt_price_x64: u128) -> Result<()> {
+    require!(
+        ctx.accounts.authority.key() == crate::admin::id()
+            || ctx
+                .accounts
+                .operation_state
+                .load()?
+                .validate_operation_owner(ctx.accounts.authority.key()),
+        ErrorCode::NotApproved
+    );
+    require!(
+        ctx.accounts.token_vault_0.amount == 0 && ctx.accounts.token_vault_1.amount == 0,
+        ErrorCode::PoolNotDrained
+    );
+
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    let sqrt_price_x64_before = pool_state.sqrt_price_x64;
+    pool_state.reset_sqrt_price(sqrt_price_x64)?;
+
+    let observation_info = ctx.accounts.observation_state.to_account_info();
+    let mut observation_state = ObservationStateMut::load_mut(&observation_info)?;
+    observation_state
+        .state
+        .reset(&mut observation_state.extension);
+
+    emit_event!(
+        ctx,
+        PoolSqrtPriceResetEvent {
+            pool_state: ctx.accounts.pool_state.key(),
+            sqrt_price_x64_before,
+            sqrt_price_x64,
+            tick: pool_state.tick_current,
+        }
+    )?;
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(extension_whitelist_mint_size: u16)]
+pub struct ResizeOperationAccount<'info> {
+    /// Only admin can resize the operation account, pays the rent of the grown account
+    #[account(
+        mut,
+        address = crate::admin::id() @ ErrorCode::NotApproved
+    )]
+    pub owner: Signer<'info>,
+
+    /// The operation account to be grown, the whitelist mints beyond the array are kept in the extension
+    #[account(
+        mut,
+        seeds = [
+            OPERATION_SEED.as_bytes(),
+        ],
+        bump,
+        constraint = OperationState::len_with_extension(extension_whitelist_mint_size)
+            > operation_state.to_account_info().data_len() @ ErrorCode::InvalidOperationAccountSize,
+        realloc = OperationState::len_with_extension(extension_whitelist_mint_size),
+        realloc::payer = owner,
+        realloc::zero = true,
+    )]
+    pub operation_state: AccountLoader<'info, OperationState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn resize_operation_account(
+    _ctx: Context<ResizeOperationAccount>,
+    extension_whitelist_mint_size: u16,
+) -> Result<()> {
+    require_gte!(
+        EXTENSION_WHITE_MINT_SIZE_MAX,
+        usize::from(extension_whitelist_mint_size),
+        ErrorCode::InvalidOperationAccountSize
+    );
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::util::EXTERNAL_ORACLE_DEVIATION_BPS_DENOMINATOR;
+use anchor_lang::prelude::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct SetExternalOracle<'info> {
+    /// Only admin or operation owner can set the external oracle of a pool
+    pub authority: Signer<'info>,
+
+    /// load info from the account to judge the authority
+    #[account(
+        seeds = [
+            OPERATION_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub operation_state: AccountLoader<'info, OperationState>,
+
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+}
+
+pub fn set_external_oracle(
+    ctx: Context<SetExternalOracle>,
+    external_oracle: Pubkey,
+    max_deviation_bps: u64,
+) -> Result<()> {
+    require!(
+        ctx.accounts.authority.key() == crate::admin::id()
+            || ctx
+                .accounts
+                .operation_state
+                .load()?
+                .validate_operation_owner(ctx.accounts.authority.key()),
+        ErrorCode::NotApproved
+    );
+    if external_oracle != Pubkey::default() {
+        require!(
+            max_deviation_bps > 0 && max_deviation_bps < EXTERNAL_ORACLE_DEVIATION_BPS_DENOMINATOR,
+            ErrorCode::InvalidExternalOracle
+        );
+    }
+
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    pool_state.external_oracle = external_oracle;
+    pool_state.external_oracle_max_deviation_bps = max_deviation_bps;
+
+    emit_event!(
+        ctx,
+        ExternalOracleUpdatedEvent {
+            pool_state: ctx.accounts.pool_state.key(),
+            external_oracle,
+            max_deviation_bps,
+        }
+    )?;
+    Ok(())
+}
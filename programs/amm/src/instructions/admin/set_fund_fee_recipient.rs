@@ -0,0 +1,32 @@
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct SetFundFeeRecipient<'info> {
+    /// Only admin can route the fund fees of a pool
+    #[account(address = crate::admin::id() @ ErrorCode::NotApproved)]
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+}
+
+pub fn set_fund_fee_recipient(
+    ctx: Context<SetFundFeeRecipient>,
+    fund_fee_recipient: Pubkey,
+) -> Result<()> {
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    pool_state.fund_fee_recipient = fund_fee_recipient;
+
+    emit_event!(
+        ctx,
+        FundFeeRecipientUpdatedEvent {
+            pool_state: ctx.accounts.pool_state.key(),
+            fund_fee_recipient,
+        }
+    )?;
+    Ok(())
+}
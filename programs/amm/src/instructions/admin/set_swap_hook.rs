@@ -0,0 +1,48 @@
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct SetSwapHook<'info> {
+    /// Only admin or operation owner can whitelist the swap hook of a pool
+    pub authority: Signer<'info>,
+
+    /// load info from the account to judge the authority
+    #[account(
+        seeds = [
+            OPERATION_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub operation_state: AccountLoader<'info, OperationState>,
+
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+}
+
+pub fn set_swap_hook(ctx: Context<SetSwapHook>, swap_hook: Pubkey) -> Result<()> {
+    require!(
+        ctx.accounts.authority.key() == crate::admin::id()
+            || ctx
+                .accounts
+                .operation_state
+                .load()?
+                .validate_operation_owner(ctx.accounts.authority.key()),
+        ErrorCode::NotApproved
+    );
+    // the program itself can not be a hook, which would reenter the swap
+    require_keys_neq!(swap_hook, crate::id(), ErrorCode::InvalidSwapHook);
+
+    ctx.accounts.pool_state.load_mut()?.swap_hook = swap_hook;
+
+    emit_event!(
+        ctx,
+        SwapHookUpdatedEvent {
+            pool_state: ctx.accounts.pool_state.key(),
+            swap_hook,
+        }
+    )?;
+    Ok(())
+}
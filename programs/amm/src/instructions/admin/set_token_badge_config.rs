@@ -0,0 +1,54 @@
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct SetTokenBadgeConfig<'info> {
+    /// Only admin can set the token badge config
+    #[account(
+        mut,
+        address = crate::admin::id() @ ErrorCode::NotApproved
+    )]
+    pub owner: Signer<'info>,
+
+    /// CHECK: The account receiving the fee of the self-service token badges
+    pub fee_receiver: UncheckedAccount<'info>,
+
+    /// Initialize the token badge config at the first set
+    #[account(
+        init_if_needed,
+        seeds = [
+            TOKEN_BADGE_CONFIG_SEED.as_bytes(),
+        ],
+        bump,
+        payer = owner,
+        space = TokenBadgeConfig::LEN
+    )]
+    pub token_badge_config: Box<Account<'info, TokenBadgeConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_token_badge_config(
+    ctx: Context<SetTokenBadgeConfig>,
+    fee: u64,
+    allowed_extensions: u64,
+) -> Result<()> {
+    let token_badge_config = &mut ctx.accounts.token_badge_config;
+    token_badge_config.bump = ctx.bumps.token_badge_config;
+    token_badge_config.fee = fee;
+    token_badge_config.fee_receiver = ctx.accounts.fee_receiver.key();
+    token_badge_config.allowed_extensions = allowed_extensions;
+
+    emit_event!(
+        ctx,
+        TokenBadgeConfigChangeEvent {
+            fee,
+            fee_receiver: ctx.accounts.fee_receiver.key(),
+            allowed_extensions,
+        }
+    )?;
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::util::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct SweepDust<'info> {
+    /// Only admin can sweep the dust
+    #[account(address = crate::admin::id() @ ErrorCode::NotApproved)]
+    pub owner: Signer<'info>,
+
+    /// The pool of the vaults
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// Amm config account stores fund_owner
+    #[account(address = pool_state.load()?.amm_config)]
+    pub amm_config: Box<Account<'info, AmmConfig>>,
+
+    /// The address that holds pool tokens for token_0
+    #[account(
+        mut,
+        constraint = token_vault_0.key() == pool_state.load()?.token_vault_0
+    )]
+    pub token_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The address that holds pool tokens for token_1
+    #[account(
+        mut,
+        constraint = token_vault_1.key() == pool_state.load()?.token_vault_1
+    )]
+    pub token_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of token vault 0
+    #[account(
+        address = token_vault_0.mint
+    )]
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of token vault 1
+    #[account(
+        address = token_vault_1.mint
+    )]
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: The tickarray_bitmap_extension of the pool, read if it is created
+    #[account(address = TickArrayBitmapExtension::key(pool_state.key()))]
+    pub tickarray_bitmap_extension: UncheckedAccount<'info>,
+
+    /// The token_0 account of the fund owner receiving the dust
+    #[account(
+        mut,
+        token::mint = token_vault_0.mint,
+        token::authority = amm_config.fund_owner
+    )]
+    pub recipient_token_account_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token_1 account of the fund owner receiving the dust
+    #[account(
+        mut,
+        token::mint = token_vault_1.mint,
+        token::authority = amm_config.fund_owner
+    )]
+    pub recipient_token_account_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The SPL program to perform token transfers
+    pub token_program: Program<'info, Token>,
+
+    /// The SPL program 2022 to perform token transfers
+    pub token_program_2022: Program<'info, Token2022>,
+    // remaining accounts: all initialized tick arrays of the pool, in any order,
+    // followed by the extra accounts of the transfer hooks of the vault mints
+}
+
+pub fn sweep_dust<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, SweepDust<'info>>,
+) -> Result<()> {
+    let vault_mints = [
+        ctx.accounts.vault_0_mint.to_account_info(),
+        ctx.accounts.vault_1_mint.to_account_info(),
+    ];
+    let (tick_array_infos, transfer_hook_accounts) =
+        split_transfer_hook_accounts(ctx.remaining_accounts, &vault_mints)?;
+    let liabilities = PoolLiabilities::load(
+        &*ctx.accounts.pool_state.load()?,
+        ctx.accounts.tickarray_bitmap_extension.as_ref(),
+        tick_array_infos,
+    )?;
+
+    let vault_balance_0 = ctx.accounts.token_vault_0.amount;
+    let vault_balance_1 = ctx.accounts.token_vault_1.amount;
+    let amount_0 = vault_balance_0.saturating_sub(liabilities.total_0());
+    let amount_1 = vault_balance_1.saturating_sub(liabilities.total_1());
+    transfer_from_pool_vault_to_user(
+        &ctx.accounts.pool_state,
+        &ctx.accounts.token_vault_0.to_account_info(),
+        &ctx.accounts.recipient_token_account_0.to_account_info(),
+        Some(ctx.accounts.vault_0_mint.clone()),
+        &ctx.accounts.token_program,
+        Some(ctx.accounts.token_program_2022.to_account_info()),
+        transfer_hook_accounts,
+        amount_0,
+    )?;
+    transfer_from_pool_vault_to_user(
+        &ctx.accounts.pool_state,
+        &ctx.accounts.token_vault_1.to_account_info(),
+        &ctx.accounts.recipient_token_account_1.to_account_info(),
+        Some(ctx.accounts.vault_1_mint.clone()),
+        &ctx.accounts.token_program,
+        Some(ctx.accounts.token_program_2022.to_account_info()),
+        transfer_hook_accounts,
+        amount_1,
+    )?;
+
+    emit_event!(
+        ctx,
+        SweepDustEvent {
+            pool_state: ctx.accounts.pool_state.key(),
+            vault_balance_0,
+            vault_balance_1,
+            liabilities,
+            amount_0,
+            amount_1,
+        }
+    )?;
+    Ok(())
+}
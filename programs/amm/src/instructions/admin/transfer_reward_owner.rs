@@ -17,10 +17,15 @@ pub fn transfer_reward_owner<'a, 'b, 'c, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, TransferRewardOwner<'info>>,
     new_owner: Pubkey,
 ) -> Result<()> {
-    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    check_admin_timelock_disabled(ctx.remaining_accounts)?;
+    set_reward_owner(&mut *ctx.accounts.pool_state.load_mut()?, new_owner);
+    Ok(())
+}
+
+/// Sets the pool owner and all reward authorities to the new owner
+pub fn set_reward_owner(pool_state: &mut PoolState, new_owner: Pubkey) {
     for reward_info in &mut pool_state.reward_infos {
         reward_info.authority = new_owner;
     }
     pool_state.owner = new_owner;
-    Ok(())
 }
@@ -0,0 +1,37 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateAdminTimelock<'info> {
+    /// Only admin can update the timelock
+    #[account(
+        mut,
+        address = crate::admin::id() @ ErrorCode::NotApproved
+    )]
+    pub owner: Signer<'info>,
+
+    /// Initialize the timelock account at the first update
+    #[account(
+        init_if_needed,
+        seeds = [
+            ADMIN_TIMELOCK_SEED.as_bytes(),
+        ],
+        bump,
+        payer = owner,
+        space = AdminTimelock::LEN
+    )]
+    pub admin_timelock: Box<Account<'info, AdminTimelock>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn update_admin_timelock(ctx: Context<UpdateAdminTimelock>, delay: u64) -> Result<()> {
+    require_gte!(MAX_ADMIN_TIMELOCK_DELAY, delay);
+    let admin_timelock = &mut ctx.accounts.admin_timelock;
+    // shortening the delay must be queued, otherwise the timelock can be bypassed
+    require_gte!(delay, admin_timelock.delay, ErrorCode::AdminTimelockEnabled);
+    admin_timelock.bump = ctx.bumps.admin_timelock;
+    admin_timelock.delay = delay;
+    Ok(())
+}
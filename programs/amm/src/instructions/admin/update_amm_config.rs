@@ -1,7 +1,9 @@
 use crate::error::ErrorCode;
 use crate::states::*;
+use crate::util::EventAuthority;
 use anchor_lang::prelude::*;
 
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 pub struct UpdateAmmConfig<'info> {
     /// The amm config owner or admin
@@ -10,28 +12,45 @@ pub struct UpdateAmmConfig<'info> {
 
     /// Amm config account to be changed
     #[account(mut)]
-    pub amm_config: Account<'info, AmmConfig>,
+    pub amm_config: Box<Account<'info, AmmConfig>>,
 }
 
 pub fn update_amm_config(ctx: Context<UpdateAmmConfig>, param: u8, value: u32) -> Result<()> {
-    let amm_config = &mut ctx.accounts.amm_config;
+    if TIMELOCKED_AMM_CONFIG_PARAMS.contains(&param) {
+        check_admin_timelock_disabled(ctx.remaining_accounts)?;
+    }
+    set_amm_config_param(
+        &mut ctx.accounts.amm_config,
+        param,
+        u64::from(value),
+        crate::event_authority!(ctx.accounts, ctx.bumps),
+    )
+}
+
+/// Sets a param of amm config, shared by `update_amm_config` and the queued admin action
+pub fn set_amm_config_param(
+    amm_config: &mut AmmConfig,
+    param: u8,
+    value: u64,
+    event_authority: EventAuthority,
+) -> Result<()> {
     let match_param = Some(param);
     match match_param {
-        Some(0) => update_trade_fee_rate(amm_config, value),
-        Some(1) => update_protocol_fee_rate(amm_config, value),
-        Some(2) => update_fund_fee_rate(amm_config, value),
-        Some(3) => {
-            let new_owner = *ctx.remaining_accounts.iter().next().unwrap().key;
-            set_new_owner(amm_config, new_owner);
-        }
-        Some(4) => {
-            let new_fund_owner = *ctx.remaining_accounts.iter().next().unwrap().key;
-            set_new_fund_owner(amm_config, new_fund_owner);
-        }
+        Some(0) => update_trade_fee_rate(amm_config, u32::try_from(value).unwrap()),
+        Some(1) => update_protocol_fee_rate(amm_config, u32::try_from(value).unwrap()),
+        Some(2) => update_fund_fee_rate(amm_config, u32::try_from(value).unwrap()),
+        // the owner and fund owner changes don't take effect until accepted by the new owner
+        Some(3) | Some(4) => return err!(ErrorCode::OwnershipTransferRequiresTwoSteps),
+        Some(5) => update_referral_fee_rate(amm_config, u32::try_from(value).unwrap()),
+        Some(6) => amm_config.create_pool_fee = value,
+        Some(7) => amm_config.whitelist_pool_creation = value != 0,
+        Some(8) => amm_config.sandwich_fee_multiplier = u16::try_from(value).unwrap(),
+        Some(9) => update_max_creator_fee_rate(amm_config, u32::try_from(value).unwrap()),
+        Some(10) => amm_config.freeze_authority_requires_badge = value != 0,
         _ => return err!(ErrorCode::InvalidUpdateConfigFlag),
     }
 
-    emit!(ConfigChangeEvent {
+    event_authority.emit(ConfigChangeEvent {
         index: amm_config.index,
         owner: amm_config.owner,
         trade_fee_rate: amm_config.trade_fee_rate,
@@ -39,44 +58,35 @@ pub fn update_amm_config(ctx: Context<UpdateAmmConfig>, param: u8, value: u32) -
         tick_spacing: amm_config.tick_spacing,
         fund_fee_rate: amm_config.fund_fee_rate,
         fund_owner: amm_config.fund_owner,
-    });
+    })?;
 
     Ok(())
 }
 
-fn update_protocol_fee_rate(amm_config: &mut Account<AmmConfig>, protocol_fee_rate: u32) {
+fn update_protocol_fee_rate(amm_config: &mut AmmConfig, protocol_fee_rate: u32) {
     assert!(protocol_fee_rate <= FEE_RATE_DENOMINATOR_VALUE);
     assert!(protocol_fee_rate + amm_config.fund_fee_rate <= FEE_RATE_DENOMINATOR_VALUE);
     amm_config.protocol_fee_rate = protocol_fee_rate;
 }
 
-fn update_trade_fee_rate(amm_config: &mut Account<AmmConfig>, trade_fee_rate: u32) {
+fn update_trade_fee_rate(amm_config: &mut AmmConfig, trade_fee_rate: u32) {
     assert!(trade_fee_rate < FEE_RATE_DENOMINATOR_VALUE);
     amm_config.trade_fee_rate = trade_fee_rate;
 }
 
-fn update_fund_fee_rate(amm_config: &mut Account<AmmConfig>, fund_fee_rate: u32) {
+fn update_fund_fee_rate(amm_config: &mut AmmConfig, fund_fee_rate: u32) {
     assert!(fund_fee_rate <= FEE_RATE_DENOMINATOR_VALUE);
     assert!(fund_fee_rate + amm_config.protocol_fee_rate <= FEE_RATE_DENOMINATOR_VALUE);
     amm_config.fund_fee_rate = fund_fee_rate;
 }
 
-fn set_new_owner(amm_config: &mut Account<AmmConfig>, new_owner: Pubkey) {
-    #[cfg(feature = "enable-log")]
-    msg!(
-        "amm_config, old_owner:{}, new_owner:{}",
-        amm_config.owner.to_string(),
-        new_owner.key().to_string()
-    );
-    amm_config.owner = new_owner;
+fn update_max_creator_fee_rate(amm_config: &mut AmmConfig, max_creator_fee_rate: u32) {
+    assert!(max_creator_fee_rate <= FEE_RATE_DENOMINATOR_VALUE);
+    amm_config.max_creator_fee_rate = max_creator_fee_rate;
 }
 
-fn set_new_fund_owner(amm_config: &mut Account<AmmConfig>, new_fund_owner: Pubkey) {
-    #[cfg(feature = "enable-log")]
-    msg!(
-        "amm_config, old_fund_owner:{}, new_fund_owner:{}",
-        amm_config.fund_owner.to_string(),
-        new_fund_owner.key().to_string()
-    );
-    amm_config.fund_owner = new_fund_owner;
+fn update_referral_fee_rate(amm_config: &mut AmmConfig, referral_fee_rate: u32) {
+    // referral fee is paid from the protocol fee
+    assert!(referral_fee_rate <= amm_config.protocol_fee_rate);
+    amm_config.referral_fee_rate = referral_fee_rate;
 }
@@ -0,0 +1,75 @@
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct UpdateAmmConfigExtension<'info> {
+    /// Only admin can update the extension of amm config
+    #[account(address = crate::admin::id() @ ErrorCode::NotApproved)]
+    pub owner: Signer<'info>,
+
+    /// The amm config extended
+    pub amm_config: Box<Account<'info, AmmConfig>>,
+
+    /// The extension to be changed
+    #[account(
+        mut,
+        seeds = [
+            AMM_CONFIG_EXTENSION_SEED.as_bytes(),
+            amm_config.key().as_ref()
+        ],
+        bump = amm_config_extension.bump,
+    )]
+    pub amm_config_extension: Box<Account<'info, AmmConfigExtension>>,
+}
+
+pub fn update_amm_config_extension(
+    ctx: Context<UpdateAmmConfigExtension>,
+    param: u8,
+    value: u64,
+) -> Result<()> {
+    let amm_config = &ctx.accounts.amm_config;
+    let amm_config_extension = &mut ctx.accounts.amm_config_extension;
+    let match_param = Some(param);
+    match match_param {
+        Some(0) => {
+            let max_dynamic_fee_rate = amm_config_extension.max_dynamic_fee_rate;
+            amm_config_extension.set_dynamic_fee_bounds(
+                amm_config,
+                u32::try_from(value).unwrap(),
+                max_dynamic_fee_rate,
+            )?
+        }
+        Some(1) => {
+            let min_dynamic_fee_rate = amm_config_extension.min_dynamic_fee_rate;
+            amm_config_extension.set_dynamic_fee_bounds(
+                amm_config,
+                min_dynamic_fee_rate,
+                u32::try_from(value).unwrap(),
+            )?
+        }
+        Some(2) => {
+            let treasury = ctx
+                .remaining_accounts
+                .first()
+                .ok_or(ErrorCode::NotEnoughRemainingAccounts)?;
+            amm_config_extension.treasury = treasury.key();
+        }
+        _ => return err!(ErrorCode::InvalidUpdateConfigFlag),
+    }
+
+    emit_event!(
+        ctx,
+        AmmConfigExtensionChangeEvent {
+            amm_config: amm_config_extension.amm_config,
+            version: amm_config_extension.version,
+            min_dynamic_fee_rate: amm_config_extension.min_dynamic_fee_rate,
+            max_dynamic_fee_rate: amm_config_extension.max_dynamic_fee_rate,
+            treasury: amm_config_extension.treasury,
+        }
+    )?;
+
+    Ok(())
+}
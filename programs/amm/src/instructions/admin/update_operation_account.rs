@@ -1,5 +1,6 @@
 use crate::error::ErrorCode;
 use crate::states::*;
+use crate::util::AccountLoad;
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -28,13 +29,15 @@ pub fn update_operation_account(
     param: u8,
     keys: Vec<Pubkey>,
 ) -> Result<()> {
-    let mut operation_state = ctx.accounts.operation_state.load_mut()?;
+    let operation_state_info = ctx.accounts.operation_state.to_account_info();
+    let (mut operation_state, mut extension) =
+        AccountLoad::<OperationState>::load_data_mut_with_tail(&operation_state_info)?;
     let match_param = Some(param);
     match match_param {
         Some(0) => operation_state.update_operation_owner(keys),
         Some(1) => operation_state.remove_operation_owner(keys),
-        Some(2) => operation_state.update_whitelist_mint(keys),
-        Some(3) => operation_state.remove_whitelist_mint(keys),
+        Some(2) => operation_state.update_whitelist_mint(&mut extension, keys)?,
+        Some(3) => operation_state.remove_whitelist_mint(&mut extension, keys)?,
         _ => return err!(ErrorCode::InvalidUpdateConfigFlag),
     }
     Ok(())
@@ -0,0 +1,73 @@
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::libraries::tick_math;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct UpdatePoolPriceBand<'info> {
+    /// Only admin or operation owner can update the price band
+    pub authority: Signer<'info>,
+
+    /// load info from the account to judge the authority
+    #[account(
+        seeds = [
+            OPERATION_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub operation_state: AccountLoader<'info, OperationState>,
+
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+}
+
+pub fn update_pool_price_band(
+    ctx: Context<UpdatePoolPriceBand>,
+    min_sqrt_price_x64: u128,
+    max_sqrt_price_x64: u128,
+) -> Result<()> {
+    require!(
+        ctx.accounts.authority.key() == crate::admin::id()
+            || ctx
+                .accounts
+                .operation_state
+                .load()?
+                .validate_operation_owner(ctx.accounts.authority.key()),
+        ErrorCode::NotApproved
+    );
+    if min_sqrt_price_x64 != 0 {
+        require_gt!(
+            min_sqrt_price_x64,
+            tick_math::MIN_SQRT_PRICE_X64,
+            ErrorCode::InvalidPriceBand
+        );
+    }
+    if max_sqrt_price_x64 != 0 {
+        require_gt!(
+            tick_math::MAX_SQRT_PRICE_X64,
+            max_sqrt_price_x64,
+            ErrorCode::InvalidPriceBand
+        );
+        require_gt!(
+            max_sqrt_price_x64,
+            min_sqrt_price_x64,
+            ErrorCode::InvalidPriceBand
+        );
+    }
+
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    pool_state.min_sqrt_price_x64 = min_sqrt_price_x64;
+    pool_state.max_sqrt_price_x64 = max_sqrt_price_x64;
+
+    emit_event!(
+        ctx,
+        PoolPriceBandUpdatedEvent {
+            pool_state: ctx.accounts.pool_state.key(),
+            min_sqrt_price_x64,
+            max_sqrt_price_x64,
+        }
+    )?;
+    Ok(())
+}
@@ -0,0 +1,172 @@
+use crate::decrease_liquidity::decrease_liquidity;
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::util::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct WithdrawProtocolOwnedLiquidity<'info> {
+    /// Only admin or fund_owner can withdraw the protocol owned liquidity
+    #[account(
+        constraint = (
+            owner.key() == amm_config.fund_owner || owner.key() == crate::admin::id()
+        ) @ ErrorCode::NotApproved
+    )]
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// Amm config account stores fund_owner
+    #[account(address = pool_state.load()?.amm_config)]
+    pub amm_config: Box<Account<'info, AmmConfig>>,
+
+    /// CHECK: The PDA owning the protocol owned positions of the pool
+    #[account(
+        seeds = [PROTOCOL_OWNED_LIQUIDITY_SEED.as_bytes(), pool_state.key().as_ref()],
+        bump,
+    )]
+    pub protocol_owner: UncheckedAccount<'info>,
+
+    /// CHECK: The token account for the position nft or the MPL Core asset of the position,
+    /// owned by the protocol owner
+    #[account(
+        constraint = get_position_nft_owner(
+            &nft_account,
+            &personal_position.nft_mint
+        )? == protocol_owner.key() @ ErrorCode::NotApproved
+    )]
+    pub nft_account: UncheckedAccount<'info>,
+
+    /// The protocol owned position to decrease
+    #[account(mut, constraint = personal_position.pool_id == pool_state.key())]
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
+
+    /// Token_0 vault
+    #[account(
+        mut,
+        constraint = token_vault_0.key() == pool_state.load()?.token_vault_0
+    )]
+    pub token_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token_1 vault
+    #[account(
+        mut,
+        constraint = token_vault_1.key() == pool_state.load()?.token_vault_1
+    )]
+    pub token_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Stores init state for the lower tick
+    #[account(mut, constraint = tick_array_lower.load()?.pool_id == pool_state.key())]
+    pub tick_array_lower: AccountLoader<'info, TickArrayState>,
+
+    /// Stores init state for the upper tick
+    #[account(mut, constraint = tick_array_upper.load()?.pool_id == pool_state.key())]
+    pub tick_array_upper: AccountLoader<'info, TickArrayState>,
+
+    /// The address that receives the withdrawn token_0 and the earned fees,
+    /// owned by the fund fee recipient of the pool if it is set
+    #[account(
+        mut,
+        token::mint = token_vault_0.mint
+    )]
+    pub recipient_token_account_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The address that receives the withdrawn token_1 and the earned fees,
+    /// owned by the fund fee recipient of the pool if it is set
+    #[account(
+        mut,
+        token::mint = token_vault_1.mint
+    )]
+    pub recipient_token_account_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// SPL program to transfer out tokens
+    pub token_program: Program<'info, Token>,
+
+    /// Token program 2022
+    pub token_program_2022: Program<'info, Token2022>,
+
+    /// The mint of token vault 0
+    #[account(
+        address = token_vault_0.mint
+    )]
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of token vault 1
+    #[account(
+        address = token_vault_1.mint
+    )]
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
+    // remaining accounts are the same as decrease_liquidity_v2
+}
+
+pub fn withdraw_protocol_owned_liquidity<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, WithdrawProtocolOwnedLiquidity<'info>>,
+    liquidity: u128,
+    amount_0_min: u64,
+    amount_1_min: u64,
+) -> Result<()> {
+    let fund_fee_recipient = ctx.accounts.pool_state.load()?.fund_fee_recipient;
+    if fund_fee_recipient != Pubkey::default() {
+        require!(
+            ctx.accounts.recipient_token_account_0.owner == fund_fee_recipient
+                && ctx.accounts.recipient_token_account_1.owner == fund_fee_recipient,
+            ErrorCode::InvalidFundFeeRecipient
+        );
+    }
+    decrease_liquidity(
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.pool_state,
+        &mut ctx.accounts.personal_position,
+        &ctx.accounts.token_vault_0.to_account_info(),
+        &ctx.accounts.token_vault_1.to_account_info(),
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        &ctx.accounts.recipient_token_account_0.to_account_info(),
+        &ctx.accounts.recipient_token_account_1.to_account_info(),
+        &ctx.accounts.token_program,
+        Some(ctx.accounts.token_program_2022.clone()),
+        None,
+        Some(ctx.accounts.vault_0_mint.clone()),
+        Some(ctx.accounts.vault_1_mint.clone()),
+        ctx.remaining_accounts,
+        liquidity,
+        amount_0_min,
+        amount_1_min,
+        crate::event_authority!(ctx.accounts, ctx.bumps),
+    )?;
+
+    emit_event!(
+        ctx,
+        WithdrawProtocolOwnedLiquidityEvent {
+            pool_state: ctx.accounts.pool_state.key(),
+            position_nft_mint: ctx.accounts.personal_position.nft_mint,
+            liquidity,
+            recipient_token_account_0: ctx.accounts.recipient_token_account_0.key(),
+            recipient_token_account_1: ctx.accounts.recipient_token_account_1.key(),
+        }
+    )?;
+
+    Ok(())
+}
+
+/// Emitted when the liquidity of a protocol owned position is withdrawn
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct WithdrawProtocolOwnedLiquidityEvent {
+    /// The pool of the position
+    #[index]
+    pub pool_state: Pubkey,
+    /// The protocol owned position decreased
+    pub position_nft_mint: Pubkey,
+    /// The liquidity withdrawn from the position
+    pub liquidity: u128,
+    /// The address that receives the withdrawn token_0
+    pub recipient_token_account_0: Pubkey,
+    /// The address that receives the withdrawn token_1
+    pub recipient_token_account_1: Pubkey,
+}
@@ -0,0 +1,110 @@
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::libraries::tick_math;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct ApproveOperator<'info> {
+    /// The position nft owner, pays for the operator account
+    #[account(mut)]
+    pub nft_owner: Signer<'info>,
+
+    /// The token account for the tokenized position
+    #[account(
+        constraint = nft_account.mint == personal_position.nft_mint,
+        constraint = nft_account.amount == 1,
+        token::authority = nft_owner
+    )]
+    pub nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The position to approve the operator for
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
+
+    /// Stores the operator of the position
+    #[account(
+        init_if_needed,
+        seeds = [POSITION_OPERATOR_SEED.as_bytes(), personal_position.nft_mint.as_ref()],
+        bump,
+        payer = nft_owner,
+        space = PositionOperatorState::LEN
+    )]
+    pub position_operator: Box<Account<'info, PositionOperatorState>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn approve_operator(
+    ctx: Context<ApproveOperator>,
+    operator: Pubkey,
+    permission: u8,
+    tick_lower_bound: i32,
+    tick_upper_bound: i32,
+) -> Result<()> {
+    require!(
+        permission <= OPERATOR_PERMISSION_FULL,
+        ErrorCode::InvalidOperatorPermission
+    );
+    if permission == OPERATOR_PERMISSION_REBALANCE {
+        require!(
+            tick_lower_bound < tick_upper_bound
+                && tick_lower_bound >= tick_math::MIN_TICK
+                && tick_upper_bound <= tick_math::MAX_TICK,
+            ErrorCode::InvalidOperatorPermission
+        );
+    }
+    let position_operator = &mut ctx.accounts.position_operator;
+    position_operator.bump = ctx.bumps.position_operator;
+    position_operator.nft_mint = ctx.accounts.personal_position.nft_mint;
+    position_operator.nft_owner = ctx.accounts.nft_owner.key();
+    position_operator.operator = operator;
+    position_operator.permission = permission;
+    position_operator.tick_lower_bound = tick_lower_bound;
+    position_operator.tick_upper_bound = tick_upper_bound;
+
+    emit_event!(
+        ctx,
+        ApproveOperatorEvent {
+            position_nft_mint: position_operator.nft_mint,
+            nft_owner: position_operator.nft_owner,
+            operator,
+            permission,
+            tick_lower_bound,
+            tick_upper_bound,
+        }
+    )?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RevokeOperator<'info> {
+    /// The position nft owner, receives the rent of the operator account
+    #[account(mut)]
+    pub nft_owner: Signer<'info>,
+
+    /// The token account for the tokenized position
+    #[account(
+        constraint = nft_account.mint == personal_position.nft_mint,
+        constraint = nft_account.amount == 1,
+        token::authority = nft_owner
+    )]
+    pub nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The position to revoke the operator of
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
+
+    /// The operator account to close
+    #[account(
+        mut,
+        seeds = [POSITION_OPERATOR_SEED.as_bytes(), personal_position.nft_mint.as_ref()],
+        bump = position_operator.bump,
+        close = nft_owner
+    )]
+    pub position_operator: Box<Account<'info, PositionOperatorState>>,
+}
+
+pub fn revoke_operator(_ctx: Context<RevokeOperator>) -> Result<()> {
+    Ok(())
+}
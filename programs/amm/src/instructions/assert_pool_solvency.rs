@@ -0,0 +1,99 @@
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::util::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct AssertPoolSolvency<'info> {
+    /// The pool to check, its rewards are brought up to date before the check
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// The address that holds pool tokens for token_0
+    #[account(
+        constraint = token_vault_0.key() == pool_state.load()?.token_vault_0
+    )]
+    pub token_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The address that holds pool tokens for token_1
+    #[account(
+        constraint = token_vault_1.key() == pool_state.load()?.token_vault_1
+    )]
+    pub token_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: The tickarray_bitmap_extension of the pool, read if it is created
+    #[account(address = TickArrayBitmapExtension::key(pool_state.key()))]
+    pub tickarray_bitmap_extension: UncheckedAccount<'info>,
+    // remaining accounts
+    // reward_info_extension: required if the pool has extension rewards
+    // the reward vaults of the initialized rewards, of the pool rewards then of the extension rewards
+    // all initialized tick arrays of the pool, in any order
+}
+
+pub fn assert_pool_solvency<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, AssertPoolSolvency<'info>>,
+) -> Result<()> {
+    let pool_id = ctx.accounts.pool_state.key();
+    let curr_timestamp = u64::try_from(Clock::get()?.unix_timestamp).unwrap();
+    let mut remaining_accounts = ctx.remaining_accounts;
+
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    let mut reward_infos = pool_state.update_reward_infos(curr_timestamp)?.to_vec();
+    if pool_state.extension_reward_count > 0 {
+        check_remaining_accounts_min(remaining_accounts, 1)?;
+        let reward_info_extension_info = &remaining_accounts[0];
+        require_keys_eq!(
+            reward_info_extension_info.key(),
+            RewardInfoExtension::key(pool_id),
+            ErrorCode::MissingRewardInfoExtension
+        );
+        reward_infos.extend(
+            AccountLoad::<RewardInfoExtension>::load_data_mut(reward_info_extension_info)?
+                .update_reward_infos(curr_timestamp, pool_state.liquidity)?,
+        );
+        remaining_accounts = &remaining_accounts[1..];
+    }
+
+    let mut reward_liabilities = Vec::new();
+    for reward_info in reward_infos
+        .iter()
+        .filter(|reward_info| reward_info.initialized())
+    {
+        check_remaining_accounts_min(remaining_accounts, 1)?;
+        let reward_vault = InterfaceAccount::<TokenAccount>::try_from(&remaining_accounts[0])?;
+        require_keys_eq!(reward_vault.key(), reward_info.token_vault);
+        reward_liabilities.push(RewardLiability::new(reward_info, reward_vault.amount));
+        remaining_accounts = &remaining_accounts[1..];
+    }
+
+    let liabilities = PoolLiabilities::load(
+        &pool_state,
+        ctx.accounts.tickarray_bitmap_extension.as_ref(),
+        remaining_accounts,
+    )?;
+    drop(pool_state);
+
+    let vault_balance_0 = ctx.accounts.token_vault_0.amount;
+    let vault_balance_1 = ctx.accounts.token_vault_1.amount;
+    let solvent = vault_balance_0 >= liabilities.total_0()
+        && vault_balance_1 >= liabilities.total_1()
+        && reward_liabilities
+            .iter()
+            .all(|reward_liability| reward_liability.is_solvent());
+    emit_event!(
+        ctx,
+        PoolSolvencyEvent {
+            pool_state: pool_id,
+            vault_balance_0,
+            vault_balance_1,
+            liabilities,
+            reward_liabilities,
+            solvent,
+        }
+    )?;
+    require!(solvent, ErrorCode::PoolInsolvent);
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use crate::emit_event;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct CheckpointPool<'info> {
+    /// Pays to create the checkpoint account if needed
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The pool to checkpoint
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// The ring buffer of the checkpoints of the pool
+    #[account(
+        init_if_needed,
+        seeds = [POOL_CHECKPOINT_SEED.as_bytes(), pool_state.key().as_ref()],
+        bump,
+        payer = payer,
+        space = PoolCheckpointState::LEN
+    )]
+    pub pool_checkpoint: AccountLoader<'info, PoolCheckpointState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn checkpoint_pool(ctx: Context<CheckpointPool>) -> Result<()> {
+    let pool_state = ctx.accounts.pool_state.load()?;
+    let checkpoint = PoolCheckpoint {
+        block_timestamp: u64::try_from(Clock::get()?.unix_timestamp).unwrap(),
+        fee_growth_global_0_x64: pool_state.fee_growth_global_0_x64,
+        fee_growth_global_1_x64: pool_state.fee_growth_global_1_x64,
+        liquidity: pool_state.liquidity,
+        sqrt_price_x64: pool_state.sqrt_price_x64,
+    };
+
+    let mut pool_checkpoint = match ctx.accounts.pool_checkpoint.load_mut() {
+        Ok(pool_checkpoint) => pool_checkpoint,
+        Err(_) => {
+            let mut pool_checkpoint = ctx.accounts.pool_checkpoint.load_init()?;
+            pool_checkpoint.pool_id = ctx.accounts.pool_state.key();
+            pool_checkpoint
+        }
+    };
+    pool_checkpoint.record(checkpoint)?;
+
+    emit_event!(
+        ctx,
+        PoolCheckpointEvent {
+            pool_state: ctx.accounts.pool_state.key(),
+            block_timestamp: checkpoint.block_timestamp,
+            fee_growth_global_0_x64: checkpoint.fee_growth_global_0_x64,
+            fee_growth_global_1_x64: checkpoint.fee_growth_global_1_x64,
+            liquidity: checkpoint.liquidity,
+            sqrt_price_x64: checkpoint.sqrt_price_x64,
+        }
+    )?;
+    Ok(())
+}
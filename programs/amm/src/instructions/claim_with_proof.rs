@@ -0,0 +1,90 @@
+use crate::emit_event;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimWithProof<'info> {
+    /// The claimant in the merkle tree, pays to create the claim status
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    /// The distribution to claim from
+    #[account(mut)]
+    pub distribution: Box<Account<'info, RewardDistribution>>,
+
+    /// Created once per claimant, prevents claiming twice
+    #[account(
+        init,
+        seeds = [
+            CLAIM_STATUS_SEED.as_bytes(),
+            distribution.key().as_ref(),
+            claimant.key().as_ref(),
+        ],
+        bump,
+        payer = claimant,
+        space = ClaimStatus::LEN
+    )]
+    pub claim_status: Box<Account<'info, ClaimStatus>>,
+
+    /// The vault holding the distributed token
+    #[account(
+        mut,
+        address = distribution.vault
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token account receiving the claimed amount
+    #[account(
+        mut,
+        token::mint = mint
+    )]
+    pub recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of the distributed token
+    #[account(address = distribution.mint)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn claim_with_proof(
+    ctx: Context<ClaimWithProof>,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let claimant = ctx.accounts.claimant.key();
+    ctx.accounts.distribution.claim(claimant, amount, &proof)?;
+
+    let claim_status = &mut ctx.accounts.claim_status;
+    claim_status.bump = ctx.bumps.claim_status;
+    claim_status.distribution = ctx.accounts.distribution.key();
+    claim_status.claimant = claimant;
+    claim_status.amount = amount;
+
+    spl_token_2022::onchain::invoke_transfer_checked(
+        ctx.accounts.token_program.key,
+        ctx.accounts.vault.to_account_info(),
+        ctx.accounts.mint.to_account_info(),
+        ctx.accounts.recipient_token_account.to_account_info(),
+        ctx.accounts.distribution.to_account_info(),
+        &[],
+        amount,
+        ctx.accounts.mint.decimals,
+        &[&ctx.accounts.distribution.seeds()],
+    )?;
+
+    emit_event!(
+        ctx,
+        ClaimWithProofEvent {
+            distribution: ctx.accounts.distribution.key(),
+            claimant,
+            recipient_token_account: ctx.accounts.recipient_token_account.key(),
+            amount,
+        }
+    )?;
+    Ok(())
+}
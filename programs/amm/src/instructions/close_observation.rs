@@ -0,0 +1,51 @@
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct CloseObservation<'info> {
+    /// The pool of the observation account
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// The observation account to be closed
+    #[account(
+        mut,
+        address = pool_state.load()?.observation_key,
+        close = recipient
+    )]
+    pub observation_state: AccountLoader<'info, ObservationState>,
+
+    /// CHECK: The pool creator who paid the rent, receives the reclaimed rent
+    #[account(mut, address = pool_state.load()?.owner)]
+    pub recipient: UncheckedAccount<'info>,
+}
+
+pub fn close_observation(ctx: Context<CloseObservation>) -> Result<()> {
+    let pool_state = ctx.accounts.pool_state.load()?;
+    let block_timestamp = u64::try_from(Clock::get()?.unix_timestamp).unwrap();
+    {
+        let observation_state = ctx.accounts.observation_state.load()?;
+        let data = ctx.accounts.observation_state.as_ref().try_borrow_data()?;
+        let extension = &data[8 + std::mem::size_of::<ObservationState>()..];
+        require!(
+            observation_state.is_idle(
+                extension,
+                pool_state.liquidity,
+                pool_state.open_time,
+                block_timestamp
+            ),
+            ErrorCode::PoolNotIdle
+        );
+    }
+
+    emit_event!(
+        ctx,
+        CloseObservationEvent {
+            pool_state: ctx.accounts.pool_state.key(),
+            observation_state: ctx.accounts.observation_state.key(),
+        }
+    )?;
+    Ok(())
+}
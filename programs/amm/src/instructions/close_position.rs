@@ -1,9 +1,12 @@
 use crate::error::ErrorCode;
 use crate::states::*;
-use crate::util::{burn, close_spl_account};
+use crate::util::{
+    burn, burn_core_asset, close_account, close_spl_account, get_position_nft_owner, is_core_asset,
+    AccountLoad,
+};
 use anchor_lang::prelude::*;
 use anchor_spl::token_2022::spl_token_2022;
-use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::token_interface::TokenInterface;
 
 #[derive(Accounts)]
 pub struct ClosePosition<'info> {
@@ -11,23 +14,16 @@ pub struct ClosePosition<'info> {
     #[account(mut)]
     pub nft_owner: Signer<'info>,
 
-    /// Mint address bound to the personal position.
+    /// CHECK: Mint address bound to the personal position, or the MPL Core asset of the position
     #[account(
       mut,
       address = personal_position.nft_mint,
-      mint::token_program = token_program,
     )]
-    pub position_nft_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub position_nft_mint: UncheckedAccount<'info>,
 
-    /// User token account where position NFT be minted to
-    #[account(
-        mut,
-        token::mint = position_nft_mint,
-        token::authority = nft_owner,
-        constraint = position_nft_account.amount == 1,
-        token::token_program = token_program,
-    )]
-    pub position_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: User token account where position NFT be minted to, or the MPL Core asset of the position
+    #[account(mut)]
+    pub position_nft_account: UncheckedAccount<'info>,
 
     #[account(
         mut, 
@@ -42,64 +38,151 @@ pub struct ClosePosition<'info> {
 
     /// Token/Token2022 program to close token/mint account
     pub token_program: Interface<'info, TokenInterface>,
+    // remaining accounts
+    // mpl_core_program: required if the position is an MPL Core asset, which is passed
+    // as both the position_nft_mint and the position_nft_account,
+    // position_reward_extension: required if the position has one, closed with the position
 }
 
 pub fn close_position<'a, 'b, 'c, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, ClosePosition<'info>>,
 ) -> Result<()> {
-    if ctx.accounts.personal_position.liquidity != 0
-        || ctx.accounts.personal_position.token_fees_owed_0 != 0
-        || ctx.accounts.personal_position.token_fees_owed_1 != 0
+    check_position_closable(&ctx.accounts.personal_position)?;
+    let position_reward_extension_info = check_position_reward_extension_closable(
+        &ctx.accounts.personal_position,
+        ctx.remaining_accounts,
+    )?;
+    burn_position_nft(
+        &ctx.accounts.nft_owner,
+        &ctx.accounts.position_nft_mint.to_account_info(),
+        &ctx.accounts.position_nft_account.to_account_info(),
+        &ctx.accounts.personal_position,
+        &ctx.accounts.token_program.to_account_info(),
+        ctx.remaining_accounts.first(),
+    )?;
+    if let Some(position_reward_extension_info) = position_reward_extension_info {
+        close_account(
+            position_reward_extension_info.clone(),
+            ctx.accounts.nft_owner.to_account_info(),
+        )?;
+    }
+    Ok(())
+}
+
+/// Requires the position to be unlocked and to have no liquidity, fees nor rewards left
+pub fn check_position_closable(personal_position: &PersonalPositionState) -> Result<()> {
+    require!(
+        !personal_position.is_time_locked(Clock::get()?.unix_timestamp as u64),
+        ErrorCode::PositionTimeLocked
+    );
+    if personal_position.liquidity != 0
+        || personal_position.token_fees_owed_0 != 0
+        || personal_position.token_fees_owed_1 != 0
     {
         msg!(
             "remaing liquidity:{},token_fees_owed_0:{},token_fees_owed_1:{}",
-            ctx.accounts.personal_position.liquidity,
-            ctx.accounts.personal_position.token_fees_owed_0,
-            ctx.accounts.personal_position.token_fees_owed_1
+            personal_position.liquidity,
+            personal_position.token_fees_owed_0,
+            personal_position.token_fees_owed_1
         );
         return err!(ErrorCode::ClosePositionErr);
     }
 
-    for i in 0..ctx.accounts.personal_position.reward_infos.len() {
-        if ctx.accounts.personal_position.reward_infos[i].reward_amount_owed != 0 {
+    for i in 0..personal_position.reward_infos.len() {
+        if personal_position.reward_infos[i].reward_amount_owed != 0 {
             msg!(
                 "remaing reward index:{},amount:{}",
                 i,
-                ctx.accounts.personal_position.reward_infos[i].reward_amount_owed,
+                personal_position.reward_infos[i].reward_amount_owed,
             );
             return err!(ErrorCode::ClosePositionErr);
         }
     }
+    Ok(())
+}
 
-    let token_program = ctx.accounts.token_program.to_account_info();
-    let position_nft_mint = ctx.accounts.position_nft_mint.to_account_info();
-    let personal_nft_account = ctx.accounts.position_nft_account.to_account_info();
+/// The rewards owed in the position reward extension would be lost with the position,
+/// returns the extension of the position to be closed with it
+pub fn check_position_reward_extension_closable<'c, 'info>(
+    personal_position: &PersonalPositionState,
+    remaining_accounts: &'c [AccountInfo<'info>],
+) -> Result<Option<&'c AccountInfo<'info>>> {
+    if !personal_position.has_reward_extension {
+        return Ok(None);
+    }
+    let position_reward_extension_key = PositionRewardExtension::key(personal_position.nft_mint);
+    let position_reward_extension_info = remaining_accounts
+        .iter()
+        .find(|account_info| account_info.key() == position_reward_extension_key)
+        .ok_or(ErrorCode::MissingPositionRewardExtension)?;
+    let position_reward_extension_loader =
+        AccountLoad::<PositionRewardExtension>::try_from(position_reward_extension_info)?;
+    let reward_amounts_owed = position_reward_extension_loader.load()?.reward_amounts_owed;
+    for (i, reward_amount_owed) in reward_amounts_owed.iter().enumerate() {
+        if *reward_amount_owed != 0 {
+            msg!(
+                "remaing extension reward index:{},amount:{}",
+                i,
+                reward_amount_owed
+            );
+            return err!(ErrorCode::ClosePositionErr);
+        }
+    }
+    Ok(Some(position_reward_extension_info))
+}
+
+/// Burns the position nft or the MPL Core asset of the position owned by the nft owner,
+/// and closes the nft account and the nft mint to refund the rent to the nft owner
+pub fn burn_position_nft<'info>(
+    nft_owner: &Signer<'info>,
+    position_nft_mint: &AccountInfo<'info>,
+    personal_nft_account: &AccountInfo<'info>,
+    personal_position: &Account<'info, PersonalPositionState>,
+    token_program: &AccountInfo<'info>,
+    mpl_core_program: Option<&AccountInfo<'info>>,
+) -> Result<()> {
+    require_keys_eq!(
+        get_position_nft_owner(personal_nft_account, &position_nft_mint.key())?,
+        nft_owner.key(),
+        ErrorCode::NotApproved
+    );
+    if is_core_asset(position_nft_mint) {
+        let mpl_core_program = mpl_core_program.ok_or(ErrorCode::InvalidCoreAsset)?;
+        return burn_core_asset(
+            &nft_owner.to_account_info(),
+            position_nft_mint,
+            mpl_core_program,
+        );
+    }
+
+    require_keys_eq!(*position_nft_mint.owner, token_program.key());
+    require_keys_eq!(*personal_nft_account.owner, token_program.key());
     burn(
-        &ctx.accounts.nft_owner,
-        &position_nft_mint,
-        &personal_nft_account,
-        &token_program,
+        nft_owner,
+        position_nft_mint,
+        personal_nft_account,
+        token_program,
         &[],
         1,
     )?;
 
     // close use nft token account
     close_spl_account(
-        &ctx.accounts.nft_owner,
-        &ctx.accounts.nft_owner,
-        &personal_nft_account,
-        &token_program,
+        nft_owner,
+        nft_owner,
+        personal_nft_account,
+        token_program,
         &[],
     )?;
 
     if *position_nft_mint.owner == spl_token_2022::id() {
         // close nft mint account
         close_spl_account(
-            &ctx.accounts.personal_position.to_account_info(),
-            &ctx.accounts.nft_owner,
-            &position_nft_mint,
-            &token_program,
-            &[&ctx.accounts.personal_position.seeds()],
+            &personal_position.to_account_info(),
+            nft_owner,
+            position_nft_mint,
+            token_program,
+            &[&personal_position.seeds()],
         )?;
     }
     Ok(())
@@ -0,0 +1,37 @@
+use crate::emit_event;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct CloseProtocolPosition<'info> {
+    /// The pool of the protocol position
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// The deprecated protocol position to be closed
+    #[account(
+        mut,
+        constraint = protocol_position.pool_id == pool_state.key(),
+        close = rent_recipient
+    )]
+    pub protocol_position: Box<Account<'info, ProtocolPositionState>>,
+
+    /// CHECK: The pool creator, the rent payers of the protocol positions are not recorded
+    #[account(
+        mut,
+        address = pool_state.load()?.owner
+    )]
+    pub rent_recipient: UncheckedAccount<'info>,
+}
+
+pub fn close_protocol_position(ctx: Context<CloseProtocolPosition>) -> Result<()> {
+    emit_event!(
+        ctx,
+        CloseProtocolPositionEvent {
+            pool_state: ctx.accounts.pool_state.key(),
+            protocol_position: ctx.accounts.protocol_position.key(),
+            rent_recipient: ctx.accounts.rent_recipient.key(),
+        }
+    )?;
+    Ok(())
+}
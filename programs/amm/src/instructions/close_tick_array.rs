@@ -0,0 +1,57 @@
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct CloseTickArray<'info> {
+    /// The pool of the tick array
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// The empty tick array to be closed
+    #[account(
+        mut,
+        constraint = tick_array.load()?.pool_id == pool_state.key(),
+        close = rent_recipient
+    )]
+    pub tick_array: AccountLoader<'info, TickArrayState>,
+
+    /// CHECK: The rent payer of the tick array, or the pool creator if the rent payer is not recorded
+    #[account(
+        mut,
+        address = tick_array.load()?.rent_recipient(pool_state.load()?.owner)
+    )]
+    pub rent_recipient: UncheckedAccount<'info>,
+}
+
+pub fn close_tick_array<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CloseTickArray<'info>>,
+) -> Result<()> {
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    let tick_array = ctx.accounts.tick_array.load()?;
+    require_eq!(
+        tick_array.initialized_tick_count,
+        0,
+        ErrorCode::TickArrayNotEmpty
+    );
+
+    // the optional tickarray_bitmap_extension is required if the tick array is out of the default bitmap
+    let tickarray_bitmap_extension = ctx.remaining_accounts.first();
+    if pool_state
+        .is_tick_array_initialized(tickarray_bitmap_extension, tick_array.start_tick_index)?
+    {
+        pool_state.flip_tick_array_bit(tickarray_bitmap_extension, tick_array.start_tick_index)?;
+    }
+
+    emit_event!(
+        ctx,
+        CloseTickArrayEvent {
+            pool_state: ctx.accounts.pool_state.key(),
+            start_tick_index: tick_array.start_tick_index,
+            rent_recipient: ctx.accounts.rent_recipient.key(),
+        }
+    )?;
+    Ok(())
+}
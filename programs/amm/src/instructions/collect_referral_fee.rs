@@ -0,0 +1,119 @@
+use crate::emit_event;
+use crate::states::*;
+use crate::util::transfer_from_pool_vault_to_user;
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct CollectReferralFee<'info> {
+    /// The referrer who owns the referral fee
+    pub referrer: Signer<'info>,
+
+    /// Pool state of the referral fee
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// Referral account stores the referral fee owed
+    #[account(
+        mut,
+        seeds = [
+            REFERRAL_SEED.as_bytes(),
+            pool_state.key().as_ref(),
+            referrer.key().as_ref(),
+        ],
+        bump = referral_state.bump,
+    )]
+    pub referral_state: Box<Account<'info, ReferralState>>,
+
+    /// The address that holds pool tokens for token_0
+    #[account(
+        mut,
+        constraint = token_vault_0.key() == pool_state.load()?.token_vault_0
+    )]
+    pub token_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The address that holds pool tokens for token_1
+    #[account(
+        mut,
+        constraint = token_vault_1.key() == pool_state.load()?.token_vault_1
+    )]
+    pub token_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of token vault 0
+    #[account(
+        address = token_vault_0.mint
+    )]
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of token vault 1
+    #[account(
+        address = token_vault_1.mint
+    )]
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The address that receives the collected token_0 referral fees
+    #[account(mut, token::mint = token_vault_0.mint)]
+    pub recipient_token_account_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The address that receives the collected token_1 referral fees
+    #[account(mut, token::mint = token_vault_1.mint)]
+    pub recipient_token_account_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The SPL program to perform token transfers
+    pub token_program: Program<'info, Token>,
+
+    /// The SPL program 2022 to perform token transfers
+    pub token_program_2022: Program<'info, Token2022>,
+}
+
+pub fn collect_referral_fee(ctx: Context<CollectReferralFee>) -> Result<()> {
+    let referral_state = &mut ctx.accounts.referral_state;
+    let amount_0 = referral_state.fees_owed_0;
+    let amount_1 = referral_state.fees_owed_1;
+    referral_state.fees_owed_0 = 0;
+    referral_state.fees_owed_1 = 0;
+    {
+        // the fees accrued before the pool counted the referral fees are not in the counters
+        let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+        pool_state.referral_fees_token_0 =
+            pool_state.referral_fees_token_0.saturating_sub(amount_0);
+        pool_state.referral_fees_token_1 =
+            pool_state.referral_fees_token_1.saturating_sub(amount_1);
+    }
+
+    transfer_from_pool_vault_to_user(
+        &ctx.accounts.pool_state,
+        &ctx.accounts.token_vault_0.to_account_info(),
+        &ctx.accounts.recipient_token_account_0.to_account_info(),
+        Some(ctx.accounts.vault_0_mint.clone()),
+        &ctx.accounts.token_program,
+        Some(ctx.accounts.token_program_2022.to_account_info()),
+        &[],
+        amount_0,
+    )?;
+
+    transfer_from_pool_vault_to_user(
+        &ctx.accounts.pool_state,
+        &ctx.accounts.token_vault_1.to_account_info(),
+        &ctx.accounts.recipient_token_account_1.to_account_info(),
+        Some(ctx.accounts.vault_1_mint.clone()),
+        &ctx.accounts.token_program,
+        Some(ctx.accounts.token_program_2022.to_account_info()),
+        &[],
+        amount_1,
+    )?;
+
+    emit_event!(
+        ctx,
+        CollectReferralFeeEvent {
+            pool_state: ctx.accounts.pool_state.key(),
+            referrer: ctx.accounts.referrer.key(),
+            amount_0,
+            amount_1,
+        }
+    )?;
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use crate::error::ErrorCode;
 use crate::states::*;
-use crate::util::transfer_from_pool_vault_to_user;
+use crate::util::{split_transfer_hook_accounts, transfer_from_pool_vault_to_user, AccountLoad};
 use anchor_lang::prelude::*;
 use anchor_spl::{
     token::{self, Token},
@@ -40,20 +40,38 @@ pub struct CollectRemainingRewards<'info> {
     pub memo_program: UncheckedAccount<'info>,
 }
 
-pub fn collect_remaining_rewards(
-    ctx: Context<CollectRemainingRewards>,
+pub fn collect_remaining_rewards<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CollectRemainingRewards<'info>>,
     reward_index: u8,
 ) -> Result<()> {
     // invoke_memo_instruction(
     //     COLLECT_REMAINING_MEMO_MSG,
     //     ctx.accounts.memo_program.to_account_info(),
     // )?;
-    let amount_remaining = get_remaining_reward_amount(
+    let (remaining_accounts, transfer_hook_accounts) = split_transfer_hook_accounts(
+        ctx.remaining_accounts,
+        &[ctx.accounts.reward_vault_mint.to_account_info()],
+    )?;
+    // the rewards of the reward info extension are collected by their authority,
+    // the extension is passed as the first remaining account
+    let (amount_remaining, reward_authority) = get_remaining_reward_amount(
         &ctx.accounts.pool_state,
         &ctx.accounts.reward_token_vault,
-        &ctx.accounts.reward_funder.key(),
         reward_index,
+        remaining_accounts.first(),
     )?;
+    if usize::from(reward_index) < REWARD_NUM {
+        require_keys_eq!(
+            ctx.accounts.reward_funder.key(),
+            ctx.accounts.pool_state.load()?.owner
+        );
+    } else {
+        require_keys_eq!(
+            ctx.accounts.reward_funder.key(),
+            reward_authority,
+            ErrorCode::NotApproved
+        );
+    }
 
     transfer_from_pool_vault_to_user(
         &ctx.accounts.pool_state,
@@ -62,23 +80,43 @@ pub fn collect_remaining_rewards(
         Some(ctx.accounts.reward_vault_mint.clone()),
         &ctx.accounts.token_program,
         Some(ctx.accounts.token_program_2022.to_account_info()),
+        transfer_hook_accounts,
         amount_remaining,
     )?;
 
     Ok(())
 }
 
-fn get_remaining_reward_amount(
+/// Returns the amount of the reward vault not emitted to the positions once the reward ended
+/// and the authority of the reward, the rewards from `REWARD_NUM` are in the reward info extension
+pub fn get_remaining_reward_amount(
     pool_state_loader: &AccountLoader<PoolState>,
     reward_token_vault: &InterfaceAccount<TokenAccount>,
-    reward_funder: &Pubkey,
     reward_index: u8,
-) -> Result<u64> {
+    reward_info_extension_info: Option<&AccountInfo>,
+) -> Result<(u64, Pubkey)> {
+    require_gt!(
+        REWARD_NUM + EXTENSION_REWARD_NUM,
+        usize::from(reward_index),
+        ErrorCode::InvalidRewardIndex
+    );
     let current_timestamp = u64::try_from(Clock::get()?.unix_timestamp).unwrap();
     let mut pool_state = pool_state_loader.load_mut()?;
     pool_state.update_reward_infos(current_timestamp)?;
 
-    let reward_info = pool_state.reward_infos[reward_index as usize];
+    let reward_info = match usize::from(reward_index).checked_sub(REWARD_NUM) {
+        None => pool_state.reward_infos[reward_index as usize],
+        Some(extension_index) => {
+            let reward_info_extension_info =
+                reward_info_extension_info.ok_or(ErrorCode::MissingRewardInfoExtension)?;
+            require_keys_eq!(
+                reward_info_extension_info.key(),
+                RewardInfoExtension::key(pool_state_loader.key())
+            );
+            AccountLoad::<RewardInfoExtension>::load_data_mut(reward_info_extension_info)?
+                .update_reward_infos(current_timestamp, pool_state.liquidity)?[extension_index]
+        }
+    };
     if !reward_info.initialized() {
         return err!(ErrorCode::UnInitializedRewardInfo);
     }
@@ -87,7 +125,6 @@ fn get_remaining_reward_amount(
         reward_info.end_time,
         ErrorCode::NotApproved
     );
-    require_keys_eq!(reward_funder.key(), pool_state.owner);
     require_keys_eq!(reward_token_vault.key(), reward_info.token_vault);
 
     let amount_remaining = reward_token_vault
@@ -100,5 +137,5 @@ fn get_remaining_reward_amount(
         )
         .unwrap();
 
-    Ok(amount_remaining)
+    Ok((amount_remaining, reward_info.authority))
 }
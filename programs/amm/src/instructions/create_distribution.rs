@@ -0,0 +1,107 @@
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::util;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct CreateDistribution<'info> {
+    /// The creator funding the distribution
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// The creator's token account of the distributed token
+    #[account(
+        mut,
+        token::mint = mint
+    )]
+    pub creator_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of the distributed token
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The distribution to create
+    #[account(
+        init,
+        seeds = [
+            REWARD_DISTRIBUTION_SEED.as_bytes(),
+            creator.key().as_ref(),
+            mint.key().as_ref(),
+            &id.to_le_bytes(),
+        ],
+        bump,
+        payer = creator,
+        space = RewardDistribution::LEN
+    )]
+    pub distribution: Box<Account<'info, RewardDistribution>>,
+
+    /// The vault holding the distributed token
+    #[account(
+        init,
+        seeds = [
+            REWARD_DISTRIBUTION_VAULT_SEED.as_bytes(),
+            distribution.key().as_ref(),
+        ],
+        bump,
+        payer = creator,
+        token::mint = mint,
+        token::authority = distribution,
+        token::token_program = token_program,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_distribution(
+    ctx: Context<CreateDistribution>,
+    id: u64,
+    merkle_root: [u8; 32],
+    total_amount: u64,
+) -> Result<()> {
+    if !util::is_supported_mint(&ctx.accounts.mint, false, false).unwrap() {
+        return err!(ErrorCode::NotSupportMint);
+    }
+    let distribution = &mut ctx.accounts.distribution;
+    distribution.bump = ctx.bumps.distribution;
+    distribution.creator = ctx.accounts.creator.key();
+    distribution.id = id;
+    distribution.mint = ctx.accounts.mint.key();
+    distribution.vault = ctx.accounts.vault.key();
+    distribution.merkle_root = merkle_root;
+    distribution.total_amount = total_amount;
+
+    // the vault receives the total amount after the transfer fee
+    let total_amount_with_transfer_fee = total_amount
+        .checked_add(util::get_transfer_inverse_fee(
+            ctx.accounts.mint.clone(),
+            total_amount,
+        )?)
+        .unwrap();
+    util::transfer_from_user_to_pool_vault(
+        &ctx.accounts.creator,
+        &ctx.accounts.creator_token_account.to_account_info(),
+        &ctx.accounts.vault.to_account_info(),
+        Some(ctx.accounts.mint.clone()),
+        &ctx.accounts.token_program.to_account_info(),
+        Some(ctx.accounts.token_program.to_account_info()),
+        &[],
+        total_amount_with_transfer_fee,
+    )?;
+
+    emit_event!(
+        ctx,
+        CreateDistributionEvent {
+            distribution: ctx.accounts.distribution.key(),
+            creator: ctx.accounts.creator.key(),
+            mint: ctx.accounts.mint.key(),
+            merkle_root,
+            total_amount,
+        }
+    )?;
+    Ok(())
+}
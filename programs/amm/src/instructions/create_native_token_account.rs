@@ -0,0 +1,44 @@
+use crate::swap_v2_native::NATIVE_TOKEN_ACCOUNT_SEED;
+use crate::util::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{spl_token::native_mint, Mint, Token};
+
+#[derive(Accounts)]
+pub struct CreateNativeTokenAccount<'info> {
+    /// The owner of the temporary WSOL token account, pays for its rent
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: The temporary WSOL token account of the owner, initialized in the instruction
+    #[account(
+        mut,
+        seeds = [NATIVE_TOKEN_ACCOUNT_SEED.as_bytes(), owner.key().as_ref()],
+        bump,
+    )]
+    pub native_token_account: UncheckedAccount<'info>,
+
+    /// The WSOL mint
+    #[account(address = native_mint::id())]
+    pub native_mint: Box<Account<'info, Mint>>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_native_token_account(ctx: Context<CreateNativeTokenAccount>) -> Result<()> {
+    let owner_key = ctx.accounts.owner.key();
+    create_temporary_native_account(
+        &ctx.accounts.owner,
+        &ctx.accounts.native_token_account.to_account_info(),
+        &ctx.accounts.native_mint.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        &[
+            NATIVE_TOKEN_ACCOUNT_SEED.as_bytes(),
+            owner_key.as_ref(),
+            &[ctx.bumps.native_token_account],
+        ],
+        0,
+    )
+}
@@ -1,9 +1,12 @@
+use crate::emit_event;
 use crate::error::ErrorCode;
 use crate::states::*;
 use crate::{libraries::tick_math, util};
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 // use solana_program::{program::invoke_signed, system_instruction};
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 pub struct CreatePool<'info> {
     /// Address paying to create the pool. Can be anyone
@@ -13,6 +16,13 @@ pub struct CreatePool<'info> {
     /// Which config the pool belongs to.
     pub amm_config: Box<Account<'info, AmmConfig>>,
 
+    /// CHECK: The fund owner of amm config, receives the pool creation fee
+    #[account(
+        mut,
+        address = amm_config.fund_owner
+    )]
+    pub fund_owner: UncheckedAccount<'info>,
+
     /// Initialize an account to store the pool state
     #[account(
         init,
@@ -107,16 +117,107 @@ pub struct CreatePool<'info> {
     pub system_program: Program<'info, System>,
     /// Sysvar for program account
     pub rent: Sysvar<'info, Rent>,
+    // remaining accounts, required if the mint has risky extensions
+    // #[account(
+    //     seeds = [
+    //         TOKEN_BADGE_SEED.as_bytes(),
+    //         token_mint_0.key().as_ref(),
+    //     ],
+    //     bump
+    // )]
+    // pub token_badge_0: Account<'info, TokenBadge>,
+    // #[account(
+    //     seeds = [
+    //         TOKEN_BADGE_SEED.as_bytes(),
+    //         token_mint_1.key().as_ref(),
+    //     ],
+    //     bump
+    // )]
+    // pub token_badge_1: Account<'info, TokenBadge>,
+    // remaining account, the whitelist creator of the pool creator or the operation account
+    // if the pool creator is an operation owner, required if the amm config restricts pool creation
+    // #[account(
+    //     seeds = [
+    //         WHITELIST_CREATOR_SEED.as_bytes(),
+    //         pool_creator.key().as_ref(),
+    //     ],
+    //     bump
+    // )]
+    // pub whitelist_creator: Account<'info, WhitelistCreator>,
+    // #[account(
+    //     seeds = [
+    //         OPERATION_SEED.as_bytes(),
+    //     ],
+    //     bump
+    // )]
+    // pub operation_state: AccountLoader<'info, OperationState>,
 }
 
-pub fn create_pool(ctx: Context<CreatePool>, sqrt_price_x64: u128, open_time: u64) -> Result<()> {
-    if !(util::is_supported_mint(&ctx.accounts.token_mint_0).unwrap()
-        && util::is_supported_mint(&ctx.accounts.token_mint_1).unwrap())
+/// The launch fee of a new pool, the trade fee rate starts at `fee_rate` and decays linearly
+/// to the trade fee rate of the amm config over `duration` seconds
+#[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Debug, PartialEq)]
+pub struct LaunchFeeParams {
+    pub fee_rate: u32,
+    pub duration: u64,
+}
+
+/// An option of `create_pool_v2`, the new options are appended as new variants
+/// so the instruction data of the existing ones stays valid
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum CreatePoolOption {
+    /// The part of the trade fee accrued to the pool creator, at most the `max_creator_fee_rate` of amm config
+    CreatorFeeRate(u32),
+    /// The trade fee rate at launch decaying to the trade fee rate of amm config from the open time of the pool
+    LaunchFee(LaunchFeeParams),
+}
+
+pub fn create_pool<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CreatePool<'info>>,
+    sqrt_price_x64: u128,
+    open_time: u64,
+    options: Vec<CreatePoolOption>,
+) -> Result<()> {
+    util::check_remaining_accounts_max(
+        ctx.remaining_accounts,
+        util::MAX_CREATE_POOL_REMAINING_ACCOUNTS,
+    )?;
+    if ctx.accounts.amm_config.whitelist_pool_creation {
+        check_pool_creator(ctx.accounts.pool_creator.key(), ctx.remaining_accounts)?;
+    }
+    let token_badge_0_initialized =
+        util::is_token_badge_initialized(ctx.accounts.token_mint_0.key(), ctx.remaining_accounts)?;
+    let token_badge_1_initialized =
+        util::is_token_badge_initialized(ctx.accounts.token_mint_1.key(), ctx.remaining_accounts)?;
+    let freeze_authority_requires_badge = ctx.accounts.amm_config.freeze_authority_requires_badge;
+    if !(util::is_supported_mint(
+        &ctx.accounts.token_mint_0,
+        token_badge_0_initialized,
+        freeze_authority_requires_badge,
+    )
+    .unwrap()
+        && util::is_supported_mint(
+            &ctx.accounts.token_mint_1,
+            token_badge_1_initialized,
+            freeze_authority_requires_badge,
+        )
+        .unwrap())
     {
         return err!(ErrorCode::NotSupportMint);
     }
     let block_timestamp = solana_program::clock::Clock::get()?.unix_timestamp as u64;
     require_gt!(block_timestamp, open_time);
+    if ctx.accounts.amm_config.create_pool_fee > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.pool_creator.to_account_info(),
+                    to: ctx.accounts.fund_owner.to_account_info(),
+                },
+            ),
+            ctx.accounts.amm_config.create_pool_fee,
+        )?;
+    }
     let pool_id = ctx.accounts.pool_state.key();
     let mut pool_state = ctx.accounts.pool_state.load_init()?;
 
@@ -147,21 +248,72 @@ pub fn create_pool(ctx: Context<CreatePool>, sqrt_price_x64: u128, open_time: u6
         ctx.accounts.token_mint_1.as_ref(),
         ctx.accounts.observation_state.key(),
     )?;
+    for option in options {
+        match option {
+            CreatePoolOption::CreatorFeeRate(creator_fee_rate) => {
+                pool_state.set_creator_fee_rate(&ctx.accounts.amm_config, creator_fee_rate)?
+            }
+            CreatePoolOption::LaunchFee(launch_fee) => pool_state.set_launch_fee(
+                &ctx.accounts.amm_config,
+                launch_fee.fee_rate,
+                launch_fee.duration,
+                block_timestamp,
+            )?,
+        }
+    }
 
     ctx.accounts
         .tick_array_bitmap
         .load_init()?
         .initialize(pool_id);
 
-    emit!(PoolCreatedEvent {
-        token_mint_0: ctx.accounts.token_mint_0.key(),
-        token_mint_1: ctx.accounts.token_mint_1.key(),
-        tick_spacing: ctx.accounts.amm_config.tick_spacing,
-        pool_state: ctx.accounts.pool_state.key(),
-        sqrt_price_x64,
-        tick,
-        token_vault_0: ctx.accounts.token_vault_0.key(),
-        token_vault_1: ctx.accounts.token_vault_1.key(),
-    });
+    emit_event!(
+        ctx,
+        PoolCreatedEvent {
+            token_mint_0: ctx.accounts.token_mint_0.key(),
+            token_mint_1: ctx.accounts.token_mint_1.key(),
+            tick_spacing: ctx.accounts.amm_config.tick_spacing,
+            pool_state: ctx.accounts.pool_state.key(),
+            sqrt_price_x64,
+            tick,
+            token_vault_0: ctx.accounts.token_vault_0.key(),
+            token_vault_1: ctx.accounts.token_vault_1.key(),
+        }
+    )?;
+    Ok(())
+}
+
+/// Only admin, operation owners and whitelist creators can create pool
+fn check_pool_creator<'info>(
+    pool_creator: Pubkey,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    if pool_creator == crate::admin::id() {
+        return Ok(());
+    }
+    let whitelist_creator_key = WhitelistCreator::key(pool_creator);
+    if let Some(account_info) = remaining_accounts
+        .iter()
+        .find(|account_info| account_info.key() == whitelist_creator_key)
+    {
+        let whitelist_creator = Account::<WhitelistCreator>::try_from(account_info)?;
+        require_keys_eq!(
+            whitelist_creator.creator,
+            pool_creator,
+            ErrorCode::NotApproved
+        );
+        return Ok(());
+    }
+    let operation_state_key = OperationState::key();
+    let operation_account = remaining_accounts
+        .iter()
+        .find(|account_info| account_info.key() == operation_state_key)
+        .ok_or(ErrorCode::NotApproved)?;
+    let operation_state_loader = AccountLoader::<OperationState>::try_from(operation_account)?;
+    let operation_state = operation_state_loader.load()?;
+    require!(
+        operation_state.validate_operation_owner(pool_creator),
+        ErrorCode::NotApproved
+    );
     Ok(())
 }
@@ -0,0 +1,30 @@
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CreatePoolStats<'info> {
+    /// Pays to create the statistics account, anyone can create it
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The pool to track
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// The rolling volume and fees of the pool, updated by the swaps passing it in remaining accounts
+    #[account(
+        init,
+        seeds = [POOL_STATS_SEED.as_bytes(), pool_state.key().as_ref()],
+        bump,
+        payer = payer,
+        space = PoolStatsState::LEN
+    )]
+    pub pool_stats: AccountLoader<'info, PoolStatsState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_pool_stats(ctx: Context<CreatePoolStats>) -> Result<()> {
+    let mut pool_stats = ctx.accounts.pool_stats.load_init()?;
+    pool_stats.pool_id = ctx.accounts.pool_state.key();
+    Ok(())
+}
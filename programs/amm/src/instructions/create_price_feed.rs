@@ -0,0 +1,39 @@
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CreatePriceFeed<'info> {
+    /// Pays to create the price feed account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The pool of the price feed
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// Initialize the price feed account of the pool
+    #[account(
+        init,
+        seeds = [
+            PRICE_FEED_SEED.as_bytes(),
+            pool_state.key().as_ref(),
+        ],
+        bump,
+        payer = payer,
+        space = PriceFeed::LEN
+    )]
+    pub price_feed: AccountLoader<'info, PriceFeed>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_price_feed(ctx: Context<CreatePriceFeed>) -> Result<()> {
+    let clock = Clock::get()?;
+    let mut price_feed = ctx.accounts.price_feed.load_init()?;
+    price_feed.initialize(ctx.bumps.price_feed, ctx.accounts.pool_state.key());
+    price_feed.update(
+        &*ctx.accounts.pool_state.load()?,
+        clock.slot,
+        clock.unix_timestamp as u64,
+    );
+    Ok(())
+}
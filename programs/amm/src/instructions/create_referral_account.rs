@@ -0,0 +1,39 @@
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CreateReferralAccount<'info> {
+    /// Pays to create the referral account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: The referrer who can claim the referral fee
+    pub referrer: UncheckedAccount<'info>,
+
+    /// The pool the referral fee accrued from
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// Initialize referral account to store the referral fee owed to the referrer
+    #[account(
+        init,
+        seeds = [
+            REFERRAL_SEED.as_bytes(),
+            pool_state.key().as_ref(),
+            referrer.key().as_ref(),
+        ],
+        bump,
+        payer = payer,
+        space = ReferralState::LEN
+    )]
+    pub referral_state: Box<Account<'info, ReferralState>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_referral_account(ctx: Context<CreateReferralAccount>) -> Result<()> {
+    let referral_state = &mut ctx.accounts.referral_state;
+    referral_state.bump = ctx.bumps.referral_state;
+    referral_state.pool_id = ctx.accounts.pool_state.key();
+    referral_state.referrer = ctx.accounts.referrer.key();
+    Ok(())
+}
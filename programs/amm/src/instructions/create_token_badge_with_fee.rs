@@ -0,0 +1,87 @@
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::util::is_self_service_badge_mint;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::Mint;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct CreateTokenBadgeWithFee<'info> {
+    /// Anyone can issue the token badge by paying the fee
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Stores the fee and the checks of the self-service token badges
+    #[account(
+        seeds = [
+            TOKEN_BADGE_CONFIG_SEED.as_bytes(),
+        ],
+        bump = token_badge_config.bump,
+    )]
+    pub token_badge_config: Box<Account<'info, TokenBadgeConfig>>,
+
+    /// CHECK: The account receiving the fee
+    #[account(
+        mut,
+        address = token_badge_config.fee_receiver
+    )]
+    pub fee_receiver: UncheckedAccount<'info>,
+
+    /// The mint to be vetted on chain
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The token badge account of the mint, initialized unless the mint already has a badge
+    #[account(
+        init_if_needed,
+        seeds = [
+            TOKEN_BADGE_SEED.as_bytes(),
+            token_mint.key().as_ref(),
+        ],
+        bump,
+        payer = payer,
+        space = TokenBadge::LEN
+    )]
+    pub token_badge: Box<Account<'info, TokenBadge>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_token_badge_with_fee(ctx: Context<CreateTokenBadgeWithFee>) -> Result<()> {
+    // the badge of the mint may already be issued by anyone else, which is the same badge
+    if ctx.accounts.token_badge.token_mint == ctx.accounts.token_mint.key() {
+        return Ok(());
+    }
+    let fee = ctx.accounts.token_badge_config.fee;
+    require_gt!(fee, 0, ErrorCode::SelfServiceTokenBadgeDisabled);
+    if !is_self_service_badge_mint(&ctx.accounts.token_mint, &ctx.accounts.token_badge_config)? {
+        return err!(ErrorCode::NotSupportMint);
+    }
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: ctx.accounts.fee_receiver.to_account_info(),
+            },
+        ),
+        fee,
+    )?;
+
+    let token_badge = &mut ctx.accounts.token_badge;
+    token_badge.bump = ctx.bumps.token_badge;
+    token_badge.token_mint = ctx.accounts.token_mint.key();
+
+    emit_event!(
+        ctx,
+        CreateTokenBadgeWithFeeEvent {
+            token_badge: ctx.accounts.token_badge.key(),
+            token_mint: ctx.accounts.token_mint.key(),
+            payer: ctx.accounts.payer.key(),
+            fee_receiver: ctx.accounts.fee_receiver.key(),
+            fee,
+        }
+    )?;
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CreateTraderStats<'info> {
+    /// The trader opting in, pays to create the statistics account
+    #[account(mut)]
+    pub trader: Signer<'info>,
+
+    /// The pool to track
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// The cumulative volume and fees of the trader, updated by the swaps of the trader passing it in remaining accounts
+    #[account(
+        init,
+        seeds = [
+            TRADER_STATS_SEED.as_bytes(),
+            pool_state.key().as_ref(),
+            trader.key().as_ref(),
+        ],
+        bump,
+        payer = trader,
+        space = TraderStatsState::LEN
+    )]
+    pub trader_stats: AccountLoader<'info, TraderStatsState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_trader_stats(ctx: Context<CreateTraderStats>) -> Result<()> {
+    let mut trader_stats = ctx.accounts.trader_stats.load_init()?;
+    trader_stats.bump = ctx.bumps.trader_stats;
+    trader_stats.pool_id = ctx.accounts.pool_state.key();
+    trader_stats.trader = ctx.accounts.trader.key();
+    Ok(())
+}
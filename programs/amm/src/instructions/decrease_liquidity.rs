@@ -2,7 +2,7 @@ use super::calculate_latest_token_fees;
 use super::modify_position;
 use crate::error::ErrorCode;
 use crate::states::*;
-use crate::util::{self, transfer_from_pool_vault_to_user};
+use crate::util::{self, transfer_from_pool_vault_to_user, AccountLoad, EventAuthority};
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount};
 use anchor_spl::token_2022::spl_token_2022;
@@ -12,16 +12,16 @@ use std::ops::Deref;
 
 /// Memo msg for decrease liquidity
 pub const DECREASE_MEMO_MSG: &'static [u8] = b"raydium_decrease";
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 pub struct DecreaseLiquidity<'info> {
-    /// The position owner or delegated authority
+    /// The position owner or an approved operator
     pub nft_owner: Signer<'info>,
 
     /// The token account for the tokenized position
     #[account(
         constraint = nft_account.mint == personal_position.nft_mint,
         constraint = nft_account.amount == 1,
-        token::authority = nft_owner
     )]
     pub nft_account: Box<Account<'info, TokenAccount>>,
 
@@ -32,18 +32,9 @@ pub struct DecreaseLiquidity<'info> {
     #[account(mut)]
     pub pool_state: AccountLoader<'info, PoolState>,
 
-    #[account(
-        mut,
-        seeds = [
-            POSITION_SEED.as_bytes(),
-            pool_state.key().as_ref(),
-            &personal_position.tick_lower_index.to_be_bytes(),
-            &personal_position.tick_upper_index.to_be_bytes(),
-        ],
-        bump,
-        constraint = protocol_position.pool_id == pool_state.key(),
-    )]
-    pub protocol_position: Box<Account<'info, ProtocolPositionState>>,
+    /// CHECK: Deprecated, the protocol position is folded into the ticks and this account is
+    /// ignored. It is kept so that the account layout of the instruction doesn't change
+    pub protocol_position: UncheckedAccount<'info>,
 
     /// Token_0 vault
     #[account(
@@ -100,9 +91,43 @@ pub fn decrease_liquidity_v1<'a, 'b, 'c: 'info, 'info>(
     amount_0_min: u64,
     amount_1_min: u64,
 ) -> Result<()> {
+    require!(
+        !ctx.accounts.personal_position.locked,
+        ErrorCode::PositionLocked
+    );
+    if liquidity > 0 {
+        require!(
+            !ctx.accounts
+                .personal_position
+                .is_time_locked(Clock::get()?.unix_timestamp as u64),
+            ErrorCode::PositionTimeLocked
+        );
+    }
+    let (remaining_accounts, restricted_operator) = check_position_authority(
+        &ctx.accounts.nft_owner.key(),
+        &ctx.accounts.nft_account.owner,
+        &ctx.accounts.personal_position,
+        ctx.remaining_accounts,
+        if liquidity > 0 {
+            PositionAction::DecreaseLiquidity
+        } else {
+            PositionAction::Collect
+        },
+    )?;
+    if restricted_operator {
+        check_operator_recipients(
+            &ctx.accounts.nft_account.owner,
+            &ctx.accounts.pool_state.key(),
+            &[
+                ctx.accounts.recipient_token_account_0.to_account_info(),
+                ctx.accounts.recipient_token_account_1.to_account_info(),
+            ],
+            remaining_accounts,
+        )?;
+    }
     decrease_liquidity(
+        &ctx.accounts.nft_owner.to_account_info(),
         &ctx.accounts.pool_state,
-        &mut ctx.accounts.protocol_position,
         &mut ctx.accounts.personal_position,
         &ctx.accounts.token_vault_0.to_account_info(),
         &ctx.accounts.token_vault_1.to_account_info(),
@@ -115,16 +140,17 @@ pub fn decrease_liquidity_v1<'a, 'b, 'c: 'info, 'info>(
         None,
         None,
         None,
-        &ctx.remaining_accounts,
+        remaining_accounts,
         liquidity,
         amount_0_min,
         amount_1_min,
+        crate::event_authority!(ctx.accounts, ctx.bumps),
     )
 }
 
 pub fn decrease_liquidity<'a, 'b, 'c: 'info, 'info>(
+    claimer: &'b AccountInfo<'info>,
     pool_state_loader: &'b AccountLoader<'info, PoolState>,
-    protocol_position: &'b mut Box<Account<'info, ProtocolPositionState>>,
     personal_position: &'b mut Box<Account<'info, PersonalPositionState>>,
     token_vault_0: &'b AccountInfo<'info>,
     token_vault_1: &'b AccountInfo<'info>,
@@ -141,21 +167,50 @@ pub fn decrease_liquidity<'a, 'b, 'c: 'info, 'info>(
     liquidity: u128,
     amount_0_min: u64,
     amount_1_min: u64,
+    event_authority: EventAuthority,
 ) -> Result<()> {
     // if accounts.memo_program.is_some() {
     //     let memp_program = accounts.memo_program.as_ref().unwrap().to_account_info();
     //     invoke_memo_instruction(DECREASE_MEMO_MSG, memp_program)?;
     // }
     assert!(liquidity <= personal_position.liquidity);
+    let vault_mints: Vec<AccountInfo> = vault_0_mint
+        .iter()
+        .chain(vault_1_mint.iter())
+        .map(|mint| mint.to_account_info())
+        .collect();
+    let transfer_hook_mints =
+        find_transfer_hook_mints(remaining_accounts, pool_state_loader, vault_mints)?;
+    let (remaining_accounts, transfer_hook_accounts) =
+        util::split_transfer_hook_accounts(remaining_accounts, &transfer_hook_mints)?;
+    util::check_remaining_accounts_max(
+        remaining_accounts,
+        util::MAX_DECREASE_LIQUIDITY_REMAINING_ACCOUNTS,
+    )?;
     let liquidity_before;
     let pool_sqrt_price_x64;
     let pool_tick_current;
     let mut tickarray_bitmap_extension = None;
+    let reward_info_extension_info;
+    let position_reward_extension_info;
+    let mut emergency_withdraw = false;
 
     let remaining_collect_accounts = &mut Vec::new();
     {
+        for account_info in remaining_accounts.into_iter() {
+            if account_info.key().eq(&AdminHeartbeat::key()) {
+                let admin_heartbeat = Account::<AdminHeartbeat>::try_from(account_info)?;
+                emergency_withdraw =
+                    admin_heartbeat.is_expired(Clock::get()?.unix_timestamp as u64);
+                continue;
+            }
+            remaining_collect_accounts.push(account_info);
+        }
         let pool_state = pool_state_loader.load()?;
-        if !pool_state.get_status_by_bit(PoolStatusBitIndex::DecreaseLiquidity)
+        // the expired heartbeat doesn't unlock a pool frozen on the insolvency of its vaults
+        emergency_withdraw = emergency_withdraw && !pool_state.is_frozen();
+        if !emergency_withdraw
+            && !pool_state.get_status_by_bit(PoolStatusBitIndex::DecreaseLiquidity)
             && !pool_state.get_status_by_bit(PoolStatusBitIndex::CollectFee)
             && !pool_state.get_status_by_bit(PoolStatusBitIndex::CollectReward)
         {
@@ -170,33 +225,54 @@ pub fn decrease_liquidity<'a, 'b, 'c: 'info, 'info>(
             tick_array_upper_loader.load()?.start_tick_index,
         ]);
 
-        for account_info in remaining_accounts.into_iter() {
-            if account_info
-                .key()
-                .eq(&TickArrayBitmapExtension::key(pool_state.key()))
-            {
-                tickarray_bitmap_extension = Some(account_info);
-                continue;
+        let bitmap_extension_key = TickArrayBitmapExtension::key(pool_state.key());
+        remaining_collect_accounts.retain(|account_info| {
+            if account_info.key().eq(&bitmap_extension_key) {
+                tickarray_bitmap_extension = Some(*account_info);
+                return false;
             }
-            remaining_collect_accounts.push(account_info);
-        }
+            true
+        });
         if use_tickarray_bitmap_extension {
             require!(
                 tickarray_bitmap_extension.is_some(),
                 ErrorCode::MissingTickArrayBitmapExtensionAccount
             );
         }
+
+        (reward_info_extension_info, position_reward_extension_info) =
+            find_reward_extension_accounts(
+                remaining_accounts,
+                pool_state_loader.key(),
+                &pool_state,
+                Some(personal_position.nft_mint),
+            )?;
+        remaining_collect_accounts.retain(|account_info| {
+            ![reward_info_extension_info, position_reward_extension_info]
+                .iter()
+                .flatten()
+                .any(|extension_info| extension_info.key == account_info.key)
+        });
     }
+    let mut reward_info_extension = reward_info_extension_info
+        .map(|account_info| AccountLoad::<RewardInfoExtension>::load_data_mut(account_info))
+        .transpose()?;
+    let mut position_reward_extension = position_reward_extension_info
+        .map(|account_info| AccountLoad::<PositionRewardExtension>::load_data_mut(account_info))
+        .transpose()?;
 
     let (decrease_amount_0, latest_fees_owed_0, decrease_amount_1, latest_fees_owed_1) =
         decrease_liquidity_and_update_position(
             pool_state_loader,
-            protocol_position,
             personal_position,
             tick_array_lower_loader,
             tick_array_upper_loader,
             tickarray_bitmap_extension,
+            reward_info_extension.as_deref_mut(),
+            position_reward_extension.as_deref_mut(),
             liquidity,
+            emergency_withdraw,
+            event_authority,
         )?;
 
     let mut transfer_fee_0 = 0;
@@ -209,7 +285,7 @@ pub fn decrease_liquidity<'a, 'b, 'c: 'info, 'info>(
         transfer_fee_1 =
             util::get_transfer_fee(vault_1_mint.clone().unwrap(), decrease_amount_1).unwrap();
     }
-    emit!(LiquidityCalculateEvent {
+    event_authority.emit(LiquidityCalculateEvent {
         pool_liquidity: liquidity_before,
         pool_sqrt_price_x64: pool_sqrt_price_x64,
         pool_tick: pool_tick_current,
@@ -219,7 +295,7 @@ pub fn decrease_liquidity<'a, 'b, 'c: 'info, 'info>(
         trade_fee_owed_1: latest_fees_owed_1,
         transfer_fee_0,
         transfer_fee_1,
-    });
+    })?;
     #[cfg(feature = "enable-log")]
     msg!(
         "decrease_amount_0: {}, transfer_fee_0: {}, latest_fees_owed_0: {}, decrease_amount_1: {}, transfer_fee_1: {}, latest_fees_owed_1: {}",
@@ -257,6 +333,7 @@ pub fn decrease_liquidity<'a, 'b, 'c: 'info, 'info>(
         vault_0_mint,
         token_program,
         token_2022_program_opt.clone(),
+        transfer_hook_accounts,
         transfer_amount_0,
     )?;
 
@@ -267,24 +344,30 @@ pub fn decrease_liquidity<'a, 'b, 'c: 'info, 'info>(
         vault_1_mint.clone(),
         token_program,
         token_2022_program_opt.clone(),
+        transfer_hook_accounts,
         transfer_amount_1,
     )?;
 
     check_unclaimed_fees_and_vault(pool_state_loader, token_vault_0, token_vault_1)?;
 
-    let reward_amounts = collect_rewards(
+    let (reward_amounts, extension_reward_amounts) = collect_rewards(
+        claimer,
         pool_state_loader,
         remaining_collect_accounts.as_slice(),
         token_program,
         token_2022_program_opt.clone(),
+        transfer_hook_accounts,
         personal_position,
+        reward_info_extension.as_deref_mut(),
+        position_reward_extension.as_deref_mut(),
         if token_2022_program_opt.is_none() {
             false
         } else {
             true
         },
+        emergency_withdraw,
     )?;
-    emit!(DecreaseLiquidityEvent {
+    event_authority.emit(DecreaseLiquidityEvent {
         position_nft_mint: personal_position.nft_mint,
         liquidity,
         decrease_amount_0: decrease_amount_0,
@@ -294,60 +377,75 @@ pub fn decrease_liquidity<'a, 'b, 'c: 'info, 'info>(
         reward_amounts,
         transfer_fee_0: transfer_fee_0,
         transfer_fee_1: transfer_fee_1,
-    });
+        extension_reward_amounts,
+    })?;
 
     Ok(())
 }
 
 pub fn decrease_liquidity_and_update_position<'a, 'b, 'c: 'info, 'info>(
     pool_state_loader: &AccountLoader<'info, PoolState>,
-    protocol_position: &mut Box<Account<'info, ProtocolPositionState>>,
     personal_position: &mut Box<Account<'info, PersonalPositionState>>,
     tick_array_lower: &AccountLoader<'info, TickArrayState>,
     tick_array_upper: &AccountLoader<'info, TickArrayState>,
     tick_array_bitmap_extension: Option<&'c AccountInfo<'info>>,
+    reward_info_extension: Option<&mut RewardInfoExtension>,
+    position_reward_extension: Option<&mut PositionRewardExtension>,
     liquidity: u128,
+    emergency_withdraw: bool,
+    event_authority: EventAuthority,
 ) -> Result<(u64, u64, u64, u64)> {
     let mut pool_state = pool_state_loader.load_mut()?;
     let mut decrease_amount_0 = 0;
     let mut decrease_amount_1 = 0;
-    if pool_state.get_status_by_bit(PoolStatusBitIndex::DecreaseLiquidity) {
+    if emergency_withdraw || pool_state.get_status_by_bit(PoolStatusBitIndex::DecreaseLiquidity) {
+        let mut growths_inside = PositionGrowthsInside::default();
         (decrease_amount_0, decrease_amount_1) = burn_liquidity(
             &mut pool_state,
             tick_array_lower,
             tick_array_upper,
-            protocol_position,
+            personal_position.tick_lower_index,
+            personal_position.tick_upper_index,
+            &mut growths_inside,
             tick_array_bitmap_extension,
+            reward_info_extension,
+            personal_position.liquidity,
             liquidity,
+            event_authority,
         )?;
 
         personal_position.token_fees_owed_0 = calculate_latest_token_fees(
             personal_position.token_fees_owed_0,
             personal_position.fee_growth_inside_0_last_x64,
-            protocol_position.fee_growth_inside_0_last_x64,
+            growths_inside.fee_growth_inside_0_x64,
             personal_position.liquidity,
         );
 
         personal_position.token_fees_owed_1 = calculate_latest_token_fees(
             personal_position.token_fees_owed_1,
             personal_position.fee_growth_inside_1_last_x64,
-            protocol_position.fee_growth_inside_1_last_x64,
+            growths_inside.fee_growth_inside_1_x64,
             personal_position.liquidity,
         );
 
-        personal_position.fee_growth_inside_0_last_x64 =
-            protocol_position.fee_growth_inside_0_last_x64;
-        personal_position.fee_growth_inside_1_last_x64 =
-            protocol_position.fee_growth_inside_1_last_x64;
+        personal_position.fee_growth_inside_0_last_x64 = growths_inside.fee_growth_inside_0_x64;
+        personal_position.fee_growth_inside_1_last_x64 = growths_inside.fee_growth_inside_1_x64;
 
         // update rewards, must update before decrease liquidity
-        personal_position.update_rewards(protocol_position.reward_growth_inside, true)?;
+        personal_position.update_rewards(growths_inside.reward_growths_inside, true)?;
+        if let Some(position_reward_extension) = position_reward_extension {
+            position_reward_extension.update_rewards(
+                growths_inside.extension_reward_growths_inside,
+                personal_position.liquidity,
+            )?;
+        }
+        personal_position.update_liquidity_seconds(growths_inside.seconds_inside);
         personal_position.liquidity = personal_position.liquidity.checked_sub(liquidity).unwrap();
     }
 
     let mut latest_fees_owed_0 = 0;
     let mut latest_fees_owed_1 = 0;
-    if pool_state.get_status_by_bit(PoolStatusBitIndex::CollectFee) {
+    if emergency_withdraw || pool_state.get_status_by_bit(PoolStatusBitIndex::CollectFee) {
         latest_fees_owed_0 = personal_position.token_fees_owed_0;
         latest_fees_owed_1 = personal_position.token_fees_owed_1;
 
@@ -385,9 +483,14 @@ pub fn burn_liquidity<'c: 'info, 'info>(
     pool_state: &mut RefMut<PoolState>,
     tick_array_lower_loader: &AccountLoader<'info, TickArrayState>,
     tick_array_upper_loader: &AccountLoader<'info, TickArrayState>,
-    protocol_position: &mut ProtocolPositionState,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    growths_inside: &mut PositionGrowthsInside,
     tickarray_bitmap_extension: Option<&'c AccountInfo<'info>>,
+    reward_info_extension: Option<&mut RewardInfoExtension>,
+    position_liquidity: u128,
     liquidity: u128,
+    event_authority: EventAuthority,
 ) -> Result<(u64, u64)> {
     require_keys_eq!(tick_array_lower_loader.load()?.pool_id, pool_state.key());
     require_keys_eq!(tick_array_upper_loader.load()?.pool_id, pool_state.key());
@@ -395,30 +498,34 @@ pub fn burn_liquidity<'c: 'info, 'info>(
     // get tick_state
     let mut tick_lower_state = *tick_array_lower_loader
         .load_mut()?
-        .get_tick_state_mut(protocol_position.tick_lower_index, pool_state.tick_spacing)?;
+        .get_tick_state_mut(tick_lower_index, pool_state.tick_spacing)?;
     let mut tick_upper_state = *tick_array_upper_loader
         .load_mut()?
-        .get_tick_state_mut(protocol_position.tick_upper_index, pool_state.tick_spacing)?;
+        .get_tick_state_mut(tick_upper_index, pool_state.tick_spacing)?;
     let clock = Clock::get()?;
     let (amount_0, amount_1, flip_tick_lower, flip_tick_upper) = modify_position(
         -i128::try_from(liquidity).unwrap(),
+        position_liquidity,
         pool_state,
-        protocol_position,
+        growths_inside,
         &mut tick_lower_state,
         &mut tick_upper_state,
+        reward_info_extension,
         clock.unix_timestamp as u64,
     )?;
 
     // update tick_state
     tick_array_lower_loader.load_mut()?.update_tick_state(
-        protocol_position.tick_lower_index,
+        tick_lower_index,
         pool_state.tick_spacing,
         tick_lower_state,
+        Some(event_authority),
     )?;
     tick_array_upper_loader.load_mut()?.update_tick_state(
-        protocol_position.tick_upper_index,
+        tick_upper_index,
         pool_state.tick_spacing,
         tick_upper_state,
+        Some(event_authority),
     )?;
 
     if flip_tick_lower {
@@ -442,32 +549,39 @@ pub fn burn_liquidity<'c: 'info, 'info>(
         }
     }
 
-    emit!(LiquidityChangeEvent {
+    event_authority.emit(LiquidityChangeEvent {
         pool_state: pool_state.key(),
         tick: pool_state.tick_current,
-        tick_lower: protocol_position.tick_lower_index,
-        tick_upper: protocol_position.tick_upper_index,
+        tick_lower: tick_lower_index,
+        tick_upper: tick_upper_index,
         liquidity_before: liquidity_before,
         liquidity_after: pool_state.liquidity,
-    });
+    })?;
 
     Ok((amount_0, amount_1))
 }
 
 pub fn collect_rewards<'a, 'b, 'c, 'info>(
+    claimer: &AccountInfo<'info>,
     pool_state_loader: &AccountLoader<'info, PoolState>,
     remaining_accounts: &[&'info AccountInfo<'info>],
     token_program: &'b Program<'info, Token>,
     token_program_2022: Option<AccountInfo<'info>>,
+    transfer_hook_accounts: &[AccountInfo<'info>],
     personal_position_state: &mut PersonalPositionState,
+    mut reward_info_extension: Option<&mut RewardInfoExtension>,
+    mut position_reward_extension: Option<&mut PositionRewardExtension>,
     need_reward_mint: bool,
-) -> Result<[u64; REWARD_NUM]> {
+    emergency_withdraw: bool,
+) -> Result<([u64; REWARD_NUM], [u64; EXTENSION_REWARD_NUM])> {
     let mut reward_amounts: [u64; REWARD_NUM] = [0, 0, 0];
-    if !pool_state_loader
-        .load()?
-        .get_status_by_bit(PoolStatusBitIndex::CollectReward)
+    let mut extension_reward_amounts = [0u64; EXTENSION_REWARD_NUM];
+    if !emergency_withdraw
+        && !pool_state_loader
+            .load()?
+            .get_status_by_bit(PoolStatusBitIndex::CollectReward)
     {
-        return Ok(reward_amounts);
+        return Ok((reward_amounts, extension_reward_amounts));
     }
     let mut reward_group_account_num = 3;
     if !need_reward_mint {
@@ -496,24 +610,76 @@ pub fn collect_rewards<'a, 'b, 'c, 'info>(
             )?));
         }
         require_keys_eq!(reward_token_vault.mint, recipient_token_account.mint);
-        require_keys_eq!(
-            reward_token_vault.key(),
-            pool_state_loader.load_mut()?.reward_infos[i].token_vault
-        );
 
-        let reward_amount_owed = personal_position_state.reward_infos[i].reward_amount_owed;
+        // the reward groups of the extension follow the reward groups of the pool,
+        // which are all initialized once the extension is used
+        let extension_index = i.checked_sub(REWARD_NUM);
+        let reward_amount_owed = match extension_index {
+            None => {
+                require_keys_eq!(
+                    reward_token_vault.key(),
+                    pool_state_loader.load_mut()?.reward_infos[i].token_vault
+                );
+                personal_position_state.reward_infos[i].reward_amount_owed
+            }
+            Some(j) => {
+                let reward_info_extension = reward_info_extension
+                    .as_deref()
+                    .ok_or(ErrorCode::MissingRewardInfoExtension)?;
+                require_keys_eq!(
+                    reward_token_vault.key(),
+                    reward_info_extension.reward_infos[j].token_vault
+                );
+                position_reward_extension
+                    .as_deref()
+                    .ok_or(ErrorCode::MissingPositionRewardExtension)?
+                    .reward_amounts_owed[j]
+            }
+        };
         if reward_amount_owed == 0 {
             continue;
         }
-        pool_state_loader
-            .load()?
-            .check_unclaimed_reward(i, reward_amount_owed)?;
+        match extension_index {
+            None => pool_state_loader
+                .load()?
+                .check_unclaimed_reward(i, reward_amount_owed)?,
+            Some(j) => reward_info_extension
+                .as_deref()
+                .unwrap()
+                .check_unclaimed_reward(j, reward_amount_owed)?,
+        }
 
-        let transfer_amount = if reward_amount_owed > reward_token_vault.amount {
+        let mut transfer_amount = if reward_amount_owed > reward_token_vault.amount {
             reward_token_vault.amount
         } else {
             reward_amount_owed
         };
+        // the vesting rewards of the pool can only be claimed up to the vested part of all earned rewards
+        let mut vesting_reward = false;
+        if extension_index.is_none() {
+            let (reward_vesting, reward_open_time) = {
+                let pool_state = pool_state_loader.load()?;
+                (
+                    pool_state.reward_vestings[i],
+                    pool_state.reward_infos[i].open_time,
+                )
+            };
+            if reward_vesting.is_enabled() {
+                let position_reward_extension = position_reward_extension
+                    .as_deref()
+                    .ok_or(ErrorCode::MissingPositionRewardExtension)?;
+                let reward_claimed = position_reward_extension.vesting_reward_claimed[i];
+                let claimable_amount = reward_vesting
+                    .vested_amount(
+                        reward_claimed.checked_add(reward_amount_owed).unwrap(),
+                        reward_open_time.max(position_reward_extension.vesting_start_time),
+                        Clock::get()?.unix_timestamp as u64,
+                    )
+                    .saturating_sub(reward_claimed);
+                transfer_amount = transfer_amount.min(claimable_amount);
+                vesting_reward = true;
+            }
+        }
 
         if transfer_amount > 0 {
             msg!(
@@ -522,11 +688,37 @@ pub fn collect_rewards<'a, 'b, 'c, 'info>(
                 transfer_amount,
                 reward_amount_owed
             );
-            personal_position_state.reward_infos[i].reward_amount_owed =
-                reward_amount_owed.checked_sub(transfer_amount).unwrap();
-            pool_state_loader
-                .load_mut()?
-                .add_reward_clamed(i, transfer_amount)?;
+            match extension_index {
+                None => {
+                    personal_position_state.reward_infos[i].reward_amount_owed =
+                        reward_amount_owed.checked_sub(transfer_amount).unwrap();
+                    pool_state_loader
+                        .load_mut()?
+                        .add_reward_clamed(i, transfer_amount)?;
+                    if vesting_reward {
+                        let position_reward_extension =
+                            position_reward_extension.as_deref_mut().unwrap();
+                        let mut vesting_reward_claimed =
+                            position_reward_extension.vesting_reward_claimed;
+                        vesting_reward_claimed[i] = vesting_reward_claimed[i]
+                            .checked_add(transfer_amount)
+                            .unwrap();
+                        position_reward_extension.vesting_reward_claimed = vesting_reward_claimed;
+                    }
+                }
+                Some(j) => {
+                    let position_reward_extension =
+                        position_reward_extension.as_deref_mut().unwrap();
+                    let mut reward_amounts_owed = position_reward_extension.reward_amounts_owed;
+                    reward_amounts_owed[j] =
+                        reward_amount_owed.checked_sub(transfer_amount).unwrap();
+                    position_reward_extension.reward_amounts_owed = reward_amounts_owed;
+                    reward_info_extension
+                        .as_deref_mut()
+                        .unwrap()
+                        .add_reward_clamed(j, transfer_amount)?;
+                }
+            }
 
             transfer_from_pool_vault_to_user(
                 &pool_state_loader,
@@ -535,13 +727,65 @@ pub fn collect_rewards<'a, 'b, 'c, 'info>(
                 reward_vault_mint,
                 &token_program,
                 token_program_2022.clone(),
+                transfer_hook_accounts,
                 transfer_amount,
             )?;
+            // the native SOL rewards are unwrapped when claimed to a temporary WSOL account of the claimer
+            util::unwrap_temporary_native_account(
+                claimer,
+                &recipient_token_account.to_account_info(),
+                recipient_token_account.mint,
+                recipient_token_account.owner,
+                &token_program.to_account_info(),
+            )?;
+        }
+        match extension_index {
+            None => reward_amounts[i] = transfer_amount,
+            Some(j) => extension_reward_amounts[j] = transfer_amount,
         }
-        reward_amounts[i] = transfer_amount
     }
 
-    Ok(reward_amounts)
+    Ok((reward_amounts, extension_reward_amounts))
+}
+
+/// The mints whose transfer hook accounts follow the remaining accounts, the vault mints
+/// and the reward mints passed in the reward groups
+fn find_transfer_hook_mints<'info>(
+    remaining_accounts: &[AccountInfo<'info>],
+    pool_state_loader: &AccountLoader<'info, PoolState>,
+    mut mints: Vec<AccountInfo<'info>>,
+) -> Result<Vec<AccountInfo<'info>>> {
+    let pool_state = pool_state_loader.load()?;
+    let mut reward_mints: Vec<Pubkey> = pool_state
+        .reward_infos
+        .into_iter()
+        .filter(|reward_info| reward_info.initialized())
+        .map(|reward_info| reward_info.token_mint)
+        .collect();
+    let (reward_info_extension_info, _) = find_reward_extension_accounts(
+        remaining_accounts,
+        pool_state_loader.key(),
+        &pool_state,
+        None,
+    )?;
+    if let Some(reward_info_extension_info) = reward_info_extension_info {
+        let reward_infos =
+            AccountLoad::<RewardInfoExtension>::load_data_mut(reward_info_extension_info)?
+                .reward_infos;
+        reward_mints.extend(
+            reward_infos
+                .into_iter()
+                .filter(|reward_info| reward_info.initialized())
+                .map(|reward_info| reward_info.token_mint),
+        );
+    }
+    mints.extend(
+        remaining_accounts
+            .iter()
+            .filter(|account_info| reward_mints.contains(account_info.key))
+            .cloned(),
+    );
+    Ok(mints)
 }
 
 fn check_required_accounts_length(
@@ -550,7 +794,7 @@ fn check_required_accounts_length(
     reward_group_account_num: usize,
 ) -> Result<()> {
     let pool_state = pool_state_loader.load()?;
-    let mut valid_reward_count = 0;
+    let mut valid_reward_count = usize::from(pool_state.extension_reward_count);
     for item in pool_state.reward_infos {
         if item.initialized() {
             valid_reward_count = valid_reward_count + 1;
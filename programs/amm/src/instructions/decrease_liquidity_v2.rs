@@ -1,22 +1,20 @@
 use super::decrease_liquidity::decrease_liquidity;
+use crate::error::ErrorCode;
 use crate::states::*;
+use crate::util::get_position_nft_owner;
 use anchor_lang::prelude::*;
 use anchor_spl::token::Token;
 use anchor_spl::token_interface::Mint;
 use anchor_spl::token_interface::{Token2022, TokenAccount};
 
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 pub struct DecreaseLiquidityV2<'info> {
-    /// The position owner or delegated authority
+    /// The position owner or an approved operator
     pub nft_owner: Signer<'info>,
 
-    /// The token account for the tokenized position
-    #[account(
-        constraint = nft_account.mint == personal_position.nft_mint,
-        constraint = nft_account.amount == 1,
-        token::authority = nft_owner,
-    )]
-    pub nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: The token account for the tokenized position, or the MPL Core asset of the position
+    pub nft_account: UncheckedAccount<'info>,
 
     /// Decrease liquidity for this position
     #[account(mut, constraint = personal_position.pool_id == pool_state.key())]
@@ -25,18 +23,9 @@ pub struct DecreaseLiquidityV2<'info> {
     #[account(mut)]
     pub pool_state: AccountLoader<'info, PoolState>,
 
-    #[account(
-        mut,
-        seeds = [
-            POSITION_SEED.as_bytes(),
-            pool_state.key().as_ref(),
-            &personal_position.tick_lower_index.to_be_bytes(),
-            &personal_position.tick_upper_index.to_be_bytes(),
-        ],
-        bump,
-        constraint = protocol_position.pool_id == pool_state.key(),
-    )]
-    pub protocol_position: Box<Account<'info, ProtocolPositionState>>,
+    /// CHECK: Deprecated, the protocol position is folded into the ticks and this account is
+    /// ignored. It is kept so that the account layout of the instruction doesn't change
+    pub protocol_position: UncheckedAccount<'info>,
 
     /// Token_0 vault
     #[account(
@@ -114,9 +103,47 @@ pub fn decrease_liquidity_v2<'a, 'b, 'c: 'info, 'info>(
     amount_0_min: u64,
     amount_1_min: u64,
 ) -> Result<()> {
+    require!(
+        !ctx.accounts.personal_position.locked,
+        ErrorCode::PositionLocked
+    );
+    if liquidity > 0 {
+        require!(
+            !ctx.accounts
+                .personal_position
+                .is_time_locked(Clock::get()?.unix_timestamp as u64),
+            ErrorCode::PositionTimeLocked
+        );
+    }
+    let nft_account_owner = get_position_nft_owner(
+        &ctx.accounts.nft_account,
+        &ctx.accounts.personal_position.nft_mint,
+    )?;
+    let (remaining_accounts, restricted_operator) = check_position_authority(
+        &ctx.accounts.nft_owner.key(),
+        &nft_account_owner,
+        &ctx.accounts.personal_position,
+        ctx.remaining_accounts,
+        if liquidity > 0 {
+            PositionAction::DecreaseLiquidity
+        } else {
+            PositionAction::Collect
+        },
+    )?;
+    if restricted_operator {
+        check_operator_recipients(
+            &nft_account_owner,
+            &ctx.accounts.pool_state.key(),
+            &[
+                ctx.accounts.recipient_token_account_0.to_account_info(),
+                ctx.accounts.recipient_token_account_1.to_account_info(),
+            ],
+            remaining_accounts,
+        )?;
+    }
     decrease_liquidity(
+        &ctx.accounts.nft_owner.to_account_info(),
         &ctx.accounts.pool_state,
-        &mut ctx.accounts.protocol_position,
         &mut ctx.accounts.personal_position,
         &ctx.accounts.token_vault_0.to_account_info(),
         &ctx.accounts.token_vault_1.to_account_info(),
@@ -129,9 +156,10 @@ pub fn decrease_liquidity_v2<'a, 'b, 'c: 'info, 'info>(
         Some(ctx.accounts.memo_program.clone()),
         Some(ctx.accounts.vault_0_mint.clone()),
         Some(ctx.accounts.vault_1_mint.clone()),
-        &ctx.remaining_accounts,
+        remaining_accounts,
         liquidity,
         amount_0_min,
         amount_1_min,
+        crate::event_authority!(ctx.accounts, ctx.bumps),
     )
 }
@@ -0,0 +1,136 @@
+use crate::decrease_liquidity::check_unclaimed_fees_and_vault;
+use crate::emit_event;
+use crate::states::*;
+use crate::util::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct Donate<'info> {
+    /// The donor of the tokens
+    pub donor: Signer<'info>,
+
+    /// The pool whose liquidity in range receives the donation
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// The donor token account for token_0
+    #[account(
+        mut,
+        token::mint = token_vault_0.mint
+    )]
+    pub donor_token_account_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The donor token account for token_1
+    #[account(
+        mut,
+        token::mint = token_vault_1.mint
+    )]
+    pub donor_token_account_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The address that holds pool tokens for token_0
+    #[account(
+        mut,
+        address = pool_state.load()?.token_vault_0
+    )]
+    pub token_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The address that holds pool tokens for token_1
+    #[account(
+        mut,
+        address = pool_state.load()?.token_vault_1
+    )]
+    pub token_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of token vault 0
+    #[account(
+        address = token_vault_0.mint
+    )]
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of token vault 1
+    #[account(
+        address = token_vault_1.mint
+    )]
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// SPL program to transfer out tokens
+    pub token_program: Program<'info, Token>,
+
+    /// Token program 2022
+    pub token_program_2022: Program<'info, Token2022>,
+    // remaining accounts
+    // the extra accounts of the transfer hooks of the vault mints
+}
+
+pub fn donate<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, Donate<'info>>,
+    amount_0: u64,
+    amount_1: u64,
+) -> Result<()> {
+    let vault_mints = [
+        ctx.accounts.vault_0_mint.to_account_info(),
+        ctx.accounts.vault_1_mint.to_account_info(),
+    ];
+    let (_, transfer_hook_accounts) =
+        split_transfer_hook_accounts(ctx.remaining_accounts, &vault_mints)?;
+
+    // the transfer fees are charged on the donation, the rest reaches the vaults
+    let transfer_fee_0 = get_transfer_fee(ctx.accounts.vault_0_mint.clone(), amount_0)?;
+    let transfer_fee_1 = get_transfer_fee(ctx.accounts.vault_1_mint.clone(), amount_1)?;
+    let (liquidity, fee_growth_global_0_x64, fee_growth_global_1_x64) = {
+        let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+        let (fee_growth_global_0_x64, fee_growth_global_1_x64) =
+            pool_state.donate(amount_0 - transfer_fee_0, amount_1 - transfer_fee_1)?;
+        (
+            pool_state.liquidity,
+            fee_growth_global_0_x64,
+            fee_growth_global_1_x64,
+        )
+    };
+
+    transfer_from_user_to_pool_vault(
+        &ctx.accounts.donor,
+        &ctx.accounts.donor_token_account_0.to_account_info(),
+        &ctx.accounts.token_vault_0.to_account_info(),
+        Some(ctx.accounts.vault_0_mint.clone()),
+        &ctx.accounts.token_program,
+        Some(ctx.accounts.token_program_2022.to_account_info()),
+        transfer_hook_accounts,
+        amount_0,
+    )?;
+    transfer_from_user_to_pool_vault(
+        &ctx.accounts.donor,
+        &ctx.accounts.donor_token_account_1.to_account_info(),
+        &ctx.accounts.token_vault_1.to_account_info(),
+        Some(ctx.accounts.vault_1_mint.clone()),
+        &ctx.accounts.token_program,
+        Some(ctx.accounts.token_program_2022.to_account_info()),
+        transfer_hook_accounts,
+        amount_1,
+    )?;
+
+    check_unclaimed_fees_and_vault(
+        &ctx.accounts.pool_state,
+        &ctx.accounts.token_vault_0.to_account_info(),
+        &ctx.accounts.token_vault_1.to_account_info(),
+    )?;
+
+    emit_event!(
+        ctx,
+        DonateEvent {
+            pool_state: ctx.accounts.pool_state.key(),
+            donor: ctx.accounts.donor.key(),
+            amount_0: amount_0 - transfer_fee_0,
+            transfer_fee_0,
+            amount_1: amount_1 - transfer_fee_1,
+            transfer_fee_1,
+            liquidity,
+            fee_growth_global_0_x64,
+            fee_growth_global_1_x64,
+        }
+    )?;
+    Ok(())
+}
@@ -0,0 +1,199 @@
+use super::close_position::{
+    burn_position_nft, check_position_closable, check_position_reward_extension_closable,
+};
+use super::decrease_liquidity::decrease_liquidity;
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::util::{close_account, is_core_asset};
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct ExitPosition<'info> {
+    /// The position nft owner, receives the rent of the closed accounts
+    #[account(mut)]
+    pub nft_owner: Signer<'info>,
+
+    /// CHECK: Mint address bound to the personal position, or the MPL Core asset of the position
+    #[account(
+        mut,
+        address = personal_position.nft_mint,
+    )]
+    pub position_nft_mint: UncheckedAccount<'info>,
+
+    /// CHECK: User token account where position NFT be minted to, or the MPL Core asset of the position
+    #[account(mut)]
+    pub position_nft_account: UncheckedAccount<'info>,
+
+    /// The position to exit
+    #[account(
+        mut,
+        seeds = [POSITION_SEED.as_bytes(), position_nft_mint.key().as_ref()],
+        bump,
+        constraint = personal_position.pool_id == pool_state.key(),
+        close = nft_owner
+    )]
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
+
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// Token_0 vault
+    #[account(
+        mut,
+        constraint = token_vault_0.key() == pool_state.load()?.token_vault_0
+    )]
+    pub token_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token_1 vault
+    #[account(
+        mut,
+        constraint = token_vault_1.key() == pool_state.load()?.token_vault_1
+    )]
+    pub token_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Stores init state for the lower tick
+    #[account(mut, constraint = tick_array_lower.load()?.pool_id == pool_state.key())]
+    pub tick_array_lower: AccountLoader<'info, TickArrayState>,
+
+    /// Stores init state for the upper tick
+    #[account(mut, constraint = tick_array_upper.load()?.pool_id == pool_state.key())]
+    pub tick_array_upper: AccountLoader<'info, TickArrayState>,
+
+    /// The destination token account for receive amount_0 and fee_0
+    #[account(
+        mut,
+        token::mint = token_vault_0.mint
+    )]
+    pub recipient_token_account_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The destination token account for receive amount_1 and fee_1
+    #[account(
+        mut,
+        token::mint = token_vault_1.mint
+    )]
+    pub recipient_token_account_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// SPL program to transfer out tokens and close the position nft
+    pub token_program: Program<'info, Token>,
+
+    /// Token program 2022 to transfer out tokens and close the position nft
+    pub token_program_2022: Program<'info, Token2022>,
+
+    /// The mint of token vault 0
+    #[account(
+        address = token_vault_0.mint
+    )]
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of token vault 1
+    #[account(
+        address = token_vault_1.mint
+    )]
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// System program to close the position state account
+    pub system_program: Program<'info, System>,
+    // remaining accounts
+    // mpl_core_program: required first if the position is an MPL Core asset, which is passed
+    // as both the position_nft_mint and the position_nft_account,
+    // followed by the remaining accounts of decrease_liquidity_v2
+}
+
+pub fn exit_position<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ExitPosition<'info>>,
+    amount_0_min: u64,
+    amount_1_min: u64,
+) -> Result<()> {
+    require!(
+        !ctx.accounts.personal_position.locked,
+        ErrorCode::PositionLocked
+    );
+    let position_nft_mint = ctx.accounts.position_nft_mint.to_account_info();
+    let (mpl_core_program, remaining_accounts) = if is_core_asset(&position_nft_mint) {
+        let (mpl_core_program, remaining_accounts) = ctx
+            .remaining_accounts
+            .split_first()
+            .ok_or(ErrorCode::InvalidCoreAsset)?;
+        (Some(mpl_core_program), remaining_accounts)
+    } else {
+        (None, ctx.remaining_accounts)
+    };
+
+    let liquidity = ctx.accounts.personal_position.liquidity;
+    decrease_liquidity(
+        &ctx.accounts.nft_owner.to_account_info(),
+        &ctx.accounts.pool_state,
+        &mut ctx.accounts.personal_position,
+        &ctx.accounts.token_vault_0.to_account_info(),
+        &ctx.accounts.token_vault_1.to_account_info(),
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        &ctx.accounts.recipient_token_account_0.to_account_info(),
+        &ctx.accounts.recipient_token_account_1.to_account_info(),
+        &ctx.accounts.token_program,
+        Some(ctx.accounts.token_program_2022.clone()),
+        None,
+        Some(ctx.accounts.vault_0_mint.clone()),
+        Some(ctx.accounts.vault_1_mint.clone()),
+        remaining_accounts,
+        liquidity,
+        amount_0_min,
+        amount_1_min,
+        crate::event_authority!(ctx.accounts, ctx.bumps),
+    )?;
+
+    check_position_closable(&ctx.accounts.personal_position)?;
+    let position_reward_extension_info = check_position_reward_extension_closable(
+        &ctx.accounts.personal_position,
+        remaining_accounts,
+    )?;
+    let nft_token_program = if *position_nft_mint.owner == ctx.accounts.token_program_2022.key() {
+        ctx.accounts.token_program_2022.to_account_info()
+    } else {
+        ctx.accounts.token_program.to_account_info()
+    };
+    burn_position_nft(
+        &ctx.accounts.nft_owner,
+        &position_nft_mint,
+        &ctx.accounts.position_nft_account.to_account_info(),
+        &ctx.accounts.personal_position,
+        &nft_token_program,
+        mpl_core_program,
+    )?;
+    if let Some(position_reward_extension_info) = position_reward_extension_info {
+        close_account(
+            position_reward_extension_info.clone(),
+            ctx.accounts.nft_owner.to_account_info(),
+        )?;
+    }
+
+    emit_event!(
+        ctx,
+        ExitPositionEvent {
+            pool_state: ctx.accounts.pool_state.key(),
+            position_nft_mint: position_nft_mint.key(),
+            nft_owner: ctx.accounts.nft_owner.key(),
+            liquidity,
+        }
+    )?;
+    Ok(())
+}
+
+/// Emitted when a position is exited and closed
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct ExitPositionEvent {
+    /// The pool of the position
+    #[index]
+    pub pool_state: Pubkey,
+    /// The closed position
+    pub position_nft_mint: Pubkey,
+    /// The owner receiving the tokens and the rent
+    pub nft_owner: Pubkey,
+    /// The liquidity removed from the position
+    pub liquidity: u128,
+}
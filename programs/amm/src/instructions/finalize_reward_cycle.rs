@@ -0,0 +1,117 @@
+use super::collect_remaining_rewards::get_remaining_reward_amount;
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::util::{split_transfer_hook_accounts, transfer_from_pool_vault_to_user};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::Token,
+    token_interface::{Mint, Token2022, TokenAccount},
+};
+
+/// The seconds after the end of a reward before anyone can refund its remaining tokens,
+/// leaving the reward authority time to extend the reward instead
+pub const REWARD_FINALIZE_GRACE_PERIOD: u64 = 3 * 24 * 60 * 60;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct FinalizeRewardCycle<'info> {
+    /// Anyone can finalize an ended reward
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// The vault of the reward
+    #[account(mut)]
+    pub reward_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token account of the reward authority receiving the remaining tokens
+    #[account(
+        mut,
+        token::mint = reward_token_vault.mint,
+    )]
+    pub authority_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of reward token vault
+    #[account(
+        address = reward_token_vault.mint
+    )]
+    pub reward_vault_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Program<'info, Token>,
+
+    /// Token program 2022
+    pub token_program_2022: Program<'info, Token2022>,
+}
+
+pub fn finalize_reward_cycle<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, FinalizeRewardCycle<'info>>,
+    reward_index: u8,
+) -> Result<()> {
+    require_gt!(
+        REWARD_NUM,
+        usize::from(reward_index),
+        ErrorCode::InvalidRewardIndex
+    );
+    let current_timestamp = u64::try_from(Clock::get()?.unix_timestamp).unwrap();
+    let reward_info = ctx.accounts.pool_state.load()?.reward_infos[reward_index as usize];
+    require_keys_eq!(
+        ctx.accounts.authority_token_account.owner,
+        reward_info.authority,
+        ErrorCode::NotApproved
+    );
+    let end_time = reward_info.end_time;
+    require_gte!(
+        current_timestamp,
+        end_time.checked_add(REWARD_FINALIZE_GRACE_PERIOD).unwrap(),
+        ErrorCode::RewardGracePeriodNotEnded
+    );
+    let (_, transfer_hook_accounts) = split_transfer_hook_accounts(
+        ctx.remaining_accounts,
+        &[ctx.accounts.reward_vault_mint.to_account_info()],
+    )?;
+    let (amount_remaining, _) = get_remaining_reward_amount(
+        &ctx.accounts.pool_state,
+        &ctx.accounts.reward_token_vault,
+        reward_index,
+        None,
+    )?;
+
+    transfer_from_pool_vault_to_user(
+        &ctx.accounts.pool_state,
+        &ctx.accounts.reward_token_vault.to_account_info(),
+        &ctx.accounts.authority_token_account.to_account_info(),
+        Some(ctx.accounts.reward_vault_mint.clone()),
+        &ctx.accounts.token_program,
+        Some(ctx.accounts.token_program_2022.to_account_info()),
+        transfer_hook_accounts,
+        amount_remaining,
+    )?;
+
+    emit_event!(
+        ctx,
+        FinalizeRewardCycleEvent {
+            pool_state: ctx.accounts.pool_state.key(),
+            reward_index,
+            authority_token_account: ctx.accounts.authority_token_account.key(),
+            amount: amount_remaining,
+        }
+    )?;
+    Ok(())
+}
+
+/// Emitted when the remaining tokens of an ended reward are refunded to the reward authority
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct FinalizeRewardCycleEvent {
+    /// The pool of the reward
+    #[index]
+    pub pool_state: Pubkey,
+    /// The index of the reward
+    pub reward_index: u8,
+    /// The token account of the reward authority receiving the remaining tokens
+    pub authority_token_account: Pubkey,
+    /// The amount of the reward never emitted
+    pub amount: u64,
+}
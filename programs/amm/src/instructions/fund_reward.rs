@@ -0,0 +1,160 @@
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::libraries::{fixed_point_64, full_math::MulDiv, U256};
+use crate::states::pool::{reward_period_limit, REWARD_NUM};
+use crate::states::*;
+use crate::util::{self, transfer_from_user_to_pool_vault};
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct FundReward<'info> {
+    /// Anyone who deposits reward tokens to the vault
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// The funder's reward token account
+    #[account(
+        mut,
+        token::mint = reward_token_mint
+    )]
+    pub funder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The pool of the reward
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// The vault of the reward
+    #[account(
+        mut,
+        address = pool_state.load()?.reward_infos[reward_index as usize].token_vault
+    )]
+    pub reward_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Reward mint
+    #[account(address = reward_token_vault.mint)]
+    pub reward_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub reward_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    // remaining accounts
+    // the extra accounts of the transfer hook of the reward mint
+}
+
+pub fn fund_reward<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, FundReward<'info>>,
+    reward_index: u8,
+    amount: u64,
+) -> Result<()> {
+    require_gt!(REWARD_NUM, reward_index as usize);
+    require_gt!(amount, 0);
+    let current_timestamp = u64::try_from(Clock::get()?.unix_timestamp).unwrap();
+
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    pool_state.update_reward_infos(current_timestamp)?;
+
+    let mut reward_info = pool_state.reward_infos[reward_index as usize];
+    if !reward_info.initialized() {
+        return err!(ErrorCode::UnInitializedRewardInfo);
+    }
+    // only a running or upcoming cycle can be extended, a finished cycle must be restarted by the reward authority
+    require_gt!(reward_info.end_time, current_timestamp);
+    // the period of a reward without emissions can't be extended
+    require_gt!(
+        reward_info.emissions_per_second_x64,
+        0,
+        ErrorCode::InvalidRewardPeriod
+    );
+
+    // the vault receives the amount without the transfer fee, which funds the extended period
+    let received_amount = amount
+        .checked_sub(util::get_transfer_fee(
+            ctx.accounts.reward_token_mint.clone(),
+            amount,
+        )?)
+        .ok_or(ErrorCode::CalculateOverflow)?;
+    let extend_period = U256::from(received_amount)
+        .mul_div_floor(
+            U256::from(fixed_point_64::Q64),
+            U256::from(reward_info.emissions_per_second_x64),
+        )
+        .ok_or(ErrorCode::CalculateOverflow)?
+        .as_u64();
+    require_gt!(extend_period, 0, ErrorCode::InvalidRewardPeriod);
+
+    let end_time = reward_info
+        .end_time
+        .checked_add(extend_period)
+        .ok_or(ErrorCode::CalculateOverflow)?;
+    if end_time - reward_info.open_time.max(current_timestamp)
+        > reward_period_limit::MAX_REWARD_PERIOD
+    {
+        return err!(ErrorCode::InvalidRewardPeriod);
+    }
+    reward_info.end_time = end_time;
+    pool_state.reward_infos[reward_index as usize] = reward_info;
+    drop(pool_state);
+
+    let (_, transfer_hook_accounts) = util::split_transfer_hook_accounts(
+        ctx.remaining_accounts,
+        &[ctx.accounts.reward_token_mint.to_account_info()],
+    )?;
+
+    // native SOL rewards are wrapped from the lamports of the funder when its WSOL is not enough
+    if ctx.accounts.reward_token_mint.key() == native_mint::id() {
+        util::wrap_native_sol(
+            &ctx.accounts.funder,
+            &ctx.accounts.funder_token_account.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &ctx.accounts.reward_token_program.to_account_info(),
+            amount.saturating_sub(ctx.accounts.funder_token_account.amount),
+        )?;
+    }
+    transfer_from_user_to_pool_vault(
+        &ctx.accounts.funder,
+        &ctx.accounts.funder_token_account.to_account_info(),
+        &ctx.accounts.reward_token_vault.to_account_info(),
+        Some(ctx.accounts.reward_token_mint.clone()),
+        &ctx.accounts.reward_token_program.to_account_info(),
+        Some(ctx.accounts.reward_token_program.to_account_info()),
+        transfer_hook_accounts,
+        amount,
+    )?;
+
+    emit_event!(
+        ctx,
+        FundRewardEvent {
+            pool_state: ctx.accounts.pool_state.key(),
+            funder: ctx.accounts.funder.key(),
+            reward_index,
+            amount,
+            end_time,
+        }
+    )?;
+
+    Ok(())
+}
+
+/// Emitted when anyone funds a reward of the pool to extend its current cycle
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct FundRewardEvent {
+    /// The pool of the reward
+    #[index]
+    pub pool_state: Pubkey,
+
+    /// The account that deposited the reward tokens
+    pub funder: Pubkey,
+
+    /// The index of the reward in the pool
+    pub reward_index: u8,
+
+    /// The amount of reward tokens deposited, including the transfer fee
+    pub amount: u64,
+
+    /// The end time of the reward after the extension
+    pub end_time: u64,
+}
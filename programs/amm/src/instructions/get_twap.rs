@@ -0,0 +1,112 @@
+use crate::error::ErrorCode;
+use crate::libraries::tick_math;
+use crate::states::*;
+use crate::util::create_or_allocate_account;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct GetTwap<'info> {
+    /// The pool to read the twap of
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// The observation account of the pool
+    #[account(address = pool_state.load()?.observation_key)]
+    pub observation_state: AccountLoader<'info, ObservationState>,
+}
+
+pub fn get_twap<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, GetTwap<'info>>,
+    window_seconds: u32,
+) -> Result<TwapResult> {
+    require_gt!(window_seconds, 0);
+    let block_timestamp = oracle::block_timestamp();
+    let twap = {
+        let pool_state = ctx.accounts.pool_state.load()?;
+        let observation_state = ctx.accounts.observation_state.load()?;
+        let data = ctx.accounts.observation_state.as_ref().try_borrow_data()?;
+        let extension = &data[8 + std::mem::size_of::<ObservationState>()..];
+
+        let (tick_cumulative_end, seconds_per_liquidity_end_x64) = observation_state.observe(
+            extension,
+            block_timestamp,
+            0,
+            pool_state.tick_current,
+            pool_state.liquidity,
+        )?;
+        let (tick_cumulative_start, seconds_per_liquidity_start_x64) = observation_state.observe(
+            extension,
+            block_timestamp,
+            window_seconds,
+            pool_state.tick_current,
+            pool_state.liquidity,
+        )?;
+        let average_tick =
+            get_average_tick(tick_cumulative_end - tick_cumulative_start, window_seconds);
+        TwapResult {
+            average_tick,
+            sqrt_price_x64: tick_math::get_sqrt_price_at_tick(average_tick)?,
+            seconds_per_liquidity_x64: seconds_per_liquidity_end_x64
+                .wrapping_sub(seconds_per_liquidity_start_x64),
+        }
+    };
+
+    // the twap snapshot account is optional, followed by the payer and system program to create it
+    if !ctx.remaining_accounts.is_empty() {
+        write_twap_snapshot(
+            ctx.accounts.pool_state.key(),
+            window_seconds,
+            &twap,
+            u64::from(block_timestamp),
+            ctx.remaining_accounts,
+        )?;
+    }
+    Ok(twap)
+}
+
+fn write_twap_snapshot<'info>(
+    pool_id: Pubkey,
+    window_seconds: u32,
+    twap: &TwapResult,
+    update_timestamp: u64,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    let twap_snapshot_info = &remaining_accounts[0];
+    let (twap_snapshot_key, bump) = TwapSnapshot::key(pool_id, window_seconds);
+    require_keys_eq!(twap_snapshot_info.key(), twap_snapshot_key);
+
+    if twap_snapshot_info.data_is_empty() {
+        require_gte!(
+            remaining_accounts.len(),
+            3,
+            ErrorCode::NotEnoughRemainingAccounts
+        );
+        create_or_allocate_account(
+            &crate::id(),
+            remaining_accounts[1].clone(),
+            remaining_accounts[2].clone(),
+            twap_snapshot_info.clone(),
+            &[
+                TWAP_SNAPSHOT_SEED.as_bytes(),
+                pool_id.as_ref(),
+                &window_seconds.to_be_bytes(),
+                &[bump],
+            ],
+            TwapSnapshot::LEN,
+        )?;
+    } else {
+        // check the owner and discriminator
+        Account::<TwapSnapshot>::try_from(twap_snapshot_info)?;
+    }
+
+    let twap_snapshot = TwapSnapshot {
+        bump,
+        pool_id,
+        window_seconds,
+        average_tick: twap.average_tick,
+        sqrt_price_x64: twap.sqrt_price_x64,
+        update_timestamp,
+        padding: [0; 4],
+    };
+    twap_snapshot.try_serialize(&mut &mut twap_snapshot_info.try_borrow_mut_data()?[..])?;
+    Ok(())
+}
@@ -7,34 +7,25 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount};
 use anchor_spl::token_interface::{Mint, Token2022};
 
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 pub struct IncreaseLiquidity<'info> {
-    /// Pays to mint the position
+    /// Pays to mint the position, the position owner or an approved operator
     pub nft_owner: Signer<'info>,
 
     /// The token account for nft
     #[account(
         constraint = nft_account.mint == personal_position.nft_mint,
         constraint = nft_account.amount == 1,
-        token::authority = nft_owner
     )]
     pub nft_account: Box<Account<'info, TokenAccount>>,
 
     #[account(mut)]
     pub pool_state: AccountLoader<'info, PoolState>,
 
-    #[account(
-        mut,
-        seeds = [
-            POSITION_SEED.as_bytes(),
-            pool_state.key().as_ref(),
-            &personal_position.tick_lower_index.to_be_bytes(),
-            &personal_position.tick_upper_index.to_be_bytes(),
-        ],
-        bump,
-        constraint = protocol_position.pool_id == pool_state.key(),
-    )]
-    pub protocol_position: Box<Account<'info, ProtocolPositionState>>,
+    /// CHECK: Deprecated, the protocol position is folded into the ticks and this account is
+    /// ignored. It is kept so that the account layout of the instruction doesn't change
+    pub protocol_position: UncheckedAccount<'info>,
 
     /// Increase liquidity for this position
     #[account(mut, constraint = personal_position.pool_id == pool_state.key())]
@@ -96,13 +87,19 @@ pub fn increase_liquidity_v1<'a, 'b, 'c: 'info, 'info>(
     amount_1_max: u64,
     base_flag: Option<bool>,
 ) -> Result<()> {
+    let (remaining_accounts, _) = check_position_authority(
+        &ctx.accounts.nft_owner.key(),
+        &ctx.accounts.nft_account.owner,
+        &ctx.accounts.personal_position,
+        ctx.remaining_accounts,
+        PositionAction::IncreaseLiquidity,
+    )?;
     increase_liquidity(
         &ctx.accounts.nft_owner,
         &ctx.accounts.pool_state,
-        &mut ctx.accounts.protocol_position,
         &mut ctx.accounts.personal_position,
-        &ctx.accounts.tick_array_lower,
-        &ctx.accounts.tick_array_upper,
+        &AccountLoad::<TickArrayState>::try_from(&ctx.accounts.tick_array_lower.to_account_info())?,
+        &AccountLoad::<TickArrayState>::try_from(&ctx.accounts.tick_array_upper.to_account_info())?,
         &ctx.accounts.token_account_0.to_account_info(),
         &ctx.accounts.token_account_1.to_account_info(),
         &ctx.accounts.token_vault_0.to_account_info(),
@@ -111,21 +108,21 @@ pub fn increase_liquidity_v1<'a, 'b, 'c: 'info, 'info>(
         None,
         None,
         None,
-        &ctx.remaining_accounts,
+        remaining_accounts,
         liquidity,
         amount_0_max,
         amount_1_max,
         base_flag,
+        crate::event_authority!(ctx.accounts, ctx.bumps),
     )
 }
 
 pub fn increase_liquidity<'a, 'b, 'c: 'info, 'info>(
     nft_owner: &'b Signer<'info>,
     pool_state_loader: &'b AccountLoader<'info, PoolState>,
-    protocol_position: &'b mut Box<Account<'info, ProtocolPositionState>>,
     personal_position: &'b mut Box<Account<'info, PersonalPositionState>>,
-    tick_array_lower_loader: &'b AccountLoader<'info, TickArrayState>,
-    tick_array_upper_loader: &'b AccountLoader<'info, TickArrayState>,
+    tick_array_lower_loader: &'b AccountLoad<'info, TickArrayState>,
+    tick_array_upper_loader: &'b AccountLoad<'info, TickArrayState>,
     token_account_0: &'b AccountInfo<'info>,
     token_account_1: &'b AccountInfo<'info>,
     token_vault_0: &'b AccountInfo<'info>,
@@ -140,7 +137,16 @@ pub fn increase_liquidity<'a, 'b, 'c: 'info, 'info>(
     amount_0_max: u64,
     amount_1_max: u64,
     base_flag: Option<bool>,
+    event_authority: EventAuthority,
 ) -> Result<()> {
+    let vault_mints: Vec<AccountInfo> = vault_0_mint
+        .iter()
+        .chain(vault_1_mint.iter())
+        .map(|mint| mint.to_account_info())
+        .collect();
+    let (remaining_accounts, transfer_hook_accounts) =
+        split_transfer_hook_accounts(remaining_accounts, &vault_mints)?;
+    check_remaining_accounts_max(remaining_accounts, MAX_LIQUIDITY_REMAINING_ACCOUNTS)?;
     let mut liquidity = liquidity;
     let pool_state = &mut pool_state_loader.load_mut()?;
     if !pool_state.get_status_by_bit(PoolStatusBitIndex::OpenPositionOrIncreaseLiquidity) {
@@ -151,16 +157,30 @@ pub fn increase_liquidity<'a, 'b, 'c: 'info, 'info>(
 
     let use_tickarray_bitmap_extension =
         pool_state.is_overflow_default_tickarray_bitmap(vec![tick_lower, tick_upper]);
+    let (reward_info_extension_info, position_reward_extension_info) =
+        find_reward_extension_accounts(
+            remaining_accounts,
+            pool_state_loader.key(),
+            pool_state,
+            Some(personal_position.nft_mint),
+        )?;
+    let mut reward_info_extension = reward_info_extension_info
+        .map(|account_info| AccountLoad::<RewardInfoExtension>::load_data_mut(account_info))
+        .transpose()?;
+    let mut position_reward_extension = position_reward_extension_info
+        .map(|account_info| AccountLoad::<PositionRewardExtension>::load_data_mut(account_info))
+        .transpose()?;
 
+    let mut growths_inside = PositionGrowthsInside::default();
     let (amount_0, amount_1, amount_0_transfer_fee, amount_1_transfer_fee) = add_liquidity(
         &nft_owner,
         token_account_0,
         token_account_1,
         token_vault_0,
         token_vault_1,
-        &AccountLoad::<TickArrayState>::try_from(&tick_array_lower_loader.to_account_info())?,
-        &AccountLoad::<TickArrayState>::try_from(&tick_array_upper_loader.to_account_info())?,
-        protocol_position,
+        tick_array_lower_loader,
+        tick_array_upper_loader,
+        &mut growths_inside,
         token_program_2022,
         token_program,
         vault_0_mint,
@@ -174,6 +194,7 @@ pub fn increase_liquidity<'a, 'b, 'c: 'info, 'info>(
         } else {
             None
         },
+        reward_info_extension.as_deref_mut(),
         pool_state,
         &mut liquidity,
         amount_0_max,
@@ -181,37 +202,62 @@ pub fn increase_liquidity<'a, 'b, 'c: 'info, 'info>(
         tick_lower,
         tick_upper,
         base_flag,
+        transfer_hook_accounts,
+        event_authority,
+    )?;
+
+    increase_personal_position(
+        personal_position,
+        &growths_inside,
+        position_reward_extension.as_deref_mut(),
+        liquidity,
     )?;
 
+    event_authority.emit(IncreaseLiquidityEvent {
+        position_nft_mint: personal_position.nft_mint,
+        liquidity,
+        amount_0,
+        amount_1,
+        amount_0_transfer_fee,
+        amount_1_transfer_fee,
+    })?;
+
+    Ok(())
+}
+
+/// Accrues the fees and rewards of the position up to the latest growths inside, then adds the liquidity
+pub fn increase_personal_position(
+    personal_position: &mut PersonalPositionState,
+    growths_inside: &PositionGrowthsInside,
+    position_reward_extension: Option<&mut PositionRewardExtension>,
+    liquidity: u128,
+) -> Result<()> {
     personal_position.token_fees_owed_0 = calculate_latest_token_fees(
         personal_position.token_fees_owed_0,
         personal_position.fee_growth_inside_0_last_x64,
-        protocol_position.fee_growth_inside_0_last_x64,
+        growths_inside.fee_growth_inside_0_x64,
         personal_position.liquidity,
     );
     personal_position.token_fees_owed_1 = calculate_latest_token_fees(
         personal_position.token_fees_owed_1,
         personal_position.fee_growth_inside_1_last_x64,
-        protocol_position.fee_growth_inside_1_last_x64,
+        growths_inside.fee_growth_inside_1_x64,
         personal_position.liquidity,
     );
 
-    personal_position.fee_growth_inside_0_last_x64 = protocol_position.fee_growth_inside_0_last_x64;
-    personal_position.fee_growth_inside_1_last_x64 = protocol_position.fee_growth_inside_1_last_x64;
+    personal_position.fee_growth_inside_0_last_x64 = growths_inside.fee_growth_inside_0_x64;
+    personal_position.fee_growth_inside_1_last_x64 = growths_inside.fee_growth_inside_1_x64;
 
     // update rewards, must update before increase liquidity
-    personal_position.update_rewards(protocol_position.reward_growth_inside, true)?;
+    personal_position.update_rewards(growths_inside.reward_growths_inside, true)?;
+    if let Some(position_reward_extension) = position_reward_extension {
+        position_reward_extension.update_rewards(
+            growths_inside.extension_reward_growths_inside,
+            personal_position.liquidity,
+        )?;
+    }
+    personal_position.update_liquidity_seconds(growths_inside.seconds_inside);
     personal_position.liquidity = personal_position.liquidity.checked_add(liquidity).unwrap();
-
-    emit!(IncreaseLiquidityEvent {
-        position_nft_mint: personal_position.nft_mint,
-        liquidity,
-        amount_0,
-        amount_1,
-        amount_0_transfer_fee,
-        amount_1_transfer_fee
-    });
-
     Ok(())
 }
 
@@ -1,37 +1,25 @@
 use super::increase_liquidity::increase_liquidity;
 use crate::states::*;
+use crate::util::{get_position_nft_owner, AccountLoad};
 use anchor_lang::prelude::*;
 use anchor_spl::token::Token;
 use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
 
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 pub struct IncreaseLiquidityV2<'info> {
-    /// Pays to mint the position
+    /// Pays to mint the position, the position owner or an approved operator
     pub nft_owner: Signer<'info>,
 
-    /// The token account for nft
-    #[account(
-        constraint = nft_account.mint == personal_position.nft_mint,
-        constraint = nft_account.amount == 1,
-        token::authority = nft_owner,
-    )]
-    pub nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: The token account for nft, or the MPL Core asset of the position
+    pub nft_account: UncheckedAccount<'info>,
 
     #[account(mut)]
     pub pool_state: AccountLoader<'info, PoolState>,
 
-    #[account(
-        mut,
-        seeds = [
-            POSITION_SEED.as_bytes(),
-            pool_state.key().as_ref(),
-            &personal_position.tick_lower_index.to_be_bytes(),
-            &personal_position.tick_upper_index.to_be_bytes(),
-        ],
-        bump,
-        constraint = protocol_position.pool_id == pool_state.key(),
-    )]
-    pub protocol_position: Box<Account<'info, ProtocolPositionState>>,
+    /// CHECK: Deprecated, the protocol position is folded into the ticks and this account is
+    /// ignored. It is kept so that the account layout of the instruction doesn't change
+    pub protocol_position: UncheckedAccount<'info>,
 
     /// Increase liquidity for this position
     #[account(mut, constraint = personal_position.pool_id == pool_state.key())]
@@ -108,13 +96,23 @@ pub fn increase_liquidity_v2<'a, 'b, 'c: 'info, 'info>(
     amount_1_max: u64,
     base_flag: Option<bool>,
 ) -> Result<()> {
+    let nft_account_owner = get_position_nft_owner(
+        &ctx.accounts.nft_account,
+        &ctx.accounts.personal_position.nft_mint,
+    )?;
+    let (remaining_accounts, _) = check_position_authority(
+        &ctx.accounts.nft_owner.key(),
+        &nft_account_owner,
+        &ctx.accounts.personal_position,
+        ctx.remaining_accounts,
+        PositionAction::IncreaseLiquidity,
+    )?;
     increase_liquidity(
         &ctx.accounts.nft_owner,
         &ctx.accounts.pool_state,
-        &mut ctx.accounts.protocol_position,
         &mut ctx.accounts.personal_position,
-        &ctx.accounts.tick_array_lower,
-        &ctx.accounts.tick_array_upper,
+        &AccountLoad::<TickArrayState>::try_from(&ctx.accounts.tick_array_lower.to_account_info())?,
+        &AccountLoad::<TickArrayState>::try_from(&ctx.accounts.tick_array_upper.to_account_info())?,
         &ctx.accounts.token_account_0.to_account_info(),
         &ctx.accounts.token_account_1.to_account_info(),
         &ctx.accounts.token_vault_0.to_account_info(),
@@ -123,10 +121,11 @@ pub fn increase_liquidity_v2<'a, 'b, 'c: 'info, 'info>(
         Some(&ctx.accounts.token_program_2022),
         Some(ctx.accounts.vault_0_mint.clone()),
         Some(ctx.accounts.vault_1_mint.clone()),
-        &ctx.remaining_accounts,
+        remaining_accounts,
         liquidity,
         amount_0_max,
         amount_1_max,
         base_flag,
+        crate::event_authority!(ctx.accounts, ctx.bumps),
     )
 }
@@ -0,0 +1,62 @@
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+#[instruction(observation_cardinality_next: u16)]
+pub struct IncreaseObservationCardinality<'info> {
+    /// Pays the rent of the extended observation account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The pool of the observation account
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// The observation account to be extended
+    #[account(
+        mut,
+        address = pool_state.load()?.observation_key,
+        realloc = ObservationState::len_with_cardinality(observation_cardinality_next),
+        realloc::payer = payer,
+        realloc::zero = false,
+    )]
+    pub observation_state: AccountLoader<'info, ObservationState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn increase_observation_cardinality(
+    ctx: Context<IncreaseObservationCardinality>,
+    observation_cardinality_next: u16,
+) -> Result<()> {
+    require_gte!(
+        OBSERVATION_CARDINALITY_MAX,
+        observation_cardinality_next,
+        ErrorCode::InvalidObservationCardinality
+    );
+    let mut observation_state = ctx.accounts.observation_state.load_mut()?;
+    if observation_state.observation_cardinality == 0 {
+        observation_state.observation_cardinality = OBSERVATION_NUM as u16;
+    }
+    let observation_cardinality_next_old = observation_state
+        .observation_cardinality_next
+        .max(observation_state.observation_cardinality);
+    require_gt!(
+        observation_cardinality_next,
+        observation_cardinality_next_old,
+        ErrorCode::InvalidObservationCardinality
+    );
+    observation_state.observation_cardinality_next = observation_cardinality_next;
+
+    emit_event!(
+        ctx,
+        IncreaseObservationCardinalityEvent {
+            pool_state: ctx.accounts.pool_state.key(),
+            observation_cardinality_next_old,
+            observation_cardinality_next,
+        }
+    )?;
+    Ok(())
+}
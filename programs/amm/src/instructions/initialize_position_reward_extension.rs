@@ -0,0 +1,40 @@
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct InitializePositionRewardExtension<'info> {
+    /// Pays to create the position reward extension
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The position opened before the pool had rewards in its reward info extension or vesting rewards
+    #[account(mut)]
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
+
+    /// The reward accounting of the position for the extension rewards and the vesting rewards
+    #[account(
+        init,
+        seeds = [
+            POSITION_REWARD_EXTENSION_SEED.as_bytes(),
+            personal_position.nft_mint.as_ref(),
+        ],
+        bump,
+        payer = payer,
+        space = PositionRewardExtension::LEN
+    )]
+    pub position_reward_extension: AccountLoader<'info, PositionRewardExtension>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_position_reward_extension(
+    ctx: Context<InitializePositionRewardExtension>,
+) -> Result<()> {
+    let mut position_reward_extension = ctx.accounts.position_reward_extension.load_init()?;
+    position_reward_extension.nft_mint = ctx.accounts.personal_position.nft_mint;
+    // the growths start from zero like the rewards initialized in the pool after the position was opened,
+    // positions opened after the first extension reward create the account with their growths inside
+    position_reward_extension.vesting_start_time = Clock::get()?.unix_timestamp as u64;
+    ctx.accounts.personal_position.has_reward_extension = true;
+    Ok(())
+}
@@ -1,10 +1,13 @@
+use crate::emit_event;
 use crate::error::ErrorCode;
 use crate::libraries::{fixed_point_64, full_math::MulDiv, U256};
-use crate::util::transfer_from_user_to_pool_vault;
+use crate::util::{transfer_from_user_to_pool_vault, AccountLoad};
 use crate::{states::*, util};
 use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 pub struct InitializeReward<'info> {
     /// The founder deposit reward token to vault
@@ -88,11 +91,11 @@ impl InitializeRewardParam {
     }
 }
 
-pub fn initialize_reward(
-    ctx: Context<InitializeReward>,
+pub fn initialize_reward<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, InitializeReward<'info>>,
     param: InitializeRewardParam,
 ) -> Result<()> {
-    if !util::is_supported_mint(&ctx.accounts.reward_token_mint).unwrap() {
+    if !util::is_supported_mint(&ctx.accounts.reward_token_mint, false, false).unwrap() {
         return err!(ErrorCode::NotSupportMint);
     }
     let operation_state = ctx.accounts.operation_state.load()?;
@@ -122,21 +125,86 @@ pub fn initialize_reward(
                 .unwrap(),
         )
         .unwrap();
-    require_gte!(
-        ctx.accounts.funder_token_account.amount,
-        reward_amount_with_transfer_fee
-    );
+    // native SOL rewards are wrapped from the lamports of the funder when its WSOL is not enough
+    if ctx.accounts.reward_token_mint.key() == native_mint::id() {
+        util::wrap_native_sol(
+            &ctx.accounts.reward_funder,
+            &ctx.accounts.funder_token_account.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &ctx.accounts.reward_token_program.to_account_info(),
+            reward_amount_with_transfer_fee
+                .saturating_sub(ctx.accounts.funder_token_account.amount),
+        )?;
+    } else {
+        require_gte!(
+            ctx.accounts.funder_token_account.amount,
+            reward_amount_with_transfer_fee
+        );
+    }
 
     let mut pool_state = ctx.accounts.pool_state.load_mut()?;
-    pool_state.initialize_reward(
-        param.open_time,
-        param.end_time,
-        param.emissions_per_second_x64,
-        &ctx.accounts.reward_token_mint.key(),
-        &ctx.accounts.reward_token_vault.key(),
-        &ctx.accounts.reward_funder.key(),
-        &operation_state,
-    )?;
+    if pool_state.reward_infos.iter().all(|r| r.initialized()) {
+        // the rewards beyond the slots of the pool are initialized in the reward info extension,
+        // which are controled by the admin like the last reward slot of the pool
+        require!(
+            ctx.accounts.reward_funder.key() == crate::admin::id()
+                || operation_state.validate_operation_owner(ctx.accounts.reward_funder.key()),
+            ErrorCode::NotApproved
+        );
+        let reward_info_extension_info = ctx
+            .remaining_accounts
+            .first()
+            .ok_or(ErrorCode::MissingRewardInfoExtension)?;
+        RewardInfoExtension::get_or_create_reward_info_extension(
+            ctx.accounts.reward_funder.to_account_info(),
+            reward_info_extension_info,
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.pool_state.key(),
+        )?;
+        let mut reward_info_extension =
+            AccountLoad::<RewardInfoExtension>::load_data_mut(reward_info_extension_info)?;
+        let reward_mints: Vec<Pubkey> = pool_state
+            .reward_infos
+            .iter()
+            .map(|r| r.token_mint)
+            .collect();
+        let reward_index = reward_info_extension.initialize_reward(
+            param.open_time,
+            param.end_time,
+            param.emissions_per_second_x64,
+            &ctx.accounts.reward_token_mint.key(),
+            &ctx.accounts.reward_token_vault.key(),
+            &ctx.accounts.reward_funder.key(),
+            &reward_mints,
+        )?;
+        pool_state.extension_reward_count =
+            pool_state.extension_reward_count.checked_add(1).unwrap();
+        emit_event!(
+            ctx,
+            InitializeExtensionRewardEvent {
+                pool_state: ctx.accounts.pool_state.key(),
+                reward_index: reward_index as u8,
+                reward_mint: ctx.accounts.reward_token_mint.key(),
+                open_time: param.open_time,
+                end_time: param.end_time,
+                emissions_per_second_x64: param.emissions_per_second_x64,
+            }
+        )?;
+    } else {
+        pool_state.initialize_reward(
+            param.open_time,
+            param.end_time,
+            param.emissions_per_second_x64,
+            &ctx.accounts.reward_token_mint.key(),
+            &ctx.accounts.reward_token_vault.key(),
+            &ctx.accounts.reward_funder.key(),
+            &operation_state,
+            &ctx.accounts
+                .operation_state
+                .to_account_info()
+                .try_borrow_data()?[OperationState::LEN..],
+        )?;
+    }
 
     transfer_from_user_to_pool_vault(
         &ctx.accounts.reward_funder,
@@ -145,6 +213,7 @@ pub fn initialize_reward(
         Some(ctx.accounts.reward_token_mint.clone()),
         &ctx.accounts.reward_token_program.to_account_info(),
         Some(ctx.accounts.reward_token_program.to_account_info()),
+        &[],
         reward_amount_with_transfer_fee,
     )?;
 
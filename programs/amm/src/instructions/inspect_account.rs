@@ -0,0 +1,344 @@
+use crate::states::*;
+use crate::util::AccountLoad;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct InspectAccount<'info> {
+    /// CHECK: Any account owned by the program
+    #[account(owner = crate::id())]
+    pub account: UncheckedAccount<'info>,
+}
+
+pub fn inspect_account(ctx: Context<InspectAccount>) -> Result<AccountInspection> {
+    let account_info = ctx.accounts.account.to_account_info();
+    let account_type = AccountType::from_data(&account_info.try_borrow_data()?)
+        .ok_or(anchor_lang::error::ErrorCode::AccountDiscriminatorNotFound)?;
+    let data_len = account_info.data_len();
+    // the accounts storing their layout version override it
+    let mut version = 0;
+    let fields = match account_type {
+        AccountType::AmmConfig => {
+            let amm_config = AmmConfig::try_deserialize(&mut &account_info.try_borrow_data()?[..])?;
+            vec![
+                InspectedField::new("index", amm_config.index),
+                InspectedField::new("owner", amm_config.owner),
+                InspectedField::new("tick_spacing", amm_config.tick_spacing),
+                InspectedField::new("trade_fee_rate", amm_config.trade_fee_rate),
+                InspectedField::new("protocol_fee_rate", amm_config.protocol_fee_rate),
+                InspectedField::new("fund_fee_rate", amm_config.fund_fee_rate),
+                InspectedField::new("fund_owner", amm_config.fund_owner),
+            ]
+        }
+        AccountType::AmmConfigExtension => {
+            let amm_config_extension =
+                AmmConfigExtension::try_deserialize(&mut &account_info.try_borrow_data()?[..])?;
+            version = amm_config_extension.version;
+            vec![
+                InspectedField::new("amm_config", amm_config_extension.amm_config),
+                InspectedField::new(
+                    "min_dynamic_fee_rate",
+                    amm_config_extension.min_dynamic_fee_rate,
+                ),
+                InspectedField::new(
+                    "max_dynamic_fee_rate",
+                    amm_config_extension.max_dynamic_fee_rate,
+                ),
+                InspectedField::new("treasury", amm_config_extension.treasury),
+            ]
+        }
+        AccountType::PoolState => {
+            let pool_state = AccountLoad::<PoolState>::try_from(&account_info)?;
+            let pool_state = pool_state.load()?;
+            version = pool_state.version;
+            vec![
+                InspectedField::new("amm_config", pool_state.amm_config),
+                InspectedField::new("token_mint_0", pool_state.token_mint_0),
+                InspectedField::new("token_mint_1", pool_state.token_mint_1),
+                InspectedField::new("tick_spacing", pool_state.tick_spacing),
+                InspectedField::new("liquidity", pool_state.liquidity),
+                InspectedField::new("sqrt_price_x64", pool_state.sqrt_price_x64),
+                InspectedField::new("tick_current", pool_state.tick_current),
+                InspectedField::new("status", pool_state.status),
+                InspectedField::new(
+                    "liquidity_seconds_global",
+                    pool_state.liquidity_seconds_global,
+                ),
+            ]
+        }
+        AccountType::PersonalPosition => {
+            let position =
+                PersonalPositionState::try_deserialize(&mut &account_info.try_borrow_data()?[..])?;
+            version = position.version;
+            vec![
+                InspectedField::new("nft_mint", position.nft_mint),
+                InspectedField::new("pool_id", position.pool_id),
+                InspectedField::new("tick_lower_index", position.tick_lower_index),
+                InspectedField::new("tick_upper_index", position.tick_upper_index),
+                InspectedField::new("liquidity", position.liquidity),
+                InspectedField::new("liquidity_seconds", position.liquidity_seconds),
+                InspectedField::new("locked", position.locked),
+                InspectedField::new("locked_until", position.locked_until),
+            ]
+        }
+        AccountType::ProtocolPosition => {
+            let position =
+                ProtocolPositionState::try_deserialize(&mut &account_info.try_borrow_data()?[..])?;
+            vec![
+                InspectedField::new("pool_id", position.pool_id),
+                InspectedField::new("tick_lower_index", position.tick_lower_index),
+                InspectedField::new("tick_upper_index", position.tick_upper_index),
+                InspectedField::new("liquidity", position.liquidity),
+            ]
+        }
+        AccountType::TickArray => {
+            let tick_array = AccountLoad::<TickArrayState>::try_from(&account_info)?;
+            let tick_array = tick_array.load()?;
+            version = tick_array.version;
+            vec![
+                InspectedField::new("pool_id", tick_array.pool_id),
+                InspectedField::new("start_tick_index", tick_array.start_tick_index),
+                InspectedField::new("initialized_tick_count", tick_array.initialized_tick_count),
+                InspectedField::new("rent_payer", tick_array.rent_payer),
+            ]
+        }
+        AccountType::TickArrayBitmapExtension => {
+            let extension = AccountLoad::<TickArrayBitmapExtension>::try_from(&account_info)?;
+            let pool_id = extension.load()?.pool_id;
+            vec![InspectedField::new("pool_id", pool_id)]
+        }
+        AccountType::Observation => {
+            let observation_state = AccountLoad::<ObservationState>::try_from(&account_info)?;
+            let observation_state = observation_state.load()?;
+            vec![
+                InspectedField::new("pool_id", observation_state.pool_id),
+                InspectedField::new("initialized", observation_state.initialized),
+                InspectedField::new("observation_index", observation_state.observation_index),
+            ]
+        }
+        AccountType::Operation => {
+            // the account before realloc is smaller than the current layout
+            if version == 0 {
+                vec![]
+            } else {
+                let operation_state = AccountLoad::<OperationState>::try_from(&account_info)?;
+                let operation_state = operation_state.load()?;
+                let count =
+                    |keys: &[Pubkey]| keys.iter().filter(|k| **k != Pubkey::default()).count();
+                vec![
+                    InspectedField::new(
+                        "operation_owners",
+                        count(&operation_state.operation_owners),
+                    ),
+                    InspectedField::new(
+                        "whitelist_mints",
+                        count(&operation_state.whitelist_mints)
+                            + extension_whitelist_mints(
+                                &account_info.try_borrow_data()?[OperationState::LEN..],
+                            )
+                            .count(),
+                    ),
+                ]
+            }
+        }
+        AccountType::PartnerConfig => {
+            let partner_config =
+                PartnerConfig::try_deserialize(&mut &account_info.try_borrow_data()?[..])?;
+            vec![
+                InspectedField::new("amm_config", partner_config.amm_config),
+                InspectedField::new("partner_owner", partner_config.partner_owner),
+            ]
+        }
+        AccountType::Referral => {
+            let referral =
+                ReferralState::try_deserialize(&mut &account_info.try_borrow_data()?[..])?;
+            vec![
+                InspectedField::new("pool_id", referral.pool_id),
+                InspectedField::new("referrer", referral.referrer),
+                InspectedField::new("fees_owed_0", referral.fees_owed_0),
+                InspectedField::new("fees_owed_1", referral.fees_owed_1),
+            ]
+        }
+        AccountType::TokenBadge => {
+            let token_badge =
+                TokenBadge::try_deserialize(&mut &account_info.try_borrow_data()?[..])?;
+            vec![InspectedField::new("token_mint", token_badge.token_mint)]
+        }
+        AccountType::WhitelistCreator => {
+            let whitelist_creator =
+                WhitelistCreator::try_deserialize(&mut &account_info.try_borrow_data()?[..])?;
+            vec![InspectedField::new("creator", whitelist_creator.creator)]
+        }
+        AccountType::AdminHeartbeat => {
+            let heartbeat =
+                AdminHeartbeat::try_deserialize(&mut &account_info.try_borrow_data()?[..])?;
+            vec![
+                InspectedField::new(
+                    "last_heartbeat_timestamp",
+                    heartbeat.last_heartbeat_timestamp,
+                ),
+                InspectedField::new("expire_duration", heartbeat.expire_duration()),
+            ]
+        }
+        AccountType::AmmConfigPendingOwner => {
+            let pending_owner =
+                AmmConfigPendingOwner::try_deserialize(&mut &account_info.try_borrow_data()?[..])?;
+            vec![
+                InspectedField::new("amm_config", pending_owner.amm_config),
+                InspectedField::new("pending_owner", pending_owner.pending_owner),
+                InspectedField::new("pending_fund_owner", pending_owner.pending_fund_owner),
+            ]
+        }
+        AccountType::AdminTimelock => {
+            let admin_timelock =
+                AdminTimelock::try_deserialize(&mut &account_info.try_borrow_data()?[..])?;
+            vec![InspectedField::new("delay", admin_timelock.delay)]
+        }
+        AccountType::AdminAction => {
+            let admin_action =
+                AdminActionState::try_deserialize(&mut &account_info.try_borrow_data()?[..])?;
+            vec![
+                InspectedField::new("action", admin_action.action),
+                InspectedField::new("param", admin_action.param),
+                InspectedField::new("target", admin_action.target),
+                InspectedField::new("value", admin_action.value),
+                InspectedField::new("executable_after", admin_action.executable_after),
+            ]
+        }
+        AccountType::TwapSnapshot => {
+            let twap_snapshot =
+                TwapSnapshot::try_deserialize(&mut &account_info.try_borrow_data()?[..])?;
+            vec![
+                InspectedField::new("pool_id", twap_snapshot.pool_id),
+                InspectedField::new("window_seconds", twap_snapshot.window_seconds),
+                InspectedField::new("average_tick", twap_snapshot.average_tick),
+                InspectedField::new("sqrt_price_x64", twap_snapshot.sqrt_price_x64),
+                InspectedField::new("update_timestamp", twap_snapshot.update_timestamp),
+            ]
+        }
+        AccountType::PriceFeed => {
+            let price_feed = AccountLoad::<PriceFeed>::try_from(&account_info)?;
+            let price_feed = price_feed.load()?;
+            vec![
+                InspectedField::new("pool_id", price_feed.pool_id),
+                InspectedField::new("sqrt_price_x64", price_feed.sqrt_price_x64),
+                InspectedField::new("tick", price_feed.tick),
+                InspectedField::new("confidence", price_feed.confidence),
+                InspectedField::new("publish_slot", price_feed.publish_slot),
+            ]
+        }
+        AccountType::PositionLock => {
+            let position_lock =
+                PositionLockState::try_deserialize(&mut &account_info.try_borrow_data()?[..])?;
+            vec![
+                InspectedField::new("nft_mint", position_lock.nft_mint),
+                InspectedField::new("pool_id", position_lock.pool_id),
+                InspectedField::new("fee_recipient", position_lock.fee_recipient),
+                InspectedField::new("locked_liquidity", position_lock.locked_liquidity),
+                InspectedField::new("nft_burned", position_lock.nft_burned),
+            ]
+        }
+        AccountType::PositionOperator => {
+            let position_operator =
+                PositionOperatorState::try_deserialize(&mut &account_info.try_borrow_data()?[..])?;
+            vec![
+                InspectedField::new("nft_mint", position_operator.nft_mint),
+                InspectedField::new("nft_owner", position_operator.nft_owner),
+                InspectedField::new("operator", position_operator.operator),
+                InspectedField::new("permission", position_operator.permission),
+                InspectedField::new("tick_lower_bound", position_operator.tick_lower_bound),
+                InspectedField::new("tick_upper_bound", position_operator.tick_upper_bound),
+            ]
+        }
+        AccountType::RewardInfoExtension => {
+            let extension = AccountLoad::<RewardInfoExtension>::try_from(&account_info)?;
+            let extension = extension.load()?;
+            let mut fields = vec![InspectedField::new("pool_id", extension.pool_id)];
+            for (i, reward_info) in extension.reward_infos.iter().enumerate() {
+                if reward_info.initialized() {
+                    fields.push(InspectedField::new(
+                        &format!("reward_mint_{}", i),
+                        reward_info.token_mint,
+                    ));
+                }
+            }
+            fields
+        }
+        AccountType::PositionRewardExtension => {
+            let extension = AccountLoad::<PositionRewardExtension>::try_from(&account_info)?;
+            let extension = extension.load()?;
+            let reward_amounts_owed = extension.reward_amounts_owed;
+            vec![
+                InspectedField::new("nft_mint", extension.nft_mint),
+                InspectedField::new("reward_amounts_owed", format!("{:?}", reward_amounts_owed)),
+            ]
+        }
+        AccountType::PoolCheckpoint => {
+            let pool_checkpoint = AccountLoad::<PoolCheckpointState>::try_from(&account_info)?;
+            let pool_checkpoint = pool_checkpoint.load()?;
+            let checkpoint =
+                pool_checkpoint.checkpoints[usize::from(pool_checkpoint.checkpoint_index)];
+            vec![
+                InspectedField::new("pool_id", pool_checkpoint.pool_id),
+                InspectedField::new("checkpoint_index", pool_checkpoint.checkpoint_index),
+                InspectedField::new("latest_block_timestamp", checkpoint.block_timestamp),
+            ]
+        }
+        AccountType::PoolMetadata => {
+            let pool_metadata =
+                PoolMetadata::try_deserialize(&mut &account_info.try_borrow_data()?[..])?;
+            vec![
+                InspectedField::new("pool_id", pool_metadata.pool_id),
+                InspectedField::new("creator", pool_metadata.creator),
+                InspectedField::new("label", pool_metadata.label),
+                InspectedField::new("uri", pool_metadata.uri),
+            ]
+        }
+        AccountType::PoolStats => {
+            let pool_stats = AccountLoad::<PoolStatsState>::try_from(&account_info)?;
+            let pool_stats = pool_stats.load()?;
+            let stats = pool_stats.epochs[usize::from(pool_stats.epoch_index)];
+            vec![
+                InspectedField::new("pool_id", pool_stats.pool_id),
+                InspectedField::new("latest_epoch", stats.epoch),
+                InspectedField::new("latest_volume_token_0", stats.volume_token_0),
+                InspectedField::new("latest_volume_token_1", stats.volume_token_1),
+            ]
+        }
+        AccountType::RewardDistribution => {
+            let distribution =
+                RewardDistribution::try_deserialize(&mut &account_info.try_borrow_data()?[..])?;
+            vec![
+                InspectedField::new("creator", distribution.creator),
+                InspectedField::new("mint", distribution.mint),
+                InspectedField::new("total_amount", distribution.total_amount),
+                InspectedField::new("claimed_amount", distribution.claimed_amount),
+                InspectedField::new("claimed_count", distribution.claimed_count),
+            ]
+        }
+        AccountType::ClaimStatus => {
+            let claim_status =
+                ClaimStatus::try_deserialize(&mut &account_info.try_borrow_data()?[..])?;
+            vec![
+                InspectedField::new("distribution", claim_status.distribution),
+                InspectedField::new("claimant", claim_status.claimant),
+                InspectedField::new("amount", claim_status.amount),
+            ]
+        }
+        AccountType::TraderStats => {
+            let trader_stats = AccountLoad::<TraderStatsState>::try_from(&account_info)?;
+            let trader_stats = trader_stats.load()?;
+            vec![
+                InspectedField::new("pool_id", trader_stats.pool_id),
+                InspectedField::new("trader", trader_stats.trader),
+                InspectedField::new("swap_count", trader_stats.swap_count),
+                InspectedField::new("last_swap_timestamp", trader_stats.last_swap_timestamp),
+            ]
+        }
+    };
+    Ok(AccountInspection {
+        account_type,
+        version,
+        data_len: data_len as u64,
+        fields,
+    })
+}
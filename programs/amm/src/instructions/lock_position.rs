@@ -0,0 +1,108 @@
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::util::{burn, close_spl_account};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct LockPosition<'info> {
+    /// The position nft owner, pays for the position lock account
+    #[account(mut)]
+    pub nft_owner: Signer<'info>,
+
+    /// Mint address bound to the personal position.
+    #[account(
+        mut,
+        address = personal_position.nft_mint,
+        mint::token_program = token_program,
+    )]
+    pub position_nft_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The token account for the tokenized position
+    #[account(
+        mut,
+        token::mint = position_nft_mint,
+        token::authority = nft_owner,
+        constraint = position_nft_account.amount == 1,
+        token::token_program = token_program,
+    )]
+    pub position_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The position to lock
+    #[account(
+        mut,
+        seeds = [POSITION_SEED.as_bytes(), position_nft_mint.key().as_ref()],
+        bump,
+    )]
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
+
+    /// Stores the fee recipient of the locked position
+    #[account(
+        init,
+        seeds = [POSITION_LOCK_SEED.as_bytes(), position_nft_mint.key().as_ref()],
+        bump,
+        payer = nft_owner,
+        space = PositionLockState::LEN
+    )]
+    pub position_lock: Box<Account<'info, PositionLockState>>,
+
+    /// System program to create the position lock account
+    pub system_program: Program<'info, System>,
+
+    /// Token/Token2022 program to burn the position NFT
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn lock_position<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, LockPosition<'info>>,
+    fee_recipient: Pubkey,
+    burn_nft: bool,
+) -> Result<()> {
+    let personal_position = &mut ctx.accounts.personal_position;
+    require!(!personal_position.locked, ErrorCode::PositionLocked);
+    require_gt!(personal_position.liquidity, 0, ErrorCode::InvaildLiquidity);
+    personal_position.locked = true;
+
+    let position_lock = &mut ctx.accounts.position_lock;
+    position_lock.bump = ctx.bumps.position_lock;
+    position_lock.nft_mint = personal_position.nft_mint;
+    position_lock.pool_id = personal_position.pool_id;
+    position_lock.fee_recipient = fee_recipient;
+    position_lock.locked_liquidity = personal_position.liquidity;
+    position_lock.lock_timestamp = Clock::get()?.unix_timestamp as u64;
+    position_lock.nft_burned = burn_nft;
+
+    if burn_nft {
+        let token_program = ctx.accounts.token_program.to_account_info();
+        let personal_nft_account = ctx.accounts.position_nft_account.to_account_info();
+        burn(
+            &ctx.accounts.nft_owner,
+            &ctx.accounts.position_nft_mint.to_account_info(),
+            &personal_nft_account,
+            &token_program,
+            &[],
+            1,
+        )?;
+        close_spl_account(
+            &ctx.accounts.nft_owner,
+            &ctx.accounts.nft_owner,
+            &personal_nft_account,
+            &token_program,
+            &[],
+        )?;
+    }
+
+    emit_event!(
+        ctx,
+        LockPositionEvent {
+            pool_state: personal_position.pool_id,
+            position_nft_mint: personal_position.nft_mint,
+            fee_recipient,
+            liquidity: personal_position.liquidity,
+            nft_burned: burn_nft,
+        }
+    )?;
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct LockPositionUntil<'info> {
+    /// The position nft owner
+    pub nft_owner: Signer<'info>,
+
+    /// The token account for the tokenized position
+    #[account(
+        constraint = nft_account.mint == personal_position.nft_mint,
+        constraint = nft_account.amount == 1,
+        token::authority = nft_owner
+    )]
+    pub nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The position to lock
+    #[account(mut)]
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
+}
+
+pub fn lock_position_until(ctx: Context<LockPositionUntil>, locked_until: u64) -> Result<()> {
+    let personal_position = &mut ctx.accounts.personal_position;
+    require_gt!(
+        locked_until,
+        personal_position.locked_until,
+        ErrorCode::InvalidLockTimestamp
+    );
+    require_gt!(
+        locked_until,
+        Clock::get()?.unix_timestamp as u64,
+        ErrorCode::InvalidLockTimestamp
+    );
+    personal_position.locked_until = locked_until;
+
+    emit_event!(
+        ctx,
+        LockPositionUntilEvent {
+            pool_state: personal_position.pool_id,
+            position_nft_mint: personal_position.nft_mint,
+            locked_until,
+        }
+    )?;
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::util::{realloc_account, AccountLoad};
+use anchor_lang::prelude::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// Pays the rent if the account grows, anyone can migrate an account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: A pool, personal position or tick array owned by the program
+    #[account(mut, owner = crate::id())]
+    pub account: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+    let account_info = ctx.accounts.account.to_account_info();
+    let account_type = AccountType::from_data(&account_info.try_borrow_data()?)
+        .ok_or(anchor_lang::error::ErrorCode::AccountDiscriminatorNotFound)?;
+    let len = match account_type {
+        AccountType::PoolState => PoolState::LEN,
+        AccountType::PersonalPosition => PersonalPositionState::LEN,
+        AccountType::TickArray => TickArrayState::LEN,
+        _ => return err!(ErrorCode::AccountNotMigratable),
+    };
+    // the layouts introduced later may be larger than the account
+    if account_info.data_len() < len {
+        realloc_account(
+            ctx.accounts.payer.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            account_info.clone(),
+            len,
+        )?;
+    }
+
+    let (version_before, version) = match account_type {
+        AccountType::PoolState => {
+            let pool_state_loader = AccountLoad::<PoolState>::try_from(&account_info)?;
+            let mut pool_state = pool_state_loader.load_mut()?;
+            (pool_state.migrate()?, pool_state.version)
+        }
+        AccountType::PersonalPosition => {
+            let mut data = account_info.try_borrow_mut_data()?;
+            let mut personal_position = PersonalPositionState::try_deserialize(&mut &data[..])?;
+            let version_before = personal_position.migrate()?;
+            personal_position.try_serialize(&mut &mut data[..])?;
+            (version_before, personal_position.version)
+        }
+        _ => {
+            let tick_array_loader = AccountLoad::<TickArrayState>::try_from(&account_info)?;
+            let mut tick_array = tick_array_loader.load_mut()?;
+            (tick_array.migrate()?, tick_array.version)
+        }
+    };
+
+    emit_event!(
+        ctx,
+        MigrateAccountEvent {
+            account: account_info.key(),
+            account_type,
+            version_before,
+            version,
+            data_len: account_info.data_len() as u64,
+        }
+    )?;
+    Ok(())
+}
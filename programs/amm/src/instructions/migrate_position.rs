@@ -0,0 +1,363 @@
+use super::decrease_liquidity::decrease_liquidity;
+use super::increase_liquidity::increase_liquidity;
+use super::rebalance_position::prepare_position_range;
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::libraries::{liquidity_math, tick_math};
+use crate::states::*;
+use crate::util::{check_remaining_accounts_min, get_transfer_fee};
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+#[instruction(param: MigratePositionParam)]
+pub struct MigratePosition<'info> {
+    /// The position owner, pays to create the tick arrays in the target pool
+    #[account(mut)]
+    pub nft_owner: Signer<'info>,
+
+    /// The token account for the tokenized position
+    #[account(
+        constraint = nft_account.mint == personal_position.nft_mint,
+        constraint = nft_account.amount == 1,
+        token::authority = nft_owner
+    )]
+    pub nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The position to migrate, which is moved to the target pool
+    #[account(mut, constraint = personal_position.pool_id == pool_state.key())]
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
+
+    /// The pool the position is withdrawn from
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// Stores the lower tick of the position in the pool it is withdrawn from
+    #[account(mut, constraint = tick_array_lower.load()?.pool_id == pool_state.key())]
+    pub tick_array_lower: AccountLoader<'info, TickArrayState>,
+
+    /// Stores the upper tick of the position in the pool it is withdrawn from
+    #[account(mut, constraint = tick_array_upper.load()?.pool_id == pool_state.key())]
+    pub tick_array_upper: AccountLoader<'info, TickArrayState>,
+
+    /// The address that holds tokens for token_0 of the pool the position is withdrawn from
+    #[account(
+        mut,
+        constraint = token_vault_0.key() == pool_state.load()?.token_vault_0
+    )]
+    pub token_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The address that holds tokens for token_1 of the pool the position is withdrawn from
+    #[account(
+        mut,
+        constraint = token_vault_1.key() == pool_state.load()?.token_vault_1
+    )]
+    pub token_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The pool the position is deposited into, of the same mint pair under another amm config
+    #[account(mut)]
+    pub target_pool_state: AccountLoader<'info, PoolState>,
+
+    /// CHECK: Account to store data for the lower tick of the position in the target pool
+    #[account(
+        mut,
+        seeds = [
+            TICK_ARRAY_SEED.as_bytes(),
+            target_pool_state.key().as_ref(),
+            &param.tick_array_lower_start_index.to_be_bytes(),
+        ],
+        bump,
+    )]
+    pub target_tick_array_lower: UncheckedAccount<'info>,
+
+    /// CHECK: Account to store data for the upper tick of the position in the target pool
+    #[account(
+        mut,
+        seeds = [
+            TICK_ARRAY_SEED.as_bytes(),
+            target_pool_state.key().as_ref(),
+            &param.tick_array_upper_start_index.to_be_bytes(),
+        ],
+        bump,
+    )]
+    pub target_tick_array_upper: UncheckedAccount<'info>,
+
+    /// The address that holds tokens for token_0 of the target pool
+    #[account(
+        mut,
+        constraint = target_token_vault_0.key() == target_pool_state.load()?.token_vault_0
+    )]
+    pub target_token_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The address that holds tokens for token_1 of the target pool
+    #[account(
+        mut,
+        constraint = target_token_vault_1.key() == target_pool_state.load()?.token_vault_1
+    )]
+    pub target_token_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token_0 account receiving the withdrawn tokens and paying the migrated liquidity
+    #[account(
+        mut,
+        token::mint = token_vault_0.mint
+    )]
+    pub token_account_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token_1 account receiving the withdrawn tokens and paying the migrated liquidity
+    #[account(
+        mut,
+        token::mint = token_vault_1.mint
+    )]
+    pub token_account_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Program to create the tick arrays in the target pool
+    pub system_program: Program<'info, System>,
+
+    /// SPL program to transfer tokens
+    pub token_program: Program<'info, Token>,
+
+    /// Token program 2022
+    pub token_program_2022: Program<'info, Token2022>,
+
+    /// memo program
+    /// CHECK:
+    #[account(
+        address = spl_memo::id()
+    )]
+    pub memo_program: UncheckedAccount<'info>,
+
+    /// The mint of token vault 0
+    #[account(
+        address = token_vault_0.mint
+    )]
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of token vault 1
+    #[account(
+        address = token_vault_1.mint
+    )]
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
+    // remaining accounts
+    // `decrease_remaining_accounts_len` accounts like the remaining accounts of decrease_liquidity_v2 for the pool withdrawn from,
+    // the reward accounts must be given to collect all rewards of the position
+    // the rest are used like the remaining accounts of increase_liquidity_v2 for the target pool
+}
+
+#[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Debug, PartialEq)]
+pub struct MigratePositionParam {
+    /// The low boundary of the position in the target pool
+    pub tick_lower_index: i32,
+    /// The upper boundary of the position in the target pool
+    pub tick_upper_index: i32,
+    /// The start index of tick array which include the tick low in the target pool
+    pub tick_array_lower_start_index: i32,
+    /// The start index of tick array which include the tick upper in the target pool
+    pub tick_array_upper_start_index: i32,
+    /// The minimum liquidity of the position in the target pool, which serves as a slippage check
+    pub liquidity_min: u128,
+    /// The number of remaining accounts used to decrease liquidity
+    pub decrease_remaining_accounts_len: u8,
+}
+
+pub fn migrate_position<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, MigratePosition<'info>>,
+    param: MigratePositionParam,
+) -> Result<()> {
+    let pool_id = ctx.accounts.pool_state.key();
+    let target_pool_id = ctx.accounts.target_pool_state.key();
+    {
+        let pool_state = ctx.accounts.pool_state.load()?;
+        let target_pool_state = ctx.accounts.target_pool_state.load()?;
+        require!(
+            pool_id != target_pool_id
+                && pool_state.token_mint_0 == target_pool_state.token_mint_0
+                && pool_state.token_mint_1 == target_pool_state.token_mint_1,
+            ErrorCode::InvalidPositionMigration
+        );
+        // the reward extension of the position can not be carried over between pools
+        require!(
+            !pool_state.requires_position_reward_extension()
+                && !target_pool_state.requires_position_reward_extension(),
+            ErrorCode::InvalidPositionMigration
+        );
+    }
+    require!(
+        !ctx.accounts.personal_position.locked,
+        ErrorCode::PositionLocked
+    );
+    require!(
+        !ctx.accounts
+            .personal_position
+            .is_time_locked(Clock::get()?.unix_timestamp as u64),
+        ErrorCode::PositionTimeLocked
+    );
+    let decrease_remaining_accounts_len = usize::from(param.decrease_remaining_accounts_len);
+    check_remaining_accounts_min(ctx.remaining_accounts, decrease_remaining_accounts_len)?;
+    let (decrease_remaining_accounts, increase_remaining_accounts) = ctx
+        .remaining_accounts
+        .split_at(decrease_remaining_accounts_len);
+
+    let balance_0_before = ctx.accounts.token_account_0.amount;
+    let balance_1_before = ctx.accounts.token_account_1.amount;
+
+    // withdraw all liquidity with the fees and rewards from the pool
+    let tick_lower_index_old = ctx.accounts.personal_position.tick_lower_index;
+    let tick_upper_index_old = ctx.accounts.personal_position.tick_upper_index;
+    let liquidity = ctx.accounts.personal_position.liquidity;
+    decrease_liquidity(
+        &ctx.accounts.nft_owner.to_account_info(),
+        &ctx.accounts.pool_state,
+        &mut ctx.accounts.personal_position,
+        &ctx.accounts.token_vault_0.to_account_info(),
+        &ctx.accounts.token_vault_1.to_account_info(),
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        &ctx.accounts.token_account_0.to_account_info(),
+        &ctx.accounts.token_account_1.to_account_info(),
+        &ctx.accounts.token_program,
+        Some(ctx.accounts.token_program_2022.clone()),
+        Some(ctx.accounts.memo_program.clone()),
+        Some(ctx.accounts.vault_0_mint.clone()),
+        Some(ctx.accounts.vault_1_mint.clone()),
+        decrease_remaining_accounts,
+        liquidity,
+        0,
+        0,
+        crate::event_authority!(ctx.accounts, ctx.bumps),
+    )?;
+    // the rewards of the pool are indexed differently in the target pool
+    require!(
+        ctx.accounts
+            .personal_position
+            .reward_infos
+            .iter()
+            .all(|reward_info| reward_info.reward_amount_owed == 0),
+        ErrorCode::InvalidPositionMigration
+    );
+
+    ctx.accounts.token_account_0.reload()?;
+    ctx.accounts.token_account_1.reload()?;
+    let amount_0 = ctx
+        .accounts
+        .token_account_0
+        .amount
+        .saturating_sub(balance_0_before);
+    let amount_1 = ctx
+        .accounts
+        .token_account_1
+        .amount
+        .saturating_sub(balance_1_before);
+
+    // move the position to the target pool, whose tick arrays are created if needed
+    let (tick_array_lower_loader, tick_array_upper_loader) = prepare_position_range(
+        &ctx.accounts.nft_owner.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.target_pool_state,
+        &ctx.accounts.target_tick_array_lower.to_account_info(),
+        &ctx.accounts.target_tick_array_upper.to_account_info(),
+        param.tick_lower_index,
+        param.tick_upper_index,
+        param.tick_array_lower_start_index,
+        param.tick_array_upper_start_index,
+    )?;
+    let personal_position = ctx.accounts.personal_position.as_mut();
+    personal_position.pool_id = target_pool_id;
+    personal_position.tick_lower_index = param.tick_lower_index;
+    personal_position.tick_upper_index = param.tick_upper_index;
+
+    // deposit the liquidity the withdrawn tokens can provide, net of the transfer fees
+    let new_liquidity = liquidity_math::get_liquidity_from_amounts(
+        ctx.accounts.target_pool_state.load()?.sqrt_price_x64,
+        tick_math::get_sqrt_price_at_tick(param.tick_lower_index)?,
+        tick_math::get_sqrt_price_at_tick(param.tick_upper_index)?,
+        amount_0
+            .checked_sub(get_transfer_fee(
+                ctx.accounts.vault_0_mint.clone(),
+                amount_0,
+            )?)
+            .unwrap(),
+        amount_1
+            .checked_sub(get_transfer_fee(
+                ctx.accounts.vault_1_mint.clone(),
+                amount_1,
+            )?)
+            .unwrap(),
+    );
+    require_gte!(
+        new_liquidity,
+        param.liquidity_min,
+        ErrorCode::PriceSlippageCheck
+    );
+    increase_liquidity(
+        &ctx.accounts.nft_owner,
+        &ctx.accounts.target_pool_state,
+        &mut ctx.accounts.personal_position,
+        &tick_array_lower_loader,
+        &tick_array_upper_loader,
+        &ctx.accounts.token_account_0.to_account_info(),
+        &ctx.accounts.token_account_1.to_account_info(),
+        &ctx.accounts.target_token_vault_0.to_account_info(),
+        &ctx.accounts.target_token_vault_1.to_account_info(),
+        &ctx.accounts.token_program,
+        Some(&ctx.accounts.token_program_2022),
+        Some(ctx.accounts.vault_0_mint.clone()),
+        Some(ctx.accounts.vault_1_mint.clone()),
+        increase_remaining_accounts,
+        new_liquidity,
+        amount_0,
+        amount_1,
+        None,
+        crate::event_authority!(ctx.accounts, ctx.bumps),
+    )?;
+
+    emit_event!(
+        ctx,
+        MigratePositionEvent {
+            position_nft_mint: ctx.accounts.personal_position.nft_mint,
+            pool_state_old: pool_id,
+            pool_state: target_pool_id,
+            tick_lower_index_old,
+            tick_upper_index_old,
+            tick_lower_index: param.tick_lower_index,
+            tick_upper_index: param.tick_upper_index,
+            liquidity_old: liquidity,
+            liquidity: ctx.accounts.personal_position.liquidity,
+        }
+    )?;
+    Ok(())
+}
+
+/// Emitted when a position is migrated to another pool of the same mint pair
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct MigratePositionEvent {
+    /// The position
+    #[index]
+    pub position_nft_mint: Pubkey,
+
+    /// The pool the position is withdrawn from
+    pub pool_state_old: Pubkey,
+
+    /// The pool the position is deposited into
+    pub pool_state: Pubkey,
+
+    /// The low boundary of the position in the pool withdrawn from
+    pub tick_lower_index_old: i32,
+
+    /// The upper boundary of the position in the pool withdrawn from
+    pub tick_upper_index_old: i32,
+
+    /// The low boundary of the position in the target pool
+    pub tick_lower_index: i32,
+
+    /// The upper boundary of the position in the target pool
+    pub tick_upper_index: i32,
+
+    /// The liquidity withdrawn from the pool
+    pub liquidity_old: u128,
+
+    /// The liquidity of the position in the target pool
+    pub liquidity: u128,
+}
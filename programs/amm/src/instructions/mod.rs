@@ -1,6 +1,27 @@
 pub mod create_pool;
 pub use create_pool::*;
 
+pub mod collect_creator_fee;
+pub use collect_creator_fee::*;
+
+pub mod set_pool_metadata;
+pub use set_pool_metadata::*;
+
+pub mod set_anti_bot_config;
+pub use set_anti_bot_config::*;
+
+pub mod update_open_time;
+pub use update_open_time::*;
+
+pub mod donate;
+pub use donate::*;
+
+pub mod create_distribution;
+pub use create_distribution::*;
+
+pub mod claim_with_proof;
+pub use claim_with_proof::*;
+
 pub mod open_position;
 pub use open_position::*;
 
@@ -10,9 +31,36 @@ pub use open_position_v2::*;
 pub mod open_position_with_token22_nft;
 pub use open_position_with_token22_nft::*;
 
+pub mod open_position_with_core_asset;
+pub use open_position_with_core_asset::*;
+
+pub mod open_positions_batch;
+pub use open_positions_batch::*;
+
+pub mod rebalance_position;
+pub use rebalance_position::*;
+
+pub mod migrate_position;
+pub use migrate_position::*;
+
 pub mod close_position;
 pub use close_position::*;
 
+pub mod exit_position;
+pub use exit_position::*;
+
+pub mod lock_position;
+pub use lock_position::*;
+
+pub mod lock_position_until;
+pub use lock_position_until::*;
+
+pub mod approve_operator;
+pub use approve_operator::*;
+
+pub mod collect_locked_position_fees;
+pub use collect_locked_position_fees::*;
+
 pub mod increase_liquidity;
 pub use increase_liquidity::*;
 
@@ -31,20 +79,95 @@ pub use swap::*;
 pub mod swap_v2;
 pub use swap_v2::*;
 
+pub mod swap_v2_native;
+pub use swap_v2_native::*;
+
+pub mod create_native_token_account;
+pub use create_native_token_account::*;
+
 pub mod swap_router_base_in;
 pub use swap_router_base_in::*;
 
+pub mod swap_split;
+pub use swap_split::*;
+
 pub mod update_reward_info;
 pub use update_reward_info::*;
 
+pub mod initialize_position_reward_extension;
+pub use initialize_position_reward_extension::*;
+
+pub mod update_liquidity_seconds;
+pub use update_liquidity_seconds::*;
+
+pub mod increase_observation_cardinality;
+pub use increase_observation_cardinality::*;
+
+pub mod get_twap;
+pub use get_twap::*;
+
+pub mod checkpoint_pool;
+pub use checkpoint_pool::*;
+
+pub mod create_pool_stats;
+pub use create_pool_stats::*;
+
+pub mod create_trader_stats;
+pub use create_trader_stats::*;
+
+pub mod create_price_feed;
+pub use create_price_feed::*;
+
+pub mod update_price_feed;
+pub use update_price_feed::*;
+
+pub mod close_observation;
+pub use close_observation::*;
+
+pub mod reinitialize_observation;
+pub use reinitialize_observation::*;
+
+pub mod close_tick_array;
+pub use close_tick_array::*;
+
+pub mod close_protocol_position;
+pub use close_protocol_position::*;
+
 pub mod initialize_reward;
 pub use initialize_reward::*;
 
 pub mod set_reward_params;
 pub use set_reward_params::*;
 
+pub mod set_reward_vesting;
+pub use set_reward_vesting::*;
+
+pub mod fund_reward;
+pub use fund_reward::*;
+
 pub mod collect_remaining_rewards;
 pub use collect_remaining_rewards::*;
 
+pub mod finalize_reward_cycle;
+pub use finalize_reward_cycle::*;
+
+pub mod create_referral_account;
+pub use create_referral_account::*;
+
+pub mod collect_referral_fee;
+pub use collect_referral_fee::*;
+
+pub mod assert_pool_solvency;
+pub use assert_pool_solvency::*;
+
+pub mod create_token_badge_with_fee;
+pub use create_token_badge_with_fee::*;
+
 pub mod admin;
 pub use admin::*;
+
+pub mod inspect_account;
+pub use inspect_account::*;
+
+pub mod migrate_account;
+pub use migrate_account::*;
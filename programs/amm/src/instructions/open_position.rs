@@ -18,17 +18,20 @@ use anchor_spl::token_2022::{
     spl_token_2022::{self, instruction::AuthorityType},
 };
 use anchor_spl::token_interface;
-use mpl_token_metadata::{instruction::create_metadata_accounts_v3, state::Creator};
+use mpl_token_metadata::{
+    instruction::create_metadata_accounts_v3,
+    state::{Creator, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH},
+};
 use std::cell::RefMut;
 #[cfg(feature = "enable-log")]
 use std::convert::identity;
 use std::ops::Deref;
-use std::ops::DerefMut;
 
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 #[instruction(tick_lower_index: i32, tick_upper_index: i32,tick_array_lower_start_index:i32,tick_array_upper_start_index:i32)]
 pub struct OpenPosition<'info> {
-    /// Pays to mint the position
+    /// Pays to mint the position, and the tokens if no funder signs as the first remaining account
     #[account(mut)]
     pub payer: Signer<'info>,
 
@@ -63,20 +66,9 @@ pub struct OpenPosition<'info> {
     #[account(mut)]
     pub pool_state: AccountLoader<'info, PoolState>,
 
-    /// Store the information of market marking in range
-    #[account(
-        init_if_needed,
-        seeds = [
-            POSITION_SEED.as_bytes(),
-            pool_state.key().as_ref(),
-            &tick_lower_index.to_be_bytes(),
-            &tick_upper_index.to_be_bytes(),
-        ],
-        bump,
-        payer = payer,
-        space = ProtocolPositionState::LEN
-    )]
-    pub protocol_position: Box<Account<'info, ProtocolPositionState>>,
+    /// CHECK: Deprecated, the protocol position is folded into the ticks and this account is
+    /// ignored. It is kept so that the account layout of the instruction doesn't change
+    pub protocol_position: UncheckedAccount<'info>,
 
     /// CHECK: Account to store data for the position's lower tick
     #[account(
@@ -186,7 +178,6 @@ pub fn open_position_v1<'a, 'b, 'c: 'info, 'info>(
         &ctx.accounts.pool_state,
         &ctx.accounts.tick_array_lower,
         &ctx.accounts.tick_array_upper,
-        &mut ctx.accounts.protocol_position,
         &mut ctx.accounts.personal_position,
         &ctx.accounts.token_account_0.to_account_info(),
         &ctx.accounts.token_account_1.to_account_info(),
@@ -195,13 +186,11 @@ pub fn open_position_v1<'a, 'b, 'c: 'info, 'info>(
         &ctx.accounts.rent,
         &ctx.accounts.system_program,
         &ctx.accounts.token_program,
-        &ctx.accounts.associated_token_program,
         Some(&ctx.accounts.metadata_program),
         None,
         None,
         None,
         &ctx.remaining_accounts,
-        ctx.bumps.protocol_position,
         ctx.bumps.personal_position,
         liquidity,
         amount_0_max,
@@ -210,9 +199,10 @@ pub fn open_position_v1<'a, 'b, 'c: 'info, 'info>(
         tick_upper_index,
         tick_array_lower_start_index,
         tick_array_upper_start_index,
-        with_metadata,
+        with_metadata.then(PositionNftMetadata::default),
         base_flag,
         false,
+        crate::event_authority!(ctx.accounts, ctx.bumps),
     )
 }
 
@@ -225,7 +215,6 @@ pub fn open_position<'a, 'b, 'c: 'info, 'info>(
     pool_state_loader: &'b AccountLoader<'info, PoolState>,
     tick_array_lower_loader: &'b UncheckedAccount<'info>,
     tick_array_upper_loader: &'b UncheckedAccount<'info>,
-    protocol_position: &'b mut Box<Account<'info, ProtocolPositionState>>,
     personal_position: &'b mut Box<Account<'info, PersonalPositionState>>,
     token_account_0: &'b AccountInfo<'info>,
     token_account_1: &'b AccountInfo<'info>,
@@ -234,14 +223,12 @@ pub fn open_position<'a, 'b, 'c: 'info, 'info>(
     rent: &'b Sysvar<'info, Rent>,
     system_program: &'b Program<'info, System>,
     token_program: &'b Program<'info, Token>,
-    _associated_token_program: &'b Program<'info, AssociatedToken>,
     metadata_program: Option<&'b Program<'info, Metadata>>,
     token_program_2022: Option<&'b Program<'info, Token2022>>,
     vault_0_mint: Option<Box<InterfaceAccount<'info, token_interface::Mint>>>,
     vault_1_mint: Option<Box<InterfaceAccount<'info, token_interface::Mint>>>,
 
     remaining_accounts: &'c [AccountInfo<'info>],
-    protocol_position_bump: u8,
     personal_position_bump: u8,
     liquidity: u128,
     amount_0_max: u64,
@@ -250,10 +237,27 @@ pub fn open_position<'a, 'b, 'c: 'info, 'info>(
     tick_upper_index: i32,
     tick_array_lower_start_index: i32,
     tick_array_upper_start_index: i32,
-    with_metadata: bool,
+    metadata: Option<PositionNftMetadata>,
     base_flag: Option<bool>,
     use_metadata_extension: bool,
+    event_authority: EventAuthority,
 ) -> Result<()> {
+    let vault_mints: Vec<AccountInfo> = vault_0_mint
+        .iter()
+        .chain(vault_1_mint.iter())
+        .map(|mint| mint.to_account_info())
+        .collect();
+    let (remaining_accounts, transfer_hook_accounts) =
+        split_transfer_hook_accounts(remaining_accounts, &vault_mints)?;
+    // the owner of the token accounts can fund the position apart from the payer
+    // by signing as the first remaining account
+    let (funder, remaining_accounts) = match remaining_accounts.split_first() {
+        Some((account_info, rest)) if account_info.is_signer => {
+            (Signer::try_from(account_info)?, rest)
+        }
+        _ => (payer.clone(), remaining_accounts),
+    };
+    check_remaining_accounts_max(remaining_accounts, MAX_LIQUIDITY_REMAINING_ACCOUNTS)?;
     let mut liquidity = liquidity;
     {
         let pool_state = &mut pool_state_loader.load_mut()?;
@@ -298,50 +302,54 @@ pub fn open_position<'a, 'b, 'c: 'info, 'info>(
                 )?
             };
 
-        // check if protocol position is initilized
-        let protocol_position = protocol_position.deref_mut();
-        if protocol_position.pool_id == Pubkey::default() {
-            protocol_position.bump = protocol_position_bump;
-            protocol_position.pool_id = pool_state_loader.key();
-            protocol_position.tick_lower_index = tick_lower_index;
-            protocol_position.tick_upper_index = tick_upper_index;
-            tick_array_lower_loader
-                .load_mut()?
-                .get_tick_state_mut(tick_lower_index, pool_state.tick_spacing)?
-                .tick = tick_lower_index;
-            tick_array_upper_loader
-                .load_mut()?
-                .get_tick_state_mut(tick_upper_index, pool_state.tick_spacing)?
-                .tick = tick_upper_index;
-        }
-
         let use_tickarray_bitmap_extension = pool_state.is_overflow_default_tickarray_bitmap(vec![
             tick_array_lower_start_index,
             tick_array_upper_start_index,
         ]);
+        let tickarray_bitmap_extension = if use_tickarray_bitmap_extension {
+            let tickarray_bitmap_extension = remaining_accounts
+                .first()
+                .ok_or(ErrorCode::NotEnoughRemainingAccounts)?;
+            require_keys_eq!(
+                tickarray_bitmap_extension.key(),
+                TickArrayBitmapExtension::key(pool_state_loader.key())
+            );
+            TickArrayBitmapExtension::get_or_create_tickarray_bitmap_extension(
+                payer.to_account_info(),
+                tickarray_bitmap_extension,
+                system_program.to_account_info(),
+                pool_state_loader.key(),
+            )?;
+            Some(tickarray_bitmap_extension)
+        } else {
+            None
+        };
+        let (reward_info_extension_info, _) = find_reward_extension_accounts(
+            remaining_accounts,
+            pool_state_loader.key(),
+            pool_state,
+            None,
+        )?;
+        let mut reward_info_extension = reward_info_extension_info
+            .map(|account_info| AccountLoad::<RewardInfoExtension>::load_data_mut(account_info))
+            .transpose()?;
 
+        let mut growths_inside = PositionGrowthsInside::default();
         let (amount_0, amount_1, amount_0_transfer_fee, amount_1_transfer_fee) = add_liquidity(
-            payer,
+            &funder,
             token_account_0,
             token_account_1,
             token_vault_0,
             token_vault_1,
             &tick_array_lower_loader,
             &tick_array_upper_loader,
-            protocol_position,
+            &mut growths_inside,
             token_program_2022,
             token_program,
             vault_0_mint,
             vault_1_mint,
-            if use_tickarray_bitmap_extension {
-                require_keys_eq!(
-                    remaining_accounts[0].key(),
-                    TickArrayBitmapExtension::key(pool_state_loader.key())
-                );
-                Some(&remaining_accounts[0])
-            } else {
-                None
-            },
+            tickarray_bitmap_extension,
+            reward_info_extension.as_deref_mut(),
             pool_state,
             &mut liquidity,
             amount_0_max,
@@ -349,25 +357,45 @@ pub fn open_position<'a, 'b, 'c: 'info, 'info>(
             tick_lower_index,
             tick_upper_index,
             base_flag,
+            transfer_hook_accounts,
+            event_authority,
         )?;
 
         // let personal_position = &mut personal_position;
         personal_position.bump = [personal_position_bump];
+        personal_position.version = PersonalPositionState::VERSION;
         personal_position.nft_mint = position_nft_mint.key();
         personal_position.pool_id = pool_state_loader.key();
         personal_position.tick_lower_index = tick_lower_index;
         personal_position.tick_upper_index = tick_upper_index;
 
-        personal_position.fee_growth_inside_0_last_x64 =
-            protocol_position.fee_growth_inside_0_last_x64;
-        personal_position.fee_growth_inside_1_last_x64 =
-            protocol_position.fee_growth_inside_1_last_x64;
+        personal_position.fee_growth_inside_0_last_x64 = growths_inside.fee_growth_inside_0_x64;
+        personal_position.fee_growth_inside_1_last_x64 = growths_inside.fee_growth_inside_1_x64;
 
         // update rewards, must update before update liquidity
-        personal_position.update_rewards(protocol_position.reward_growth_inside, false)?;
+        personal_position.update_rewards(growths_inside.reward_growths_inside, false)?;
+        personal_position.update_liquidity_seconds(growths_inside.seconds_inside);
         personal_position.liquidity = liquidity;
 
-        emit!(CreatePersonalPositionEvent {
+        // the position starts to earn the rewards of the extension and to vest its rewards from now on
+        if pool_state.requires_position_reward_extension() {
+            let position_reward_extension_key =
+                PositionRewardExtension::key(position_nft_mint.key());
+            let position_reward_extension_info = remaining_accounts
+                .iter()
+                .find(|account_info| account_info.key() == position_reward_extension_key)
+                .ok_or(ErrorCode::MissingPositionRewardExtension)?;
+            PositionRewardExtension::create_position_reward_extension(
+                payer.to_account_info(),
+                position_reward_extension_info,
+                system_program.to_account_info(),
+                position_nft_mint.key(),
+                growths_inside.extension_reward_growths_inside,
+            )?;
+            personal_position.has_reward_extension = true;
+        }
+
+        event_authority.emit(CreatePersonalPositionEvent {
             pool_state: pool_state_loader.key(),
             minter: payer.key(),
             nft_owner: position_nft_owner.key(),
@@ -377,10 +405,14 @@ pub fn open_position<'a, 'b, 'c: 'info, 'info>(
             deposit_amount_0: amount_0,
             deposit_amount_1: amount_1,
             deposit_amount_0_transfer_fee: amount_0_transfer_fee,
-            deposit_amount_1_transfer_fee: amount_1_transfer_fee
-        });
+            deposit_amount_1_transfer_fee: amount_1_transfer_fee,
+        })?;
     }
 
+    // the MPL Core asset of the position is created by the caller
+    if is_core_asset(position_nft_mint) {
+        return Ok(());
+    }
     mint_nft_and_remove_mint_authority(
         payer,
         pool_state_loader,
@@ -393,7 +425,7 @@ pub fn open_position<'a, 'b, 'c: 'info, 'info>(
         token_program_2022,
         system_program,
         rent,
-        with_metadata,
+        metadata,
         use_metadata_extension,
     )
 }
@@ -407,12 +439,13 @@ pub fn add_liquidity<'b, 'c: 'info, 'info>(
     token_vault_1: &'b AccountInfo<'info>,
     tick_array_lower_loader: &'b AccountLoad<'info, TickArrayState>,
     tick_array_upper_loader: &'b AccountLoad<'info, TickArrayState>,
-    protocol_position: &mut ProtocolPositionState,
+    growths_inside: &mut PositionGrowthsInside,
     token_program_2022: Option<&Program<'info, Token2022>>,
     token_program: &'b Program<'info, Token>,
     vault_0_mint: Option<Box<InterfaceAccount<'info, token_interface::Mint>>>,
     vault_1_mint: Option<Box<InterfaceAccount<'info, token_interface::Mint>>>,
     tick_array_bitmap_extension: Option<&'c AccountInfo<'info>>,
+    reward_info_extension: Option<&mut RewardInfoExtension>,
     pool_state: &mut RefMut<PoolState>,
     liquidity: &mut u128,
     amount_0_max: u64,
@@ -420,6 +453,8 @@ pub fn add_liquidity<'b, 'c: 'info, 'info>(
     tick_lower_index: i32,
     tick_upper_index: i32,
     base_flag: Option<bool>,
+    transfer_hook_accounts: &'c [AccountInfo<'info>],
+    event_authority: EventAuthority,
 ) -> Result<(u64, u64, u64, u64)> {
     if *liquidity == 0 {
         if base_flag.is_none() {
@@ -466,73 +501,19 @@ pub fn add_liquidity<'b, 'c: 'info, 'info>(
     }
     assert!(*liquidity > 0);
     let liquidity_before = pool_state.liquidity;
-    require_keys_eq!(tick_array_lower_loader.load()?.pool_id, pool_state.key());
-    require_keys_eq!(tick_array_upper_loader.load()?.pool_id, pool_state.key());
-
-    // get tick_state
-    let mut tick_lower_state = *tick_array_lower_loader
-        .load_mut()?
-        .get_tick_state_mut(tick_lower_index, pool_state.tick_spacing)?;
-    let mut tick_upper_state = *tick_array_upper_loader
-        .load_mut()?
-        .get_tick_state_mut(tick_upper_index, pool_state.tick_spacing)?;
-    if tick_lower_state.tick == 0 {
-        tick_lower_state.tick = tick_lower_index;
-    }
-    if tick_upper_state.tick == 0 {
-        tick_upper_state.tick = tick_upper_index;
-    }
-    let clock = Clock::get()?;
-    let (amount_0, amount_1, flip_tick_lower, flip_tick_upper) = modify_position(
-        i128::try_from(*liquidity).unwrap(),
+    let (amount_0, amount_1) = add_liquidity_to_ticks(
+        tick_array_lower_loader,
+        tick_array_upper_loader,
+        growths_inside,
+        tick_array_bitmap_extension,
+        reward_info_extension,
         pool_state,
-        protocol_position,
-        &mut tick_lower_state,
-        &mut tick_upper_state,
-        clock.unix_timestamp as u64,
-    )?;
-
-    // update tick_state
-    tick_array_lower_loader.load_mut()?.update_tick_state(
+        *liquidity,
         tick_lower_index,
-        pool_state.tick_spacing,
-        tick_lower_state,
-    )?;
-    tick_array_upper_loader.load_mut()?.update_tick_state(
         tick_upper_index,
-        pool_state.tick_spacing,
-        tick_upper_state,
+        event_authority,
     )?;
 
-    if flip_tick_lower {
-        let mut tick_array_lower = tick_array_lower_loader.load_mut()?;
-        let before_init_tick_count = tick_array_lower.initialized_tick_count;
-        tick_array_lower.update_initialized_tick_count(true)?;
-
-        if before_init_tick_count == 0 {
-            pool_state.flip_tick_array_bit(
-                tick_array_bitmap_extension,
-                tick_array_lower.start_tick_index,
-            )?;
-        }
-    }
-    if flip_tick_upper {
-        let mut tick_array_upper = tick_array_upper_loader.load_mut()?;
-        let before_init_tick_count = tick_array_upper.initialized_tick_count;
-        tick_array_upper.update_initialized_tick_count(true)?;
-
-        if before_init_tick_count == 0 {
-            pool_state.flip_tick_array_bit(
-                tick_array_bitmap_extension,
-                tick_array_upper.start_tick_index,
-            )?;
-        }
-    }
-    require!(
-        amount_0 > 0 || amount_1 > 0,
-        ErrorCode::ForbidBothZeroForSupplyLiquidity
-    );
-
     let mut amount_0_transfer_fee = 0;
     let mut amount_1_transfer_fee = 0;
     if vault_0_mint.is_some() {
@@ -543,7 +524,7 @@ pub fn add_liquidity<'b, 'c: 'info, 'info>(
         amount_1_transfer_fee =
             get_transfer_inverse_fee(vault_1_mint.clone().unwrap(), amount_1).unwrap();
     }
-    emit!(LiquidityCalculateEvent {
+    event_authority.emit(LiquidityCalculateEvent {
         pool_liquidity: liquidity_before,
         pool_sqrt_price_x64: pool_state.sqrt_price_x64,
         pool_tick: pool_state.tick_current,
@@ -553,7 +534,7 @@ pub fn add_liquidity<'b, 'c: 'info, 'info>(
         trade_fee_owed_1: 0,
         transfer_fee_0: amount_0_transfer_fee,
         transfer_fee_1: amount_1_transfer_fee,
-    });
+    })?;
     #[cfg(feature = "enable-log")]
     msg!(
         "amount_0: {}, amount_0_transfer_fee: {}, amount_1: {}, amount_1_transfer_fee: {}",
@@ -583,6 +564,7 @@ pub fn add_liquidity<'b, 'c: 'info, 'info>(
         vault_0_mint,
         &token_program,
         token_2022_program_opt.clone(),
+        transfer_hook_accounts,
         amount_0 + amount_0_transfer_fee,
     )?;
 
@@ -593,16 +575,17 @@ pub fn add_liquidity<'b, 'c: 'info, 'info>(
         vault_1_mint,
         &token_program,
         token_2022_program_opt.clone(),
+        transfer_hook_accounts,
         amount_1 + amount_1_transfer_fee,
     )?;
-    emit!(LiquidityChangeEvent {
+    event_authority.emit(LiquidityChangeEvent {
         pool_state: pool_state.key(),
         tick: pool_state.tick_current,
         tick_lower: tick_lower_index,
         tick_upper: tick_upper_index,
         liquidity_before: liquidity_before,
         liquidity_after: pool_state.liquidity,
-    });
+    })?;
     Ok((
         amount_0,
         amount_1,
@@ -611,20 +594,117 @@ pub fn add_liquidity<'b, 'c: 'info, 'info>(
     ))
 }
 
+/// Add the liquidity to the ticks of the position and the pool, returns the token amounts
+/// the liquidity requires without transferring them
+pub fn add_liquidity_to_ticks<'c: 'info, 'info>(
+    tick_array_lower_loader: &AccountLoad<'info, TickArrayState>,
+    tick_array_upper_loader: &AccountLoad<'info, TickArrayState>,
+    growths_inside: &mut PositionGrowthsInside,
+    tick_array_bitmap_extension: Option<&'c AccountInfo<'info>>,
+    reward_info_extension: Option<&mut RewardInfoExtension>,
+    pool_state: &mut RefMut<PoolState>,
+    liquidity: u128,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    event_authority: EventAuthority,
+) -> Result<(u64, u64)> {
+    require_keys_eq!(tick_array_lower_loader.load()?.pool_id, pool_state.key());
+    require_keys_eq!(tick_array_upper_loader.load()?.pool_id, pool_state.key());
+
+    // get tick_state
+    let mut tick_lower_state = *tick_array_lower_loader
+        .load_mut()?
+        .get_tick_state_mut(tick_lower_index, pool_state.tick_spacing)?;
+    let mut tick_upper_state = *tick_array_upper_loader
+        .load_mut()?
+        .get_tick_state_mut(tick_upper_index, pool_state.tick_spacing)?;
+    if tick_lower_state.tick == 0 {
+        tick_lower_state.tick = tick_lower_index;
+    }
+    if tick_upper_state.tick == 0 {
+        tick_upper_state.tick = tick_upper_index;
+    }
+    let clock = Clock::get()?;
+    // the added liquidity is never zero, the liquidity of the position is only checked for a zero delta
+    let (amount_0, amount_1, flip_tick_lower, flip_tick_upper) = modify_position(
+        i128::try_from(liquidity).unwrap(),
+        0,
+        pool_state,
+        growths_inside,
+        &mut tick_lower_state,
+        &mut tick_upper_state,
+        reward_info_extension,
+        clock.unix_timestamp as u64,
+    )?;
+
+    // update tick_state
+    tick_array_lower_loader.load_mut()?.update_tick_state(
+        tick_lower_index,
+        pool_state.tick_spacing,
+        tick_lower_state,
+        Some(event_authority),
+    )?;
+    tick_array_upper_loader.load_mut()?.update_tick_state(
+        tick_upper_index,
+        pool_state.tick_spacing,
+        tick_upper_state,
+        Some(event_authority),
+    )?;
+
+    if flip_tick_lower {
+        let mut tick_array_lower = tick_array_lower_loader.load_mut()?;
+        let before_init_tick_count = tick_array_lower.initialized_tick_count;
+        tick_array_lower.update_initialized_tick_count(true)?;
+
+        if before_init_tick_count == 0 {
+            pool_state.flip_tick_array_bit(
+                tick_array_bitmap_extension,
+                tick_array_lower.start_tick_index,
+            )?;
+        }
+    }
+    if flip_tick_upper {
+        let mut tick_array_upper = tick_array_upper_loader.load_mut()?;
+        let before_init_tick_count = tick_array_upper.initialized_tick_count;
+        tick_array_upper.update_initialized_tick_count(true)?;
+
+        if before_init_tick_count == 0 {
+            pool_state.flip_tick_array_bit(
+                tick_array_bitmap_extension,
+                tick_array_upper.start_tick_index,
+            )?;
+        }
+    }
+    require!(
+        amount_0 > 0 || amount_1 > 0,
+        ErrorCode::ForbidBothZeroForSupplyLiquidity
+    );
+    Ok((amount_0, amount_1))
+}
+
+/// Updates a position with the given liquidity delta, `position_liquidity` is the liquidity of
+/// the position before the update
 pub fn modify_position(
     liquidity_delta: i128,
+    position_liquidity: u128,
     pool_state: &mut RefMut<PoolState>,
-    protocol_position_state: &mut ProtocolPositionState,
+    growths_inside: &mut PositionGrowthsInside,
     tick_lower_state: &mut TickState,
     tick_upper_state: &mut TickState,
+    reward_info_extension: Option<&mut RewardInfoExtension>,
     timestamp: u64,
 ) -> Result<(u64, u64, bool, bool)> {
+    // a zero delta only pokes a position which has liquidity
+    if liquidity_delta == 0 {
+        require_gt!(position_liquidity, 0, ErrorCode::InvaildLiquidity);
+    }
     let (flip_tick_lower, flip_tick_upper) = update_position(
         liquidity_delta,
         pool_state,
-        protocol_position_state,
+        growths_inside,
         tick_lower_state,
         tick_upper_state,
+        reward_info_extension,
         timestamp,
     )?;
     let mut amount_0 = 0;
@@ -649,16 +729,42 @@ pub fn modify_position(
     Ok((amount_0, amount_1, flip_tick_lower, flip_tick_upper))
 }
 
-/// Updates a position with the given liquidity delta and tick
+/// Updates the ticks of a position with the given liquidity delta,
+/// and computes the growths inside the range of the position
 pub fn update_position(
     liquidity_delta: i128,
     pool_state: &mut RefMut<PoolState>,
-    protocol_position_state: &mut ProtocolPositionState,
+    growths_inside: &mut PositionGrowthsInside,
     tick_lower_state: &mut TickState,
     tick_upper_state: &mut TickState,
+    reward_info_extension: Option<&mut RewardInfoExtension>,
     timestamp: u64,
 ) -> Result<(bool, bool)> {
+    require!(
+        tick_lower_state.tick >= tick_math::MIN_TICK
+            && tick_lower_state.tick <= tick_math::MAX_TICK,
+        ErrorCode::TickLowerOverflow
+    );
+    require!(
+        tick_upper_state.tick >= tick_math::MIN_TICK
+            && tick_upper_state.tick <= tick_math::MAX_TICK,
+        ErrorCode::TickUpperOverflow
+    );
     let updated_reward_infos = pool_state.update_reward_infos(timestamp)?;
+    // the ticks must track the rewards of the extension once any of them is initialized
+    let updated_extension_reward_infos = match reward_info_extension {
+        Some(reward_info_extension) => {
+            Some(reward_info_extension.update_reward_infos(timestamp, pool_state.liquidity)?)
+        }
+        None => {
+            require!(
+                pool_state.extension_reward_count == 0,
+                ErrorCode::MissingRewardInfoExtension
+            );
+            None
+        }
+    };
+    pool_state.update_liquidity_seconds(timestamp);
 
     let mut flipped_lower = false;
     let mut flipped_upper = false;
@@ -673,6 +779,7 @@ pub fn update_position(
             pool_state.fee_growth_global_1_x64,
             false,
             &updated_reward_infos,
+            timestamp,
         )?;
         flipped_upper = tick_upper_state.update(
             pool_state.tick_current,
@@ -681,7 +788,23 @@ pub fn update_position(
             pool_state.fee_growth_global_1_x64,
             true,
             &updated_reward_infos,
+            timestamp,
         )?;
+        if let Some(extension_reward_infos) = &updated_extension_reward_infos {
+            // a tick flips to initialized only when liquidity is added
+            if flipped_lower && liquidity_delta > 0 {
+                tick_lower_state.initialize_extension_reward_growths(
+                    pool_state.tick_current,
+                    extension_reward_infos,
+                );
+            }
+            if flipped_upper && liquidity_delta > 0 {
+                tick_upper_state.initialize_extension_reward_growths(
+                    pool_state.tick_current,
+                    extension_reward_infos,
+                );
+            }
+        }
         #[cfg(feature = "enable-log")]
         msg!(
             "tick_upper.reward_growths_outside_x64:{:?}, tick_lower.reward_growths_outside_x64:{:?}",
@@ -691,7 +814,10 @@ pub fn update_position(
     }
 
     // Update fees
-    let (fee_growth_inside_0_x64, fee_growth_inside_1_x64) = tick_array::get_fee_growth_inside(
+    (
+        growths_inside.fee_growth_inside_0_x64,
+        growths_inside.fee_growth_inside_1_x64,
+    ) = tick_array::get_fee_growth_inside(
         tick_lower_state.deref(),
         tick_upper_state.deref(),
         pool_state.tick_current,
@@ -700,21 +826,32 @@ pub fn update_position(
     );
 
     // Update reward outside if needed
-    let reward_growths_inside = tick_array::get_reward_growths_inside(
+    growths_inside.reward_growths_inside = tick_array::get_reward_growths_inside(
         tick_lower_state.deref(),
         tick_upper_state.deref(),
         pool_state.tick_current,
         &updated_reward_infos,
     );
-
-    protocol_position_state.update(
-        tick_lower_state.tick,
-        tick_upper_state.tick,
-        liquidity_delta,
-        fee_growth_inside_0_x64,
-        fee_growth_inside_1_x64,
-        reward_growths_inside,
-    )?;
+    #[cfg(feature = "enable-log")]
+    msg!(
+        "position reward_growths_inside:{:?}",
+        growths_inside.reward_growths_inside
+    );
+    if let Some(extension_reward_infos) = &updated_extension_reward_infos {
+        growths_inside.extension_reward_growths_inside =
+            tick_array::get_extension_reward_growths_inside(
+                tick_lower_state.deref(),
+                tick_upper_state.deref(),
+                pool_state.tick_current,
+                extension_reward_infos,
+            );
+    }
+    growths_inside.seconds_inside = tick_array::get_seconds_inside(
+        tick_lower_state.deref(),
+        tick_upper_state.deref(),
+        pool_state.tick_current,
+        timestamp,
+    );
     if liquidity_delta < 0 {
         if flipped_lower {
             tick_lower_state.clear();
@@ -738,7 +875,7 @@ fn mint_nft_and_remove_mint_authority<'info>(
     token_program_2022: Option<&Program<'info, Token2022>>,
     system_program: &Program<'info, System>,
     rent: &Sysvar<'info, Rent>,
-    with_metadata: bool,
+    metadata: Option<PositionNftMetadata>,
     use_metadata_extension: bool,
 ) -> Result<()> {
     let pool_state_info = pool_state_loader.to_account_info();
@@ -752,8 +889,8 @@ fn mint_nft_and_remove_mint_authority<'info>(
         token_program_2022.unwrap().to_account_info()
     };
 
-    if with_metadata {
-        let (name, symbol, uri) = get_metadata_data(personal_position.key());
+    if let Some(metadata) = metadata {
+        let (name, symbol, uri) = metadata.into_data(personal_position.key());
         if use_metadata_extension {
             initialize_token_metadata_extension(
                 payer,
@@ -811,15 +948,51 @@ fn mint_nft_and_remove_mint_authority<'info>(
     )
 }
 
-fn get_metadata_data(personal_position_id: Pubkey) -> (String, String, String) {
-    return (
-        String::from("Raydium Concentrated Liquidity"),
-        String::from("RCL"),
-        format!(
-            "https://dynamic-ipfs.raydium.io/clmm/position?id={}",
-            personal_position_id.to_string()
-        ),
-    );
+/// The metadata of a position NFT, the default Raydium metadata is used for the fields not set
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct PositionNftMetadata {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub uri: Option<String>,
+}
+
+impl PositionNftMetadata {
+    /// Creates the metadata with the lengths of the fields set bounded like the metaplex metadata
+    pub fn new(name: Option<String>, symbol: Option<String>, uri: Option<String>) -> Result<Self> {
+        for (field, max_length) in [
+            (&name, MAX_NAME_LENGTH),
+            (&symbol, MAX_SYMBOL_LENGTH),
+            (&uri, MAX_URI_LENGTH),
+        ] {
+            if let Some(field) = field {
+                require_gte!(
+                    max_length,
+                    field.len(),
+                    ErrorCode::InvalidPositionNftMetadata
+                );
+            }
+        }
+        Ok(Self { name, symbol, uri })
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.name.is_none() && self.symbol.is_none() && self.uri.is_none()
+    }
+
+    /// Returns the name, symbol and uri of the position NFT
+    pub fn into_data(self, personal_position_id: Pubkey) -> (String, String, String) {
+        (
+            self.name
+                .unwrap_or_else(|| String::from("Raydium Concentrated Liquidity")),
+            self.symbol.unwrap_or_else(|| String::from("RCL")),
+            self.uri.unwrap_or_else(|| {
+                format!(
+                    "https://dynamic-ipfs.raydium.io/clmm/position?id={}",
+                    personal_position_id.to_string()
+                )
+            }),
+        )
+    }
 }
 
 fn initialize_metadata_account<'info>(
@@ -941,6 +1114,7 @@ mod modify_position_test {
     use crate::states::oracle::block_timestamp_mock;
     use crate::states::pool_test::build_pool;
     use crate::states::protocol_position::*;
+    use crate::states::tick_array::{self, TickDelta};
     use crate::states::tick_array_test::build_tick;
 
     #[test]
@@ -951,17 +1125,66 @@ mod modify_position_test {
         let tick_upper_state = &mut build_tick(2, 10, -10).take();
 
         let result = modify_position(
+            0,
             0,
             pool_state,
-            &mut ProtocolPositionState::default(),
+            &mut PositionGrowthsInside::default(),
             tick_lower_state,
             tick_upper_state,
+            None,
             block_timestamp_mock(),
         );
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), ErrorCode::InvaildLiquidity.into());
     }
 
+    #[test]
+    fn liquidity_delta_zero_computes_growths_inside_test() {
+        let pool_state_ref = build_pool(1, 10, 1000, 10000);
+        let pool_state = &mut pool_state_ref.borrow_mut();
+        pool_state.fee_growth_global_0_x64 = 100;
+        pool_state.fee_growth_global_1_x64 = 200;
+        let tick_lower_state = &mut build_tick(1, 10, 10).take();
+        let tick_upper_state = &mut build_tick(2, 10, -10).take();
+        tick_lower_state.fee_growth_outside_0_x64 = 10;
+        tick_upper_state.fee_growth_outside_1_x64 = 20;
+        let tick_lower_before = *tick_lower_state;
+        let tick_upper_before = *tick_upper_state;
+
+        let growths_inside = &mut PositionGrowthsInside::default();
+        let (amount_0, amount_1, flip_tick_lower, flip_tick_upper) = modify_position(
+            0,
+            10,
+            pool_state,
+            growths_inside,
+            tick_lower_state,
+            tick_upper_state,
+            None,
+            block_timestamp_mock(),
+        )
+        .unwrap();
+        assert_eq!((amount_0, amount_1), (0, 0));
+        assert!(!flip_tick_lower && !flip_tick_upper);
+        assert!(TickDelta::diff(&tick_lower_before, tick_lower_state).is_none());
+        assert!(TickDelta::diff(&tick_upper_before, tick_upper_state).is_none());
+
+        let (fee_growth_inside_0_x64, fee_growth_inside_1_x64) = tick_array::get_fee_growth_inside(
+            tick_lower_state,
+            tick_upper_state,
+            pool_state.tick_current,
+            pool_state.fee_growth_global_0_x64,
+            pool_state.fee_growth_global_1_x64,
+        );
+        assert_eq!(
+            growths_inside.fee_growth_inside_0_x64,
+            fee_growth_inside_0_x64
+        );
+        assert_eq!(
+            growths_inside.fee_growth_inside_1_x64,
+            fee_growth_inside_1_x64
+        );
+    }
+
     #[test]
     fn init_position_in_range_test() {
         let liquidity = 10000;
@@ -980,13 +1203,15 @@ mod modify_position_test {
         let tick_upper_state = &mut build_tick(tick_upper_index, 0, 0).take();
 
         let liquidity_delta = 10000;
-        let protocol_position = &mut ProtocolPositionState::default();
+        let growths_inside = &mut PositionGrowthsInside::default();
         let (amount_0_int, amount_1_int, flip_tick_lower, flip_tick_upper) = modify_position(
             liquidity_delta,
+            0,
             pool_state,
-            protocol_position,
+            growths_inside,
             tick_lower_state,
             tick_upper_state,
+            None,
             block_timestamp_mock(),
         )
         .unwrap();
@@ -1012,21 +1237,15 @@ mod modify_position_test {
         assert!(tick_upper_state.fee_growth_outside_0_x64 == 0);
         assert!(tick_upper_state.fee_growth_outside_1_x64 == 0);
 
-        // check protocol position
+        // check growths inside
         let fee_growth_inside_0_last_x64 = pool_state.fee_growth_global_0_x64
             - tick_lower_state.fee_growth_outside_0_x64
             - tick_upper_state.fee_growth_outside_0_x64;
         let fee_growth_inside_1_last_x64 = pool_state.fee_growth_global_1_x64
             - tick_lower_state.fee_growth_outside_1_x64
             - tick_upper_state.fee_growth_outside_1_x64;
-        assert!(protocol_position.fee_growth_inside_0_last_x64 == fee_growth_inside_0_last_x64);
-        assert!(protocol_position.fee_growth_inside_1_last_x64 == fee_growth_inside_1_last_x64);
-        assert!(protocol_position.token_fees_owed_0 == 0);
-        assert!(protocol_position.token_fees_owed_1 == 0);
-        assert!(protocol_position.tick_lower_index == tick_lower_index);
-        assert!(protocol_position.tick_upper_index == tick_upper_index);
-
-        // check protocol position state
+        assert!(growths_inside.fee_growth_inside_0_x64 == fee_growth_inside_0_last_x64);
+        assert!(growths_inside.fee_growth_inside_1_x64 == fee_growth_inside_1_last_x64);
     }
 
     #[test]
@@ -1047,13 +1266,15 @@ mod modify_position_test {
         let tick_upper_state = &mut build_tick(tick_upper_index, 0, 0).take();
 
         let liquidity_delta = 10000;
-        let protocol_position = &mut ProtocolPositionState::default();
+        let growths_inside = &mut PositionGrowthsInside::default();
         let (amount_0_int, amount_1_int, flip_tick_lower, flip_tick_upper) = modify_position(
             liquidity_delta,
+            0,
             pool_state,
-            protocol_position,
+            growths_inside,
             tick_lower_state,
             tick_upper_state,
+            None,
             block_timestamp_mock(),
         )
         .unwrap();
@@ -1079,19 +1300,15 @@ mod modify_position_test {
         assert!(tick_upper_state.fee_growth_outside_0_x64 == pool_state.fee_growth_global_0_x64);
         assert!(tick_upper_state.fee_growth_outside_1_x64 == pool_state.fee_growth_global_1_x64);
 
-        // check protocol position
+        // check growths inside
         let fee_growth_inside_0_last_x64 = pool_state.fee_growth_global_0_x64
             - tick_lower_state.fee_growth_outside_0_x64
             - (pool_state.fee_growth_global_0_x64 - tick_upper_state.fee_growth_outside_0_x64);
         let fee_growth_inside_1_last_x64 = pool_state.fee_growth_global_1_x64
             - tick_lower_state.fee_growth_outside_1_x64
             - (pool_state.fee_growth_global_1_x64 - tick_upper_state.fee_growth_outside_1_x64);
-        assert!(protocol_position.fee_growth_inside_0_last_x64 == fee_growth_inside_0_last_x64);
-        assert!(protocol_position.fee_growth_inside_1_last_x64 == fee_growth_inside_1_last_x64);
-        assert!(protocol_position.token_fees_owed_0 == 0);
-        assert!(protocol_position.token_fees_owed_1 == 0);
-        assert!(protocol_position.tick_lower_index == tick_lower_index);
-        assert!(protocol_position.tick_upper_index == tick_upper_index);
+        assert!(growths_inside.fee_growth_inside_0_x64 == fee_growth_inside_0_last_x64);
+        assert!(growths_inside.fee_growth_inside_1_x64 == fee_growth_inside_1_last_x64);
     }
 
     #[test]
@@ -1112,13 +1329,15 @@ mod modify_position_test {
         let tick_upper_state = &mut build_tick(tick_upper_index, 0, 0).take();
 
         let liquidity_delta = 10000;
-        let protocol_position = &mut ProtocolPositionState::default();
+        let growths_inside = &mut PositionGrowthsInside::default();
         let (amount_0_int, amount_1_int, flip_tick_lower, flip_tick_upper) = modify_position(
             liquidity_delta,
+            0,
             pool_state,
-            protocol_position,
+            growths_inside,
             tick_lower_state,
             tick_upper_state,
+            None,
             block_timestamp_mock(),
         )
         .unwrap();
@@ -1144,20 +1363,44 @@ mod modify_position_test {
         assert!(tick_upper_state.fee_growth_outside_0_x64 == 0);
         assert!(tick_upper_state.fee_growth_outside_1_x64 == 0);
 
-        // check protocol position
+        // check growths inside
         let fee_growth_inside_0_last_x64 = pool_state.fee_growth_global_0_x64
             - (pool_state.fee_growth_global_0_x64 - tick_lower_state.fee_growth_outside_0_x64)
             - tick_upper_state.fee_growth_outside_0_x64;
         let fee_growth_inside_1_last_x64 = pool_state.fee_growth_global_1_x64
             - (pool_state.fee_growth_global_1_x64 - tick_lower_state.fee_growth_outside_1_x64)
             - tick_upper_state.fee_growth_outside_1_x64;
-        assert!(protocol_position.fee_growth_inside_0_last_x64 == fee_growth_inside_0_last_x64);
-        assert!(protocol_position.fee_growth_inside_1_last_x64 == fee_growth_inside_1_last_x64);
-        assert!(protocol_position.token_fees_owed_0 == 0);
-        assert!(protocol_position.token_fees_owed_1 == 0);
-        assert!(protocol_position.tick_lower_index == tick_lower_index);
-        assert!(protocol_position.tick_upper_index == tick_upper_index);
-
-        // check protocol position state
+        assert!(growths_inside.fee_growth_inside_0_x64 == fee_growth_inside_0_last_x64);
+        assert!(growths_inside.fee_growth_inside_1_x64 == fee_growth_inside_1_last_x64);
+    }
+}
+
+#[cfg(test)]
+mod position_nft_metadata_test {
+    use super::*;
+
+    #[test]
+    fn position_nft_metadata_test() {
+        let personal_position_id = Pubkey::new_unique();
+        let (name, symbol, uri) = PositionNftMetadata::default().into_data(personal_position_id);
+        assert_eq!(name, "Raydium Concentrated Liquidity");
+        assert_eq!(symbol, "RCL");
+        assert!(uri.ends_with(&personal_position_id.to_string()));
+
+        let metadata = PositionNftMetadata::new(None, Some(String::from("VAULT")), None).unwrap();
+        assert!(!metadata.is_default());
+        let (name, symbol, _) = metadata.into_data(personal_position_id);
+        assert_eq!(name, "Raydium Concentrated Liquidity");
+        assert_eq!(symbol, "VAULT");
+
+        assert_eq!(
+            PositionNftMetadata::new(Some("n".repeat(MAX_NAME_LENGTH + 1)), None, None)
+                .unwrap_err(),
+            error!(ErrorCode::InvalidPositionNftMetadata)
+        );
+        assert_eq!(
+            PositionNftMetadata::new(None, None, Some("u".repeat(MAX_URI_LENGTH + 1))).unwrap_err(),
+            error!(ErrorCode::InvalidPositionNftMetadata)
+        );
     }
 }
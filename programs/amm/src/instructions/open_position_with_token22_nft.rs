@@ -1,4 +1,5 @@
-use super::open_position::open_position;
+use super::open_position::{open_position, PositionNftMetadata};
+use crate::error::ErrorCode;
 use crate::states::*;
 use crate::util::create_position_nft_mint_with_extensions;
 use anchor_lang::prelude::*;
@@ -6,10 +7,11 @@ use anchor_spl::associated_token::{create, AssociatedToken, Create};
 use anchor_spl::token::Token;
 use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
 
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 #[instruction(tick_lower_index: i32, tick_upper_index: i32,tick_array_lower_start_index:i32,tick_array_upper_start_index:i32)]
 pub struct OpenPositionWithToken22Nft<'info> {
-    /// Pays to mint the position
+    /// Pays to mint the position, and the tokens if no funder signs as the first remaining account
     #[account(mut)]
     pub payer: Signer<'info>,
 
@@ -28,20 +30,9 @@ pub struct OpenPositionWithToken22Nft<'info> {
     #[account(mut)]
     pub pool_state: AccountLoader<'info, PoolState>,
 
-    /// Store the information of market marking in range
-    #[account(
-        init_if_needed,
-        seeds = [
-            POSITION_SEED.as_bytes(),
-            pool_state.key().as_ref(),
-            &tick_lower_index.to_be_bytes(),
-            &tick_upper_index.to_be_bytes(),
-        ],
-        bump,
-        payer = payer,
-        space = ProtocolPositionState::LEN
-    )]
-    pub protocol_position: Box<Account<'info, ProtocolPositionState>>,
+    /// CHECK: Deprecated, the protocol position is folded into the ticks and this account is
+    /// ignored. It is kept so that the account layout of the instruction doesn't change
+    pub protocol_position: UncheckedAccount<'info>,
 
     /// CHECK:  Account to store data for the position's lower tick
     #[account(
@@ -142,6 +133,18 @@ pub struct OpenPositionWithToken22Nft<'info> {
     // pub tick_array_bitmap: AccountLoader<'info, TickArrayBitmapExtension>,
 }
 
+/// An option of `open_position_with_token22_nft_v2`, the new options are appended as new variants
+/// so the instruction data of the existing ones stays valid
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum OpenPositionOption {
+    /// The liquidity of the position can't be decreased before this timestamp
+    LockedUntil(u64),
+    /// The name, symbol and uri of the NFT metadata instead of the default ones, requires `with_metadata`
+    Metadata(PositionNftMetadata),
+    /// The NFT can't be transferred from the owner, so the position stays bound to the owner until it is closed
+    NonTransferable,
+}
+
 pub fn open_position_with_token22_nft<'a, 'b, 'c: 'info, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, OpenPositionWithToken22Nft<'info>>,
     liquidity: u128,
@@ -153,7 +156,24 @@ pub fn open_position_with_token22_nft<'a, 'b, 'c: 'info, 'info>(
     tick_array_upper_start_index: i32,
     with_metadata: bool,
     base_flag: Option<bool>,
+    options: Vec<OpenPositionOption>,
 ) -> Result<()> {
+    let mut locked_until = None;
+    let mut non_transferable = false;
+    let mut metadata = PositionNftMetadata::default();
+    for option in options {
+        match option {
+            OpenPositionOption::LockedUntil(timestamp) => locked_until = Some(timestamp),
+            OpenPositionOption::Metadata(PositionNftMetadata { name, symbol, uri }) => {
+                metadata = PositionNftMetadata::new(name, symbol, uri)?
+            }
+            OpenPositionOption::NonTransferable => non_transferable = true,
+        }
+    }
+    require!(
+        with_metadata || metadata.is_default(),
+        ErrorCode::InvalidPositionNftMetadata
+    );
     create_position_nft_mint_with_extensions(
         &ctx.accounts.payer,
         &ctx.accounts.position_nft_mint,
@@ -162,6 +182,7 @@ pub fn open_position_with_token22_nft<'a, 'b, 'c: 'info, 'info>(
         &ctx.accounts.system_program,
         &ctx.accounts.token_program_2022,
         with_metadata,
+        non_transferable,
     )?;
 
     // create user position nft account
@@ -186,7 +207,6 @@ pub fn open_position_with_token22_nft<'a, 'b, 'c: 'info, 'info>(
         &ctx.accounts.pool_state,
         &ctx.accounts.tick_array_lower,
         &ctx.accounts.tick_array_upper,
-        &mut ctx.accounts.protocol_position,
         &mut ctx.accounts.personal_position,
         &ctx.accounts.token_account_0.to_account_info(),
         &ctx.accounts.token_account_1.to_account_info(),
@@ -195,13 +215,11 @@ pub fn open_position_with_token22_nft<'a, 'b, 'c: 'info, 'info>(
         &ctx.accounts.rent,
         &ctx.accounts.system_program,
         &ctx.accounts.token_program,
-        &ctx.accounts.associated_token_program,
         None,
         Some(&ctx.accounts.token_program_2022),
         Some(ctx.accounts.vault_0_mint.clone()),
         Some(ctx.accounts.vault_1_mint.clone()),
         &ctx.remaining_accounts,
-        ctx.bumps.protocol_position,
         ctx.bumps.personal_position,
         liquidity,
         amount_0_max,
@@ -210,8 +228,14 @@ pub fn open_position_with_token22_nft<'a, 'b, 'c: 'info, 'info>(
         tick_upper_index,
         tick_array_lower_start_index,
         tick_array_upper_start_index,
-        with_metadata,
+        with_metadata.then_some(metadata),
         base_flag,
         true,
-    )
+        crate::event_authority!(ctx.accounts, ctx.bumps),
+    )?;
+
+    if let Some(locked_until) = locked_until {
+        ctx.accounts.personal_position.locked_until = locked_until;
+    }
+    Ok(())
 }
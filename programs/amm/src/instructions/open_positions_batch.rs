@@ -0,0 +1,222 @@
+use super::open_position::open_position;
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::util::{
+    check_remaining_accounts_max, check_remaining_accounts_min, create_or_allocate_account,
+    create_position_nft_mint_with_extensions, OPEN_POSITIONS_BATCH_GROUP_ACCOUNTS,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::{create, AssociatedToken, Create};
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
+
+/// The max number of positions opened in a batch
+pub const MAX_OPEN_POSITIONS_BATCH: usize = 5;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct OpenPositionsBatch<'info> {
+    /// Pays to mint the positions, and the tokens if no funder signs as the first remaining account of a position
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Receives the position NFTs
+    pub position_nft_owner: UncheckedAccount<'info>,
+
+    /// Add liquidity for this pool
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// The token_0 account deposit token to the pool
+    #[account(
+        mut,
+        token::mint = token_vault_0.mint
+    )]
+    pub token_account_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token_1 account deposit token to the pool
+    #[account(
+        mut,
+        token::mint = token_vault_1.mint
+    )]
+    pub token_account_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The address that holds pool tokens for token_0
+    #[account(
+        mut,
+        constraint = token_vault_0.key() == pool_state.load()?.token_vault_0
+    )]
+    pub token_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The address that holds pool tokens for token_1
+    #[account(
+        mut,
+        constraint = token_vault_1.key() == pool_state.load()?.token_vault_1
+    )]
+    pub token_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Sysvar for token mint and ATA creation
+    pub rent: Sysvar<'info, Rent>,
+
+    /// Program to create the position manager state account
+    pub system_program: Program<'info, System>,
+
+    /// Program to transfer for token account
+    pub token_program: Program<'info, Token>,
+
+    /// Program to create an ATA for receiving position NFT
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// Program to create NFT mint/token account and transfer for token22 account
+    pub token_program_2022: Program<'info, Token2022>,
+
+    /// The mint of token vault 0
+    #[account(
+        address = token_vault_0.mint
+    )]
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of token vault 1
+    #[account(
+        address = token_vault_1.mint
+    )]
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
+    // remaining accounts, for each position in order:
+    // position_nft_mint(signer), position_nft_account, personal_position,
+    // tick_array_lower, tick_array_upper, followed by `remaining_accounts_len` accounts
+    // of the position like the remaining accounts of open_position_with_token22_nft
+}
+
+#[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Debug, PartialEq)]
+pub struct OpenPositionParam {
+    /// The low boundary of market
+    pub tick_lower_index: i32,
+    /// The upper boundary of market
+    pub tick_upper_index: i32,
+    /// The start index of tick array which include tick low
+    pub tick_array_lower_start_index: i32,
+    /// The start index of tick array which include tick upper
+    pub tick_array_upper_start_index: i32,
+    /// The liquidity to be added, calculated from the amount of base_flag if zero
+    pub liquidity: u128,
+    /// The max amount of token_0 to spend, which serves as a slippage check
+    pub amount_0_max: u64,
+    /// The max amount of token_1 to spend, which serves as a slippage check
+    pub amount_1_max: u64,
+    /// If the liquidity is zero, true: calculate liquidity base amount_0_max otherwise base amount_1_max
+    pub base_flag: Option<bool>,
+    /// The number of remaining accounts of the position following its group of accounts
+    pub remaining_accounts_len: u8,
+}
+
+pub fn open_positions_batch<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, OpenPositionsBatch<'info>>,
+    params: Vec<OpenPositionParam>,
+) -> Result<()> {
+    require!(
+        !params.is_empty() && params.len() <= MAX_OPEN_POSITIONS_BATCH,
+        ErrorCode::InvalidOpenPositionsBatch
+    );
+    let mut remaining_accounts: &'c [AccountInfo<'info>] = ctx.remaining_accounts;
+    for param in params {
+        let group_len =
+            OPEN_POSITIONS_BATCH_GROUP_ACCOUNTS + usize::from(param.remaining_accounts_len);
+        check_remaining_accounts_min(remaining_accounts, group_len)?;
+        let (group, rest) = remaining_accounts.split_at(group_len);
+        remaining_accounts = rest;
+        let (group, position_remaining_accounts) =
+            group.split_at(OPEN_POSITIONS_BATCH_GROUP_ACCOUNTS);
+        let position_nft_mint = Signer::try_from(&group[0])?;
+        let position_nft_account = &group[1];
+        let tick_array_lower = UncheckedAccount::try_from(&group[3]);
+        let tick_array_upper = UncheckedAccount::try_from(&group[4]);
+
+        let (personal_position_key, personal_position_bump) = Pubkey::find_program_address(
+            &[POSITION_SEED.as_bytes(), position_nft_mint.key().as_ref()],
+            &crate::id(),
+        );
+        require_keys_eq!(group[2].key(), personal_position_key);
+        require_keys_eq!(*group[2].owner, system_program::ID);
+
+        // the personal position must be new, like the account of open_position_with_token22_nft
+        create_or_allocate_account(
+            &crate::id(),
+            ctx.accounts.payer.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            group[2].clone(),
+            &[
+                POSITION_SEED.as_bytes(),
+                position_nft_mint.key().as_ref(),
+                &[personal_position_bump],
+            ],
+            PersonalPositionState::LEN,
+        )?;
+        let mut personal_position = Box::new(Account::<PersonalPositionState>::try_from_unchecked(
+            &group[2],
+        )?);
+
+        create_position_nft_mint_with_extensions(
+            &ctx.accounts.payer,
+            &position_nft_mint,
+            &ctx.accounts.pool_state.to_account_info(),
+            &personal_position.to_account_info(),
+            &ctx.accounts.system_program,
+            &ctx.accounts.token_program_2022,
+            false,
+            false,
+        )?;
+        create(CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            Create {
+                payer: ctx.accounts.payer.to_account_info(),
+                associated_token: position_nft_account.clone(),
+                authority: ctx.accounts.position_nft_owner.to_account_info(),
+                mint: position_nft_mint.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program_2022.to_account_info(),
+            },
+        ))?;
+
+        open_position(
+            &ctx.accounts.payer,
+            &ctx.accounts.position_nft_owner,
+            &position_nft_mint,
+            position_nft_account,
+            None,
+            &ctx.accounts.pool_state,
+            &tick_array_lower,
+            &tick_array_upper,
+            &mut personal_position,
+            &ctx.accounts.token_account_0.to_account_info(),
+            &ctx.accounts.token_account_1.to_account_info(),
+            &ctx.accounts.token_vault_0.to_account_info(),
+            &ctx.accounts.token_vault_1.to_account_info(),
+            &ctx.accounts.rent,
+            &ctx.accounts.system_program,
+            &ctx.accounts.token_program,
+            None,
+            Some(&ctx.accounts.token_program_2022),
+            Some(ctx.accounts.vault_0_mint.clone()),
+            Some(ctx.accounts.vault_1_mint.clone()),
+            position_remaining_accounts,
+            personal_position_bump,
+            param.liquidity,
+            param.amount_0_max,
+            param.amount_1_max,
+            param.tick_lower_index,
+            param.tick_upper_index,
+            param.tick_array_lower_start_index,
+            param.tick_array_upper_start_index,
+            None,
+            param.base_flag,
+            true,
+            crate::event_authority!(ctx.accounts, ctx.bumps),
+        )?;
+
+        // persist the position before the next one
+        personal_position.exit(&crate::id())?;
+    }
+    check_remaining_accounts_max(remaining_accounts, 0)?;
+    Ok(())
+}
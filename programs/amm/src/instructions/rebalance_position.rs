@@ -0,0 +1,469 @@
+use super::decrease_liquidity::decrease_liquidity;
+use super::increase_liquidity::increase_liquidity;
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::libraries::{liquidity_math, tick_math};
+use crate::states::*;
+use crate::swap_v2::{exact_internal_v2, SwapSingleV2};
+use crate::util::{check_remaining_accounts_min, get_transfer_fee, AccountLoad};
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+#[instruction(param: RebalanceParam)]
+pub struct RebalancePosition<'info> {
+    /// The position owner or an approved operator, pays to create the new tick arrays
+    #[account(mut)]
+    pub nft_owner: Signer<'info>,
+
+    /// The token account for the tokenized position
+    #[account(
+        constraint = nft_account.mint == personal_position.nft_mint,
+        constraint = nft_account.amount == 1,
+    )]
+    pub nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The position to rebalance, which is moved to the new tick range
+    #[account(mut, constraint = personal_position.pool_id == pool_state.key())]
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
+
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// The factory state to read protocol fees of the swap
+    #[account(address = pool_state.load()?.amm_config)]
+    pub amm_config: Box<Account<'info, AmmConfig>>,
+
+    /// The program account for the most recent oracle observation, written by the swap
+    #[account(mut, address = pool_state.load()?.observation_key)]
+    pub observation_state: AccountLoader<'info, ObservationState>,
+
+    /// Stores the lower tick of the current tick range
+    #[account(mut, constraint = tick_array_lower.load()?.pool_id == pool_state.key())]
+    pub tick_array_lower: AccountLoader<'info, TickArrayState>,
+
+    /// Stores the upper tick of the current tick range
+    #[account(mut, constraint = tick_array_upper.load()?.pool_id == pool_state.key())]
+    pub tick_array_upper: AccountLoader<'info, TickArrayState>,
+
+    /// CHECK: Account to store data for the lower tick of the new tick range
+    #[account(
+        mut,
+        seeds = [
+            TICK_ARRAY_SEED.as_bytes(),
+            pool_state.key().as_ref(),
+            &param.tick_array_lower_start_index.to_be_bytes(),
+        ],
+        bump,
+    )]
+    pub new_tick_array_lower: UncheckedAccount<'info>,
+
+    /// CHECK: Account to store data for the upper tick of the new tick range
+    #[account(
+        mut,
+        seeds = [
+            TICK_ARRAY_SEED.as_bytes(),
+            pool_state.key().as_ref(),
+            &param.tick_array_upper_start_index.to_be_bytes(),
+        ],
+        bump,
+    )]
+    pub new_tick_array_upper: UncheckedAccount<'info>,
+
+    /// The token_0 account receiving the withdrawn tokens and paying the new liquidity
+    #[account(
+        mut,
+        token::mint = token_vault_0.mint
+    )]
+    pub token_account_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token_1 account receiving the withdrawn tokens and paying the new liquidity
+    #[account(
+        mut,
+        token::mint = token_vault_1.mint
+    )]
+    pub token_account_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The address that holds pool tokens for token_0
+    #[account(
+        mut,
+        constraint = token_vault_0.key() == pool_state.load()?.token_vault_0
+    )]
+    pub token_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The address that holds pool tokens for token_1
+    #[account(
+        mut,
+        constraint = token_vault_1.key() == pool_state.load()?.token_vault_1
+    )]
+    pub token_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Program to create the new tick arrays
+    pub system_program: Program<'info, System>,
+
+    /// SPL program to transfer tokens
+    pub token_program: Program<'info, Token>,
+
+    /// Token program 2022
+    pub token_program_2022: Program<'info, Token2022>,
+
+    /// memo program
+    /// CHECK:
+    #[account(
+        address = spl_memo::id()
+    )]
+    pub memo_program: UncheckedAccount<'info>,
+
+    /// The mint of token vault 0
+    #[account(
+        address = token_vault_0.mint
+    )]
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of token vault 1
+    #[account(
+        address = token_vault_1.mint
+    )]
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
+    // remaining accounts
+    // position_operator: required if the signer is an operator of the position
+    // `decrease_remaining_accounts_len` accounts like the remaining accounts of decrease_liquidity_v2
+    // `increase_remaining_accounts_len` accounts like the remaining accounts of increase_liquidity_v2
+    // the rest are used like the remaining accounts of swap_v2 if swapping
+}
+
+#[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Debug, PartialEq)]
+pub struct RebalanceParam {
+    /// The low boundary of the new tick range
+    pub tick_lower_index: i32,
+    /// The upper boundary of the new tick range
+    pub tick_upper_index: i32,
+    /// The start index of tick array which include the new tick low
+    pub tick_array_lower_start_index: i32,
+    /// The start index of tick array which include the new tick upper
+    pub tick_array_upper_start_index: i32,
+    /// The amount of tokens withdrawn to swap before reopening, zero means no swap
+    pub swap_amount: u64,
+    /// The direction of the swap, true swaps token_0 to token_1
+    pub swap_zero_for_one: bool,
+    /// The price limit of the swap
+    pub swap_sqrt_price_limit_x64: u128,
+    /// The minimum liquidity of the reopened position, which serves as a slippage check
+    pub liquidity_min: u128,
+    /// The number of remaining accounts used to decrease liquidity
+    pub decrease_remaining_accounts_len: u8,
+    /// The number of remaining accounts used to increase liquidity
+    pub increase_remaining_accounts_len: u8,
+}
+
+pub fn rebalance_position<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, RebalancePosition<'info>>,
+    param: RebalanceParam,
+) -> Result<()> {
+    let tick_lower_index_old = ctx.accounts.personal_position.tick_lower_index;
+    let tick_upper_index_old = ctx.accounts.personal_position.tick_upper_index;
+    require!(
+        param.tick_lower_index != tick_lower_index_old
+            || param.tick_upper_index != tick_upper_index_old,
+        ErrorCode::InvaildTickIndex
+    );
+    require!(
+        !ctx.accounts.personal_position.locked,
+        ErrorCode::PositionLocked
+    );
+    require!(
+        !ctx.accounts
+            .personal_position
+            .is_time_locked(Clock::get()?.unix_timestamp as u64),
+        ErrorCode::PositionTimeLocked
+    );
+    let (remaining_accounts, restricted_operator) = check_position_authority(
+        &ctx.accounts.nft_owner.key(),
+        &ctx.accounts.nft_account.owner,
+        &ctx.accounts.personal_position,
+        ctx.remaining_accounts,
+        PositionAction::DecreaseLiquidity,
+    )?;
+    if restricted_operator {
+        // the new tick range must be allowed for the operator as well
+        let mut rebalanced_position = (**ctx.accounts.personal_position).clone();
+        rebalanced_position.tick_lower_index = param.tick_lower_index;
+        rebalanced_position.tick_upper_index = param.tick_upper_index;
+        check_position_authority(
+            &ctx.accounts.nft_owner.key(),
+            &ctx.accounts.nft_account.owner,
+            &rebalanced_position,
+            ctx.remaining_accounts,
+            PositionAction::IncreaseLiquidity,
+        )?;
+        check_operator_recipients(
+            &ctx.accounts.nft_account.owner,
+            &ctx.accounts.pool_state.key(),
+            &[
+                ctx.accounts.token_account_0.to_account_info(),
+                ctx.accounts.token_account_1.to_account_info(),
+            ],
+            remaining_accounts,
+        )?;
+    }
+    let decrease_remaining_accounts_len = usize::from(param.decrease_remaining_accounts_len);
+    let increase_remaining_accounts_len = usize::from(param.increase_remaining_accounts_len);
+    check_remaining_accounts_min(
+        remaining_accounts,
+        decrease_remaining_accounts_len + increase_remaining_accounts_len,
+    )?;
+    let (decrease_remaining_accounts, remaining_accounts) =
+        remaining_accounts.split_at(decrease_remaining_accounts_len);
+    let (increase_remaining_accounts, swap_remaining_accounts) =
+        remaining_accounts.split_at(increase_remaining_accounts_len);
+
+    let balance_0_before = ctx.accounts.token_account_0.amount;
+    let balance_1_before = ctx.accounts.token_account_1.amount;
+
+    // withdraw all liquidity with the fees and rewards of the current tick range
+    let liquidity = ctx.accounts.personal_position.liquidity;
+    decrease_liquidity(
+        &ctx.accounts.nft_owner.to_account_info(),
+        &ctx.accounts.pool_state,
+        &mut ctx.accounts.personal_position,
+        &ctx.accounts.token_vault_0.to_account_info(),
+        &ctx.accounts.token_vault_1.to_account_info(),
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        &ctx.accounts.token_account_0.to_account_info(),
+        &ctx.accounts.token_account_1.to_account_info(),
+        &ctx.accounts.token_program,
+        Some(ctx.accounts.token_program_2022.clone()),
+        Some(ctx.accounts.memo_program.clone()),
+        Some(ctx.accounts.vault_0_mint.clone()),
+        Some(ctx.accounts.vault_1_mint.clone()),
+        decrease_remaining_accounts,
+        liquidity,
+        0,
+        0,
+        crate::event_authority!(ctx.accounts, ctx.bumps),
+    )?;
+
+    // swap the imbalance of the withdrawn tokens for the new tick range
+    if param.swap_amount > 0 {
+        ctx.accounts.token_account_0.reload()?;
+        ctx.accounts.token_account_1.reload()?;
+        let accounts = &ctx.accounts;
+        let (input_token_account, output_token_account, input_vault, output_vault) =
+            if param.swap_zero_for_one {
+                (
+                    &accounts.token_account_0,
+                    &accounts.token_account_1,
+                    &accounts.token_vault_0,
+                    &accounts.token_vault_1,
+                )
+            } else {
+                (
+                    &accounts.token_account_1,
+                    &accounts.token_account_0,
+                    &accounts.token_vault_1,
+                    &accounts.token_vault_0,
+                )
+            };
+        let (input_vault_mint, output_vault_mint) = if param.swap_zero_for_one {
+            (&accounts.vault_0_mint, &accounts.vault_1_mint)
+        } else {
+            (&accounts.vault_1_mint, &accounts.vault_0_mint)
+        };
+        exact_internal_v2(
+            &mut SwapSingleV2 {
+                payer: accounts.nft_owner.clone(),
+                amm_config: accounts.amm_config.clone(),
+                pool_state: accounts.pool_state.clone(),
+                input_token_account: input_token_account.clone(),
+                output_token_account: output_token_account.clone(),
+                input_vault: input_vault.clone(),
+                output_vault: output_vault.clone(),
+                observation_state: accounts.observation_state.clone(),
+                token_program: accounts.token_program.clone(),
+                token_program_2022: accounts.token_program_2022.clone(),
+                memo_program: accounts.memo_program.clone(),
+                #[cfg(not(feature = "log-events"))]
+                event_authority: accounts.event_authority.clone(),
+                #[cfg(not(feature = "log-events"))]
+                program: accounts.program.clone(),
+                input_vault_mint: input_vault_mint.clone(),
+                output_vault_mint: output_vault_mint.clone(),
+            },
+            swap_remaining_accounts,
+            &[],
+            param.swap_amount,
+            param.swap_sqrt_price_limit_x64,
+            true,
+            false,
+            crate::event_authority!(accounts, ctx.bumps),
+        )?;
+    }
+    ctx.accounts.token_account_0.reload()?;
+    ctx.accounts.token_account_1.reload()?;
+    let amount_0 = ctx
+        .accounts
+        .token_account_0
+        .amount
+        .saturating_sub(balance_0_before);
+    let amount_1 = ctx
+        .accounts
+        .token_account_1
+        .amount
+        .saturating_sub(balance_1_before);
+
+    // move the position to the new tick range, whose tick arrays are created if needed
+    let (tick_array_lower_loader, tick_array_upper_loader) = prepare_position_range(
+        &ctx.accounts.nft_owner.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.pool_state,
+        &ctx.accounts.new_tick_array_lower.to_account_info(),
+        &ctx.accounts.new_tick_array_upper.to_account_info(),
+        param.tick_lower_index,
+        param.tick_upper_index,
+        param.tick_array_lower_start_index,
+        param.tick_array_upper_start_index,
+    )?;
+    let personal_position = ctx.accounts.personal_position.as_mut();
+    personal_position.tick_lower_index = param.tick_lower_index;
+    personal_position.tick_upper_index = param.tick_upper_index;
+
+    // reopen with the liquidity the withdrawn tokens can provide, net of the transfer fees
+    let new_liquidity = liquidity_math::get_liquidity_from_amounts(
+        ctx.accounts.pool_state.load()?.sqrt_price_x64,
+        tick_math::get_sqrt_price_at_tick(param.tick_lower_index)?,
+        tick_math::get_sqrt_price_at_tick(param.tick_upper_index)?,
+        amount_0
+            .checked_sub(get_transfer_fee(
+                ctx.accounts.vault_0_mint.clone(),
+                amount_0,
+            )?)
+            .unwrap(),
+        amount_1
+            .checked_sub(get_transfer_fee(
+                ctx.accounts.vault_1_mint.clone(),
+                amount_1,
+            )?)
+            .unwrap(),
+    );
+    require_gte!(
+        new_liquidity,
+        param.liquidity_min,
+        ErrorCode::PriceSlippageCheck
+    );
+    increase_liquidity(
+        &ctx.accounts.nft_owner,
+        &ctx.accounts.pool_state,
+        &mut ctx.accounts.personal_position,
+        &tick_array_lower_loader,
+        &tick_array_upper_loader,
+        &ctx.accounts.token_account_0.to_account_info(),
+        &ctx.accounts.token_account_1.to_account_info(),
+        &ctx.accounts.token_vault_0.to_account_info(),
+        &ctx.accounts.token_vault_1.to_account_info(),
+        &ctx.accounts.token_program,
+        Some(&ctx.accounts.token_program_2022),
+        Some(ctx.accounts.vault_0_mint.clone()),
+        Some(ctx.accounts.vault_1_mint.clone()),
+        increase_remaining_accounts,
+        new_liquidity,
+        amount_0,
+        amount_1,
+        None,
+        crate::event_authority!(ctx.accounts, ctx.bumps),
+    )?;
+
+    emit_event!(
+        ctx,
+        RebalancePositionEvent {
+            position_nft_mint: ctx.accounts.personal_position.nft_mint,
+            tick_lower_index_old,
+            tick_upper_index_old,
+            tick_lower_index: param.tick_lower_index,
+            tick_upper_index: param.tick_upper_index,
+            liquidity_old: liquidity,
+            liquidity: ctx.accounts.personal_position.liquidity,
+        }
+    )?;
+    Ok(())
+}
+
+/// Checks the tick range and creates its tick arrays if needed,
+/// returns the loaders of the lower and upper tick arrays
+pub fn prepare_position_range<'info>(
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    pool_state_loader: &AccountLoader<'info, PoolState>,
+    tick_array_lower_info: &AccountInfo<'info>,
+    tick_array_upper_info: &AccountInfo<'info>,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    tick_array_lower_start_index: i32,
+    tick_array_upper_start_index: i32,
+) -> Result<(
+    AccountLoad<'info, TickArrayState>,
+    AccountLoad<'info, TickArrayState>,
+)> {
+    let pool_state = pool_state_loader.load()?;
+    check_ticks_order(tick_lower_index, tick_upper_index)?;
+    check_tick_array_start_index(
+        tick_array_lower_start_index,
+        tick_lower_index,
+        pool_state.tick_spacing,
+    )?;
+    check_tick_array_start_index(
+        tick_array_upper_start_index,
+        tick_upper_index,
+        pool_state.tick_spacing,
+    )?;
+    let tick_array_lower_loader = TickArrayState::get_or_create_tick_array(
+        payer.clone(),
+        tick_array_lower_info.clone(),
+        system_program.clone(),
+        pool_state_loader,
+        tick_array_lower_start_index,
+        pool_state.tick_spacing,
+    )?;
+    let tick_array_upper_loader = if tick_array_lower_start_index == tick_array_upper_start_index {
+        AccountLoad::<TickArrayState>::try_from(tick_array_upper_info)?
+    } else {
+        TickArrayState::get_or_create_tick_array(
+            payer.clone(),
+            tick_array_upper_info.clone(),
+            system_program.clone(),
+            pool_state_loader,
+            tick_array_upper_start_index,
+            pool_state.tick_spacing,
+        )?
+    };
+    Ok((tick_array_lower_loader, tick_array_upper_loader))
+}
+
+/// Emitted when a position is moved to a new tick range
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct RebalancePositionEvent {
+    /// The position
+    #[index]
+    pub position_nft_mint: Pubkey,
+
+    /// The low boundary of the previous tick range
+    pub tick_lower_index_old: i32,
+
+    /// The upper boundary of the previous tick range
+    pub tick_upper_index_old: i32,
+
+    /// The low boundary of the new tick range
+    pub tick_lower_index: i32,
+
+    /// The upper boundary of the new tick range
+    pub tick_upper_index: i32,
+
+    /// The liquidity withdrawn from the previous tick range
+    pub liquidity_old: u128,
+
+    /// The liquidity of the position in the new tick range
+    pub liquidity: u128,
+}
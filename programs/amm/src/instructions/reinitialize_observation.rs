@@ -0,0 +1,35 @@
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ReinitializeObservation<'info> {
+    /// Pays to create the observation account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The pool of the closed observation account
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// Initialize the observation account of the pool again
+    #[account(
+        init,
+        seeds = [
+            OBSERVATION_SEED.as_bytes(),
+            pool_state.key().as_ref(),
+        ],
+        bump,
+        payer = payer,
+        space = ObservationState::LEN,
+        address = pool_state.load()?.observation_key
+    )]
+    pub observation_state: AccountLoader<'info, ObservationState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn reinitialize_observation(ctx: Context<ReinitializeObservation>) -> Result<()> {
+    ctx.accounts
+        .observation_state
+        .load_init()?
+        .initialize(ctx.accounts.pool_state.key())
+}
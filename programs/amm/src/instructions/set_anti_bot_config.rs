@@ -0,0 +1,35 @@
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct SetAntiBotConfig<'info> {
+    /// Only the pool creator can limit the swaps of the pool opening window
+    #[account(address = pool_state.load()?.owner @ ErrorCode::NotApproved)]
+    pub creator: Signer<'info>,
+
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+}
+
+pub fn set_anti_bot_config(
+    ctx: Context<SetAntiBotConfig>,
+    max_amount_out: u64,
+    duration: u64,
+) -> Result<()> {
+    let block_timestamp = Clock::get()?.unix_timestamp as u64;
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    pool_state.set_anti_bot_config(max_amount_out, duration, block_timestamp)?;
+
+    emit_event!(
+        ctx,
+        AntiBotConfigUpdatedEvent {
+            pool_state: ctx.accounts.pool_state.key(),
+            max_amount_out,
+            duration,
+        }
+    )?;
+    Ok(())
+}
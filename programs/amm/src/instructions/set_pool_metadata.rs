@@ -0,0 +1,49 @@
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct SetPoolMetadata<'info> {
+    /// The pool creator, pays to create the metadata account if needed
+    #[account(
+        mut,
+        address = pool_state.load()?.owner @ ErrorCode::NotApproved
+    )]
+    pub creator: Signer<'info>,
+
+    /// The pool to describe
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// The metadata of the pool, can be created in the same transaction as the pool
+    #[account(
+        init_if_needed,
+        seeds = [POOL_METADATA_SEED.as_bytes(), pool_state.key().as_ref()],
+        bump,
+        payer = creator,
+        space = PoolMetadata::LEN
+    )]
+    pub pool_metadata: Box<Account<'info, PoolMetadata>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_pool_metadata(ctx: Context<SetPoolMetadata>, label: String, uri: String) -> Result<()> {
+    let pool_metadata = &mut ctx.accounts.pool_metadata;
+    pool_metadata.bump = ctx.bumps.pool_metadata;
+    pool_metadata.pool_id = ctx.accounts.pool_state.key();
+    pool_metadata.creator = ctx.accounts.creator.key();
+    pool_metadata.set(label, uri)?;
+
+    emit_event!(
+        ctx,
+        SetPoolMetadataEvent {
+            pool_state: ctx.accounts.pool_state.key(),
+            creator: ctx.accounts.creator.key(),
+            label: ctx.accounts.pool_metadata.label.clone(),
+            uri: ctx.accounts.pool_metadata.uri.clone(),
+        }
+    )?;
+    Ok(())
+}
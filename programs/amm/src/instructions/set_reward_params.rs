@@ -2,7 +2,10 @@ use crate::error::ErrorCode;
 use crate::libraries::{fixed_point_64, full_math::MulDiv, U256};
 use crate::states::pool::{reward_period_limit, PoolState, REWARD_NUM};
 use crate::states::*;
-use crate::util::transfer_from_user_to_pool_vault;
+use crate::util::{
+    check_remaining_accounts_min, transfer_from_user_to_pool_vault, AccountLoad,
+    SET_REWARD_PARAMS_REMAINING_ACCOUNTS,
+};
 use anchor_lang::prelude::*;
 use anchor_spl::token::Token;
 use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
@@ -15,7 +18,7 @@ pub struct SetRewardParams<'info> {
     #[account(
         address = pool_state.load()?.amm_config
     )]
-    pub amm_config: Account<'info, AmmConfig>,
+    pub amm_config: Box<Account<'info, AmmConfig>>,
 
     #[account(
         mut,
@@ -45,7 +48,13 @@ pub fn set_reward_params<'a, 'b, 'c: 'info, 'info>(
     open_time: u64,
     end_time: u64,
 ) -> Result<()> {
-    assert!((reward_index as usize) < REWARD_NUM);
+    require_gt!(
+        REWARD_NUM + EXTENSION_REWARD_NUM,
+        usize::from(reward_index),
+        ErrorCode::InvalidRewardIndex
+    );
+    // the rewards from `REWARD_NUM` are in the reward info extension, passed as the first remaining account
+    let extension_index = usize::from(reward_index).checked_sub(REWARD_NUM);
     require_gt!(end_time, open_time);
     require_gt!(emissions_per_second_x64, 0);
     let operation_state = ctx.accounts.operation_state.load()?;
@@ -64,7 +73,32 @@ pub fn set_reward_params<'a, 'b, 'c: 'info, 'info>(
 
     pool_state.update_reward_infos(current_timestamp)?;
 
-    let mut reward_info = pool_state.reward_infos[reward_index as usize];
+    let mut remaining_accounts = ctx.remaining_accounts.iter();
+    let mut reward_info_extension = match extension_index {
+        Some(_) => {
+            // the extension rewards are controled by the admin
+            require!(admin_operator, ErrorCode::NotApproved);
+            let reward_info_extension_info = remaining_accounts
+                .next()
+                .ok_or(ErrorCode::MissingRewardInfoExtension)?;
+            require_keys_eq!(
+                reward_info_extension_info.key(),
+                RewardInfoExtension::key(ctx.accounts.pool_state.key())
+            );
+            let mut reward_info_extension =
+                AccountLoad::<RewardInfoExtension>::load_data_mut(reward_info_extension_info)?;
+            reward_info_extension.update_reward_infos(current_timestamp, pool_state.liquidity)?;
+            Some(reward_info_extension)
+        }
+        None => None,
+    };
+
+    let mut reward_info = match (extension_index, &reward_info_extension) {
+        (Some(extension_index), Some(reward_info_extension)) => {
+            reward_info_extension.reward_infos[extension_index]
+        }
+        _ => pool_state.reward_infos[reward_index as usize],
+    };
     if !reward_info.initialized() {
         return err!(ErrorCode::UnInitializedRewardInfo);
     }
@@ -92,10 +126,18 @@ pub fn set_reward_params<'a, 'b, 'c: 'info, 'info>(
         .unwrap()
     };
 
-    pool_state.reward_infos[reward_index as usize] = reward_info;
+    match (extension_index, reward_info_extension.as_deref_mut()) {
+        (Some(extension_index), Some(reward_info_extension)) => {
+            reward_info_extension.reward_infos[extension_index] = reward_info
+        }
+        _ => pool_state.reward_infos[reward_index as usize] = reward_info,
+    }
 
     if reward_amount > 0 {
-        let mut remaining_accounts = ctx.remaining_accounts.iter();
+        check_remaining_accounts_min(
+            remaining_accounts.as_slice(),
+            SET_REWARD_PARAMS_REMAINING_ACCOUNTS,
+        )?;
 
         let reward_token_vault =
             InterfaceAccount::<TokenAccount>::try_from(&remaining_accounts.next().unwrap())?;
@@ -114,6 +156,7 @@ pub fn set_reward_params<'a, 'b, 'c: 'info, 'info>(
             Some(Box::new(reward_vault_mint)),
             &ctx.accounts.token_program,
             Some(ctx.accounts.token_program_2022.to_account_info()),
+            &[],
             reward_amount,
         )?;
     }
@@ -0,0 +1,88 @@
+use crate::emit_event;
+use crate::error::ErrorCode;
+use crate::states::pool::{PoolState, RewardVesting, REWARD_NUM};
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct SetRewardVesting<'info> {
+    /// The pool owner or an operation owner
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// load info from the account to judge reward permission
+    #[account(
+        seeds = [
+            OPERATION_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub operation_state: AccountLoader<'info, OperationState>,
+}
+
+pub fn set_reward_vesting(
+    ctx: Context<SetRewardVesting>,
+    reward_index: u8,
+    cliff_duration: u64,
+    vesting_duration: u64,
+) -> Result<()> {
+    require_gt!(REWARD_NUM, reward_index as usize);
+    require!(
+        cliff_duration <= vesting_duration,
+        ErrorCode::InvalidRewardVesting
+    );
+    let operation_state = ctx.accounts.operation_state.load()?;
+    let admin_operator = operation_state
+        .operation_owners
+        .contains(&ctx.accounts.authority.key())
+        && ctx.accounts.authority.key() != Pubkey::default();
+
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    if !admin_operator {
+        require_keys_eq!(ctx.accounts.authority.key(), pool_state.owner);
+    }
+
+    let reward_info = pool_state.reward_infos[reward_index as usize];
+    if !reward_info.initialized() {
+        return err!(ErrorCode::UnInitializedRewardInfo);
+    }
+    // positions must not have earned any of the reward, or the vesting would lock rewards already claimable
+    let current_timestamp = u64::try_from(Clock::get()?.unix_timestamp).unwrap();
+    require_gt!(reward_info.open_time, current_timestamp);
+
+    pool_state.reward_vestings[reward_index as usize] = RewardVesting {
+        cliff_duration,
+        vesting_duration,
+    };
+    emit_event!(
+        ctx,
+        SetRewardVestingEvent {
+            pool_state: ctx.accounts.pool_state.key(),
+            reward_index,
+            cliff_duration,
+            vesting_duration,
+        }
+    )?;
+    Ok(())
+}
+
+/// Emitted when the vesting schedule of a reward is set
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct SetRewardVestingEvent {
+    /// The pool of the reward
+    #[index]
+    pub pool_state: Pubkey,
+
+    /// The index of the reward in the pool
+    pub reward_index: u8,
+
+    /// The seconds before any reward is claimable
+    pub cliff_duration: u64,
+
+    /// The seconds for the rewards to be fully claimable, 0 disables the vesting
+    pub vesting_duration: u64,
+}
@@ -8,10 +8,9 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount};
 use std::cell::RefMut;
 use std::collections::VecDeque;
-#[cfg(feature = "enable-log")]
-use std::convert::identity;
 use std::ops::{Deref, Neg};
 
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 pub struct SwapSingle<'info> {
     /// The user performing the swap
@@ -82,6 +81,9 @@ pub struct SwapAccounts<'b, 'info> {
 
     /// The program account for the oracle observation
     pub observation_state: &'b mut AccountLoader<'info, ObservationState>,
+
+    /// The event authority to emit the swap events
+    pub event_authority: EventAuthority<'b, 'info>,
 }
 
 // the top level state of the swap, the results of which are recorded in storage at the end
@@ -103,6 +105,8 @@ pub struct SwapState {
     pub protocol_fee: u64,
     // amount of input token paid as fund fee
     pub fund_fee: u64,
+    /// amount of input token paid as creator fee
+    pub creator_fee: u64,
     // the current liquidity in range
     pub liquidity: u128,
 }
@@ -125,20 +129,36 @@ struct StepComputations {
     fee_amount: u64,
 }
 
+/// Takes the tick array of the pool starting at `start_tick_index` out of the supplied tick arrays,
+/// which can be passed in any order and include arrays the swap never reaches
+fn take_tick_array<'a>(
+    tick_array_states: &mut VecDeque<RefMut<'a, TickArrayState>>,
+    pool_id: Pubkey,
+    start_tick_index: i32,
+) -> Option<RefMut<'a, TickArrayState>> {
+    let index = tick_array_states.iter().position(|tick_array| {
+        tick_array.pool_id == pool_id && tick_array.start_tick_index == start_tick_index
+    })?;
+    tick_array_states.remove(index)
+}
+
 pub fn swap_internal<'b, 'info>(
     amm_config: &AmmConfig,
     pool_state: &mut RefMut<PoolState>,
     tick_array_states: &mut VecDeque<RefMut<TickArrayState>>,
-    observation_state: &mut RefMut<ObservationState>,
+    observation_state: &mut impl ObservationWriter,
     tickarray_bitmap_extension: &Option<TickArrayBitmapExtension>,
+    reward_info_extension: Option<&mut RewardInfoExtension>,
     amount_specified: u64,
     sqrt_price_limit_x64: u128,
     zero_for_one: bool,
     is_base_input: bool,
     block_timestamp: u32,
+    event_authority: Option<EventAuthority>,
+    pool_stats: Option<&mut PoolStatsState>,
 ) -> Result<(u64, u64)> {
     require!(amount_specified != 0, ErrorCode::ZeroAmountSpecified);
-    if !pool_state.get_status_by_bit(PoolStatusBitIndex::Swap) {
+    if !pool_state.is_swap_enabled(zero_for_one) {
         return err!(ErrorCode::NotApproved);
     }
     require!(
@@ -151,10 +171,23 @@ pub fn swap_internal<'b, 'info>(
         },
         ErrorCode::SqrtPriceLimitOverflow
     );
+    let sqrt_price_limit_x64 =
+        pool_state.clamp_sqrt_price_limit(zero_for_one, sqrt_price_limit_x64)?;
 
     let liquidity_start = pool_state.liquidity;
 
     let updated_reward_infos = pool_state.update_reward_infos(block_timestamp as u64)?;
+    // the reward info extension is optional, the active liquidity only changes when a tick is crossed,
+    // so it is only required by the swaps crossing a tick once any extension reward is initialized
+    let updated_extension_reward_infos = match reward_info_extension {
+        Some(reward_info_extension) => Some(
+            reward_info_extension.update_reward_infos(block_timestamp as u64, liquidity_start)?,
+        ),
+        None => None,
+    };
+    pool_state.update_liquidity_seconds(u64::from(block_timestamp));
+    let trade_fee_rate =
+        pool_state.swap_trade_fee_rate(amm_config, zero_for_one, u64::from(block_timestamp));
 
     let mut state = SwapState {
         amount_specified_remaining: amount_specified,
@@ -169,34 +202,31 @@ pub fn swap_internal<'b, 'info>(
         fee_amount: 0,
         protocol_fee: 0,
         fund_fee: 0,
+        creator_fee: 0,
         liquidity: liquidity_start,
     };
 
+    // derive the pool key once, it is checked against every tick array of the swap
+    let pool_id = pool_state.key();
+    let tick_spacing = pool_state.tick_spacing;
+    let recent_epoch = get_recent_epoch()?;
+    // the deltas of the crossed ticks of the current tick array, emitted once the swap leaves the array
+    let mut tick_deltas: Vec<TickDelta> = Vec::new();
+
     // check observation account is owned by the pool
-    require_keys_eq!(observation_state.pool_id, pool_state.key());
+    require_keys_eq!(observation_state.pool_id, pool_id);
 
     let (mut is_match_pool_current_tick_array, first_vaild_tick_array_start_index) =
         pool_state.get_first_initialized_tick_array(&tickarray_bitmap_extension, zero_for_one)?;
     let mut current_vaild_tick_array_start_index = first_vaild_tick_array_start_index;
 
-    let mut tick_array_current = tick_array_states.pop_front().unwrap();
-    // find the first active tick array account
-    for _ in 0..tick_array_states.len() {
-        if tick_array_current.start_tick_index == current_vaild_tick_array_start_index {
-            break;
-        }
-        tick_array_current = tick_array_states
-            .pop_front()
-            .ok_or(ErrorCode::NotEnoughTickArrayAccount)?;
-    }
-    // check the first tick_array account is owned by the pool
-    require_keys_eq!(tick_array_current.pool_id, pool_state.key());
-    // check first tick array account is correct
-    require_eq!(
-        tick_array_current.start_tick_index,
+    // find the first active tick array account among the supplied ones
+    let mut tick_array_current = take_tick_array(
+        tick_array_states,
+        pool_id,
         current_vaild_tick_array_start_index,
-        ErrorCode::InvalidFirstTickArrayAccount
-    );
+    )
+    .ok_or(ErrorCode::InvalidFirstTickArrayAccount)?;
 
     // continue swapping as long as we haven't used the entire input/output and haven't
     // reached the price limit
@@ -220,26 +250,30 @@ pub fn swap_internal<'b, 'info>(
         let mut step = StepComputations::default();
         step.sqrt_price_start_x64 = state.sqrt_price_x64;
 
-        let mut next_initialized_tick = if let Some(tick_state) = tick_array_current
-            .next_initialized_tick(state.tick, pool_state.tick_spacing, zero_for_one)?
-        {
-            Box::new(*tick_state)
-        } else {
-            if !is_match_pool_current_tick_array {
+        // only the index of the next initialized tick is kept, the tick is crossed in place
+        let mut next_initialized_tick = match tick_array_current.next_initialized_tick(
+            state.tick,
+            tick_spacing,
+            zero_for_one,
+        )? {
+            Some(tick_state) => Some(tick_state.tick),
+            None if !is_match_pool_current_tick_array => {
                 is_match_pool_current_tick_array = true;
-                Box::new(*tick_array_current.first_initialized_tick(zero_for_one)?)
-            } else {
-                Box::new(TickState::default())
+                Some(
+                    tick_array_current
+                        .first_initialized_tick(zero_for_one)?
+                        .tick,
+                )
             }
+            None => None,
         };
         #[cfg(feature = "enable-log")]
         msg!(
-            "next_initialized_tick, status:{}, tick_index:{}, tick_array_current:{}",
-            next_initialized_tick.is_initialized(),
-            identity(next_initialized_tick.tick),
+            "next_initialized_tick:{:?}, tick_array_current:{}",
+            next_initialized_tick,
             tick_array_current.key().to_string(),
         );
-        if !next_initialized_tick.is_initialized() {
+        if next_initialized_tick.is_none() {
             let next_initialized_tickarray_index = pool_state
                 .next_initialized_tick_array_start_index(
                     &tickarray_bitmap_extension,
@@ -250,20 +284,27 @@ pub fn swap_internal<'b, 'info>(
                 return err!(ErrorCode::LiquidityInsufficient);
             }
 
-            while tick_array_current.start_tick_index != next_initialized_tickarray_index.unwrap() {
-                tick_array_current = tick_array_states
-                    .pop_front()
-                    .ok_or(ErrorCode::NotEnoughTickArrayAccount)?;
-                // check the tick_array account is owned by the pool
-                require_keys_eq!(tick_array_current.pool_id, pool_state.key());
-            }
+            emit_tick_deltas(
+                pool_id,
+                tick_array_current.start_tick_index,
+                &mut tick_deltas,
+                event_authority,
+            )?;
+            // jump to the next initialized tick array, the empty ones in between are not needed
             current_vaild_tick_array_start_index = next_initialized_tickarray_index.unwrap();
+            tick_array_current = take_tick_array(
+                tick_array_states,
+                pool_id,
+                current_vaild_tick_array_start_index,
+            )
+            .ok_or(ErrorCode::NotEnoughTickArrayAccount)?;
 
             let first_initialized_tick = tick_array_current.first_initialized_tick(zero_for_one)?;
-            next_initialized_tick = Box::new(*first_initialized_tick);
+            next_initialized_tick = Some(first_initialized_tick.tick);
         }
-        step.tick_next = next_initialized_tick.tick;
-        step.initialized = next_initialized_tick.is_initialized();
+        // the ticks returned by the tick array are always initialized
+        step.tick_next = next_initialized_tick.unwrap();
+        step.initialized = true;
 
         if step.tick_next < tick_math::MIN_TICK {
             step.tick_next = tick_math::MIN_TICK;
@@ -302,7 +343,7 @@ pub fn swap_internal<'b, 'info>(
             target_price,
             state.liquidity,
             state.amount_specified_remaining,
-            amm_config.trade_fee_rate,
+            trade_fee_rate,
             is_base_input,
             zero_for_one,
             block_timestamp,
@@ -344,7 +385,17 @@ pub fn swap_internal<'b, 'info>(
                 .ok_or(ErrorCode::CalculateOverflow)?;
         }
 
-        let step_fee_amount = step.fee_amount;
+        // the escalated part of the trade fee belongs to the liquidity providers only
+        let step_fee_amount = if trade_fee_rate > amm_config.trade_fee_rate {
+            U128::from(step.fee_amount)
+                .checked_mul(amm_config.trade_fee_rate.into())
+                .unwrap()
+                .checked_div(trade_fee_rate.into())
+                .unwrap()
+                .as_u64()
+        } else {
+            step.fee_amount
+        };
         // if the protocol fee is on, calculate how much is owed, decrement fee_amount, and increment protocol_fee
         if amm_config.protocol_fee_rate > 0 {
             let delta = U128::from(step_fee_amount)
@@ -367,6 +418,18 @@ pub fn swap_internal<'b, 'info>(
             step.fee_amount = step.fee_amount.checked_sub(delta).unwrap();
             state.fund_fee = state.fund_fee.checked_add(delta).unwrap();
         }
+        // if the creator fee is on, calculate how much is owed, decrement fee_amount, and increment creator_fee
+        if pool_state.creator_fee_rate > 0 {
+            let delta = U128::from(step_fee_amount)
+                .checked_mul(pool_state.creator_fee_rate.into())
+                .unwrap()
+                .checked_div(FEE_RATE_DENOMINATOR_VALUE.into())
+                .unwrap()
+                .as_u64()
+                .min(step.fee_amount);
+            step.fee_amount = step.fee_amount.checked_sub(delta).unwrap();
+            state.creator_fee = state.creator_fee.checked_add(delta).unwrap();
+        }
 
         // update global fee tracker
         if state.liquidity > 0 {
@@ -394,7 +457,11 @@ pub fn swap_internal<'b, 'info>(
                 #[cfg(feature = "enable-log")]
                 msg!("loading next tick {}", step.tick_next);
 
-                let mut liquidity_net = next_initialized_tick.cross(
+                let tick_state =
+                    tick_array_current.get_tick_state_mut(step.tick_next, tick_spacing)?;
+                // the tick before crossing is only needed for the delta event
+                let tick_state_before = event_authority.map(|_| *tick_state);
+                let mut liquidity_net = tick_state.cross(
                     if zero_for_one {
                         state.fee_growth_global_x64
                     } else {
@@ -406,13 +473,23 @@ pub fn swap_internal<'b, 'info>(
                         state.fee_growth_global_x64
                     },
                     &updated_reward_infos,
+                    u64::from(block_timestamp),
                 );
-                // update tick_state to tick_array account
-                tick_array_current.update_tick_state(
-                    next_initialized_tick.tick,
-                    pool_state.tick_spacing.into(),
-                    *next_initialized_tick,
-                )?;
+                match &updated_extension_reward_infos {
+                    Some(extension_reward_infos) => {
+                        tick_state.cross_extension_rewards(extension_reward_infos)
+                    }
+                    None => require!(
+                        pool_state.extension_reward_count == 0,
+                        ErrorCode::MissingRewardInfoExtension
+                    ),
+                }
+                if let Some(delta) = tick_state_before
+                    .and_then(|tick_state_before| TickDelta::diff(&tick_state_before, tick_state))
+                {
+                    tick_deltas.push(delta);
+                }
+                tick_array_current.recent_epoch = recent_epoch;
 
                 if zero_for_one {
                     liquidity_net = liquidity_net.neg();
@@ -460,12 +537,20 @@ pub fn swap_internal<'b, 'info>(
         //     zero_for_one,
         // });
     }
-    // update tick
-    if state.tick != pool_state.tick_current {
-        // update the previous tick to the observation
-        observation_state.update(block_timestamp, pool_state.tick_current);
-        pool_state.tick_current = state.tick;
-    }
+    emit_tick_deltas(
+        pool_id,
+        tick_array_current.start_tick_index,
+        &mut tick_deltas,
+        event_authority,
+    )?;
+    // accumulate the time since the last observation with the previous tick and liquidity,
+    // the liquidity may have changed without the tick moving
+    observation_state.write(
+        block_timestamp,
+        pool_state.tick_current,
+        pool_state.liquidity,
+    );
+    pool_state.tick_current = state.tick;
     pool_state.sqrt_price_x64 = state.sqrt_price_x64;
 
     if liquidity_start != state.liquidity {
@@ -487,6 +572,7 @@ pub fn swap_internal<'b, 'info>(
                 .unwrap(),
         )
     };
+    pool_state.add_slot_swap_volume(zero_for_one, if zero_for_one { amount_0 } else { amount_1 });
 
     if zero_for_one {
         pool_state.fee_growth_global_0_x64 = state.fee_growth_global_x64;
@@ -507,6 +593,12 @@ pub fn swap_internal<'b, 'info>(
                 .checked_add(state.fund_fee)
                 .unwrap();
         }
+        if state.creator_fee > 0 {
+            pool_state.creator_fees_token_0 = pool_state
+                .creator_fees_token_0
+                .checked_add(state.creator_fee)
+                .unwrap();
+        }
         pool_state.swap_in_amount_token_0 = pool_state
             .swap_in_amount_token_0
             .checked_add(u128::from(amount_0))
@@ -534,6 +626,12 @@ pub fn swap_internal<'b, 'info>(
                 .checked_add(state.fund_fee)
                 .unwrap();
         }
+        if state.creator_fee > 0 {
+            pool_state.creator_fees_token_1 = pool_state
+                .creator_fees_token_1
+                .checked_add(state.creator_fee)
+                .unwrap();
+        }
         pool_state.swap_in_amount_token_1 = pool_state
             .swap_in_amount_token_1
             .checked_add(u128::from(amount_1))
@@ -544,9 +642,38 @@ pub fn swap_internal<'b, 'info>(
             .unwrap();
     }
 
+    if let Some(pool_stats) = pool_stats {
+        pool_stats.record_swap(
+            u64::from(block_timestamp),
+            zero_for_one,
+            amount_0,
+            amount_1,
+            state.fee_amount + state.protocol_fee + state.fund_fee + state.creator_fee,
+        );
+    }
+
     Ok((amount_0, amount_1))
 }
 
+/// Emits the deltas of the ticks crossed in a tick array with a single event
+fn emit_tick_deltas(
+    pool_id: Pubkey,
+    start_tick_index: i32,
+    tick_deltas: &mut Vec<TickDelta>,
+    event_authority: Option<EventAuthority>,
+) -> Result<()> {
+    if let Some(event_authority) = event_authority {
+        if !tick_deltas.is_empty() {
+            event_authority.emit(TickArrayDeltaEvent {
+                pool_state: pool_id,
+                start_tick_index,
+                deltas: std::mem::take(tick_deltas),
+            })?;
+        }
+    }
+    Ok(())
+}
+
 /// Performs a single exact input/output swap
 /// if is_base_input = true, return vaule is the max_amount_out, otherwise is min_amount_in
 pub fn exact_internal<'b, 'c: 'info, 'info>(
@@ -562,6 +689,12 @@ pub fn exact_internal<'b, 'c: 'info, 'info>(
     let amount_1;
     let zero_for_one;
     let swap_price_before;
+    let trade_fee;
+    let protocol_fee;
+    let fund_fee;
+    let creator_fee;
+    let observation_index;
+    let observation_timestamp;
 
     let input_balance_before = ctx.input_vault.amount;
     let output_balance_before = ctx.output_vault.amount;
@@ -572,6 +705,7 @@ pub fn exact_internal<'b, 'c: 'info, 'info>(
         zero_for_one = ctx.input_vault.mint == pool_state.token_mint_0;
 
         require_gt!(block_timestamp, pool_state.open_time);
+        pool_state.update_swap_slot(solana_program::clock::Clock::get()?.slot);
 
         require!(
             if zero_for_one {
@@ -588,6 +722,21 @@ pub fn exact_internal<'b, 'c: 'info, 'info>(
         let tick_array_states = &mut VecDeque::new();
         tick_array_states.push_back(ctx.tick_array_state.load_mut()?);
 
+        let reward_info_extension_info = find_swap_reward_extension_account(
+            remaining_accounts,
+            ctx.pool_state.key(),
+            pool_state,
+        );
+        let mut reward_info_extension = reward_info_extension_info
+            .map(|account_info| AccountLoad::<RewardInfoExtension>::load_data_mut(account_info))
+            .transpose()?;
+        let pool_stats_info = find_pool_stats_account(remaining_accounts, ctx.pool_state.key());
+        let trader_stats_info =
+            find_trader_stats_account(remaining_accounts, ctx.pool_state.key(), ctx.signer.key());
+        let mut pool_stats = pool_stats_info
+            .map(|account_info| AccountLoad::<PoolStatsState>::load_data_mut(account_info))
+            .transpose()?;
+
         let tick_array_bitmap_extension_key = TickArrayBitmapExtension::key(pool_state.key());
         for account_info in remaining_accounts.into_iter() {
             if account_info.key().eq(&tick_array_bitmap_extension_key) {
@@ -598,15 +747,24 @@ pub fn exact_internal<'b, 'c: 'info, 'info>(
                 );
                 continue;
             }
+            if reward_info_extension_info.is_some_and(|info| info.key == account_info.key)
+                || pool_stats_info.is_some_and(|info| info.key == account_info.key)
+                || trader_stats_info.is_some_and(|info| info.key == account_info.key)
+            {
+                continue;
+            }
             tick_array_states.push_back(AccountLoad::load_data_mut(account_info)?);
         }
 
+        let fees_before = pool_state.accumulated_swap_fees(zero_for_one);
+        let mut observation_state = ObservationStateMut::load_mut(ctx.observation_state.as_ref())?;
         (amount_0, amount_1) = swap_internal(
             &ctx.amm_config,
             pool_state,
             tick_array_states,
-            &mut ctx.observation_state.load_mut()?,
+            &mut observation_state,
             &tickarray_bitmap_extension,
+            reward_info_extension.as_deref_mut(),
             amount_specified,
             if sqrt_price_limit_x64 == 0 {
                 if zero_for_one {
@@ -620,7 +778,16 @@ pub fn exact_internal<'b, 'c: 'info, 'info>(
             zero_for_one,
             is_base_input,
             oracle::block_timestamp(),
+            Some(ctx.event_authority),
+            pool_stats.as_deref_mut(),
         )?;
+        (observation_index, observation_timestamp) =
+            observation_state.latest_observation(&observation_state.extension);
+        let fees_after = pool_state.accumulated_swap_fees(zero_for_one);
+        protocol_fee = fees_after.1 - fees_before.1;
+        fund_fee = fees_after.2 - fees_before.2;
+        creator_fee = fees_after.3 - fees_before.3;
+        trade_fee = fees_after.0 - fees_before.0 + protocol_fee + fund_fee + creator_fee;
 
         #[cfg(feature = "enable-log")]
         msg!(
@@ -633,6 +800,19 @@ pub fn exact_internal<'b, 'c: 'info, 'info>(
             amount_0 != 0 && amount_1 != 0,
             ErrorCode::TooSmallInputOrOutputAmount
         );
+        pool_state.check_anti_bot_amount_out(
+            if zero_for_one { amount_1 } else { amount_0 },
+            block_timestamp,
+        )?;
+        if let Some(trader_stats_info) = trader_stats_info {
+            AccountLoad::<TraderStatsState>::load_data_mut(trader_stats_info)?.record_swap(
+                block_timestamp,
+                zero_for_one,
+                amount_0,
+                amount_1,
+                trade_fee,
+            );
+        }
     }
     let (token_account_0, token_account_1, vault_0, vault_1) = if zero_for_one {
         (
@@ -659,6 +839,7 @@ pub fn exact_internal<'b, 'c: 'info, 'info>(
             None,
             &ctx.token_program,
             None,
+            &[],
             amount_0,
         )?;
         if vault_1.amount <= amount_1 {
@@ -673,6 +854,7 @@ pub fn exact_internal<'b, 'c: 'info, 'info>(
             None,
             &ctx.token_program,
             None,
+            &[],
             amount_1,
         )?;
     } else {
@@ -683,6 +865,7 @@ pub fn exact_internal<'b, 'c: 'info, 'info>(
             None,
             &ctx.token_program,
             None,
+            &[],
             amount_1,
         )?;
         if vault_0.amount <= amount_0 {
@@ -696,6 +879,7 @@ pub fn exact_internal<'b, 'c: 'info, 'info>(
             None,
             &ctx.token_program,
             None,
+            &[],
             amount_0,
         )?;
     }
@@ -703,7 +887,7 @@ pub fn exact_internal<'b, 'c: 'info, 'info>(
     ctx.input_vault.reload()?;
 
     let pool_state = ctx.pool_state.load()?;
-    emit!(SwapEvent {
+    let swap_event = SwapEvent {
         pool_state: pool_state.key(),
         sender: ctx.signer.key(),
         token_account_0: token_account_0.key(),
@@ -715,8 +899,16 @@ pub fn exact_internal<'b, 'c: 'info, 'info>(
         zero_for_one,
         sqrt_price_x64: pool_state.sqrt_price_x64,
         liquidity: pool_state.liquidity,
-        tick: pool_state.tick_current
-    });
+        tick: pool_state.tick_current,
+        version: SWAP_EVENT_VERSION,
+        trade_fee,
+        protocol_fee,
+        fund_fee,
+        creator_fee,
+        observation_index,
+        observation_timestamp,
+    };
+    ctx.event_authority.emit(swap_event)?;
     if zero_for_one {
         require_gt!(swap_price_before, pool_state.sqrt_price_x64);
     } else {
@@ -759,6 +951,7 @@ pub fn swap<'a, 'b, 'c: 'info, 'info>(
     sqrt_price_limit_x64: u128,
     is_base_input: bool,
 ) -> Result<()> {
+    check_remaining_accounts_max(ctx.remaining_accounts, MAX_SWAP_REMAINING_ACCOUNTS)?;
     let amount = exact_internal(
         &mut SwapAccounts {
             signer: ctx.accounts.payer.clone(),
@@ -771,6 +964,7 @@ pub fn swap<'a, 'b, 'c: 'info, 'info>(
             pool_state: &mut ctx.accounts.pool_state,
             tick_array_state: &mut ctx.accounts.tick_array,
             observation_state: &mut ctx.accounts.observation_state,
+            event_authority: crate::event_authority!(ctx.accounts, ctx.bumps),
         },
         ctx.remaining_accounts,
         amount,
@@ -957,6 +1151,7 @@ mod swap_test {
                             0,
                             false,
                             &[RewardInfo::default(); 3],
+                            0,
                         )
                         .unwrap();
 
@@ -978,6 +1173,7 @@ mod swap_test {
                             0,
                             false,
                             &[RewardInfo::default(); 3],
+                            0,
                         )
                         .unwrap();
                 }
@@ -1005,6 +1201,7 @@ mod swap_test {
                             0,
                             true,
                             &[RewardInfo::default(); 3],
+                            0,
                         )
                         .unwrap();
 
@@ -1027,6 +1224,7 @@ mod swap_test {
                             0,
                             true,
                             &[RewardInfo::default(); 3],
+                            0,
                         )
                         .unwrap();
                 }
@@ -1121,11 +1319,14 @@ mod swap_test {
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
                 &None,
+                None,
                 12188240002,
                 3049500711113990606,
                 true,
                 true,
                 oracle::block_timestamp_mock() as u32,
+                None,
+                None,
             )
             .unwrap();
             println!("amount_0:{},amount_1:{}", amount_0, amount_1);
@@ -1152,11 +1353,14 @@ mod swap_test {
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
                 &None,
+                None,
                 121882400020,
                 3049500711113990606,
                 true,
                 true,
                 oracle::block_timestamp_mock() as u32,
+                None,
+                None,
             )
             .unwrap();
             println!("amount_0:{},amount_1:{}", amount_0, amount_1);
@@ -1180,11 +1384,14 @@ mod swap_test {
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
                 &None,
+                None,
                 60941200010,
                 3049500711113990606,
                 true,
                 true,
                 oracle::block_timestamp_mock() as u32,
+                None,
+                None,
             )
             .unwrap();
             println!("amount_0:{},amount_1:{}", amount_0, amount_1);
@@ -1237,11 +1444,14 @@ mod swap_test {
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
                 &None,
+                None,
                 477470480,
                 3049500711113990606,
                 true,
                 false,
                 oracle::block_timestamp_mock() as u32,
+                None,
+                None,
             )
             .unwrap();
             println!("amount_0:{},amount_1:{}", amount_0, amount_1);
@@ -1268,11 +1478,14 @@ mod swap_test {
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
                 &None,
+                None,
                 4751002622,
                 3049500711113990606,
                 true,
                 false,
                 oracle::block_timestamp_mock() as u32,
+                None,
+                None,
             )
             .unwrap();
             println!("amount_0:{},amount_1:{}", amount_0, amount_1);
@@ -1296,11 +1509,14 @@ mod swap_test {
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
                 &None,
+                None,
                 2358130642,
                 3049500711113990606,
                 true,
                 false,
                 oracle::block_timestamp_mock() as u32,
+                None,
+                None,
             )
             .unwrap();
             println!("amount_0:{},amount_1:{}", amount_0, amount_1);
@@ -1353,11 +1569,14 @@ mod swap_test {
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
                 &None,
+                None,
                 887470480,
                 5882283448660210779,
                 false,
                 true,
                 oracle::block_timestamp_mock() as u32,
+                None,
+                None,
             )
             .unwrap();
             println!("amount_0:{},amount_1:{}", amount_0, amount_1);
@@ -1383,11 +1602,14 @@ mod swap_test {
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
                 &None,
+                None,
                 3087470480,
                 5882283448660210779,
                 false,
                 true,
                 oracle::block_timestamp_mock() as u32,
+                None,
+                None,
             )
             .unwrap();
             println!("amount_0:{},amount_1:{}", amount_0, amount_1);
@@ -1412,11 +1634,14 @@ mod swap_test {
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
                 &None,
+                None,
                 200941200010,
                 5882283448660210779,
                 false,
                 true,
                 oracle::block_timestamp_mock() as u32,
+                None,
+                None,
             )
             .unwrap();
             println!("amount_0:{},amount_1:{}", amount_0, amount_1);
@@ -1469,11 +1694,14 @@ mod swap_test {
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
                 &None,
+                None,
                 22796232052,
                 5882283448660210779,
                 false,
                 false,
                 oracle::block_timestamp_mock() as u32,
+                None,
+                None,
             )
             .unwrap();
             println!("amount_0:{},amount_1:{}", amount_0, amount_1);
@@ -1499,11 +1727,14 @@ mod swap_test {
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
                 &None,
+                None,
                 79023558189,
                 5882283448660210779,
                 false,
                 false,
                 oracle::block_timestamp_mock() as u32,
+                None,
+                None,
             )
             .unwrap();
             println!("amount_0:{},amount_1:{}", amount_0, amount_1);
@@ -1528,11 +1759,14 @@ mod swap_test {
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
                 &None,
+                None,
                 4315086194758,
                 5882283448660210779,
                 false,
                 false,
                 oracle::block_timestamp_mock() as u32,
+                None,
+                None,
             )
             .unwrap();
             println!("amount_0:{},amount_1:{}", amount_0, amount_1);
@@ -1545,6 +1779,74 @@ mod swap_test {
             assert!(pool_state.borrow().liquidity == (liquidity - 1330680689));
             assert!(amount_0 == 4315086194758);
         }
+
+        #[test]
+        fn unordered_tick_arrays_test() {
+            let tick_arrays = || {
+                vec![
+                    TickArrayInfo {
+                        start_tick_index: -32400,
+                        ticks: vec![
+                            build_tick(-32400, 277065331032, -277065331032).take(),
+                            build_tick(-29220, 1330680689, -1330680689).take(),
+                        ],
+                    },
+                    TickArrayInfo {
+                        start_tick_index: -36000,
+                        ticks: vec![
+                            build_tick(-32460, 1194569667438, 536061033698).take(),
+                            build_tick(-32520, 790917615645, 790917615645).take(),
+                        ],
+                    },
+                    TickArrayInfo {
+                        start_tick_index: -28800,
+                        ticks: vec![build_tick(-28740, 6408486554, -6408486554).take()],
+                    },
+                ]
+            };
+            let swap = |tick_array_infos: Vec<TickArrayInfo>| {
+                let (amm_config, pool_state, tick_array_states, observation_state) =
+                    build_swap_param(
+                        -32395,
+                        60,
+                        3651942632306380802,
+                        5124165121219,
+                        tick_array_infos,
+                    );
+                let (amount_0, amount_1) = swap_internal(
+                    &amm_config,
+                    &mut pool_state.borrow_mut(),
+                    &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
+                    &mut observation_state.borrow_mut(),
+                    &None,
+                    None,
+                    134070640022,
+                    3049500711113990606,
+                    true,
+                    true,
+                    oracle::block_timestamp_mock() as u32,
+                    None,
+                    None,
+                )
+                .unwrap();
+                let pool_state = pool_state.borrow();
+                (
+                    amount_0,
+                    amount_1,
+                    pool_state.tick_current,
+                    pool_state.liquidity,
+                )
+            };
+
+            // the tick arrays crossed in order, followed by an array the swap never reaches
+            let ordered = swap(tick_arrays());
+            assert!(ordered.2 > -32520 && ordered.2 < -32460);
+
+            // the same tick arrays in reverse order
+            let mut reversed_tick_arrays = tick_arrays();
+            reversed_tick_arrays.reverse();
+            assert_eq!(swap(reversed_tick_arrays), ordered);
+        }
     }
 
     #[cfg(test)]
@@ -1578,11 +1880,14 @@ mod swap_test {
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
                 &None,
+                None,
                 12188240002,
                 tick_math::get_sqrt_price_at_tick(-32400).unwrap(),
                 true,
                 true,
                 oracle::block_timestamp_mock() as u32,
+                None,
+                None,
             )
             .unwrap();
             println!("amount_0:{},amount_1:{}", amount_0, amount_1);
@@ -1623,11 +1928,14 @@ mod swap_test {
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
                 &None,
+                None,
                 12188240002,
                 tick_math::get_sqrt_price_at_tick(-28860).unwrap(),
                 false,
                 true,
                 oracle::block_timestamp_mock() as u32,
+                None,
+                None,
             )
             .unwrap();
             println!("amount_0:{},amount_1:{}", amount_0, amount_1);
@@ -1668,11 +1976,14 @@ mod swap_test {
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
                 &None,
+                None,
                 12188240002,
                 tick_math::get_sqrt_price_at_tick(-32400).unwrap(),
                 true,
                 true,
                 oracle::block_timestamp_mock() as u32,
+                None,
+                None,
             );
             assert!(result.is_err());
             assert_eq!(
@@ -1709,11 +2020,14 @@ mod swap_test {
             &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
             &mut observation_state.borrow_mut(),
             &None,
+            None,
             25,
             tick_math::get_sqrt_price_at_tick(-32400).unwrap(),
             true,
             true,
             oracle::block_timestamp_mock() as u32,
+            None,
+            None,
         )
         .unwrap();
         println!("amount_0:{},amount_1:{}", amount_0, amount_1);
@@ -1732,11 +2046,14 @@ mod swap_test {
             &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
             &mut observation_state.borrow_mut(),
             &None,
+            None,
             3,
             tick_math::get_sqrt_price_at_tick(-32400).unwrap(),
             true,
             true,
             oracle::block_timestamp_mock() as u32,
+            None,
+            None,
         )
         .unwrap();
         println!("amount_0:{},amount_1:{}", amount_0, amount_1);
@@ -1758,11 +2075,14 @@ mod swap_test {
             &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
             &mut observation_state.borrow_mut(),
             &None,
+            None,
             50,
             tick_math::get_sqrt_price_at_tick(-32400).unwrap(),
             true,
             true,
             oracle::block_timestamp_mock() as u32,
+            None,
+            None,
         )
         .unwrap();
         println!("amount_0:{},amount_1:{}", amount_0, amount_1);
@@ -1811,11 +2131,14 @@ mod swap_test {
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
                 &None,
+                None,
                 27,
                 tick_math::get_sqrt_price_at_tick(-32400).unwrap(),
                 true,
                 true,
                 oracle::block_timestamp_mock() as u32,
+                None,
+                None,
             )
             .unwrap();
             println!("amount_0:{},amount_1:{}", amount_0, amount_1);
@@ -1838,11 +2161,14 @@ mod swap_test {
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
                 &None,
+                None,
                 1,
                 tick_math::get_sqrt_price_at_tick(-32400).unwrap(),
                 true,
                 true,
                 oracle::block_timestamp_mock() as u32,
+                None,
+                None,
             )
             .unwrap();
             println!("amount_0:{},amount_1:{}", amount_0, amount_1);
@@ -1863,11 +2189,14 @@ mod swap_test {
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
                 &None,
+                None,
                 10,
                 tick_math::get_sqrt_price_at_tick(-28800).unwrap(),
                 false,
                 true,
                 oracle::block_timestamp_mock() as u32,
+                None,
+                None,
             )
             .unwrap();
             println!("amount_0:{},amount_1:{}", amount_0, amount_1);
@@ -1880,6 +2209,154 @@ mod swap_test {
         }
     }
 
+    mod creator_fee_test {
+        use super::*;
+
+        #[test]
+        fn creator_fee_carved_out_of_trade_fee_test() {
+            let tick_current = -28859;
+            let liquidity = 1_000_000_000_000_000;
+            let sqrt_price_x64 = tick_math::get_sqrt_price_at_tick(tick_current).unwrap();
+            let (amm_config, pool_state, tick_array_states, observation_state) = build_swap_param(
+                tick_current,
+                60,
+                sqrt_price_x64,
+                liquidity,
+                vec![TickArrayInfo {
+                    start_tick_index: -32400,
+                    ticks: vec![build_tick(-32400, 277065331032, -277065331032).take()],
+                }],
+            );
+            let amm_config = AmmConfig {
+                max_creator_fee_rate: 100_000,
+                ..amm_config
+            };
+            pool_state
+                .borrow_mut()
+                .set_creator_fee_rate(&amm_config, 100_000)
+                .unwrap();
+
+            swap_internal(
+                &amm_config,
+                &mut pool_state.borrow_mut(),
+                &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
+                &mut observation_state.borrow_mut(),
+                &None,
+                None,
+                1_000_000,
+                tick_math::get_sqrt_price_at_tick(-32400).unwrap(),
+                true,
+                true,
+                oracle::block_timestamp_mock() as u32,
+                None,
+                None,
+            )
+            .unwrap();
+            let pool_state = pool_state.borrow();
+            let creator_fees_token_0 = pool_state.creator_fees_token_0;
+            let trade_fee = pool_state.total_fees_token_0 + creator_fees_token_0;
+            assert!(creator_fees_token_0 > 0);
+            assert_eq!(creator_fees_token_0, trade_fee / 10);
+            assert_eq!({ pool_state.creator_fees_token_1 }, 0);
+        }
+
+        #[test]
+        fn creator_fee_rate_exceeds_trade_fee_test() {
+            let amm_config = AmmConfig {
+                protocol_fee_rate: 120_000,
+                fund_fee_rate: 40_000,
+                max_creator_fee_rate: FEE_RATE_DENOMINATOR_VALUE,
+                ..Default::default()
+            };
+            let mut pool_state = PoolState::default();
+            pool_state
+                .set_creator_fee_rate(&amm_config, FEE_RATE_DENOMINATOR_VALUE - 160_000)
+                .unwrap();
+            assert_eq!(
+                pool_state
+                    .set_creator_fee_rate(&amm_config, FEE_RATE_DENOMINATOR_VALUE - 159_999)
+                    .unwrap_err(),
+                error!(ErrorCode::InvalidCreatorFeeRate)
+            );
+        }
+
+        #[test]
+        fn creator_fee_rate_exceeds_config_cap_test() {
+            let mut amm_config = AmmConfig::default();
+            let mut pool_state = PoolState::default();
+            // the pools of a config without a cap can't charge a creator fee
+            assert_eq!(
+                pool_state.set_creator_fee_rate(&amm_config, 1).unwrap_err(),
+                error!(ErrorCode::InvalidCreatorFeeRate)
+            );
+            amm_config.max_creator_fee_rate = 50_000;
+            pool_state
+                .set_creator_fee_rate(&amm_config, 50_000)
+                .unwrap();
+            assert_eq!(
+                pool_state
+                    .set_creator_fee_rate(&amm_config, 50_001)
+                    .unwrap_err(),
+                error!(ErrorCode::InvalidCreatorFeeRate)
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod observation_test {
+        use super::*;
+
+        #[test]
+        fn swap_without_tick_move_accumulates_seconds_per_liquidity_test() {
+            let tick_current = -28859;
+            let liquidity = 1_000_000_000_000_000;
+            // just below the next tick so that a small swap doesn't move the current tick
+            let sqrt_price_x64 = tick_math::get_sqrt_price_at_tick(tick_current + 1).unwrap() - 1;
+            let (amm_config, pool_state, tick_array_states, observation_state) = build_swap_param(
+                tick_current,
+                60,
+                sqrt_price_x64,
+                liquidity,
+                vec![TickArrayInfo {
+                    start_tick_index: -32400,
+                    ticks: vec![build_tick(-32400, 277065331032, -277065331032).take()],
+                }],
+            );
+            let block_timestamp = oracle::block_timestamp_mock() as u32;
+            for timestamp in [
+                block_timestamp,
+                block_timestamp + OBSERVATION_UPDATE_DURATION_DEFAULT,
+            ] {
+                swap_internal(
+                    &amm_config,
+                    &mut pool_state.borrow_mut(),
+                    &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
+                    &mut observation_state.borrow_mut(),
+                    &None,
+                    None,
+                    1_000,
+                    tick_math::get_sqrt_price_at_tick(-32400).unwrap(),
+                    true,
+                    true,
+                    timestamp,
+                    None,
+                    None,
+                )
+                .unwrap();
+                assert_eq!({ pool_state.borrow().tick_current }, tick_current);
+            }
+
+            let observation_state = observation_state.borrow();
+            assert_eq!({ observation_state.observation_index }, 1);
+            let observation = observation_state.observations[1];
+            assert_eq!(
+                { observation.seconds_per_liquidity_cumulative_x64 },
+                (u128::from(OBSERVATION_UPDATE_DURATION_DEFAULT) << fixed_point_64::RESOLUTION)
+                    / liquidity
+            );
+        }
+    }
+
     #[cfg(test)]
     mod sqrt_price_limit_optimization_min_specified_test {
         use super::*;
@@ -1924,11 +2401,14 @@ mod swap_test {
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
                 &Some(bitmap_extension_state),
+                None,
                 amount_specified,
                 tick_math::MIN_SQRT_PRICE_X64 + 1,
                 zero_for_one,
                 is_base_input,
                 1,
+                None,
+                None,
             );
             println!("{:#?}", result);
             let pool = pool_state.borrow();
@@ -1978,11 +2458,14 @@ mod swap_test {
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
                 &Some(bitmap_extension_state),
+                None,
                 amount_specified,
                 tick_math::MIN_SQRT_PRICE_X64 + 1,
                 zero_for_one,
                 is_base_input,
                 1,
+                None,
+                None,
             );
             println!("{:#?}", result);
             let pool = pool_state.borrow();
@@ -2032,11 +2515,14 @@ mod swap_test {
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
                 &Some(bitmap_extension_state),
+                None,
                 amount_specified,
                 tick_math::MAX_SQRT_PRICE_X64 - 1,
                 zero_for_one,
                 is_base_input,
                 1,
+                None,
+                None,
             );
             println!("{:#?}", result);
             let pool = pool_state.borrow();
@@ -2085,11 +2571,14 @@ mod swap_test {
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
                 &Some(bitmap_extension_state),
+                None,
                 amount_specified,
                 tick_math::MAX_SQRT_PRICE_X64 - 1,
                 zero_for_one,
                 is_base_input,
                 1,
+                None,
+                None,
             );
             println!("{:#?}", result);
             let pool = pool_state.borrow();
@@ -2142,11 +2631,14 @@ mod swap_test {
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
                 &Some(bitmap_extension_state),
+                None,
                 amount_specified,
                 tick_math::MIN_SQRT_PRICE_X64 + 1,
                 zero_for_one,
                 is_base_input,
                 1,
+                None,
+                None,
             );
             println!("{:#?}", result);
             let pool = pool_state.borrow();
@@ -2196,11 +2688,14 @@ mod swap_test {
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
                 &Some(bitmap_extension_state),
+                None,
                 amount_specified,
                 tick_math::MIN_SQRT_PRICE_X64 + 1,
                 zero_for_one,
                 is_base_input,
                 1,
+                None,
+                None,
             );
             println!("{:#?}", result);
             let pool = pool_state.borrow();
@@ -2250,11 +2745,14 @@ mod swap_test {
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
                 &Some(bitmap_extension_state),
+                None,
                 amount_specified,
                 tick_math::MAX_SQRT_PRICE_X64 - 1,
                 zero_for_one,
                 is_base_input,
                 1,
+                None,
+                None,
             );
             println!("{:#?}", result);
             let pool = pool_state.borrow();
@@ -2303,11 +2801,14 @@ mod swap_test {
                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                 &mut observation_state.borrow_mut(),
                 &Some(bitmap_extension_state),
+                None,
                 amount_specified,
                 tick_math::MAX_SQRT_PRICE_X64 - 1,
                 zero_for_one,
                 is_base_input,
                 1,
+                None,
+                None,
             );
             println!("{:#?}", result);
             let pool = pool_state.borrow();
@@ -2356,11 +2857,14 @@ mod swap_test {
                         &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                         &mut observation_state.borrow_mut(),
                         &Some(bitmap_extension_state),
+                        None,
                         amount_specified,
                         tick_math::MIN_SQRT_PRICE_X64 + 1,
                         zero_for_one,
                         is_base_input,
                         0,
+                        None,
+                        None,
                     );
 
                     if result.is_ok() {
@@ -2378,11 +2882,14 @@ mod swap_test {
                             &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                             &mut observation_state.borrow_mut(),
                             &Some(bitmap_extension_state),
+                            None,
                             amount_specified,
                             tick_math::MIN_SQRT_PRICE_X64 + 1,
                             zero_for_one,
                             is_base_input,
                             oracle::block_timestamp_mock() as u32,
+                            None,
+                            None,
                         );
                         assert!(result.is_ok());
 
@@ -2402,11 +2909,14 @@ mod swap_test {
                                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                                 &mut observation_state.borrow_mut(),
                                 &Some(bitmap_extension_state),
+                                None,
                                 amount_specified,
                                 tick_math::MIN_SQRT_PRICE_X64 + 1,
                                 zero_for_one,
                                 is_base_input,
                                 oracle::block_timestamp_mock() as u32,
+                                None,
+                                None,
                             );
                             if result.is_err(){
                                 println!("{:#?}", result);
@@ -2447,11 +2957,14 @@ mod swap_test {
                         &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                         &mut observation_state.borrow_mut(),
                         &Some(bitmap_extension_state),
+                        None,
                         amount_specified,
                         tick_math::MIN_SQRT_PRICE_X64 + 1,
                         zero_for_one,
                         base_input,
                         0,
+                        None,
+                        None,
                     );
 
                     if result.is_ok() {
@@ -2469,11 +2982,14 @@ mod swap_test {
                             &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                             &mut observation_state.borrow_mut(),
                             &Some(bitmap_extension_state),
+                            None,
                             amount_specified,
                             tick_math::MIN_SQRT_PRICE_X64 + 1,
                             zero_for_one,
                             base_input,
                             oracle::block_timestamp_mock() as u32,
+                            None,
+                            None,
                         );
                         assert!(result.is_ok());
 
@@ -2493,11 +3009,14 @@ mod swap_test {
                                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                                 &mut observation_state.borrow_mut(),
                                 &Some(bitmap_extension_state),
+                                None,
                                 amount_specified,
                                 tick_math::MIN_SQRT_PRICE_X64 + 1,
                                 zero_for_one,
                                 base_input,
                                 oracle::block_timestamp_mock() as u32,
+                                None,
+                                None,
                             );
                             if result.is_err(){
                                 println!("{:#?}", result);
@@ -2539,11 +3058,14 @@ mod swap_test {
                         &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                         &mut observation_state.borrow_mut(),
                         &Some(bitmap_extension_state),
+                        None,
                         amount_specified,
                         tick_math::MAX_SQRT_PRICE_X64 - 1,
                         zero_for_one,
                         is_base_input,
                         0,
+                        None,
+                        None,
                     );
 
 
@@ -2562,11 +3084,14 @@ mod swap_test {
                             &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                             &mut observation_state.borrow_mut(),
                             &Some(bitmap_extension_state),
+                            None,
                             amount_specified,
                             tick_math::MAX_SQRT_PRICE_X64 - 1,
                             zero_for_one,
                             is_base_input,
                             oracle::block_timestamp_mock() as u32,
+                            None,
+                            None,
                         );
                         assert!(result.is_ok());
 
@@ -2586,11 +3111,14 @@ mod swap_test {
                                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                                 &mut observation_state.borrow_mut(),
                                 &Some(bitmap_extension_state),
+                                None,
                                 amount_specified,
                                 tick_math::MAX_SQRT_PRICE_X64 - 1,
                                 zero_for_one,
                                 is_base_input,
                                 oracle::block_timestamp_mock() as u32,
+                                None,
+                                None,
                             );
 
                         }else{
@@ -2630,11 +3158,14 @@ mod swap_test {
                         &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                         &mut observation_state.borrow_mut(),
                         &Some(bitmap_extension_state),
+                        None,
                         amount_specified,
                         tick_math::MAX_SQRT_PRICE_X64 - 1,
                         zero_for_one,
                         is_base_input,
                         0,
+                        None,
+                        None,
                     );
 
                     if result.is_ok() {
@@ -2652,11 +3183,14 @@ mod swap_test {
                             &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                             &mut observation_state.borrow_mut(),
                             &Some(bitmap_extension_state),
+                            None,
                             amount_specified,
                             tick_math::MAX_SQRT_PRICE_X64 - 1,
                             zero_for_one,
                             is_base_input,
                             oracle::block_timestamp_mock() as u32,
+                            None,
+                            None,
                         );
                         assert!(result.is_ok());
 
@@ -2676,11 +3210,14 @@ mod swap_test {
                                 &mut get_tick_array_states_mut(&tick_array_states).borrow_mut(),
                                 &mut observation_state.borrow_mut(),
                                 &Some(bitmap_extension_state),
+                                None,
                                 amount_specified,
                                 tick_math::MAX_SQRT_PRICE_X64 - 1,
                                 zero_for_one,
                                 is_base_input,
                                 oracle::block_timestamp_mock() as u32,
+                                None,
+                                None,
                             );
                         }else{
                             println!("{}", err);
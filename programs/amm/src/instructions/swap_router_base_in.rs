@@ -1,12 +1,15 @@
 use crate::error::ErrorCode;
 use crate::states::*;
 use crate::swap_v2::{exact_internal_v2, SwapSingleV2};
+use crate::util::*;
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
 use anchor_spl::{
     token::Token,
     token_interface::{Mint, Token2022, TokenAccount},
 };
 
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 pub struct SwapRouterBaseIn<'info> {
     /// The user performing the swap
@@ -14,11 +17,11 @@ pub struct SwapRouterBaseIn<'info> {
 
     /// The token account that pays input tokens for the swap
     #[account(mut)]
-    pub input_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub input_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The mint of input token
     #[account(mut)]
-    pub input_token_mint: InterfaceAccount<'info, Mint>,
+    pub input_token_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// SPL program for token transfers
     pub token_program: Program<'info, Token>,
@@ -37,71 +40,85 @@ pub fn swap_router_base_in<'a, 'b, 'c: 'info, 'info>(
     amount_in: u64,
     amount_out_minimum: u64,
 ) -> Result<()> {
+    check_remaining_accounts_max(ctx.remaining_accounts, MAX_SWAP_ROUTER_REMAINING_ACCOUNTS)?;
+    let event_authority = crate::event_authority!(ctx.accounts, ctx.bumps);
     let mut amount_in_internal = amount_in;
-    let mut input_token_account = Box::new(ctx.accounts.input_token_account.clone());
-    let mut input_token_mint = Box::new(ctx.accounts.input_token_mint.clone());
-    let mut accounts: &[AccountInfo] = ctx.remaining_accounts;
+    let mut input_token_account = ctx.accounts.input_token_account.clone();
+    let mut input_token_mint = ctx.accounts.input_token_mint.clone();
+    let mut accounts: &'c [AccountInfo<'info>] = ctx.remaining_accounts;
     while !accounts.is_empty() {
-        let mut remaining_accounts = accounts.iter();
-        let account_info = remaining_accounts.next().unwrap();
+        let account_info = &accounts[0];
         if accounts.len() != ctx.remaining_accounts.len()
             && account_info.data_len() != AmmConfig::LEN
         {
-            accounts = remaining_accounts.as_slice();
+            accounts = &accounts[1..];
             continue;
         }
-        let amm_config = Box::new(Account::<AmmConfig>::try_from(account_info)?);
-        let pool_state_loader =
-            AccountLoader::<PoolState>::try_from(remaining_accounts.next().unwrap())?;
-        let output_token_account = Box::new(InterfaceAccount::<TokenAccount>::try_from(
-            &remaining_accounts.next().unwrap(),
-        )?);
-        let input_vault = Box::new(InterfaceAccount::<TokenAccount>::try_from(
-            remaining_accounts.next().unwrap(),
-        )?);
-        let output_vault = Box::new(InterfaceAccount::<TokenAccount>::try_from(
-            remaining_accounts.next().unwrap(),
-        )?);
-        let output_token_mint = Box::new(InterfaceAccount::<Mint>::try_from(
-            remaining_accounts.next().unwrap(),
-        )?);
-        let observation_state =
-            AccountLoader::<ObservationState>::try_from(remaining_accounts.next().unwrap())?;
+        check_remaining_accounts_min(accounts, SWAP_ROUTER_HOP_ACCOUNTS)?;
+        let (hop_accounts, rest) = accounts.split_at(SWAP_ROUTER_HOP_ACCOUNTS);
+        accounts = rest;
+        // the tick arrays of the hop are read from all the accounts after it
+        (amount_in_internal, input_token_account, input_token_mint) = swap_hop(
+            &ctx.accounts,
+            hop_accounts,
+            input_token_account,
+            input_token_mint,
+            accounts,
+            &[],
+            amount_in_internal,
+            event_authority,
+        )?;
+    }
+    require_gte!(
+        amount_in_internal,
+        amount_out_minimum,
+        ErrorCode::TooLittleOutputReceived
+    );
 
-        {
-            let pool_state = pool_state_loader.load()?;
-            // check observation account is owned by the pool
-            require_keys_eq!(pool_state.observation_key, observation_state.key());
-            // check ammConfig account is associate with the pool
-            require_keys_eq!(pool_state.amm_config, amm_config.key());
-        }
+    Ok(())
+}
 
-        // solana_program::log::sol_log_compute_units();
-        accounts = remaining_accounts.as_slice();
-        amount_in_internal = exact_internal_v2(
-            &mut SwapSingleV2 {
-                payer: ctx.accounts.payer.clone(),
-                amm_config,
-                input_token_account: input_token_account.clone(),
-                pool_state: pool_state_loader,
-                output_token_account: output_token_account.clone(),
-                input_vault: input_vault.clone(),
-                output_vault: output_vault.clone(),
-                input_vault_mint: input_token_mint.clone(),
-                output_vault_mint: output_token_mint.clone(),
-                observation_state,
-                token_program: ctx.accounts.token_program.clone(),
-                token_program_2022: ctx.accounts.token_program_2022.clone(),
-                memo_program: ctx.accounts.memo_program.clone(),
-            },
-            accounts,
+pub fn swap_router_base_in_v2<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, SwapRouterBaseIn<'info>>,
+    amount_in: u64,
+    amount_out_minimum: u64,
+) -> Result<()> {
+    check_remaining_accounts_max(ctx.remaining_accounts, MAX_SWAP_ROUTER_REMAINING_ACCOUNTS)?;
+    let event_authority = crate::event_authority!(ctx.accounts, ctx.bumps);
+    let mut amount_in_internal = amount_in;
+    let mut input_token_account = ctx.accounts.input_token_account.clone();
+    let mut input_token_mint = ctx.accounts.input_token_mint.clone();
+    let mut accounts: &'c [AccountInfo<'info>] = ctx.remaining_accounts;
+    while !accounts.is_empty() {
+        // the accounts of a hop end at the amm config of the next hop
+        let hop_len = accounts
+            .iter()
+            .skip(1)
+            .position(is_amm_config)
+            .map_or(accounts.len(), |index| index + 1);
+        let (hop_accounts, rest) = accounts.split_at(hop_len);
+        accounts = rest;
+        check_remaining_accounts_min(hop_accounts, SWAP_ROUTER_HOP_ACCOUNTS)?;
+        let (hop_accounts, hop_remaining_accounts) =
+            hop_accounts.split_at(SWAP_ROUTER_HOP_ACCOUNTS);
+        // the transfer fees and hooks of the mints of the hop apply to its own transfers
+        let (tick_array_accounts, transfer_hook_accounts) = split_transfer_hook_accounts(
+            hop_remaining_accounts,
+            &[
+                input_token_mint.to_account_info(),
+                hop_accounts[5].to_account_info(),
+            ],
+        )?;
+        (amount_in_internal, input_token_account, input_token_mint) = swap_hop(
+            &ctx.accounts,
+            hop_accounts,
+            input_token_account,
+            input_token_mint,
+            tick_array_accounts,
+            transfer_hook_accounts,
             amount_in_internal,
-            0,
-            true,
+            event_authority,
         )?;
-        // output token is the new swap input token
-        input_token_account = output_token_account;
-        input_token_mint = output_token_mint;
     }
     require_gte!(
         amount_in_internal,
@@ -111,3 +128,81 @@ pub fn swap_router_base_in<'a, 'b, 'c: 'info, 'info>(
 
     Ok(())
 }
+
+/// Swaps the input of a hop in its pool, returns the output amount, which is the input amount
+/// of the next hop, with the output token account and mint
+fn swap_hop<'c: 'info, 'info>(
+    accounts: &SwapRouterBaseIn<'info>,
+    hop_accounts: &'c [AccountInfo<'info>],
+    input_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    input_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    remaining_accounts: &'c [AccountInfo<'info>],
+    transfer_hook_accounts: &'c [AccountInfo<'info>],
+    amount_in: u64,
+    event_authority: EventAuthority,
+) -> Result<(
+    u64,
+    Box<InterfaceAccount<'info, TokenAccount>>,
+    Box<InterfaceAccount<'info, Mint>>,
+)> {
+    let amm_config = Box::new(Account::<AmmConfig>::try_from(&hop_accounts[0])?);
+    let pool_state_loader = AccountLoader::<PoolState>::try_from(&hop_accounts[1])?;
+    let output_token_account = Box::new(InterfaceAccount::<TokenAccount>::try_from(
+        &hop_accounts[2],
+    )?);
+    let input_vault = Box::new(InterfaceAccount::<TokenAccount>::try_from(
+        &hop_accounts[3],
+    )?);
+    let output_vault = Box::new(InterfaceAccount::<TokenAccount>::try_from(
+        &hop_accounts[4],
+    )?);
+    let output_token_mint = Box::new(InterfaceAccount::<Mint>::try_from(&hop_accounts[5])?);
+    let observation_state = AccountLoader::<ObservationState>::try_from(&hop_accounts[6])?;
+
+    {
+        let pool_state = pool_state_loader.load()?;
+        // check observation account is owned by the pool
+        require_keys_eq!(pool_state.observation_key, observation_state.key());
+        // check ammConfig account is associate with the pool
+        require_keys_eq!(pool_state.amm_config, amm_config.key());
+    }
+
+    // solana_program::log::sol_log_compute_units();
+    let amount_out = exact_internal_v2(
+        &mut SwapSingleV2 {
+            payer: accounts.payer.clone(),
+            amm_config,
+            input_token_account,
+            pool_state: pool_state_loader,
+            output_token_account: output_token_account.clone(),
+            input_vault,
+            output_vault,
+            input_vault_mint: input_token_mint,
+            output_vault_mint: output_token_mint.clone(),
+            observation_state,
+            token_program: accounts.token_program.clone(),
+            token_program_2022: accounts.token_program_2022.clone(),
+            memo_program: accounts.memo_program.clone(),
+            #[cfg(not(feature = "log-events"))]
+            event_authority: accounts.event_authority.clone(),
+            #[cfg(not(feature = "log-events"))]
+            program: accounts.program.clone(),
+        },
+        remaining_accounts,
+        transfer_hook_accounts,
+        amount_in,
+        0,
+        true,
+        false,
+        event_authority,
+    )?;
+    // output token is the new swap input token
+    Ok((amount_out, output_token_account, output_token_mint))
+}
+
+fn is_amm_config(account_info: &AccountInfo) -> bool {
+    account_info.owner == &crate::id()
+        && account_info
+            .try_borrow_data()
+            .is_ok_and(|data| data.starts_with(&AmmConfig::discriminator()))
+}
@@ -0,0 +1,250 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::swap_v2::{exact_internal_v2, SwapSingleV2};
+use crate::util::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
+
+/// The denominator of split_bps
+pub const SWAP_SPLIT_BPS_DENOMINATOR: u64 = 10_000;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct SwapSplit<'info> {
+    /// The user performing the swap
+    pub payer: Signer<'info>,
+
+    /// The factory state of the first pool
+    #[account(address = pool_state_a.load()?.amm_config)]
+    pub amm_config_a: Box<Account<'info, AmmConfig>>,
+
+    /// The first pool in which the swap is performed
+    #[account(mut)]
+    pub pool_state_a: AccountLoader<'info, PoolState>,
+
+    /// The vault token account of the first pool for input token
+    #[account(mut)]
+    pub input_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The vault token account of the first pool for output token
+    #[account(mut)]
+    pub output_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The program account for the most recent oracle observation of the first pool
+    #[account(mut, address = pool_state_a.load()?.observation_key)]
+    pub observation_state_a: AccountLoader<'info, ObservationState>,
+
+    /// The factory state of the second pool
+    #[account(address = pool_state_b.load()?.amm_config)]
+    pub amm_config_b: Box<Account<'info, AmmConfig>>,
+
+    /// The second pool in which the swap is performed, of the same mint pair as the first pool
+    #[account(mut)]
+    pub pool_state_b: AccountLoader<'info, PoolState>,
+
+    /// The vault token account of the second pool for input token
+    #[account(mut)]
+    pub input_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The vault token account of the second pool for output token
+    #[account(mut)]
+    pub output_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The program account for the most recent oracle observation of the second pool
+    #[account(mut, address = pool_state_b.load()?.observation_key)]
+    pub observation_state_b: AccountLoader<'info, ObservationState>,
+
+    /// The user token account for input token
+    #[account(mut)]
+    pub input_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The user token account for output token
+    #[account(mut)]
+    pub output_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of input token
+    #[account(
+        address = input_vault_a.mint
+    )]
+    pub input_vault_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of output token
+    #[account(
+        address = output_vault_a.mint
+    )]
+    pub output_vault_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// SPL program for token transfers
+    pub token_program: Program<'info, Token>,
+
+    /// SPL program 2022 for token transfers
+    pub token_program_2022: Program<'info, Token2022>,
+
+    /// CHECK:
+    #[account(
+        address = spl_memo::id()
+    )]
+    pub memo_program: UncheckedAccount<'info>,
+    // remaining accounts
+    // `remaining_accounts_a_len` accounts of the first pool: tickarray_bitmap_extension, reward_info_extension and tick arrays
+    // the accounts of the second pool like the accounts of the first pool
+    // transfer hook accounts: required if the mints have transfer hooks, must follow the tick arrays
+}
+
+impl<'info> SwapSplit<'info> {
+    /// The accounts of the swap in the first pool if `first`, otherwise in the second pool
+    fn leg(&self, first: bool) -> SwapSingleV2<'info> {
+        let (amm_config, pool_state, input_vault, output_vault, observation_state) = if first {
+            (
+                self.amm_config_a.clone(),
+                self.pool_state_a.clone(),
+                self.input_vault_a.clone(),
+                self.output_vault_a.clone(),
+                self.observation_state_a.clone(),
+            )
+        } else {
+            (
+                self.amm_config_b.clone(),
+                self.pool_state_b.clone(),
+                self.input_vault_b.clone(),
+                self.output_vault_b.clone(),
+                self.observation_state_b.clone(),
+            )
+        };
+        SwapSingleV2 {
+            payer: self.payer.clone(),
+            amm_config,
+            pool_state,
+            input_token_account: self.input_token_account.clone(),
+            output_token_account: self.output_token_account.clone(),
+            input_vault,
+            output_vault,
+            observation_state,
+            token_program: self.token_program.clone(),
+            token_program_2022: self.token_program_2022.clone(),
+            memo_program: self.memo_program.clone(),
+            input_vault_mint: self.input_vault_mint.clone(),
+            output_vault_mint: self.output_vault_mint.clone(),
+            #[cfg(not(feature = "log-events"))]
+            event_authority: self.event_authority.clone(),
+            #[cfg(not(feature = "log-events"))]
+            program: self.program.clone(),
+        }
+    }
+}
+
+#[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Debug, PartialEq)]
+pub struct SwapSplitParam {
+    /// Arranged in pairs with other_amount_threshold. (amount_in, amount_out_minimum) or (amount_out, amount_in_maximum)
+    pub amount: u64,
+    /// For slippage check of the total amount swapped in both pools
+    pub other_amount_threshold: u64,
+    /// The part of the amount swapped in the first pool, in basis points
+    pub split_bps: u16,
+    /// swap base input or swap base output
+    pub is_base_input: bool,
+    /// The number of remaining accounts of the first pool
+    pub remaining_accounts_a_len: u8,
+}
+
+pub fn swap_split<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, SwapSplit<'info>>,
+    param: SwapSplitParam,
+) -> Result<()> {
+    {
+        let pool_state_a = ctx.accounts.pool_state_a.load()?;
+        let pool_state_b = ctx.accounts.pool_state_b.load()?;
+        require!(
+            ctx.accounts.pool_state_a.key() != ctx.accounts.pool_state_b.key()
+                && pool_state_a.token_mint_0 == pool_state_b.token_mint_0
+                && pool_state_a.token_mint_1 == pool_state_b.token_mint_1,
+            ErrorCode::InvalidSwapSplit
+        );
+    }
+    let (amount_a, amount_b) = split_amount(param.amount, param.split_bps)?;
+
+    let (remaining_accounts, transfer_hook_accounts) = split_transfer_hook_accounts(
+        ctx.remaining_accounts,
+        &[
+            ctx.accounts.input_vault_mint.to_account_info(),
+            ctx.accounts.output_vault_mint.to_account_info(),
+        ],
+    )?;
+    let remaining_accounts_a_len = usize::from(param.remaining_accounts_a_len);
+    check_remaining_accounts_min(remaining_accounts, remaining_accounts_a_len)?;
+    let (remaining_accounts_a, remaining_accounts_b) =
+        remaining_accounts.split_at(remaining_accounts_a_len);
+    check_remaining_accounts_max(remaining_accounts_a, MAX_SWAP_REMAINING_ACCOUNTS)?;
+    check_remaining_accounts_max(remaining_accounts_b, MAX_SWAP_REMAINING_ACCOUNTS)?;
+
+    let mut amount_result: u64 = 0;
+    for (first, amount, remaining_accounts) in [
+        (true, amount_a, remaining_accounts_a),
+        (false, amount_b, remaining_accounts_b),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+        amount_result = amount_result
+            .checked_add(exact_internal_v2(
+                &mut ctx.accounts.leg(first),
+                remaining_accounts,
+                transfer_hook_accounts,
+                amount,
+                0,
+                param.is_base_input,
+                false,
+                crate::event_authority!(ctx.accounts, ctx.bumps),
+            )?)
+            .unwrap();
+        // the balances before the next leg are read from the token accounts
+        ctx.accounts.input_token_account.reload()?;
+        ctx.accounts.output_token_account.reload()?;
+    }
+
+    if param.is_base_input {
+        require_gte!(
+            amount_result,
+            param.other_amount_threshold,
+            ErrorCode::TooLittleOutputReceived
+        );
+    } else {
+        require_gte!(
+            param.other_amount_threshold,
+            amount_result,
+            ErrorCode::TooMuchInputPaid
+        );
+    }
+    Ok(())
+}
+
+/// Splits the amount into the parts swapped in the first and the second pool
+pub fn split_amount(amount: u64, split_bps: u16) -> Result<(u64, u64)> {
+    require_gte!(
+        SWAP_SPLIT_BPS_DENOMINATOR,
+        u64::from(split_bps),
+        ErrorCode::InvalidSwapSplit
+    );
+    let amount_a = u64::try_from(
+        u128::from(amount) * u128::from(split_bps) / u128::from(SWAP_SPLIT_BPS_DENOMINATOR),
+    )
+    .unwrap();
+    Ok((amount_a, amount - amount_a))
+}
+
+#[cfg(test)]
+mod swap_split_test {
+    use super::*;
+
+    #[test]
+    fn split_amount_test() {
+        assert_eq!(split_amount(1_000_000, 0).unwrap(), (0, 1_000_000));
+        assert_eq!(split_amount(1_000_000, 2_500).unwrap(), (250_000, 750_000));
+        assert_eq!(split_amount(1_000_000, 10_000).unwrap(), (1_000_000, 0));
+        // the rounding remainder goes to the second pool
+        assert_eq!(split_amount(3, 5_000).unwrap(), (1, 2));
+        assert_eq!(split_amount(u64::MAX, 5_000).unwrap().1, u64::MAX / 2 + 1);
+        assert!(split_amount(1_000_000, 10_001).is_err());
+    }
+}
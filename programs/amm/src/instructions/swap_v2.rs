@@ -2,7 +2,7 @@ use std::collections::VecDeque;
 use std::ops::Deref;
 
 use crate::error::ErrorCode;
-use crate::libraries::tick_math;
+use crate::libraries::{big_num::U256, fixed_point_64, tick_math};
 use crate::swap::swap_internal;
 use crate::util::*;
 use crate::{states::*, util};
@@ -12,6 +12,9 @@ use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
 
 /// Memo msg for swap
 pub const SWAP_MEMO_MSG: &'static [u8] = b"raydium_swap";
+/// The denominator of max_price_impact_bps and the slippage_bps of `SwapSlippage`
+pub const PRICE_IMPACT_BPS_DENOMINATOR: u64 = 10_000;
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 pub struct SwapSingleV2<'info> {
     /// The user performing the swap
@@ -69,20 +72,29 @@ pub struct SwapSingleV2<'info> {
     )]
     pub output_vault_mint: Box<InterfaceAccount<'info, Mint>>,
     // remaining accounts
+    // referral_state: optional, must be the first remaining account if provided
     // tickarray_bitmap_extension: must add account if need regardless the sequence
     // tick_array_account_1
     // tick_array_account_2
-    // tick_array_account_...
+    // tick_array_account_...: in any order, the swap picks the arrays it crosses by their start index
+    // transfer hook accounts: required if the mints have transfer hooks, must follow the tick arrays
 }
 
 /// Performs a single exact input/output swap
-/// if is_base_input = true, return vaule is the max_amount_out, otherwise is min_amount_in
+/// if is_base_input = true, return vaule is the max_amount_out, otherwise is min_amount_in.
+/// The return value is read from the balance change of the user's token accounts after the transfers,
+/// it is net of the Token2022 transfer fee, so the slippage check never applies to pre-fee amounts.
+/// The swap hook and the external oracle of the pool are only enforced if `enforce_pool_hooks`,
+/// by `swap_v3`, the other swaps behave as if the pool had none.
 pub fn exact_internal_v2<'c: 'info, 'info>(
     ctx: &mut SwapSingleV2<'info>,
     remaining_accounts: &'c [AccountInfo<'info>],
+    transfer_hook_accounts: &'c [AccountInfo<'info>],
     amount_specified: u64,
     sqrt_price_limit_x64: u128,
     is_base_input: bool,
+    enforce_pool_hooks: bool,
+    event_authority: EventAuthority,
 ) -> Result<u64> {
     // invoke_memo_instruction(SWAP_MEMO_MSG, ctx.memo_program.to_account_info())?;
 
@@ -92,6 +104,14 @@ pub fn exact_internal_v2<'c: 'info, 'info>(
     let amount_1;
     let zero_for_one;
     let swap_price_before;
+    let trade_fee;
+    let protocol_fee;
+    let fund_fee;
+    let creator_fee;
+    let swap_hook_program;
+    let external_oracle_info;
+    let observation_index;
+    let observation_timestamp;
 
     let input_balance_before = ctx.input_token_account.amount;
     let output_balance_before = ctx.output_token_account.amount;
@@ -114,6 +134,7 @@ pub fn exact_internal_v2<'c: 'info, 'info>(
         zero_for_one = ctx.input_vault.mint == pool_state.token_mint_0;
 
         require_gt!(block_timestamp, pool_state.open_time);
+        pool_state.update_swap_slot(solana_program::clock::Clock::get()?.slot);
 
         require!(
             if zero_for_one {
@@ -129,6 +150,31 @@ pub fn exact_internal_v2<'c: 'info, 'info>(
         let mut tickarray_bitmap_extension = None;
         let tick_array_states = &mut VecDeque::new();
 
+        let reward_info_extension_info = find_swap_reward_extension_account(
+            remaining_accounts,
+            ctx.pool_state.key(),
+            pool_state,
+        );
+        let mut reward_info_extension = reward_info_extension_info
+            .map(|account_info| AccountLoad::<RewardInfoExtension>::load_data_mut(account_info))
+            .transpose()?;
+        let pool_stats_info = find_pool_stats_account(remaining_accounts, ctx.pool_state.key());
+        let trader_stats_info =
+            find_trader_stats_account(remaining_accounts, ctx.pool_state.key(), ctx.payer.key());
+        swap_hook_program = if enforce_pool_hooks {
+            find_swap_hook_program(remaining_accounts, pool_state.swap_hook)?
+        } else {
+            None
+        };
+        external_oracle_info = if enforce_pool_hooks {
+            find_external_oracle_account(remaining_accounts, pool_state.external_oracle)?
+        } else {
+            None
+        };
+        let mut pool_stats = pool_stats_info
+            .map(|account_info| AccountLoad::<PoolStatsState>::load_data_mut(account_info))
+            .transpose()?;
+
         let tick_array_bitmap_extension_key = TickArrayBitmapExtension::key(pool_state.key());
         for account_info in remaining_accounts.into_iter() {
             if account_info.key().eq(&tick_array_bitmap_extension_key) {
@@ -139,15 +185,26 @@ pub fn exact_internal_v2<'c: 'info, 'info>(
                 );
                 continue;
             }
+            if reward_info_extension_info.is_some_and(|info| info.key == account_info.key)
+                || pool_stats_info.is_some_and(|info| info.key == account_info.key)
+                || trader_stats_info.is_some_and(|info| info.key == account_info.key)
+                || swap_hook_program.is_some_and(|info| info.key == account_info.key)
+                || external_oracle_info.is_some_and(|info| info.key == account_info.key)
+            {
+                continue;
+            }
             tick_array_states.push_back(AccountLoad::load_data_mut(account_info)?);
         }
 
+        let fees_before = pool_state.accumulated_swap_fees(zero_for_one);
+        let mut observation_state = ObservationStateMut::load_mut(ctx.observation_state.as_ref())?;
         (amount_0, amount_1) = swap_internal(
             &ctx.amm_config,
             pool_state,
             tick_array_states,
-            &mut ctx.observation_state.load_mut()?,
+            &mut observation_state,
             &tickarray_bitmap_extension,
+            reward_info_extension.as_deref_mut(),
             amount_calculate_specified,
             if sqrt_price_limit_x64 == 0 {
                 if zero_for_one {
@@ -161,7 +218,16 @@ pub fn exact_internal_v2<'c: 'info, 'info>(
             zero_for_one,
             is_base_input,
             oracle::block_timestamp(),
+            Some(event_authority),
+            pool_stats.as_deref_mut(),
         )?;
+        (observation_index, observation_timestamp) =
+            observation_state.latest_observation(&observation_state.extension);
+        let fees_after = pool_state.accumulated_swap_fees(zero_for_one);
+        protocol_fee = fees_after.1 - fees_before.1;
+        fund_fee = fees_after.2 - fees_before.2;
+        creator_fee = fees_after.3 - fees_before.3;
+        trade_fee = fees_after.0 - fees_before.0 + protocol_fee + fund_fee + creator_fee;
 
         #[cfg(feature = "enable-log")]
         msg!(
@@ -174,6 +240,28 @@ pub fn exact_internal_v2<'c: 'info, 'info>(
             amount_0 != 0 && amount_1 != 0,
             ErrorCode::TooSmallInputOrOutputAmount
         );
+        pool_state.check_anti_bot_amount_out(
+            if zero_for_one { amount_1 } else { amount_0 },
+            block_timestamp,
+        )?;
+        if let Some(external_oracle_info) = external_oracle_info {
+            check_external_oracle_price(
+                external_oracle_info,
+                pool_state,
+                amount_0,
+                amount_1,
+                block_timestamp,
+            )?;
+        }
+        if let Some(trader_stats_info) = trader_stats_info {
+            AccountLoad::<TraderStatsState>::load_data_mut(trader_stats_info)?.record_swap(
+                block_timestamp,
+                zero_for_one,
+                amount_0,
+                amount_1,
+                trade_fee,
+            );
+        }
     }
     let (token_account_0, token_account_1, vault_0, vault_1, vault_0_mint, vault_1_mint) =
         if zero_for_one {
@@ -228,6 +316,7 @@ pub fn exact_internal_v2<'c: 'info, 'info>(
             Some(vault_0_mint),
             &ctx.token_program,
             Some(ctx.token_program_2022.to_account_info()),
+            transfer_hook_accounts,
             transfer_amount_0,
         )?;
         if vault_1.amount <= transfer_amount_1 {
@@ -242,6 +331,7 @@ pub fn exact_internal_v2<'c: 'info, 'info>(
             Some(vault_1_mint),
             &ctx.token_program,
             Some(ctx.token_program_2022.to_account_info()),
+            transfer_hook_accounts,
             transfer_amount_1,
         )?;
     } else {
@@ -266,6 +356,7 @@ pub fn exact_internal_v2<'c: 'info, 'info>(
             Some(vault_1_mint),
             &ctx.token_program,
             Some(ctx.token_program_2022.to_account_info()),
+            transfer_hook_accounts,
             transfer_amount_1,
         )?;
         if vault_0.amount <= transfer_amount_0 {
@@ -279,6 +370,7 @@ pub fn exact_internal_v2<'c: 'info, 'info>(
             Some(vault_0_mint),
             &ctx.token_program,
             Some(ctx.token_program_2022.to_account_info()),
+            transfer_hook_accounts,
             transfer_amount_0,
         )?;
     }
@@ -286,7 +378,7 @@ pub fn exact_internal_v2<'c: 'info, 'info>(
     ctx.input_token_account.reload()?;
 
     let pool_state = ctx.pool_state.load()?;
-    emit!(SwapEvent {
+    let swap_event = SwapEvent {
         pool_state: pool_state.key(),
         sender: ctx.payer.key(),
         token_account_0: token_account_0.key(),
@@ -298,8 +390,15 @@ pub fn exact_internal_v2<'c: 'info, 'info>(
         zero_for_one,
         sqrt_price_x64: pool_state.sqrt_price_x64,
         liquidity: pool_state.liquidity,
-        tick: pool_state.tick_current
-    });
+        tick: pool_state.tick_current,
+        version: SWAP_EVENT_VERSION,
+        trade_fee,
+        protocol_fee,
+        fund_fee,
+        creator_fee,
+        observation_index,
+        observation_timestamp,
+    };
     if zero_for_one {
         require_gt!(swap_price_before, pool_state.sqrt_price_x64);
     } else {
@@ -321,6 +420,16 @@ pub fn exact_internal_v2<'c: 'info, 'info>(
             }
         }
     }
+    // the hook is only told about the swaps which passed all the checks
+    if let Some(swap_hook_program) = swap_hook_program {
+        invoke_swap_hook(
+            swap_hook_program,
+            &ctx.pool_state.to_account_info(),
+            &ctx.payer.to_account_info(),
+            &swap_event,
+        )?;
+    }
+    event_authority.emit(swap_event)?;
 
     if is_base_input {
         Ok(ctx
@@ -341,14 +450,144 @@ pub fn swap_v2<'a, 'b, 'c: 'info, 'info>(
     other_amount_threshold: u64,
     sqrt_price_limit_x64: u128,
     is_base_input: bool,
+    max_price_impact_bps: Option<u16>,
+    slippage: Option<SwapSlippage>,
+    enforce_pool_hooks: bool,
 ) -> Result<()> {
-    let amount_result = exact_internal_v2(
+    // the event authority is cloned out of the accounts borrowed by the swap
+    #[cfg(not(feature = "log-events"))]
+    let event_authority_account = ctx.accounts.event_authority.clone();
+    #[cfg(not(feature = "log-events"))]
+    let event_authority = EventAuthority::new(&event_authority_account, ctx.bumps.event_authority);
+    #[cfg(feature = "log-events")]
+    let event_authority = EventAuthority::logs();
+    swap_single_v2(
         ctx.accounts,
         ctx.remaining_accounts,
+        event_authority,
+        amount,
+        other_amount_threshold,
+        sqrt_price_limit_x64,
+        is_base_input,
+        max_price_impact_bps,
+        slippage,
+        enforce_pool_hooks,
+    )
+}
+
+/// Performs the swap of `swap_v2` with its optional referral state and price feed,
+/// then checks the slippage and the price impact
+pub fn swap_single_v2<'c: 'info, 'info>(
+    accounts: &mut SwapSingleV2<'info>,
+    remaining_accounts: &'c [AccountInfo<'info>],
+    event_authority: EventAuthority,
+    amount: u64,
+    other_amount_threshold: u64,
+    sqrt_price_limit_x64: u128,
+    is_base_input: bool,
+    max_price_impact_bps: Option<u16>,
+    slippage: Option<SwapSlippage>,
+    enforce_pool_hooks: bool,
+) -> Result<()> {
+    let (mut remaining_accounts, transfer_hook_accounts) = split_transfer_hook_accounts(
+        remaining_accounts,
+        &[
+            accounts.input_vault_mint.to_account_info(),
+            accounts.output_vault_mint.to_account_info(),
+        ],
+    )?;
+    check_remaining_accounts_max(remaining_accounts, MAX_SWAP_V2_REMAINING_ACCOUNTS)?;
+    let mut referral_state = None;
+    if let Some(account_info) = remaining_accounts.first() {
+        if let Ok(referral) = Account::<ReferralState>::try_from(account_info) {
+            require_keys_eq!(referral.pool_id, accounts.pool_state.key());
+            referral_state = Some(referral);
+            remaining_accounts = &remaining_accounts[1..];
+        }
+    }
+    let mut price_feed = None;
+    if let Some(account_info) = remaining_accounts.first() {
+        if account_info.key() == PriceFeed::key(accounts.pool_state.key()) {
+            price_feed = Some(AccountLoader::<PriceFeed>::try_from(account_info)?);
+            remaining_accounts = &remaining_accounts[1..];
+        }
+    }
+    let clock = Clock::get()?;
+    if let Some(price_feed) = &price_feed {
+        // publish the price before the swap to track the price spread within the slot
+        price_feed.load_mut()?.update(
+            &*accounts.pool_state.load()?,
+            clock.slot,
+            clock.unix_timestamp as u64,
+        );
+    }
+    let (fees_before, sqrt_price_before, other_amount_threshold) = {
+        let pool_state = accounts.pool_state.load()?;
+        let other_amount_threshold = match slippage {
+            Some(SwapSlippage {
+                reference_sqrt_price_x64,
+                slippage_bps,
+            }) => {
+                require!(
+                    (tick_math::MIN_SQRT_PRICE_X64..=tick_math::MAX_SQRT_PRICE_X64)
+                        .contains(&reference_sqrt_price_x64),
+                    ErrorCode::SqrtPriceX64
+                );
+                // quoted at the price the client expects, not the pool price which can be moved
+                // ahead of the swap in the same slot
+                let quoted_threshold = slippage_threshold(
+                    reference_sqrt_price_x64,
+                    pool_state
+                        .base_trade_fee_rate(&accounts.amm_config, clock.unix_timestamp as u64),
+                    amount,
+                    accounts.input_vault.key() == pool_state.token_vault_0,
+                    is_base_input,
+                    slippage_bps,
+                );
+                // the stricter of the thresholds applies
+                if is_base_input {
+                    other_amount_threshold.max(quoted_threshold)
+                } else {
+                    other_amount_threshold.min(quoted_threshold)
+                }
+            }
+            None => other_amount_threshold,
+        };
+        (
+            SwapFeeSnapshot::new(&pool_state),
+            pool_state.sqrt_price_x64,
+            other_amount_threshold,
+        )
+    };
+
+    let amount_result = exact_internal_v2(
+        accounts,
+        remaining_accounts,
+        transfer_hook_accounts,
         amount,
         sqrt_price_limit_x64,
         is_base_input,
+        enforce_pool_hooks,
+        event_authority,
     )?;
+
+    if let Some(mut referral_state) = referral_state {
+        accrue_referral_fee(
+            &accounts.amm_config,
+            &mut *accounts.pool_state.load_mut()?,
+            &mut referral_state,
+            &fees_before,
+            event_authority,
+        )?;
+        referral_state.exit(&crate::id())?;
+    }
+    if let Some(price_feed) = &price_feed {
+        price_feed.load_mut()?.update(
+            &*accounts.pool_state.load()?,
+            clock.slot,
+            clock.unix_timestamp as u64,
+        );
+    }
     if is_base_input {
         require_gte!(
             amount_result,
@@ -362,6 +601,241 @@ pub fn swap_v2<'a, 'b, 'c: 'info, 'info>(
             ErrorCode::TooMuchInputPaid
         );
     }
+    if let Some(max_price_impact_bps) = max_price_impact_bps {
+        check_price_impact(
+            sqrt_price_before,
+            accounts.pool_state.load()?.sqrt_price_x64,
+            max_price_impact_bps,
+        )?;
+    }
 
     Ok(())
 }
+
+/// Checks the price move between sqrt_price_before and sqrt_price_after
+/// does not exceed max_price_impact_bps
+pub fn check_price_impact(
+    sqrt_price_before_x64: u128,
+    sqrt_price_after_x64: u128,
+    max_price_impact_bps: u16,
+) -> Result<()> {
+    let price_before = U256::from(sqrt_price_before_x64) * U256::from(sqrt_price_before_x64);
+    let price_after = U256::from(sqrt_price_after_x64) * U256::from(sqrt_price_after_x64);
+    let denominator = U256::from(PRICE_IMPACT_BPS_DENOMINATOR);
+    let max_price_impact_bps = U256::from(max_price_impact_bps);
+    let within_limit = if price_after < price_before {
+        // price_after >= price_before * (1 - max_price_impact_bps / 10000)
+        max_price_impact_bps >= denominator
+            || price_after * denominator >= price_before * (denominator - max_price_impact_bps)
+    } else {
+        // price_after <= price_before * (1 + max_price_impact_bps / 10000)
+        price_after * denominator <= price_before * (denominator + max_price_impact_bps)
+    };
+    require!(within_limit, ErrorCode::PriceImpactTooHigh);
+    Ok(())
+}
+
+/// The slippage of a swap from the amount quoted at a reference price supplied by the client,
+/// like the pool price the client quoted the swap at
+#[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Debug, PartialEq)]
+pub struct SwapSlippage {
+    /// The Q64.64 sqrt price (amount_token_1 / amount_token_0) the swap is quoted at
+    pub reference_sqrt_price_x64: u128,
+    /// The slippage in basis points from the quoted amount
+    pub slippage_bps: u16,
+}
+
+/// Returns the slippage threshold of the amount quoted at the reference price and the trade fee rate,
+/// the minimum output for a base input swap or the maximum input for a base output swap.
+/// The quote ignores the price move of the swap itself and the Token2022 transfer fees
+pub fn slippage_threshold(
+    sqrt_price_x64: u128,
+    trade_fee_rate: u32,
+    amount: u64,
+    zero_for_one: bool,
+    is_base_input: bool,
+    slippage_bps: u16,
+) -> u64 {
+    let q64 = U256::from(fixed_point_64::Q64);
+    let sqrt_price_x64 = U256::from(sqrt_price_x64);
+    // the price of token_0 in token_1 is sqrt_price_x64^2 / 2^128
+    let convert = |amount: U256, amount_of_token_0: bool| {
+        if amount_of_token_0 {
+            amount * sqrt_price_x64 / q64 * sqrt_price_x64 / q64
+        } else {
+            amount * q64 / sqrt_price_x64 * q64 / sqrt_price_x64
+        }
+    };
+    let fee_denominator = U256::from(FEE_RATE_DENOMINATOR_VALUE);
+    let fee_numerator = fee_denominator - U256::from(trade_fee_rate);
+    let bps_denominator = U256::from(PRICE_IMPACT_BPS_DENOMINATOR);
+    let slippage_bps = U256::from(slippage_bps).min(bps_denominator);
+    let threshold = if is_base_input {
+        let amount_out = convert(
+            U256::from(amount) * fee_numerator / fee_denominator,
+            zero_for_one,
+        );
+        amount_out * (bps_denominator - slippage_bps) / bps_denominator
+    } else {
+        let amount_in =
+            convert(U256::from(amount), !zero_for_one) * fee_denominator / fee_numerator;
+        amount_in * (bps_denominator + slippage_bps) / bps_denominator
+    };
+    threshold.min(U256::from(u64::MAX)).as_u64()
+}
+
+/// The fee counters of the pool before swap, used to calculate the fee charged in the swap
+struct SwapFeeSnapshot {
+    total_fees_token_0: u64,
+    total_fees_token_1: u64,
+    protocol_fees_token_0: u64,
+    protocol_fees_token_1: u64,
+    fund_fees_token_0: u64,
+    fund_fees_token_1: u64,
+    creator_fees_token_0: u64,
+    creator_fees_token_1: u64,
+}
+
+impl SwapFeeSnapshot {
+    fn new(pool_state: &PoolState) -> Self {
+        Self {
+            total_fees_token_0: pool_state.total_fees_token_0,
+            total_fees_token_1: pool_state.total_fees_token_1,
+            protocol_fees_token_0: pool_state.protocol_fees_token_0,
+            protocol_fees_token_1: pool_state.protocol_fees_token_1,
+            fund_fees_token_0: pool_state.fund_fees_token_0,
+            fund_fees_token_1: pool_state.fund_fees_token_1,
+            creator_fees_token_0: pool_state.creator_fees_token_0,
+            creator_fees_token_1: pool_state.creator_fees_token_1,
+        }
+    }
+}
+
+/// Move the referral part of the trade fee from the protocol fee to the referrer
+fn accrue_referral_fee(
+    amm_config: &AmmConfig,
+    pool_state: &mut PoolState,
+    referral_state: &mut ReferralState,
+    fees_before: &SwapFeeSnapshot,
+    event_authority: EventAuthority,
+) -> Result<()> {
+    if amm_config.referral_fee_rate == 0 {
+        return Ok(());
+    }
+    // the fee is always charged in the input token
+    let zero_for_one = pool_state.total_fees_token_0 != fees_before.total_fees_token_0
+        || pool_state.protocol_fees_token_0 != fees_before.protocol_fees_token_0
+        || pool_state.fund_fees_token_0 != fees_before.fund_fees_token_0
+        || pool_state.creator_fees_token_0 != fees_before.creator_fees_token_0;
+    let (lp_fee, protocol_fee, fund_fee, creator_fee) = if zero_for_one {
+        (
+            pool_state.total_fees_token_0 - fees_before.total_fees_token_0,
+            pool_state.protocol_fees_token_0 - fees_before.protocol_fees_token_0,
+            pool_state.fund_fees_token_0 - fees_before.fund_fees_token_0,
+            pool_state.creator_fees_token_0 - fees_before.creator_fees_token_0,
+        )
+    } else {
+        (
+            pool_state.total_fees_token_1 - fees_before.total_fees_token_1,
+            pool_state.protocol_fees_token_1 - fees_before.protocol_fees_token_1,
+            pool_state.fund_fees_token_1 - fees_before.fund_fees_token_1,
+            pool_state.creator_fees_token_1 - fees_before.creator_fees_token_1,
+        )
+    };
+    let trade_fee = lp_fee + protocol_fee + fund_fee + creator_fee;
+    let referral_fee = ReferralState::calculate_referral_fee(
+        trade_fee,
+        protocol_fee,
+        amm_config.referral_fee_rate,
+    );
+    if referral_fee == 0 {
+        return Ok(());
+    }
+    if zero_for_one {
+        pool_state.protocol_fees_token_0 = pool_state
+            .protocol_fees_token_0
+            .checked_sub(referral_fee)
+            .unwrap();
+        pool_state.referral_fees_token_0 = pool_state
+            .referral_fees_token_0
+            .checked_add(referral_fee)
+            .unwrap();
+    } else {
+        pool_state.protocol_fees_token_1 = pool_state
+            .protocol_fees_token_1
+            .checked_sub(referral_fee)
+            .unwrap();
+        pool_state.referral_fees_token_1 = pool_state
+            .referral_fees_token_1
+            .checked_add(referral_fee)
+            .unwrap();
+    }
+    referral_state.accrue_fee(zero_for_one, referral_fee);
+
+    event_authority.emit(ReferralFeeAccruedEvent {
+        pool_state: pool_state.key(),
+        referrer: referral_state.referrer,
+        zero_for_one,
+        amount: referral_fee,
+    })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod price_impact_test {
+    use super::*;
+
+    #[test]
+    fn check_price_impact_test() {
+        let sqrt_price_before = fixed_point_64::Q64;
+        // sqrt price moves 0.5%, the price moves about 1%
+        let sqrt_price_down = sqrt_price_before / 1000 * 995;
+        let sqrt_price_up = sqrt_price_before / 1000 * 1005;
+
+        assert!(check_price_impact(sqrt_price_before, sqrt_price_before, 0).is_ok());
+        assert!(check_price_impact(sqrt_price_before, sqrt_price_down, 100).is_ok());
+        assert!(check_price_impact(sqrt_price_before, sqrt_price_up, 101).is_ok());
+        assert_eq!(
+            check_price_impact(sqrt_price_before, sqrt_price_down, 99).unwrap_err(),
+            error!(ErrorCode::PriceImpactTooHigh)
+        );
+        assert_eq!(
+            check_price_impact(sqrt_price_before, sqrt_price_up, 100).unwrap_err(),
+            error!(ErrorCode::PriceImpactTooHigh)
+        );
+        // any downward move is within 100%
+        assert!(check_price_impact(sqrt_price_before, 1, 10_000).is_ok());
+    }
+
+    #[test]
+    fn slippage_threshold_test() {
+        // the price of token_0 is 4 token_1
+        let sqrt_price_x64 = fixed_point_64::Q64 * 2;
+        // 0.25% trade fee and 1% slippage
+        assert_eq!(
+            slippage_threshold(sqrt_price_x64, 2500, 1_000_000, true, true, 100),
+            3_950_100
+        );
+        assert_eq!(
+            slippage_threshold(sqrt_price_x64, 2500, 4_000_000, false, true, 100),
+            987_525
+        );
+        assert_eq!(
+            slippage_threshold(sqrt_price_x64, 2500, 3_990_000, true, false, 100),
+            1_010_000
+        );
+        assert_eq!(
+            slippage_threshold(sqrt_price_x64, 2500, 997_500, false, false, 100),
+            4_040_000
+        );
+        // the output can be zero, the input is capped
+        assert_eq!(
+            slippage_threshold(sqrt_price_x64, 0, 1_000, true, true, 10_000),
+            0
+        );
+        assert_eq!(
+            slippage_threshold(fixed_point_64::Q64 << 20, 0, u64::MAX, false, false, 100),
+            u64::MAX
+        );
+    }
+}
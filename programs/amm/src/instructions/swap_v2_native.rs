@@ -0,0 +1,180 @@
+use crate::error::ErrorCode;
+use crate::swap_v2::{swap_single_v2, SwapSingleV2, SwapSlippage};
+use crate::util::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
+
+use crate::states::*;
+
+pub const NATIVE_TOKEN_ACCOUNT_SEED: &str = "native_token_account";
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct SwapSingleV2Native<'info> {
+    /// The user performing the swap, pays the native SOL in or receives the native SOL out
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The factory state to read protocol fees
+    #[account(address = pool_state.load()?.amm_config)]
+    pub amm_config: Box<Account<'info, AmmConfig>>,
+
+    /// The program account of the pool in which the swap will be performed
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// The user token account for input token, none to pay native SOL if the input token is WSOL
+    #[account(mut)]
+    pub input_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The user token account for output token, none to receive native SOL if the output token is WSOL
+    #[account(mut)]
+    pub output_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The vault token account for input token
+    #[account(mut)]
+    pub input_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The vault token account for output token
+    #[account(mut)]
+    pub output_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The program account for the most recent oracle observation
+    #[account(mut, address = pool_state.load()?.observation_key)]
+    pub observation_state: AccountLoader<'info, ObservationState>,
+
+    /// SPL program for token transfers
+    pub token_program: Program<'info, Token>,
+
+    /// SPL program 2022 for token transfers
+    pub token_program_2022: Program<'info, Token2022>,
+
+    /// CHECK:
+    #[account(
+        address = spl_memo::id()
+    )]
+    pub memo_program: UncheckedAccount<'info>,
+
+    /// The mint of token vault 0
+    #[account(
+        address = input_vault.mint
+    )]
+    pub input_vault_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of token vault 1
+    #[account(
+        address = output_vault.mint
+    )]
+    pub output_vault_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// To create the temporary WSOL token account
+    pub system_program: Program<'info, System>,
+    // remaining accounts
+    // native_token_account: the temporary WSOL token account of the payer at the PDA of `[NATIVE_TOKEN_ACCOUNT_SEED, payer]`,
+    // created and closed within the swap, must be the first remaining account if a user token account is none
+    // the remaining accounts of swap_v2
+}
+
+pub fn swap_v2_native<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, SwapSingleV2Native<'info>>,
+    amount: u64,
+    other_amount_threshold: u64,
+    sqrt_price_limit_x64: u128,
+    is_base_input: bool,
+    max_price_impact_bps: Option<u16>,
+    slippage: Option<SwapSlippage>,
+) -> Result<()> {
+    let payer_key = ctx.accounts.payer.key();
+    let native_input = ctx.accounts.input_token_account.is_none();
+    let native_output = ctx.accounts.output_token_account.is_none();
+    let mut remaining_accounts = ctx.remaining_accounts;
+    let mut native_token_account = None;
+    if native_input || native_output {
+        require!(!(native_input && native_output), ErrorCode::NotApproved);
+        check_remaining_accounts_min(remaining_accounts, 1)?;
+        let (native_token_account_key, bump) = Pubkey::find_program_address(
+            &[NATIVE_TOKEN_ACCOUNT_SEED.as_bytes(), payer_key.as_ref()],
+            &crate::id(),
+        );
+        let native_token_account_info = &remaining_accounts[0];
+        require_keys_eq!(native_token_account_info.key(), native_token_account_key);
+        remaining_accounts = &remaining_accounts[1..];
+
+        let native_mint = if native_input {
+            &ctx.accounts.input_vault_mint
+        } else {
+            &ctx.accounts.output_vault_mint
+        };
+        // the input is wrapped up to the maximum amount paid, the surplus is unwrapped with the output
+        let wrap_amount = match (native_input, is_base_input) {
+            (false, _) => 0,
+            (true, true) => amount,
+            (true, false) => other_amount_threshold,
+        };
+        create_temporary_native_account(
+            &ctx.accounts.payer,
+            native_token_account_info,
+            &native_mint.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            &[
+                NATIVE_TOKEN_ACCOUNT_SEED.as_bytes(),
+                payer_key.as_ref(),
+                &[bump],
+            ],
+            wrap_amount,
+        )?;
+        native_token_account = Some(native_token_account_info);
+    }
+
+    let token_account =
+        |account: &Option<Box<InterfaceAccount<'info, TokenAccount>>>| match account {
+            Some(account) => Ok(account.clone()),
+            None => InterfaceAccount::<TokenAccount>::try_from(native_token_account.unwrap())
+                .map(Box::new),
+        };
+    let mut swap_accounts = SwapSingleV2 {
+        payer: ctx.accounts.payer.clone(),
+        amm_config: ctx.accounts.amm_config.clone(),
+        pool_state: ctx.accounts.pool_state.clone(),
+        input_token_account: token_account(&ctx.accounts.input_token_account)?,
+        output_token_account: token_account(&ctx.accounts.output_token_account)?,
+        input_vault: ctx.accounts.input_vault.clone(),
+        output_vault: ctx.accounts.output_vault.clone(),
+        observation_state: ctx.accounts.observation_state.clone(),
+        token_program: ctx.accounts.token_program.clone(),
+        token_program_2022: ctx.accounts.token_program_2022.clone(),
+        memo_program: ctx.accounts.memo_program.clone(),
+        input_vault_mint: ctx.accounts.input_vault_mint.clone(),
+        output_vault_mint: ctx.accounts.output_vault_mint.clone(),
+        #[cfg(not(feature = "log-events"))]
+        event_authority: ctx.accounts.event_authority.clone(),
+        #[cfg(not(feature = "log-events"))]
+        program: ctx.accounts.program.clone(),
+    };
+    swap_single_v2(
+        &mut swap_accounts,
+        remaining_accounts,
+        crate::event_authority!(ctx.accounts, ctx.bumps),
+        amount,
+        other_amount_threshold,
+        sqrt_price_limit_x64,
+        is_base_input,
+        max_price_impact_bps,
+        slippage,
+        false,
+    )?;
+
+    if let Some(native_token_account) = native_token_account {
+        // the output and the unspent input are returned to the payer as native SOL
+        close_spl_account(
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            native_token_account,
+            &ctx.accounts.token_program.to_account_info(),
+            &[],
+        )?;
+    }
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use crate::emit_event;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct UpdateLiquiditySeconds<'info> {
+    /// The pool of the position
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// The position to accumulate liquidity seconds
+    #[account(mut, constraint = personal_position.pool_id == pool_state.key())]
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
+
+    /// Stores the lower tick of the position
+    #[account(constraint = tick_array_lower.load()?.pool_id == pool_state.key())]
+    pub tick_array_lower: AccountLoader<'info, TickArrayState>,
+
+    /// Stores the upper tick of the position
+    #[account(constraint = tick_array_upper.load()?.pool_id == pool_state.key())]
+    pub tick_array_upper: AccountLoader<'info, TickArrayState>,
+}
+
+pub fn update_liquidity_seconds(ctx: Context<UpdateLiquiditySeconds>) -> Result<()> {
+    let block_timestamp = u64::try_from(Clock::get()?.unix_timestamp).unwrap();
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    pool_state.update_liquidity_seconds(block_timestamp);
+
+    let personal_position = &mut ctx.accounts.personal_position;
+    let tick_array_lower = ctx.accounts.tick_array_lower.load()?;
+    let tick_array_upper = ctx.accounts.tick_array_upper.load()?;
+    let seconds_inside = tick_array::get_seconds_inside(
+        tick_array_lower
+            .get_tick_state(personal_position.tick_lower_index, pool_state.tick_spacing)?,
+        tick_array_upper
+            .get_tick_state(personal_position.tick_upper_index, pool_state.tick_spacing)?,
+        pool_state.tick_current,
+        block_timestamp,
+    );
+    personal_position.update_liquidity_seconds(seconds_inside);
+
+    emit_event!(
+        ctx,
+        UpdateLiquiditySecondsEvent {
+            pool_state: ctx.accounts.pool_state.key(),
+            position_nft_mint: personal_position.nft_mint,
+            liquidity_seconds: personal_position.liquidity_seconds,
+            seconds_inside: personal_position.seconds_inside_last,
+            liquidity_seconds_global: pool_state.liquidity_seconds_global,
+        }
+    )?;
+
+    Ok(())
+}
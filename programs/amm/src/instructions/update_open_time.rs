@@ -0,0 +1,52 @@
+use crate::emit_event;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct UpdateOpenTime<'info> {
+    /// The pool owner or an operation owner
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// load info from the account to judge the authority
+    #[account(
+        seeds = [
+            OPERATION_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub operation_state: AccountLoader<'info, OperationState>,
+}
+
+pub fn update_open_time(ctx: Context<UpdateOpenTime>, open_time: u64) -> Result<()> {
+    let block_timestamp = Clock::get()?.unix_timestamp as u64;
+    let operation_state = ctx.accounts.operation_state.load()?;
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    if !operation_state.validate_operation_owner(ctx.accounts.authority.key()) {
+        require_keys_eq!(ctx.accounts.authority.key(), pool_state.owner);
+    }
+    pool_state.update_open_time(open_time, block_timestamp)?;
+    emit_event!(
+        ctx,
+        UpdateOpenTimeEvent {
+            pool_state: ctx.accounts.pool_state.key(),
+            open_time,
+        }
+    )?;
+    Ok(())
+}
+
+/// Emitted when the open time of a pool is updated
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct UpdateOpenTimeEvent {
+    /// The pool of which the open time is updated
+    #[index]
+    pub pool_state: Pubkey,
+
+    /// The timestamp from which swaps are allowed in the pool
+    pub open_time: u64,
+}
@@ -0,0 +1,29 @@
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdatePriceFeed<'info> {
+    /// The pool of the price feed
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// The price feed account to be refreshed
+    #[account(
+        mut,
+        seeds = [
+            PRICE_FEED_SEED.as_bytes(),
+            pool_state.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub price_feed: AccountLoader<'info, PriceFeed>,
+}
+
+pub fn update_price_feed(ctx: Context<UpdatePriceFeed>) -> Result<()> {
+    let clock = Clock::get()?;
+    ctx.accounts.price_feed.load_mut()?.update(
+        &*ctx.accounts.pool_state.load()?,
+        clock.slot,
+        clock.unix_timestamp as u64,
+    );
+    Ok(())
+}
@@ -1,24 +1,66 @@
 use crate::states::*;
+use crate::util::{AccountLoad, EventAuthority};
 use anchor_lang::prelude::*;
 
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 pub struct UpdateRewardInfos<'info> {
     /// The liquidity pool for which reward info to update
     #[account(mut)]
     pub pool_state: AccountLoader<'info, PoolState>,
+    // remaining accounts
+    // the reward info extension of the pool, optional
+    // the other pools to update, each followed by its reward info extension optionally
 }
 
 pub fn update_reward_infos<'a, 'b, 'c, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, UpdateRewardInfos<'info>>,
 ) -> Result<()> {
-    let clock = Clock::get()?;
-    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
-    let updated_reward_infos =
-        pool_state.update_reward_infos(u64::try_from(clock.unix_timestamp).unwrap())?;
-
-    emit!(UpdateRewardInfosEvent {
-        reward_growth_global_x64: RewardInfo::get_reward_growths(&updated_reward_infos)
-    });
+    let block_timestamp = u64::try_from(Clock::get()?.unix_timestamp).unwrap();
+    let event_authority = crate::event_authority!(ctx.accounts, ctx.bumps);
+    let pool_state_info = ctx.accounts.pool_state.to_account_info();
+    let accounts: Vec<&AccountInfo> = std::iter::once(&pool_state_info)
+        .chain(ctx.remaining_accounts.iter())
+        .collect();
 
+    let mut index = 0;
+    while index < accounts.len() {
+        let pool_state_info = accounts[index];
+        // the reward info extension of the pool is updated as well if it follows the pool
+        let reward_info_extension_info = accounts.get(index + 1).copied().filter(|account_info| {
+            account_info.key() == RewardInfoExtension::key(pool_state_info.key())
+        });
+        index += if reward_info_extension_info.is_some() {
+            2
+        } else {
+            1
+        };
+        update_pool_reward_infos(
+            pool_state_info,
+            reward_info_extension_info,
+            block_timestamp,
+            &event_authority,
+        )?;
+    }
     Ok(())
 }
+
+/// Updates the reward infos of the pool and its reward info extension if passed
+fn update_pool_reward_infos(
+    pool_state_info: &AccountInfo,
+    reward_info_extension_info: Option<&AccountInfo>,
+    block_timestamp: u64,
+    event_authority: &EventAuthority,
+) -> Result<()> {
+    let mut pool_state = AccountLoad::<PoolState>::load_data_mut(pool_state_info)?;
+    let updated_reward_infos = pool_state.update_reward_infos(block_timestamp)?;
+
+    if let Some(reward_info_extension_info) = reward_info_extension_info {
+        AccountLoad::<RewardInfoExtension>::load_data_mut(reward_info_extension_info)?
+            .update_reward_infos(block_timestamp, pool_state.liquidity)?;
+    }
+
+    event_authority.emit(UpdateRewardInfosEvent {
+        reward_growth_global_x64: RewardInfo::get_reward_growths(&updated_reward_infos),
+    })
+}
@@ -79,14 +79,112 @@ pub mod amm_v3 {
     /// * `trade_fee_rate`- The new trade fee rate of amm config, be set when `param` is 0
     /// * `protocol_fee_rate`- The new protocol fee rate of amm config, be set when `param` is 1
     /// * `fund_fee_rate`- The new fund fee rate of amm config, be set when `param` is 2
-    /// * `new_owner`- The config's new owner, be set when `param` is 3
-    /// * `new_fund_owner`- The config's new fund owner, be set when `param` is 4
-    /// * `param`- The vaule can be 0 | 1 | 2 | 3 | 4, otherwise will report a error
+    /// * `referral_fee_rate`- The new referral fee rate of amm config, be set when `param` is 5
+    /// * `create_pool_fee`- The new pool creation fee in lamports, be set when `param` is 6
+    /// * `whitelist_pool_creation`- Restrict pool creation to operation owners and whitelist creators if not 0, be set when `param` is 7
+    /// * `sandwich_fee_multiplier`- The trade fee multiplier in percent for a swap following an opposite direction swap in the same slot, be set when `param` is 8
+    /// * `max_creator_fee_rate`- The maximum creator fee rate of the pools of amm config, be set when `param` is 9
+    /// * `freeze_authority_requires_badge`- Require a token badge for the Token2022 mints with freeze authority if not 0, be set when `param` is 10
+    /// * `param`- The vaule can be 0 | 1 | 2 | 5 | 6 | 7 | 8 | 9 | 10, otherwise will report a error,
+    /// 3 | 4 fail with `OwnershipTransferRequiresTwoSteps` since the owner and fund owner are transferred
+    /// by `propose_ownership` and `accept_ownership`
+    ///
+    /// The admin timelock account must be passed in remaining accounts when `param` is 0 | 1 | 2 | 5 | 6 | 8 | 9,
+    /// fails if the timelock is enabled.
     ///
     pub fn update_amm_config(ctx: Context<UpdateAmmConfig>, param: u8, value: u32) -> Result<()> {
         instructions::update_amm_config(ctx, param, value)
     }
 
+    /// Create the extension of an amm config, which holds the parameters of the config
+    /// not fitting in the layout of amm config
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn create_amm_config_extension(ctx: Context<CreateAmmConfigExtension>) -> Result<()> {
+        instructions::create_amm_config_extension(ctx)
+    }
+
+    /// Update the extension of an amm config, only the admin can call it
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `min_dynamic_fee_rate`- The lower bound of the dynamic trade fee rate, be set when `param` is 0
+    /// * `max_dynamic_fee_rate`- The upper bound of the dynamic trade fee rate, be set when `param` is 1
+    /// * `treasury`- The treasury of the config passed in remaining accounts, be set when `param` is 2
+    /// * `param`- The vaule can be 0 | 1 | 2, otherwise will report a error
+    ///
+    pub fn update_amm_config_extension(
+        ctx: Context<UpdateAmmConfigExtension>,
+        param: u8,
+        value: u64,
+    ) -> Result<()> {
+        instructions::update_amm_config_extension(ctx, param, value)
+    }
+
+    /// Proposes a new owner or fund owner of the amm config, takes effect after the proposed
+    /// account calls `accept_ownership`
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `param`- 3 to propose the owner, 4 to propose the fund owner, otherwise will report a error
+    ///
+    pub fn propose_ownership(ctx: Context<ProposeOwnership>, param: u8) -> Result<()> {
+        instructions::propose_ownership(ctx, param)
+    }
+
+    /// Accepts the proposed owner or fund owner of the amm config, must be signed by the proposed account
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn accept_ownership(ctx: Context<AcceptOwnership>) -> Result<()> {
+        instructions::accept_ownership(ctx)
+    }
+
+    /// Creates a partner-branded amm config, the protocol fee collected from its pools is
+    /// split between the protocol and the partner owner.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The accounts needed by instruction.
+    /// * `index` - The index of amm config, there may be multiple config.
+    /// * `tick_spacing` - The tickspacing binding with config, cannot be changed.
+    /// * `trade_fee_rate` - Trade fee rate, can be changed.
+    /// * `protocol_fee_rate` - The rate of protocol fee within tarde fee.
+    /// * `fund_fee_rate` - The rate of fund fee within tarde fee.
+    /// * `partner_fee_rate` - The rate of partner fee within protocol fee, cannot be changed.
+    ///
+    pub fn create_partner_amm_config(
+        ctx: Context<CreatePartnerAmmConfig>,
+        index: u16,
+        tick_spacing: u16,
+        trade_fee_rate: u32,
+        protocol_fee_rate: u32,
+        fund_fee_rate: u32,
+        partner_fee_rate: u32,
+    ) -> Result<()> {
+        assert!(trade_fee_rate < FEE_RATE_DENOMINATOR_VALUE);
+        assert!(protocol_fee_rate <= FEE_RATE_DENOMINATOR_VALUE);
+        assert!(fund_fee_rate <= FEE_RATE_DENOMINATOR_VALUE);
+        assert!(fund_fee_rate + protocol_fee_rate <= FEE_RATE_DENOMINATOR_VALUE);
+        assert!(partner_fee_rate > 0 && partner_fee_rate <= FEE_RATE_DENOMINATOR_VALUE);
+        instructions::create_partner_amm_config(
+            ctx,
+            index,
+            tick_spacing,
+            trade_fee_rate,
+            protocol_fee_rate,
+            fund_fee_rate,
+            partner_fee_rate,
+        )
+    }
+
     /// Creates a pool for the given token pair and the initial price
     ///
     /// # Arguments
@@ -94,12 +192,150 @@ pub mod amm_v3 {
     /// * `ctx`- The context of accounts
     /// * `sqrt_price_x64` - the initial sqrt price (amount_token_1 / amount_token_0) of the pool as a Q64.64
     /// Note: The open_time must be smaller than the current block_timestamp on chain.
-    pub fn create_pool(
-        ctx: Context<CreatePool>,
+    /// Note: The `create_pool_fee` of amm config in lamports is charged to the pool creator.
+    /// Note: The token badge of a Token2022 mint with risky extensions must be passed in remaining accounts.
+    /// Note: The whitelist creator of the pool creator, or the operation account if the pool creator is an operation owner,
+    /// must be passed in remaining accounts if the amm config restricts pool creation.
+    pub fn create_pool<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CreatePool<'info>>,
         sqrt_price_x64: u128,
         open_time: u64,
     ) -> Result<()> {
-        instructions::create_pool(ctx, sqrt_price_x64, open_time)
+        instructions::create_pool(ctx, sqrt_price_x64, open_time, Vec::new())
+    }
+
+    /// Creates a pool like `create_pool`, with options
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `sqrt_price_x64` - the initial sqrt price (amount_token_1 / amount_token_0) of the pool as a Q64.64
+    /// * `options` - The options of the pool, like the part of the trade fee accrued to the pool creator,
+    /// at most the `max_creator_fee_rate` of amm config, or the trade fee rate at launch decaying to
+    /// the trade fee rate of amm config from the open time of the pool
+    /// Note: The remaining accounts are like the ones of `create_pool`.
+    pub fn create_pool_v2<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CreatePool<'info>>,
+        sqrt_price_x64: u128,
+        open_time: u64,
+        options: Vec<CreatePoolOption>,
+    ) -> Result<()> {
+        instructions::create_pool(ctx, sqrt_price_x64, open_time, options)
+    }
+
+    /// Collect the creator fee accrued to the pool, must be called by the pool creator
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `amount_0_requested` - The maximum amount of token_0 to send, can be 0 to collect fees in only token_1
+    /// * `amount_1_requested` - The maximum amount of token_1 to send, can be 0 to collect fees in only token_0
+    ///
+    /// The accounts of the transfer hooks of the mints are passed in remaining accounts if the mints have transfer hooks.
+    ///
+    pub fn collect_creator_fee<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CollectCreatorFee<'info>>,
+        amount_0_requested: u64,
+        amount_1_requested: u64,
+    ) -> Result<()> {
+        instructions::collect_creator_fee(ctx, amount_0_requested, amount_1_requested)
+    }
+
+    /// Creates or updates the metadata of a pool, must be called by the pool creator
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `label` - A short label of the pool
+    /// * `uri` - The uri of the external description of the pool
+    ///
+    pub fn set_pool_metadata(
+        ctx: Context<SetPoolMetadata>,
+        label: String,
+        uri: String,
+    ) -> Result<()> {
+        instructions::set_pool_metadata(ctx, label, uri)
+    }
+
+    /// Limits the output amount of a swap in the opening window of a pool, must be called by
+    /// the pool creator before any swap happens in the pool
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `max_amount_out` - The maximum output amount of a swap in the window, 0 means no limit
+    /// * `duration` - The seconds after the open time of the pool during which the limit applies
+    ///
+    pub fn set_anti_bot_config(
+        ctx: Context<SetAntiBotConfig>,
+        max_amount_out: u64,
+        duration: u64,
+    ) -> Result<()> {
+        instructions::set_anti_bot_config(ctx, max_amount_out, duration)
+    }
+
+    /// Updates the open time of a pool before any swap happens in the pool, must be called
+    /// by the pool creator or an operation owner
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `open_time` - The timestamp from which swaps are allowed, must be after the current time
+    ///
+    pub fn update_open_time(ctx: Context<UpdateOpenTime>, open_time: u64) -> Result<()> {
+        instructions::update_open_time(ctx, open_time)
+    }
+
+    /// Donates tokens to the liquidity in range of a pool, credited as fees to the in range positions
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `amount_0` - The amount of token_0 to donate, the transfer fee is deducted from it
+    /// * `amount_1` - The amount of token_1 to donate, the transfer fee is deducted from it
+    ///
+    /// Note: The pool must have liquidity in range.
+    ///
+    pub fn donate<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, Donate<'info>>,
+        amount_0: u64,
+        amount_1: u64,
+    ) -> Result<()> {
+        instructions::donate(ctx, amount_0, amount_1)
+    }
+
+    /// Creates and funds a distribution of rewards computed off chain, claimed by merkle proofs
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `id` - The id of the distribution among the ones of the creator and the mint
+    /// * `merkle_root` - The merkle root of the leaves `sha256(claimant, amount as le bytes)`
+    /// * `total_amount` - The total amount of the claims, transferred to the vault of the distribution
+    ///
+    pub fn create_distribution(
+        ctx: Context<CreateDistribution>,
+        id: u64,
+        merkle_root: [u8; 32],
+        total_amount: u64,
+    ) -> Result<()> {
+        instructions::create_distribution(ctx, id, merkle_root, total_amount)
+    }
+
+    /// Claims the amount of the claimant from a distribution, once per claimant
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `amount` - The amount of the claimant in the merkle tree
+    /// * `proof` - The sibling nodes from the leaf to the root, each pair is hashed in sorted order
+    ///
+    pub fn claim_with_proof(
+        ctx: Context<ClaimWithProof>,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::claim_with_proof(ctx, amount, proof)
     }
 
     /// Update pool status for given vaule
@@ -107,12 +343,112 @@ pub mod amm_v3 {
     /// # Arguments
     ///
     /// * `ctx`- The context of accounts
-    /// * `status` - The vaule of status
+    /// * `status` - The vaule of status, bit 5 sets the pool withdraw-only,
+    /// bit 6 and bit 7 disable zero for one and one for zero swap
+    ///
+    /// The admin timelock account must be passed in remaining accounts, fails if the timelock is enabled.
     ///
     pub fn update_pool_status(ctx: Context<UpdatePoolStatus>, status: u8) -> Result<()> {
         instructions::update_pool_status(ctx, status)
     }
 
+    /// Update the price band of the pool, must be called by admin or operation owner
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `min_sqrt_price_x64` - Swaps can not move the price below it, 0 means no lower bound
+    /// * `max_sqrt_price_x64` - Swaps can not move the price above it, 0 means no upper bound
+    ///
+    pub fn update_pool_price_band(
+        ctx: Context<UpdatePoolPriceBand>,
+        min_sqrt_price_x64: u128,
+        max_sqrt_price_x64: u128,
+    ) -> Result<()> {
+        instructions::update_pool_price_band(ctx, min_sqrt_price_x64, max_sqrt_price_x64)
+    }
+
+    /// Set the program invoked with the result of each swap of the pool, must be called by admin or operation owner
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `swap_hook` - The hook program, default pubkey to remove the swap hook
+    ///
+    /// Note: The swap hook is only invoked by `swap_v3`, which requires the swap hook program
+    /// in its remaining accounts, the other swaps of the pool don't invoke it.
+    ///
+    pub fn set_swap_hook(ctx: Context<SetSwapHook>, swap_hook: Pubkey) -> Result<()> {
+        instructions::set_swap_hook(ctx, swap_hook)
+    }
+
+    /// Set the external price feed checked against the execution price of each swap of the pool,
+    /// must be called by admin or operation owner
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `external_oracle` - The Pyth price update account quoting token_0 in token_1,
+    /// default pubkey to remove the oracle check
+    /// * `max_deviation_bps` - The maximum deviation of the execution price from the oracle price,
+    /// in basis points, which must cover the trade fee and the price impact of the swaps
+    ///
+    /// Note: The external oracle is only checked by `swap_v3`, which requires it in its remaining accounts
+    /// and its price to be published within `EXTERNAL_ORACLE_MAX_AGE` seconds, the other swaps of the pool don't check it.
+    ///
+    pub fn set_external_oracle(
+        ctx: Context<SetExternalOracle>,
+        external_oracle: Pubkey,
+        max_deviation_bps: u64,
+    ) -> Result<()> {
+        instructions::set_external_oracle(ctx, external_oracle, max_deviation_bps)
+    }
+
+    /// Reset the price of a mispriced pool without liquidity in range and with empty vaults,
+    /// must be called by admin or operation owner
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `sqrt_price_x64` - the new sqrt price (amount_token_1 / amount_token_0) of the pool as a Q64.64
+    ///
+    /// Note: The observations of the pool are cleared since they were recorded at the old price.
+    ///
+    pub fn reset_sqrt_price(ctx: Context<ResetSqrtPrice>, sqrt_price_x64: u128) -> Result<()> {
+        instructions::reset_sqrt_price(ctx, sqrt_price_x64)
+    }
+
+    /// Route the fund fees of the pool to the token accounts of a recipient instead of the fund owner, must be called by admin
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `fund_fee_recipient` - The owner of the token accounts receiving the fund fees, default pubkey to remove the override
+    ///
+    pub fn set_fund_fee_recipient(
+        ctx: Context<SetFundFeeRecipient>,
+        fund_fee_recipient: Pubkey,
+    ) -> Result<()> {
+        instructions::set_fund_fee_recipient(ctx, fund_fee_recipient)
+    }
+
+    /// Close a drained pool with its vaults, observation and bitmap extension, must be called by admin.
+    /// The pool must have no liquidity, no initialized tick arrays, no uncollected protocol and fund fees and empty vaults.
+    /// The accounts derived from the pool are closed with it, so that a pool created again at the same address
+    /// doesn't take them over.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts, the reward vaults of the initialized rewards of the pool and the reward info extension must be passed in remaining accounts,
+    /// followed by the reward info extension, pool stats, pool metadata, pool checkpoint and price feed of the pool
+    /// even if not created, then the tick arrays, protocol positions, twap snapshots and trader stats of the pool
+    ///
+    pub fn close_pool<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ClosePool<'info>>,
+    ) -> Result<()> {
+        instructions::close_pool(ctx)
+    }
+
     /// Creates an operation account for the program
     ///
     /// # Arguments
@@ -142,6 +478,104 @@ pub mod amm_v3 {
         instructions::update_operation_account(ctx, param, keys)
     }
 
+    /// Grow the operation account to hold more whitelist mints than its array, must be called by admin
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `extension_whitelist_mint_size`- The number of whitelist mints the extension can hold, only grows
+    ///
+    pub fn resize_operation_account(
+        ctx: Context<ResizeOperationAccount>,
+        extension_whitelist_mint_size: u16,
+    ) -> Result<()> {
+        instructions::resize_operation_account(ctx, extension_whitelist_mint_size)
+    }
+
+    /// Allow an address to create pools with the amm configs restricting pool creation, must be called by admin
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `creator`- The address allowed to create pool
+    ///
+    pub fn create_whitelist_creator(
+        ctx: Context<CreateWhitelistCreator>,
+        creator: Pubkey,
+    ) -> Result<()> {
+        instructions::create_whitelist_creator(ctx, creator)
+    }
+
+    /// Remove an address from the pool creator whitelist, must be called by admin
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn delete_whitelist_creator(ctx: Context<DeleteWhitelistCreator>) -> Result<()> {
+        instructions::delete_whitelist_creator(ctx)
+    }
+
+    /// Issue a token badge to a Token2022 mint with risky extensions, must be called by admin or operation owner
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn create_token_badge(ctx: Context<CreateTokenBadge>) -> Result<()> {
+        instructions::create_token_badge(ctx)
+    }
+
+    /// Issue token badges to multiple Token2022 mints in one instruction, must be called by admin or operation owner
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts, the token mint and its token badge of each mint are passed as remaining accounts
+    ///
+    pub fn create_token_badges<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CreateTokenBadges<'info>>,
+    ) -> Result<()> {
+        instructions::create_token_badges(ctx)
+    }
+
+    /// Revoke the token badge of a mint, must be called by admin or operation owner
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn delete_token_badge(ctx: Context<DeleteTokenBadge>) -> Result<()> {
+        instructions::delete_token_badge(ctx)
+    }
+
+    /// Set the fee and the allowed extensions of the self-service token badges, must be called by admin
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `fee`- The lamports paid to the fee receiver to issue a token badge, zero disables the self-service token badges
+    /// * `allowed_extensions`- The bitmap of the extension types requiring a token badge that can be unlocked by paying the fee
+    ///
+    pub fn set_token_badge_config(
+        ctx: Context<SetTokenBadgeConfig>,
+        fee: u64,
+        allowed_extensions: u64,
+    ) -> Result<()> {
+        instructions::set_token_badge_config(ctx, fee, allowed_extensions)
+    }
+
+    /// Issue a token badge to a Token2022 mint by paying the fee of the token badge config, the mint
+    /// is vetted on chain: it must have no freeze authority and only the extensions supported without
+    /// a badge or allowed by the config. Creating the badge of a mint which already has one does nothing
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn create_token_badge_with_fee(ctx: Context<CreateTokenBadgeWithFee>) -> Result<()> {
+        instructions::create_token_badge_with_fee(ctx)
+    }
+
     /// Transfer reward owner
     ///
     /// # Arguments
@@ -149,6 +583,8 @@ pub mod amm_v3 {
     /// * `ctx`- The context of accounts
     /// * `new_owner`- new owner pubkey
     ///
+    /// The admin timelock account must be passed in remaining accounts, fails if the timelock is enabled.
+    ///
     pub fn transfer_reward_owner<'a, 'b, 'c, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, TransferRewardOwner<'info>>,
         new_owner: Pubkey,
@@ -156,6 +592,63 @@ pub mod amm_v3 {
         instructions::transfer_reward_owner(ctx, new_owner)
     }
 
+    /// Refresh the admin heartbeat, must be called by admin periodically, `create_amm_config` refreshes it too.
+    /// Once the heartbeat expires, withdrawals are permitted for all pools not frozen regardless of status.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `expire_duration`- The inactive duration after which the heartbeat expires, zero keeps the current one
+    ///
+    pub fn refresh_admin_heartbeat(
+        ctx: Context<RefreshAdminHeartbeat>,
+        expire_duration: u64,
+    ) -> Result<()> {
+        instructions::refresh_admin_heartbeat(ctx, expire_duration)
+    }
+
+    /// Creates or extends the admin timelock, fee-rate changes, pool status changes and
+    /// reward owner transfers must be queued while the timelock is enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `delay`- The delay in seconds, can't be shortened directly
+    ///
+    pub fn update_admin_timelock(ctx: Context<UpdateAdminTimelock>, delay: u64) -> Result<()> {
+        instructions::update_admin_timelock(ctx, delay)
+    }
+
+    /// Queues an admin action, it can be executed after the timelock delay
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `action`- 0 update amm config, 1 update pool status, 2 transfer reward owner, 3 shorten the timelock delay
+    /// * `param`- The `update_amm_config` param when `action` is 0, otherwise 0
+    /// * `value`- The new value of the amm config param, pool status or timelock delay
+    /// * `new_key`- The new reward owner when `action` is 2
+    ///
+    pub fn queue_admin_action(
+        ctx: Context<QueueAdminAction>,
+        action: u8,
+        param: u8,
+        value: u64,
+        new_key: Pubkey,
+    ) -> Result<()> {
+        instructions::queue_admin_action(ctx, action, param, value, new_key)
+    }
+
+    /// Executes a queued admin action after the timelock delay
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn execute_admin_action(ctx: Context<ExecuteAdminAction>) -> Result<()> {
+        instructions::execute_admin_action(ctx)
+    }
+
     /// Initialize a reward info for a given pool and reward index
     ///
     /// # Arguments
@@ -166,8 +659,15 @@ pub mod amm_v3 {
     /// * `end_time` - reward end timestamp
     /// * `emissions_per_second_x64` - Token reward per second are earned per unit of liquidity.
     ///
-    pub fn initialize_reward(
-        ctx: Context<InitializeReward>,
+    /// Once the reward slots of the pool are full, the reward is initialized in the reward info extension
+    /// of the pool, which must be passed as the first remaining account and is created if needed.
+    ///
+    /// A native SOL reward uses the WSOL mint and is wrapped from the lamports of the funder when its
+    /// WSOL account is not enough. It's only unwrapped when claimed to the temporary WSOL account of the
+    /// claimer created by `create_native_token_account`, which is closed to the claimer.
+    ///
+    pub fn initialize_reward<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, InitializeReward<'info>>,
         param: InitializeRewardParam,
     ) -> Result<()> {
         instructions::initialize_reward(ctx, param)
@@ -178,20 +678,43 @@ pub mod amm_v3 {
     /// # Arguments
     ///
     /// * `ctx`- The context of accounts
-    /// * `reward_index` - the index to reward info
+    /// * `reward_index` - the index to reward info, the rewards from `REWARD_NUM` are in the reward info extension
+    ///
+    /// The accounts of the transfer hook of the reward mint follow the reward info extension in remaining accounts
+    /// if the mint has a transfer hook.
     ///
-    pub fn collect_remaining_rewards(
-        ctx: Context<CollectRemainingRewards>,
+    pub fn collect_remaining_rewards<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CollectRemainingRewards<'info>>,
         reward_index: u8,
     ) -> Result<()> {
         instructions::collect_remaining_rewards(ctx, reward_index)
     }
 
-    /// Update rewards info of the given pool, can be called for everyone
+    /// Refund the remaining reward tokens never emitted to the reward authority once the grace period
+    /// after the end of the reward is over, can be called by anyone
     ///
     /// # Arguments
     ///
     /// * `ctx`- The context of accounts
+    /// * `reward_index` - the index to reward info
+    ///
+    /// The accounts of the transfer hook of the reward mint are passed in remaining accounts if the mint has a transfer hook.
+    ///
+    pub fn finalize_reward_cycle<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, FinalizeRewardCycle<'info>>,
+        reward_index: u8,
+    ) -> Result<()> {
+        instructions::finalize_reward_cycle(ctx, reward_index)
+    }
+
+    /// Update rewards info of the given pools, can be called for everyone
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    /// The reward info extension of the pool is updated too if it is passed as the first remaining account.
+    /// More pools can be passed in remaining accounts, each followed by its reward info extension optionally.
     ///
     pub fn update_reward_infos<'a, 'b, 'c, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, UpdateRewardInfos<'info>>,
@@ -199,12 +722,134 @@ pub mod amm_v3 {
         instructions::update_reward_infos(ctx)
     }
 
+    /// Create the reward extension of a position opened before the pool had rewards in its reward info extension
+    /// or vesting rewards,
+    /// required to change the liquidity of the position once the pool has any, can be called for everyone
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn initialize_position_reward_extension(
+        ctx: Context<InitializePositionRewardExtension>,
+    ) -> Result<()> {
+        instructions::initialize_position_reward_extension(ctx)
+    }
+
+    /// Accumulate the in-range liquidity seconds of the given position and its pool,
+    /// can be called for everyone
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn update_liquidity_seconds(ctx: Context<UpdateLiquiditySeconds>) -> Result<()> {
+        instructions::update_liquidity_seconds(ctx)
+    }
+
+    /// Extend the observation account of the pool to store more observations for a longer TWAP window,
+    /// can be called for everyone
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `observation_cardinality_next` - The number of observations to store, takes effect when the
+    /// observation index wraps around. The account size can grow at most 10KB in an instruction.
+    ///
+    pub fn increase_observation_cardinality(
+        ctx: Context<IncreaseObservationCardinality>,
+        observation_cardinality_next: u16,
+    ) -> Result<()> {
+        instructions::increase_observation_cardinality(ctx, observation_cardinality_next)
+    }
+
+    /// Read the time-weighted average price of the pool over a window from the observations,
+    /// returns the average tick and sqrt price as return data
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts, the optional TwapSnapshot PDA can be passed in remaining accounts
+    /// to store the result, followed by the payer and system program if it is not created yet
+    /// * `window_seconds` - The length of the window ending now, in seconds
+    ///
+    pub fn get_twap<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, GetTwap<'info>>,
+        window_seconds: u32,
+    ) -> Result<TwapResult> {
+        instructions::get_twap(ctx, window_seconds)
+    }
+
+    /// Create the price feed account of a pool, can be called for everyone
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn create_price_feed(ctx: Context<CreatePriceFeed>) -> Result<()> {
+        instructions::create_price_feed(ctx)
+    }
+
+    /// Refresh the price feed account of a pool with the current pool price, can be called for everyone
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn update_price_feed(ctx: Context<UpdatePriceFeed>) -> Result<()> {
+        instructions::update_price_feed(ctx)
+    }
+
+    /// Close the observation account of an idle pool to reclaim the rent to the pool creator, can be called for everyone.
+    /// The pool must have no in-range liquidity and no price move for 30 days.
+    /// Swaps of the pool fail until the observation account is reinitialized.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn close_observation(ctx: Context<CloseObservation>) -> Result<()> {
+        instructions::close_observation(ctx)
+    }
+
+    /// Create the closed observation account of a pool again to reactivate it, can be called for everyone
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn reinitialize_observation(ctx: Context<ReinitializeObservation>) -> Result<()> {
+        instructions::reinitialize_observation(ctx)
+    }
+
+    /// Close an empty tick array and refund the rent to its rent payer, can be called for everyone
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts, the tickarray_bitmap_extension must be passed in remaining accounts
+    /// if the tick array is out of the default bitmap range
+    ///
+    pub fn close_tick_array<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CloseTickArray<'info>>,
+    ) -> Result<()> {
+        instructions::close_tick_array(ctx)
+    }
+
+    /// Close a deprecated protocol position and refund the rent to the pool creator, can be called for everyone
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn close_protocol_position(ctx: Context<CloseProtocolPosition>) -> Result<()> {
+        instructions::close_protocol_position(ctx)
+    }
+
     /// Restset reward param, start a new reward cycle or extend the current cycle.
     ///
     /// # Arguments
     ///
     /// * `ctx` - The context of accounts
-    /// * `reward_index` - The index of reward token in the pool.
+    /// * `reward_index` - The index of reward token in the pool, the rewards from `REWARD_NUM` are in the reward info extension.
     /// * `emissions_per_second_x64` - The per second emission reward, when extend the current cycle,
     ///    new value can't be less than old value
     /// * `open_time` - reward open timestamp, must be set when state a new cycle
@@ -226,7 +871,81 @@ pub mod amm_v3 {
         )
     }
 
-    /// Collect the protocol fee accrued to the pool
+    /// Set the vesting schedule of a reward before it opens, the rewards owed to a position
+    /// become claimable linearly after the cliff, counted from the later of the reward open time
+    /// and the time the position started to vest
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `reward_index` - The index of the reward in the pool
+    /// * `cliff_duration` - The seconds before any reward is claimable
+    /// * `vesting_duration` - The seconds for the rewards to be fully claimable, 0 disables the vesting
+    ///
+    pub fn set_reward_vesting(
+        ctx: Context<SetRewardVesting>,
+        reward_index: u8,
+        cliff_duration: u64,
+        vesting_duration: u64,
+    ) -> Result<()> {
+        instructions::set_reward_vesting(ctx, reward_index, cliff_duration, vesting_duration)
+    }
+
+    /// Deposit reward tokens to a running or upcoming reward of the pool, which extends its end time
+    /// at the current emissions rate, can be called for everyone
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `reward_index` - The index of the reward in the pool
+    /// * `amount` - The amount of reward tokens to deposit, including the transfer fee
+    ///
+    /// The extra accounts of the transfer hook of the reward mint are passed in remaining accounts.
+    ///
+    pub fn fund_reward<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, FundReward<'info>>,
+        reward_index: u8,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::fund_reward(ctx, reward_index, amount)
+    }
+
+    /// Record the fee growths, liquidity and price of the pool in its checkpoint ring buffer,
+    /// at most once per hour, can be called for everyone
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn checkpoint_pool(ctx: Context<CheckpointPool>) -> Result<()> {
+        instructions::checkpoint_pool(ctx)
+    }
+
+    /// Create the account of the rolling swap volume and fees of the pool by day,
+    /// which is updated by the swaps passing it in remaining accounts, can be called for everyone
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn create_pool_stats(ctx: Context<CreatePoolStats>) -> Result<()> {
+        instructions::create_pool_stats(ctx)
+    }
+
+    /// Creates the cumulative swap volume and fees account of the trader in the pool, opted in by the trader
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    /// Note: The account is updated by the swaps of the trader passing it in remaining accounts.
+    ///
+    pub fn create_trader_stats(ctx: Context<CreateTraderStats>) -> Result<()> {
+        instructions::create_trader_stats(ctx)
+    }
+
+    /// Collect the protocol fee accrued to the pool, the admin or config owner can send it to any accounts,
+    /// anyone else, e.g. a keeper, can only send it to the associated token accounts of the config owner
     ///
     /// # Arguments
     ///
@@ -234,8 +953,12 @@ pub mod amm_v3 {
     /// * `amount_0_requested` - The maximum amount of token_0 to send, can be 0 to collect fees in only token_1
     /// * `amount_1_requested` - The maximum amount of token_1 to send, can be 0 to collect fees in only token_0
     ///
-    pub fn collect_protocol_fee(
-        ctx: Context<CollectProtocolFee>,
+    /// For a partner amm config, remaining accounts must be the partner config and the partner's
+    /// token_0 and token_1 accounts, the partner part of collected fee is sent to them.
+    /// The accounts of the transfer hooks of the mints follow them if the mints have transfer hooks.
+    ///
+    pub fn collect_protocol_fee<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CollectProtocolFee<'info>>,
         amount_0_requested: u64,
         amount_1_requested: u64,
     ) -> Result<()> {
@@ -250,16 +973,126 @@ pub mod amm_v3 {
     /// * `amount_0_requested` - The maximum amount of token_0 to send, can be 0 to collect fees in only token_1
     /// * `amount_1_requested` - The maximum amount of token_1 to send, can be 0 to collect fees in only token_0
     ///
-    pub fn collect_fund_fee(
-        ctx: Context<CollectFundFee>,
+    /// The accounts of the transfer hooks of the mints are passed in remaining accounts if the mints have transfer hooks.
+    ///
+    pub fn collect_fund_fee<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CollectFundFee<'info>>,
         amount_0_requested: u64,
         amount_1_requested: u64,
     ) -> Result<()> {
         instructions::collect_fund_fee(ctx, amount_0_requested, amount_1_requested)
     }
 
+    /// Deploy the fund fee accrued to the pool into a protocol owned position, whose nft is held by
+    /// the protocol owned liquidity PDA of the pool. The position is opened empty by the fund owner
+    /// with the PDA as the nft owner.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `liquidity` - The liquidity to add to the position
+    /// * `amount_0_max` - The maximum amount of token_0 fund fees to deploy
+    /// * `amount_1_max` - The maximum amount of token_1 fund fees to deploy
+    ///
+    pub fn deploy_protocol_owned_liquidity<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, DeployProtocolOwnedLiquidity<'info>>,
+        liquidity: u128,
+        amount_0_max: u64,
+        amount_1_max: u64,
+    ) -> Result<()> {
+        instructions::deploy_protocol_owned_liquidity(ctx, liquidity, amount_0_max, amount_1_max)
+    }
+
+    /// Withdraw the liquidity of a protocol owned position with its earned fees and rewards
+    /// to the fund owner or the fund fee recipient of the pool
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `liquidity` - The liquidity to withdraw from the position
+    /// * `amount_0_min` - The minimum amount of token_0 to withdraw
+    /// * `amount_1_min` - The minimum amount of token_1 to withdraw
+    ///
+    pub fn withdraw_protocol_owned_liquidity<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, WithdrawProtocolOwnedLiquidity<'info>>,
+        liquidity: u128,
+        amount_0_min: u64,
+        amount_1_min: u64,
+    ) -> Result<()> {
+        instructions::withdraw_protocol_owned_liquidity(ctx, liquidity, amount_0_min, amount_1_min)
+    }
+
+    /// Sweep the dust of the vaults to the fund owner, the dust is the vault balance above
+    /// the tokens backing the liquidity, the unclaimed LP fees and the protocol, fund and referral fees
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts, all initialized tick arrays of the pool must be passed
+    /// in remaining accounts to compute the tokens backing the liquidity, followed by the extra
+    /// accounts of the transfer hooks of the vault mints
+    ///
+    pub fn sweep_dust<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, SweepDust<'info>>,
+    ) -> Result<()> {
+        instructions::sweep_dust(ctx)
+    }
+
+    /// Assert that the vaults of the pool cover the amounts owed out of them: the tokens backing
+    /// the liquidity, the unclaimed LP fees, the protocol, fund and referral fees and the unclaimed rewards.
+    /// Emits the amounts owed and fails if any vault falls short, anyone can call it
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts, the reward info extension if the pool has extension rewards,
+    /// the reward vaults of the initialized rewards and all initialized tick arrays of the pool
+    /// must be passed in remaining accounts
+    ///
+    pub fn assert_pool_solvency<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, AssertPoolSolvency<'info>>,
+    ) -> Result<()> {
+        instructions::assert_pool_solvency(ctx)
+    }
+
+    /// #[deprecated(note = "Use `open_position_with_token22_nft` instead.")]
+    /// Creates a new position wrapped in a NFT
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `tick_lower_index` - The low boundary of market
+    /// * `tick_upper_index` - The upper boundary of market
+    /// * `tick_array_lower_start_index` - The start index of tick array which include tick low
+    /// * `tick_array_upper_start_index` - The start index of tick array which include tick upper
+    /// * `liquidity` - The liquidity to be added
+    /// * `amount_0_max` - The max amount of token_0 to spend, which serves as a slippage check
+    /// * `amount_1_max` - The max amount of token_1 to spend, which serves as a slippage check
+    ///
+    pub fn open_position<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, OpenPosition<'info>>,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+        tick_array_lower_start_index: i32,
+        tick_array_upper_start_index: i32,
+        liquidity: u128,
+        amount_0_max: u64,
+        amount_1_max: u64,
+    ) -> Result<()> {
+        instructions::open_position_v1(
+            ctx,
+            liquidity,
+            amount_0_max,
+            amount_1_max,
+            tick_lower_index,
+            tick_upper_index,
+            tick_array_lower_start_index,
+            tick_array_upper_start_index,
+            true,
+            None,
+        )
+    }
+
     /// #[deprecated(note = "Use `open_position_with_token22_nft` instead.")]
-    /// Creates a new position wrapped in a NFT
+    /// Creates a new position wrapped in a NFT, support Token2022
     ///
     /// # Arguments
     ///
@@ -268,12 +1101,14 @@ pub mod amm_v3 {
     /// * `tick_upper_index` - The upper boundary of market
     /// * `tick_array_lower_start_index` - The start index of tick array which include tick low
     /// * `tick_array_upper_start_index` - The start index of tick array which include tick upper
-    /// * `liquidity` - The liquidity to be added
+    /// * `liquidity` - The liquidity to be added, if zero, and the base_flage is specified, calculate liquidity base amount_0_max or amount_1_max according base_flag, otherwise open position with zero liquidity
     /// * `amount_0_max` - The max amount of token_0 to spend, which serves as a slippage check
     /// * `amount_1_max` - The max amount of token_1 to spend, which serves as a slippage check
+    /// * `with_metadata` - The flag indicating whether to create NFT mint metadata
+    /// * `base_flag` - if the liquidity specified as zero, true: calculate liquidity base amount_0_max otherwise base amount_1_max
     ///
-    pub fn open_position<'a, 'b, 'c: 'info, 'info>(
-        ctx: Context<'a, 'b, 'c, 'info, OpenPosition<'info>>,
+    pub fn open_position_v2<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, OpenPositionV2<'info>>,
         tick_lower_index: i32,
         tick_upper_index: i32,
         tick_array_lower_start_index: i32,
@@ -281,8 +1116,10 @@ pub mod amm_v3 {
         liquidity: u128,
         amount_0_max: u64,
         amount_1_max: u64,
+        with_metadata: bool,
+        base_flag: Option<bool>,
     ) -> Result<()> {
-        instructions::open_position_v1(
+        instructions::open_position_v2(
             ctx,
             liquidity,
             amount_0_max,
@@ -291,13 +1128,12 @@ pub mod amm_v3 {
             tick_upper_index,
             tick_array_lower_start_index,
             tick_array_upper_start_index,
-            true,
-            None,
+            with_metadata,
+            base_flag,
         )
     }
 
-    /// #[deprecated(note = "Use `open_position_with_token22_nft` instead.")]
-    /// Creates a new position wrapped in a NFT, support Token2022
+    /// Creates a new position wrapped in a Token2022 NFT without relying on metadata_program and metadata_account, reduce the cost for user to create a personal position.
     ///
     /// # Arguments
     ///
@@ -312,8 +1148,14 @@ pub mod amm_v3 {
     /// * `with_metadata` - The flag indicating whether to create NFT mint metadata
     /// * `base_flag` - if the liquidity specified as zero, true: calculate liquidity base amount_0_max otherwise base amount_1_max
     ///
-    pub fn open_position_v2<'a, 'b, 'c: 'info, 'info>(
-        ctx: Context<'a, 'b, 'c, 'info, OpenPositionV2<'info>>,
+    /// The missing tick arrays are created with payer funds, and so is the tickarray_bitmap_extension
+    /// passed in remaining accounts if the tick arrays are out of the default bitmap range.
+    /// The tokens are paid by the payer, unless the owner of the token accounts signs as the first remaining account.
+    /// Once the pool has rewards in its reward info extension, the extension must be passed in remaining accounts
+    /// of all liquidity and swap instructions, along with the reward extension of the position, which is created here.
+    ///
+    pub fn open_position_with_token22_nft<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, OpenPositionWithToken22Nft<'info>>,
         tick_lower_index: i32,
         tick_upper_index: i32,
         tick_array_lower_start_index: i32,
@@ -324,7 +1166,7 @@ pub mod amm_v3 {
         with_metadata: bool,
         base_flag: Option<bool>,
     ) -> Result<()> {
-        instructions::open_position_v2(
+        instructions::open_position_with_token22_nft(
             ctx,
             liquidity,
             amount_0_max,
@@ -335,10 +1177,11 @@ pub mod amm_v3 {
             tick_array_upper_start_index,
             with_metadata,
             base_flag,
+            Vec::new(),
         )
     }
 
-    /// Creates a new position wrapped in a Token2022 NFT without relying on metadata_program and metadata_account, reduce the cost for user to create a personal position.
+    /// Creates a new position wrapped in a Token2022 NFT like `open_position_with_token22_nft`, with options
     ///
     /// # Arguments
     ///
@@ -352,8 +1195,13 @@ pub mod amm_v3 {
     /// * `amount_1_max` - The max amount of token_1 to spend, which serves as a slippage check
     /// * `with_metadata` - The flag indicating whether to create NFT mint metadata
     /// * `base_flag` - if the liquidity specified as zero, true: calculate liquidity base amount_0_max otherwise base amount_1_max
+    /// * `options` - The options of the position, like the timestamp before which its liquidity can't be decreased,
+    /// the name, symbol and uri of the NFT metadata instead of the default ones, at most 32, 10 and 200 bytes,
+    /// or a non transferable NFT, so the position stays bound to the owner until it is closed
     ///
-    pub fn open_position_with_token22_nft<'a, 'b, 'c: 'info, 'info>(
+    /// The remaining accounts are like the ones of `open_position_with_token22_nft`.
+    ///
+    pub fn open_position_with_token22_nft_v2<'a, 'b, 'c: 'info, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, OpenPositionWithToken22Nft<'info>>,
         tick_lower_index: i32,
         tick_upper_index: i32,
@@ -364,6 +1212,7 @@ pub mod amm_v3 {
         amount_1_max: u64,
         with_metadata: bool,
         base_flag: Option<bool>,
+        options: Vec<OpenPositionOption>,
     ) -> Result<()> {
         instructions::open_position_with_token22_nft(
             ctx,
@@ -376,21 +1225,227 @@ pub mod amm_v3 {
             tick_array_upper_start_index,
             with_metadata,
             base_flag,
+            options,
+        )
+    }
+
+    /// Creates a new position represented by an MPL Core asset instead of an NFT mint and token account,
+    /// which costs less rent. The asset is used as the position NFT account by `increase_liquidity_v2`,
+    /// `decrease_liquidity_v2` and `close_position`, which burns it.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `tick_lower_index` - The low boundary of market
+    /// * `tick_upper_index` - The upper boundary of market
+    /// * `tick_array_lower_start_index` - The start index of tick array which include tick low
+    /// * `tick_array_upper_start_index` - The start index of tick array which include tick upper
+    /// * `liquidity` - The liquidity to be added, if zero, and the base_flage is specified, calculate liquidity base amount_0_max or amount_1_max according base_flag, otherwise open position with zero liquidity
+    /// * `amount_0_max` - The max amount of token_0 to spend, which serves as a slippage check
+    /// * `amount_1_max` - The max amount of token_1 to spend, which serves as a slippage check
+    /// * `base_flag` - if the liquidity specified as zero, true: calculate liquidity base amount_0_max otherwise base amount_1_max
+    /// * `locked_until` - The liquidity of the position can't be decreased before this timestamp if specified
+    ///
+    /// The remaining accounts are like the ones of `open_position_with_token22_nft`.
+    ///
+    pub fn open_position_with_core_asset<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, OpenPositionWithCoreAsset<'info>>,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+        tick_array_lower_start_index: i32,
+        tick_array_upper_start_index: i32,
+        liquidity: u128,
+        amount_0_max: u64,
+        amount_1_max: u64,
+        base_flag: Option<bool>,
+        locked_until: Option<u64>,
+    ) -> Result<()> {
+        instructions::open_position_with_core_asset(
+            ctx,
+            liquidity,
+            amount_0_max,
+            amount_1_max,
+            tick_lower_index,
+            tick_upper_index,
+            tick_array_lower_start_index,
+            tick_array_upper_start_index,
+            base_flag,
+            locked_until,
         )
     }
 
+    /// Opens up to 5 positions of the pool in different tick ranges at once, each wrapped in a Token2022 NFT
+    /// without metadata like `open_position_with_token22_nft`, all or none of them are opened.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `params` - The tick range, liquidity and slippage of each position, in the order of the
+    /// groups of position accounts in remaining accounts
+    ///
+    /// The remaining accounts are the position_nft_mint, position_nft_account, personal_position,
+    /// tick_array_lower and tick_array_upper of each position, each followed by
+    /// `remaining_accounts_len` accounts used like the remaining accounts of `open_position_with_token22_nft`.
+    /// Tick arrays can be shared by the positions and the tokens of each position are transferred separately.
+    ///
+    pub fn open_positions_batch<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, OpenPositionsBatch<'info>>,
+        params: Vec<OpenPositionParam>,
+    ) -> Result<()> {
+        instructions::open_positions_batch(ctx, params)
+    }
+
+    /// Moves all liquidity of a position to a new tick range, optionally swapping the withdrawn tokens
+    /// through the pool in between, the position NFT and personal position are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `param` - The new tick range, the swap of the withdrawn tokens and the minimum liquidity
+    /// of the reopened position
+    ///
+    /// The remaining accounts are the `decrease_remaining_accounts_len` accounts used to decrease liquidity,
+    /// the `increase_remaining_accounts_len` accounts used to increase liquidity and the accounts of the swap.
+    ///
+    pub fn rebalance_position<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, RebalancePosition<'info>>,
+        param: RebalanceParam,
+    ) -> Result<()> {
+        instructions::rebalance_position(ctx, param)
+    }
+
+    /// Withdraws all liquidity of a position from a pool and deposits the proceeds into a range of another
+    /// pool of the same mint pair, the position NFT and personal position are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `param` - The tick range in the target pool and the minimum liquidity of the migrated position
+    ///
+    /// The remaining accounts are the `decrease_remaining_accounts_len` accounts used to decrease liquidity
+    /// in the pool withdrawn from, including the reward accounts to collect all rewards of the position,
+    /// followed by the accounts used to increase liquidity in the target pool.
+    ///
+    pub fn migrate_position<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, MigratePosition<'info>>,
+        param: MigratePositionParam,
+    ) -> Result<()> {
+        instructions::migrate_position(ctx, param)
+    }
+
     /// Close the user's position and NFT account. If the NFT mint belongs to token2022, it will also be closed and the funds returned to the NFT owner.
     ///
     /// # Arguments
     ///
     /// * `ctx` - The context of accounts
     ///
+    /// The position reward extension of the position must be passed in remaining accounts if it is created,
+    /// after the MPL Core program of an MPL Core asset. It must have no rewards owed and is closed with the position.
+    ///
     pub fn close_position<'a, 'b, 'c, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, ClosePosition<'info>>,
     ) -> Result<()> {
         instructions::close_position(ctx)
     }
 
+    /// Exit the position in one instruction: remove all the liquidity, collect the fees and
+    /// the rewards, then close the position and the NFT accounts and refund the rent to the NFT owner
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `amount_0_min` - The minimum amount of token_0 to receive from the liquidity, excluding the fees
+    /// * `amount_1_min` - The minimum amount of token_1 to receive from the liquidity, excluding the fees
+    ///
+    /// The remaining accounts are the same as `decrease_liquidity_v2`, preceded by the MPL Core program
+    /// if the position is an MPL Core asset. The reward accounts of every initialized reward must be passed,
+    /// the position reward extension of the position is closed with it.
+    ///
+    pub fn exit_position<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ExitPosition<'info>>,
+        amount_0_min: u64,
+        amount_1_min: u64,
+    ) -> Result<()> {
+        instructions::exit_position(ctx, amount_0_min, amount_1_min)
+    }
+
+    /// Permanently lock the liquidity of a position, the liquidity can never be decreased after,
+    /// the fees and rewards of the position can only be collected by the fee recipient.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `fee_recipient` - The signer allowed to collect the fees and rewards of the locked position
+    /// * `burn_nft` - Whether to burn the position NFT and close the NFT account
+    ///
+    pub fn lock_position<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, LockPosition<'info>>,
+        fee_recipient: Pubkey,
+        burn_nft: bool,
+    ) -> Result<()> {
+        instructions::lock_position(ctx, fee_recipient, burn_nft)
+    }
+
+    /// Lock the liquidity of a position until the timestamp, the lock can only be extended
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `locked_until` - The liquidity of the position can't be decreased before this timestamp
+    ///
+    pub fn lock_position_until(ctx: Context<LockPositionUntil>, locked_until: u64) -> Result<()> {
+        instructions::lock_position_until(ctx, locked_until)
+    }
+
+    /// Approve an operator to manage the position without holding the NFT, replaces the previous operator.
+    /// The operator account must be passed as the first remaining account of the liquidity instructions.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `operator` - The operator to approve
+    /// * `permission` - 0: collect fees and rewards only, 1: rebalance within the tick bounds, 2: full
+    /// * `tick_lower_bound` - The lowest tick of the position a rebalance operator can manage
+    /// * `tick_upper_bound` - The highest tick of the position a rebalance operator can manage
+    ///
+    pub fn approve_operator(
+        ctx: Context<ApproveOperator>,
+        operator: Pubkey,
+        permission: u8,
+        tick_lower_bound: i32,
+        tick_upper_bound: i32,
+    ) -> Result<()> {
+        instructions::approve_operator(
+            ctx,
+            operator,
+            permission,
+            tick_lower_bound,
+            tick_upper_bound,
+        )
+    }
+
+    /// Revoke the operator of the position and close the operator account
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    ///
+    pub fn revoke_operator(ctx: Context<RevokeOperator>) -> Result<()> {
+        instructions::revoke_operator(ctx)
+    }
+
+    /// Collect the fees and rewards of a locked position, support Token2022
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts, the remaining accounts are the same as decrease_liquidity_v2
+    ///
+    pub fn collect_locked_position_fees<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CollectLockedPositionFees<'info>>,
+    ) -> Result<()> {
+        instructions::collect_locked_position_fees(ctx)
+    }
+
     /// #[deprecated(note = "Use `increase_liquidity_v2` instead.")]
     /// Increases liquidity with a exist position, with amount paid by `payer`
     ///
@@ -462,6 +1517,11 @@ pub mod amm_v3 {
     /// * `amount_0_min` - The minimum amount of token_0 that should be accounted for the burned liquidity
     /// * `amount_1_min` - The minimum amount of token_1 that should be accounted for the burned liquidity
     ///
+    /// The reward groups in remaining accounts are `(reward_vault, recipient_token_account, reward_mint)`
+    /// of each initialized reward in order, the NFT owner can send the rewards to any token account
+    /// of the reward mint, while a restricted operator can only send them to the NFT owner.
+    /// The accounts of the transfer hooks of the vault and reward mints follow them if the mints have transfer hooks.
+    ///
     pub fn decrease_liquidity_v2<'a, 'b, 'c: 'info, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, DecreaseLiquidityV2<'info>>,
         liquidity: u128,
@@ -504,10 +1564,18 @@ pub mod amm_v3 {
     ///
     /// * `ctx` - The context of accounts
     /// * `amount` - Arranged in pairs with other_amount_threshold. (amount_in, amount_out_minimum) or (amount_out, amount_in_maximum)
-    /// * `other_amount_threshold` - For slippage check
+    /// * `other_amount_threshold` - For slippage check, compared with the net amount actually received
+    /// (amount_out_minimum) or paid (amount_in_maximum) by the user's token accounts, so the
+    /// Token2022 transfer fee at the execution epoch is always accounted for
     /// * `sqrt_price_limit` - The Q64.64 sqrt price √P limit. If zero for one, the price cannot
     /// * `is_base_input` - swap base input or swap base output
     ///
+    /// An optional referral account can be passed as the first remaining account,
+    /// the referral part of the trade fee is accrued to it.
+    /// The optional price feed account of the pool can be passed next, it is refreshed by the swap.
+    /// The tick arrays can be passed in any order and include arrays the swap does not cross.
+    /// The accounts of the transfer hooks of the mints must follow the tick arrays if the mints have transfer hooks.
+    ///
     pub fn swap_v2<'a, 'b, 'c: 'info, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, SwapSingleV2<'info>>,
         amount: u64,
@@ -521,9 +1589,135 @@ pub mod amm_v3 {
             other_amount_threshold,
             sqrt_price_limit_x64,
             is_base_input,
+            None,
+            None,
+            false,
+        )
+    }
+
+    /// Swaps like `swap_v2` with the optional guards of the price impact and the slippage,
+    /// the swap hook of the pool is invoked with the result of the swap and the execution price is
+    /// checked against the external oracle of the pool
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `amount` - Arranged in pairs with other_amount_threshold. (amount_in, amount_out_minimum) or (amount_out, amount_in_maximum)
+    /// * `other_amount_threshold` - For slippage check, compared with the net amount actually received or paid
+    /// * `sqrt_price_limit` - The Q64.64 sqrt price √P limit. If zero for one, the price cannot
+    /// * `is_base_input` - swap base input or swap base output
+    /// * `max_price_impact_bps` - Optional, the maximum price move of the pool caused by the swap, in basis points
+    /// * `slippage` - Optional, the slippage in basis points from the amount quoted at the reference price
+    /// supplied by the client and the trade fee, the stricter of it and other_amount_threshold applies.
+    /// The quote does not include the Token2022 transfer fees
+    ///
+    pub fn swap_v3<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, SwapSingleV2<'info>>,
+        amount: u64,
+        other_amount_threshold: u64,
+        sqrt_price_limit_x64: u128,
+        is_base_input: bool,
+        max_price_impact_bps: Option<u16>,
+        slippage: Option<SwapSlippage>,
+    ) -> Result<()> {
+        instructions::swap_v2(
+            ctx,
+            amount,
+            other_amount_threshold,
+            sqrt_price_limit_x64,
+            is_base_input,
+            max_price_impact_bps,
+            slippage,
+            true,
+        )
+    }
+
+    /// Swaps like `swap_v2`, paying or receiving native SOL when the input or output token is WSOL
+    /// and its user token account is none. The native SOL is wrapped into a temporary WSOL token
+    /// account of the payer, which is closed to the payer after the swap
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts, the remaining accounts are like the ones of `swap_v2`
+    /// * `amount` - Arranged in pairs with other_amount_threshold. (amount_in, amount_out_minimum) or (amount_out, amount_in_maximum)
+    /// * `other_amount_threshold` - For slippage check, the native SOL wrapped for a base output swap
+    /// * `sqrt_price_limit` - The Q64.64 sqrt price √P limit. If zero for one, the price cannot
+    /// * `is_base_input` - swap base input or swap base output
+    /// * `max_price_impact_bps` - Optional, the maximum price move of the pool caused by the swap, in basis points
+    /// * `slippage` - Optional, like the one of `swap_v3`, the native SOL wrapped is still other_amount_threshold
+    ///
+    pub fn swap_v2_native<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, SwapSingleV2Native<'info>>,
+        amount: u64,
+        other_amount_threshold: u64,
+        sqrt_price_limit_x64: u128,
+        is_base_input: bool,
+        max_price_impact_bps: Option<u16>,
+        slippage: Option<SwapSlippage>,
+    ) -> Result<()> {
+        instructions::swap_v2_native(
+            ctx,
+            amount,
+            other_amount_threshold,
+            sqrt_price_limit_x64,
+            is_base_input,
+            max_price_impact_bps,
+            slippage,
         )
     }
 
+    /// Creates the temporary WSOL token account of the owner at the PDA of `[NATIVE_TOKEN_ACCOUNT_SEED, owner]`.
+    /// Native SOL rewards claimed to it are unwrapped, the account is closed to the owner after the claim
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn create_native_token_account(ctx: Context<CreateNativeTokenAccount>) -> Result<()> {
+        instructions::create_native_token_account(ctx)
+    }
+
+    /// Creates a referral account to accrue the referral fee of the given pool for the referrer
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn create_referral_account(ctx: Context<CreateReferralAccount>) -> Result<()> {
+        instructions::create_referral_account(ctx)
+    }
+
+    /// Detects the type of any program-owned account and returns its key fields as return data
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn inspect_account(ctx: Context<InspectAccount>) -> Result<AccountInspection> {
+        instructions::inspect_account(ctx)
+    }
+
+    /// Upgrades a pool, personal position or tick array to the current layout version,
+    /// growing the account if the new layout is larger. Anyone can migrate an account
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        instructions::migrate_account(ctx)
+    }
+
+    /// Collect the referral fee accrued to the referrer
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn collect_referral_fee(ctx: Context<CollectReferralFee>) -> Result<()> {
+        instructions::collect_referral_fee(ctx)
+    }
+
     /// Swap token for as much as possible of another token across the path provided, base input
     ///
     /// # Arguments
@@ -539,4 +1733,46 @@ pub mod amm_v3 {
     ) -> Result<()> {
         instructions::swap_router_base_in(ctx, amount_in, amount_out_minimum)
     }
+
+    /// Swap token for as much as possible of another token across the path provided, base input,
+    /// like `swap_router_base_in` with the accounts of each hop bounded and its transfer hook accounts
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `amount_in` - Token amount to be swapped in
+    /// * `amount_out_minimum` - Panic if output amount is below minimum amount. For slippage.
+    ///
+    /// The remaining accounts are the amm_config, pool_state, output_token_account, input_vault,
+    /// output_vault, output_token_mint and observation_state of each hop, each followed by its
+    /// tickarray_bitmap_extension, reward_info_extension and tick arrays, then the transfer hook accounts of its mints.
+    /// The mints of a hop may be SPL or Token2022 mints, the transfer fees are charged on each hop
+    /// and the output of a hop net of the fees is the input of the next hop.
+    ///
+    pub fn swap_router_base_in_v2<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, SwapRouterBaseIn<'info>>,
+        amount_in: u64,
+        amount_out_minimum: u64,
+    ) -> Result<()> {
+        instructions::swap_router_base_in_v2(ctx, amount_in, amount_out_minimum)
+    }
+
+    /// Splits a swap across two pools of the same mint pair, usually of different fee tiers,
+    /// both parts are swapped atomically and the slippage is checked on their total amount
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `param` - The amount, the slippage threshold and the part of the amount swapped in the first pool
+    ///
+    /// The remaining accounts are the `remaining_accounts_a_len` accounts of the first pool, the
+    /// tickarray_bitmap_extension, reward_info_extension and tick arrays, followed by those of the second pool,
+    /// then the accounts of the transfer hooks of the mints if the mints have transfer hooks.
+    ///
+    pub fn swap_split<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, SwapSplit<'info>>,
+        param: SwapSplitParam,
+    ) -> Result<()> {
+        instructions::swap_split(ctx, param)
+    }
 }
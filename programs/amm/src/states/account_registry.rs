@@ -0,0 +1,183 @@
+use super::*;
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+/// All account types owned by the program, the discriminator identifies the type
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AccountType {
+    AmmConfig,
+    PoolState,
+    PersonalPosition,
+    ProtocolPosition,
+    TickArray,
+    TickArrayBitmapExtension,
+    Observation,
+    Operation,
+    PartnerConfig,
+    Referral,
+    TokenBadge,
+    AdminHeartbeat,
+    AmmConfigPendingOwner,
+    AdminTimelock,
+    AdminAction,
+    TwapSnapshot,
+    PriceFeed,
+    PositionLock,
+    PositionOperator,
+    RewardInfoExtension,
+    PositionRewardExtension,
+    PoolCheckpoint,
+    PoolMetadata,
+    PoolStats,
+    RewardDistribution,
+    ClaimStatus,
+    TraderStats,
+    AmmConfigExtension,
+    WhitelistCreator,
+}
+
+impl AccountType {
+    pub const ALL: [AccountType; 29] = [
+        AccountType::AmmConfig,
+        AccountType::PoolState,
+        AccountType::PersonalPosition,
+        AccountType::ProtocolPosition,
+        AccountType::TickArray,
+        AccountType::TickArrayBitmapExtension,
+        AccountType::Observation,
+        AccountType::Operation,
+        AccountType::PartnerConfig,
+        AccountType::Referral,
+        AccountType::TokenBadge,
+        AccountType::AdminHeartbeat,
+        AccountType::AmmConfigPendingOwner,
+        AccountType::AdminTimelock,
+        AccountType::AdminAction,
+        AccountType::TwapSnapshot,
+        AccountType::PriceFeed,
+        AccountType::PositionLock,
+        AccountType::PositionOperator,
+        AccountType::RewardInfoExtension,
+        AccountType::PositionRewardExtension,
+        AccountType::PoolCheckpoint,
+        AccountType::PoolMetadata,
+        AccountType::PoolStats,
+        AccountType::RewardDistribution,
+        AccountType::ClaimStatus,
+        AccountType::TraderStats,
+        AccountType::AmmConfigExtension,
+        AccountType::WhitelistCreator,
+    ];
+
+    pub fn discriminator(self) -> [u8; 8] {
+        match self {
+            AccountType::AmmConfig => AmmConfig::discriminator(),
+            AccountType::PoolState => PoolState::discriminator(),
+            AccountType::PersonalPosition => PersonalPositionState::discriminator(),
+            AccountType::ProtocolPosition => ProtocolPositionState::discriminator(),
+            AccountType::TickArray => TickArrayState::discriminator(),
+            AccountType::TickArrayBitmapExtension => TickArrayBitmapExtension::discriminator(),
+            AccountType::Observation => ObservationState::discriminator(),
+            AccountType::Operation => OperationState::discriminator(),
+            AccountType::PartnerConfig => PartnerConfig::discriminator(),
+            AccountType::Referral => ReferralState::discriminator(),
+            AccountType::TokenBadge => TokenBadge::discriminator(),
+            AccountType::AdminHeartbeat => AdminHeartbeat::discriminator(),
+            AccountType::AmmConfigPendingOwner => AmmConfigPendingOwner::discriminator(),
+            AccountType::AdminTimelock => AdminTimelock::discriminator(),
+            AccountType::AdminAction => AdminActionState::discriminator(),
+            AccountType::TwapSnapshot => TwapSnapshot::discriminator(),
+            AccountType::PriceFeed => PriceFeed::discriminator(),
+            AccountType::PositionLock => PositionLockState::discriminator(),
+            AccountType::PositionOperator => PositionOperatorState::discriminator(),
+            AccountType::RewardInfoExtension => RewardInfoExtension::discriminator(),
+            AccountType::PositionRewardExtension => PositionRewardExtension::discriminator(),
+            AccountType::PoolCheckpoint => PoolCheckpointState::discriminator(),
+            AccountType::PoolMetadata => PoolMetadata::discriminator(),
+            AccountType::PoolStats => PoolStatsState::discriminator(),
+            AccountType::RewardDistribution => RewardDistribution::discriminator(),
+            AccountType::ClaimStatus => ClaimStatus::discriminator(),
+            AccountType::TraderStats => TraderStatsState::discriminator(),
+            AccountType::AmmConfigExtension => AmmConfigExtension::discriminator(),
+            AccountType::WhitelistCreator => WhitelistCreator::discriminator(),
+        }
+    }
+
+    /// Detects the account type by the first 8 bytes of account data
+    pub fn from_data(data: &[u8]) -> Option<AccountType> {
+        if data.len() < 8 {
+            return None;
+        }
+        AccountType::ALL
+            .into_iter()
+            .find(|account_type| account_type.discriminator() == data[..8])
+    }
+}
+
+/// Emitted when an account is upgraded to the current layout version
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct MigrateAccountEvent {
+    /// The upgraded account
+    #[index]
+    pub account: Pubkey,
+    /// The type of the upgraded account
+    pub account_type: AccountType,
+    /// The layout version before the upgrade
+    pub version_before: u8,
+    /// The layout version after the upgrade
+    pub version: u8,
+    /// The data length of the account after the upgrade
+    pub data_len: u64,
+}
+
+/// A decoded field of the inspected account
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct InspectedField {
+    pub name: String,
+    pub value: String,
+}
+
+impl InspectedField {
+    pub fn new(name: &str, value: impl ToString) -> Self {
+        Self {
+            name: name.to_string(),
+            value: value.to_string(),
+        }
+    }
+}
+
+/// Returned by `inspect_account` as return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct AccountInspection {
+    pub account_type: AccountType,
+    pub version: u8,
+    pub data_len: u64,
+    /// The key fields of the account
+    pub fields: Vec<InspectedField>,
+}
+
+#[cfg(test)]
+mod account_registry_test {
+    use super::*;
+
+    #[test]
+    fn discriminators_are_unique_test() {
+        for (i, a) in AccountType::ALL.iter().enumerate() {
+            for b in AccountType::ALL.iter().skip(i + 1) {
+                assert_ne!(a.discriminator(), b.discriminator());
+            }
+        }
+    }
+
+    #[test]
+    fn from_data_test() {
+        for account_type in AccountType::ALL {
+            let mut data = account_type.discriminator().to_vec();
+            data.extend_from_slice(&[0; 8]);
+            assert_eq!(AccountType::from_data(&data), Some(account_type));
+        }
+        assert_eq!(AccountType::from_data(&[0; 16]), None);
+        assert_eq!(AccountType::from_data(&[0; 4]), None);
+    }
+}
@@ -0,0 +1,106 @@
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+
+pub const ADMIN_HEARTBEAT_SEED: &str = "admin_heartbeat";
+
+/// If the admin has not refreshed the heartbeat within the expire duration of the heartbeat,
+/// every pool not frozen permits withdrawals regardless of its status flags.
+/// The expire duration is 90 days until set by admin.
+pub const ADMIN_HEARTBEAT_EXPIRE_DURATION: u64 = 90 * 24 * 60 * 60;
+/// The expire duration can't be set shorter than 1 day
+pub const ADMIN_HEARTBEAT_MIN_EXPIRE_DURATION: u64 = 24 * 60 * 60;
+
+/// Liveness proof of the admin key, refreshed periodically by the admin
+#[account]
+#[derive(Default, Debug)]
+pub struct AdminHeartbeat {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The block timestamp of the last refresh
+    pub last_heartbeat_timestamp: u64,
+    /// The inactive duration after which the heartbeat expires, zero means `ADMIN_HEARTBEAT_EXPIRE_DURATION`
+    pub expire_duration: u64,
+    // padding space for upgrade
+    pub padding: [u64; 7],
+}
+
+impl AdminHeartbeat {
+    pub const LEN: usize = 8 + 1 + 8 + 8 + 8 * 7;
+
+    pub fn key() -> Pubkey {
+        Pubkey::find_program_address(&[ADMIN_HEARTBEAT_SEED.as_bytes()], &crate::id()).0
+    }
+
+    /// Refreshes the heartbeat, the expire duration is kept if `expire_duration` is zero
+    pub fn refresh(
+        &mut self,
+        bump: u8,
+        current_timestamp: u64,
+        expire_duration: u64,
+    ) -> Result<()> {
+        if expire_duration != 0 {
+            require_gte!(
+                expire_duration,
+                ADMIN_HEARTBEAT_MIN_EXPIRE_DURATION,
+                ErrorCode::InvalidAdminHeartbeatExpireDuration
+            );
+            self.expire_duration = expire_duration;
+        }
+        self.bump = bump;
+        self.last_heartbeat_timestamp = current_timestamp;
+        Ok(())
+    }
+
+    pub fn expire_duration(&self) -> u64 {
+        if self.expire_duration == 0 {
+            ADMIN_HEARTBEAT_EXPIRE_DURATION
+        } else {
+            self.expire_duration
+        }
+    }
+
+    /// Returns true if the admin has been inactive for longer than the expire duration
+    pub fn is_expired(&self, current_timestamp: u64) -> bool {
+        current_timestamp.saturating_sub(self.last_heartbeat_timestamp) > self.expire_duration()
+    }
+}
+
+#[cfg(test)]
+mod admin_heartbeat_test {
+    use super::*;
+
+    #[test]
+    fn is_expired_test() {
+        let heartbeat = AdminHeartbeat {
+            last_heartbeat_timestamp: 1_000,
+            ..Default::default()
+        };
+        assert!(!heartbeat.is_expired(0));
+        assert!(!heartbeat.is_expired(1_000 + ADMIN_HEARTBEAT_EXPIRE_DURATION));
+        assert!(heartbeat.is_expired(1_000 + ADMIN_HEARTBEAT_EXPIRE_DURATION + 1));
+    }
+
+    #[test]
+    fn refresh_expire_duration_test() {
+        let mut heartbeat = AdminHeartbeat::default();
+        heartbeat.refresh(255, 1_000, 0).unwrap();
+        assert_eq!(heartbeat.expire_duration(), ADMIN_HEARTBEAT_EXPIRE_DURATION);
+
+        heartbeat
+            .refresh(255, 2_000, ADMIN_HEARTBEAT_MIN_EXPIRE_DURATION)
+            .unwrap();
+        assert!(!heartbeat.is_expired(2_000 + ADMIN_HEARTBEAT_MIN_EXPIRE_DURATION));
+        assert!(heartbeat.is_expired(2_000 + ADMIN_HEARTBEAT_MIN_EXPIRE_DURATION + 1));
+
+        // a refresh without a duration keeps the current one
+        heartbeat.refresh(255, 3_000, 0).unwrap();
+        assert_eq!(
+            heartbeat.expire_duration,
+            ADMIN_HEARTBEAT_MIN_EXPIRE_DURATION
+        );
+
+        assert!(heartbeat
+            .refresh(255, 4_000, ADMIN_HEARTBEAT_MIN_EXPIRE_DURATION - 1)
+            .is_err());
+    }
+}
@@ -0,0 +1,170 @@
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+
+pub const ADMIN_TIMELOCK_SEED: &str = "admin_timelock";
+pub const ADMIN_ACTION_SEED: &str = "admin_action";
+
+/// The timelock delay can't be set longer than 30 days
+pub const MAX_ADMIN_TIMELOCK_DELAY: u64 = 30 * 24 * 60 * 60;
+
+/// Update a param of amm config, the same as `update_amm_config`
+pub const ADMIN_ACTION_UPDATE_AMM_CONFIG: u8 = 0;
+/// Update the status of pool, the same as `update_pool_status`
+pub const ADMIN_ACTION_UPDATE_POOL_STATUS: u8 = 1;
+/// Transfer the reward owner of pool, the same as `transfer_reward_owner`
+pub const ADMIN_ACTION_TRANSFER_REWARD_OWNER: u8 = 2;
+/// Shorten the delay of the timelock, extending it takes effect immediately
+pub const ADMIN_ACTION_UPDATE_TIMELOCK_DELAY: u8 = 3;
+
+/// The `update_amm_config` params which must be queued when the timelock is enabled
+pub const TIMELOCKED_AMM_CONFIG_PARAMS: [u8; 7] = [0, 1, 2, 5, 6, 8, 9];
+
+/// Holds the delay of the admin actions
+#[account]
+#[derive(Default, Debug)]
+pub struct AdminTimelock {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The delay in seconds between queueing and executing an admin action
+    pub delay: u64,
+    // padding space for upgrade
+    pub padding: [u64; 8],
+}
+
+impl AdminTimelock {
+    pub const LEN: usize = 8 + 1 + 8 + 8 * 8;
+
+    pub fn key() -> Pubkey {
+        Pubkey::find_program_address(&[ADMIN_TIMELOCK_SEED.as_bytes()], &crate::id()).0
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.delay > 0
+    }
+}
+
+/// Direct admin updates are only allowed while the timelock is disabled,
+/// the timelock account must be passed in remaining accounts even if it is not initialized.
+pub fn check_admin_timelock_disabled(remaining_accounts: &[AccountInfo]) -> Result<()> {
+    let timelock_key = AdminTimelock::key();
+    let account_info = remaining_accounts
+        .iter()
+        .find(|account_info| account_info.key() == timelock_key)
+        .ok_or(ErrorCode::InvalidAdminTimelockAccount)?;
+    if account_info.data_is_empty() {
+        return Ok(());
+    }
+    require_keys_eq!(*account_info.owner, crate::id());
+    let admin_timelock = AdminTimelock::try_deserialize(&mut &account_info.try_borrow_data()?[..])?;
+    require!(
+        !admin_timelock.is_enabled(),
+        ErrorCode::AdminTimelockEnabled
+    );
+    Ok(())
+}
+
+/// An admin action waiting for the timelock delay
+#[account]
+#[derive(Default, Debug)]
+pub struct AdminActionState {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The kind of action, ADMIN_ACTION_*
+    pub action: u8,
+    /// The `update_amm_config` param, zero for other actions
+    pub param: u8,
+    /// The account to be changed
+    pub target: Pubkey,
+    /// The new value of the param, status or delay
+    pub value: u64,
+    /// The new reward owner
+    pub new_key: Pubkey,
+    /// The action can be executed after this timestamp
+    pub executable_after: u64,
+    // padding space for upgrade
+    pub padding: [u64; 8],
+}
+
+impl AdminActionState {
+    pub const LEN: usize = 8 + 1 + 1 + 1 + 32 + 8 + 32 + 8 + 8 * 8;
+
+    pub fn is_executable(&self, current_timestamp: u64) -> bool {
+        current_timestamp >= self.executable_after
+    }
+}
+
+/// Emitted when an admin action is queued
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct AdminActionQueuedEvent {
+    /// The kind of action, ADMIN_ACTION_*
+    pub action: u8,
+    /// The `update_amm_config` param, zero for other actions
+    pub param: u8,
+    /// The account to be changed
+    #[index]
+    pub target: Pubkey,
+    /// The new value of the param, status or delay
+    pub value: u64,
+    /// The new reward owner
+    pub new_key: Pubkey,
+    /// The action can be executed after this timestamp
+    pub executable_after: u64,
+}
+
+#[cfg(test)]
+mod admin_timelock_test {
+    use super::*;
+
+    #[test]
+    fn is_executable_test() {
+        let admin_action = AdminActionState {
+            executable_after: 1_000,
+            ..Default::default()
+        };
+        assert!(!admin_action.is_executable(999));
+        assert!(admin_action.is_executable(1_000));
+        assert!(admin_action.is_executable(1_001));
+    }
+
+    #[test]
+    fn check_admin_timelock_disabled_test() {
+        let key = AdminTimelock::key();
+        let owner = crate::id();
+        let mut lamports = 0;
+        let mut data = vec![];
+        let account_info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        assert!(check_admin_timelock_disabled(&[]).is_err());
+        // not initialized timelock is disabled
+        assert!(check_admin_timelock_disabled(&[account_info]).is_ok());
+
+        let mut data = vec![];
+        AdminTimelock {
+            delay: 60,
+            ..Default::default()
+        }
+        .try_serialize(&mut data)
+        .unwrap();
+        let mut lamports = 1;
+        let account_info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        assert!(check_admin_timelock_disabled(&[account_info]).is_err());
+    }
+}
@@ -5,6 +5,9 @@ pub const AMM_CONFIG_SEED: &str = "amm_config";
 
 pub const FEE_RATE_DENOMINATOR_VALUE: u32 = 1_000_000;
 
+/// The denominator of sandwich_fee_multiplier, in percent
+pub const SANDWICH_FEE_MULTIPLIER_DENOMINATOR: u16 = 100;
+
 /// Holds the current owner of the factory
 #[account]
 #[derive(Default, Debug)]
@@ -22,10 +25,26 @@ pub struct AmmConfig {
     pub tick_spacing: u16,
     /// The fund fee, denominated in hundredths of a bip (10^-6)
     pub fund_fee_rate: u32,
-    // padding space for upgrade
-    pub padding_u32: u32,
+    /// The rate of protocol fee shared to the partner, denominated in hundredths of a bip (10^-6),
+    /// fixed at config creation, zero means not a partner config
+    pub partner_fee_rate: u32,
     pub fund_owner: Pubkey,
-    pub padding: [u64; 3],
+    /// The referral fee, a rate of trade fee paid from the protocol fee to the referrer,
+    /// denominated in hundredths of a bip (10^-6)
+    pub referral_fee_rate: u32,
+    /// Only operation owners and whitelist creators can create pool if true
+    pub whitelist_pool_creation: bool,
+    /// The trade fee multiplier in percent applied to a swap following an opposite direction swap
+    /// in the same slot, values not greater than 100 disable it
+    pub sandwich_fee_multiplier: u16,
+    /// The Token2022 mints with freeze authority need a token badge to create pool if true
+    pub freeze_authority_requires_badge: bool,
+    /// The fee in lamports charged to the pool creator, sent to the fund owner
+    pub create_pool_fee: u64,
+    /// The maximum creator fee rate of the pools, denominated in hundredths of a bip (10^-6),
+    /// zero means the pools can't charge a creator fee
+    pub max_creator_fee_rate: u32,
+    pub padding_u32: u32,
 }
 
 impl AmmConfig {
@@ -42,6 +61,19 @@ impl AmmConfig {
         );
         Ok(())
     }
+
+    pub fn is_partner_config(&self) -> bool {
+        self.partner_fee_rate > 0
+    }
+
+    /// Returns the part of protocol fee belongs to the partner
+    pub fn partner_fee_amount(&self, protocol_fee_amount: u64) -> u64 {
+        u64::try_from(
+            u128::from(protocol_fee_amount) * u128::from(self.partner_fee_rate)
+                / u128::from(FEE_RATE_DENOMINATOR_VALUE),
+        )
+        .unwrap()
+    }
 }
 
 /// Emitted when create or update a config
@@ -57,3 +89,238 @@ pub struct ConfigChangeEvent {
     pub fund_fee_rate: u32,
     pub fund_owner: Pubkey,
 }
+
+pub const AMM_CONFIG_EXTENSION_SEED: &str = "amm_config_extension";
+
+/// Holds the parameters of an amm config which don't fit in its padding, new parameters are
+/// appended to the extension in a new version instead of changing the layout of amm config
+#[account]
+#[derive(Default, Debug)]
+pub struct AmmConfigExtension {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The layout version of the extension
+    pub version: u8,
+    /// The amm config extended
+    pub amm_config: Pubkey,
+    /// The lower bound of the dynamic trade fee rate, denominated in hundredths of a bip (10^-6),
+    /// zero means the trade fee rate of amm config
+    pub min_dynamic_fee_rate: u32,
+    /// The upper bound of the dynamic trade fee rate, denominated in hundredths of a bip (10^-6),
+    /// zero means the trade fee rate of amm config
+    pub max_dynamic_fee_rate: u32,
+    /// The treasury receiving the protocol proceeds of the config, default pubkey means the fund owner
+    pub treasury: Pubkey,
+    // padding space for upgrade
+    pub padding: [u64; 32],
+}
+
+impl AmmConfigExtension {
+    pub const LEN: usize = 8 + 1 + 1 + 32 + 4 + 4 + 32 + 8 * 32;
+    pub const VERSION: u8 = 1;
+
+    pub fn key(amm_config: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[AMM_CONFIG_EXTENSION_SEED.as_bytes(), amm_config.as_ref()],
+            &crate::id(),
+        )
+        .0
+    }
+
+    /// Returns the lower and upper bounds of the dynamic trade fee rate of the config
+    pub fn dynamic_fee_bounds(&self, amm_config: &AmmConfig) -> (u32, u32) {
+        let bound_or_trade_fee_rate = |rate: u32| {
+            if rate == 0 {
+                amm_config.trade_fee_rate
+            } else {
+                rate
+            }
+        };
+        (
+            bound_or_trade_fee_rate(self.min_dynamic_fee_rate),
+            bound_or_trade_fee_rate(self.max_dynamic_fee_rate),
+        )
+    }
+
+    /// Returns the treasury of the config
+    pub fn treasury(&self, amm_config: &AmmConfig) -> Pubkey {
+        if self.treasury == Pubkey::default() {
+            amm_config.fund_owner
+        } else {
+            self.treasury
+        }
+    }
+
+    pub fn set_dynamic_fee_bounds(
+        &mut self,
+        amm_config: &AmmConfig,
+        min_dynamic_fee_rate: u32,
+        max_dynamic_fee_rate: u32,
+    ) -> Result<()> {
+        self.min_dynamic_fee_rate = min_dynamic_fee_rate;
+        self.max_dynamic_fee_rate = max_dynamic_fee_rate;
+        let (min_fee_rate, max_fee_rate) = self.dynamic_fee_bounds(amm_config);
+        require!(
+            min_fee_rate <= max_fee_rate && max_fee_rate < FEE_RATE_DENOMINATOR_VALUE,
+            ErrorCode::InvalidDynamicFeeBounds
+        );
+        Ok(())
+    }
+}
+
+/// Emitted when create or update an amm config extension
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct AmmConfigExtensionChangeEvent {
+    #[index]
+    pub amm_config: Pubkey,
+    pub version: u8,
+    pub min_dynamic_fee_rate: u32,
+    pub max_dynamic_fee_rate: u32,
+    pub treasury: Pubkey,
+}
+
+pub const AMM_CONFIG_PENDING_OWNER_SEED: &str = "amm_config_pending_owner";
+
+/// Holds the proposed owners of an amm config until they accept the ownership
+#[account]
+#[derive(Default, Debug)]
+pub struct AmmConfigPendingOwner {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The amm config to be transferred
+    pub amm_config: Pubkey,
+    /// The proposed owner, default pubkey means no pending transfer
+    pub pending_owner: Pubkey,
+    /// The proposed fund owner, default pubkey means no pending transfer
+    pub pending_fund_owner: Pubkey,
+    // padding space for upgrade
+    pub padding: [u64; 8],
+}
+
+impl AmmConfigPendingOwner {
+    pub const LEN: usize = 8 + 1 + 32 * 3 + 8 * 8;
+
+    pub fn key(amm_config: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                AMM_CONFIG_PENDING_OWNER_SEED.as_bytes(),
+                amm_config.as_ref(),
+            ],
+            &crate::id(),
+        )
+        .0
+    }
+
+    /// Moves the pending owners matching the signer into the amm config,
+    /// returns false if the signer is not a pending owner
+    pub fn accept(&mut self, amm_config: &mut AmmConfig, signer: Pubkey) -> bool {
+        let mut accepted = false;
+        if signer != Pubkey::default() && signer == self.pending_owner {
+            amm_config.owner = signer;
+            self.pending_owner = Pubkey::default();
+            accepted = true;
+        }
+        if signer != Pubkey::default() && signer == self.pending_fund_owner {
+            amm_config.fund_owner = signer;
+            self.pending_fund_owner = Pubkey::default();
+            accepted = true;
+        }
+        accepted
+    }
+}
+
+/// Emitted when a new owner or fund owner of amm config is proposed
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct OwnershipProposedEvent {
+    #[index]
+    pub amm_config: Pubkey,
+    /// The proposed owner, default pubkey if not changed
+    pub pending_owner: Pubkey,
+    /// The proposed fund owner, default pubkey if not changed
+    pub pending_fund_owner: Pubkey,
+}
+
+#[cfg(test)]
+mod config_test {
+    use super::*;
+
+    #[test]
+    fn partner_fee_amount_test() {
+        let mut amm_config = AmmConfig::default();
+        assert!(!amm_config.is_partner_config());
+        assert_eq!(amm_config.partner_fee_amount(1_000_000), 0);
+
+        amm_config.partner_fee_rate = 300_000;
+        assert!(amm_config.is_partner_config());
+        assert_eq!(amm_config.partner_fee_amount(1_000_000), 300_000);
+        assert_eq!(amm_config.partner_fee_amount(3), 0);
+        assert_eq!(amm_config.partner_fee_amount(u64::MAX), 5534023222112865484);
+    }
+
+    #[test]
+    fn amm_config_extension_test() {
+        let amm_config = AmmConfig {
+            trade_fee_rate: 2500,
+            fund_owner: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        let mut extension = AmmConfigExtension::default();
+        assert_eq!(extension.dynamic_fee_bounds(&amm_config), (2500, 2500));
+        assert_eq!(extension.treasury(&amm_config), amm_config.fund_owner);
+
+        extension
+            .set_dynamic_fee_bounds(&amm_config, 100, 10000)
+            .unwrap();
+        assert_eq!(extension.dynamic_fee_bounds(&amm_config), (100, 10000));
+        // only the upper bound is set
+        extension
+            .set_dynamic_fee_bounds(&amm_config, 0, 5000)
+            .unwrap();
+        assert_eq!(extension.dynamic_fee_bounds(&amm_config), (2500, 5000));
+
+        assert_eq!(
+            extension
+                .set_dynamic_fee_bounds(&amm_config, 0, 1000)
+                .unwrap_err(),
+            error!(ErrorCode::InvalidDynamicFeeBounds)
+        );
+        assert_eq!(
+            extension
+                .set_dynamic_fee_bounds(&amm_config, 100, FEE_RATE_DENOMINATOR_VALUE)
+                .unwrap_err(),
+            error!(ErrorCode::InvalidDynamicFeeBounds)
+        );
+
+        extension.treasury = Pubkey::new_unique();
+        assert_eq!(extension.treasury(&amm_config), extension.treasury);
+    }
+
+    #[test]
+    fn accept_ownership_test() {
+        let mut amm_config = AmmConfig::default();
+        let new_owner = Pubkey::new_unique();
+        let new_fund_owner = Pubkey::new_unique();
+        let mut pending = AmmConfigPendingOwner {
+            pending_owner: new_owner,
+            pending_fund_owner: new_fund_owner,
+            ..Default::default()
+        };
+
+        assert!(!pending.accept(&mut amm_config, Pubkey::new_unique()));
+        assert!(!pending.accept(&mut amm_config, Pubkey::default()));
+        assert_eq!(amm_config.owner, Pubkey::default());
+
+        assert!(pending.accept(&mut amm_config, new_owner));
+        assert_eq!(amm_config.owner, new_owner);
+        assert_eq!(amm_config.fund_owner, Pubkey::default());
+        assert_eq!(pending.pending_owner, Pubkey::default());
+        // can't accept twice
+        assert!(!pending.accept(&mut amm_config, new_owner));
+
+        assert!(pending.accept(&mut amm_config, new_fund_owner));
+        assert_eq!(amm_config.fund_owner, new_fund_owner);
+        assert_eq!(pending.pending_fund_owner, Pubkey::default());
+    }
+}
@@ -1,17 +1,53 @@
+pub mod account_registry;
+pub mod admin_heartbeat;
+pub mod admin_timelock;
 pub mod config;
 pub mod operation_account;
 pub mod oracle;
+pub mod partner_config;
 pub mod personal_position;
 pub mod pool;
+pub mod pool_checkpoint;
+pub mod pool_liabilities;
+pub mod pool_metadata;
+pub mod pool_stats;
+pub mod position_lock;
+pub mod position_operator;
+pub mod price_feed;
 pub mod protocol_position;
+pub mod referral;
+pub mod reward_distribution;
+pub mod reward_info_extension;
 pub mod tick_array;
 pub mod tickarray_bitmap_extension;
+pub mod token_badge;
+pub mod trader_stats;
+pub mod twap_snapshot;
+pub mod whitelist_creator;
 
+pub use account_registry::*;
+pub use admin_heartbeat::*;
+pub use admin_timelock::*;
 pub use config::*;
 pub use operation_account::*;
 pub use oracle::*;
+pub use partner_config::*;
 pub use personal_position::*;
 pub use pool::*;
+pub use pool_checkpoint::*;
+pub use pool_liabilities::*;
+pub use pool_metadata::*;
+pub use pool_stats::*;
+pub use position_lock::*;
+pub use position_operator::*;
+pub use price_feed::*;
 pub use protocol_position::*;
+pub use referral::*;
+pub use reward_distribution::*;
+pub use reward_info_extension::*;
 pub use tick_array::*;
 pub use tickarray_bitmap_extension::*;
+pub use token_badge::*;
+pub use trader_stats::*;
+pub use twap_snapshot::*;
+pub use whitelist_creator::*;
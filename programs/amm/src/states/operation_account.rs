@@ -1,11 +1,16 @@
+use crate::error::ErrorCode;
 use anchor_lang::prelude::*;
 use std::collections::HashSet;
 
 pub const OPERATION_SEED: &str = "operation";
 pub const OPERATION_SIZE_USIZE: usize = 10;
 pub const WHITE_MINT_SIZE_USIZE: usize = 100;
+/// The maximum number of whitelist mints in the extension of the operation account
+pub const EXTENSION_WHITE_MINT_SIZE_MAX: usize = 1000;
 
-/// Holds the current owner of the factory
+/// Holds the current owner of the factory.
+/// The account can be grown by `resize_operation_account`, the extension after the
+/// structure holds the whitelist mints beyond `WHITE_MINT_SIZE_USIZE`, 32 bytes each.
 #[account(zero_copy(unsafe))]
 #[repr(C, packed)]
 #[derive(Debug)]
@@ -20,6 +25,16 @@ pub struct OperationState {
 
 impl OperationState {
     pub const LEN: usize = 8 + 1 + 32 * OPERATION_SIZE_USIZE + 32 * WHITE_MINT_SIZE_USIZE;
+
+    /// The account size with room for `extension_whitelist_mint_size` whitelist mints in the extension
+    pub fn len_with_extension(extension_whitelist_mint_size: u16) -> usize {
+        Self::LEN + 32 * usize::from(extension_whitelist_mint_size)
+    }
+
+    pub fn key() -> Pubkey {
+        Pubkey::find_program_address(&[OPERATION_SEED.as_bytes()], &crate::id()).0
+    }
+
     pub fn initialize(&mut self, bump: u8) {
         self.bump = bump;
         self.operation_owners = [Pubkey::default(); OPERATION_SIZE_USIZE];
@@ -30,8 +45,10 @@ impl OperationState {
         owner != Pubkey::default() && self.operation_owners.contains(&owner)
     }
 
-    pub fn validate_whitelist_mint(&self, mint: Pubkey) -> bool {
-        mint != Pubkey::default() && self.whitelist_mints.contains(&mint)
+    pub fn validate_whitelist_mint(&self, extension: &[u8], mint: Pubkey) -> bool {
+        mint != Pubkey::default()
+            && (self.whitelist_mints.contains(&mint)
+                || extension_whitelist_mints(extension).any(|item| item == mint))
     }
 
     pub fn update_operation_owner(&mut self, keys: Vec<Pubkey>) {
@@ -58,29 +75,52 @@ impl OperationState {
             .copy_from_slice(operation_owners.as_slice());
     }
 
-    pub fn update_whitelist_mint(&mut self, keys: Vec<Pubkey>) {
+    pub fn update_whitelist_mint(&mut self, extension: &mut [u8], keys: Vec<Pubkey>) -> Result<()> {
         let mut whitelist_mints = self.whitelist_mints.to_vec();
+        whitelist_mints.extend(extension_whitelist_mints(extension));
         whitelist_mints.extend(keys.as_slice().iter());
         whitelist_mints.retain(|&item| item != Pubkey::default());
         let owners_set: HashSet<Pubkey> = HashSet::from_iter(whitelist_mints.iter().cloned());
         let updated_mints: Vec<Pubkey> = owners_set.into_iter().collect();
-        // clear
-        self.whitelist_mints = [Pubkey::default(); WHITE_MINT_SIZE_USIZE];
-        // update
-        self.whitelist_mints[0..updated_mints.len()].copy_from_slice(updated_mints.as_slice());
+        self.set_whitelist_mints(extension, &updated_mints)
     }
 
-    pub fn remove_whitelist_mint(&mut self, keys: Vec<Pubkey>) {
+    pub fn remove_whitelist_mint(&mut self, extension: &mut [u8], keys: Vec<Pubkey>) -> Result<()> {
         let mut whitelist_mints = self.whitelist_mints.to_vec();
+        whitelist_mints.extend(extension_whitelist_mints(extension));
         // remove keys from whitelist_mint
-        whitelist_mints.retain(|x| !keys.contains(&x));
+        whitelist_mints.retain(|x| *x != Pubkey::default() && !keys.contains(&x));
+        self.set_whitelist_mints(extension, &whitelist_mints)
+    }
+
+    /// Writes the whitelist mints to the array of the structure, then to the extension
+    fn set_whitelist_mints(&mut self, extension: &mut [u8], mints: &[Pubkey]) -> Result<()> {
+        require_gte!(
+            WHITE_MINT_SIZE_USIZE + extension.len() / 32,
+            mints.len(),
+            ErrorCode::OperationAccountFull
+        );
+        let (array_mints, extension_mints) = mints.split_at(mints.len().min(WHITE_MINT_SIZE_USIZE));
         // clear
         self.whitelist_mints = [Pubkey::default(); WHITE_MINT_SIZE_USIZE];
+        extension.fill(0);
         // update
-        self.whitelist_mints[0..whitelist_mints.len()].copy_from_slice(whitelist_mints.as_slice());
+        self.whitelist_mints[0..array_mints.len()].copy_from_slice(array_mints);
+        for (chunk, mint) in extension.chunks_exact_mut(32).zip(extension_mints) {
+            chunk.copy_from_slice(mint.as_ref());
+        }
+        Ok(())
     }
 }
 
+/// The whitelist mints in the extension of the operation account
+pub fn extension_whitelist_mints(extension: &[u8]) -> impl Iterator<Item = Pubkey> + '_ {
+    extension
+        .chunks_exact(32)
+        .map(|chunk| Pubkey::try_from(chunk).unwrap())
+        .filter(|mint| *mint != Pubkey::default())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -213,6 +253,46 @@ mod test {
         println!("{:?}", operation_state.operation_owners);
     }
 
+    #[test]
+    fn test_update_and_remove_whitelist_mint_with_extension() {
+        let mut operation_state = OperationState {
+            bump: 0,
+            operation_owners: [Pubkey::default(); OPERATION_SIZE_USIZE],
+            whitelist_mints: [Pubkey::default(); WHITE_MINT_SIZE_USIZE],
+        };
+        let keys: Vec<Pubkey> = (0..WHITE_MINT_SIZE_USIZE + 2)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        // the array alone can not hold all keys
+        assert_eq!(
+            operation_state
+                .update_whitelist_mint(&mut [], keys.clone())
+                .unwrap_err(),
+            error!(ErrorCode::OperationAccountFull)
+        );
+
+        let mut extension = vec![0u8; 32 * 2];
+        operation_state
+            .update_whitelist_mint(&mut extension, keys.clone())
+            .unwrap();
+        assert_eq!(extension_whitelist_mints(&extension).count(), 2);
+        for key in keys.iter() {
+            assert!(operation_state.validate_whitelist_mint(&extension, *key));
+        }
+        assert!(!operation_state.validate_whitelist_mint(&extension, Pubkey::default()));
+
+        operation_state
+            .remove_whitelist_mint(&mut extension, keys[0..3].to_vec())
+            .unwrap();
+        assert_eq!(extension_whitelist_mints(&extension).count(), 0);
+        for key in keys[0..3].iter() {
+            assert!(!operation_state.validate_whitelist_mint(&extension, *key));
+        }
+        for key in keys[3..].iter() {
+            assert!(operation_state.validate_whitelist_mint(&extension, *key));
+        }
+    }
+
     #[test]
     fn operation_layout_test() {
         use anchor_lang::Discriminator;
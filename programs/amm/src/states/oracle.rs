@@ -1,14 +1,25 @@
 /// Oracle provides price data useful for a wide variety of system designs
 ///
 use anchor_lang::prelude::*;
+use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use std::cell::RefMut;
+use std::ops::{Deref, DerefMut};
 
-use crate::util::get_recent_epoch;
+use crate::error::ErrorCode;
+use crate::libraries::fixed_point_64;
+use crate::util::{get_recent_epoch, AccountLoad};
 
 /// Seed to derive account address and signature
 pub const OBSERVATION_SEED: &str = "observation";
 // Number of ObservationState element
 pub const OBSERVATION_NUM: usize = 100;
 pub const OBSERVATION_UPDATE_DURATION_DEFAULT: u32 = 15;
+// The max number of observations of a pool, the observations beyond OBSERVATION_NUM
+// are stored in the reallocated tail of the ObservationState account
+pub const OBSERVATION_CARDINALITY_MAX: u16 = 8192;
+// The observation account of a pool without in-range liquidity and price moves
+// for this duration(30 days) can be closed to reclaim the rent
+pub const OBSERVATION_IDLE_DURATION: u64 = 30 * 24 * 60 * 60;
 
 /// The element of observations in ObservationState
 #[zero_copy(unsafe)]
@@ -19,12 +30,58 @@ pub struct Observation {
     pub block_timestamp: u32,
     /// the cumulative of tick during the duration time
     pub tick_cumulative: i64,
+    /// the cumulative of seconds per in-range liquidity during the duration time, as a Q64.64
+    pub seconds_per_liquidity_cumulative_x64: u128,
     /// padding for feature update
-    pub padding: [u64; 4],
+    pub padding: [u64; 2],
 }
 
 impl Observation {
-    pub const LEN: usize = 4 + 8 + 8 * 4;
+    pub const LEN: usize = 4 + 8 + 16 + 8 * 2;
+
+    /// Reads an observation stored in the extension of ObservationState
+    pub fn unpack(data: &[u8]) -> Observation {
+        let data = array_ref![data, 0, Observation::LEN];
+        let (block_timestamp, tick_cumulative, seconds_per_liquidity_cumulative_x64, padding) =
+            array_refs![data, 4, 8, 16, 16];
+        Observation {
+            block_timestamp: u32::from_le_bytes(*block_timestamp),
+            tick_cumulative: i64::from_le_bytes(*tick_cumulative),
+            seconds_per_liquidity_cumulative_x64: u128::from_le_bytes(
+                *seconds_per_liquidity_cumulative_x64,
+            ),
+            padding: [
+                u64::from_le_bytes(*array_ref![padding, 0, 8]),
+                u64::from_le_bytes(*array_ref![padding, 8, 8]),
+            ],
+        }
+    }
+
+    /// Returns the cumulative values at `block_timestamp`, extrapolated from the observation
+    /// with the active tick and liquidity since it
+    pub fn transform(&self, block_timestamp: u32, tick: i32, liquidity: u128) -> (i64, u128) {
+        let delta_time = block_timestamp.saturating_sub(self.block_timestamp);
+        let tick_cumulative =
+            { self.tick_cumulative }.wrapping_add(i64::from(tick) * i64::from(delta_time));
+        let seconds_per_liquidity_cumulative_x64 = { self.seconds_per_liquidity_cumulative_x64 }
+            .wrapping_add(
+                (u128::from(delta_time) << fixed_point_64::RESOLUTION) / liquidity.max(1),
+            );
+        (tick_cumulative, seconds_per_liquidity_cumulative_x64)
+    }
+
+    /// Writes an observation to the extension of ObservationState
+    pub fn pack(&self, data: &mut [u8]) {
+        let data = array_mut_ref![data, 0, Observation::LEN];
+        let (block_timestamp, tick_cumulative, seconds_per_liquidity_cumulative_x64, padding) =
+            mut_array_refs![data, 4, 8, 16, 16];
+        *block_timestamp = { self.block_timestamp }.to_le_bytes();
+        *tick_cumulative = { self.tick_cumulative }.to_le_bytes();
+        *seconds_per_liquidity_cumulative_x64 =
+            { self.seconds_per_liquidity_cumulative_x64 }.to_le_bytes();
+        padding[0..8].copy_from_slice(&{ self.padding[0] }.to_le_bytes());
+        padding[8..16].copy_from_slice(&{ self.padding[1] }.to_le_bytes());
+    }
 }
 
 #[account(zero_copy(unsafe))]
@@ -41,8 +98,13 @@ pub struct ObservationState {
     pub pool_id: Pubkey,
     /// observation array
     pub observations: [Observation; OBSERVATION_NUM],
+    /// the number of observations in use, 0 means OBSERVATION_NUM
+    pub observation_cardinality: u16,
+    /// the number of observations to use once the index wraps around, 0 means observation_cardinality
+    pub observation_cardinality_next: u16,
+    pub padding_u16: [u16; 2],
     /// padding for feature update
-    pub padding: [u64; 4],
+    pub padding: [u64; 3],
 }
 
 impl Default for ObservationState {
@@ -54,7 +116,10 @@ impl Default for ObservationState {
             observation_index: 0,
             pool_id: Pubkey::default(),
             observations: [Observation::default(); OBSERVATION_NUM],
-            padding: [0u64; 4],
+            observation_cardinality: 0,
+            observation_cardinality_next: 0,
+            padding_u16: [0u16; 2],
+            padding: [0u64; 3],
         }
     }
 }
@@ -68,43 +133,329 @@ impl ObservationState {
         self.observation_index = 0;
         self.pool_id = pool_id;
         self.observations = [Observation::default(); OBSERVATION_NUM];
-        self.padding = [0u64; 4];
+        self.observation_cardinality = 0;
+        self.observation_cardinality_next = 0;
+        self.padding_u16 = [0u16; 2];
+        self.padding = [0u64; 3];
         Ok(())
     }
 
+    /// Clears the observations of the account, the cardinality is kept
+    pub fn reset(&mut self, extension: &mut [u8]) {
+        self.initialized = false;
+        self.observation_index = 0;
+        self.observations = [Observation::default(); OBSERVATION_NUM];
+        extension.fill(0);
+    }
+
+    /// Returns the account size to store the given number of observations
+    pub fn len_with_cardinality(observation_cardinality: u16) -> usize {
+        let extension_num = usize::from(observation_cardinality).saturating_sub(OBSERVATION_NUM);
+        ObservationState::LEN + extension_num * Observation::LEN
+    }
+
+    /// Returns the number of observations in use, limited by the extension size
+    pub fn cardinality(&self, extension: &[u8]) -> usize {
+        let cardinality = if self.observation_cardinality == 0 {
+            OBSERVATION_NUM
+        } else {
+            usize::from(self.observation_cardinality)
+        };
+        cardinality.min(OBSERVATION_NUM + extension.len() / Observation::LEN)
+    }
+
+    /// Returns the number of observations to use once the index wraps around
+    pub fn cardinality_next(&self, extension: &[u8]) -> usize {
+        let cardinality_next = usize::from(self.observation_cardinality_next)
+            .min(OBSERVATION_NUM + extension.len() / Observation::LEN);
+        cardinality_next.max(self.cardinality(extension))
+    }
+
+    /// Returns the observation of the index, the observations beyond OBSERVATION_NUM are read from the extension
+    pub fn get_observation(&self, extension: &[u8], index: usize) -> Observation {
+        if index < OBSERVATION_NUM {
+            self.observations[index]
+        } else {
+            let offset = (index - OBSERVATION_NUM) * Observation::LEN;
+            Observation::unpack(&extension[offset..offset + Observation::LEN])
+        }
+    }
+
+    /// Returns the index and the block timestamp of the most recently updated observation
+    pub fn latest_observation(&self, extension: &[u8]) -> (u16, u32) {
+        let observation_index = self.observation_index;
+        (
+            observation_index,
+            self.get_observation(extension, usize::from(observation_index))
+                .block_timestamp,
+        )
+    }
+
+    fn set_observation(&mut self, extension: &mut [u8], index: usize, observation: Observation) {
+        if index < OBSERVATION_NUM {
+            self.observations[index] = observation;
+        } else {
+            let offset = (index - OBSERVATION_NUM) * Observation::LEN;
+            observation.pack(&mut extension[offset..offset + Observation::LEN]);
+        }
+    }
+
     /// Writes an oracle observation to the account
     ///
     /// # Arguments
     ///
     /// * `self` - The ObservationState account to write in
+    /// * `extension` - The reallocated tail of the account storing the observations beyond OBSERVATION_NUM
     /// * `block_timestamp` - The current timestamp of to update
+    /// * `tick` - The active tick since the last observation
+    /// * `liquidity` - The active liquidity since the last observation
     ///
-    pub fn update(&mut self, block_timestamp: u32, tick: i32) {
+    pub fn update(
+        &mut self,
+        extension: &mut [u8],
+        block_timestamp: u32,
+        tick: i32,
+        liquidity: u128,
+    ) {
         let observation_index = self.observation_index;
         if !self.initialized {
             self.initialized = true;
             self.observations[observation_index as usize].block_timestamp = block_timestamp;
             self.observations[observation_index as usize].tick_cumulative = 0;
+            self.observations[observation_index as usize].seconds_per_liquidity_cumulative_x64 = 0;
         } else {
-            let last_observation = self.observations[observation_index as usize];
+            let mut cardinality = self.cardinality(extension);
+            if usize::from(observation_index) >= cardinality {
+                // the extension is not provided
+                return;
+            }
+            let last_observation = self.get_observation(extension, observation_index as usize);
             let delta_time = block_timestamp.saturating_sub(last_observation.block_timestamp);
             if delta_time < OBSERVATION_UPDATE_DURATION_DEFAULT {
                 return;
             }
 
             let delta_tick_cumulative = i64::from(tick).checked_mul(delta_time.into()).unwrap();
-            let next_observation_index = if observation_index as usize == OBSERVATION_NUM - 1 {
+            // the liquidity is treated as 1 if there is no liquidity in range
+            let delta_seconds_per_liquidity_x64 =
+                (u128::from(delta_time) << fixed_point_64::RESOLUTION) / liquidity.max(1);
+            // grow the cardinality only at the end of the ring to keep the observations in order
+            let cardinality_next = self.cardinality_next(extension);
+            if cardinality_next > cardinality && observation_index as usize == cardinality - 1 {
+                cardinality = cardinality_next;
+                self.observation_cardinality = u16::try_from(cardinality).unwrap();
+            }
+            let next_observation_index = if observation_index as usize == cardinality - 1 {
                 0
             } else {
                 observation_index + 1
             };
-            self.observations[next_observation_index as usize].block_timestamp = block_timestamp;
-            self.observations[next_observation_index as usize].tick_cumulative = last_observation
+            let mut next_observation =
+                self.get_observation(extension, next_observation_index as usize);
+            next_observation.block_timestamp = block_timestamp;
+            next_observation.tick_cumulative = last_observation
                 .tick_cumulative
                 .wrapping_add(delta_tick_cumulative);
+            next_observation.seconds_per_liquidity_cumulative_x64 = last_observation
+                .seconds_per_liquidity_cumulative_x64
+                .wrapping_add(delta_seconds_per_liquidity_x64);
+            self.set_observation(extension, next_observation_index as usize, next_observation);
             self.observation_index = next_observation_index;
         }
     }
+
+    /// Returns true if the pool has no in-range liquidity and the price has not moved for
+    /// OBSERVATION_IDLE_DURATION. Any swap writes an observation, so the latest observation is
+    /// the time of the last swap.
+    pub fn is_idle(
+        &self,
+        extension: &[u8],
+        liquidity: u128,
+        open_time: u64,
+        block_timestamp: u64,
+    ) -> bool {
+        if liquidity != 0 {
+            return false;
+        }
+        let last_observation_timestamp = if self.initialized {
+            let observation = self.get_observation(extension, self.observation_index.into());
+            u64::from(observation.block_timestamp)
+        } else {
+            0
+        };
+        let last_active_time = last_observation_timestamp.max(open_time);
+        block_timestamp.saturating_sub(last_active_time) >= OBSERVATION_IDLE_DURATION
+    }
+
+    /// Returns the cumulative values as of `seconds_ago` before `block_timestamp`, interpolated
+    /// between the surrounding observations or extrapolated from the latest observation
+    ///
+    /// # Arguments
+    ///
+    /// * `extension` - The reallocated tail of the account storing the observations beyond OBSERVATION_NUM
+    /// * `block_timestamp` - The current block timestamp
+    /// * `seconds_ago` - How long ago to look back, in seconds
+    /// * `tick` - The current tick of the pool
+    /// * `liquidity` - The current in-range liquidity of the pool
+    ///
+    pub fn observe(
+        &self,
+        extension: &[u8],
+        block_timestamp: u32,
+        seconds_ago: u32,
+        tick: i32,
+        liquidity: u128,
+    ) -> Result<(i64, u128)> {
+        require!(self.initialized, ErrorCode::ObservationTooOld);
+        let target = block_timestamp
+            .checked_sub(seconds_ago)
+            .ok_or(ErrorCode::ObservationTooOld)?;
+        let cardinality = self.cardinality(extension);
+        let observation_index = usize::from(self.observation_index);
+        require_gt!(cardinality, observation_index);
+
+        let last_observation = self.get_observation(extension, observation_index);
+        if target >= last_observation.block_timestamp {
+            return Ok(last_observation.transform(target, tick, liquidity));
+        }
+
+        // the oldest observation is the next one if the ring has wrapped around, otherwise the first one
+        let mut oldest_index = (observation_index + 1) % cardinality;
+        if self
+            .get_observation(extension, oldest_index)
+            .block_timestamp
+            == 0
+        {
+            oldest_index = 0;
+        }
+        let oldest_observation = self.get_observation(extension, oldest_index);
+        require!(
+            target >= oldest_observation.block_timestamp,
+            ErrorCode::ObservationTooOld
+        );
+
+        // binary search for the observations at or surrounding the target
+        let mut left = observation_index + 1;
+        let mut right = left + cardinality - 1;
+        let (before, after) = loop {
+            let middle = (left + right) / 2;
+            let before = self.get_observation(extension, middle % cardinality);
+            if before.block_timestamp == 0 {
+                left = middle + 1;
+                continue;
+            }
+            let after = self.get_observation(extension, (middle + 1) % cardinality);
+            if before.block_timestamp <= target && target <= after.block_timestamp {
+                break (before, after);
+            }
+            if before.block_timestamp < target {
+                left = middle + 1;
+            } else {
+                right = middle - 1;
+            }
+        };
+
+        if target == before.block_timestamp {
+            return Ok((
+                before.tick_cumulative,
+                before.seconds_per_liquidity_cumulative_x64,
+            ));
+        }
+        if target == after.block_timestamp {
+            return Ok((
+                after.tick_cumulative,
+                after.seconds_per_liquidity_cumulative_x64,
+            ));
+        }
+        let observation_time_delta = i64::from(after.block_timestamp - before.block_timestamp);
+        let target_delta = i64::from(target - before.block_timestamp);
+        let tick_cumulative = before.tick_cumulative
+            + (after.tick_cumulative - before.tick_cumulative) / observation_time_delta
+                * target_delta;
+        let seconds_per_liquidity_cumulative_x64 =
+            before.seconds_per_liquidity_cumulative_x64.wrapping_add(
+                after
+                    .seconds_per_liquidity_cumulative_x64
+                    .wrapping_sub(before.seconds_per_liquidity_cumulative_x64)
+                    * target_delta as u128
+                    / observation_time_delta as u128,
+            );
+        Ok((tick_cumulative, seconds_per_liquidity_cumulative_x64))
+    }
+}
+
+/// Emitted when the observation cardinality of a pool is increased
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct IncreaseObservationCardinalityEvent {
+    /// The pool of the observation account
+    #[index]
+    pub pool_state: Pubkey,
+
+    /// The previous number of observations to store
+    pub observation_cardinality_next_old: u16,
+
+    /// The new number of observations to store
+    pub observation_cardinality_next: u16,
+}
+
+/// Emitted when the observation account of an idle pool is closed
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct CloseObservationEvent {
+    /// The pool of the observation account
+    #[index]
+    pub pool_state: Pubkey,
+
+    /// The closed observation account
+    pub observation_state: Pubkey,
+}
+
+/// The observation account written by swap
+pub trait ObservationWriter: DerefMut<Target = ObservationState> {
+    /// Writes an oracle observation, see `ObservationState::update`
+    fn write(&mut self, block_timestamp: u32, tick: i32, liquidity: u128);
+}
+
+impl ObservationWriter for RefMut<'_, ObservationState> {
+    fn write(&mut self, block_timestamp: u32, tick: i32, liquidity: u128) {
+        self.update(&mut [], block_timestamp, tick, liquidity)
+    }
+}
+
+/// The ObservationState with the reallocated tail of the account
+pub struct ObservationStateMut<'a> {
+    pub state: RefMut<'a, ObservationState>,
+    pub extension: RefMut<'a, [u8]>,
+}
+
+impl<'a> ObservationStateMut<'a> {
+    pub fn load_mut(account_info: &'a AccountInfo) -> Result<ObservationStateMut<'a>> {
+        let (state, extension) =
+            AccountLoad::<ObservationState>::load_data_mut_with_tail(account_info)?;
+        Ok(ObservationStateMut { state, extension })
+    }
+}
+
+impl Deref for ObservationStateMut<'_> {
+    type Target = ObservationState;
+
+    fn deref(&self) -> &ObservationState {
+        &self.state
+    }
+}
+
+impl DerefMut for ObservationStateMut<'_> {
+    fn deref_mut(&mut self) -> &mut ObservationState {
+        &mut self.state
+    }
+}
+
+impl ObservationWriter for ObservationStateMut<'_> {
+    fn write(&mut self, block_timestamp: u32, tick: i32, liquidity: u128) {
+        self.state
+            .update(&mut self.extension, block_timestamp, tick, liquidity)
+    }
 }
 
 /// Returns the block timestamp truncated to 32 bits, i.e. mod 2**32
@@ -132,12 +483,10 @@ pub mod oracle_layout_test {
         let recent_epoch: u64 = 0x123456789abcdef0;
         let observation_index: u16 = 0x1122;
         let pool_id: Pubkey = Pubkey::new_unique();
-        let padding: [u64; 4] = [
-            0x123456789abcde0f,
-            0x123456789abcd0ef,
-            0x123456789abc0def,
-            0x123456789ab0cdef,
-        ];
+        let observation_cardinality: u16 = 0x3344;
+        let observation_cardinality_next: u16 = 0x5566;
+        let padding_u16: [u16; 2] = [0x7788, 0x99aa];
+        let padding: [u64; 3] = [0x123456789abcde0f, 0x123456789abcd0ef, 0x123456789abc0def];
 
         let mut observation_datas = [0u8; Observation::LEN * OBSERVATION_NUM];
         let mut observations = [Observation::default(); OBSERVATION_NUM];
@@ -146,27 +495,24 @@ pub mod oracle_layout_test {
             let index = i + 1;
             let block_timestamp: u32 = u32::MAX - 3 * index as u32;
             let tick_cumulative: i64 = i64::MAX - 3 * index as i64;
-            let padding: [u64; 4] = [
-                u64::MAX - index as u64,
-                u64::MAX - 2 * index as u64,
-                u64::MAX - 3 * index as u64,
-                u64::MAX - 4 * index as u64,
-            ];
+            let seconds_per_liquidity_cumulative_x64: u128 = u128::MAX - 3 * index as u128;
+            let padding: [u64; 2] = [u64::MAX - index as u64, u64::MAX - 2 * index as u64];
             observations[i].block_timestamp = block_timestamp;
             observations[i].tick_cumulative = tick_cumulative;
+            observations[i].seconds_per_liquidity_cumulative_x64 =
+                seconds_per_liquidity_cumulative_x64;
             observations[i].padding = padding;
             observation_datas[offset..offset + 4].copy_from_slice(&block_timestamp.to_le_bytes());
             offset += 4;
             observation_datas[offset..offset + 8].copy_from_slice(&tick_cumulative.to_le_bytes());
             offset += 8;
+            observation_datas[offset..offset + 16]
+                .copy_from_slice(&seconds_per_liquidity_cumulative_x64.to_le_bytes());
+            offset += 16;
             observation_datas[offset..offset + 8].copy_from_slice(&padding[0].to_le_bytes());
             offset += 8;
             observation_datas[offset..offset + 8].copy_from_slice(&padding[1].to_le_bytes());
             offset += 8;
-            observation_datas[offset..offset + 8].copy_from_slice(&padding[2].to_le_bytes());
-            offset += 8;
-            observation_datas[offset..offset + 8].copy_from_slice(&padding[3].to_le_bytes());
-            offset += 8;
         }
 
         // serialize original data
@@ -188,14 +534,22 @@ pub mod oracle_layout_test {
         observation_state_data[offset..offset + Observation::LEN * OBSERVATION_NUM]
             .copy_from_slice(&observation_datas);
         offset += Observation::LEN * OBSERVATION_NUM;
+        observation_state_data[offset..offset + 2]
+            .copy_from_slice(&observation_cardinality.to_le_bytes());
+        offset += 2;
+        observation_state_data[offset..offset + 2]
+            .copy_from_slice(&observation_cardinality_next.to_le_bytes());
+        offset += 2;
+        observation_state_data[offset..offset + 2].copy_from_slice(&padding_u16[0].to_le_bytes());
+        offset += 2;
+        observation_state_data[offset..offset + 2].copy_from_slice(&padding_u16[1].to_le_bytes());
+        offset += 2;
         observation_state_data[offset..offset + 8].copy_from_slice(&padding[0].to_le_bytes());
         offset += 8;
         observation_state_data[offset..offset + 8].copy_from_slice(&padding[1].to_le_bytes());
         offset += 8;
         observation_state_data[offset..offset + 8].copy_from_slice(&padding[2].to_le_bytes());
         offset += 8;
-        observation_state_data[offset..offset + 8].copy_from_slice(&padding[3].to_le_bytes());
-        offset += 8;
         // len check
         assert_eq!(offset, observation_state_data.len());
         assert_eq!(
@@ -217,6 +571,15 @@ pub mod oracle_layout_test {
         assert_eq!(unpack_observation_index, observation_index);
         let unpack_pool_id = unpack_data.pool_id;
         assert_eq!(unpack_pool_id, pool_id);
+        let unpack_observation_cardinality = unpack_data.observation_cardinality;
+        assert_eq!(unpack_observation_cardinality, observation_cardinality);
+        let unpack_observation_cardinality_next = unpack_data.observation_cardinality_next;
+        assert_eq!(
+            unpack_observation_cardinality_next,
+            observation_cardinality_next
+        );
+        let unpack_padding_u16 = unpack_data.padding_u16;
+        assert_eq!(unpack_padding_u16, padding_u16);
         let unpack_padding = unpack_data.padding;
         assert_eq!(unpack_padding, padding);
         for (observation, unpack_observation) in
@@ -224,14 +587,196 @@ pub mod oracle_layout_test {
         {
             let block_timestamp = observation.block_timestamp;
             let tick_cumulative = observation.tick_cumulative;
+            let seconds_per_liquidity_cumulative_x64 =
+                observation.seconds_per_liquidity_cumulative_x64;
             let padding = observation.padding;
 
             let unpack_block_timestamp = unpack_observation.block_timestamp;
             let unpack_tick_cumulative = unpack_observation.tick_cumulative;
+            let unpack_seconds_per_liquidity_cumulative_x64 =
+                unpack_observation.seconds_per_liquidity_cumulative_x64;
             let unpack_padding = unpack_observation.padding;
             assert_eq!(block_timestamp, unpack_block_timestamp);
             assert_eq!(tick_cumulative, unpack_tick_cumulative);
+            assert_eq!(
+                seconds_per_liquidity_cumulative_x64,
+                unpack_seconds_per_liquidity_cumulative_x64
+            );
             assert_eq!(padding, unpack_padding);
         }
     }
 }
+
+#[cfg(test)]
+mod observation_update_test {
+    use super::*;
+    use std::convert::identity;
+
+    #[test]
+    fn seconds_per_liquidity_cumulative_test() {
+        let observation_state = &mut ObservationState::default();
+        observation_state.update(&mut [], 1000, 10, 100);
+        assert_eq!(
+            identity(observation_state.observations[0].seconds_per_liquidity_cumulative_x64),
+            0
+        );
+
+        // too close to the last observation, skipped
+        observation_state.update(&mut [], 1010, 10, 100);
+        assert_eq!(identity(observation_state.observation_index), 0);
+
+        observation_state.update(&mut [], 1020, 10, 100);
+        assert_eq!(identity(observation_state.observation_index), 1);
+        assert_eq!(
+            identity(observation_state.observations[1].tick_cumulative),
+            200
+        );
+        assert_eq!(
+            identity(observation_state.observations[1].seconds_per_liquidity_cumulative_x64),
+            (20u128 << fixed_point_64::RESOLUTION) / 100
+        );
+
+        // no liquidity in range
+        observation_state.update(&mut [], 1040, 10, 0);
+        assert_eq!(
+            identity(observation_state.observations[2].seconds_per_liquidity_cumulative_x64),
+            (20u128 << fixed_point_64::RESOLUTION) / 100 + (20u128 << fixed_point_64::RESOLUTION)
+        );
+    }
+
+    #[test]
+    fn increase_cardinality_test() {
+        let observation_state = &mut ObservationState::default();
+        let extension = &mut [0u8; Observation::LEN * 2];
+        let mut block_timestamp = 1000;
+        observation_state.update(extension, block_timestamp, 1, 100);
+        for _ in 0..50 {
+            block_timestamp += OBSERVATION_UPDATE_DURATION_DEFAULT;
+            observation_state.update(extension, block_timestamp, 1, 100);
+        }
+        assert_eq!(identity(observation_state.observation_index), 50);
+
+        // the cardinality grows when the index reaches the end of the ring
+        observation_state.observation_cardinality_next = OBSERVATION_NUM as u16 + 2;
+        for _ in 0..(OBSERVATION_NUM - 51) {
+            block_timestamp += OBSERVATION_UPDATE_DURATION_DEFAULT;
+            observation_state.update(extension, block_timestamp, 1, 100);
+        }
+        assert_eq!(
+            identity(observation_state.observation_index) as usize,
+            OBSERVATION_NUM - 1
+        );
+        assert_eq!(observation_state.cardinality(extension), OBSERVATION_NUM);
+
+        block_timestamp += OBSERVATION_UPDATE_DURATION_DEFAULT;
+        observation_state.update(extension, block_timestamp, 1, 100);
+        assert_eq!(
+            identity(observation_state.observation_index) as usize,
+            OBSERVATION_NUM
+        );
+        assert_eq!(
+            observation_state.cardinality(extension),
+            OBSERVATION_NUM + 2
+        );
+        let observation = observation_state.get_observation(extension, OBSERVATION_NUM);
+        assert_eq!(identity(observation.block_timestamp), block_timestamp);
+        assert_eq!(
+            identity(observation.tick_cumulative),
+            i64::from(block_timestamp - 1000)
+        );
+        // the latest observation is read from the extension
+        assert_eq!(
+            observation_state.latest_observation(extension),
+            (OBSERVATION_NUM as u16, block_timestamp)
+        );
+
+        block_timestamp += OBSERVATION_UPDATE_DURATION_DEFAULT;
+        observation_state.update(extension, block_timestamp, 1, 100);
+        block_timestamp += OBSERVATION_UPDATE_DURATION_DEFAULT;
+        observation_state.update(extension, block_timestamp, 1, 100);
+        assert_eq!(identity(observation_state.observation_index), 0);
+        assert_eq!(
+            identity(observation_state.observations[0].block_timestamp),
+            block_timestamp
+        );
+        assert_eq!(
+            observation_state.latest_observation(extension),
+            (0, block_timestamp)
+        );
+
+        // the cardinality is limited by the extension size
+        assert_eq!(observation_state.cardinality(&[]), OBSERVATION_NUM);
+    }
+
+    #[test]
+    fn observe_test() {
+        let observation_state = &mut ObservationState::default();
+        assert!(observation_state.observe(&[], 1000, 0, 10, 100).is_err());
+
+        observation_state.update(&mut [], 1000, 10, 100);
+        observation_state.update(&mut [], 1020, 10, 100);
+        observation_state.update(&mut [], 1040, -30, 100);
+
+        // extrapolated from the latest observation
+        assert_eq!(
+            observation_state.observe(&[], 1050, 0, 5, 100).unwrap().0,
+            200 + 20 * -30 + 10 * 5
+        );
+        // at an observation
+        assert_eq!(
+            observation_state.observe(&[], 1050, 30, 5, 100).unwrap().0,
+            200
+        );
+        // interpolated between observations
+        let (tick_cumulative, seconds_per_liquidity_cumulative_x64) =
+            observation_state.observe(&[], 1050, 20, 5, 100).unwrap();
+        assert_eq!(tick_cumulative, 200 + 10 * -30);
+        assert_eq!(
+            seconds_per_liquidity_cumulative_x64,
+            (30u128 << fixed_point_64::RESOLUTION) / 100
+        );
+        // older than the oldest observation
+        assert!(observation_state.observe(&[], 1050, 51, 5, 100).is_err());
+
+        // the latest observation is at 1040
+        let idle_time = 1040 + OBSERVATION_IDLE_DURATION;
+        assert!(!observation_state.is_idle(&[], 0, 0, idle_time - 1));
+        assert!(observation_state.is_idle(&[], 0, 0, idle_time));
+        assert!(!observation_state.is_idle(&[], 1, 0, idle_time));
+        assert!(!observation_state.is_idle(&[], 0, 1041, idle_time));
+
+        // the ring has wrapped around
+        let mut block_timestamp = 1040;
+        for _ in 0..OBSERVATION_NUM {
+            block_timestamp += OBSERVATION_UPDATE_DURATION_DEFAULT;
+            observation_state.update(&mut [], block_timestamp, 1, 100);
+        }
+        let oldest_timestamp =
+            block_timestamp - OBSERVATION_UPDATE_DURATION_DEFAULT * (OBSERVATION_NUM as u32 - 1);
+        assert!(observation_state
+            .observe(
+                &[],
+                block_timestamp,
+                block_timestamp - oldest_timestamp,
+                1,
+                100
+            )
+            .is_ok());
+        assert!(observation_state
+            .observe(
+                &[],
+                block_timestamp,
+                block_timestamp - oldest_timestamp + 1,
+                1,
+                100
+            )
+            .is_err());
+        let (tick_cumulative_end, _) = observation_state
+            .observe(&[], block_timestamp, 0, 1, 100)
+            .unwrap();
+        let (tick_cumulative_start, _) = observation_state
+            .observe(&[], block_timestamp, 600, 1, 100)
+            .unwrap();
+        assert_eq!(tick_cumulative_end - tick_cumulative_start, 600);
+    }
+}
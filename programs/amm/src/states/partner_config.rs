@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+pub const PARTNER_CONFIG_SEED: &str = "partner_config";
+
+/// Holds the partner of a partner-branded amm config
+#[account]
+#[derive(Default, Debug)]
+pub struct PartnerConfig {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The amm config this partner bound to
+    pub amm_config: Pubkey,
+    /// Address of the partner, receives the partner part of protocol fee
+    pub partner_owner: Pubkey,
+    // padding space for upgrade
+    pub padding: [u64; 8],
+}
+
+impl PartnerConfig {
+    pub const LEN: usize = 8 + 1 + 32 + 32 + 8 * 8;
+
+    pub fn key(amm_config: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[PARTNER_CONFIG_SEED.as_bytes(), amm_config.as_ref()],
+            &crate::id(),
+        )
+        .0
+    }
+}
+
+/// Emitted when create a partner amm config
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct PartnerConfigCreatedEvent {
+    /// The partner-branded amm config
+    #[index]
+    pub amm_config: Pubkey,
+
+    /// The partner owner
+    pub partner_owner: Pubkey,
+
+    /// The rate of protocol fee shared to the partner
+    pub partner_fee_rate: u32,
+}
+
+/// Emitted when the partner part of protocol fee is collected
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct CollectPartnerFeeEvent {
+    /// The pool whose protocol fee is collected
+    #[index]
+    pub pool_state: Pubkey,
+
+    /// The partner owner
+    pub partner_owner: Pubkey,
+
+    /// The address that receives the partner part of token_0 protocol fees
+    pub recipient_token_account_0: Pubkey,
+
+    /// The address that receives the partner part of token_1 protocol fees
+    pub recipient_token_account_1: Pubkey,
+
+    /// The amount of token_0 protocol fees that is sent to partner
+    pub amount_0: u64,
+
+    /// The amount of token_1 protocol fees that is sent to partner
+    pub amount_1: u64,
+}
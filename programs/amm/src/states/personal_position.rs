@@ -1,9 +1,10 @@
+use crate::error::ErrorCode;
 use crate::libraries::{big_num::U256, fixed_point_64, full_math::MulDiv};
 use crate::pool::REWARD_NUM;
 use crate::util::get_recent_epoch;
 use anchor_lang::prelude::*;
 
-use super::POSITION_SEED;
+use super::{EXTENSION_REWARD_NUM, POSITION_SEED};
 
 #[account]
 #[derive(Default, Debug)]
@@ -26,10 +27,10 @@ pub struct PersonalPositionState {
     /// The amount of liquidity owned by this position
     pub liquidity: u128,
 
-    /// The token_0 fee growth of the aggregate position as of the last action on the individual position
+    /// The token_0 fee growth inside the range of the position as of its last action
     pub fee_growth_inside_0_last_x64: u128,
 
-    /// The token_1 fee growth of the aggregate position as of the last action on the individual position
+    /// The token_1 fee growth inside the range of the position as of its last action
     pub fee_growth_inside_1_last_x64: u128,
 
     /// The fees owed to the position owner in token_0, as of the last computation
@@ -42,11 +43,31 @@ pub struct PersonalPositionState {
     pub reward_infos: [PositionRewardInfo; REWARD_NUM],
     // account update recent epoch
     pub recent_epoch: u64,
+    /// The accumulated liquidity multiplied by the seconds it was in range
+    pub liquidity_seconds: u128,
+    /// The seconds spent in range as of the last action on the position
+    pub seconds_inside_last: u64,
+    /// Whether seconds_inside_last has been recorded, positions created before
+    /// the liquidity seconds tracking start accumulating from their first update
+    pub liquidity_seconds_initialized: bool,
+    /// Whether the position is permanently locked, see `PositionLockState`
+    pub locked: bool,
+    /// The layout version of the account, 0 for positions opened before the field was introduced
+    pub version: u8,
+    /// Whether the position reward extension of the position is created, which must be passed
+    /// to close the position
+    pub has_reward_extension: bool,
+    pub padding_u8: [u8; 4],
+    /// The liquidity of the position can't be decreased before this timestamp, zero if not time locked
+    pub locked_until: u64,
     // Unused bytes for future upgrades.
-    pub padding: [u64; 7],
+    pub padding: [u64; 2],
 }
 
 impl PersonalPositionState {
+    /// The current layout version, bumped when new fields are introduced
+    pub const VERSION: u8 = 1;
+
     pub const LEN: usize =
         8 + 1 + 32 + 32 + 4 + 4 + 16 + 16 + 16 + 8 + 8 + PositionRewardInfo::LEN * REWARD_NUM + 64;
 
@@ -58,6 +79,15 @@ impl PersonalPositionState {
         ]
     }
 
+    /// Upgrades the account to the current layout version, returns the version before the upgrade
+    pub fn migrate(&mut self) -> Result<u8> {
+        let version = self.version;
+        require_gt!(Self::VERSION, version, ErrorCode::AccountAlreadyMigrated);
+        // version 1 only introduces the version field
+        self.version = Self::VERSION;
+        Ok(version)
+    }
+
     pub fn update_rewards(
         &mut self,
         reward_growths_inside: [u128; REWARD_NUM],
@@ -93,6 +123,23 @@ impl PersonalPositionState {
         self.recent_epoch = get_recent_epoch()?;
         Ok(())
     }
+
+    /// Accumulates the liquidity seconds since the last update,
+    /// must be called before the liquidity of the position changes.
+    pub fn update_liquidity_seconds(&mut self, seconds_inside: u64) {
+        if self.liquidity_seconds_initialized {
+            let seconds_delta = seconds_inside.wrapping_sub(self.seconds_inside_last);
+            self.liquidity_seconds = self
+                .liquidity_seconds
+                .saturating_add(self.liquidity.saturating_mul(u128::from(seconds_delta)));
+        }
+        self.seconds_inside_last = seconds_inside;
+        self.liquidity_seconds_initialized = true;
+    }
+
+    pub fn is_time_locked(&self, block_timestamp: u64) -> bool {
+        self.locked_until > block_timestamp
+    }
 }
 
 #[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Default, Debug, PartialEq)]
@@ -190,6 +237,8 @@ pub struct DecreaseLiquidityEvent {
     pub transfer_fee_0: u64,
     /// The amount of token_1 transfer fee
     pub transfer_fee_1: u64,
+    /// The amount of the rewards in the reward info extension of the pool
+    pub extension_reward_amounts: [u64; EXTENSION_REWARD_NUM],
 }
 
 /// Emitted when liquidity decreased or increase.
@@ -244,3 +293,40 @@ pub struct UpdateRewardInfosEvent {
     /// Reward info
     pub reward_growth_global_x64: [u128; REWARD_NUM],
 }
+
+/// Emitted when the liquidity seconds of a position are updated by the crank
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct UpdateLiquiditySecondsEvent {
+    /// The pool of the position
+    #[index]
+    pub pool_state: Pubkey,
+
+    /// The nft mint of the position
+    pub position_nft_mint: Pubkey,
+
+    /// The accumulated in-range liquidity seconds of the position
+    pub liquidity_seconds: u128,
+
+    /// The seconds spent in range of the position ticks, the difference between two
+    /// updates is the seconds the position was in range between them
+    pub seconds_inside: u64,
+
+    /// The accumulated in-range liquidity seconds of the pool
+    pub liquidity_seconds_global: u128,
+}
+
+/// Emitted when the lock timestamp of a position is extended
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct LockPositionUntilEvent {
+    /// The pool of the position
+    #[index]
+    pub pool_state: Pubkey,
+
+    /// The locked position
+    pub position_nft_mint: Pubkey,
+
+    /// The liquidity of the position can't be decreased before this timestamp
+    pub locked_until: u64,
+}
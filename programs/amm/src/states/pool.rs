@@ -18,8 +18,14 @@ pub const POOL_SEED: &str = "pool";
 pub const POOL_VAULT_SEED: &str = "pool_vault";
 pub const POOL_REWARD_VAULT_SEED: &str = "pool_reward_vault";
 pub const POOL_TICK_ARRAY_BITMAP_SEED: &str = "pool_tick_array_bitmap_extension";
+/// Seed of the PDA owning the protocol owned positions of a pool, which are funded by the fund fees
+pub const PROTOCOL_OWNED_LIQUIDITY_SEED: &str = "protocol_owned_liquidity";
 // Number of rewards Token
 pub const REWARD_NUM: usize = 3;
+/// The maximum seconds over which the launch fee of a pool decays
+pub const MAX_LAUNCH_FEE_DURATION: u64 = 7 * 24 * 60 * 60;
+/// The maximum seconds after the open time during which the output of a swap is limited
+pub const MAX_ANTI_BOT_DURATION: u64 = 24 * 60 * 60;
 
 #[cfg(feature = "paramset")]
 pub mod reward_period_limit {
@@ -40,6 +46,13 @@ pub enum PoolStatusBitIndex {
     CollectFee,
     CollectReward,
     Swap,
+    /// Only decrease liquidity and collecting fee and reward are permitted,
+    /// overrides the other bits so users can always exit
+    WithdrawOnly,
+    /// Swap token_0 for token_1
+    SwapZeroForOne,
+    /// Swap token_1 for token_0
+    SwapOneForZero,
 }
 
 #[derive(PartialEq, Eq)]
@@ -111,9 +124,16 @@ pub struct PoolState {
     /// bit2, 1: disable collect fee, 0: normal
     /// bit3, 1: disable collect reward, 0: normal
     /// bit4, 1: disable swap, 0: normal
+    /// bit5, 1: withdraw-only, overrides the bits above, 0: normal
+    /// bit6, 1: disable zero for one swap, 0: normal
+    /// bit7, 1: disable one for zero swap, 0: normal
     pub status: u8,
+    /// The number of rewards initialized in the reward info extension of the pool
+    pub extension_reward_count: u8,
+    /// The layout version of the account, 0 for pools created before the field was introduced
+    pub version: u8,
     /// Leave blank for future use
-    pub padding: [u8; 7],
+    pub padding: [u8; 5],
 
     pub reward_infos: [RewardInfo; REWARD_NUM],
 
@@ -131,17 +151,80 @@ pub struct PoolState {
     pub fund_fees_token_1: u64,
 
     // The timestamp allowed for swap in the pool.
-    // Note: The open_time is disabled for now.
+    // Note: The pool opens at creation, the open_time passed to `create_pool` is only checked to be in the past,
+    // the open_time can be moved to the future by `update_open_time` before any swap.
     pub open_time: u64,
     // account recent update epoch
     pub recent_epoch: u64,
 
+    /// The accumulated in-range liquidity multiplied by the seconds it was active
+    pub liquidity_seconds_global: u128,
+    /// The block timestamp when liquidity_seconds_global was last updated
+    pub liquidity_seconds_update_time: u64,
+
+    /// The lower bound of the price band, swaps can not move the price below it, 0 means no bound
+    pub min_sqrt_price_x64: u128,
+    /// The upper bound of the price band, swaps can not move the price above it, 0 means no bound
+    pub max_sqrt_price_x64: u128,
+
+    /// The slot of the last swap
+    pub last_swap_slot: u64,
+    /// The accumulated zero for one swap input amount of token_0 in the last swap slot
+    pub slot_swap_volume_zero_for_one: u64,
+    /// The accumulated one for zero swap input amount of token_1 in the last swap slot
+    pub slot_swap_volume_one_for_zero: u64,
+
+    /// The vesting schedules of the rewards, the rewards owed to a position become claimable over time
+    pub reward_vestings: [RewardVesting; REWARD_NUM],
+
+    /// The token_0 referral fees accrued to the referrers and not collected yet
+    pub referral_fees_token_0: u64,
+    /// The token_1 referral fees accrued to the referrers and not collected yet
+    pub referral_fees_token_1: u64,
+
+    /// The token_0 creator fees accrued to the pool creator and not collected yet
+    pub creator_fees_token_0: u64,
+    /// The token_1 creator fees accrued to the pool creator and not collected yet
+    pub creator_fees_token_1: u64,
+    /// The part of the trade fee accrued to the pool creator, set at creation, 0 means no creator fee
+    pub creator_fee_rate: u32,
+    /// The trade fee rate at the launch of the pool, decays linearly to the trade fee rate of
+    /// the amm config over the launch fee duration, 0 means no launch fee
+    pub launch_fee_rate: u32,
+
+    /// The owner of the token accounts receiving the fund fees of the pool instead of the fund owner
+    /// of the amm config, default pubkey means no override
+    pub fund_fee_recipient: Pubkey,
+
+    /// The timestamp from which the launch fee decays, the creation of the pool or the open time set later
+    pub launch_fee_start_time: u64,
+    /// The seconds over which the launch fee decays to the trade fee rate of the amm config
+    pub launch_fee_duration: u64,
+
+    /// The maximum output amount of a swap in the anti-bot window, 0 means no limit
+    pub anti_bot_max_amount_out: u64,
+    /// The seconds after the open time during which the output of a swap is limited
+    pub anti_bot_duration: u64,
+
+    /// The program invoked with the result of each swap after settlement, set by the
+    /// operation owner, default pubkey means no swap hook
+    pub swap_hook: Pubkey,
+
+    /// The external price feed (a Pyth price update account) checked against the execution
+    /// price of each swap, set by the operation owner, default pubkey means no oracle check
+    pub external_oracle: Pubkey,
+    /// The maximum deviation of the execution price of a swap from the external oracle price,
+    /// in basis points
+    pub external_oracle_max_deviation_bps: u64,
+
     // Unused bytes for future upgrades.
-    pub padding1: [u64; 24],
-    pub padding2: [u64; 32],
+    pub padding2: [u64; 18],
 }
 
 impl PoolState {
+    /// The current layout version, bumped when new fields are introduced
+    pub const VERSION: u8 = 1;
+
     pub const LEN: usize = 8
         + 1
         + 32 * 7
@@ -219,7 +302,9 @@ impl PoolState {
         self.swap_in_amount_token_1 = 0;
         self.swap_out_amount_token_0 = 0;
         self.status = 0;
-        self.padding = [0; 7];
+        self.extension_reward_count = 0;
+        self.version = Self::VERSION;
+        self.padding = [0; 5];
         self.tick_array_bitmap = [0; 16];
         self.total_fees_token_0 = 0;
         self.total_fees_claimed_token_0 = 0;
@@ -229,13 +314,43 @@ impl PoolState {
         self.fund_fees_token_1 = 0;
         self.open_time = open_time;
         self.recent_epoch = get_recent_epoch()?;
-        self.padding1 = [0; 24];
-        self.padding2 = [0; 32];
+        self.liquidity_seconds_global = 0;
+        self.liquidity_seconds_update_time = 0;
+        self.min_sqrt_price_x64 = 0;
+        self.max_sqrt_price_x64 = 0;
+        self.last_swap_slot = 0;
+        self.slot_swap_volume_zero_for_one = 0;
+        self.slot_swap_volume_one_for_zero = 0;
+        self.reward_vestings = [RewardVesting::default(); REWARD_NUM];
+        self.referral_fees_token_0 = 0;
+        self.referral_fees_token_1 = 0;
+        self.creator_fees_token_0 = 0;
+        self.creator_fees_token_1 = 0;
+        self.creator_fee_rate = 0;
+        self.launch_fee_rate = 0;
+        self.fund_fee_recipient = Pubkey::default();
+        self.launch_fee_start_time = 0;
+        self.launch_fee_duration = 0;
+        self.anti_bot_max_amount_out = 0;
+        self.anti_bot_duration = 0;
+        self.swap_hook = Pubkey::default();
+        self.external_oracle = Pubkey::default();
+        self.external_oracle_max_deviation_bps = 0;
+        self.padding2 = [0; 18];
         self.observation_key = observation_state_key;
 
         Ok(())
     }
 
+    /// Upgrades the account to the current layout version, returns the version before the upgrade
+    pub fn migrate(&mut self) -> Result<u8> {
+        let version = self.version;
+        require_gt!(Self::VERSION, version, ErrorCode::AccountAlreadyMigrated);
+        // version 1 only introduces the version field
+        self.version = Self::VERSION;
+        Ok(version)
+    }
+
     pub fn initialize_reward(
         &mut self,
         open_time: u64,
@@ -245,6 +360,7 @@ impl PoolState {
         token_vault: &Pubkey,
         authority: &Pubkey,
         operation_state: &OperationState,
+        whitelist_mint_extension: &[u8],
     ) -> Result<()> {
         let reward_infos = self.reward_infos;
         let lowest_index = match reward_infos.iter().position(|r| !r.initialized()) {
@@ -266,7 +382,6 @@ impl PoolState {
             !reward_mints.contains(token_mint),
             ErrorCode::RewardTokenAlreadyInUse
         );
-        let whitelist_mints = operation_state.whitelist_mints.to_vec();
         // The current init token is the penult.
         if lowest_index == REWARD_NUM - 2 {
             // If token_mint_0 or token_mint_1 is not contains in the initialized rewards token,
@@ -277,7 +392,8 @@ impl PoolState {
                 require!(
                     *token_mint == self.token_mint_0
                         || *token_mint == self.token_mint_1
-                        || whitelist_mints.contains(token_mint),
+                        || operation_state
+                            .validate_whitelist_mint(whitelist_mint_extension, *token_mint),
                     ErrorCode::ExceptPoolVaultMint
                 );
             }
@@ -308,6 +424,21 @@ impl PoolState {
         Ok(())
     }
 
+    /// Accumulates the active liquidity over the seconds elapsed since the last update,
+    /// must be called before the active liquidity changes.
+    pub fn update_liquidity_seconds(&mut self, curr_timestamp: u64) {
+        if curr_timestamp > self.liquidity_seconds_update_time {
+            // the accumulation starts from the first update after the pool is created or upgraded
+            if self.liquidity_seconds_update_time != 0 {
+                let time_delta = curr_timestamp - self.liquidity_seconds_update_time;
+                self.liquidity_seconds_global = self
+                    .liquidity_seconds_global
+                    .saturating_add(self.liquidity.saturating_mul(u128::from(time_delta)));
+            }
+            self.liquidity_seconds_update_time = curr_timestamp;
+        }
+    }
+
     // Calculates the next global reward growth variables based on the given timestamp.
     // The provided timestamp must be greater than or equal to the last updated timestamp.
     pub fn update_reward_infos(&mut self, curr_timestamp: u64) -> Result<[RewardInfo; REWARD_NUM]> {
@@ -317,67 +448,9 @@ impl PoolState {
         let mut next_reward_infos = self.reward_infos;
 
         for i in 0..REWARD_NUM {
-            let reward_info = &mut next_reward_infos[i];
-            if !reward_info.initialized() {
-                continue;
-            }
-            if curr_timestamp <= reward_info.open_time {
-                continue;
-            }
-            let latest_update_timestamp = curr_timestamp.min(reward_info.end_time);
-
-            if self.liquidity != 0 {
-                require_gte!(latest_update_timestamp, reward_info.last_update_time);
-                let time_delta = latest_update_timestamp
-                    .checked_sub(reward_info.last_update_time)
-                    .unwrap();
-
-                let reward_growth_delta = U256::from(time_delta)
-                    .mul_div_floor(
-                        U256::from(reward_info.emissions_per_second_x64),
-                        U256::from(self.liquidity),
-                    )
-                    .unwrap();
-
-                reward_info.reward_growth_global_x64 = reward_info
-                    .reward_growth_global_x64
-                    .checked_add(reward_growth_delta.as_u128())
-                    .unwrap();
-
-                reward_info.reward_total_emissioned = reward_info
-                    .reward_total_emissioned
-                    .checked_add(
-                        U128::from(time_delta)
-                            .mul_div_ceil(
-                                U128::from(reward_info.emissions_per_second_x64),
-                                U128::from(fixed_point_64::Q64),
-                            )
-                            .unwrap()
-                            .as_u64(),
-                    )
-                    .unwrap();
-                #[cfg(feature = "enable-log")]
-                msg!(
-                    "reward_index:{},latest_update_timestamp:{},reward_info.reward_last_update_time:{},time_delta:{},reward_emission_per_second_x64:{},reward_growth_delta:{},reward_info.reward_growth_global_x64:{}, reward_info.reward_claim:{}",
-                    i,
-                    latest_update_timestamp,
-                    identity(reward_info.last_update_time),
-                    time_delta,
-                    identity(reward_info.emissions_per_second_x64),
-                    reward_growth_delta,
-                    identity(reward_info.reward_growth_global_x64),
-                    identity(reward_info.reward_claimed)
-                );
-            }
-            reward_info.last_update_time = latest_update_timestamp;
-            // update reward state
-            if latest_update_timestamp >= reward_info.open_time
-                && latest_update_timestamp < reward_info.end_time
-            {
-                reward_info.reward_state = RewardState::Opening as u8;
-            } else if latest_update_timestamp == next_reward_infos[i].end_time {
-                next_reward_infos[i].reward_state = RewardState::Ended as u8;
-            }
+            #[cfg(feature = "enable-log")]
+            msg!("reward_index:{}", i);
+            next_reward_infos[i].update(curr_timestamp, self.liquidity)?;
         }
         self.reward_infos = next_reward_infos;
         #[cfg(feature = "enable-log")]
@@ -397,6 +470,18 @@ impl PoolState {
         Ok(())
     }
 
+    /// Whether any reward of the pool has a vesting schedule
+    pub fn has_reward_vesting(&self) -> bool {
+        self.reward_vestings
+            .iter()
+            .any(|vesting| vesting.is_enabled())
+    }
+
+    /// Whether the positions of the pool must have a reward extension to track the extension rewards or the vesting
+    pub fn requires_position_reward_extension(&self) -> bool {
+        self.extension_reward_count > 0 || self.has_reward_vesting()
+    }
+
     pub fn add_reward_clamed(&mut self, index: usize, amount: u64) -> Result<()> {
         assert!(index < REWARD_NUM);
         self.reward_infos[index].reward_claimed = self.reward_infos[index]
@@ -446,6 +531,29 @@ impl PoolState {
         }
     }
 
+    /// Returns true if the bit of the tick array is set in the bitmap
+    pub fn is_tick_array_initialized<'c: 'info, 'info>(
+        &self,
+        tickarray_bitmap_extension: Option<&'c AccountInfo<'info>>,
+        tick_array_start_index: i32,
+    ) -> Result<bool> {
+        if self.is_overflow_default_tickarray_bitmap(vec![tick_array_start_index]) {
+            require_keys_eq!(
+                tickarray_bitmap_extension.unwrap().key(),
+                TickArrayBitmapExtension::key(self.key())
+            );
+            let (is_initialized, _) = AccountLoader::<TickArrayBitmapExtension>::try_from(
+                tickarray_bitmap_extension.unwrap(),
+            )?
+            .load()?
+            .check_tick_array_is_initialized(tick_array_start_index, self.tick_spacing)?;
+            Ok(is_initialized)
+        } else {
+            let tick_array_offset_in_bitmap = self.get_tick_array_offset(tick_array_start_index)?;
+            Ok(U1024(self.tick_array_bitmap).bit(tick_array_offset_in_bitmap))
+        }
+    }
+
     pub fn get_first_initialized_tick_array(
         &self,
         tickarray_bitmap_extension: &Option<TickArrayBitmapExtension>,
@@ -543,10 +651,278 @@ impl PoolState {
 
     /// Get status by bit, if it is `noraml` status, return true
     pub fn get_status_by_bit(&self, bit: PoolStatusBitIndex) -> bool {
+        if self.is_withdraw_only() {
+            return matches!(
+                bit,
+                PoolStatusBitIndex::DecreaseLiquidity
+                    | PoolStatusBitIndex::CollectFee
+                    | PoolStatusBitIndex::CollectReward
+            );
+        }
         let status = u8::from(1) << (bit as u8);
         self.status.bitand(status) == 0
     }
 
+    /// Swap is permitted in the given direction
+    pub fn is_swap_enabled(&self, zero_for_one: bool) -> bool {
+        self.get_status_by_bit(PoolStatusBitIndex::Swap)
+            && self.get_status_by_bit(if zero_for_one {
+                PoolStatusBitIndex::SwapZeroForOne
+            } else {
+                PoolStatusBitIndex::SwapOneForZero
+            })
+    }
+
+    /// The pool is frozen with the status 255, as on the insolvency of its vaults
+    pub fn is_frozen(&self) -> bool {
+        self.status == 255
+    }
+
+    /// The withdraw-only bit is set, the frozen status 255 still disables everything
+    pub fn is_withdraw_only(&self) -> bool {
+        let status: u8 = 1 << (PoolStatusBitIndex::WithdrawOnly as u8);
+        self.status != 255 && self.status.bitand(status) != 0
+    }
+
+    /// Clamps the sqrt price limit of a swap inside the price band,
+    /// fails if the current price is already out of the band in the swap direction
+    pub fn clamp_sqrt_price_limit(
+        &self,
+        zero_for_one: bool,
+        sqrt_price_limit_x64: u128,
+    ) -> Result<u128> {
+        if zero_for_one {
+            if self.min_sqrt_price_x64 == 0 {
+                return Ok(sqrt_price_limit_x64);
+            }
+            require_gt!(
+                self.sqrt_price_x64,
+                self.min_sqrt_price_x64,
+                ErrorCode::PriceBandExceeded
+            );
+            Ok(sqrt_price_limit_x64.max(self.min_sqrt_price_x64))
+        } else {
+            if self.max_sqrt_price_x64 == 0 {
+                return Ok(sqrt_price_limit_x64);
+            }
+            require_gt!(
+                self.max_sqrt_price_x64,
+                self.sqrt_price_x64,
+                ErrorCode::PriceBandExceeded
+            );
+            Ok(sqrt_price_limit_x64.min(self.max_sqrt_price_x64))
+        }
+    }
+
+    /// Resets the same slot swap volumes when a swap happens in a new slot
+    pub fn update_swap_slot(&mut self, slot: u64) {
+        if slot != self.last_swap_slot {
+            self.last_swap_slot = slot;
+            self.slot_swap_volume_zero_for_one = 0;
+            self.slot_swap_volume_one_for_zero = 0;
+        }
+    }
+
+    /// Accumulates the swap input amount of the direction in the last swap slot
+    pub fn add_slot_swap_volume(&mut self, zero_for_one: bool, amount_in: u64) {
+        if zero_for_one {
+            self.slot_swap_volume_zero_for_one =
+                self.slot_swap_volume_zero_for_one.saturating_add(amount_in);
+        } else {
+            self.slot_swap_volume_one_for_zero =
+                self.slot_swap_volume_one_for_zero.saturating_add(amount_in);
+        }
+    }
+
+    /// Returns the accumulated LP, protocol, fund and creator fees of the input token of the swap direction
+    pub fn accumulated_swap_fees(&self, zero_for_one: bool) -> (u64, u64, u64, u64) {
+        if zero_for_one {
+            (
+                self.total_fees_token_0,
+                self.protocol_fees_token_0,
+                self.fund_fees_token_0,
+                self.creator_fees_token_0,
+            )
+        } else {
+            (
+                self.total_fees_token_1,
+                self.protocol_fees_token_1,
+                self.fund_fees_token_1,
+                self.creator_fees_token_1,
+            )
+        }
+    }
+
+    /// Sets the creator fee rate at creation, the protocol, fund and creator fees
+    /// together can not exceed the trade fee
+    pub fn set_creator_fee_rate(
+        &mut self,
+        amm_config: &AmmConfig,
+        creator_fee_rate: u32,
+    ) -> Result<()> {
+        require_gte!(
+            amm_config.max_creator_fee_rate,
+            creator_fee_rate,
+            ErrorCode::InvalidCreatorFeeRate
+        );
+        require_gte!(
+            FEE_RATE_DENOMINATOR_VALUE,
+            amm_config.protocol_fee_rate + amm_config.fund_fee_rate + creator_fee_rate,
+            ErrorCode::InvalidCreatorFeeRate
+        );
+        self.creator_fee_rate = creator_fee_rate;
+        Ok(())
+    }
+
+    /// Sets the launch fee at creation, the trade fee rate starts at `launch_fee_rate` and
+    /// decays linearly to the trade fee rate of the amm config over `launch_fee_duration` seconds
+    pub fn set_launch_fee(
+        &mut self,
+        amm_config: &AmmConfig,
+        launch_fee_rate: u32,
+        launch_fee_duration: u64,
+        block_timestamp: u64,
+    ) -> Result<()> {
+        require!(
+            launch_fee_rate > amm_config.trade_fee_rate
+                && launch_fee_rate < FEE_RATE_DENOMINATOR_VALUE
+                && launch_fee_duration > 0
+                && launch_fee_duration <= MAX_LAUNCH_FEE_DURATION,
+            ErrorCode::InvalidLaunchFee
+        );
+        self.launch_fee_rate = launch_fee_rate;
+        self.launch_fee_start_time = block_timestamp;
+        self.launch_fee_duration = launch_fee_duration;
+        Ok(())
+    }
+
+    /// Returns whether any swap has happened in the pool
+    pub fn has_swapped(&self) -> bool {
+        self.swap_in_amount_token_0 != 0 || self.swap_in_amount_token_1 != 0
+    }
+
+    /// Credits the donated amounts to the fee growth of the liquidity in range, returns
+    /// the fee growth of token_0 and token_1 after the donation
+    pub fn donate(&mut self, amount_0: u64, amount_1: u64) -> Result<(u128, u128)> {
+        let liquidity = self.liquidity;
+        require_gt!(liquidity, 0, ErrorCode::NoLiquidityToDonate);
+        let fee_growth_delta = |amount: u64| {
+            U128::from(amount)
+                .mul_div_floor(U128::from(fixed_point_64::Q64), U128::from(liquidity))
+                .unwrap()
+                .as_u128()
+        };
+        self.fee_growth_global_0_x64 = self
+            .fee_growth_global_0_x64
+            .checked_add(fee_growth_delta(amount_0))
+            .unwrap();
+        self.fee_growth_global_1_x64 = self
+            .fee_growth_global_1_x64
+            .checked_add(fee_growth_delta(amount_1))
+            .unwrap();
+        self.total_fees_token_0 = self.total_fees_token_0.checked_add(amount_0).unwrap();
+        self.total_fees_token_1 = self.total_fees_token_1.checked_add(amount_1).unwrap();
+        Ok((self.fee_growth_global_0_x64, self.fee_growth_global_1_x64))
+    }
+
+    /// Resets the price of a pool without liquidity in range, the tick follows the price
+    pub fn reset_sqrt_price(&mut self, sqrt_price_x64: u128) -> Result<()> {
+        require_eq!(self.liquidity, 0, ErrorCode::PoolNotDrained);
+        let tick = tick_math::get_tick_at_sqrt_price(sqrt_price_x64)?;
+        self.sqrt_price_x64 = sqrt_price_x64;
+        self.tick_current = tick;
+        Ok(())
+    }
+
+    /// Moves the open time of the pool to the future, it can only be updated before any swap
+    /// happens in the pool
+    pub fn update_open_time(&mut self, open_time: u64, block_timestamp: u64) -> Result<()> {
+        require!(!self.has_swapped(), ErrorCode::PoolAlreadySwapped);
+        require_gt!(open_time, block_timestamp, ErrorCode::InvalidOpenTime);
+        self.open_time = open_time;
+        // the launch fee decays from the new open time
+        if self.launch_fee_rate > 0 {
+            self.launch_fee_start_time = open_time;
+        }
+        Ok(())
+    }
+
+    /// Limits the output amount of a swap to `max_amount_out` for `duration` seconds after
+    /// the open time, it can only be set before any swap happens in the pool
+    pub fn set_anti_bot_config(
+        &mut self,
+        max_amount_out: u64,
+        duration: u64,
+        block_timestamp: u64,
+    ) -> Result<()> {
+        require!(!self.has_swapped(), ErrorCode::PoolAlreadySwapped);
+        if max_amount_out != 0 {
+            require!(
+                duration > 0
+                    && duration <= MAX_ANTI_BOT_DURATION
+                    && self.open_time + duration > block_timestamp,
+                ErrorCode::InvalidAntiBotConfig
+            );
+        }
+        self.anti_bot_max_amount_out = max_amount_out;
+        self.anti_bot_duration = duration;
+        Ok(())
+    }
+
+    /// Checks the output amount of a swap against the anti-bot limit in the window after the open time
+    pub fn check_anti_bot_amount_out(&self, amount_out: u64, block_timestamp: u64) -> Result<()> {
+        if self.anti_bot_max_amount_out != 0
+            && block_timestamp < self.open_time + self.anti_bot_duration
+        {
+            require_gte!(
+                self.anti_bot_max_amount_out,
+                amount_out,
+                ErrorCode::AntiBotAmountOutExceeded
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the trade fee rate before the sandwich fee, the launch fee rate decayed
+    /// at the block timestamp if it is higher than the trade fee rate of the amm config.
+    /// The launch fee decays from the open time of the pool, or from its creation if it is open
+    pub fn base_trade_fee_rate(&self, amm_config: &AmmConfig, block_timestamp: u64) -> u32 {
+        let elapsed =
+            block_timestamp.saturating_sub(self.launch_fee_start_time.max(self.open_time));
+        if self.launch_fee_rate <= amm_config.trade_fee_rate || elapsed >= self.launch_fee_duration
+        {
+            return amm_config.trade_fee_rate;
+        }
+        let decay = u64::from(self.launch_fee_rate - amm_config.trade_fee_rate) * elapsed
+            / self.launch_fee_duration;
+        self.launch_fee_rate - u32::try_from(decay).unwrap()
+    }
+
+    /// Returns the trade fee rate of a swap, it is escalated by the sandwich fee multiplier
+    /// if an opposite direction swap has happened in the same slot
+    pub fn swap_trade_fee_rate(
+        &self,
+        amm_config: &AmmConfig,
+        zero_for_one: bool,
+        block_timestamp: u64,
+    ) -> u32 {
+        let base_trade_fee_rate = self.base_trade_fee_rate(amm_config, block_timestamp);
+        let opposite_volume = if zero_for_one {
+            self.slot_swap_volume_one_for_zero
+        } else {
+            self.slot_swap_volume_zero_for_one
+        };
+        if opposite_volume == 0
+            || amm_config.sandwich_fee_multiplier <= SANDWICH_FEE_MULTIPLIER_DENOMINATOR
+        {
+            return base_trade_fee_rate;
+        }
+        let trade_fee_rate = u64::from(base_trade_fee_rate)
+            * u64::from(amm_config.sandwich_fee_multiplier)
+            / u64::from(SANDWICH_FEE_MULTIPLIER_DENOMINATOR);
+        u32::try_from(trade_fee_rate.min(u64::from(FEE_RATE_DENOMINATOR_VALUE - 1))).unwrap()
+    }
+
     pub fn is_overflow_default_tickarray_bitmap(&self, tick_indexs: Vec<i32>) -> bool {
         let (min_tick_array_start_index_boundary, max_tick_array_index_boundary) =
             self.tick_array_start_index_range();
@@ -642,13 +1018,112 @@ impl RewardInfo {
         self.token_mint.ne(&Pubkey::default())
     }
 
-    pub fn get_reward_growths(reward_infos: &[RewardInfo; REWARD_NUM]) -> [u128; REWARD_NUM] {
-        let mut reward_growths = [0u128; REWARD_NUM];
-        for i in 0..REWARD_NUM {
+    pub fn get_reward_growths<const N: usize>(reward_infos: &[RewardInfo; N]) -> [u128; N] {
+        let mut reward_growths = [0u128; N];
+        for i in 0..N {
             reward_growths[i] = reward_infos[i].reward_growth_global_x64;
         }
         reward_growths
     }
+
+    /// Accumulates the reward growth of the given active liquidity up to the given timestamp,
+    /// the timestamp must be greater than or equal to the last updated timestamp.
+    pub fn update(&mut self, curr_timestamp: u64, liquidity: u128) -> Result<()> {
+        if !self.initialized() {
+            return Ok(());
+        }
+        if curr_timestamp <= self.open_time {
+            return Ok(());
+        }
+        let latest_update_timestamp = curr_timestamp.min(self.end_time);
+
+        if liquidity != 0 {
+            require_gte!(latest_update_timestamp, self.last_update_time);
+            let time_delta = latest_update_timestamp
+                .checked_sub(self.last_update_time)
+                .unwrap();
+
+            let reward_growth_delta = U256::from(time_delta)
+                .mul_div_floor(
+                    U256::from(self.emissions_per_second_x64),
+                    U256::from(liquidity),
+                )
+                .unwrap();
+
+            self.reward_growth_global_x64 = self
+                .reward_growth_global_x64
+                .checked_add(reward_growth_delta.as_u128())
+                .unwrap();
+
+            self.reward_total_emissioned = self
+                .reward_total_emissioned
+                .checked_add(
+                    U128::from(time_delta)
+                        .mul_div_ceil(
+                            U128::from(self.emissions_per_second_x64),
+                            U128::from(fixed_point_64::Q64),
+                        )
+                        .unwrap()
+                        .as_u64(),
+                )
+                .unwrap();
+            #[cfg(feature = "enable-log")]
+            msg!(
+                "latest_update_timestamp:{},reward_info.reward_last_update_time:{},time_delta:{},reward_emission_per_second_x64:{},reward_growth_delta:{},reward_info.reward_growth_global_x64:{}, reward_info.reward_claim:{}",
+                latest_update_timestamp,
+                identity(self.last_update_time),
+                time_delta,
+                identity(self.emissions_per_second_x64),
+                reward_growth_delta,
+                identity(self.reward_growth_global_x64),
+                identity(self.reward_claimed)
+            );
+        }
+        self.last_update_time = latest_update_timestamp;
+        // update reward state
+        if latest_update_timestamp >= self.open_time && latest_update_timestamp < self.end_time {
+            self.reward_state = RewardState::Opening as u8;
+        } else if latest_update_timestamp == self.end_time {
+            self.reward_state = RewardState::Ended as u8;
+        }
+        Ok(())
+    }
+}
+
+/// The vesting schedule of a reward, counted from when a position starts to earn the reward:
+/// nothing is claimable before the cliff, then the earned rewards vest linearly over the vesting duration
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct RewardVesting {
+    /// The seconds before any reward is claimable
+    pub cliff_duration: u64,
+    /// The seconds for the rewards to be fully claimable, 0 means no vesting
+    pub vesting_duration: u64,
+}
+
+impl RewardVesting {
+    pub fn is_enabled(&self) -> bool {
+        self.vesting_duration > 0
+    }
+
+    /// Calculates the vested part of the earned amount at the given timestamp
+    pub fn vested_amount(&self, earned_amount: u64, start_time: u64, curr_timestamp: u64) -> u64 {
+        if !self.is_enabled() {
+            return earned_amount;
+        }
+        let elapsed = curr_timestamp.saturating_sub(start_time);
+        if elapsed < self.cliff_duration {
+            return 0;
+        }
+        if elapsed >= self.vesting_duration {
+            return earned_amount;
+        }
+        U128::from(earned_amount)
+            .mul_div_floor(U128::from(elapsed), U128::from(self.vesting_duration))
+            .unwrap()
+            .as_u64()
+    }
 }
 
 /// Emitted when a pool is created and initialized with a starting price
@@ -703,6 +1178,147 @@ pub struct CollectProtocolFeeEvent {
     pub amount_1: u64,
 }
 
+/// Emitted when the creator fees are collected by the pool creator
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct CollectCreatorFeeEvent {
+    /// The pool whose creator fee is collected
+    #[index]
+    pub pool_state: Pubkey,
+
+    /// The address that receives the collected token_0 creator fees
+    pub recipient_token_account_0: Pubkey,
+
+    /// The address that receives the collected token_1 creator fees
+    pub recipient_token_account_1: Pubkey,
+
+    /// The amount of token_0 creator fees that is withdrawn
+    pub amount_0: u64,
+
+    /// The amount of token_1 creator fees that is withdrawn
+    pub amount_1: u64,
+}
+
+/// Emitted when the fund fee recipient of a pool is updated
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct FundFeeRecipientUpdatedEvent {
+    /// The pool of which the fund fee recipient is updated
+    #[index]
+    pub pool_state: Pubkey,
+
+    /// The owner of the token accounts receiving the fund fees, default pubkey means the fund owner
+    pub fund_fee_recipient: Pubkey,
+}
+
+/// Emitted when the anti-bot limit of the pool opening window is updated
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct AntiBotConfigUpdatedEvent {
+    /// The pool of which the anti-bot limit is updated
+    #[index]
+    pub pool_state: Pubkey,
+
+    /// The maximum output amount of a swap in the window, 0 means no limit
+    pub max_amount_out: u64,
+
+    /// The seconds after the open time during which the output of a swap is limited
+    pub duration: u64,
+}
+
+/// Emitted when tokens are donated to the liquidity in range of a pool
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct DonateEvent {
+    /// The pool receiving the donation
+    #[index]
+    pub pool_state: Pubkey,
+
+    /// The donor of the tokens
+    pub donor: Pubkey,
+
+    /// The amount of token_0 credited to the liquidity in range
+    pub amount_0: u64,
+
+    /// The transfer fee of token_0 charged on the donation
+    pub transfer_fee_0: u64,
+
+    /// The amount of token_1 credited to the liquidity in range
+    pub amount_1: u64,
+
+    /// The transfer fee of token_1 charged on the donation
+    pub transfer_fee_1: u64,
+
+    /// The liquidity in range sharing the donation
+    pub liquidity: u128,
+
+    /// The fee growth of token_0 after the donation
+    pub fee_growth_global_0_x64: u128,
+
+    /// The fee growth of token_1 after the donation
+    pub fee_growth_global_1_x64: u128,
+}
+
+/// Emitted when the price of a pool without liquidity is reset
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct PoolSqrtPriceResetEvent {
+    /// The pool of which the price is reset
+    #[index]
+    pub pool_state: Pubkey,
+
+    /// The sqrt price of the pool before the reset
+    pub sqrt_price_x64_before: u128,
+
+    /// The new sqrt price of the pool
+    pub sqrt_price_x64: u128,
+
+    /// The tick of the new sqrt price
+    pub tick: i32,
+}
+
+/// Emitted when the swap hook of a pool is updated
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct SwapHookUpdatedEvent {
+    /// The pool of which the swap hook is updated
+    #[index]
+    pub pool_state: Pubkey,
+
+    /// The program invoked after each swap, default pubkey means no swap hook
+    pub swap_hook: Pubkey,
+}
+
+/// Emitted when the external oracle of a pool is updated
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct ExternalOracleUpdatedEvent {
+    /// The pool of which the external oracle is updated
+    #[index]
+    pub pool_state: Pubkey,
+
+    /// The price feed checked against the execution price, default pubkey means no oracle check
+    pub external_oracle: Pubkey,
+
+    /// The maximum deviation of the execution price from the oracle price, in basis points
+    pub max_deviation_bps: u64,
+}
+
+/// Emitted when a drained pool is closed
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct ClosePoolEvent {
+    /// The closed pool
+    #[index]
+    pub pool_state: Pubkey,
+
+    /// The account received the rent of the closed accounts
+    pub recipient: Pubkey,
+}
+
+/// The layout version of `SwapEvent`
+pub const SWAP_EVENT_VERSION: u8 = 3;
+
 /// Emitted by when a swap is performed for a pool
 #[event]
 #[cfg_attr(feature = "client", derive(Debug))]
@@ -748,6 +1364,27 @@ pub struct SwapEvent {
 
     /// The log base 1.0001 of price of the pool after the swap
     pub tick: i32,
+
+    /// The layout version of the event, the fee breakdown below is emitted since version 1
+    pub version: u8,
+
+    /// The trade fee charged on the input token, including the protocol fee, fund fee and creator fee
+    pub trade_fee: u64,
+
+    /// The part of the trade fee owed to the protocol
+    pub protocol_fee: u64,
+
+    /// The part of the trade fee owed to the fund
+    pub fund_fee: u64,
+
+    /// The part of the trade fee owed to the pool creator, emitted since version 2
+    pub creator_fee: u64,
+
+    /// The index of the most recently updated oracle observation after the swap, emitted since version 3
+    pub observation_index: u16,
+
+    /// The block timestamp of the most recently updated oracle observation, emitted since version 3
+    pub observation_timestamp: u32,
 }
 
 /// Emitted pool liquidity change when increase and decrease liquidity
@@ -774,6 +1411,21 @@ pub struct LiquidityChangeEvent {
     pub liquidity_after: u128,
 }
 
+/// Emitted when the price band of a pool is updated
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct PoolPriceBandUpdatedEvent {
+    /// The pool of which the price band is updated
+    #[index]
+    pub pool_state: Pubkey,
+
+    /// The lower bound of the price band, 0 means no bound
+    pub min_sqrt_price_x64: u128,
+
+    /// The upper bound of the price band, 0 means no bound
+    pub max_sqrt_price_x64: u128,
+}
+
 // /// Emitted when price move in a swap step
 // #[event]
 // #[cfg_attr(feature = "client", derive(Debug))]
@@ -1000,6 +1652,63 @@ pub mod pool_test {
                 false
             );
         }
+
+        #[test]
+        fn withdraw_only_status() {
+            let mut pool_state = PoolState::default();
+            // withdraw-only overrides the disabled decrease liquidity bit
+            pool_state.set_status(0b0010_0010);
+            assert!(pool_state.is_withdraw_only());
+            assert!(!pool_state.get_status_by_bit(PoolStatusBitIndex::Swap));
+            assert!(
+                !pool_state.get_status_by_bit(PoolStatusBitIndex::OpenPositionOrIncreaseLiquidity)
+            );
+            assert!(pool_state.get_status_by_bit(PoolStatusBitIndex::DecreaseLiquidity));
+            assert!(pool_state.get_status_by_bit(PoolStatusBitIndex::CollectFee));
+            assert!(pool_state.get_status_by_bit(PoolStatusBitIndex::CollectReward));
+
+            pool_state
+                .set_status_by_bit(PoolStatusBitIndex::WithdrawOnly, PoolStatusBitFlag::Enable);
+            assert!(!pool_state.is_withdraw_only());
+            assert!(pool_state.get_status_by_bit(PoolStatusBitIndex::Swap));
+            assert!(!pool_state.get_status_by_bit(PoolStatusBitIndex::DecreaseLiquidity));
+
+            // frozen pool disables everything
+            pool_state.set_status(255);
+            assert!(pool_state.is_frozen());
+            assert!(!pool_state.is_withdraw_only());
+            assert!(!pool_state.get_status_by_bit(PoolStatusBitIndex::DecreaseLiquidity));
+        }
+
+        #[test]
+        fn direction_swap_status() {
+            let mut pool_state = PoolState::default();
+            assert!(pool_state.is_swap_enabled(true));
+            assert!(pool_state.is_swap_enabled(false));
+
+            pool_state.set_status_by_bit(
+                PoolStatusBitIndex::SwapZeroForOne,
+                PoolStatusBitFlag::Disable,
+            );
+            assert!(!pool_state.is_swap_enabled(true));
+            assert!(pool_state.is_swap_enabled(false));
+
+            pool_state.set_status_by_bit(
+                PoolStatusBitIndex::SwapZeroForOne,
+                PoolStatusBitFlag::Enable,
+            );
+            pool_state.set_status_by_bit(
+                PoolStatusBitIndex::SwapOneForZero,
+                PoolStatusBitFlag::Disable,
+            );
+            assert!(pool_state.is_swap_enabled(true));
+            assert!(!pool_state.is_swap_enabled(false));
+
+            // the swap bit disables both directions
+            pool_state.set_status(1 << PoolStatusBitIndex::Swap as u8);
+            assert!(!pool_state.is_swap_enabled(true));
+            assert!(!pool_state.is_swap_enabled(false));
+        }
     }
 
     mod update_reward_infos_test {
@@ -1025,6 +1734,7 @@ pub mod pool_test {
                     &Pubkey::default(),
                     &Pubkey::default(),
                     &operation_state,
+                    &[],
                 )
                 .unwrap();
 
@@ -1059,6 +1769,316 @@ pub mod pool_test {
         }
     }
 
+    mod price_band_test {
+        use super::*;
+
+        #[test]
+        fn clamp_sqrt_price_limit_test() {
+            let pool_state = &mut PoolState::default();
+            pool_state.sqrt_price_x64 = 1000;
+            // no band
+            assert_eq!(pool_state.clamp_sqrt_price_limit(true, 10).unwrap(), 10);
+            assert_eq!(
+                pool_state.clamp_sqrt_price_limit(false, 5000).unwrap(),
+                5000
+            );
+
+            pool_state.min_sqrt_price_x64 = 900;
+            pool_state.max_sqrt_price_x64 = 1100;
+            assert_eq!(pool_state.clamp_sqrt_price_limit(true, 10).unwrap(), 900);
+            assert_eq!(pool_state.clamp_sqrt_price_limit(true, 950).unwrap(), 950);
+            assert_eq!(
+                pool_state.clamp_sqrt_price_limit(false, 5000).unwrap(),
+                1100
+            );
+            assert_eq!(
+                pool_state.clamp_sqrt_price_limit(false, 1050).unwrap(),
+                1050
+            );
+
+            // price is on the lower bound, only one for zero is permitted
+            pool_state.sqrt_price_x64 = 900;
+            assert_eq!(
+                pool_state.clamp_sqrt_price_limit(true, 10).unwrap_err(),
+                error!(ErrorCode::PriceBandExceeded)
+            );
+            assert_eq!(
+                pool_state.clamp_sqrt_price_limit(false, 5000).unwrap(),
+                1100
+            );
+
+            // price is above the upper bound, only zero for one is permitted
+            pool_state.sqrt_price_x64 = 1200;
+            assert_eq!(
+                pool_state.clamp_sqrt_price_limit(false, 5000).unwrap_err(),
+                error!(ErrorCode::PriceBandExceeded)
+            );
+            assert_eq!(pool_state.clamp_sqrt_price_limit(true, 10).unwrap(), 900);
+        }
+    }
+
+    mod sandwich_fee_test {
+        use super::*;
+
+        #[test]
+        fn swap_trade_fee_rate_test() {
+            let pool_state = &mut PoolState::default();
+            let amm_config = &mut AmmConfig {
+                trade_fee_rate: 2500,
+                ..Default::default()
+            };
+            pool_state.update_swap_slot(10);
+            assert_eq!(pool_state.swap_trade_fee_rate(amm_config, true, 0), 2500);
+            pool_state.add_slot_swap_volume(true, 1000);
+            // disabled
+            assert_eq!(pool_state.swap_trade_fee_rate(amm_config, false, 0), 2500);
+
+            amm_config.sandwich_fee_multiplier = 300;
+            // the same direction is not escalated
+            assert_eq!(pool_state.swap_trade_fee_rate(amm_config, true, 0), 2500);
+            assert_eq!(pool_state.swap_trade_fee_rate(amm_config, false, 0), 7500);
+
+            // the multiplied fee rate is capped
+            amm_config.sandwich_fee_multiplier = u16::MAX;
+            amm_config.trade_fee_rate = 100_000;
+            assert_eq!(
+                pool_state.swap_trade_fee_rate(amm_config, false, 0),
+                FEE_RATE_DENOMINATOR_VALUE - 1
+            );
+
+            // volumes are reset in a new slot
+            pool_state.update_swap_slot(11);
+            assert_eq!(
+                pool_state.swap_trade_fee_rate(amm_config, false, 0),
+                100_000
+            );
+        }
+    }
+
+    mod anti_bot_test {
+        use super::*;
+
+        #[test]
+        fn check_anti_bot_amount_out_test() {
+            let pool_state = &mut PoolState::default();
+            pool_state.open_time = 1000;
+            pool_state
+                .check_anti_bot_amount_out(u64::MAX, 1000)
+                .unwrap();
+
+            // the window has ended
+            assert_eq!(
+                pool_state.set_anti_bot_config(100, 60, 1060).unwrap_err(),
+                error!(ErrorCode::InvalidAntiBotConfig)
+            );
+            pool_state.set_anti_bot_config(100, 60, 1000).unwrap();
+            pool_state.check_anti_bot_amount_out(100, 1000).unwrap();
+            pool_state.check_anti_bot_amount_out(100, 1059).unwrap();
+            assert_eq!(
+                pool_state.check_anti_bot_amount_out(101, 1059).unwrap_err(),
+                error!(ErrorCode::AntiBotAmountOutExceeded)
+            );
+            pool_state.check_anti_bot_amount_out(101, 1060).unwrap();
+
+            // can not be changed once the pool has swapped
+            pool_state.swap_in_amount_token_1 = 1;
+            assert_eq!(
+                pool_state.set_anti_bot_config(0, 0, 1000).unwrap_err(),
+                error!(ErrorCode::PoolAlreadySwapped)
+            );
+        }
+    }
+
+    mod donate_test {
+        use super::*;
+
+        #[test]
+        fn donate_test() {
+            let pool_state = &mut PoolState::default();
+            assert_eq!(
+                pool_state.donate(100, 100).unwrap_err(),
+                error!(ErrorCode::NoLiquidityToDonate)
+            );
+
+            pool_state.liquidity = 4;
+            let (fee_growth_global_0_x64, fee_growth_global_1_x64) =
+                pool_state.donate(100, 0).unwrap();
+            assert_eq!(fee_growth_global_0_x64, 25 * fixed_point_64::Q64);
+            assert_eq!(fee_growth_global_1_x64, 0);
+            assert_eq!({ pool_state.total_fees_token_0 }, 100);
+            assert_eq!({ pool_state.total_fees_token_1 }, 0);
+        }
+    }
+
+    mod reset_sqrt_price_test {
+        use super::*;
+
+        #[test]
+        fn reset_sqrt_price_test() {
+            let pool_state = &mut PoolState::default();
+            let sqrt_price_x64 = tick_math::get_sqrt_price_at_tick(100).unwrap();
+            pool_state.reset_sqrt_price(sqrt_price_x64).unwrap();
+            assert_eq!({ pool_state.sqrt_price_x64 }, sqrt_price_x64);
+            assert_eq!({ pool_state.tick_current }, 100);
+
+            assert!(pool_state
+                .reset_sqrt_price(tick_math::MAX_SQRT_PRICE_X64)
+                .is_err());
+
+            pool_state.liquidity = 1;
+            assert_eq!(
+                pool_state.reset_sqrt_price(sqrt_price_x64).unwrap_err(),
+                error!(ErrorCode::PoolNotDrained)
+            );
+        }
+    }
+
+    mod open_time_test {
+        use super::*;
+
+        #[test]
+        fn update_open_time_test() {
+            let pool_state = &mut PoolState::default();
+            pool_state.update_open_time(2000, 1000).unwrap();
+            assert_eq!({ pool_state.open_time }, 2000);
+            // can be pulled forward but not past now
+            pool_state.update_open_time(1500, 1000).unwrap();
+            assert_eq!({ pool_state.open_time }, 1500);
+            assert_eq!(
+                pool_state.update_open_time(1000, 1000).unwrap_err(),
+                error!(ErrorCode::InvalidOpenTime)
+            );
+
+            pool_state.swap_in_amount_token_0 = 1;
+            assert_eq!(
+                pool_state.update_open_time(3000, 1000).unwrap_err(),
+                error!(ErrorCode::PoolAlreadySwapped)
+            );
+        }
+
+        #[test]
+        fn update_open_time_moves_launch_fee_test() {
+            let pool_state = &mut PoolState::default();
+            let amm_config = &AmmConfig {
+                trade_fee_rate: 2500,
+                ..Default::default()
+            };
+            pool_state
+                .set_launch_fee(amm_config, 502_500, 100, 1000)
+                .unwrap();
+            pool_state.update_open_time(2000, 1000).unwrap();
+            assert_eq!({ pool_state.launch_fee_start_time }, 2000);
+            // pulled forward, the launch fee decays from the new open time
+            pool_state.update_open_time(1500, 1000).unwrap();
+            assert_eq!({ pool_state.launch_fee_start_time }, 1500);
+            assert_eq!(pool_state.base_trade_fee_rate(amm_config, 1500), 502_500);
+            assert_eq!(pool_state.base_trade_fee_rate(amm_config, 1550), 252_500);
+        }
+    }
+
+    mod launch_fee_test {
+        use super::*;
+
+        #[test]
+        fn base_trade_fee_rate_test() {
+            let pool_state = &mut PoolState::default();
+            let amm_config = &AmmConfig {
+                trade_fee_rate: 2500,
+                ..Default::default()
+            };
+            assert_eq!(pool_state.base_trade_fee_rate(amm_config, 1000), 2500);
+
+            pool_state
+                .set_launch_fee(amm_config, 502_500, 100, 1000)
+                .unwrap();
+            assert_eq!(pool_state.base_trade_fee_rate(amm_config, 1000), 502_500);
+            assert_eq!(pool_state.base_trade_fee_rate(amm_config, 1025), 377_500);
+            assert_eq!(pool_state.base_trade_fee_rate(amm_config, 1050), 252_500);
+            assert_eq!(pool_state.base_trade_fee_rate(amm_config, 1099), 7_500);
+            assert_eq!(pool_state.base_trade_fee_rate(amm_config, 1100), 2500);
+            assert_eq!(pool_state.base_trade_fee_rate(amm_config, 2000), 2500);
+
+            // the launch fee decays from the open time of the pool
+            pool_state.open_time = 2000;
+            assert_eq!(pool_state.base_trade_fee_rate(amm_config, 2000), 502_500);
+            assert_eq!(pool_state.base_trade_fee_rate(amm_config, 2050), 252_500);
+            assert_eq!(pool_state.base_trade_fee_rate(amm_config, 2100), 2500);
+        }
+
+        #[test]
+        fn set_launch_fee_test() {
+            let pool_state = &mut PoolState::default();
+            let amm_config = &AmmConfig {
+                trade_fee_rate: 2500,
+                ..Default::default()
+            };
+            for (launch_fee_rate, launch_fee_duration) in [
+                (2500, 100),
+                (FEE_RATE_DENOMINATOR_VALUE, 100),
+                (10_000, 0),
+                (10_000, MAX_LAUNCH_FEE_DURATION + 1),
+            ] {
+                assert_eq!(
+                    pool_state
+                        .set_launch_fee(amm_config, launch_fee_rate, launch_fee_duration, 0)
+                        .unwrap_err(),
+                    error!(ErrorCode::InvalidLaunchFee)
+                );
+            }
+        }
+    }
+
+    mod update_liquidity_seconds_test {
+        use super::*;
+        use std::convert::identity;
+
+        #[test]
+        fn liquidity_seconds_test() {
+            let pool_state = &mut PoolState::default();
+            pool_state.liquidity = 100;
+            // the first update only records the timestamp
+            pool_state.update_liquidity_seconds(1000);
+            assert_eq!(identity(pool_state.liquidity_seconds_global), 0);
+            assert_eq!(identity(pool_state.liquidity_seconds_update_time), 1000);
+
+            pool_state.update_liquidity_seconds(1010);
+            assert_eq!(identity(pool_state.liquidity_seconds_global), 1000);
+
+            // the same or an earlier timestamp accumulates nothing
+            pool_state.liquidity = 200;
+            pool_state.update_liquidity_seconds(1010);
+            pool_state.update_liquidity_seconds(1005);
+            assert_eq!(identity(pool_state.liquidity_seconds_global), 1000);
+            assert_eq!(identity(pool_state.liquidity_seconds_update_time), 1010);
+
+            pool_state.update_liquidity_seconds(1015);
+            assert_eq!(identity(pool_state.liquidity_seconds_global), 2000);
+        }
+    }
+
+    mod reward_vesting_test {
+        use super::*;
+
+        #[test]
+        fn vested_amount_test() {
+            let vesting = RewardVesting {
+                cliff_duration: 100,
+                vesting_duration: 1000,
+            };
+            // nothing before the cliff
+            assert_eq!(vesting.vested_amount(1000, 500, 599), 0);
+            // linear since the start after the cliff
+            assert_eq!(vesting.vested_amount(1000, 500, 600), 100);
+            assert_eq!(vesting.vested_amount(1000, 500, 1000), 500);
+            assert_eq!(vesting.vested_amount(1000, 500, 1500), 1000);
+            assert_eq!(vesting.vested_amount(1000, 500, 9999), 1000);
+            // a start in the future vests nothing
+            assert_eq!(vesting.vested_amount(1000, 2000, 1000), 0);
+
+            assert_eq!(RewardVesting::default().vested_amount(1000, 500, 0), 1000);
+        }
+    }
+
     mod use_tickarray_bitmap_extension_test {
 
         use std::ops::Deref;
@@ -1593,7 +2613,9 @@ pub mod pool_test {
             let swap_in_amount_token_1: u128 = 0x11223344556677008899aabbccddeeff;
             let swap_out_amount_token_0: u128 = 0x11223344556677880099aabbccddeeff;
             let status: u8 = 0x1b;
-            let padding: [u8; 7] = [0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18];
+            let extension_reward_count: u8 = 0x12;
+            let version: u8 = 0x13;
+            let padding: [u8; 5] = [0x14, 0x15, 0x16, 0x17, 0x18];
             // RewardInfo
             let reward_state: u8 = 0x1c;
             let open_time: u64 = 0x123456789abc0def;
@@ -1664,18 +2686,33 @@ pub mod pool_test {
             let fund_fees_token_1: u64 = 0x1230456789abcdef;
             let pool_open_time: u64 = 0x1203456789abcdef;
             let recent_epoch: u64 = 0x1023456789abcdef;
-            let mut padding1: [u64; 24] = [0u64; 24];
-            let mut padding1_data = [0u8; 8 * 24];
-            let mut offset = 0;
-            for i in 0..24 {
-                padding1[i] = u64::MAX - i as u64;
-                padding1_data[offset..offset + 8].copy_from_slice(&padding1[i].to_le_bytes());
-                offset += 8;
-            }
-            let mut padding2: [u64; 32] = [0u64; 32];
-            let mut padding2_data = [0u8; 8 * 32];
+            let liquidity_seconds_global: u128 = 0x11223344556677889900aabbccddeeff;
+            let liquidity_seconds_update_time: u64 = 0x1032456789abcdef;
+            let min_sqrt_price_x64: u128 = 0x112233445566778899;
+            let max_sqrt_price_x64: u128 = 0x99887766554433221100;
+            let last_swap_slot: u64 = 0x1122334455667788;
+            let slot_swap_volume_zero_for_one: u64 = 0x2233445566778899;
+            let slot_swap_volume_one_for_zero: u64 = 0x33445566778899aa;
+            let reward_vesting_cliff_duration: u64 = 0x44556677889900aa;
+            let reward_vesting_vesting_duration: u64 = 0x5566778899aabb00;
+            let referral_fees_token_0: u64 = 0x66778899aabbcc00;
+            let referral_fees_token_1: u64 = 0x778899aabbcc0011;
+            let creator_fees_token_0: u64 = 0x8899aabbccdd0011;
+            let creator_fees_token_1: u64 = 0x99aabbccdd001122;
+            let creator_fee_rate: u32 = 0x11223344;
+            let launch_fee_rate: u32 = 0x22334455;
+            let fund_fee_recipient = Pubkey::new_unique();
+            let launch_fee_start_time: u64 = 0xaabbccdd00112233;
+            let launch_fee_duration: u64 = 0xbbccdd0011223344;
+            let anti_bot_max_amount_out: u64 = 0xccdd001122334455;
+            let anti_bot_duration: u64 = 0xdd00112233445566;
+            let swap_hook = Pubkey::new_unique();
+            let external_oracle = Pubkey::new_unique();
+            let external_oracle_max_deviation_bps: u64 = 0xee00112233445566;
+            let mut padding2: [u64; 18] = [0u64; 18];
+            let mut padding2_data = [0u8; 8 * 18];
             let mut offset = 0;
-            for i in 24..(24 + 32) {
+            for i in 24..(24 + 18) {
                 padding2[i - 24] = u64::MAX - i as u64;
                 padding2_data[offset..offset + 8].copy_from_slice(&padding2[i - 24].to_le_bytes());
                 offset += 8;
@@ -1735,8 +2772,12 @@ pub mod pool_test {
             offset += 16;
             pool_data[offset..offset + 1].copy_from_slice(&status.to_le_bytes());
             offset += 1;
-            pool_data[offset..offset + 7].copy_from_slice(&padding);
-            offset += 7;
+            pool_data[offset..offset + 1].copy_from_slice(&extension_reward_count.to_le_bytes());
+            offset += 1;
+            pool_data[offset..offset + 1].copy_from_slice(&version.to_le_bytes());
+            offset += 1;
+            pool_data[offset..offset + 5].copy_from_slice(&padding);
+            offset += 5;
             pool_data[offset..offset + RewardInfo::LEN * REWARD_NUM]
                 .copy_from_slice(&reward_info_datas);
             offset += RewardInfo::LEN * REWARD_NUM;
@@ -1760,10 +2801,62 @@ pub mod pool_test {
             offset += 8;
             pool_data[offset..offset + 8].copy_from_slice(&recent_epoch.to_le_bytes());
             offset += 8;
-            pool_data[offset..offset + 8 * 24].copy_from_slice(&padding1_data);
-            offset += 8 * 24;
-            pool_data[offset..offset + 8 * 32].copy_from_slice(&padding2_data);
-            offset += 8 * 32;
+            pool_data[offset..offset + 16].copy_from_slice(&liquidity_seconds_global.to_le_bytes());
+            offset += 16;
+            pool_data[offset..offset + 8]
+                .copy_from_slice(&liquidity_seconds_update_time.to_le_bytes());
+            offset += 8;
+            pool_data[offset..offset + 16].copy_from_slice(&min_sqrt_price_x64.to_le_bytes());
+            offset += 16;
+            pool_data[offset..offset + 16].copy_from_slice(&max_sqrt_price_x64.to_le_bytes());
+            offset += 16;
+            pool_data[offset..offset + 8].copy_from_slice(&last_swap_slot.to_le_bytes());
+            offset += 8;
+            pool_data[offset..offset + 8]
+                .copy_from_slice(&slot_swap_volume_zero_for_one.to_le_bytes());
+            offset += 8;
+            pool_data[offset..offset + 8]
+                .copy_from_slice(&slot_swap_volume_one_for_zero.to_le_bytes());
+            offset += 8;
+            for _ in 0..REWARD_NUM {
+                pool_data[offset..offset + 8]
+                    .copy_from_slice(&reward_vesting_cliff_duration.to_le_bytes());
+                offset += 8;
+                pool_data[offset..offset + 8]
+                    .copy_from_slice(&reward_vesting_vesting_duration.to_le_bytes());
+                offset += 8;
+            }
+            pool_data[offset..offset + 8].copy_from_slice(&referral_fees_token_0.to_le_bytes());
+            offset += 8;
+            pool_data[offset..offset + 8].copy_from_slice(&referral_fees_token_1.to_le_bytes());
+            offset += 8;
+            pool_data[offset..offset + 8].copy_from_slice(&creator_fees_token_0.to_le_bytes());
+            offset += 8;
+            pool_data[offset..offset + 8].copy_from_slice(&creator_fees_token_1.to_le_bytes());
+            offset += 8;
+            pool_data[offset..offset + 4].copy_from_slice(&creator_fee_rate.to_le_bytes());
+            offset += 4;
+            pool_data[offset..offset + 4].copy_from_slice(&launch_fee_rate.to_le_bytes());
+            offset += 4;
+            pool_data[offset..offset + 32].copy_from_slice(&fund_fee_recipient.to_bytes());
+            offset += 32;
+            pool_data[offset..offset + 8].copy_from_slice(&launch_fee_start_time.to_le_bytes());
+            offset += 8;
+            pool_data[offset..offset + 8].copy_from_slice(&launch_fee_duration.to_le_bytes());
+            offset += 8;
+            pool_data[offset..offset + 8].copy_from_slice(&anti_bot_max_amount_out.to_le_bytes());
+            offset += 8;
+            pool_data[offset..offset + 8].copy_from_slice(&anti_bot_duration.to_le_bytes());
+            offset += 8;
+            pool_data[offset..offset + 32].copy_from_slice(&swap_hook.to_bytes());
+            offset += 32;
+            pool_data[offset..offset + 32].copy_from_slice(&external_oracle.to_bytes());
+            offset += 32;
+            pool_data[offset..offset + 8]
+                .copy_from_slice(&external_oracle_max_deviation_bps.to_le_bytes());
+            offset += 8;
+            pool_data[offset..offset + 8 * 18].copy_from_slice(&padding2_data);
+            offset += 8 * 18;
 
             // len check
             assert_eq!(offset, pool_data.len());
@@ -1824,6 +2917,10 @@ pub mod pool_test {
             assert_eq!(unpack_swap_out_amount_token_0, swap_out_amount_token_0);
             let unpack_status = unpack_data.status;
             assert_eq!(unpack_status, status);
+            let unpack_extension_reward_count = unpack_data.extension_reward_count;
+            assert_eq!(unpack_extension_reward_count, extension_reward_count);
+            let unpack_version = unpack_data.version;
+            assert_eq!(unpack_version, version);
             let unpack_padding = unpack_data.padding;
             assert_eq!(unpack_padding, padding);
 
@@ -1874,8 +2971,67 @@ pub mod pool_test {
             assert_eq!(unpack_open_time, pool_open_time);
             let unpack_recent_epoch = unpack_data.recent_epoch;
             assert_eq!(unpack_recent_epoch, recent_epoch);
-            let unpack_padding1 = unpack_data.padding1;
-            assert_eq!(unpack_padding1, padding1);
+            let unpack_liquidity_seconds_global = unpack_data.liquidity_seconds_global;
+            assert_eq!(unpack_liquidity_seconds_global, liquidity_seconds_global);
+            let unpack_liquidity_seconds_update_time = unpack_data.liquidity_seconds_update_time;
+            assert_eq!(
+                unpack_liquidity_seconds_update_time,
+                liquidity_seconds_update_time
+            );
+            let unpack_min_sqrt_price_x64 = unpack_data.min_sqrt_price_x64;
+            assert_eq!(unpack_min_sqrt_price_x64, min_sqrt_price_x64);
+            let unpack_max_sqrt_price_x64 = unpack_data.max_sqrt_price_x64;
+            assert_eq!(unpack_max_sqrt_price_x64, max_sqrt_price_x64);
+            let unpack_last_swap_slot = unpack_data.last_swap_slot;
+            assert_eq!(unpack_last_swap_slot, last_swap_slot);
+            let unpack_slot_swap_volume_zero_for_one = unpack_data.slot_swap_volume_zero_for_one;
+            assert_eq!(
+                unpack_slot_swap_volume_zero_for_one,
+                slot_swap_volume_zero_for_one
+            );
+            let unpack_slot_swap_volume_one_for_zero = unpack_data.slot_swap_volume_one_for_zero;
+            assert_eq!(
+                unpack_slot_swap_volume_one_for_zero,
+                slot_swap_volume_one_for_zero
+            );
+            for reward_vesting in unpack_data.reward_vestings {
+                let unpack_cliff_duration = reward_vesting.cliff_duration;
+                assert_eq!(unpack_cliff_duration, reward_vesting_cliff_duration);
+                let unpack_vesting_duration = reward_vesting.vesting_duration;
+                assert_eq!(unpack_vesting_duration, reward_vesting_vesting_duration);
+            }
+            let unpack_referral_fees_token_0 = unpack_data.referral_fees_token_0;
+            assert_eq!(unpack_referral_fees_token_0, referral_fees_token_0);
+            let unpack_referral_fees_token_1 = unpack_data.referral_fees_token_1;
+            assert_eq!(unpack_referral_fees_token_1, referral_fees_token_1);
+            let unpack_creator_fees_token_0 = unpack_data.creator_fees_token_0;
+            assert_eq!(unpack_creator_fees_token_0, creator_fees_token_0);
+            let unpack_creator_fees_token_1 = unpack_data.creator_fees_token_1;
+            assert_eq!(unpack_creator_fees_token_1, creator_fees_token_1);
+            let unpack_creator_fee_rate = unpack_data.creator_fee_rate;
+            assert_eq!(unpack_creator_fee_rate, creator_fee_rate);
+            let unpack_launch_fee_rate = unpack_data.launch_fee_rate;
+            assert_eq!(unpack_launch_fee_rate, launch_fee_rate);
+            let unpack_fund_fee_recipient = unpack_data.fund_fee_recipient;
+            assert_eq!(unpack_fund_fee_recipient, fund_fee_recipient);
+            let unpack_launch_fee_start_time = unpack_data.launch_fee_start_time;
+            assert_eq!(unpack_launch_fee_start_time, launch_fee_start_time);
+            let unpack_launch_fee_duration = unpack_data.launch_fee_duration;
+            assert_eq!(unpack_launch_fee_duration, launch_fee_duration);
+            let unpack_anti_bot_max_amount_out = unpack_data.anti_bot_max_amount_out;
+            assert_eq!(unpack_anti_bot_max_amount_out, anti_bot_max_amount_out);
+            let unpack_anti_bot_duration = unpack_data.anti_bot_duration;
+            assert_eq!(unpack_anti_bot_duration, anti_bot_duration);
+            let unpack_swap_hook = unpack_data.swap_hook;
+            assert_eq!(unpack_swap_hook, swap_hook);
+            let unpack_external_oracle = unpack_data.external_oracle;
+            assert_eq!(unpack_external_oracle, external_oracle);
+            let unpack_external_oracle_max_deviation_bps =
+                unpack_data.external_oracle_max_deviation_bps;
+            assert_eq!(
+                unpack_external_oracle_max_deviation_bps,
+                external_oracle_max_deviation_bps
+            );
             let unpack_padding2 = unpack_data.padding2;
             assert_eq!(unpack_padding2, padding2);
         }
@@ -0,0 +1,149 @@
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+
+pub const POOL_CHECKPOINT_SEED: &str = "pool_checkpoint";
+/// The number of checkpoints kept in the ring buffer
+pub const POOL_CHECKPOINT_NUM: usize = 128;
+/// The minimum seconds between two checkpoints, so that the ring buffer covers more than 5 days
+pub const POOL_CHECKPOINT_INTERVAL: u64 = 60 * 60;
+
+/// The element of checkpoints in PoolCheckpointState
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
+#[derive(Default, Debug)]
+pub struct PoolCheckpoint {
+    /// The block timestamp of the checkpoint
+    pub block_timestamp: u64,
+    /// The fee growth of token_0 as a Q64.64 number
+    pub fee_growth_global_0_x64: u128,
+    /// The fee growth of token_1 as a Q64.64 number
+    pub fee_growth_global_1_x64: u128,
+    /// The in-range liquidity of the pool
+    pub liquidity: u128,
+    /// The sqrt price of the pool as a Q64.64
+    pub sqrt_price_x64: u128,
+}
+
+impl PoolCheckpoint {
+    pub const LEN: usize = 8 + 16 * 4;
+}
+
+/// The recent checkpoints of the fee growths of a pool, recorded by `checkpoint_pool`
+#[account(zero_copy(unsafe))]
+#[repr(C, packed)]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct PoolCheckpointState {
+    /// The pool of the checkpoints
+    pub pool_id: Pubkey,
+    /// The index of the latest checkpoint
+    pub checkpoint_index: u16,
+    pub padding_u8: [u8; 6],
+    /// The ring buffer of the checkpoints
+    pub checkpoints: [PoolCheckpoint; POOL_CHECKPOINT_NUM],
+    // padding for feature update
+    pub padding: [u64; 4],
+}
+
+impl Default for PoolCheckpointState {
+    #[inline]
+    fn default() -> PoolCheckpointState {
+        PoolCheckpointState {
+            pool_id: Pubkey::default(),
+            checkpoint_index: 0,
+            padding_u8: [0; 6],
+            checkpoints: [PoolCheckpoint::default(); POOL_CHECKPOINT_NUM],
+            padding: [0; 4],
+        }
+    }
+}
+
+impl PoolCheckpointState {
+    pub const LEN: usize = 8 + 32 + 2 + 6 + PoolCheckpoint::LEN * POOL_CHECKPOINT_NUM + 8 * 4;
+
+    pub fn key(pool_id: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[POOL_CHECKPOINT_SEED.as_bytes(), pool_id.as_ref()],
+            &crate::id(),
+        )
+        .0
+    }
+
+    /// Records the checkpoint after the latest one, which must be at least `POOL_CHECKPOINT_INTERVAL` old
+    pub fn record(&mut self, checkpoint: PoolCheckpoint) -> Result<()> {
+        let mut next_index = usize::from(self.checkpoint_index);
+        let last_timestamp = self.checkpoints[next_index].block_timestamp;
+        if last_timestamp != 0 {
+            require_gte!(
+                checkpoint.block_timestamp,
+                last_timestamp + POOL_CHECKPOINT_INTERVAL,
+                ErrorCode::CheckpointTooFrequent
+            );
+            next_index = (next_index + 1) % POOL_CHECKPOINT_NUM;
+        }
+        self.checkpoints[next_index] = checkpoint;
+        self.checkpoint_index = next_index as u16;
+        Ok(())
+    }
+}
+
+/// Emitted when a checkpoint of a pool is recorded
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct PoolCheckpointEvent {
+    /// The pool of the checkpoint
+    #[index]
+    pub pool_state: Pubkey,
+    /// The block timestamp of the checkpoint
+    pub block_timestamp: u64,
+    /// The fee growth of token_0 as a Q64.64 number
+    pub fee_growth_global_0_x64: u128,
+    /// The fee growth of token_1 as a Q64.64 number
+    pub fee_growth_global_1_x64: u128,
+    /// The in-range liquidity of the pool
+    pub liquidity: u128,
+    /// The sqrt price of the pool as a Q64.64
+    pub sqrt_price_x64: u128,
+}
+
+#[cfg(test)]
+mod pool_checkpoint_test {
+    use super::*;
+
+    fn checkpoint(block_timestamp: u64) -> PoolCheckpoint {
+        PoolCheckpoint {
+            block_timestamp,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn record_test() {
+        let mut state = PoolCheckpointState::default();
+        state.record(checkpoint(1000)).unwrap();
+        assert_eq!({ state.checkpoint_index }, 0);
+        assert_eq!({ state.checkpoints[0].block_timestamp }, 1000);
+
+        assert!(state
+            .record(checkpoint(1000 + POOL_CHECKPOINT_INTERVAL - 1))
+            .is_err());
+        state
+            .record(checkpoint(1000 + POOL_CHECKPOINT_INTERVAL))
+            .unwrap();
+        assert_eq!({ state.checkpoint_index }, 1);
+    }
+
+    #[test]
+    fn record_wraps_around_test() {
+        let mut state = PoolCheckpointState::default();
+        for i in 0..=POOL_CHECKPOINT_NUM as u64 {
+            state
+                .record(checkpoint(1 + i * POOL_CHECKPOINT_INTERVAL))
+                .unwrap();
+        }
+        assert_eq!({ state.checkpoint_index }, 0);
+        assert_eq!(
+            { state.checkpoints[0].block_timestamp },
+            1 + POOL_CHECKPOINT_NUM as u64 * POOL_CHECKPOINT_INTERVAL
+        );
+    }
+}
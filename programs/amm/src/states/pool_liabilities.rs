@@ -0,0 +1,422 @@
+use crate::error::ErrorCode;
+use crate::libraries::{liquidity_math, tick_math};
+use crate::states::*;
+use crate::util::AccountLoad;
+use anchor_lang::prelude::*;
+use std::ops::Deref;
+
+/// The amounts of tokens owed by the pool out of its vaults
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq)]
+pub struct PoolLiabilities {
+    /// The token_0 backing the liquidity of all positions at the current price, rounded up
+    pub liquidity_amount_0: u64,
+    /// The token_1 backing the liquidity of all positions at the current price, rounded up
+    pub liquidity_amount_1: u64,
+    /// The token_0 LP fees not claimed by the positions
+    pub unclaimed_fees_0: u64,
+    /// The token_1 LP fees not claimed by the positions
+    pub unclaimed_fees_1: u64,
+    /// The token_0 protocol fees not collected
+    pub protocol_fees_0: u64,
+    /// The token_1 protocol fees not collected
+    pub protocol_fees_1: u64,
+    /// The token_0 fund fees not collected
+    pub fund_fees_0: u64,
+    /// The token_1 fund fees not collected
+    pub fund_fees_1: u64,
+    /// The token_0 referral fees not collected by the referrers
+    pub referral_fees_0: u64,
+    /// The token_1 referral fees not collected by the referrers
+    pub referral_fees_1: u64,
+    /// The token_0 creator fees not collected by the pool creator
+    pub creator_fees_0: u64,
+    /// The token_1 creator fees not collected by the pool creator
+    pub creator_fees_1: u64,
+}
+
+impl PoolLiabilities {
+    /// Computes the liabilities of the pool, the tick arrays must include all initialized tick arrays
+    /// of the pool, which is checked against the tick array bitmaps of the pool
+    pub fn new<T: Deref<Target = TickArrayState>>(
+        pool_state: &PoolState,
+        tickarray_bitmap_extension: Option<&TickArrayBitmapExtension>,
+        tick_arrays: &[T],
+    ) -> Result<Self> {
+        let (liquidity_amount_0, liquidity_amount_1) =
+            get_liquidity_amounts(pool_state, tickarray_bitmap_extension, tick_arrays)?;
+        Ok(Self {
+            liquidity_amount_0,
+            liquidity_amount_1,
+            unclaimed_fees_0: pool_state
+                .total_fees_token_0
+                .saturating_sub(pool_state.total_fees_claimed_token_0),
+            unclaimed_fees_1: pool_state
+                .total_fees_token_1
+                .saturating_sub(pool_state.total_fees_claimed_token_1),
+            protocol_fees_0: pool_state.protocol_fees_token_0,
+            protocol_fees_1: pool_state.protocol_fees_token_1,
+            fund_fees_0: pool_state.fund_fees_token_0,
+            fund_fees_1: pool_state.fund_fees_token_1,
+            referral_fees_0: pool_state.referral_fees_token_0,
+            referral_fees_1: pool_state.referral_fees_token_1,
+            creator_fees_0: pool_state.creator_fees_token_0,
+            creator_fees_1: pool_state.creator_fees_token_1,
+        })
+    }
+
+    /// Computes the liabilities of the pool from the accounts of its tickarray_bitmap_extension,
+    /// read if it is created, and of all its initialized tick arrays
+    pub fn load<'info>(
+        pool_state: &PoolState,
+        tickarray_bitmap_extension_info: &AccountInfo<'info>,
+        tick_array_infos: &[AccountInfo<'info>],
+    ) -> Result<Self> {
+        let tickarray_bitmap_extension_loader =
+            if tickarray_bitmap_extension_info.owner == &crate::id() {
+                Some(AccountLoad::<TickArrayBitmapExtension>::try_from(
+                    tickarray_bitmap_extension_info,
+                )?)
+            } else {
+                None
+            };
+        let tickarray_bitmap_extension = tickarray_bitmap_extension_loader
+            .as_ref()
+            .map(|loader| loader.load())
+            .transpose()?;
+        let tick_array_loaders = tick_array_infos
+            .iter()
+            .map(AccountLoad::<TickArrayState>::try_from)
+            .collect::<Result<Vec<_>>>()?;
+        let tick_arrays = tick_array_loaders
+            .iter()
+            .map(|loader| loader.load())
+            .collect::<Result<Vec<_>>>()?;
+        Self::new(
+            pool_state,
+            tickarray_bitmap_extension.as_deref(),
+            &tick_arrays,
+        )
+    }
+
+    pub fn total_0(&self) -> u64 {
+        self.liquidity_amount_0
+            .saturating_add(self.unclaimed_fees_0)
+            .saturating_add(self.protocol_fees_0)
+            .saturating_add(self.fund_fees_0)
+            .saturating_add(self.referral_fees_0)
+            .saturating_add(self.creator_fees_0)
+    }
+
+    pub fn total_1(&self) -> u64 {
+        self.liquidity_amount_1
+            .saturating_add(self.unclaimed_fees_1)
+            .saturating_add(self.protocol_fees_1)
+            .saturating_add(self.fund_fees_1)
+            .saturating_add(self.referral_fees_1)
+            .saturating_add(self.creator_fees_1)
+    }
+}
+
+/// Computes the tokens backing the liquidity of all positions at the current price
+/// by walking the initialized ticks of the pool in order, rounded up
+pub fn get_liquidity_amounts<T: Deref<Target = TickArrayState>>(
+    pool_state: &PoolState,
+    tickarray_bitmap_extension: Option<&TickArrayBitmapExtension>,
+    tick_arrays: &[T],
+) -> Result<(u64, u64)> {
+    let pool_id = pool_state.key();
+    let mut start_indexes = Vec::with_capacity(tick_arrays.len());
+    let mut ticks = Vec::new();
+    for tick_array in tick_arrays {
+        require_keys_eq!(tick_array.pool_id, pool_id);
+        let initialized_ticks = tick_array
+            .ticks
+            .iter()
+            .filter(|tick_state| tick_state.is_initialized())
+            .map(|tick_state| (tick_state.tick, tick_state.liquidity_net));
+        let tick_count = ticks.len();
+        ticks.extend(initialized_ticks);
+        if ticks.len() == tick_count {
+            continue;
+        }
+        let start_tick_index = tick_array.start_tick_index;
+        require!(
+            !start_indexes.contains(&start_tick_index),
+            ErrorCode::IncompleteTickArrays
+        );
+        start_indexes.push(start_tick_index);
+    }
+    // the bit of a tick array is set as long as it has initialized ticks
+    let tick_array_bitmap = pool_state.tick_array_bitmap;
+    let mut initialized_tick_array_count: u32 =
+        tick_array_bitmap.iter().map(|word| word.count_ones()).sum();
+    if let Some(tickarray_bitmap_extension) = tickarray_bitmap_extension {
+        for i in 0..EXTENSION_TICKARRAY_BITMAP_SIZE {
+            let positive_bitmap = tickarray_bitmap_extension.positive_tick_array_bitmap[i];
+            let negative_bitmap = tickarray_bitmap_extension.negative_tick_array_bitmap[i];
+            initialized_tick_array_count += positive_bitmap
+                .iter()
+                .chain(negative_bitmap.iter())
+                .map(|word| word.count_ones())
+                .sum::<u32>();
+        }
+    }
+    require_eq!(
+        start_indexes.len(),
+        initialized_tick_array_count as usize,
+        ErrorCode::IncompleteTickArrays
+    );
+
+    ticks.sort_unstable_by_key(|(tick, _)| *tick);
+    let mut liquidity: u128 = 0;
+    let mut current_liquidity: u128 = 0;
+    let mut amount_0: u64 = 0;
+    let mut amount_1: u64 = 0;
+    for (i, &(tick_lower, liquidity_net)) in ticks.iter().enumerate() {
+        liquidity = liquidity_math::add_delta(liquidity, liquidity_net)?;
+        let Some(&(tick_upper, _)) = ticks.get(i + 1) else {
+            break;
+        };
+        if tick_lower <= pool_state.tick_current && pool_state.tick_current < tick_upper {
+            current_liquidity = liquidity;
+        }
+        if liquidity == 0 {
+            continue;
+        }
+        let sqrt_price_lower_x64 = tick_math::get_sqrt_price_at_tick(tick_lower)?;
+        let sqrt_price_upper_x64 = tick_math::get_sqrt_price_at_tick(tick_upper)?;
+        let sqrt_price_x64 = pool_state
+            .sqrt_price_x64
+            .clamp(sqrt_price_lower_x64, sqrt_price_upper_x64);
+        if sqrt_price_x64 < sqrt_price_upper_x64 {
+            amount_0 = amount_0
+                .checked_add(liquidity_math::get_delta_amount_0_unsigned(
+                    sqrt_price_x64,
+                    sqrt_price_upper_x64,
+                    liquidity,
+                    true,
+                )?)
+                .ok_or(ErrorCode::MaxTokenOverflow)?;
+        }
+        if sqrt_price_x64 > sqrt_price_lower_x64 {
+            amount_1 = amount_1
+                .checked_add(liquidity_math::get_delta_amount_1_unsigned(
+                    sqrt_price_lower_x64,
+                    sqrt_price_x64,
+                    liquidity,
+                    true,
+                )?)
+                .ok_or(ErrorCode::MaxTokenOverflow)?;
+        }
+    }
+    // the liquidity of the ticks must add up to the liquidity of the pool
+    require_eq!(liquidity, 0, ErrorCode::IncompleteTickArrays);
+    require_eq!(
+        current_liquidity,
+        pool_state.liquidity,
+        ErrorCode::IncompleteTickArrays
+    );
+    Ok((amount_0, amount_1))
+}
+
+/// Emitted when the dust of the vaults is swept
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct SweepDustEvent {
+    /// The pool of the vaults
+    #[index]
+    pub pool_state: Pubkey,
+    /// The token_0 balance of the vault before the sweep
+    pub vault_balance_0: u64,
+    /// The token_1 balance of the vault before the sweep
+    pub vault_balance_1: u64,
+    /// The amounts owed by the pool
+    pub liabilities: PoolLiabilities,
+    /// The token_0 surplus swept to the fund owner
+    pub amount_0: u64,
+    /// The token_1 surplus swept to the fund owner
+    pub amount_1: u64,
+}
+
+/// The amount of a reward owed by the pool out of its reward vault
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq)]
+pub struct RewardLiability {
+    /// The mint of the reward
+    pub reward_mint: Pubkey,
+    /// The balance of the reward vault
+    pub vault_balance: u64,
+    /// The reward emitted and not claimed by the positions
+    pub unclaimed_reward: u64,
+}
+
+impl RewardLiability {
+    pub fn new(reward_info: &RewardInfo, vault_balance: u64) -> Self {
+        Self {
+            reward_mint: reward_info.token_mint,
+            vault_balance,
+            unclaimed_reward: reward_info
+                .reward_total_emissioned
+                .saturating_sub(reward_info.reward_claimed),
+        }
+    }
+
+    pub fn is_solvent(&self) -> bool {
+        self.vault_balance >= self.unclaimed_reward
+    }
+}
+
+/// Emitted when the solvency of a pool is asserted
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct PoolSolvencyEvent {
+    /// The pool of the vaults
+    #[index]
+    pub pool_state: Pubkey,
+    /// The token_0 balance of the vault
+    pub vault_balance_0: u64,
+    /// The token_1 balance of the vault
+    pub vault_balance_1: u64,
+    /// The amounts owed by the pool out of the token vaults
+    pub liabilities: PoolLiabilities,
+    /// The amounts owed by the pool out of the reward vaults, of the pool rewards then of the extension rewards
+    pub reward_liabilities: Vec<RewardLiability>,
+    /// Whether all vaults cover the amounts owed out of them
+    pub solvent: bool,
+}
+
+#[cfg(test)]
+mod pool_liabilities_test {
+    use super::*;
+    use crate::states::pool_test::build_pool;
+    use crate::states::tick_array_test::{build_tick, build_tick_array_with_tick_states};
+    use std::cell::RefCell;
+
+    fn build_pool_with_position(
+        tick_lower: i32,
+        tick_upper: i32,
+        liquidity: u128,
+    ) -> (RefCell<PoolState>, Vec<RefCell<TickArrayState>>) {
+        let tick_current = 100;
+        let pool_state = build_pool(
+            tick_current,
+            10,
+            tick_math::get_sqrt_price_at_tick(tick_current).unwrap(),
+            if tick_lower <= tick_current && tick_current < tick_upper {
+                liquidity
+            } else {
+                0
+            },
+        );
+        let pool_id = pool_state.borrow().key();
+        let mut tick_arrays = Vec::new();
+        for (tick, liquidity_net) in [
+            (tick_lower, liquidity as i128),
+            (tick_upper, -(liquidity as i128)),
+        ] {
+            let start_index = TickArrayState::get_array_start_index(tick, 10);
+            tick_arrays.push(build_tick_array_with_tick_states(
+                pool_id,
+                start_index,
+                10,
+                vec![build_tick(tick, liquidity, liquidity_net).take()],
+            ));
+            pool_state
+                .borrow_mut()
+                .flip_tick_array_bit(None, start_index)
+                .unwrap();
+        }
+        (pool_state, tick_arrays)
+    }
+
+    #[test]
+    fn liquidity_amounts_in_range_test() {
+        let liquidity = 1_000_000_000;
+        let (pool_state, tick_arrays) = build_pool_with_position(-1200, 1200, liquidity);
+        let pool_state = pool_state.borrow();
+        let tick_arrays: Vec<_> = tick_arrays
+            .iter()
+            .map(|tick_array| tick_array.borrow())
+            .collect();
+
+        let (amount_0, amount_1) = get_liquidity_amounts(&pool_state, None, &tick_arrays).unwrap();
+        assert_eq!(
+            amount_0,
+            liquidity_math::get_delta_amount_0_unsigned(
+                pool_state.sqrt_price_x64,
+                tick_math::get_sqrt_price_at_tick(1200).unwrap(),
+                liquidity,
+                true
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            amount_1,
+            liquidity_math::get_delta_amount_1_unsigned(
+                tick_math::get_sqrt_price_at_tick(-1200).unwrap(),
+                pool_state.sqrt_price_x64,
+                liquidity,
+                true
+            )
+            .unwrap()
+        );
+
+        // the tick arrays can be given in any order
+        let reversed: Vec<_> = tick_arrays
+            .iter()
+            .rev()
+            .map(|tick_array| tick_array.deref())
+            .collect();
+        assert_eq!(
+            get_liquidity_amounts(&pool_state, None, &reversed).unwrap(),
+            (amount_0, amount_1)
+        );
+    }
+
+    #[test]
+    fn liquidity_amounts_out_of_range_test() {
+        let liquidity = 1_000_000_000;
+        let (pool_state, tick_arrays) = build_pool_with_position(1200, 2400, liquidity);
+        let pool_state = pool_state.borrow();
+        let tick_arrays: Vec<_> = tick_arrays
+            .iter()
+            .map(|tick_array| tick_array.borrow())
+            .collect();
+
+        let (amount_0, amount_1) = get_liquidity_amounts(&pool_state, None, &tick_arrays).unwrap();
+        assert!(amount_0 > 0);
+        assert_eq!(amount_1, 0);
+    }
+
+    #[test]
+    fn liquidity_amounts_missing_tick_array_test() {
+        let (pool_state, tick_arrays) = build_pool_with_position(-1200, 1200, 1_000_000_000);
+        let pool_state = pool_state.borrow();
+        let tick_arrays: Vec<_> = tick_arrays
+            .iter()
+            .map(|tick_array| tick_array.borrow())
+            .collect();
+
+        assert_eq!(
+            get_liquidity_amounts(&pool_state, None, &tick_arrays[..1]).unwrap_err(),
+            error!(ErrorCode::IncompleteTickArrays)
+        );
+        // a tick array given twice is not counted as the missing one
+        let duplicated = [tick_arrays[0].deref(), tick_arrays[0].deref()];
+        assert_eq!(
+            get_liquidity_amounts(&pool_state, None, &duplicated).unwrap_err(),
+            error!(ErrorCode::IncompleteTickArrays)
+        );
+    }
+
+    #[test]
+    fn reward_liability_test() {
+        let mut reward_info = RewardInfo::new(Pubkey::new_unique());
+        reward_info.reward_total_emissioned = 1_000;
+        reward_info.reward_claimed = 400;
+
+        let reward_liability = RewardLiability::new(&reward_info, 600);
+        assert_eq!(reward_liability.unclaimed_reward, 600);
+        assert!(reward_liability.is_solvent());
+        assert!(!RewardLiability::new(&reward_info, 599).is_solvent());
+    }
+}
@@ -0,0 +1,110 @@
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+
+pub const POOL_METADATA_SEED: &str = "pool_metadata";
+/// The max length of the label of a pool in bytes
+pub const MAX_POOL_LABEL_LENGTH: usize = 32;
+/// The max length of the uri of a pool in bytes
+pub const MAX_POOL_URI_LENGTH: usize = 200;
+
+/// Describes a pool on chain, set by the pool creator so that explorers and UIs
+/// can tell the canonical pools of a project from copycats
+#[account]
+#[derive(Default, Debug)]
+pub struct PoolMetadata {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The pool described by the metadata
+    pub pool_id: Pubkey,
+    /// The creator of the pool, the only one can set the metadata
+    pub creator: Pubkey,
+    /// A short label of the pool
+    pub label: String,
+    /// The uri of the external description of the pool
+    pub uri: String,
+    // padding space for upgrade
+    pub padding: [u64; 8],
+}
+
+impl PoolMetadata {
+    pub const LEN: usize =
+        8 + 1 + 32 + 32 + 4 + MAX_POOL_LABEL_LENGTH + 4 + MAX_POOL_URI_LENGTH + 8 * 8;
+
+    pub fn key(pool_id: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[POOL_METADATA_SEED.as_bytes(), pool_id.as_ref()],
+            &crate::id(),
+        )
+        .0
+    }
+
+    pub fn set(&mut self, label: String, uri: String) -> Result<()> {
+        require_gte!(
+            MAX_POOL_LABEL_LENGTH,
+            label.len(),
+            ErrorCode::InvalidPoolMetadata
+        );
+        require_gte!(
+            MAX_POOL_URI_LENGTH,
+            uri.len(),
+            ErrorCode::InvalidPoolMetadata
+        );
+        self.label = label;
+        self.uri = uri;
+        Ok(())
+    }
+}
+
+/// Emitted when the pool creator sets the metadata of a pool
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct SetPoolMetadataEvent {
+    /// The pool described by the metadata
+    #[index]
+    pub pool_state: Pubkey,
+
+    /// The creator of the pool
+    pub creator: Pubkey,
+
+    /// The label of the pool
+    pub label: String,
+
+    /// The uri of the external description of the pool
+    pub uri: String,
+}
+
+#[cfg(test)]
+mod pool_metadata_test {
+    use super::*;
+
+    #[test]
+    fn set_test() {
+        let mut pool_metadata = PoolMetadata::default();
+        pool_metadata
+            .set(
+                "a".repeat(MAX_POOL_LABEL_LENGTH),
+                "b".repeat(MAX_POOL_URI_LENGTH),
+            )
+            .unwrap();
+        assert_eq!(pool_metadata.label.len(), MAX_POOL_LABEL_LENGTH);
+        assert_eq!(pool_metadata.uri.len(), MAX_POOL_URI_LENGTH);
+        // the largest metadata fits in the account
+        assert_eq!(
+            pool_metadata.try_to_vec().unwrap().len() + 8,
+            PoolMetadata::LEN
+        );
+
+        assert_eq!(
+            pool_metadata
+                .set("a".repeat(MAX_POOL_LABEL_LENGTH + 1), String::new())
+                .unwrap_err(),
+            error!(ErrorCode::InvalidPoolMetadata)
+        );
+        assert_eq!(
+            pool_metadata
+                .set(String::new(), "b".repeat(MAX_POOL_URI_LENGTH + 1))
+                .unwrap_err(),
+            error!(ErrorCode::InvalidPoolMetadata)
+        );
+    }
+}
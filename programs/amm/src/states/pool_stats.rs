@@ -0,0 +1,189 @@
+use anchor_lang::prelude::*;
+
+pub const POOL_STATS_SEED: &str = "pool_stats";
+/// The number of epochs kept in the ring buffer
+pub const POOL_STATS_EPOCH_NUM: usize = 30;
+/// The seconds of an epoch of the statistics, a day
+pub const POOL_STATS_EPOCH_DURATION: u64 = 60 * 60 * 24;
+
+/// The element of epochs in PoolStatsState, the swap volume and fees of an epoch
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
+#[derive(Default, Debug)]
+pub struct PoolStatsEpoch {
+    /// The epoch number, the block timestamp divided by `POOL_STATS_EPOCH_DURATION`
+    pub epoch: u64,
+    /// The amount of token_0 swapped in and out
+    pub volume_token_0: u128,
+    /// The amount of token_1 swapped in and out
+    pub volume_token_1: u128,
+    /// The trade fees of token_0, including the protocol and fund fees
+    pub fees_token_0: u64,
+    /// The trade fees of token_1, including the protocol and fund fees
+    pub fees_token_1: u64,
+}
+
+impl PoolStatsEpoch {
+    pub const LEN: usize = 8 + 16 * 2 + 8 * 2;
+}
+
+/// The rolling swap volume and fees of a pool by epoch, updated by the swaps passing the account
+#[account(zero_copy(unsafe))]
+#[repr(C, packed)]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct PoolStatsState {
+    /// The pool of the statistics
+    pub pool_id: Pubkey,
+    /// The index of the latest epoch
+    pub epoch_index: u16,
+    pub padding_u8: [u8; 6],
+    /// The ring buffer of the epochs
+    pub epochs: [PoolStatsEpoch; POOL_STATS_EPOCH_NUM],
+    // padding for feature update
+    pub padding: [u64; 4],
+}
+
+impl Default for PoolStatsState {
+    #[inline]
+    fn default() -> PoolStatsState {
+        PoolStatsState {
+            pool_id: Pubkey::default(),
+            epoch_index: 0,
+            padding_u8: [0; 6],
+            epochs: [PoolStatsEpoch::default(); POOL_STATS_EPOCH_NUM],
+            padding: [0; 4],
+        }
+    }
+}
+
+impl PoolStatsState {
+    pub const LEN: usize = 8 + 32 + 2 + 6 + PoolStatsEpoch::LEN * POOL_STATS_EPOCH_NUM + 8 * 4;
+
+    pub fn key(pool_id: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[POOL_STATS_SEED.as_bytes(), pool_id.as_ref()],
+            &crate::id(),
+        )
+        .0
+    }
+
+    /// Adds a swap to the epoch of the block timestamp, a new epoch overwrites the oldest one
+    pub fn record_swap(
+        &mut self,
+        block_timestamp: u64,
+        zero_for_one: bool,
+        amount_0: u64,
+        amount_1: u64,
+        trade_fee: u64,
+    ) {
+        let epoch = block_timestamp / POOL_STATS_EPOCH_DURATION;
+        let mut index = usize::from(self.epoch_index);
+        let latest_epoch = self.epochs[index].epoch;
+        if latest_epoch != epoch {
+            // the first epoch is written at index 0
+            if latest_epoch != 0 {
+                index = (index + 1) % POOL_STATS_EPOCH_NUM;
+            }
+            self.epochs[index] = PoolStatsEpoch {
+                epoch,
+                ..Default::default()
+            };
+            self.epoch_index = index as u16;
+        }
+        let stats = &mut self.epochs[index];
+        stats.volume_token_0 = stats
+            .volume_token_0
+            .checked_add(u128::from(amount_0))
+            .unwrap();
+        stats.volume_token_1 = stats
+            .volume_token_1
+            .checked_add(u128::from(amount_1))
+            .unwrap();
+        if zero_for_one {
+            stats.fees_token_0 = stats.fees_token_0.checked_add(trade_fee).unwrap();
+        } else {
+            stats.fees_token_1 = stats.fees_token_1.checked_add(trade_fee).unwrap();
+        }
+    }
+
+    /// Returns the volume of token_0 and token_1 of the latest `epoch_count` epochs
+    /// up to the epoch of the block timestamp
+    pub fn recent_volume(&self, block_timestamp: u64, epoch_count: u64) -> (u128, u128) {
+        let current_epoch = block_timestamp / POOL_STATS_EPOCH_DURATION;
+        let epochs = self.epochs;
+        epochs
+            .iter()
+            .filter(|stats| {
+                let epoch = stats.epoch;
+                epoch != 0 && epoch <= current_epoch && epoch + epoch_count > current_epoch
+            })
+            .fold((0, 0), |(volume_0, volume_1), stats| {
+                (
+                    volume_0 + stats.volume_token_0,
+                    volume_1 + stats.volume_token_1,
+                )
+            })
+    }
+}
+
+/// Finds the statistics account of the pool in the remaining accounts, the statistics
+/// are only updated by the swaps passing it
+pub fn find_pool_stats_account<'c, 'info>(
+    remaining_accounts: &'c [AccountInfo<'info>],
+    pool_id: Pubkey,
+) -> Option<&'c AccountInfo<'info>> {
+    let pool_stats_key = PoolStatsState::key(pool_id);
+    remaining_accounts
+        .iter()
+        .find(|account_info| account_info.key() == pool_stats_key)
+}
+
+#[cfg(test)]
+mod pool_stats_test {
+    use super::*;
+
+    #[test]
+    fn record_swap_test() {
+        let mut pool_stats = PoolStatsState::default();
+        let day = POOL_STATS_EPOCH_DURATION;
+        let start = 1_700_000_000 / day * day;
+
+        pool_stats.record_swap(start, true, 100, 90, 1);
+        pool_stats.record_swap(start + day - 1, false, 50, 60, 2);
+        let epoch_index = pool_stats.epoch_index;
+        assert_eq!(epoch_index, 0);
+        let stats = pool_stats.epochs[0];
+        assert_eq!({ stats.volume_token_0 }, 150);
+        assert_eq!({ stats.volume_token_1 }, 150);
+        assert_eq!({ stats.fees_token_0 }, 1);
+        assert_eq!({ stats.fees_token_1 }, 2);
+
+        pool_stats.record_swap(start + day, true, 10, 9, 1);
+        let epoch_index = pool_stats.epoch_index;
+        assert_eq!(epoch_index, 1);
+        assert_eq!(pool_stats.recent_volume(start + day, 1), (10, 9));
+        assert_eq!(pool_stats.recent_volume(start + day, 2), (160, 159));
+        // no swap in the epoch of the timestamp
+        assert_eq!(pool_stats.recent_volume(start + 2 * day, 1), (0, 0));
+    }
+
+    #[test]
+    fn record_swap_ring_buffer_test() {
+        let mut pool_stats = PoolStatsState::default();
+        let day = POOL_STATS_EPOCH_DURATION;
+        let start = 1_700_000_000 / day * day;
+        for i in 0..(POOL_STATS_EPOCH_NUM as u64 + 1) {
+            pool_stats.record_swap(start + i * day, true, 1, 1, 0);
+        }
+        // the oldest epoch is overwritten
+        let epoch_index = pool_stats.epoch_index;
+        assert_eq!(epoch_index, 0);
+        let epoch = pool_stats.epochs[0].epoch;
+        assert_eq!(epoch, start / day + POOL_STATS_EPOCH_NUM as u64);
+        let now = start + POOL_STATS_EPOCH_NUM as u64 * day;
+        assert_eq!(
+            pool_stats.recent_volume(now, POOL_STATS_EPOCH_NUM as u64 + 1),
+            (POOL_STATS_EPOCH_NUM as u128, POOL_STATS_EPOCH_NUM as u128)
+        );
+    }
+}
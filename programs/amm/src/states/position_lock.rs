@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+pub const POSITION_LOCK_SEED: &str = "position_lock";
+
+/// Created when a position is permanently locked, the liquidity of the position
+/// can never be decreased and its fees and rewards can only be collected by the fee recipient
+#[account]
+#[derive(Default, Debug)]
+pub struct PositionLockState {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// Mint address of the locked position
+    pub nft_mint: Pubkey,
+    /// The pool of the locked position
+    pub pool_id: Pubkey,
+    /// The only signer allowed to collect the fees and rewards of the position
+    pub fee_recipient: Pubkey,
+    /// The liquidity of the position when it was locked
+    pub locked_liquidity: u128,
+    /// The block timestamp when the position was locked
+    pub lock_timestamp: u64,
+    /// Whether the position NFT was burned when locking
+    pub nft_burned: bool,
+    // padding space for upgrade
+    pub padding: [u64; 8],
+}
+
+impl PositionLockState {
+    pub const LEN: usize = 8 + 1 + 32 + 32 + 32 + 16 + 8 + 1 + 8 * 8;
+
+    pub fn key(nft_mint: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[POSITION_LOCK_SEED.as_bytes(), nft_mint.as_ref()],
+            &crate::id(),
+        )
+        .0
+    }
+}
+
+/// Emitted when a position is permanently locked
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct LockPositionEvent {
+    /// The pool of the locked position
+    #[index]
+    pub pool_state: Pubkey,
+
+    /// The locked position
+    pub position_nft_mint: Pubkey,
+
+    /// The signer allowed to collect the fees and rewards of the position
+    pub fee_recipient: Pubkey,
+
+    /// The locked liquidity
+    pub liquidity: u128,
+
+    /// Whether the position NFT was burned
+    pub nft_burned: bool,
+}
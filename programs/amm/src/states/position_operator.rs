@@ -0,0 +1,187 @@
+use super::PersonalPositionState;
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_2022::spl_token_2022::{self, extension::StateWithExtensions};
+use anchor_spl::token_interface::Token2022;
+
+pub const POSITION_OPERATOR_SEED: &str = "position_operator";
+
+/// The operator can only collect the fees and rewards of the position
+pub const OPERATOR_PERMISSION_COLLECT: u8 = 0;
+/// The operator can collect, increase and decrease liquidity if the position is within the tick bounds
+pub const OPERATOR_PERMISSION_REBALANCE: u8 = 1;
+/// The operator can take any liquidity action of the position
+pub const OPERATOR_PERMISSION_FULL: u8 = 2;
+
+/// The liquidity actions of a position an operator can be approved for
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PositionAction {
+    Collect,
+    IncreaseLiquidity,
+    DecreaseLiquidity,
+}
+
+/// An operator approved by the nft owner to manage the position without holding the nft
+#[account]
+#[derive(Default, Debug)]
+pub struct PositionOperatorState {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// Mint address of the position
+    pub nft_mint: Pubkey,
+    /// The nft owner who approved the operator, the approval is void once the nft is transferred
+    pub nft_owner: Pubkey,
+    /// The approved operator
+    pub operator: Pubkey,
+    /// The allowed actions, OPERATOR_PERMISSION_*
+    pub permission: u8,
+    /// The lowest tick of the positions a rebalance operator can manage
+    pub tick_lower_bound: i32,
+    /// The highest tick of the positions a rebalance operator can manage
+    pub tick_upper_bound: i32,
+    // padding space for upgrade
+    pub padding: [u64; 4],
+}
+
+impl PositionOperatorState {
+    pub const LEN: usize = 8 + 1 + 32 + 32 + 32 + 1 + 4 + 4 + 8 * 4;
+
+    pub fn key(nft_mint: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[POSITION_OPERATOR_SEED.as_bytes(), nft_mint.as_ref()],
+            &crate::id(),
+        )
+        .0
+    }
+
+    pub fn is_allowed(&self, action: PositionAction, tick_lower: i32, tick_upper: i32) -> bool {
+        match self.permission {
+            OPERATOR_PERMISSION_COLLECT => action == PositionAction::Collect,
+            OPERATOR_PERMISSION_REBALANCE => {
+                tick_lower >= self.tick_lower_bound && tick_upper <= self.tick_upper_bound
+            }
+            OPERATOR_PERMISSION_FULL => true,
+            _ => false,
+        }
+    }
+}
+
+/// Checks the signer is the nft owner or an approved operator of the position allowed to take the action.
+/// The operator account must be the first remaining account, the rest of the remaining accounts is returned
+/// together with whether the signer is a restricted operator.
+pub fn check_position_authority<'c, 'info>(
+    signer: &Pubkey,
+    nft_account_owner: &Pubkey,
+    personal_position: &PersonalPositionState,
+    remaining_accounts: &'c [AccountInfo<'info>],
+    action: PositionAction,
+) -> Result<(&'c [AccountInfo<'info>], bool)> {
+    if signer == nft_account_owner {
+        return Ok((remaining_accounts, false));
+    }
+    let account_info = remaining_accounts
+        .first()
+        .filter(|account_info| {
+            account_info.key() == PositionOperatorState::key(personal_position.nft_mint)
+        })
+        .ok_or(ErrorCode::OperatorNotAllowed)?;
+    require_keys_eq!(*account_info.owner, crate::id());
+    let position_operator =
+        PositionOperatorState::try_deserialize(&mut &account_info.try_borrow_data()?[..])?;
+    require!(
+        position_operator.operator == *signer
+            && position_operator.nft_owner == *nft_account_owner
+            && position_operator.is_allowed(
+                action,
+                personal_position.tick_lower_index,
+                personal_position.tick_upper_index
+            ),
+        ErrorCode::OperatorNotAllowed
+    );
+    Ok((
+        &remaining_accounts[1..],
+        position_operator.permission != OPERATOR_PERMISSION_FULL,
+    ))
+}
+
+/// Restricted operators can only send the tokens of the position to the nft owner,
+/// any token account not owned by the nft owner or the pool must not be passed.
+pub fn check_operator_recipients<'info>(
+    nft_owner: &Pubkey,
+    pool_state: &Pubkey,
+    recipients: &[AccountInfo<'info>],
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    for account_info in recipients.iter().chain(remaining_accounts.iter()) {
+        if *account_info.owner != Token::id() && *account_info.owner != Token2022::id() {
+            continue;
+        }
+        let data = account_info.try_borrow_data()?;
+        if let Ok(token_account) =
+            StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)
+        {
+            require!(
+                token_account.base.owner == *nft_owner || token_account.base.owner == *pool_state,
+                ErrorCode::OperatorNotAllowed
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Emitted when an operator of a position is approved
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct ApproveOperatorEvent {
+    /// The position
+    pub position_nft_mint: Pubkey,
+
+    /// The nft owner approving the operator
+    pub nft_owner: Pubkey,
+
+    /// The approved operator
+    pub operator: Pubkey,
+
+    /// The allowed actions, OPERATOR_PERMISSION_*
+    pub permission: u8,
+
+    /// The lowest tick of the positions a rebalance operator can manage
+    pub tick_lower_bound: i32,
+
+    /// The highest tick of the positions a rebalance operator can manage
+    pub tick_upper_bound: i32,
+}
+
+#[cfg(test)]
+mod position_operator_test {
+    use super::*;
+
+    fn operator(permission: u8) -> PositionOperatorState {
+        PositionOperatorState {
+            permission,
+            tick_lower_bound: -100,
+            tick_upper_bound: 100,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn is_allowed_test() {
+        let collect = operator(OPERATOR_PERMISSION_COLLECT);
+        assert!(collect.is_allowed(PositionAction::Collect, -10, 10));
+        assert!(!collect.is_allowed(PositionAction::IncreaseLiquidity, -10, 10));
+        assert!(!collect.is_allowed(PositionAction::DecreaseLiquidity, -10, 10));
+
+        let rebalance = operator(OPERATOR_PERMISSION_REBALANCE);
+        assert!(rebalance.is_allowed(PositionAction::DecreaseLiquidity, -100, 100));
+        assert!(rebalance.is_allowed(PositionAction::IncreaseLiquidity, -10, 10));
+        assert!(!rebalance.is_allowed(PositionAction::IncreaseLiquidity, -110, 10));
+        assert!(!rebalance.is_allowed(PositionAction::Collect, -10, 110));
+
+        let full = operator(OPERATOR_PERMISSION_FULL);
+        assert!(full.is_allowed(PositionAction::DecreaseLiquidity, -1000, 1000));
+
+        assert!(!operator(3).is_allowed(PositionAction::Collect, -10, 10));
+    }
+}
@@ -0,0 +1,99 @@
+use crate::states::PoolState;
+use anchor_lang::prelude::*;
+
+pub const PRICE_FEED_SEED: &str = "price_feed";
+
+/// A small fixed layout account holding the latest price of a pool,
+/// refreshed by swap_v2 and the update_price_feed crank
+#[account(zero_copy(unsafe))]
+#[repr(C, packed)]
+#[derive(Default, Debug)]
+pub struct PriceFeed {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The pool of the price feed
+    pub pool_id: Pubkey,
+    /// The sqrt price of the pool, as a Q64.64
+    pub sqrt_price_x64: u128,
+    /// The tick of the pool
+    pub tick: i32,
+    /// The tick spread the price moved within the publish slot, as a proxy of confidence
+    pub confidence: u32,
+    /// The lowest tick within the publish slot
+    pub slot_tick_low: i32,
+    /// The highest tick within the publish slot
+    pub slot_tick_high: i32,
+    /// The slot of the last update
+    pub publish_slot: u64,
+    /// The block timestamp of the last update
+    pub publish_timestamp: u64,
+    // padding space for upgrade
+    pub padding: [u64; 4],
+}
+
+impl PriceFeed {
+    pub const LEN: usize = 8 + 1 + 32 + 16 + 4 + 4 + 4 + 4 + 8 + 8 + 8 * 4;
+
+    pub fn key(pool_id: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[PRICE_FEED_SEED.as_bytes(), pool_id.as_ref()],
+            &crate::id(),
+        )
+        .0
+    }
+
+    pub fn initialize(&mut self, bump: u8, pool_id: Pubkey) {
+        self.bump = bump;
+        self.pool_id = pool_id;
+        self.padding = [0; 4];
+    }
+
+    /// Publishes the current price of the pool, the confidence widens with
+    /// every price move within the same slot
+    pub fn update(&mut self, pool_state: &PoolState, slot: u64, timestamp: u64) {
+        let tick = pool_state.tick_current;
+        if slot != self.publish_slot {
+            self.slot_tick_low = tick;
+            self.slot_tick_high = tick;
+        } else {
+            self.slot_tick_low = self.slot_tick_low.min(tick);
+            self.slot_tick_high = self.slot_tick_high.max(tick);
+        }
+        self.confidence = self.slot_tick_high.abs_diff(self.slot_tick_low);
+        self.sqrt_price_x64 = pool_state.sqrt_price_x64;
+        self.tick = tick;
+        self.publish_slot = slot;
+        self.publish_timestamp = timestamp;
+    }
+}
+
+#[cfg(test)]
+mod price_feed_test {
+    use super::*;
+    use std::convert::identity;
+
+    #[test]
+    fn update_test() {
+        let price_feed = &mut PriceFeed::default();
+        let pool_state = &mut PoolState::default();
+
+        pool_state.tick_current = 100;
+        price_feed.update(pool_state, 10, 1000);
+        assert_eq!(identity(price_feed.tick), 100);
+        assert_eq!(identity(price_feed.confidence), 0);
+
+        // the price moves within the same slot
+        pool_state.tick_current = 80;
+        price_feed.update(pool_state, 10, 1000);
+        pool_state.tick_current = 130;
+        price_feed.update(pool_state, 10, 1000);
+        assert_eq!(identity(price_feed.tick), 130);
+        assert_eq!(identity(price_feed.confidence), 50);
+
+        // the spread is reset in a new slot
+        price_feed.update(pool_state, 11, 1001);
+        assert_eq!(identity(price_feed.confidence), 0);
+        assert_eq!(identity(price_feed.publish_slot), 11);
+        assert_eq!(identity(price_feed.publish_timestamp), 1001);
+    }
+}
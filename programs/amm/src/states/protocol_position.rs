@@ -1,17 +1,13 @@
-use crate::libraries::tick_math;
-use crate::libraries::{big_num::U128, full_math::MulDiv};
 use crate::pool::REWARD_NUM;
-use crate::util::get_recent_epoch;
-use crate::{
-    error::ErrorCode,
-    libraries::{fixed_point_64, liquidity_math},
-};
+use crate::states::EXTENSION_REWARD_NUM;
 use anchor_lang::prelude::*;
 
 /// Seed to derive account address and signature
 pub const POSITION_SEED: &str = "position";
 
-/// Info stored for each user's position
+/// Info stored for the aggregate position of a tick range.
+/// Deprecated, the personal positions accrue their fees and rewards from the ticks directly,
+/// the existing accounts can only be closed by `close_protocol_position`
 #[account]
 #[derive(Default, Debug)]
 pub struct ProtocolPositionState {
@@ -46,73 +42,44 @@ pub struct ProtocolPositionState {
     pub reward_growth_inside: [u128; REWARD_NUM], // 24
     // account update recent epoch
     pub recent_epoch: u64,
+    /// The seconds spent in range as of the last update, only has relative meaning
+    pub seconds_inside_last: u64,
+    /// The growth per unit of liquidity of the rewards in the reward info extension as of the last update
+    pub extension_reward_growth_inside: [u128; EXTENSION_REWARD_NUM],
     // Unused bytes for future upgrades.
-    pub padding: [u64; 7],
+    pub padding: [u64; 2],
 }
 
 impl ProtocolPositionState {
     pub const LEN: usize = 8 + 1 + 32 + 4 + 4 + 16 + 16 + 16 + 8 + 8 + 16 * REWARD_NUM + 64;
+}
 
-    pub fn update(
-        &mut self,
-        tick_lower_index: i32,
-        tick_upper_index: i32,
-        liquidity_delta: i128,
-        fee_growth_inside_0_x64: u128,
-        fee_growth_inside_1_x64: u128,
-        reward_growths_inside: [u128; REWARD_NUM],
-    ) -> Result<()> {
-        require!(
-            tick_lower_index >= tick_math::MIN_TICK && tick_lower_index <= tick_math::MAX_TICK,
-            ErrorCode::TickLowerOverflow
-        );
-        require!(
-            tick_upper_index >= tick_math::MIN_TICK && tick_upper_index <= tick_math::MAX_TICK,
-            ErrorCode::TickUpperOverflow
-        );
-        let liquidity_next = if liquidity_delta == 0 {
-            require_gt!(self.liquidity, 0, ErrorCode::InvaildLiquidity);
-            self.liquidity
-        } else {
-            liquidity_math::add_delta(self.liquidity, liquidity_delta)?
-        };
-
-        // calculate accumulated Fees
-        let tokens_owed_0 =
-            U128::from(fee_growth_inside_0_x64.saturating_sub(self.fee_growth_inside_0_last_x64))
-                .mul_div_floor(U128::from(self.liquidity), U128::from(fixed_point_64::Q64))
-                .unwrap()
-                .to_underflow_u64();
-        let tokens_owed_1 =
-            U128::from(fee_growth_inside_1_x64.saturating_sub(self.fee_growth_inside_1_last_x64))
-                .mul_div_floor(U128::from(self.liquidity), U128::from(fixed_point_64::Q64))
-                .unwrap()
-                .to_underflow_u64();
-
-        // Update the position
-        if liquidity_delta != 0 {
-            self.liquidity = liquidity_next;
-        }
-        self.fee_growth_inside_0_last_x64 = fee_growth_inside_0_x64;
-        self.fee_growth_inside_1_last_x64 = fee_growth_inside_1_x64;
-        self.tick_lower_index = tick_lower_index;
-        self.tick_upper_index = tick_upper_index;
-        if tokens_owed_0 > 0 || tokens_owed_1 > 0 {
-            self.token_fees_owed_0 = self.token_fees_owed_0.checked_add(tokens_owed_0).unwrap();
-            self.token_fees_owed_1 = self.token_fees_owed_1.checked_add(tokens_owed_1).unwrap();
-        }
-        #[cfg(feature = "enable-log")]
-        msg!(
-            "protocol position reward_growths_inside:{:?}",
-            reward_growths_inside
-        );
-        self.update_reward_growths_inside(reward_growths_inside);
-        self.recent_epoch = get_recent_epoch()?;
-        Ok(())
-    }
+/// The growths per unit of liquidity inside the range of a position, computed from its ticks
+/// when the position is updated, the personal position accrues its fees and rewards up to them
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct PositionGrowthsInside {
+    /// The token_0 fee growth inside the range
+    pub fee_growth_inside_0_x64: u128,
+    /// The token_1 fee growth inside the range
+    pub fee_growth_inside_1_x64: u128,
+    /// The reward growths inside the range
+    pub reward_growths_inside: [u128; REWARD_NUM],
+    /// The growths inside the range of the rewards in the reward info extension,
+    /// only computed if the pool has the reward info extension
+    pub extension_reward_growths_inside: [u128; EXTENSION_REWARD_NUM],
+    /// The seconds spent in range, only has relative meaning
+    pub seconds_inside: u64,
+}
 
-    pub fn update_reward_growths_inside(&mut self, reward_growths_inside: [u128; REWARD_NUM]) {
-        // just record, calculate reward owed in personal position
-        self.reward_growth_inside = reward_growths_inside;
-    }
+/// Emitted when a deprecated protocol position is closed
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct CloseProtocolPositionEvent {
+    /// The pool of the protocol position
+    #[index]
+    pub pool_state: Pubkey,
+    /// The closed protocol position
+    pub protocol_position: Pubkey,
+    /// The account received the rent
+    pub rent_recipient: Pubkey,
 }
@@ -0,0 +1,122 @@
+use crate::states::FEE_RATE_DENOMINATOR_VALUE;
+use anchor_lang::prelude::*;
+
+pub const REFERRAL_SEED: &str = "referral";
+
+/// Holds the referral fee accrued to a referrer for a pool, claimable by the referrer
+#[account]
+#[derive(Default, Debug)]
+pub struct ReferralState {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The pool the referral fee accrued from
+    pub pool_id: Pubkey,
+    /// The referrer who can claim the fee
+    pub referrer: Pubkey,
+    /// The amount of token_0 referral fee not yet claimed
+    pub fees_owed_0: u64,
+    /// The amount of token_1 referral fee not yet claimed
+    pub fees_owed_1: u64,
+    // padding space for upgrade
+    pub padding: [u64; 8],
+}
+
+impl ReferralState {
+    pub const LEN: usize = 8 + 1 + 32 + 32 + 8 + 8 + 8 * 8;
+
+    pub fn key(pool_id: Pubkey, referrer: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                REFERRAL_SEED.as_bytes(),
+                pool_id.as_ref(),
+                referrer.as_ref(),
+            ],
+            &crate::id(),
+        )
+        .0
+    }
+
+    /// Calculate the referral part of the trade fee, paid from the protocol fee
+    pub fn calculate_referral_fee(
+        trade_fee: u64,
+        protocol_fee: u64,
+        referral_fee_rate: u32,
+    ) -> u64 {
+        let referral_fee = u128::from(trade_fee) * u128::from(referral_fee_rate)
+            / u128::from(FEE_RATE_DENOMINATOR_VALUE);
+        u64::try_from(referral_fee).unwrap().min(protocol_fee)
+    }
+
+    pub fn accrue_fee(&mut self, zero_for_one: bool, referral_fee: u64) {
+        if zero_for_one {
+            self.fees_owed_0 = self.fees_owed_0.checked_add(referral_fee).unwrap();
+        } else {
+            self.fees_owed_1 = self.fees_owed_1.checked_add(referral_fee).unwrap();
+        }
+    }
+}
+
+/// Emitted when referral fee accrued in swap
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct ReferralFeeAccruedEvent {
+    /// The pool of the swap
+    #[index]
+    pub pool_state: Pubkey,
+
+    /// The referrer who receive the fee
+    pub referrer: Pubkey,
+
+    /// The direction of the swap, the fee is in token_0 if true
+    pub zero_for_one: bool,
+
+    /// The amount of referral fee
+    pub amount: u64,
+}
+
+/// Emitted when referral fee is collected
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct CollectReferralFeeEvent {
+    /// The pool whose referral fee is collected
+    #[index]
+    pub pool_state: Pubkey,
+
+    /// The referrer
+    pub referrer: Pubkey,
+
+    /// The amount of token_0 referral fee that is withdrawn
+    pub amount_0: u64,
+
+    /// The amount of token_1 referral fee that is withdrawn
+    pub amount_1: u64,
+}
+
+#[cfg(test)]
+mod referral_test {
+    use super::*;
+
+    #[test]
+    fn calculate_referral_fee_test() {
+        assert_eq!(ReferralState::calculate_referral_fee(10_000, 1_200, 0), 0);
+        assert_eq!(
+            ReferralState::calculate_referral_fee(10_000, 1_200, 100_000),
+            1_000
+        );
+        // can't exceed the protocol fee
+        assert_eq!(
+            ReferralState::calculate_referral_fee(10_000, 800, 100_000),
+            800
+        );
+    }
+
+    #[test]
+    fn accrue_fee_test() {
+        let mut referral = ReferralState::default();
+        referral.accrue_fee(true, 10);
+        referral.accrue_fee(false, 20);
+        referral.accrue_fee(true, 5);
+        assert_eq!(referral.fees_owed_0, 15);
+        assert_eq!(referral.fees_owed_1, 20);
+    }
+}
@@ -0,0 +1,192 @@
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+pub const REWARD_DISTRIBUTION_SEED: &str = "reward_distribution";
+pub const REWARD_DISTRIBUTION_VAULT_SEED: &str = "reward_distribution_vault";
+pub const CLAIM_STATUS_SEED: &str = "claim_status";
+
+/// Rewards computed off chain and settled from a funded vault, each claimant proves its
+/// amount by a merkle proof against the root of the distribution
+#[account]
+#[derive(Default, Debug)]
+pub struct RewardDistribution {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The creator who funded the distribution
+    pub creator: Pubkey,
+    /// The id of the distribution among the ones of the creator and the mint
+    pub id: u64,
+    /// The mint of the distributed token
+    pub mint: Pubkey,
+    /// The vault holding the distributed token, owned by the distribution
+    pub vault: Pubkey,
+    /// The merkle root of the leaves `hash(claimant, amount)`, the pairs are hashed in sorted order
+    pub merkle_root: [u8; 32],
+    /// The total amount funded to be claimed
+    pub total_amount: u64,
+    /// The amount claimed so far
+    pub claimed_amount: u64,
+    /// The number of the claims so far
+    pub claimed_count: u64,
+    // padding space for upgrade
+    pub padding: [u64; 8],
+}
+
+impl RewardDistribution {
+    pub const LEN: usize = 8 + 1 + 32 + 8 + 32 + 32 + 32 + 8 + 8 + 8 + 8 * 8;
+
+    pub fn seeds(&self) -> [&[u8]; 5] {
+        [
+            REWARD_DISTRIBUTION_SEED.as_bytes(),
+            self.creator.as_ref(),
+            self.mint.as_ref(),
+            bytemuck::bytes_of(&self.id),
+            std::slice::from_ref(&self.bump),
+        ]
+    }
+
+    /// Verifies the claim of the claimant by the merkle proof and counts the claimed amount
+    pub fn claim(&mut self, claimant: Pubkey, amount: u64, proof: &[[u8; 32]]) -> Result<()> {
+        require!(
+            verify_merkle_proof(proof, self.merkle_root, distribution_leaf(claimant, amount)),
+            ErrorCode::InvalidMerkleProof
+        );
+        self.claimed_amount = self.claimed_amount.checked_add(amount).unwrap();
+        require_gte!(
+            self.total_amount,
+            self.claimed_amount,
+            ErrorCode::DistributionOverClaimed
+        );
+        self.claimed_count = self.claimed_count.checked_add(1).unwrap();
+        Ok(())
+    }
+}
+
+/// Records the claim of a claimant, which can only be created once per distribution
+#[account]
+#[derive(Default, Debug)]
+pub struct ClaimStatus {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The distribution claimed from
+    pub distribution: Pubkey,
+    /// The claimant of the amount
+    pub claimant: Pubkey,
+    /// The claimed amount
+    pub amount: u64,
+}
+
+impl ClaimStatus {
+    pub const LEN: usize = 8 + 1 + 32 + 32 + 8;
+}
+
+/// The merkle leaf of a claim
+pub fn distribution_leaf(claimant: Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[claimant.as_ref(), &amount.to_le_bytes()]).to_bytes()
+}
+
+/// Verifies the leaf is in the tree of the root, each pair of nodes is hashed in sorted order
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let computed_root = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            hashv(&[&node, sibling]).to_bytes()
+        } else {
+            hashv(&[sibling, &node]).to_bytes()
+        }
+    });
+    computed_root == root
+}
+
+/// Emitted when a reward distribution is created and funded
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct CreateDistributionEvent {
+    /// The created distribution
+    #[index]
+    pub distribution: Pubkey,
+    /// The creator who funded the distribution
+    pub creator: Pubkey,
+    /// The mint of the distributed token
+    pub mint: Pubkey,
+    /// The merkle root of the claims
+    pub merkle_root: [u8; 32],
+    /// The total amount funded to be claimed
+    pub total_amount: u64,
+}
+
+/// Emitted when a claimant claims from a reward distribution
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct ClaimWithProofEvent {
+    /// The distribution claimed from
+    #[index]
+    pub distribution: Pubkey,
+    /// The claimant of the amount
+    pub claimant: Pubkey,
+    /// The token account receiving the claimed amount
+    pub recipient_token_account: Pubkey,
+    /// The claimed amount
+    pub amount: u64,
+}
+
+#[cfg(test)]
+mod reward_distribution_test {
+    use super::*;
+
+    fn hash_pair(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        if a <= b {
+            hashv(&[&a, &b]).to_bytes()
+        } else {
+            hashv(&[&b, &a]).to_bytes()
+        }
+    }
+
+    #[test]
+    fn claim_test() {
+        let claims: Vec<(Pubkey, u64)> = (0..3).map(|i| (Pubkey::new_unique(), 100 + i)).collect();
+        let leaves: Vec<[u8; 32]> = claims
+            .iter()
+            .map(|(claimant, amount)| distribution_leaf(*claimant, *amount))
+            .collect();
+        // the odd leaf is promoted to the next level
+        let node_01 = hash_pair(leaves[0], leaves[1]);
+        let root = hash_pair(node_01, leaves[2]);
+
+        let mut distribution = RewardDistribution {
+            merkle_root: root,
+            total_amount: 301,
+            ..Default::default()
+        };
+        distribution
+            .claim(claims[0].0, claims[0].1, &[leaves[1], leaves[2]])
+            .unwrap();
+        distribution
+            .claim(claims[2].0, claims[2].1, &[node_01])
+            .unwrap();
+        assert_eq!(distribution.claimed_amount, 202);
+        assert_eq!(distribution.claimed_count, 2);
+
+        // wrong amount or proof
+        assert_eq!(
+            distribution
+                .claim(claims[1].0, claims[1].1 + 1, &[leaves[0], leaves[2]])
+                .unwrap_err(),
+            error!(ErrorCode::InvalidMerkleProof)
+        );
+        assert_eq!(
+            distribution
+                .claim(claims[1].0, claims[1].1, &[leaves[2]])
+                .unwrap_err(),
+            error!(ErrorCode::InvalidMerkleProof)
+        );
+
+        // the claims exceed the funded amount
+        assert_eq!(
+            distribution
+                .claim(claims[1].0, claims[1].1, &[leaves[0], leaves[2]])
+                .unwrap_err(),
+            error!(ErrorCode::DistributionOverClaimed)
+        );
+    }
+}
@@ -0,0 +1,385 @@
+use crate::error::ErrorCode;
+use crate::libraries::{fixed_point_64, full_math::MulDiv, U256};
+use crate::states::{PoolState, RewardInfo, REWARD_NUM};
+use crate::util::{create_or_allocate_account, AccountLoad};
+use anchor_lang::{prelude::*, system_program};
+
+pub const REWARD_INFO_EXTENSION_SEED: &str = "reward_info_extension";
+pub const POSITION_REWARD_EXTENSION_SEED: &str = "position_reward_extension";
+/// The number of reward slots in the reward info extension of a pool
+pub const EXTENSION_REWARD_NUM: usize = 2;
+
+/// Holds the rewards of a pool beyond the `REWARD_NUM` slots of the pool state,
+/// PDA of `[REWARD_INFO_EXTENSION_SEED, pool_id]`
+#[account(zero_copy(unsafe))]
+#[repr(C, packed)]
+#[derive(Default, Debug)]
+pub struct RewardInfoExtension {
+    pub pool_id: Pubkey,
+    pub reward_infos: [RewardInfo; EXTENSION_REWARD_NUM],
+    // Unused bytes for future upgrades.
+    pub padding: [u64; 16],
+}
+
+impl RewardInfoExtension {
+    pub const LEN: usize = 8 + 32 + RewardInfo::LEN * EXTENSION_REWARD_NUM + 8 * 16;
+
+    pub fn key(pool_id: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[REWARD_INFO_EXTENSION_SEED.as_bytes(), pool_id.as_ref()],
+            &crate::id(),
+        )
+        .0
+    }
+
+    /// Creates the reward info extension of the pool with payer funds if it does not exist
+    pub fn get_or_create_reward_info_extension<'info>(
+        payer: AccountInfo<'info>,
+        reward_info_extension_info: &AccountInfo<'info>,
+        system_program: AccountInfo<'info>,
+        pool_id: Pubkey,
+    ) -> Result<()> {
+        if reward_info_extension_info.owner != &system_program::ID {
+            return Ok(());
+        }
+        let (expect_pda_address, bump) = Pubkey::find_program_address(
+            &[REWARD_INFO_EXTENSION_SEED.as_bytes(), pool_id.as_ref()],
+            &crate::id(),
+        );
+        require_keys_eq!(expect_pda_address, reward_info_extension_info.key());
+        create_or_allocate_account(
+            &crate::id(),
+            payer,
+            system_program,
+            reward_info_extension_info.clone(),
+            &[
+                REWARD_INFO_EXTENSION_SEED.as_bytes(),
+                pool_id.as_ref(),
+                &[bump],
+            ],
+            RewardInfoExtension::LEN,
+        )?;
+        AccountLoad::<RewardInfoExtension>::try_from_unchecked(
+            &crate::id(),
+            reward_info_extension_info,
+        )?
+        .load_init()?
+        .pool_id = pool_id;
+        Ok(())
+    }
+
+    /// Initializes the lowest uninitialized reward slot of the extension and returns its index,
+    /// `reward_mints` are the mints of the rewards in the pool state
+    pub fn initialize_reward(
+        &mut self,
+        open_time: u64,
+        end_time: u64,
+        reward_per_second_x64: u128,
+        token_mint: &Pubkey,
+        token_vault: &Pubkey,
+        authority: &Pubkey,
+        reward_mints: &[Pubkey],
+    ) -> Result<usize> {
+        let reward_infos = self.reward_infos;
+        let lowest_index = reward_infos
+            .iter()
+            .position(|r| !r.initialized())
+            .ok_or(ErrorCode::FullRewardInfo)?;
+        require!(
+            !reward_mints.contains(token_mint)
+                && !reward_infos.iter().any(|r| r.token_mint == *token_mint),
+            ErrorCode::RewardTokenAlreadyInUse
+        );
+
+        let reward_info = &mut self.reward_infos[lowest_index];
+        reward_info.last_update_time = open_time;
+        reward_info.open_time = open_time;
+        reward_info.end_time = end_time;
+        reward_info.emissions_per_second_x64 = reward_per_second_x64;
+        reward_info.token_mint = *token_mint;
+        reward_info.token_vault = *token_vault;
+        reward_info.authority = *authority;
+        #[cfg(feature = "enable-log")]
+        msg!(
+            "extension reward_index:{}, reward_infos:{:?}",
+            lowest_index,
+            self.reward_infos[lowest_index],
+        );
+        Ok(lowest_index)
+    }
+
+    /// Calculates the next global reward growths of the extension rewards like `PoolState::update_reward_infos`,
+    /// `liquidity` is the active liquidity of the pool before any change
+    pub fn update_reward_infos(
+        &mut self,
+        curr_timestamp: u64,
+        liquidity: u128,
+    ) -> Result<[RewardInfo; EXTENSION_REWARD_NUM]> {
+        let mut next_reward_infos = self.reward_infos;
+        for reward_info in next_reward_infos.iter_mut() {
+            reward_info.update(curr_timestamp, liquidity)?;
+        }
+        self.reward_infos = next_reward_infos;
+        Ok(next_reward_infos)
+    }
+
+    pub fn check_unclaimed_reward(&self, index: usize, reward_amount_owed: u64) -> Result<()> {
+        assert!(index < EXTENSION_REWARD_NUM);
+        let unclaimed_reward = self.reward_infos[index]
+            .reward_total_emissioned
+            .checked_sub(self.reward_infos[index].reward_claimed)
+            .unwrap();
+        require_gte!(unclaimed_reward, reward_amount_owed);
+        Ok(())
+    }
+
+    pub fn add_reward_clamed(&mut self, index: usize, amount: u64) -> Result<()> {
+        assert!(index < EXTENSION_REWARD_NUM);
+        self.reward_infos[index].reward_claimed = self.reward_infos[index]
+            .reward_claimed
+            .checked_add(amount)
+            .unwrap();
+        Ok(())
+    }
+}
+
+/// The reward accounting of a position for the rewards in the reward info extension of its pool,
+/// PDA of `[POSITION_REWARD_EXTENSION_SEED, nft_mint]`
+#[account(zero_copy(unsafe))]
+#[repr(C, packed)]
+#[derive(Default, Debug)]
+pub struct PositionRewardExtension {
+    /// Mint address of the position
+    pub nft_mint: Pubkey,
+    /// Q64.64 reward growth inside of the position as of the last update
+    pub growth_inside_last_x64: [u128; EXTENSION_REWARD_NUM],
+    /// The rewards owed to the position owner
+    pub reward_amounts_owed: [u64; EXTENSION_REWARD_NUM],
+    /// The block timestamp when the rewards of the position start to vest
+    pub vesting_start_time: u64,
+    /// The claimed amounts of the vesting rewards of the pool
+    pub vesting_reward_claimed: [u64; REWARD_NUM],
+    // Unused bytes for future upgrades.
+    pub padding: [u64; 4],
+}
+
+impl PositionRewardExtension {
+    pub const LEN: usize =
+        8 + 32 + 16 * EXTENSION_REWARD_NUM + 8 * EXTENSION_REWARD_NUM + 8 + 8 * REWARD_NUM + 8 * 4;
+
+    pub fn key(nft_mint: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[POSITION_REWARD_EXTENSION_SEED.as_bytes(), nft_mint.as_ref()],
+            &crate::id(),
+        )
+        .0
+    }
+
+    /// Creates the position reward extension with payer funds, the growths inside are recorded
+    /// as the starting point of the position and its rewards start to vest from now on
+    pub fn create_position_reward_extension<'info>(
+        payer: AccountInfo<'info>,
+        position_reward_extension_info: &AccountInfo<'info>,
+        system_program: AccountInfo<'info>,
+        nft_mint: Pubkey,
+        reward_growths_inside: [u128; EXTENSION_REWARD_NUM],
+    ) -> Result<()> {
+        let (expect_pda_address, bump) = Pubkey::find_program_address(
+            &[POSITION_REWARD_EXTENSION_SEED.as_bytes(), nft_mint.as_ref()],
+            &crate::id(),
+        );
+        require_keys_eq!(expect_pda_address, position_reward_extension_info.key());
+        create_or_allocate_account(
+            &crate::id(),
+            payer,
+            system_program,
+            position_reward_extension_info.clone(),
+            &[
+                POSITION_REWARD_EXTENSION_SEED.as_bytes(),
+                nft_mint.as_ref(),
+                &[bump],
+            ],
+            PositionRewardExtension::LEN,
+        )?;
+        let position_reward_extension_loader =
+            AccountLoad::<PositionRewardExtension>::try_from_unchecked(
+                &crate::id(),
+                position_reward_extension_info,
+            )?;
+        let mut position_reward_extension = position_reward_extension_loader.load_init()?;
+        position_reward_extension.nft_mint = nft_mint;
+        position_reward_extension.growth_inside_last_x64 = reward_growths_inside;
+        position_reward_extension.vesting_start_time = Clock::get()?.unix_timestamp as u64;
+        Ok(())
+    }
+
+    /// Accumulates the rewards owed since the last update,
+    /// must be called before the liquidity of the position changes.
+    pub fn update_rewards(
+        &mut self,
+        reward_growths_inside: [u128; EXTENSION_REWARD_NUM],
+        liquidity: u128,
+    ) -> Result<()> {
+        let growth_inside_last_x64 = self.growth_inside_last_x64;
+        let mut reward_amounts_owed = self.reward_amounts_owed;
+        for i in 0..EXTENSION_REWARD_NUM {
+            // the position loses the rewards if the delta overflows, like the rewards of the personal position
+            let reward_growth_delta =
+                reward_growths_inside[i].wrapping_sub(growth_inside_last_x64[i]);
+            let amount_owed_delta = U256::from(reward_growth_delta)
+                .mul_div_floor(U256::from(liquidity), U256::from(fixed_point_64::Q64))
+                .unwrap()
+                .to_underflow_u64();
+            reward_amounts_owed[i] = reward_amounts_owed[i]
+                .checked_add(amount_owed_delta)
+                .unwrap();
+            #[cfg(feature = "enable-log")]
+            msg!(
+                "update position extension reward, index:{}, amount_owed_delta:{}, owed_after:{}",
+                i,
+                amount_owed_delta,
+                reward_amounts_owed[i]
+            );
+        }
+        self.reward_amounts_owed = reward_amounts_owed;
+        self.growth_inside_last_x64 = reward_growths_inside;
+        Ok(())
+    }
+}
+
+/// Finds the reward info extension of the pool and, if `nft_mint` is given, the reward extension of the position
+/// in the remaining accounts, both are required once any reward of the extension is initialized,
+/// and the reward extension of the position is also required once any reward of the pool vests
+pub fn find_reward_extension_accounts<'c, 'info>(
+    remaining_accounts: &'c [AccountInfo<'info>],
+    pool_id: Pubkey,
+    pool_state: &PoolState,
+    nft_mint: Option<Pubkey>,
+) -> Result<(
+    Option<&'c AccountInfo<'info>>,
+    Option<&'c AccountInfo<'info>>,
+)> {
+    let reward_info_extension_info = if pool_state.extension_reward_count > 0 {
+        let reward_info_extension_key = RewardInfoExtension::key(pool_id);
+        Some(
+            remaining_accounts
+                .iter()
+                .find(|account_info| account_info.key() == reward_info_extension_key)
+                .ok_or(ErrorCode::MissingRewardInfoExtension)?,
+        )
+    } else {
+        None
+    };
+
+    let position_reward_extension_info = match nft_mint {
+        Some(nft_mint) if pool_state.requires_position_reward_extension() => {
+            let position_reward_extension_key = PositionRewardExtension::key(nft_mint);
+            Some(
+                remaining_accounts
+                    .iter()
+                    .find(|account_info| account_info.key() == position_reward_extension_key)
+                    .ok_or(ErrorCode::MissingPositionRewardExtension)?,
+            )
+        }
+        _ => None,
+    };
+    Ok((reward_info_extension_info, position_reward_extension_info))
+}
+
+/// Finds the optional reward info extension of the pool in the remaining accounts of a swap,
+/// the swap requires it only when a tick is crossed while extension rewards are initialized
+pub fn find_swap_reward_extension_account<'c, 'info>(
+    remaining_accounts: &'c [AccountInfo<'info>],
+    pool_id: Pubkey,
+    pool_state: &PoolState,
+) -> Option<&'c AccountInfo<'info>> {
+    if pool_state.extension_reward_count == 0 {
+        return None;
+    }
+    let reward_info_extension_key = RewardInfoExtension::key(pool_id);
+    remaining_accounts
+        .iter()
+        .find(|account_info| account_info.key() == reward_info_extension_key)
+}
+
+/// Emitted when a reward is initialized in the reward info extension of a pool
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct InitializeExtensionRewardEvent {
+    /// The pool of the reward
+    #[index]
+    pub pool_state: Pubkey,
+
+    /// The index of the reward in the reward info extension
+    pub reward_index: u8,
+
+    /// The reward token mint
+    pub reward_mint: Pubkey,
+
+    /// The reward open time
+    pub open_time: u64,
+
+    /// The reward end time
+    pub end_time: u64,
+
+    /// Q64.64 number indicates how many tokens per second are earned per unit of liquidity
+    pub emissions_per_second_x64: u128,
+}
+
+#[cfg(test)]
+mod reward_info_extension_test {
+    use super::*;
+    use crate::states::RewardState;
+    use std::convert::identity;
+
+    #[test]
+    fn update_reward_infos_accumulates_initialized_rewards_only() {
+        let mut extension = RewardInfoExtension::default();
+        let mint = Pubkey::new_unique();
+        let index = extension
+            .initialize_reward(
+                100,
+                1000,
+                fixed_point_64::Q64 * 2,
+                &mint,
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+                &[],
+            )
+            .unwrap();
+        assert_eq!(index, 0);
+
+        let reward_infos = extension.update_reward_infos(200, 10).unwrap();
+        // 100 seconds * 2 tokens / 10 liquidity
+        assert_eq!(
+            identity(reward_infos[0].reward_growth_global_x64),
+            fixed_point_64::Q64 * 20
+        );
+        assert_eq!(identity(reward_infos[0].reward_total_emissioned), 200);
+        assert_eq!(reward_infos[0].reward_state, RewardState::Opening as u8);
+        assert!(!reward_infos[1].initialized());
+
+        let mut position_extension = PositionRewardExtension::default();
+        position_extension
+            .update_rewards(RewardInfo::get_reward_growths(&reward_infos), 5)
+            .unwrap();
+        assert_eq!(identity(position_extension.reward_amounts_owed), [100, 0]);
+    }
+
+    #[test]
+    fn initialize_reward_rejects_duplicate_mint() {
+        let mut extension = RewardInfoExtension::default();
+        let mint = Pubkey::new_unique();
+        assert!(extension
+            .initialize_reward(
+                100,
+                1000,
+                1,
+                &mint,
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+                &[mint],
+            )
+            .is_err());
+    }
+}
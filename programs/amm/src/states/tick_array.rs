@@ -2,6 +2,7 @@ use super::pool::PoolState;
 use crate::error::ErrorCode;
 use crate::libraries::{liquidity_math, tick_math};
 use crate::pool::{RewardInfo, REWARD_NUM};
+use crate::states::EXTENSION_REWARD_NUM;
 use crate::util::*;
 use crate::Result;
 use anchor_lang::{prelude::*, system_program};
@@ -22,11 +23,20 @@ pub struct TickArrayState {
     pub initialized_tick_count: u8,
     // account update recent epoch
     pub recent_epoch: u64,
+    /// The payer of the account rent, refunded when the empty tick array is closed
+    pub rent_payer: Pubkey,
+    /// The layout version of the account, 0 for tick arrays created before the field was introduced
+    pub version: u8,
     // Unused bytes for future upgrades.
-    pub padding: [u8; 107],
+    pub padding: [u8; 74],
 }
 
 impl TickArrayState {
+    /// The current layout version, bumped when new fields are introduced. The padding is reserved
+    /// for the fields of the array, the new fields of the ticks are appended after the layout and
+    /// `migrate_account` reallocates the initialized arrays to `LEN` of the new version
+    pub const VERSION: u8 = 1;
+
     pub const LEN: usize = 8 + 32 + 4 + TickState::LEN * TICK_ARRAY_SIZE_USIZE + 1 + 115;
 
     pub fn key(&self) -> Pubkey {
@@ -54,6 +64,7 @@ impl TickArrayState {
             ErrorCode::InvaildTickIndex
         );
 
+        let payer_key = payer.key();
         let tick_array_state = if tick_array_account_info.owner == &system_program::ID {
             let (expect_pda_address, bump) = Pubkey::find_program_address(
                 &[
@@ -88,6 +99,7 @@ impl TickArrayState {
                     tick_spacing,
                     pool_state_loader.key(),
                 )?;
+                tick_array_account.rent_payer = payer_key;
             }
             tick_array_state_loader
         } else {
@@ -109,9 +121,28 @@ impl TickArrayState {
         self.start_tick_index = start_index;
         self.pool_id = pool_key;
         self.recent_epoch = get_recent_epoch()?;
+        self.version = Self::VERSION;
         Ok(())
     }
 
+    /// Upgrades the account to the current layout version, returns the version before the upgrade
+    pub fn migrate(&mut self) -> Result<u8> {
+        let version = self.version;
+        require_gt!(Self::VERSION, version, ErrorCode::AccountAlreadyMigrated);
+        // version 1 only introduces the version field
+        self.version = Self::VERSION;
+        Ok(version)
+    }
+
+    /// Returns the account to refund the rent, the pool creator if the rent payer is not recorded
+    pub fn rent_recipient(&self, pool_owner: Pubkey) -> Pubkey {
+        if self.rent_payer == Pubkey::default() {
+            pool_owner
+        } else {
+            self.rent_payer
+        }
+    }
+
     pub fn update_initialized_tick_count(&mut self, add: bool) -> Result<()> {
         if add {
             self.initialized_tick_count += 1;
@@ -130,15 +161,30 @@ impl TickArrayState {
         Ok(&mut self.ticks[offset_in_array])
     }
 
+    pub fn get_tick_state(&self, tick_index: i32, tick_spacing: u16) -> Result<&TickState> {
+        let offset_in_array = self.get_tick_offset_in_array(tick_index, tick_spacing)?;
+        Ok(&self.ticks[offset_in_array])
+    }
+
+    /// Updates the tick state, the delta of the tick is emitted with the event authority if given
     pub fn update_tick_state(
         &mut self,
         tick_index: i32,
         tick_spacing: u16,
         tick_state: TickState,
+        event_authority: Option<EventAuthority>,
     ) -> Result<()> {
         let offset_in_array = self.get_tick_offset_in_array(tick_index, tick_spacing)?;
+        let delta = TickDelta::diff(&self.ticks[offset_in_array], &tick_state);
         self.ticks[offset_in_array] = tick_state;
         self.recent_epoch = get_recent_epoch()?;
+        if let (Some(delta), Some(event_authority)) = (delta, event_authority) {
+            event_authority.emit(TickArrayDeltaEvent {
+                pool_state: self.pool_id,
+                start_tick_index: self.start_tick_index,
+                deltas: vec![delta],
+            })?;
+        }
         Ok(())
     }
 
@@ -259,7 +305,9 @@ impl Default for TickArrayState {
             start_tick_index: 0,
             initialized_tick_count: 0,
             recent_epoch: 0,
-            padding: [0; 107],
+            rent_payer: Pubkey::default(),
+            version: 0,
+            padding: [0; 74],
         }
     }
 }
@@ -281,8 +329,13 @@ pub struct TickState {
 
     // Reward growth per unit of liquidity like fee, array of Q64.64
     pub reward_growths_outside_x64: [u128; REWARD_NUM],
+    /// The seconds spent on the _other_ side of this tick (relative to the current tick),
+    /// only has relative meaning like fee growth outside
+    pub seconds_outside: u64,
+    /// Reward growth outside of the rewards in the reward info extension of the pool, like `reward_growths_outside_x64`
+    pub extension_reward_growths_outside_x64: [u128; EXTENSION_REWARD_NUM],
     // Unused bytes for future upgrades.
-    pub padding: [u32; 13],
+    pub padding: [u32; 3],
 }
 
 impl TickState {
@@ -308,6 +361,7 @@ impl TickState {
         fee_growth_global_1_x64: u128,
         upper: bool,
         reward_infos: &[RewardInfo; REWARD_NUM],
+        timestamp: u64,
     ) -> Result<bool> {
         let liquidity_gross_before = self.liquidity_gross;
         let liquidity_gross_after =
@@ -322,6 +376,7 @@ impl TickState {
                 self.fee_growth_outside_0_x64 = fee_growth_global_0_x64;
                 self.fee_growth_outside_1_x64 = fee_growth_global_1_x64;
                 self.reward_growths_outside_x64 = RewardInfo::get_reward_growths(reward_infos);
+                self.seconds_outside = timestamp;
             }
         }
 
@@ -345,6 +400,7 @@ impl TickState {
         fee_growth_global_0_x64: u128,
         fee_growth_global_1_x64: u128,
         reward_infos: &[RewardInfo; REWARD_NUM],
+        timestamp: u64,
     ) -> i128 {
         self.fee_growth_outside_0_x64 = fee_growth_global_0_x64
             .checked_sub(self.fee_growth_outside_0_x64)
@@ -353,16 +409,9 @@ impl TickState {
             .checked_sub(self.fee_growth_outside_1_x64)
             .unwrap();
 
-        for i in 0..REWARD_NUM {
-            if !reward_infos[i].initialized() {
-                continue;
-            }
-
-            self.reward_growths_outside_x64[i] = reward_infos[i]
-                .reward_growth_global_x64
-                .checked_sub(self.reward_growths_outside_x64[i])
-                .unwrap();
-        }
+        self.reward_growths_outside_x64 =
+            cross_reward_growths_outside(self.reward_growths_outside_x64, reward_infos);
+        self.seconds_outside = timestamp.wrapping_sub(self.seconds_outside);
 
         self.liquidity_net
     }
@@ -373,6 +422,27 @@ impl TickState {
         self.fee_growth_outside_0_x64 = 0;
         self.fee_growth_outside_1_x64 = 0;
         self.reward_growths_outside_x64 = [0; REWARD_NUM];
+        self.seconds_outside = 0;
+        self.extension_reward_growths_outside_x64 = [0; EXTENSION_REWARD_NUM];
+    }
+
+    /// Records the extension reward growths of a tick initialized by `update`,
+    /// by the same convention as the growths of the pool rewards
+    pub fn initialize_extension_reward_growths(
+        &mut self,
+        tick_current: i32,
+        reward_infos: &[RewardInfo; EXTENSION_REWARD_NUM],
+    ) {
+        if self.tick <= tick_current {
+            self.extension_reward_growths_outside_x64 =
+                RewardInfo::get_reward_growths(reward_infos);
+        }
+    }
+
+    /// Flips the extension reward growths outside when the tick is crossed, like `cross`
+    pub fn cross_extension_rewards(&mut self, reward_infos: &[RewardInfo; EXTENSION_REWARD_NUM]) {
+        self.extension_reward_growths_outside_x64 =
+            cross_reward_growths_outside(self.extension_reward_growths_outside_x64, reward_infos);
     }
 
     pub fn is_initialized(self) -> bool {
@@ -386,6 +456,108 @@ impl TickState {
     }
 }
 
+/// Compact encoding of a single tick change, only the fields that changed are present
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct TickDelta {
+    /// The tick index that changed
+    pub tick: i32,
+    pub liquidity_net: Option<i128>,
+    pub liquidity_gross: Option<u128>,
+    pub fee_growth_outside_0_x64: Option<u128>,
+    pub fee_growth_outside_1_x64: Option<u128>,
+    pub reward_growths_outside_x64: Option<[u128; REWARD_NUM]>,
+}
+
+impl TickDelta {
+    /// Returns the changed fields between two tick states, or None if nothing changed
+    pub fn diff(before: &TickState, after: &TickState) -> Option<TickDelta> {
+        fn changed<T: PartialEq>(before: T, after: T) -> Option<T> {
+            if before != after {
+                Some(after)
+            } else {
+                None
+            }
+        }
+        let delta = TickDelta {
+            tick: after.tick,
+            liquidity_net: changed(before.liquidity_net, after.liquidity_net),
+            liquidity_gross: changed(before.liquidity_gross, after.liquidity_gross),
+            fee_growth_outside_0_x64: changed(
+                before.fee_growth_outside_0_x64,
+                after.fee_growth_outside_0_x64,
+            ),
+            fee_growth_outside_1_x64: changed(
+                before.fee_growth_outside_1_x64,
+                after.fee_growth_outside_1_x64,
+            ),
+            reward_growths_outside_x64: changed(
+                before.reward_growths_outside_x64,
+                after.reward_growths_outside_x64,
+            ),
+        };
+        if delta.is_empty() && before.tick == after.tick {
+            None
+        } else {
+            Some(delta)
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.liquidity_net.is_none()
+            && self.liquidity_gross.is_none()
+            && self.fee_growth_outside_0_x64.is_none()
+            && self.fee_growth_outside_1_x64.is_none()
+            && self.reward_growths_outside_x64.is_none()
+    }
+
+    /// Applies the delta to a cached tick state
+    pub fn apply(&self, tick_state: &mut TickState) {
+        tick_state.tick = self.tick;
+        if let Some(liquidity_net) = self.liquidity_net {
+            tick_state.liquidity_net = liquidity_net;
+        }
+        if let Some(liquidity_gross) = self.liquidity_gross {
+            tick_state.liquidity_gross = liquidity_gross;
+        }
+        if let Some(fee_growth_outside_0_x64) = self.fee_growth_outside_0_x64 {
+            tick_state.fee_growth_outside_0_x64 = fee_growth_outside_0_x64;
+        }
+        if let Some(fee_growth_outside_1_x64) = self.fee_growth_outside_1_x64 {
+            tick_state.fee_growth_outside_1_x64 = fee_growth_outside_1_x64;
+        }
+        if let Some(reward_growths_outside_x64) = self.reward_growths_outside_x64 {
+            tick_state.reward_growths_outside_x64 = reward_growths_outside_x64;
+        }
+    }
+}
+
+/// Emitted when ticks of a tick array are mutated, so off-chain quoters can
+/// keep their tick array cache up to date from logs
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct TickArrayDeltaEvent {
+    /// The pool of the tick array
+    #[index]
+    pub pool_state: Pubkey,
+    /// The start tick index of the mutated tick array
+    pub start_tick_index: i32,
+    /// The changed ticks, the unchanged fields are omitted
+    pub deltas: Vec<TickDelta>,
+}
+
+/// Emitted when an empty tick array is closed
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct CloseTickArrayEvent {
+    /// The pool of the tick array
+    #[index]
+    pub pool_state: Pubkey,
+    /// The start tick index of the closed tick array
+    pub start_tick_index: i32,
+    /// The account received the rent
+    pub rent_recipient: Pubkey,
+}
+
 // Calculates the fee growths inside of tick_lower and tick_upper based on their positions relative to tick_current.
 /// `fee_growth_inside = fee_growth_global - fee_growth_below(lower) - fee_growth_above(upper)`
 ///
@@ -446,28 +618,63 @@ pub fn get_reward_growths_inside(
     tick_current_index: i32,
     reward_infos: &[RewardInfo; REWARD_NUM],
 ) -> [u128; REWARD_NUM] {
-    let mut reward_growths_inside = [0; REWARD_NUM];
+    reward_growths_inside(
+        tick_lower.tick,
+        tick_lower.reward_growths_outside_x64,
+        tick_upper.tick,
+        tick_upper.reward_growths_outside_x64,
+        tick_current_index,
+        reward_infos,
+    )
+}
+
+// Calculates the growths inside of the rewards in the reward info extension like `get_reward_growths_inside`.
+pub fn get_extension_reward_growths_inside(
+    tick_lower: &TickState,
+    tick_upper: &TickState,
+    tick_current_index: i32,
+    reward_infos: &[RewardInfo; EXTENSION_REWARD_NUM],
+) -> [u128; EXTENSION_REWARD_NUM] {
+    reward_growths_inside(
+        tick_lower.tick,
+        tick_lower.extension_reward_growths_outside_x64,
+        tick_upper.tick,
+        tick_upper.extension_reward_growths_outside_x64,
+        tick_current_index,
+        reward_infos,
+    )
+}
+
+fn reward_growths_inside<const N: usize>(
+    tick_lower_index: i32,
+    tick_lower_growths_outside_x64: [u128; N],
+    tick_upper_index: i32,
+    tick_upper_growths_outside_x64: [u128; N],
+    tick_current_index: i32,
+    reward_infos: &[RewardInfo; N],
+) -> [u128; N] {
+    let mut reward_growths_inside = [0; N];
 
-    for i in 0..REWARD_NUM {
+    for i in 0..N {
         if !reward_infos[i].initialized() {
             continue;
         }
 
-        let reward_growths_below = if tick_current_index >= tick_lower.tick {
-            tick_lower.reward_growths_outside_x64[i]
+        let reward_growths_below = if tick_current_index >= tick_lower_index {
+            tick_lower_growths_outside_x64[i]
         } else {
             reward_infos[i]
                 .reward_growth_global_x64
-                .checked_sub(tick_lower.reward_growths_outside_x64[i])
+                .checked_sub(tick_lower_growths_outside_x64[i])
                 .unwrap()
         };
 
-        let reward_growths_above = if tick_current_index < tick_upper.tick {
-            tick_upper.reward_growths_outside_x64[i]
+        let reward_growths_above = if tick_current_index < tick_upper_index {
+            tick_upper_growths_outside_x64[i]
         } else {
             reward_infos[i]
                 .reward_growth_global_x64
-                .checked_sub(tick_upper.reward_growths_outside_x64[i])
+                .checked_sub(tick_upper_growths_outside_x64[i])
                 .unwrap()
         };
         reward_growths_inside[i] = reward_infos[i]
@@ -488,6 +695,48 @@ pub fn get_reward_growths_inside(
     reward_growths_inside
 }
 
+// Flips the reward growths outside of a tick to the other side when the tick is crossed.
+fn cross_reward_growths_outside<const N: usize>(
+    reward_growths_outside_x64: [u128; N],
+    reward_infos: &[RewardInfo; N],
+) -> [u128; N] {
+    let mut reward_growths_outside_x64 = reward_growths_outside_x64;
+    for i in 0..N {
+        if !reward_infos[i].initialized() {
+            continue;
+        }
+
+        reward_growths_outside_x64[i] = reward_infos[i]
+            .reward_growth_global_x64
+            .checked_sub(reward_growths_outside_x64[i])
+            .unwrap();
+    }
+    reward_growths_outside_x64
+}
+
+/// Calculates the seconds spent inside of tick_lower and tick_upper, only has relative meaning,
+/// the difference of two results is the seconds in range between them
+pub fn get_seconds_inside(
+    tick_lower: &TickState,
+    tick_upper: &TickState,
+    tick_current: i32,
+    timestamp: u64,
+) -> u64 {
+    let seconds_below = if tick_current >= tick_lower.tick {
+        tick_lower.seconds_outside
+    } else {
+        timestamp.wrapping_sub(tick_lower.seconds_outside)
+    };
+    let seconds_above = if tick_current < tick_upper.tick {
+        tick_upper.seconds_outside
+    } else {
+        timestamp.wrapping_sub(tick_upper.seconds_outside)
+    };
+    timestamp
+        .wrapping_sub(seconds_below)
+        .wrapping_sub(seconds_above)
+}
+
 pub fn check_tick_array_start_index(
     tick_array_start_index: i32,
     tick_index: i32,
@@ -823,12 +1072,14 @@ pub mod tick_array_test {
                     fee_growth_global_0_x64,
                     fee_growth_global_1_x64,
                     &[RewardInfo::default(); 3],
+                    0,
                 );
             } else {
                 tick_upper.cross(
                     fee_growth_global_0_x64,
                     fee_growth_global_1_x64,
                     &[RewardInfo::default(); 3],
+                    0,
                 );
             }
 
@@ -1159,9 +1410,9 @@ pub mod tick_array_test {
 
             reward_growth_global_x64 = reward_growth_global_x64 + reward_growth_global_delta;
             if cross_tick_lower {
-                tick_lower.cross(0, 0, &build_reward_infos(reward_growth_global_x64));
+                tick_lower.cross(0, 0, &build_reward_infos(reward_growth_global_x64), 0);
             } else {
-                tick_upper.cross(0, 0, &build_reward_infos(reward_growth_global_x64));
+                tick_upper.cross(0, 0, &build_reward_infos(reward_growth_global_x64), 0);
             }
 
             tick_current = target_tick_current;
@@ -1307,6 +1558,168 @@ pub mod tick_array_test {
             assert_eq!(reward_frowth_inside_delta, 500);
         }
     }
+
+    mod get_seconds_inside_test {
+        use crate::states::tick_array::{get_seconds_inside, TickState};
+        use crate::states::RewardInfo;
+
+        fn seconds_inside_delta_when_price_move(
+            start_timestamp: u64,
+            duration_before: u64,
+            duration_after: u64,
+            tick_current: i32,
+            tick_lower: &mut TickState,
+            tick_upper: &mut TickState,
+            cross_tick_lower: bool,
+        ) -> u64 {
+            let seconds_inside_start =
+                get_seconds_inside(tick_lower, tick_upper, tick_current, start_timestamp);
+            let cross_timestamp = start_timestamp + duration_before;
+            let target_tick_current = if cross_tick_lower {
+                tick_lower.cross(0, 0, &[RewardInfo::default(); 3], cross_timestamp);
+                if tick_current >= tick_lower.tick {
+                    tick_lower.tick - 1
+                } else {
+                    tick_lower.tick
+                }
+            } else {
+                tick_upper.cross(0, 0, &[RewardInfo::default(); 3], cross_timestamp);
+                if tick_current >= tick_upper.tick {
+                    tick_upper.tick - 1
+                } else {
+                    tick_upper.tick
+                }
+            };
+            let seconds_inside_end = get_seconds_inside(
+                tick_lower,
+                tick_upper,
+                target_tick_current,
+                cross_timestamp + duration_after,
+            );
+            seconds_inside_end.wrapping_sub(seconds_inside_start)
+        }
+
+        fn initialized_tick(tick: i32, tick_current: i32, timestamp: u64) -> TickState {
+            let mut tick_state = TickState {
+                tick,
+                ..Default::default()
+            };
+            tick_state
+                .update(
+                    tick_current,
+                    10,
+                    0,
+                    0,
+                    false,
+                    &[RewardInfo::default(); 3],
+                    timestamp,
+                )
+                .unwrap();
+            tick_state
+        }
+
+        #[test]
+        fn price_in_range_test() {
+            // tick_lower <= tick_current < tick_upper, ticks initialized at 100
+            let tick_lower = &mut initialized_tick(-10, 0, 100);
+            let tick_upper = &mut initialized_tick(10, 0, 100);
+            assert_eq!(
+                get_seconds_inside(tick_lower, tick_upper, 0, 150)
+                    .wrapping_sub(get_seconds_inside(tick_lower, tick_upper, 0, 120)),
+                30
+            );
+            // price moves below tick_lower after 20 seconds, stays there for 30 seconds
+            assert_eq!(
+                seconds_inside_delta_when_price_move(150, 20, 30, 0, tick_lower, tick_upper, true),
+                20
+            );
+        }
+
+        #[test]
+        fn price_out_of_range_test() {
+            // tick_current < tick_lower, ticks initialized at 100
+            let tick_lower = &mut initialized_tick(10, 0, 100);
+            let tick_upper = &mut initialized_tick(20, 0, 100);
+            assert_eq!(
+                get_seconds_inside(tick_lower, tick_upper, 0, 150)
+                    .wrapping_sub(get_seconds_inside(tick_lower, tick_upper, 0, 120)),
+                0
+            );
+            // price moves into the range after 20 seconds, stays there for 30 seconds
+            assert_eq!(
+                seconds_inside_delta_when_price_move(150, 20, 30, 0, tick_lower, tick_upper, true),
+                30
+            );
+            // price moves above tick_upper after 5 seconds, stays there for 30 seconds
+            assert_eq!(
+                seconds_inside_delta_when_price_move(200, 5, 30, 10, tick_lower, tick_upper, false),
+                5
+            );
+        }
+    }
+    mod tick_delta_test {
+        use super::*;
+        use std::convert::identity;
+
+        #[test]
+        fn diff_unchanged_tick_test() {
+            let tick = build_tick(10, 100, 100).take();
+            assert!(TickDelta::diff(&tick, &tick).is_none());
+        }
+
+        #[test]
+        fn diff_only_contains_changed_fields_test() {
+            let before = build_tick(10, 100, 100).take();
+            let mut after = before;
+            after.fee_growth_outside_0_x64 = 500;
+            let delta = TickDelta::diff(&before, &after).unwrap();
+            assert_eq!(delta.tick, 10);
+            assert_eq!(delta.fee_growth_outside_0_x64, Some(500));
+            assert!(delta.liquidity_net.is_none());
+            assert!(delta.liquidity_gross.is_none());
+            assert!(delta.fee_growth_outside_1_x64.is_none());
+            assert!(delta.reward_growths_outside_x64.is_none());
+
+            // an unchanged field costs only one byte in the encoding
+            let full = TickDelta {
+                liquidity_net: Some(0),
+                liquidity_gross: Some(0),
+                fee_growth_outside_1_x64: Some(0),
+                reward_growths_outside_x64: Some([0; REWARD_NUM]),
+                ..delta
+            };
+            assert_eq!(
+                full.try_to_vec().unwrap().len() - delta.try_to_vec().unwrap().len(),
+                16 + 16 + 16 + 16 * REWARD_NUM
+            );
+        }
+
+        #[test]
+        fn apply_delta_test() {
+            let before = build_tick(10, 100, 100).take();
+            let mut after = before;
+            after.liquidity_gross = 0;
+            after.liquidity_net = 0;
+            after.reward_growths_outside_x64 = [1, 2, 3];
+            let delta = TickDelta::diff(&before, &after).unwrap();
+
+            let mut cached = before;
+            delta.apply(&mut cached);
+            assert_eq!(identity(cached.liquidity_gross), 0);
+            assert_eq!(identity(cached.liquidity_net), 0);
+            assert_eq!(identity(cached.reward_growths_outside_x64), [1, 2, 3]);
+            assert!(TickDelta::diff(&cached, &after).is_none());
+        }
+
+        #[test]
+        fn update_tick_state_test() {
+            let mut tick_array = TickArrayState::default();
+            tick_array.start_tick_index = 0;
+            let tick = build_tick(10, 100, 100).take();
+            tick_array.update_tick_state(10, 10, tick, None).unwrap();
+            assert_eq!(identity(tick_array.ticks[1].liquidity_gross), 100);
+        }
+    }
     mod tick_array_layout_test {
         use super::*;
         use anchor_lang::Discriminator;
@@ -1316,9 +1729,11 @@ pub mod tick_array_test {
             let start_tick_index: i32 = 0x12345678;
             let initialized_tick_count: u8 = 0x12;
             let recent_epoch: u64 = 0x123456789abcdef0;
-            let mut padding: [u8; 107] = [0u8; 107];
-            let mut padding_data = [0u8; 107];
-            for i in 0..107 {
+            let rent_payer = Pubkey::new_unique();
+            let version: u8 = 0x34;
+            let mut padding: [u8; 74] = [0u8; 74];
+            let mut padding_data = [0u8; 74];
+            for i in 0..74 {
                 padding[i] = i as u8;
                 padding_data[i] = i as u8;
             }
@@ -1333,10 +1748,15 @@ pub mod tick_array_test {
                 0x11223344556600778899aabbccddeeff,
                 0x11223344556677008899aabbccddeeff,
             ];
-            let mut tick_padding: [u32; 13] = [0u32; 13];
-            let mut tick_padding_data = [0u8; 4 * 13];
+            let seconds_outside: u64 = 0x1122334455667788;
+            let extension_reward_growths_outside_x64: [u128; EXTENSION_REWARD_NUM] = [
+                0x11223344556677889900aabbccddeeff,
+                0x112233445566778899aa00bbccddeeff,
+            ];
+            let mut tick_padding: [u32; 3] = [0u32; 3];
+            let mut tick_padding_data = [0u8; 4 * 3];
             let mut offset = 0;
-            for i in 0..13 {
+            for i in 0..3 {
                 tick_padding[i] = u32::MAX - 3 * i as u32;
                 tick_padding_data[offset..offset + 4]
                     .copy_from_slice(&tick_padding[i].to_le_bytes());
@@ -1360,8 +1780,15 @@ pub mod tick_array_test {
                     .copy_from_slice(&reward_growths_outside_x64[i].to_le_bytes());
                 offset += 16;
             }
-            tick_data[offset..offset + 4 * 13].copy_from_slice(&tick_padding_data);
-            offset += 4 * 13;
+            tick_data[offset..offset + 8].copy_from_slice(&seconds_outside.to_le_bytes());
+            offset += 8;
+            for i in 0..EXTENSION_REWARD_NUM {
+                tick_data[offset..offset + 16]
+                    .copy_from_slice(&extension_reward_growths_outside_x64[i].to_le_bytes());
+                offset += 16;
+            }
+            tick_data[offset..offset + 4 * 3].copy_from_slice(&tick_padding_data);
+            offset += 4 * 3;
             assert_eq!(offset, tick_data.len());
             assert_eq!(tick_data.len(), core::mem::size_of::<TickState>());
 
@@ -1383,8 +1810,12 @@ pub mod tick_array_test {
             offset += 1;
             tick_array_data[offset..offset + 8].copy_from_slice(&recent_epoch.to_le_bytes());
             offset += 8;
-            tick_array_data[offset..offset + 107].copy_from_slice(&padding);
-            offset += 107;
+            tick_array_data[offset..offset + 32].copy_from_slice(&rent_payer.to_bytes());
+            offset += 32;
+            tick_array_data[offset..offset + 1].copy_from_slice(&version.to_le_bytes());
+            offset += 1;
+            tick_array_data[offset..offset + 74].copy_from_slice(&padding);
+            offset += 74;
 
             // len check
             assert_eq!(offset, tick_array_data.len());
@@ -1419,6 +1850,14 @@ pub mod tick_array_test {
                     unpack_reward_growths_outside_x64,
                     reward_growths_outside_x64
                 );
+                let unpack_seconds_outside = tick_item.seconds_outside;
+                assert_eq!(unpack_seconds_outside, seconds_outside);
+                let unpack_extension_reward_growths_outside_x64 =
+                    tick_item.extension_reward_growths_outside_x64;
+                assert_eq!(
+                    unpack_extension_reward_growths_outside_x64,
+                    extension_reward_growths_outside_x64
+                );
                 let unpack_tick_padding = tick_item.padding;
                 assert_eq!(unpack_tick_padding, tick_padding);
             }
@@ -1426,6 +1865,10 @@ pub mod tick_array_test {
             assert_eq!(unpack_initialized_tick_count, initialized_tick_count);
             let unpack_recent_epoch = unpack_data.recent_epoch;
             assert_eq!(unpack_recent_epoch, recent_epoch);
+            let unpack_rent_payer = unpack_data.rent_payer;
+            assert_eq!(unpack_rent_payer, rent_payer);
+            let unpack_version = unpack_data.version;
+            assert_eq!(unpack_version, version);
             let unpack_padding = unpack_data.padding;
             assert_eq!(padding, unpack_padding);
         }
@@ -8,10 +8,11 @@ use crate::libraries::{
     tick_math,
 };
 use crate::states::{TickArrayState, POOL_TICK_ARRAY_BITMAP_SEED};
-use anchor_lang::prelude::*;
+use crate::util::{create_or_allocate_account, AccountLoad};
+use anchor_lang::{prelude::*, system_program};
 use std::ops::BitXor;
 
-const EXTENSION_TICKARRAY_BITMAP_SIZE: usize = 14;
+pub const EXTENSION_TICKARRAY_BITMAP_SIZE: usize = 14;
 
 #[account(zero_copy(unsafe))]
 #[repr(C, packed)]
@@ -44,6 +45,51 @@ impl TickArrayBitmapExtension {
         self.negative_tick_array_bitmap = [[0; 8]; EXTENSION_TICKARRAY_BITMAP_SIZE];
     }
 
+    /// Creates the bitmap extension of the pool with payer funds if it does not exist,
+    /// the extension of the pools created before it was required may be missing
+    pub fn get_or_create_tickarray_bitmap_extension<'info>(
+        payer: AccountInfo<'info>,
+        tickarray_bitmap_extension_info: &AccountInfo<'info>,
+        system_program: AccountInfo<'info>,
+        pool_id: Pubkey,
+    ) -> Result<()> {
+        if tickarray_bitmap_extension_info.owner != &system_program::ID {
+            return Ok(());
+        }
+        let (expect_pda_address, bump) = Pubkey::find_program_address(
+            &[POOL_TICK_ARRAY_BITMAP_SEED.as_bytes(), pool_id.as_ref()],
+            &crate::id(),
+        );
+        require_keys_eq!(expect_pda_address, tickarray_bitmap_extension_info.key());
+        create_or_allocate_account(
+            &crate::id(),
+            payer,
+            system_program,
+            tickarray_bitmap_extension_info.clone(),
+            &[
+                POOL_TICK_ARRAY_BITMAP_SEED.as_bytes(),
+                pool_id.as_ref(),
+                &[bump],
+            ],
+            TickArrayBitmapExtension::LEN,
+        )?;
+        AccountLoad::<TickArrayBitmapExtension>::try_from_unchecked(
+            &crate::id(),
+            tickarray_bitmap_extension_info,
+        )?
+        .load_init()?
+        .initialize(pool_id);
+        Ok(())
+    }
+
+    /// Returns true if no tick array is initialized in the extension
+    pub fn is_empty(&self) -> bool {
+        let positive_tick_array_bitmap = self.positive_tick_array_bitmap;
+        let negative_tick_array_bitmap = self.negative_tick_array_bitmap;
+        positive_tick_array_bitmap == [[0; 8]; EXTENSION_TICKARRAY_BITMAP_SIZE]
+            && negative_tick_array_bitmap == [[0; 8]; EXTENSION_TICKARRAY_BITMAP_SIZE]
+    }
+
     pub fn key(pool_id: Pubkey) -> Pubkey {
         Pubkey::find_program_address(
             &[POOL_TICK_ARRAY_BITMAP_SEED.as_bytes(), pool_id.as_ref()],
@@ -417,6 +463,13 @@ pub mod tick_array_bitmap_extension_test {
         assert!(U512(tick_array_bitmap_extension.negative_tick_array_bitmap[0]).bit(511) == false);
         assert!(U512(tick_array_bitmap_extension.negative_tick_array_bitmap[0]).bit(510) == false);
         assert!(U512(tick_array_bitmap_extension.negative_tick_array_bitmap[13]).bit(286) == false);
+        assert!(!tick_array_bitmap_extension.is_empty());
+        flip_tick_array_bit_helper(
+            tick_array_bitmap_extension,
+            tick_spacing as u16,
+            vec![-tick_spacing * TICK_ARRAY_SIZE * 1024],
+        );
+        assert!(tick_array_bitmap_extension.is_empty());
 
         let tick_array_bitmap_extension = &mut TickArrayBitmapExtension::default();
         let tick_spacing = 3;
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+
+pub const TOKEN_BADGE_SEED: &str = "token_badge";
+pub const TOKEN_BADGE_CONFIG_SEED: &str = "token_badge_config";
+
+/// Issued by operation owner to vet a Token2022 mint with risky extensions, or by anyone paying the
+/// fee of the token badge config if the mint passes its checks, pools of the mint can only be created
+/// if the badge exists
+#[account]
+#[derive(Default, Debug)]
+pub struct TokenBadge {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The vetted mint
+    pub token_mint: Pubkey,
+    // padding space for upgrade
+    pub padding: [u64; 8],
+}
+
+impl TokenBadge {
+    pub const LEN: usize = 8 + 1 + 32 + 8 * 8;
+
+    pub fn key(token_mint: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[TOKEN_BADGE_SEED.as_bytes(), token_mint.as_ref()],
+            &crate::id(),
+        )
+        .0
+    }
+}
+
+/// Holds the terms on which anyone can issue a token badge with `create_token_badge_with_fee`,
+/// a single account for the program set by admin
+#[account]
+#[derive(Default, Debug)]
+pub struct TokenBadgeConfig {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The lamports paid to issue a token badge, zero means the self-service token badge is disabled
+    pub fee: u64,
+    /// The account receiving the fee
+    pub fee_receiver: Pubkey,
+    /// The bitmap of the extension types requiring a token badge that a mint may have to be
+    /// issued a token badge by paying the fee, bit `n` allows the extension type `n`
+    pub allowed_extensions: u64,
+    // padding space for upgrade
+    pub padding: [u64; 8],
+}
+
+impl TokenBadgeConfig {
+    pub const LEN: usize = 8 + 1 + 8 + 32 + 8 + 8 * 8;
+
+    pub fn key() -> Pubkey {
+        Pubkey::find_program_address(&[TOKEN_BADGE_CONFIG_SEED.as_bytes()], &crate::id()).0
+    }
+
+    /// Returns true if a self-service token badge can unlock the extension type
+    pub fn is_allowed_extension(&self, extension_type: u16) -> bool {
+        extension_type < 64 && self.allowed_extensions & (1 << extension_type) != 0
+    }
+}
+
+/// Emitted when the token badge config is set
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct TokenBadgeConfigChangeEvent {
+    pub fee: u64,
+    pub fee_receiver: Pubkey,
+    pub allowed_extensions: u64,
+}
+
+/// Emitted when a token badge is issued by paying the fee of the token badge config
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct CreateTokenBadgeWithFeeEvent {
+    /// The issued token badge
+    #[index]
+    pub token_badge: Pubkey,
+    /// The vetted mint
+    pub token_mint: Pubkey,
+    /// The payer of the fee
+    pub payer: Pubkey,
+    /// The account receiving the fee
+    pub fee_receiver: Pubkey,
+    /// The lamports paid
+    pub fee: u64,
+}
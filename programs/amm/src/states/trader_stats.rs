@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+
+pub const TRADER_STATS_SEED: &str = "trader_stats";
+
+/// The cumulative swap volume and fees of a trader in a pool, opted in by the trader and
+/// updated by the swaps of the trader passing the account
+#[account(zero_copy(unsafe))]
+#[repr(C, packed)]
+#[derive(Default, Debug)]
+pub struct TraderStatsState {
+    /// Bump to identify PDA
+    pub bump: u8,
+    pub padding_u8: [u8; 7],
+    /// The pool of the statistics
+    pub pool_id: Pubkey,
+    /// The trader of the statistics, the signer of the swaps
+    pub trader: Pubkey,
+    /// The amount of token_0 swapped in by the trader
+    pub volume_in_token_0: u128,
+    /// The amount of token_0 swapped out to the trader
+    pub volume_out_token_0: u128,
+    /// The amount of token_1 swapped in by the trader
+    pub volume_in_token_1: u128,
+    /// The amount of token_1 swapped out to the trader
+    pub volume_out_token_1: u128,
+    /// The trade fees of token_0 paid by the trader, including the protocol, fund and creator fees
+    pub fees_token_0: u64,
+    /// The trade fees of token_1 paid by the trader, including the protocol, fund and creator fees
+    pub fees_token_1: u64,
+    /// The number of the swaps recorded
+    pub swap_count: u64,
+    /// The block timestamp of the latest recorded swap
+    pub last_swap_timestamp: u64,
+    // padding for feature update
+    pub padding: [u64; 8],
+}
+
+impl TraderStatsState {
+    pub const LEN: usize = 8 + 1 + 7 + 32 * 2 + 16 * 4 + 8 * 4 + 8 * 8;
+
+    pub fn key(pool_id: Pubkey, trader: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                TRADER_STATS_SEED.as_bytes(),
+                pool_id.as_ref(),
+                trader.as_ref(),
+            ],
+            &crate::id(),
+        )
+        .0
+    }
+
+    /// Adds a swap of the trader
+    pub fn record_swap(
+        &mut self,
+        block_timestamp: u64,
+        zero_for_one: bool,
+        amount_0: u64,
+        amount_1: u64,
+        trade_fee: u64,
+    ) {
+        if zero_for_one {
+            self.volume_in_token_0 = self
+                .volume_in_token_0
+                .checked_add(u128::from(amount_0))
+                .unwrap();
+            self.volume_out_token_1 = self
+                .volume_out_token_1
+                .checked_add(u128::from(amount_1))
+                .unwrap();
+            self.fees_token_0 = self.fees_token_0.checked_add(trade_fee).unwrap();
+        } else {
+            self.volume_in_token_1 = self
+                .volume_in_token_1
+                .checked_add(u128::from(amount_1))
+                .unwrap();
+            self.volume_out_token_0 = self
+                .volume_out_token_0
+                .checked_add(u128::from(amount_0))
+                .unwrap();
+            self.fees_token_1 = self.fees_token_1.checked_add(trade_fee).unwrap();
+        }
+        self.swap_count = self.swap_count.checked_add(1).unwrap();
+        self.last_swap_timestamp = block_timestamp;
+    }
+}
+
+/// Finds the statistics account of the trader in the pool in the remaining accounts
+pub fn find_trader_stats_account<'c, 'info>(
+    remaining_accounts: &'c [AccountInfo<'info>],
+    pool_id: Pubkey,
+    trader: Pubkey,
+) -> Option<&'c AccountInfo<'info>> {
+    let trader_stats_key = TraderStatsState::key(pool_id, trader);
+    remaining_accounts
+        .iter()
+        .find(|account_info| account_info.key() == trader_stats_key)
+}
+
+#[cfg(test)]
+mod trader_stats_test {
+    use super::*;
+
+    #[test]
+    fn record_swap_test() {
+        let mut trader_stats = TraderStatsState::default();
+        trader_stats.record_swap(1000, true, 100, 90, 1);
+        trader_stats.record_swap(2000, false, 50, 60, 2);
+        trader_stats.record_swap(3000, true, 10, 9, 1);
+
+        assert_eq!({ trader_stats.volume_in_token_0 }, 110);
+        assert_eq!({ trader_stats.volume_out_token_1 }, 99);
+        assert_eq!({ trader_stats.volume_in_token_1 }, 60);
+        assert_eq!({ trader_stats.volume_out_token_0 }, 50);
+        assert_eq!({ trader_stats.fees_token_0 }, 2);
+        assert_eq!({ trader_stats.fees_token_1 }, 2);
+        assert_eq!({ trader_stats.swap_count }, 3);
+        assert_eq!({ trader_stats.last_swap_timestamp }, 3000);
+        assert_eq!(
+            core::mem::size_of::<TraderStatsState>() + 8,
+            TraderStatsState::LEN
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+pub const TWAP_SNAPSHOT_SEED: &str = "twap_snapshot";
+
+/// The latest time-weighted average price of a pool over a window, written by `get_twap`
+#[account]
+#[derive(Default, Debug)]
+pub struct TwapSnapshot {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The pool of the twap
+    pub pool_id: Pubkey,
+    /// The window of the twap in seconds
+    pub window_seconds: u32,
+    /// The time-weighted average tick over the window
+    pub average_tick: i32,
+    /// The sqrt price of the average tick, as a Q64.64
+    pub sqrt_price_x64: u128,
+    /// The block timestamp of the last update
+    pub update_timestamp: u64,
+    // padding space for upgrade
+    pub padding: [u64; 4],
+}
+
+impl TwapSnapshot {
+    pub const LEN: usize = 8 + 1 + 32 + 4 + 4 + 16 + 8 + 8 * 4;
+
+    pub fn key(pool_id: Pubkey, window_seconds: u32) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                TWAP_SNAPSHOT_SEED.as_bytes(),
+                pool_id.as_ref(),
+                &window_seconds.to_be_bytes(),
+            ],
+            &crate::id(),
+        )
+    }
+}
+
+/// Returned by `get_twap` as return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, PartialEq, Eq, Debug)]
+pub struct TwapResult {
+    /// The time-weighted average tick over the window, rounded to negative infinity
+    pub average_tick: i32,
+    /// The sqrt price of the average tick, as a Q64.64
+    pub sqrt_price_x64: u128,
+    /// The seconds per in-range liquidity accumulated over the window, as a Q64.64
+    pub seconds_per_liquidity_x64: u128,
+}
+
+/// Returns the average tick of the tick cumulative delta, rounded to negative infinity
+pub fn get_average_tick(tick_cumulative_delta: i64, window_seconds: u32) -> i32 {
+    let window_seconds = i64::from(window_seconds);
+    let mut average_tick = tick_cumulative_delta / window_seconds;
+    if tick_cumulative_delta < 0 && tick_cumulative_delta % window_seconds != 0 {
+        average_tick -= 1;
+    }
+    i32::try_from(average_tick).unwrap()
+}
+
+#[cfg(test)]
+mod twap_snapshot_test {
+    use super::*;
+
+    #[test]
+    fn get_average_tick_test() {
+        assert_eq!(get_average_tick(600, 60), 10);
+        assert_eq!(get_average_tick(610, 60), 10);
+        assert_eq!(get_average_tick(-600, 60), -10);
+        assert_eq!(get_average_tick(-610, 60), -11);
+    }
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+pub const WHITELIST_CREATOR_SEED: &str = "whitelist_creator";
+
+/// Issued by admin to allow an address to create pools with the amm configs restricting
+/// pool creation, one account per creator so the whitelist is not bounded by the operation account
+#[account]
+#[derive(Default, Debug)]
+pub struct WhitelistCreator {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The address allowed to create pool
+    pub creator: Pubkey,
+    // padding space for upgrade
+    pub padding: [u64; 8],
+}
+
+impl WhitelistCreator {
+    pub const LEN: usize = 8 + 1 + 32 + 8 * 8;
+
+    pub fn key(creator: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[WHITELIST_CREATOR_SEED.as_bytes(), creator.as_ref()],
+            &crate::id(),
+        )
+        .0
+    }
+}
@@ -109,6 +109,38 @@ impl<'info, T: ZeroCopy + Owner> AccountLoad<'info, T> {
         }))
     }
 
+    /// Returns a RefMut to the account data structure and the rest of the account data,
+    /// used by the account reallocated beyond the size of the structure.
+    pub fn load_data_mut_with_tail<'a>(
+        acc_info: &'a AccountInfo,
+    ) -> Result<(RefMut<'a, T>, RefMut<'a, [u8]>)> {
+        if acc_info.owner != &T::owner() {
+            return Err(Error::from(ErrorCode::AccountOwnedByWrongProgram)
+                .with_pubkeys((*acc_info.owner, T::owner())));
+        }
+        if !acc_info.is_writable {
+            return Err(ErrorCode::AccountNotMutable.into());
+        }
+
+        let data = acc_info.try_borrow_mut_data()?;
+        if data.len() < T::discriminator().len() {
+            return Err(ErrorCode::AccountDiscriminatorNotFound.into());
+        }
+
+        let disc_bytes = array_ref![data, 0, 8];
+        if disc_bytes != &T::discriminator() {
+            return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+        }
+
+        let (data, tail) = RefMut::map_split(data, |data| {
+            data.deref_mut().split_at_mut(mem::size_of::<T>() + 8)
+        });
+        Ok((
+            RefMut::map(data, |data| bytemuck::from_bytes_mut(&mut data[8..])),
+            tail,
+        ))
+    }
+
     /// Returns a Ref to the account data structure for reading.
     pub fn load(&self) -> Result<Ref<T>> {
         let data = self.acc_info.try_borrow_data()?;
@@ -0,0 +1,171 @@
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{self, instruction::Instruction};
+use anchor_spl::token_interface::TokenAccount;
+
+/// The MPL Core program, whose assets can represent positions instead of NFT mints
+pub mod mpl_core_program {
+    use anchor_lang::prelude::declare_id;
+    declare_id!("CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d");
+}
+
+/// The instruction discriminator of `CreateV1` of the MPL Core program
+const CREATE_V1_DISCRIMINATOR: u8 = 0;
+/// The instruction discriminator of `BurnV1` of the MPL Core program
+const BURN_V1_DISCRIMINATOR: u8 = 12;
+/// The account key of an `AssetV1` of the MPL Core program
+const ASSET_V1_KEY: u8 = 1;
+
+/// Returns whether the account is an asset of the MPL Core program
+pub fn is_core_asset(account_info: &AccountInfo) -> bool {
+    account_info.owner == &mpl_core_program::id()
+}
+
+/// Reads the owner of the MPL Core asset, which follows the account key in the asset data
+pub fn get_core_asset_owner(asset: &AccountInfo) -> Result<Pubkey> {
+    require!(is_core_asset(asset), ErrorCode::InvalidCoreAsset);
+    let data = asset.try_borrow_data()?;
+    require!(
+        data.len() >= 33 && data[0] == ASSET_V1_KEY,
+        ErrorCode::InvalidCoreAsset
+    );
+    Ok(Pubkey::try_from(&data[1..33]).unwrap())
+}
+
+/// Returns the owner of the position NFT, `nft_account` is either the token account holding
+/// the NFT mint of the position or the MPL Core asset of the position
+pub fn get_position_nft_owner(nft_account: &AccountInfo, nft_mint: &Pubkey) -> Result<Pubkey> {
+    if is_core_asset(nft_account) {
+        require_keys_eq!(nft_account.key(), *nft_mint, ErrorCode::InvalidCoreAsset);
+        return get_core_asset_owner(nft_account);
+    }
+    require!(
+        nft_account.owner == &anchor_spl::token::ID
+            || nft_account.owner == &anchor_spl::token_2022::ID,
+        ErrorCode::NotApproved
+    );
+    let token_account = TokenAccount::try_deserialize(&mut &nft_account.try_borrow_data()?[..])?;
+    require_keys_eq!(token_account.mint, *nft_mint, ErrorCode::NotApproved);
+    require_eq!(token_account.amount, 1, ErrorCode::NotApproved);
+    Ok(token_account.owner)
+}
+
+/// The account meta of an optional account of the MPL Core program left none, which is the program itself
+fn none_account_meta() -> AccountMeta {
+    AccountMeta::new_readonly(mpl_core_program::id(), false)
+}
+
+/// Creates the MPL Core asset owned by `owner` without collection nor plugins
+pub fn create_core_asset<'info>(
+    payer: &AccountInfo<'info>,
+    asset: &AccountInfo<'info>,
+    owner: &AccountInfo<'info>,
+    update_authority: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    mpl_core_program: &AccountInfo<'info>,
+    name: String,
+    uri: String,
+) -> Result<()> {
+    require_keys_eq!(mpl_core_program.key(), mpl_core_program::id());
+    let mut data = vec![CREATE_V1_DISCRIMINATOR];
+    // data_state: AccountState
+    data.push(0);
+    name.serialize(&mut data)?;
+    uri.serialize(&mut data)?;
+    // plugins: None
+    None::<u8>.serialize(&mut data)?;
+    let ix = Instruction {
+        program_id: mpl_core_program::id(),
+        accounts: vec![
+            AccountMeta::new(asset.key(), true),
+            none_account_meta(),
+            none_account_meta(),
+            AccountMeta::new(payer.key(), true),
+            AccountMeta::new_readonly(owner.key(), false),
+            AccountMeta::new_readonly(update_authority.key(), false),
+            AccountMeta::new_readonly(system_program.key(), false),
+            none_account_meta(),
+        ],
+        data,
+    };
+    solana_program::program::invoke(
+        &ix,
+        &[
+            asset.clone(),
+            payer.clone(),
+            owner.clone(),
+            update_authority.clone(),
+            system_program.clone(),
+            mpl_core_program.clone(),
+        ],
+    )?;
+    Ok(())
+}
+
+/// Burns the MPL Core asset by its owner, the rent of the asset is returned to the owner
+pub fn burn_core_asset<'info>(
+    owner: &AccountInfo<'info>,
+    asset: &AccountInfo<'info>,
+    mpl_core_program: &AccountInfo<'info>,
+) -> Result<()> {
+    require_keys_eq!(mpl_core_program.key(), mpl_core_program::id());
+    let mut data = vec![BURN_V1_DISCRIMINATOR];
+    // compression_proof: None
+    None::<u8>.serialize(&mut data)?;
+    let ix = Instruction {
+        program_id: mpl_core_program::id(),
+        accounts: vec![
+            AccountMeta::new(asset.key(), false),
+            none_account_meta(),
+            AccountMeta::new(owner.key(), true),
+            none_account_meta(),
+            none_account_meta(),
+            none_account_meta(),
+        ],
+        data,
+    };
+    solana_program::program::invoke(
+        &ix,
+        &[asset.clone(), owner.clone(), mpl_core_program.clone()],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod core_asset_test {
+    use super::*;
+
+    #[test]
+    fn get_position_nft_owner_test() {
+        let asset_key = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let core_program_id = mpl_core_program::id();
+        let mut lamports = 0;
+        let mut data = vec![ASSET_V1_KEY];
+        data.extend_from_slice(owner.as_ref());
+        // the update authority, name and uri follow the owner
+        data.extend_from_slice(&[0; 16]);
+        let asset = AccountInfo::new(
+            &asset_key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &core_program_id,
+            false,
+            0,
+        );
+        assert_eq!(get_position_nft_owner(&asset, &asset_key).unwrap(), owner);
+        assert_eq!(
+            get_position_nft_owner(&asset, &Pubkey::new_unique()).unwrap_err(),
+            error!(ErrorCode::InvalidCoreAsset)
+        );
+
+        // a burnt asset has no owner
+        asset.try_borrow_mut_data().unwrap()[0] = 0;
+        assert_eq!(
+            get_position_nft_owner(&asset, &asset_key).unwrap_err(),
+            error!(ErrorCode::InvalidCoreAsset)
+        );
+    }
+}
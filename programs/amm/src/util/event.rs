@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Event;
+#[cfg(not(feature = "log-events"))]
+use anchor_lang::{
+    event::EVENT_IX_TAG_LE,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program::invoke_signed,
+    },
+};
+use std::marker::PhantomData;
+
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+/// Emits the events of an instruction. With the default `log-events` feature they are written to the
+/// program logs like `emit!`, which keeps the accounts of the existing instructions unchanged.
+/// Built without it, the events are carried by a self CPI signed by the event authority, which indexers
+/// read from the inner instructions since logs are truncated on busy transactions, and the event
+/// authority and program accounts are appended to the instructions.
+#[derive(Clone, Copy)]
+pub struct EventAuthority<'a, 'info> {
+    #[cfg(not(feature = "log-events"))]
+    pub account: &'a AccountInfo<'info>,
+    #[cfg(not(feature = "log-events"))]
+    pub bump: u8,
+    _info: PhantomData<&'a AccountInfo<'info>>,
+}
+
+impl<'a, 'info> EventAuthority<'a, 'info> {
+    #[cfg(not(feature = "log-events"))]
+    pub fn new(account: &'a AccountInfo<'info>, bump: u8) -> Self {
+        Self {
+            account,
+            bump,
+            _info: PhantomData,
+        }
+    }
+
+    /// The event authority writing the events to the program logs
+    #[cfg(feature = "log-events")]
+    pub fn logs() -> Self {
+        Self { _info: PhantomData }
+    }
+
+    pub fn key() -> Pubkey {
+        Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &crate::id()).0
+    }
+
+    /// Emits the event like `emit_cpi!`, or like `emit!` with the `log-events` feature
+    #[cfg(feature = "log-events")]
+    pub fn emit<E: Event>(&self, event: E) -> Result<()> {
+        anchor_lang::solana_program::log::sol_log_data(&[&event.data()]);
+        Ok(())
+    }
+
+    /// Emits the event like `emit_cpi!`, or like `emit!` with the `log-events` feature
+    #[cfg(not(feature = "log-events"))]
+    pub fn emit<E: Event>(&self, event: E) -> Result<()> {
+        let ix = Instruction::new_with_bytes(
+            crate::id(),
+            &EVENT_IX_TAG_LE
+                .into_iter()
+                .chain(event.data())
+                .collect::<Vec<u8>>(),
+            vec![AccountMeta::new_readonly(*self.account.key, true)],
+        );
+        invoke_signed(
+            &ix,
+            std::slice::from_ref(self.account),
+            &[&[EVENT_AUTHORITY_SEED, &[self.bump]]],
+        )
+        .map_err(Into::into)
+    }
+}
+
+/// The event authority of the instruction accounts, the `event_authority` account and bump
+/// don't exist with the `log-events` feature
+#[cfg(not(feature = "log-events"))]
+#[macro_export]
+macro_rules! event_authority {
+    ($accounts:expr, $bumps:expr) => {
+        $crate::util::EventAuthority::new(&$accounts.event_authority, $bumps.event_authority)
+    };
+}
+
+/// The event authority of the instruction accounts, the `event_authority` account and bump
+/// don't exist with the `log-events` feature
+#[cfg(feature = "log-events")]
+#[macro_export]
+macro_rules! event_authority {
+    ($accounts:expr, $bumps:expr) => {
+        $crate::util::EventAuthority::logs()
+    };
+}
+
+/// Emits the event with the event authority of the instruction context, like `emit_cpi!` or
+/// `emit!` with the `log-events` feature
+#[macro_export]
+macro_rules! emit_event {
+    ($ctx:expr, $event:expr) => {
+        $crate::event_authority!($ctx.accounts, $ctx.bumps).emit($event)
+    };
+}
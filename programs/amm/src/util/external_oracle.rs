@@ -0,0 +1,217 @@
+use crate::error::ErrorCode;
+use crate::libraries::big_num::U256;
+use crate::states::PoolState;
+use anchor_lang::prelude::*;
+
+/// The Pyth receiver program, which owns the price update accounts of the Pyth pull oracle
+pub mod pyth_receiver_program {
+    use anchor_lang::prelude::declare_id;
+    declare_id!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+}
+
+/// The account discriminator of `PriceUpdateV2` of the Pyth receiver program
+const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+/// The borsh tag of `VerificationLevel::Full`, only fully verified updates are accepted
+const VERIFICATION_LEVEL_FULL: u8 = 1;
+/// The offset of the price message: discriminator + write_authority + verification_level
+const PRICE_MESSAGE_OFFSET: usize = 8 + 32 + 1;
+/// The oracle price must be published within the seconds before the swap
+pub const EXTERNAL_ORACLE_MAX_AGE: u64 = 60;
+/// The maximum power of ten to scale the oracle price to the raw amounts of the pool
+const MAX_PRICE_EXPONENT: i32 = 30;
+/// The denominator of the max deviation of the external oracle
+pub const EXTERNAL_ORACLE_DEVIATION_BPS_DENOMINATOR: u64 = 10_000;
+
+/// The price of a Pyth price update, `price * 10^exponent`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OraclePrice {
+    pub price: i64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+/// Reads the price of a fully verified `PriceUpdateV2` account of the Pyth receiver program
+pub fn read_pyth_price_update(data: &[u8]) -> Result<OraclePrice> {
+    require!(
+        data.len() >= PRICE_MESSAGE_OFFSET + 32 + 8 + 8 + 4 + 8
+            && data[..8] == PRICE_UPDATE_V2_DISCRIMINATOR
+            && data[PRICE_MESSAGE_OFFSET - 1] == VERIFICATION_LEVEL_FULL,
+        ErrorCode::InvalidExternalOracle
+    );
+    // the price message starts with the feed id, followed by the price, conf and exponent
+    let price_offset = PRICE_MESSAGE_OFFSET + 32;
+    let read = |offset: usize, len: usize| &data[offset..offset + len];
+    Ok(OraclePrice {
+        price: i64::from_le_bytes(read(price_offset, 8).try_into().unwrap()),
+        exponent: i32::from_le_bytes(read(price_offset + 16, 4).try_into().unwrap()),
+        publish_time: i64::from_le_bytes(read(price_offset + 20, 8).try_into().unwrap()),
+    })
+}
+
+/// Finds the external oracle of the pool in the remaining accounts of `swap_v3`, the oracle must
+/// be passed if the pool has an external oracle
+pub fn find_external_oracle_account<'c, 'info>(
+    remaining_accounts: &'c [AccountInfo<'info>],
+    external_oracle: Pubkey,
+) -> Result<Option<&'c AccountInfo<'info>>> {
+    if external_oracle == Pubkey::default() {
+        return Ok(None);
+    }
+    let external_oracle_info = remaining_accounts
+        .iter()
+        .find(|account_info| account_info.key() == external_oracle)
+        .ok_or(ErrorCode::MissingExternalOracle)?;
+    Ok(Some(external_oracle_info))
+}
+
+/// Checks the execution price of a swap, amount_1 / amount_0, against the fresh price of the
+/// external oracle of the pool, which quotes token_0 in token_1
+pub fn check_external_oracle_price(
+    external_oracle_info: &AccountInfo,
+    pool_state: &PoolState,
+    amount_0: u64,
+    amount_1: u64,
+    block_timestamp: u64,
+) -> Result<()> {
+    require_keys_eq!(
+        *external_oracle_info.owner,
+        pyth_receiver_program::id(),
+        ErrorCode::InvalidExternalOracle
+    );
+    let oracle_price = read_pyth_price_update(&external_oracle_info.try_borrow_data()?)?;
+    require!(
+        oracle_price.publish_time > 0
+            && oracle_price.publish_time as u64 + EXTERNAL_ORACLE_MAX_AGE >= block_timestamp,
+        ErrorCode::StaleExternalOraclePrice
+    );
+    check_execution_price_deviation(
+        amount_0,
+        amount_1,
+        oracle_price,
+        pool_state.mint_decimals_0,
+        pool_state.mint_decimals_1,
+        pool_state.external_oracle_max_deviation_bps,
+    )
+}
+
+/// Checks the execution price amount_1 / amount_0 in raw amounts deviates from the oracle price
+/// scaled by the decimals of the mints by at most max_deviation_bps
+pub fn check_execution_price_deviation(
+    amount_0: u64,
+    amount_1: u64,
+    oracle_price: OraclePrice,
+    mint_decimals_0: u8,
+    mint_decimals_1: u8,
+    max_deviation_bps: u64,
+) -> Result<()> {
+    let exponent = oracle_price.exponent + i32::from(mint_decimals_1) - i32::from(mint_decimals_0);
+    require!(
+        oracle_price.price > 0 && exponent.abs() <= MAX_PRICE_EXPONENT,
+        ErrorCode::InvalidExternalOracle
+    );
+    let scale = U256::from(10).pow(U256::from(exponent.unsigned_abs()));
+    // oracle price in raw amounts = price_numerator / price_denominator
+    let (price_numerator, price_denominator) = if exponent >= 0 {
+        (U256::from(oracle_price.price) * scale, U256::one())
+    } else {
+        (U256::from(oracle_price.price), scale)
+    };
+    let denominator = U256::from(EXTERNAL_ORACLE_DEVIATION_BPS_DENOMINATOR);
+    let max_deviation_bps = U256::from(max_deviation_bps).min(denominator);
+    let execution_value = U256::from(amount_1) * price_denominator * denominator;
+    let oracle_value = U256::from(amount_0) * price_numerator;
+    require!(
+        execution_value >= oracle_value * (denominator - max_deviation_bps)
+            && execution_value <= oracle_value * (denominator + max_deviation_bps),
+        ErrorCode::ExternalOraclePriceDeviation
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod external_oracle_test {
+    use super::*;
+
+    fn price_update_data(verification_level: &[u8], price: i64, exponent: i32) -> Vec<u8> {
+        let mut data = PRICE_UPDATE_V2_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[7u8; 32]);
+        data.extend_from_slice(verification_level);
+        data.extend_from_slice(&[9u8; 32]);
+        data.extend_from_slice(&price.to_le_bytes());
+        data.extend_from_slice(&5u64.to_le_bytes());
+        data.extend_from_slice(&exponent.to_le_bytes());
+        data.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+        data.extend_from_slice(&[0u8; 8 * 4 + 8]);
+        data
+    }
+
+    #[test]
+    fn read_pyth_price_update_test() {
+        assert_eq!(
+            read_pyth_price_update(&price_update_data(&[1], 15_000_000_000, -8)).unwrap(),
+            OraclePrice {
+                price: 15_000_000_000,
+                exponent: -8,
+                publish_time: 1_700_000_000,
+            }
+        );
+        // partially verified
+        assert_eq!(
+            read_pyth_price_update(&price_update_data(&[0, 3], 15_000_000_000, -8)).unwrap_err(),
+            error!(ErrorCode::InvalidExternalOracle)
+        );
+        let mut data = price_update_data(&[1], 15_000_000_000, -8);
+        data[0] = 0;
+        assert_eq!(
+            read_pyth_price_update(&data).unwrap_err(),
+            error!(ErrorCode::InvalidExternalOracle)
+        );
+    }
+
+    #[test]
+    fn check_execution_price_deviation_test() {
+        // 1 token_0 with 9 decimals is 150 token_1 with 6 decimals
+        let oracle_price = OraclePrice {
+            price: 15_000_000_000,
+            exponent: -8,
+            publish_time: 0,
+        };
+        let check = |amount_0: u64, amount_1: u64, max_deviation_bps: u64| {
+            check_execution_price_deviation(
+                amount_0,
+                amount_1,
+                oracle_price,
+                9,
+                6,
+                max_deviation_bps,
+            )
+        };
+        check(1_000_000_000, 150_000_000, 0).unwrap();
+        check(1_000_000_000, 148_500_000, 100).unwrap();
+        check(1_000_000_000, 151_500_000, 100).unwrap();
+        assert_eq!(
+            check(1_000_000_000, 148_499_999, 100).unwrap_err(),
+            error!(ErrorCode::ExternalOraclePriceDeviation)
+        );
+        assert_eq!(
+            check(1_000_000_000, 151_500_001, 100).unwrap_err(),
+            error!(ErrorCode::ExternalOraclePriceDeviation)
+        );
+        // a non positive price is rejected
+        assert_eq!(
+            check_execution_price_deviation(
+                1,
+                1,
+                OraclePrice {
+                    price: 0,
+                    ..oracle_price
+                },
+                9,
+                6,
+                100
+            )
+            .unwrap_err(),
+            error!(ErrorCode::InvalidExternalOracle)
+        );
+    }
+}
@@ -1,8 +1,23 @@
 pub mod token;
 pub use token::*;
 
+pub mod core_asset;
+pub use core_asset::*;
+
 pub mod system;
 pub use system::*;
 
 pub mod account_load;
 pub use account_load::*;
+
+pub mod remaining_accounts;
+pub use remaining_accounts::*;
+
+pub mod event;
+pub use event::*;
+
+pub mod swap_hook;
+pub use swap_hook::*;
+
+pub mod external_oracle;
+pub use external_oracle::*;
@@ -0,0 +1,55 @@
+use crate::error::ErrorCode;
+use crate::states::{EXTENSION_REWARD_NUM, REWARD_NUM};
+use anchor_lang::prelude::*;
+
+/// The max number of tick array accounts can be passed to a single pool swap
+pub const MAX_SWAP_TICK_ARRAY_ACCOUNTS: usize = 20;
+/// swap: tickarray_bitmap_extension + reward_info_extension + pool_stats + trader_stats + tick arrays
+pub const MAX_SWAP_REMAINING_ACCOUNTS: usize = 4 + MAX_SWAP_TICK_ARRAY_ACCOUNTS;
+/// swap_v2 and swap_v3: referral_state + price_feed + tickarray_bitmap_extension + reward_info_extension
+/// + pool_stats + trader_stats + swap_hook_program and external_oracle of swap_v3 + tick arrays
+pub const MAX_SWAP_V2_REMAINING_ACCOUNTS: usize = 8 + MAX_SWAP_TICK_ARRAY_ACCOUNTS;
+/// swap_router_base_in: limited by the max account locks(64) of a transaction
+pub const MAX_SWAP_ROUTER_REMAINING_ACCOUNTS: usize = 64 - 5;
+/// swap_router_base_in: amm_config + pool_state + output_token_account + input_vault
+/// + output_vault + output_token_mint + observation_state of each hop
+pub const SWAP_ROUTER_HOP_ACCOUNTS: usize = 7;
+/// create_pool: token badges of token_0 and token_1 + whitelist_creator + operation_state
+pub const MAX_CREATE_POOL_REMAINING_ACCOUNTS: usize = 4;
+/// open_position and increase_liquidity: tickarray_bitmap_extension
+/// + reward_info_extension + position_reward_extension
+pub const MAX_LIQUIDITY_REMAINING_ACCOUNTS: usize = 3;
+/// decrease_liquidity: tickarray_bitmap_extension + admin_heartbeat
+/// + reward_info_extension + position_reward_extension
+/// + (reward_vault, recipient_token_account, reward_mint) of each reward
+pub const MAX_DECREASE_LIQUIDITY_REMAINING_ACCOUNTS: usize =
+    4 + 3 * (REWARD_NUM + EXTENSION_REWARD_NUM);
+/// open_positions_batch: position_nft_mint + position_nft_account + personal_position
+/// + tick_array_lower + tick_array_upper of each position
+pub const OPEN_POSITIONS_BATCH_GROUP_ACCOUNTS: usize = 5;
+/// create_token_badges: token_mint + token_badge of each mint
+pub const CREATE_TOKEN_BADGES_GROUP_ACCOUNTS: usize = 2;
+/// set_reward_params: reward_vault + authority_token_account + reward_mint
+pub const SET_REWARD_PARAMS_REMAINING_ACCOUNTS: usize = 3;
+/// collect_protocol_fee: partner_config + partner token_0 account + partner token_1 account
+pub const COLLECT_PROTOCOL_FEE_REMAINING_ACCOUNTS: usize = 3;
+
+/// Fail immediately if more remaining accounts are passed than the instruction can use
+pub fn check_remaining_accounts_max(remaining_accounts: &[AccountInfo], max: usize) -> Result<()> {
+    require_gte!(
+        max,
+        remaining_accounts.len(),
+        ErrorCode::TooManyRemainingAccounts
+    );
+    Ok(())
+}
+
+/// Fail immediately if less remaining accounts are passed than the instruction required
+pub fn check_remaining_accounts_min(remaining_accounts: &[AccountInfo], min: usize) -> Result<()> {
+    require_gte!(
+        remaining_accounts.len(),
+        min,
+        ErrorCode::NotEnoughRemainingAccounts
+    );
+    Ok(())
+}
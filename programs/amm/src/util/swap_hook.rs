@@ -0,0 +1,53 @@
+use crate::error::ErrorCode;
+use crate::states::SwapEvent;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{self, instruction::Instruction};
+
+/// The instruction discriminator of the swap hook, i.e. an anchor instruction named `on_swap`
+/// taking the fields of the `SwapEvent` as its arguments
+pub const SWAP_HOOK_DISCRIMINATOR: [u8; 8] = [201, 131, 123, 189, 167, 226, 124, 81];
+
+/// Finds the swap hook program of the pool in the remaining accounts of `swap_v3`, the hook
+/// program must be passed if the pool has a swap hook
+pub fn find_swap_hook_program<'c, 'info>(
+    remaining_accounts: &'c [AccountInfo<'info>],
+    swap_hook: Pubkey,
+) -> Result<Option<&'c AccountInfo<'info>>> {
+    if swap_hook == Pubkey::default() {
+        return Ok(None);
+    }
+    let swap_hook_program = remaining_accounts
+        .iter()
+        .find(|account_info| account_info.key() == swap_hook)
+        .ok_or(ErrorCode::MissingSwapHook)?;
+    Ok(Some(swap_hook_program))
+}
+
+/// Invokes the swap hook program with the result of a settled swap, the pool and the sender
+/// are passed as read-only accounts without signature
+pub fn invoke_swap_hook<'info>(
+    swap_hook_program: &AccountInfo<'info>,
+    pool_state: &AccountInfo<'info>,
+    sender: &AccountInfo<'info>,
+    swap_event: &SwapEvent,
+) -> Result<()> {
+    let mut data = SWAP_HOOK_DISCRIMINATOR.to_vec();
+    swap_event.serialize(&mut data)?;
+    let ix = Instruction {
+        program_id: swap_hook_program.key(),
+        accounts: vec![
+            AccountMeta::new_readonly(pool_state.key(), false),
+            AccountMeta::new_readonly(sender.key(), false),
+        ],
+        data,
+    };
+    solana_program::program::invoke(
+        &ix,
+        &[
+            pool_state.clone(),
+            sender.clone(),
+            swap_hook_program.clone(),
+        ],
+    )?;
+    Ok(())
+}
@@ -69,3 +69,38 @@ pub fn get_recent_epoch() -> Result<u64> {
         .as_secs()
         / (2 * 24 * 3600))
 }
+
+/// Closes a program owned account and moves its lamports to the destination
+pub fn close_account<'a>(
+    target_account: AccountInfo<'a>,
+    destination: AccountInfo<'a>,
+) -> Result<()> {
+    let destination_lamports = destination.lamports();
+    **destination.lamports.borrow_mut() = destination_lamports
+        .checked_add(target_account.lamports())
+        .unwrap();
+    **target_account.lamports.borrow_mut() = 0;
+    target_account.assign(&system_program::ID);
+    target_account.realloc(0, false).map_err(Into::into)
+}
+
+/// Grows a program owned account to `space`, the payer tops up the rent exemption
+pub fn realloc_account<'a>(
+    payer: AccountInfo<'a>,
+    system_program: AccountInfo<'a>,
+    target_account: AccountInfo<'a>,
+    space: usize,
+) -> Result<()> {
+    let required_lamports = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(target_account.lamports());
+    if required_lamports > 0 {
+        let cpi_accounts = system_program::Transfer {
+            from: payer,
+            to: target_account.clone(),
+        };
+        let cpi_context = CpiContext::new(system_program, cpi_accounts);
+        system_program::transfer(cpi_context, required_lamports)?;
+    }
+    target_account.realloc(space, false).map_err(Into::into)
+}
@@ -3,9 +3,9 @@ use crate::error::ErrorCode;
 use crate::states::*;
 use anchor_lang::{
     prelude::*,
-    system_program::{create_account, CreateAccount},
+    system_program::{self, create_account, CreateAccount},
 };
-use anchor_spl::token::{self, Token};
+use anchor_spl::token::{self, spl_token::native_mint, Token};
 use anchor_spl::token_2022::{
     self,
     spl_token_2022::{
@@ -13,12 +13,14 @@ use anchor_spl::token_2022::{
         extension::{
             metadata_pointer,
             transfer_fee::{TransferFeeConfig, MAX_FEE_BASIS_POINTS},
-            BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+            transfer_hook::TransferHook,
+            BaseStateWithExtensions, Extension, ExtensionType, StateWithExtensions,
         },
     },
     Token2022,
 };
 use anchor_spl::token_interface::{initialize_mint2, InitializeMint2, Mint};
+use spl_transfer_hook_interface::get_extra_account_metas_address;
 use std::collections::HashSet;
 
 const MINT_WHITELIST: [&'static str; 5] = [
@@ -29,6 +31,9 @@ const MINT_WHITELIST: [&'static str; 5] = [
     "DAUDev5e4Go36o7d9LEXSKD4YeQ3tHri9oPTXWxh5YqU",
 ];
 
+/// Extension type of the ScaledUiAmount mint extension, which is newer than the token2022 crate in use
+pub const SCALED_UI_AMOUNT_EXTENSION_TYPE: u16 = 25;
+
 pub fn invoke_memo_instruction<'info>(
     memo_msg: &[u8],
     memo_program: AccountInfo<'info>,
@@ -45,6 +50,7 @@ pub fn transfer_from_user_to_pool_vault<'info>(
     mint: Option<Box<InterfaceAccount<'info, Mint>>>,
     token_program: &AccountInfo<'info>,
     token_program_2022: Option<AccountInfo<'info>>,
+    transfer_hook_accounts: &[AccountInfo<'info>],
     amount: u64,
 ) -> Result<()> {
     if amount == 0 {
@@ -57,19 +63,19 @@ pub fn transfer_from_user_to_pool_vault<'info>(
             if from_token_info.owner == token_program_2022.key {
                 token_program_info = token_program_2022.to_account_info()
             }
-            token_2022::transfer_checked(
-                CpiContext::new(
-                    token_program_info,
-                    token_2022::TransferChecked {
-                        from: from_token_info,
-                        to: to_vault.to_account_info(),
-                        authority: signer.to_account_info(),
-                        mint: mint.to_account_info(),
-                    },
-                ),
+            // resolves the extra accounts of the transfer hook if the mint has one
+            spl_token_2022::onchain::invoke_transfer_checked(
+                token_program_info.key,
+                from_token_info,
+                mint.to_account_info(),
+                to_vault.to_account_info(),
+                signer.to_account_info(),
+                transfer_hook_accounts,
                 amount,
                 mint.decimals,
+                &[],
             )
+            .map_err(Into::into)
         }
         _ => token::transfer(
             CpiContext::new(
@@ -92,6 +98,7 @@ pub fn transfer_from_pool_vault_to_user<'info>(
     mint: Option<Box<InterfaceAccount<'info, Mint>>>,
     token_program: &AccountInfo<'info>,
     token_program_2022: Option<AccountInfo<'info>>,
+    transfer_hook_accounts: &[AccountInfo<'info>],
     amount: u64,
 ) -> Result<()> {
     if amount == 0 {
@@ -104,20 +111,19 @@ pub fn transfer_from_pool_vault_to_user<'info>(
             if from_vault_info.owner == token_program_2022.key {
                 token_program_info = token_program_2022.to_account_info()
             }
-            token_2022::transfer_checked(
-                CpiContext::new_with_signer(
-                    token_program_info,
-                    token_2022::TransferChecked {
-                        from: from_vault_info,
-                        to: to.to_account_info(),
-                        authority: pool_state_loader.to_account_info(),
-                        mint: mint.to_account_info(),
-                    },
-                    &[&pool_state_loader.load()?.seeds()],
-                ),
+            // resolves the extra accounts of the transfer hook if the mint has one
+            spl_token_2022::onchain::invoke_transfer_checked(
+                token_program_info.key,
+                from_vault_info,
+                mint.to_account_info(),
+                to.to_account_info(),
+                pool_state_loader.to_account_info(),
+                transfer_hook_accounts,
                 amount,
                 mint.decimals,
+                &[&pool_state_loader.load()?.seeds()],
             )
+            .map_err(Into::into)
         }
         _ => token::transfer(
             CpiContext::new_with_signer(
@@ -152,6 +158,101 @@ pub fn close_spl_account<'a, 'b, 'c, 'info>(
     ))
 }
 
+/// Wraps native SOL of the signer into its WSOL token account
+pub fn wrap_native_sol<'info>(
+    signer: &Signer<'info>,
+    native_token_account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    system_program::transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            system_program::Transfer {
+                from: signer.to_account_info(),
+                to: native_token_account.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    token_2022::sync_native(CpiContext::new(
+        token_program.to_account_info(),
+        token_2022::SyncNative {
+            account: native_token_account.to_account_info(),
+        },
+    ))
+}
+
+/// Unwraps the WSOL received by the claimer by closing its token account to the claimer,
+/// only the temporary WSOL token account of the claimer created by `create_native_token_account`
+/// is closed, any other recipient token account is kept
+pub fn unwrap_temporary_native_account<'info>(
+    claimer: &AccountInfo<'info>,
+    token_account: &AccountInfo<'info>,
+    token_account_mint: Pubkey,
+    token_account_owner: Pubkey,
+    token_program: &AccountInfo<'info>,
+) -> Result<()> {
+    if token_account_mint != native_mint::id()
+        || token_account_owner != claimer.key()
+        || !claimer.is_signer
+    {
+        return Ok(());
+    }
+    let (native_token_account_key, _) = Pubkey::find_program_address(
+        &[
+            crate::swap_v2_native::NATIVE_TOKEN_ACCOUNT_SEED.as_bytes(),
+            claimer.key.as_ref(),
+        ],
+        &crate::id(),
+    );
+    if token_account.key() != native_token_account_key {
+        return Ok(());
+    }
+    close_spl_account(claimer, claimer, token_account, token_program, &[])
+}
+
+/// Creates the temporary WSOL token account of the owner at a PDA of the program
+/// and wraps `amount` of the native SOL of the owner into it
+pub fn create_temporary_native_account<'info>(
+    owner: &Signer<'info>,
+    native_token_account: &AccountInfo<'info>,
+    native_mint: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    signer_seeds: &[&[u8]],
+    amount: u64,
+) -> Result<()> {
+    require_keys_eq!(native_mint.key(), native_mint::id());
+    super::create_or_allocate_account(
+        token_program.key,
+        owner.to_account_info(),
+        system_program.clone(),
+        native_token_account.clone(),
+        signer_seeds,
+        token::TokenAccount::LEN,
+    )?;
+    token_2022::initialize_account3(CpiContext::new(
+        token_program.clone(),
+        token_2022::InitializeAccount3 {
+            account: native_token_account.clone(),
+            mint: native_mint.clone(),
+            authority: owner.to_account_info(),
+        },
+    ))?;
+    wrap_native_sol(
+        owner,
+        native_token_account,
+        system_program,
+        token_program,
+        amount,
+    )
+}
+
 pub fn burn<'a, 'b, 'c, 'info>(
     owner: &Signer<'info>,
     mint: &AccountInfo<'info>,
@@ -188,7 +289,9 @@ pub fn get_transfer_inverse_fee(
     let mint_data = mint_info.try_borrow_data()?;
     let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
 
-    let fee = if let Ok(transfer_fee_config) = mint.get_extension::<TransferFeeConfig>() {
+    let fee = if let Some(transfer_fee_config) =
+        get_mint_extension::<TransferFeeConfig>(mint.get_tlv_data())?
+    {
         let epoch = get_recent_epoch()?;
 
         let transfer_fee = transfer_fee_config.get_epoch_fee(epoch);
@@ -217,7 +320,9 @@ pub fn get_transfer_fee(
     let mint_data = mint_info.try_borrow_data()?;
     let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
 
-    let fee = if let Ok(transfer_fee_config) = mint.get_extension::<TransferFeeConfig>() {
+    let fee = if let Some(transfer_fee_config) =
+        get_mint_extension::<TransferFeeConfig>(mint.get_tlv_data())?
+    {
         transfer_fee_config
             .calculate_epoch_fee(get_recent_epoch()?, pre_fee_amount)
             .unwrap()
@@ -227,7 +332,48 @@ pub fn get_transfer_fee(
     Ok(fee)
 }
 
-pub fn is_supported_mint(mint_account: &InterfaceAccount<Mint>) -> Result<bool> {
+/// Returns the transfer hook program of the mint, None if the mint has no transfer hook
+pub fn get_transfer_hook_program_id(mint_info: &AccountInfo) -> Result<Option<Pubkey>> {
+    if *mint_info.owner == Token::id() {
+        return Ok(None);
+    }
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    Ok(get_mint_extension::<TransferHook>(mint.get_tlv_data())?
+        .and_then(|transfer_hook| Option::<Pubkey>::from(transfer_hook.program_id)))
+}
+
+/// Splits the transfer hook accounts of the mints off the tail of the remaining accounts.
+/// The hook accounts start from the extra account metas account of the first hook mint,
+/// the order of the hook accounts doesn't matter as they are resolved by key.
+pub fn split_transfer_hook_accounts<'c, 'info>(
+    remaining_accounts: &'c [AccountInfo<'info>],
+    mints: &[AccountInfo<'info>],
+) -> Result<(&'c [AccountInfo<'info>], &'c [AccountInfo<'info>])> {
+    let mut extra_account_metas_keys = Vec::new();
+    for mint_info in mints {
+        if let Some(program_id) = get_transfer_hook_program_id(mint_info)? {
+            extra_account_metas_keys
+                .push(get_extra_account_metas_address(mint_info.key, &program_id));
+        }
+    }
+    if extra_account_metas_keys.is_empty() {
+        return Ok((remaining_accounts, &[]));
+    }
+    let index = remaining_accounts
+        .iter()
+        .position(|account_info| extra_account_metas_keys.contains(account_info.key))
+        .ok_or(ErrorCode::NotEnoughRemainingAccounts)?;
+    Ok(remaining_accounts.split_at(index))
+}
+
+/// Token2022 mints with permanent delegate or transfer hook are only supported if the mint
+/// has a token badge, so are the ones with freeze authority if `freeze_authority_requires_badge`
+pub fn is_supported_mint(
+    mint_account: &InterfaceAccount<Mint>,
+    token_badge_initialized: bool,
+    freeze_authority_requires_badge: bool,
+) -> Result<bool> {
     let mint_info = mint_account.to_account_info();
     if *mint_info.owner == Token::id() {
         return Ok(true);
@@ -236,32 +382,116 @@ pub fn is_supported_mint(mint_account: &InterfaceAccount<Mint>) -> Result<bool>
     if mint_whitelist.contains(mint_account.key().to_string().as_str()) {
         return Ok(true);
     }
+    if freeze_authority_requires_badge
+        && mint_account.freeze_authority.is_some()
+        && !token_badge_initialized
+    {
+        return Ok(false);
+    }
     let mint_data = mint_info.try_borrow_data()?;
     let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
-    let extensions = mint.get_extension_types()?;
-    for e in extensions {
-        if e != ExtensionType::TransferFeeConfig
-            && e != ExtensionType::MetadataPointer
-            && e != ExtensionType::TokenMetadata
-            && e != ExtensionType::InterestBearingConfig
-            && e != ExtensionType::MintCloseAuthority
-        {
-            return Ok(false);
+    let extensions = get_mint_extensions(mint.get_tlv_data())?;
+    Ok(extensions
+        .iter()
+        .all(|(e, _)| is_supported_mint_extension(*e, token_badge_initialized)))
+}
+
+/// Token2022 mints can be issued a token badge by anyone paying the fee of the token badge config
+/// if they have no freeze authority, which can't be set again once revoked, so the badge never unlocks
+/// the freeze authority, and all their extensions are supported, the ones requiring a token badge
+/// only if allowed by the config
+pub fn is_self_service_badge_mint(
+    mint_account: &InterfaceAccount<Mint>,
+    token_badge_config: &TokenBadgeConfig,
+) -> Result<bool> {
+    let mint_info = mint_account.to_account_info();
+    if *mint_info.owner != Token2022::id() || mint_account.freeze_authority.is_some() {
+        return Ok(false);
+    }
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    let extensions = get_mint_extensions(mint.get_tlv_data())?;
+    Ok(extensions.iter().all(|(e, _)| {
+        is_supported_mint_extension(*e, false)
+            || (is_supported_mint_extension(*e, true)
+                && token_badge_config.is_allowed_extension(*e))
+    }))
+}
+
+/// InterestBearingConfig and ScaledUiAmount only change the ui amount of the mint,
+/// the pool always accounts and quotes in raw amounts, so both are supported as is
+pub fn is_supported_mint_extension(extension_type: u16, token_badge_initialized: bool) -> bool {
+    if extension_type == ExtensionType::TransferFeeConfig as u16
+        || extension_type == ExtensionType::MetadataPointer as u16
+        || extension_type == ExtensionType::TokenMetadata as u16
+        || extension_type == ExtensionType::InterestBearingConfig as u16
+        || extension_type == ExtensionType::MintCloseAuthority as u16
+        || extension_type == SCALED_UI_AMOUNT_EXTENSION_TYPE
+    {
+        return true;
+    }
+    token_badge_initialized
+        && (extension_type == ExtensionType::PermanentDelegate as u16
+            || extension_type == ExtensionType::TransferHook as u16)
+}
+
+/// Returns the raw type and value of the extensions in the tlv data of a Token2022 mint.
+/// Unlike `get_extension_types`, extension types unknown to the token2022 crate are kept.
+pub fn get_mint_extensions(tlv_data: &[u8]) -> Result<Vec<(u16, &[u8])>> {
+    let mut extensions = Vec::new();
+    let mut start_index = 0;
+    while start_index + 4 <= tlv_data.len() {
+        let extension_type = u16::from_le_bytes([tlv_data[start_index], tlv_data[start_index + 1]]);
+        if extension_type == ExtensionType::Uninitialized as u16 {
+            break;
         }
+        let length =
+            u16::from_le_bytes([tlv_data[start_index + 2], tlv_data[start_index + 3]]) as usize;
+        let value_start = start_index + 4;
+        let value_end = value_start + length;
+        if value_end > tlv_data.len() {
+            return Err(ProgramError::InvalidAccountData.into());
+        }
+        extensions.push((extension_type, &tlv_data[value_start..value_end]));
+        start_index = value_end;
     }
-    Ok(true)
+    Ok(extensions)
 }
 
-pub fn create_position_nft_mint_with_extensions<'info>(
-    payer: &Signer<'info>,
-    position_nft_mint: &AccountInfo<'info>,
-    mint_authority: &AccountInfo<'info>,
-    mint_close_authority: &AccountInfo<'info>,
-    system_program: &Program<'info, System>,
-    token_2022_program: &Program<'info, Token2022>,
+/// Returns the extension of the mint if it exists, skipping extension types unknown to the token2022 crate
+pub fn get_mint_extension<V: Extension + bytemuck::Pod>(tlv_data: &[u8]) -> Result<Option<&V>> {
+    for (extension_type, value) in get_mint_extensions(tlv_data)? {
+        if extension_type == V::TYPE as u16 {
+            let extension = bytemuck::try_from_bytes::<V>(value)
+                .map_err(|_| ProgramError::InvalidAccountData)?;
+            return Ok(Some(extension));
+        }
+    }
+    Ok(None)
+}
+
+/// Returns true if a valid token badge of the mint exists in the accounts
+pub fn is_token_badge_initialized<'info>(
+    token_mint: Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+) -> Result<bool> {
+    let token_badge_key = TokenBadge::key(token_mint);
+    for account_info in accounts {
+        if account_info.key() == token_badge_key {
+            let token_badge = Account::<TokenBadge>::try_from(account_info)?;
+            return Ok(token_badge.token_mint == token_mint);
+        }
+    }
+    Ok(false)
+}
+
+/// Returns the extensions of a position NFT mint, a soulbound position NFT can't be moved
+/// from the owner, only burnt
+pub fn position_nft_mint_extensions(
     with_matedata: bool,
-) -> Result<()> {
-    let extensions = if with_matedata {
+    non_transferable: bool,
+) -> Vec<ExtensionType> {
+    let mut extensions = if with_matedata {
         [
             ExtensionType::MintCloseAuthority,
             ExtensionType::MetadataPointer,
@@ -270,6 +500,23 @@ pub fn create_position_nft_mint_with_extensions<'info>(
     } else {
         [ExtensionType::MintCloseAuthority].to_vec()
     };
+    if non_transferable {
+        extensions.push(ExtensionType::NonTransferable);
+    }
+    extensions
+}
+
+pub fn create_position_nft_mint_with_extensions<'info>(
+    payer: &Signer<'info>,
+    position_nft_mint: &AccountInfo<'info>,
+    mint_authority: &AccountInfo<'info>,
+    mint_close_authority: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    token_2022_program: &Program<'info, Token2022>,
+    with_matedata: bool,
+    non_transferable: bool,
+) -> Result<()> {
+    let extensions = position_nft_mint_extensions(with_matedata, non_transferable);
     let space =
         ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&extensions)?;
 
@@ -307,6 +554,19 @@ pub fn create_position_nft_mint_with_extensions<'info>(
                     ],
                 )?;
             }
+            ExtensionType::NonTransferable => {
+                let ix = spl_token_2022::instruction::initialize_non_transferable_mint(
+                    token_2022_program.key,
+                    position_nft_mint.key,
+                )?;
+                solana_program::program::invoke(
+                    &ix,
+                    &[
+                        token_2022_program.to_account_info(),
+                        position_nft_mint.to_account_info(),
+                    ],
+                )?;
+            }
             ExtensionType::MintCloseAuthority => {
                 let ix = spl_token_2022::instruction::initialize_mint_close_authority(
                     token_2022_program.key,
@@ -340,3 +600,154 @@ pub fn create_position_nft_mint_with_extensions<'info>(
         None,
     )
 }
+
+#[cfg(test)]
+mod token_test {
+    use super::*;
+    use anchor_spl::token_2022::spl_token_2022::extension::{
+        interest_bearing_mint::InterestBearingConfig, mint_close_authority::MintCloseAuthority,
+        non_transferable::NonTransferable, StateWithExtensionsMut,
+    };
+
+    fn push_extension(tlv_data: &mut Vec<u8>, extension_type: u16, value: &[u8]) {
+        tlv_data.extend_from_slice(&extension_type.to_le_bytes());
+        tlv_data.extend_from_slice(&(value.len() as u16).to_le_bytes());
+        tlv_data.extend_from_slice(value);
+    }
+
+    fn transfer_fee_config(transfer_fee_basis_points: u16, maximum_fee: u64) -> TransferFeeConfig {
+        let mut config = TransferFeeConfig::default();
+        config.newer_transfer_fee.transfer_fee_basis_points = transfer_fee_basis_points.into();
+        config.newer_transfer_fee.maximum_fee = maximum_fee.into();
+        config.older_transfer_fee = config.newer_transfer_fee;
+        config
+    }
+
+    #[test]
+    fn get_mint_extensions_keeps_unknown_extension_test() {
+        let mut tlv_data = Vec::new();
+        push_extension(
+            &mut tlv_data,
+            ExtensionType::InterestBearingConfig as u16,
+            bytemuck::bytes_of(&InterestBearingConfig::default()),
+        );
+        push_extension(&mut tlv_data, SCALED_UI_AMOUNT_EXTENSION_TYPE, &[1u8; 56]);
+        // trailing uninitialized space is ignored
+        tlv_data.extend_from_slice(&[0u8; 6]);
+
+        let extensions = get_mint_extensions(&tlv_data).unwrap();
+        assert_eq!(extensions.len(), 2);
+        assert_eq!(extensions[0].0, ExtensionType::InterestBearingConfig as u16);
+        assert_eq!(extensions[1].0, SCALED_UI_AMOUNT_EXTENSION_TYPE);
+        assert_eq!(extensions[1].1, &[1u8; 56]);
+        assert!(extensions
+            .iter()
+            .all(|(e, _)| is_supported_mint_extension(*e, false)));
+
+        // value length out of bounds
+        let mut malformed = Vec::new();
+        push_extension(&mut malformed, SCALED_UI_AMOUNT_EXTENSION_TYPE, &[1u8; 56]);
+        malformed.truncate(40);
+        assert!(get_mint_extensions(&malformed).is_err());
+    }
+
+    #[test]
+    fn is_supported_mint_extension_test() {
+        assert!(is_supported_mint_extension(
+            ExtensionType::InterestBearingConfig as u16,
+            false
+        ));
+        assert!(is_supported_mint_extension(
+            SCALED_UI_AMOUNT_EXTENSION_TYPE,
+            false
+        ));
+        assert!(!is_supported_mint_extension(
+            ExtensionType::TransferHook as u16,
+            false
+        ));
+        assert!(is_supported_mint_extension(
+            ExtensionType::TransferHook as u16,
+            true
+        ));
+        assert!(!is_supported_mint_extension(
+            ExtensionType::NonTransferable as u16,
+            true
+        ));
+        // unknown extension types are rejected
+        assert!(!is_supported_mint_extension(
+            SCALED_UI_AMOUNT_EXTENSION_TYPE + 1,
+            true
+        ));
+    }
+
+    #[test]
+    fn position_nft_mint_non_transferable_test() {
+        assert!(
+            !position_nft_mint_extensions(true, false).contains(&ExtensionType::NonTransferable)
+        );
+        let extensions = position_nft_mint_extensions(false, true);
+        assert_eq!(
+            extensions,
+            vec![
+                ExtensionType::MintCloseAuthority,
+                ExtensionType::NonTransferable
+            ]
+        );
+
+        // the mint laid out with the extensions is non transferable, and so are its token accounts
+        let space =
+            ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&extensions)
+                .unwrap();
+        let mut mint_data = vec![0u8; space];
+        let mut mint = StateWithExtensionsMut::<spl_token_2022::state::Mint>::unpack_uninitialized(
+            &mut mint_data,
+        )
+        .unwrap();
+        mint.init_extension::<MintCloseAuthority>(true).unwrap();
+        mint.init_extension::<NonTransferable>(true).unwrap();
+        mint.base.is_initialized = true;
+        mint.pack_base();
+        mint.init_account_type().unwrap();
+
+        let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data).unwrap();
+        assert!(mint.get_extension::<NonTransferable>().is_ok());
+        assert!(ExtensionType::get_required_init_account_extensions(
+            &mint.get_extension_types().unwrap()
+        )
+        .contains(&ExtensionType::NonTransferableAccount));
+    }
+
+    #[test]
+    fn transfer_fee_quote_with_ui_amount_extensions_test() {
+        let mut tlv_data = Vec::new();
+        push_extension(&mut tlv_data, SCALED_UI_AMOUNT_EXTENSION_TYPE, &[2u8; 56]);
+        push_extension(
+            &mut tlv_data,
+            ExtensionType::InterestBearingConfig as u16,
+            bytemuck::bytes_of(&InterestBearingConfig::default()),
+        );
+        push_extension(
+            &mut tlv_data,
+            ExtensionType::TransferFeeConfig as u16,
+            bytemuck::bytes_of(&transfer_fee_config(100, u64::MAX)),
+        );
+
+        // the fee config behind the unknown extension is still found
+        let config = get_mint_extension::<TransferFeeConfig>(&tlv_data)
+            .unwrap()
+            .unwrap();
+        assert_eq!(*config, transfer_fee_config(100, u64::MAX));
+        assert!(get_mint_extension::<TransferHook>(&tlv_data)
+            .unwrap()
+            .is_none());
+
+        // fees are quoted on raw amounts, the ui multiplier of the mint doesn't apply
+        let raw_amount = 1_000_000u64;
+        let fee = config.calculate_epoch_fee(0, raw_amount).unwrap();
+        assert_eq!(fee, 10_000);
+        let inverse_fee = config
+            .calculate_inverse_epoch_fee(0, raw_amount - fee)
+            .unwrap();
+        assert_eq!(inverse_fee, fee);
+    }
+}